@@ -0,0 +1,3 @@
+-- Rollback Full-Text Search Index
+
+DROP TABLE IF EXISTS notes_fts;
@@ -0,0 +1,10 @@
+-- Full-Text Search Index
+-- FTS5 virtual table holding the searchable title and plain-text body of each note.
+-- Rows are maintained by the search engine adapter (index_note / remove_from_index / rebuild_index).
+
+CREATE VIRTUAL TABLE notes_fts USING fts5(
+    note_id UNINDEXED,
+    title,
+    content,
+    tokenize = 'porter unicode61 remove_diacritics 2'
+);
@@ -0,0 +1,3 @@
+-- Rollback Note Chunks
+
+DROP TABLE IF EXISTS note_chunks;
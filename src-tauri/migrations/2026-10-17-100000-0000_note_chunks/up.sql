@@ -0,0 +1,16 @@
+-- Note Chunks
+-- Heading/paragraph-aware passages of each note with their own embeddings.
+-- Offsets are byte ranges into the note's markdown file.
+
+CREATE TABLE note_chunks (
+    note_id TEXT NOT NULL,
+    chunk_index INTEGER NOT NULL,
+    heading_path TEXT NOT NULL DEFAULT '[]',
+    content TEXT NOT NULL,
+    start_offset INTEGER NOT NULL,
+    end_offset INTEGER NOT NULL,
+    embedding BLOB,
+    created_at INTEGER NOT NULL,
+    PRIMARY KEY (note_id, chunk_index),
+    FOREIGN KEY (note_id) REFERENCES notes(id) ON DELETE CASCADE
+);
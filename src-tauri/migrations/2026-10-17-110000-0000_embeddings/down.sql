@@ -0,0 +1,20 @@
+-- Rollback Embeddings
+-- Unquantized vectors move back into the notes and note_chunks rows.
+
+ALTER TABLE notes ADD COLUMN embedding BLOB;
+ALTER TABLE note_chunks ADD COLUMN embedding BLOB;
+
+UPDATE notes
+SET embedding = (
+    SELECT vector FROM embeddings
+    WHERE embeddings.id = notes.id AND embeddings.quantization = 'f32'
+);
+
+UPDATE note_chunks
+SET embedding = (
+    SELECT vector FROM embeddings
+    WHERE embeddings.id = note_chunks.note_id || '#' || note_chunks.chunk_index
+      AND embeddings.quantization = 'f32'
+);
+
+DROP TABLE IF EXISTS embeddings;
@@ -0,0 +1,41 @@
+-- Embeddings
+-- Vectors of whole notes (chunk_index NULL) and of their passages, kept out of
+-- the notes and note_chunks rows so listing notes never reads them.
+-- id is the note ID for note-level rows and '<note id>#<chunk index>' for passages.
+-- quantization: 'f32' (4 bytes per value), 'f16' (2 bytes per value) or
+-- 'int8' (4-byte f32 scale followed by 1 byte per value), all little-endian.
+
+CREATE TABLE embeddings (
+    id TEXT PRIMARY KEY NOT NULL,
+    note_id TEXT NOT NULL,
+    chunk_index INTEGER,
+    model_name TEXT NOT NULL,
+    model_version TEXT NOT NULL,
+    content_hash TEXT,
+    dimensions INTEGER NOT NULL,
+    quantization TEXT NOT NULL DEFAULT 'f32',
+    vector BLOB NOT NULL,
+    created_at INTEGER NOT NULL,
+    FOREIGN KEY (note_id) REFERENCES notes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_embeddings_note_id ON embeddings(note_id);
+
+-- Existing vectors were produced by the bundled FastEmbed model, recorded by
+-- the model ID that settings refer to
+INSERT INTO embeddings (id, note_id, chunk_index, model_name, model_version, content_hash,
+                        dimensions, quantization, vector, created_at)
+SELECT id, id, NULL, 'AllMiniLML6V2', 'fastembed-5', NULL,
+       length(embedding) / 4, 'f32', embedding, updated_at
+FROM notes
+WHERE embedding IS NOT NULL AND length(embedding) > 0;
+
+INSERT INTO embeddings (id, note_id, chunk_index, model_name, model_version, content_hash,
+                        dimensions, quantization, vector, created_at)
+SELECT note_id || '#' || chunk_index, note_id, chunk_index, 'AllMiniLML6V2', 'fastembed-5',
+       NULL, length(embedding) / 4, 'f32', embedding, created_at
+FROM note_chunks
+WHERE embedding IS NOT NULL AND length(embedding) > 0;
+
+ALTER TABLE notes DROP COLUMN embedding;
+ALTER TABLE note_chunks DROP COLUMN embedding;
@@ -0,0 +1,3 @@
+-- Rollback Note Aliases
+
+ALTER TABLE notes DROP COLUMN aliases;
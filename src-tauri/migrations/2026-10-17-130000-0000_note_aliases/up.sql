@@ -0,0 +1,5 @@
+-- Note Aliases
+-- Other names a note answers to in wiki links, from its `aliases:`
+-- frontmatter. Stored as a JSON array of strings.
+
+ALTER TABLE notes ADD COLUMN aliases TEXT NOT NULL DEFAULT '[]';
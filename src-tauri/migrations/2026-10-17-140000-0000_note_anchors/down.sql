@@ -0,0 +1,18 @@
+-- Rollback Note Anchors
+
+DROP TABLE IF EXISTS note_blocks;
+
+CREATE TABLE note_links_old (
+    source_note_id TEXT NOT NULL,
+    target_note_id TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    PRIMARY KEY (source_note_id, target_note_id),
+    FOREIGN KEY (source_note_id) REFERENCES notes(id) ON DELETE CASCADE,
+    FOREIGN KEY (target_note_id) REFERENCES notes(id) ON DELETE CASCADE
+);
+
+INSERT OR IGNORE INTO note_links_old (source_note_id, target_note_id, created_at)
+SELECT source_note_id, target_note_id, created_at FROM note_links;
+
+DROP TABLE note_links;
+ALTER TABLE note_links_old RENAME TO note_links;
@@ -0,0 +1,33 @@
+-- Note Anchors
+-- Links may point at a heading or `^block-id` of their target, so the anchor
+-- joins the key of note_links ('' for links to the whole note). Blocks marked
+-- with `^block-id` are indexed per note; offsets are byte ranges into the
+-- note's markdown file.
+
+CREATE TABLE note_links_new (
+    source_note_id TEXT NOT NULL,
+    target_note_id TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    anchor TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (source_note_id, target_note_id, anchor),
+    FOREIGN KEY (source_note_id) REFERENCES notes(id) ON DELETE CASCADE,
+    FOREIGN KEY (target_note_id) REFERENCES notes(id) ON DELETE CASCADE
+);
+
+INSERT INTO note_links_new (source_note_id, target_note_id, created_at)
+SELECT source_note_id, target_note_id, created_at FROM note_links;
+
+DROP TABLE note_links;
+ALTER TABLE note_links_new RENAME TO note_links;
+
+CREATE TABLE note_blocks (
+    note_id TEXT NOT NULL,
+    block_id TEXT NOT NULL,
+    content TEXT NOT NULL,
+    start_offset INTEGER NOT NULL,
+    end_offset INTEGER NOT NULL,
+    line_number INTEGER NOT NULL,
+    created_at INTEGER NOT NULL,
+    PRIMARY KEY (note_id, block_id),
+    FOREIGN KEY (note_id) REFERENCES notes(id) ON DELETE CASCADE
+);
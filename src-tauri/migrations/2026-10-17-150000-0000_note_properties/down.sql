@@ -0,0 +1,3 @@
+-- Rollback Note Properties
+
+ALTER TABLE notes DROP COLUMN properties;
@@ -0,0 +1,5 @@
+-- Note Properties
+-- Custom keys from a note's frontmatter, other than those the app reads
+-- itself. Stored as a JSON object.
+
+ALTER TABLE notes ADD COLUMN properties TEXT NOT NULL DEFAULT '{}';
@@ -0,0 +1,3 @@
+-- Rollback Note Tag Sources
+
+ALTER TABLE note_tags DROP COLUMN source;
@@ -0,0 +1,5 @@
+-- Note Tag Sources
+-- Whether a note's tag was added by hand (from the app or the frontmatter
+-- tags list) or comes from a #hashtag in its content.
+
+ALTER TABLE note_tags ADD COLUMN source TEXT NOT NULL DEFAULT 'manual';
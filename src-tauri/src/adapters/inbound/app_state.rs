@@ -6,9 +6,10 @@
 use std::sync::Arc;
 
 use crate::domain::ports::inbound::{
-    AttachmentUseCases, DatabaseUseCases, ExportUseCases, GitUseCases, GraphUseCases,
-    NoteUseCases, NotebookUseCases, QuickCaptureUseCases, SearchUseCases, SettingsUseCases,
-    SystemUseCases, TagUseCases, TaskUseCases, TopicUseCases, VersionUseCases, WorkspaceUseCases,
+    AttachmentUseCases, DatabaseUseCases, DuplicateUseCases, ExportUseCases, GitUseCases,
+    GraphUseCases, NoteUseCases, NotebookUseCases, PeriodicNoteUseCases,
+    QuestionAnsweringUseCases, QuickCaptureUseCases, RefactorUseCases, SearchUseCases, SettingsUseCases, SystemUseCases, TagUseCases,
+    TaskUseCases, TemplateUseCases, TopicUseCases, VersionUseCases, WorkspaceUseCases,
 };
 
 /// Application State
@@ -33,7 +34,12 @@ pub struct AppState {
     pub search_usecases: Arc<dyn SearchUseCases>,
     pub graph_usecases: Arc<dyn GraphUseCases>,
     pub quick_capture_usecases: Arc<dyn QuickCaptureUseCases>,
+    pub periodic_note_usecases: Arc<dyn PeriodicNoteUseCases>,
     pub task_usecases: Arc<dyn TaskUseCases>,
+    pub template_usecases: Arc<dyn TemplateUseCases>,
+    pub duplicate_usecases: Arc<dyn DuplicateUseCases>,
+    pub refactor_usecases: Arc<dyn RefactorUseCases>,
+    pub question_answering_usecases: Arc<dyn QuestionAnsweringUseCases>,
 
     // System use cases
     pub database_usecases: Arc<dyn DatabaseUseCases>,
@@ -57,7 +63,12 @@ impl AppState {
         search_usecases: Arc<dyn SearchUseCases>,
         graph_usecases: Arc<dyn GraphUseCases>,
         quick_capture_usecases: Arc<dyn QuickCaptureUseCases>,
+        periodic_note_usecases: Arc<dyn PeriodicNoteUseCases>,
         task_usecases: Arc<dyn TaskUseCases>,
+        template_usecases: Arc<dyn TemplateUseCases>,
+        duplicate_usecases: Arc<dyn DuplicateUseCases>,
+        refactor_usecases: Arc<dyn RefactorUseCases>,
+        question_answering_usecases: Arc<dyn QuestionAnsweringUseCases>,
         database_usecases: Arc<dyn DatabaseUseCases>,
         git_usecases: Arc<dyn GitUseCases>,
         export_usecases: Arc<dyn ExportUseCases>,
@@ -75,7 +86,12 @@ impl AppState {
             search_usecases,
             graph_usecases,
             quick_capture_usecases,
+            periodic_note_usecases,
             task_usecases,
+            template_usecases,
+            duplicate_usecases,
+            refactor_usecases,
+            question_answering_usecases,
             database_usecases,
             git_usecases,
             export_usecases,
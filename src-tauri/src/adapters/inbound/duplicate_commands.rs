@@ -0,0 +1,34 @@
+//! Duplicate Command Handlers
+
+use tauri::State;
+
+use crate::{
+    adapters::inbound::app_state::AppState,
+    domain::ports::inbound::{
+        DuplicateCluster, FindDuplicatesRequest, MergeNotesRequest, MergeNotesResponse,
+    },
+};
+
+#[tauri::command]
+pub async fn find_duplicate_notes(
+    state: State<'_, AppState>,
+    request: Option<FindDuplicatesRequest>,
+) -> Result<Vec<DuplicateCluster>, String> {
+    state
+        .duplicate_usecases
+        .find_duplicates(request.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn merge_notes(
+    state: State<'_, AppState>,
+    request: MergeNotesRequest,
+) -> Result<MergeNotesResponse, String> {
+    state
+        .duplicate_usecases
+        .merge_notes(request)
+        .await
+        .map_err(|e| e.to_string())
+}
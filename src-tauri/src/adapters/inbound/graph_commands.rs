@@ -4,7 +4,13 @@ use tauri::State;
 
 use crate::{
     adapters::inbound::app_state::AppState,
-    domain::ports::inbound::{GraphData, GraphDataOptions, NoteLinkInfo},
+    domain::{
+        entities::NoteBlock,
+        ports::inbound::{
+            AmbiguousLink, ApplyLinkSuggestionRequest, EmbedContent, GraphData, GraphDataOptions,
+            LinkSuggestion, LinkSuggestionsRequest, NoteLinkInfo,
+        },
+    },
 };
 
 #[tauri::command]
@@ -42,3 +48,64 @@ pub async fn get_graph_data(
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn get_link_suggestions(
+    state: State<'_, AppState>,
+    request: LinkSuggestionsRequest,
+) -> Result<Vec<LinkSuggestion>, String> {
+    state
+        .graph_usecases
+        .get_link_suggestions(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn apply_link_suggestion(
+    state: State<'_, AppState>,
+    request: ApplyLinkSuggestionRequest,
+) -> Result<String, String> {
+    state
+        .graph_usecases
+        .apply_link_suggestion(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_ambiguous_links(
+    state: State<'_, AppState>,
+    note_id: String,
+) -> Result<Vec<AmbiguousLink>, String> {
+    state
+        .graph_usecases
+        .get_ambiguous_links(&note_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_note_blocks(
+    state: State<'_, AppState>,
+    note_id: String,
+) -> Result<Vec<NoteBlock>, String> {
+    state
+        .graph_usecases
+        .get_note_blocks(&note_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resolve_embed(
+    state: State<'_, AppState>,
+    note_id: String,
+    target: String,
+) -> Result<Option<EmbedContent>, String> {
+    state
+        .graph_usecases
+        .resolve_embed(&note_id, &target)
+        .await
+        .map_err(|e| e.to_string())
+}
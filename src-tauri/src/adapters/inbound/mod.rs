@@ -19,8 +19,13 @@ pub mod export_commands;
 pub mod attachment_commands;
 pub mod settings_commands;
 pub mod graph_commands;
+pub mod duplicate_commands;
+pub mod refactor_commands;
+pub mod question_answering_commands;
+pub mod periodic_note_commands;
 pub mod quick_capture_commands;
 pub mod task_commands;
+pub mod template_commands;
 pub mod version_commands;
 pub mod performance_commands;
 
@@ -0,0 +1,47 @@
+//! Periodic Note Command Handlers
+
+use tauri::State;
+
+use crate::{
+    adapters::inbound::app_state::AppState,
+    domain::ports::inbound::{
+        OpenPeriodicNoteRequest, PeriodicNoteResponse, PeriodicNotesSettings,
+    },
+};
+
+#[tauri::command]
+pub async fn open_periodic_note(
+    state: State<'_, AppState>,
+    request: OpenPeriodicNoteRequest,
+) -> Result<PeriodicNoteResponse, String> {
+    state
+        .periodic_note_usecases
+        .open_periodic_note(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_periodic_settings(
+    state: State<'_, AppState>,
+    workspace_id: Option<String>,
+) -> Result<PeriodicNotesSettings, String> {
+    state
+        .periodic_note_usecases
+        .get_periodic_settings(workspace_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_periodic_settings(
+    state: State<'_, AppState>,
+    workspace_id: Option<String>,
+    settings: PeriodicNotesSettings,
+) -> Result<PeriodicNotesSettings, String> {
+    state
+        .periodic_note_usecases
+        .update_periodic_settings(workspace_id, settings)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,44 @@
+//! Question Answering Command Handlers
+
+use tauri::State;
+
+use crate::{
+    adapters::inbound::app_state::AppState,
+    domain::ports::{
+        inbound::{AnswerResponse, AskQuestionRequest},
+        outbound::ChatModelConfig,
+    },
+};
+
+#[tauri::command]
+pub async fn ask_question(
+    state: State<'_, AppState>,
+    request: AskQuestionRequest,
+) -> Result<AnswerResponse, String> {
+    state
+        .question_answering_usecases
+        .ask_question(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_chat_settings(state: State<'_, AppState>) -> Result<ChatModelConfig, String> {
+    state
+        .question_answering_usecases
+        .get_chat_settings()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_chat_settings(
+    state: State<'_, AppState>,
+    config: ChatModelConfig,
+) -> Result<ChatModelConfig, String> {
+    state
+        .question_answering_usecases
+        .update_chat_settings(config)
+        .await
+        .map_err(|e| e.to_string())
+}
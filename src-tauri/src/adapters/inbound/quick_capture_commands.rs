@@ -4,7 +4,7 @@ use tauri::{AppHandle, State};
 
 use crate::{
     adapters::inbound::{app_state::AppState, ui::quick_capture_window},
-    domain::ports::inbound::AppendToJournalResponse,
+    domain::{ports::inbound::AppendToJournalResponse, services::NotePeriod},
 };
 
 #[tauri::command]
@@ -30,6 +30,20 @@ pub async fn append_to_journal(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn append_to_periodic_note(
+    state: State<'_, AppState>,
+    content: String,
+    period: NotePeriod,
+    workspace_id: Option<String>,
+) -> Result<AppendToJournalResponse, String> {
+    state
+        .quick_capture_usecases
+        .append_to_periodic_note(&content, period, workspace_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn hide_quick_capture(app: AppHandle) -> quick_capture_window::PanelOperationResult {
     quick_capture_window::hide(&app)
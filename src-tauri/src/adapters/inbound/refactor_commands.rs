@@ -0,0 +1,59 @@
+//! Refactor Command Handlers
+
+use tauri::State;
+
+use crate::{
+    adapters::inbound::app_state::AppState,
+    domain::ports::inbound::{
+        RenameNotePlan, RenameNoteRequest, RenameNoteResponse, RenameTagPlan, RenameTagRequest,
+        RenameTagResponse,
+    },
+};
+
+#[tauri::command]
+pub async fn preview_note_rename(
+    state: State<'_, AppState>,
+    request: RenameNoteRequest,
+) -> Result<RenameNotePlan, String> {
+    state
+        .refactor_usecases
+        .preview_note_rename(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn rename_note(
+    state: State<'_, AppState>,
+    request: RenameNoteRequest,
+) -> Result<RenameNoteResponse, String> {
+    state
+        .refactor_usecases
+        .rename_note(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn preview_tag_rename(
+    state: State<'_, AppState>,
+    request: RenameTagRequest,
+) -> Result<RenameTagPlan, String> {
+    state
+        .refactor_usecases
+        .preview_tag_rename(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn rename_tag(
+    state: State<'_, AppState>,
+    request: RenameTagRequest,
+) -> Result<RenameTagResponse, String> {
+    state
+        .refactor_usecases
+        .rename_tag(request)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -9,7 +9,7 @@ use crate::{
         ports::{
             inbound::{
                 HybridSearchResultItem, HybridSearchWeights, SearchByDateRangeRequest,
-                SearchRequest, VectorSearchResult,
+                SearchByTagsRequest, SearchRequest, VectorSearchResult,
             },
             outbound::SearchResult,
         },
@@ -101,3 +101,34 @@ pub async fn search_by_date_range(
 
     Ok(SearchByDateRangeResponse { results })
 }
+
+/// Response wrapper for tag search results
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchByTagsResponse {
+    pub results: Vec<Note>,
+}
+
+#[tauri::command]
+pub async fn search_by_tags(
+    state: State<'_, AppState>,
+    request: SearchByTagsRequest,
+) -> Result<SearchByTagsResponse, String> {
+    let results = state
+        .search_usecases
+        .search_by_tags(request)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(SearchByTagsResponse { results })
+}
+
+/// Rebuild the full-text search index from the notes on disk
+#[tauri::command]
+pub async fn rebuild_search_index(state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .search_usecases
+        .rebuild_index()
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -5,7 +5,7 @@ use tauri::State;
 use crate::{
     adapters::inbound::app_state::AppState,
     domain::{
-        entities::Tag,
+        entities::{NoteTag, Tag},
         ports::{
             inbound::{CreateTagRequest, ListTagsRequest, TagList, UpdateTagRequest},
             outbound::TagWithCount,
@@ -78,6 +78,8 @@ pub async fn list_tags(
                     created_at: t.created_at,
                     updated_at: t.updated_at,
                     note_count: 0,
+                    total_note_count: 0,
+                    children: Vec::new(),
                 })
                 .collect()
         }
@@ -125,7 +127,7 @@ pub async fn remove_tag_from_note(
 pub async fn get_tags_for_note(
     state: State<'_, AppState>,
     note_id: String,
-) -> Result<Vec<Tag>, String> {
+) -> Result<Vec<NoteTag>, String> {
     state
         .tag_usecases
         .get_note_tags(&note_id)
@@ -0,0 +1,73 @@
+//! Template Command Handlers
+
+use tauri::State;
+
+use crate::{
+    adapters::inbound::app_state::AppState,
+    domain::ports::inbound::{
+        CreateNoteFromTemplateRequest, CreateNoteFromTemplateResponse, TemplateInfo,
+        TemplateSettings,
+    },
+};
+
+#[tauri::command]
+pub async fn list_templates(
+    state: State<'_, AppState>,
+    workspace_id: Option<String>,
+) -> Result<Vec<TemplateInfo>, String> {
+    state
+        .template_usecases
+        .list_templates(workspace_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_template_settings(
+    state: State<'_, AppState>,
+    workspace_id: Option<String>,
+) -> Result<TemplateSettings, String> {
+    state
+        .template_usecases
+        .get_template_settings(workspace_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn update_template_settings(
+    state: State<'_, AppState>,
+    workspace_id: Option<String>,
+    settings: TemplateSettings,
+) -> Result<TemplateSettings, String> {
+    state
+        .template_usecases
+        .update_template_settings(workspace_id, settings)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_notebook_template(
+    state: State<'_, AppState>,
+    notebook_id: String,
+    template_path: Option<String>,
+) -> Result<TemplateSettings, String> {
+    state
+        .template_usecases
+        .set_notebook_template(&notebook_id, template_path)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_note_from_template(
+    state: State<'_, AppState>,
+    request: CreateNoteFromTemplateRequest,
+) -> Result<CreateNoteFromTemplateResponse, String> {
+    state
+        .template_usecases
+        .create_note_from_template(request)
+        .await
+        .map_err(|e| e.to_string())
+}
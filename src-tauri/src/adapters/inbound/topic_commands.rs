@@ -8,10 +8,12 @@ use crate::{
         entities::{Note, Topic},
         ports::{
             inbound::{
-                ClassifyAllResponse, ClassifyNoteResponse, CreateTopicRequest,
-                EmbeddingStatusResponse, NoteTopicInfo, SimilarNoteResult, UpdateTopicRequest,
+                AcceptTopicCandidateRequest, ClassifyAllResponse, ClassifyNoteResponse,
+                CreateTopicRequest, DiscoverTopicsRequest, DiscoverTopicsResponse,
+                EmbeddingSettingsResponse, EmbeddingStatusResponse, NoteTopicInfo,
+                SimilarNoteResult, StartEmbeddingQueueRequest, UpdateTopicRequest,
             },
-            outbound::TopicWithCount,
+            outbound::{EmbeddingProviderConfig, EmbeddingQueueStatus, TopicWithCount},
         },
     },
 };
@@ -102,6 +104,32 @@ pub async fn classify_all_notes(
         .map_err(|e| e.to_string())
 }
 
+/// Propose new topics by clustering note embeddings
+#[tauri::command]
+pub async fn discover_topics(
+    state: State<'_, AppState>,
+    request: Option<DiscoverTopicsRequest>,
+) -> Result<DiscoverTopicsResponse, String> {
+    state
+        .topic_usecases
+        .discover_topics(request.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Create a topic from a discovered candidate
+#[tauri::command]
+pub async fn accept_topic_candidate(
+    state: State<'_, AppState>,
+    request: AcceptTopicCandidateRequest,
+) -> Result<Topic, String> {
+    state
+        .topic_usecases
+        .accept_topic_candidate(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Response for get_similar_notes
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -158,6 +186,77 @@ pub async fn get_embedding_status(
         .map_err(|e| e.to_string())
 }
 
+/// Get the embedding provider, current model and the models to choose from
+#[tauri::command]
+pub async fn get_embedding_settings(
+    state: State<'_, AppState>,
+) -> Result<EmbeddingSettingsResponse, String> {
+    state
+        .topic_usecases
+        .get_embedding_settings()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Switch embedding provider; notes embedded with another model are re-embedded
+#[tauri::command]
+pub async fn update_embedding_settings(
+    state: State<'_, AppState>,
+    provider: EmbeddingProviderConfig,
+) -> Result<EmbeddingSettingsResponse, String> {
+    state
+        .topic_usecases
+        .update_embedding_settings(provider)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Start embedding un-embedded and out-of-date notes in the background
+#[tauri::command]
+pub async fn start_embedding_queue(
+    state: State<'_, AppState>,
+    request: Option<StartEmbeddingQueueRequest>,
+) -> Result<EmbeddingQueueStatus, String> {
+    state
+        .topic_usecases
+        .start_embedding_queue(request.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pause_embedding_queue(
+    state: State<'_, AppState>,
+) -> Result<EmbeddingQueueStatus, String> {
+    state
+        .topic_usecases
+        .pause_embedding_queue()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resume_embedding_queue(
+    state: State<'_, AppState>,
+) -> Result<EmbeddingQueueStatus, String> {
+    state
+        .topic_usecases
+        .resume_embedding_queue()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cancel_embedding_queue(
+    state: State<'_, AppState>,
+) -> Result<EmbeddingQueueStatus, String> {
+    state
+        .topic_usecases
+        .cancel_embedding_queue()
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Response for get_topics_for_note
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
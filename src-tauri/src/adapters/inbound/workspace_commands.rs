@@ -10,8 +10,8 @@ use crate::{
     domain::{
         entities::Workspace,
         ports::inbound::{
-            CreateFolderRequest, CreateFolderResponse, CreateWorkspaceRequest, MoveFolderRequest,
-            MoveFolderResponse, RenameFolderRequest, RenameFolderResponse, ScanWorkspaceResponse,
+            CreateFolderRequest, CreateFolderResponse, CreateWorkspaceRequest, FilenameSettings,
+            MoveFolderRequest, MoveFolderResponse, RenameFolderRequest, RenameFolderResponse, ScanWorkspaceResponse,
             SelectFolderRequest, SelectFolderResponse, SyncWorkspaceResponse,
             UpdateWorkspaceRequest, ValidatePathResponse,
         },
@@ -251,4 +251,31 @@ pub async fn move_folder(
         .map_err(|e| e.to_string())
 }
 
+/// Get how a workspace names note files
+#[tauri::command]
+pub async fn get_filename_settings(
+    state: State<'_, AppState>,
+    workspace_id: Option<String>,
+) -> Result<FilenameSettings, String> {
+    state
+        .workspace_usecases
+        .get_filename_settings(workspace_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Update how a workspace names note files
+#[tauri::command]
+pub async fn update_filename_settings(
+    state: State<'_, AppState>,
+    workspace_id: Option<String>,
+    settings: FilenameSettings,
+) -> Result<FilenameSettings, String> {
+    state
+        .workspace_usecases
+        .update_filename_settings(workspace_id, settings)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // Commands are exported individually and registered in lib.rs
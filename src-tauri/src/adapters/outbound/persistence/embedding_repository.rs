@@ -0,0 +1,447 @@
+//! Embedding Repository Implementation
+//!
+//! Diesel-based implementation of the Embedding repository port. Vectors are
+//! stored in the configured quantization and read back in whatever format
+//! each row was written in.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::domain::{
+    entities::Embedding,
+    errors::{DomainError, DomainResult},
+    ports::outbound::{EmbeddingRepository, EmbeddingSummary},
+};
+use crate::shared::database::schema::embeddings;
+
+use super::{
+    db_pool::{get_connection, DbPool},
+    mappers::{EmbeddingRow, InsertableEmbedding},
+    utils::{map_diesel_error, optional_timestamp_to_datetime, EmbeddingQuantization},
+};
+
+/// Diesel implementation of EmbeddingRepository
+pub struct DieselEmbeddingRepository {
+    pool: Arc<DbPool>,
+    quantization: EmbeddingQuantization,
+}
+
+impl DieselEmbeddingRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self {
+            pool,
+            quantization: EmbeddingQuantization::default(),
+        }
+    }
+
+    /// Store new vectors in a smaller format (existing rows are kept as written)
+    pub fn with_quantization(mut self, quantization: EmbeddingQuantization) -> Self {
+        self.quantization = quantization;
+        self
+    }
+}
+
+#[async_trait]
+impl EmbeddingRepository for DieselEmbeddingRepository {
+    /// Get the note-level embedding of a note
+    async fn find_for_note(&self, note_id: &str) -> DomainResult<Option<Embedding>> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let row = embeddings::table
+                .filter(embeddings::note_id.eq(&note_id))
+                .filter(embeddings::chunk_index.is_null())
+                .first::<EmbeddingRow>(&mut conn)
+                .optional()
+                .map_err(map_diesel_error)?;
+
+            Ok(row.map(|row| row.to_domain()))
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Get the note-level embeddings of several notes, keyed by note ID
+    async fn find_for_notes(
+        &self,
+        note_ids: &[String],
+    ) -> DomainResult<HashMap<String, Embedding>> {
+        let pool = self.pool.clone();
+        let note_ids = note_ids.to_vec();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let rows = embeddings::table
+                .filter(embeddings::note_id.eq_any(note_ids))
+                .filter(embeddings::chunk_index.is_null())
+                .load::<EmbeddingRow>(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(rows
+                .into_iter()
+                .map(|row| {
+                    let embedding = row.to_domain();
+                    (embedding.note_id.clone(), embedding)
+                })
+                .collect())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Get the passage embeddings of a note, in document order
+    async fn find_chunks_for_note(&self, note_id: &str) -> DomainResult<Vec<Embedding>> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let rows = embeddings::table
+                .filter(embeddings::note_id.eq(&note_id))
+                .filter(embeddings::chunk_index.is_not_null())
+                .order(embeddings::chunk_index.asc())
+                .load::<EmbeddingRow>(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(rows.into_iter().map(|row| row.to_domain()).collect())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Get every stored embedding, note-level and passage
+    async fn find_all(&self) -> DomainResult<Vec<Embedding>> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let rows = embeddings::table
+                .order((embeddings::note_id.asc(), embeddings::chunk_index.asc()))
+                .load::<EmbeddingRow>(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(rows.into_iter().map(|row| row.to_domain()).collect())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Replace all embeddings of a note
+    async fn replace_for_note(&self, note_id: &str, vectors: Vec<Embedding>) -> DomainResult<()> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+        let quantization = self.quantization;
+
+        if vectors.iter().any(|embedding| embedding.note_id != note_id) {
+            return Err(DomainError::ValidationError(
+                "All embeddings must belong to the note being replaced".to_string(),
+            ));
+        }
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            // Use transaction so readers never see a half-replaced note
+            conn.transaction(|conn| {
+                diesel::delete(embeddings::table.filter(embeddings::note_id.eq(&note_id)))
+                    .execute(conn)?;
+
+                let rows: Vec<InsertableEmbedding> = vectors
+                    .iter()
+                    .map(|embedding| InsertableEmbedding::from_domain(embedding, quantization))
+                    .collect();
+                if !rows.is_empty() {
+                    diesel::insert_into(embeddings::table)
+                        .values(&rows)
+                        .execute(conn)?;
+                }
+
+                Ok(())
+            })
+            .map_err(map_diesel_error)
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Remove all embeddings of a note
+    async fn delete_for_note(&self, note_id: &str) -> DomainResult<()> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            diesel::delete(embeddings::table.filter(embeddings::note_id.eq(note_id)))
+                .execute(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Count notes with a note-level embedding from the given model
+    async fn count_embedded_notes(
+        &self,
+        model_name: &str,
+        model_version: &str,
+    ) -> DomainResult<i64> {
+        let pool = self.pool.clone();
+        let model_name = model_name.to_string();
+        let model_version = model_version.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            embeddings::table
+                .filter(embeddings::chunk_index.is_null())
+                .filter(embeddings::model_name.eq(&model_name))
+                .filter(embeddings::model_version.eq(&model_version))
+                .count()
+                .get_result::<i64>(&mut conn)
+                .map_err(map_diesel_error)
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Count the note-level and passage embeddings of a model and find the newest
+    async fn summarize_model(
+        &self,
+        model_name: &str,
+        model_version: &str,
+    ) -> DomainResult<EmbeddingSummary> {
+        let pool = self.pool.clone();
+        let model_name = model_name.to_string();
+        let model_version = model_version.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let (count, last_created_at) = embeddings::table
+                .filter(embeddings::model_name.eq(&model_name))
+                .filter(embeddings::model_version.eq(&model_version))
+                .select((
+                    diesel::dsl::count_star(),
+                    diesel::dsl::max(embeddings::created_at),
+                ))
+                .first::<(i64, Option<i64>)>(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(EmbeddingSummary {
+                count,
+                last_created_at: optional_timestamp_to_datetime(last_created_at),
+            })
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Get the notes with embeddings from any other model
+    async fn find_stale_note_ids(
+        &self,
+        model_name: &str,
+        model_version: &str,
+    ) -> DomainResult<Vec<String>> {
+        let pool = self.pool.clone();
+        let model_name = model_name.to_string();
+        let model_version = model_version.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            embeddings::table
+                .filter(
+                    embeddings::model_name
+                        .ne(&model_name)
+                        .or(embeddings::model_version.ne(&model_version)),
+                )
+                .select(embeddings::note_id)
+                .distinct()
+                .order(embeddings::note_id.asc())
+                .load::<String>(&mut conn)
+                .map_err(map_diesel_error)
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::outbound::persistence::DieselNoteRepository;
+    use crate::domain::entities::{Note, NoteChunk};
+    use crate::domain::ports::outbound::NoteRepository;
+    use crate::domain::services::TextChunk;
+    use crate::infrastructure::run_migrations;
+    use crate::shared::database::{create_pool, DatabaseConfig};
+
+    async fn setup(quantization: EmbeddingQuantization) -> (DieselEmbeddingRepository, Note) {
+        let pool = Arc::new(create_pool(DatabaseConfig::in_memory()).unwrap());
+        run_migrations(&pool).unwrap();
+
+        let note = Note::new("Embedded", None).unwrap();
+        DieselNoteRepository::new(pool.clone())
+            .save(&note)
+            .await
+            .unwrap();
+
+        (
+            DieselEmbeddingRepository::new(pool).with_quantization(quantization),
+            note,
+        )
+    }
+
+    fn passage(note_id: &str, index: usize, embedding: Vec<f32>) -> Embedding {
+        let text_chunk = TextChunk {
+            index,
+            heading_path: Vec::new(),
+            text: format!("Passage {}", index),
+            start: index * 10,
+            end: index * 10 + 9,
+        };
+        let chunk = NoteChunk::new(note_id, &text_chunk)
+            .unwrap()
+            .with_embedding(embedding);
+        Embedding::for_chunk(&chunk, "test-model", "1").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_find_embeddings() {
+        let (repo, note) = setup(EmbeddingQuantization::F32).await;
+
+        let note_level = Embedding::for_note(&note.id, vec![0.5, 0.5], "test-model", "1")
+            .unwrap()
+            .with_content_hash("abc");
+        repo.replace_for_note(
+            &note.id,
+            vec![
+                note_level,
+                passage(&note.id, 1, vec![0.0, 1.0]),
+                passage(&note.id, 0, vec![1.0, 0.0]),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let found = repo.find_for_note(&note.id).await.unwrap().unwrap();
+        assert_eq!(found.vector, vec![0.5, 0.5]);
+        assert_eq!(found.content_hash.as_deref(), Some("abc"));
+        assert!(found.is_from_model("test-model", "1"));
+
+        let passages = repo.find_chunks_for_note(&note.id).await.unwrap();
+        assert_eq!(
+            passages.iter().map(|e| e.chunk_index).collect::<Vec<_>>(),
+            vec![Some(0), Some(1)]
+        );
+        assert_eq!(passages[0].vector, vec![1.0, 0.0]);
+        assert_eq!(
+            passages[0].content_hash,
+            Some(Embedding::hash_content("Passage 0"))
+        );
+
+        assert_eq!(repo.find_all().await.unwrap().len(), 3);
+        assert_eq!(
+            repo.count_embedded_notes("test-model", "1").await.unwrap(),
+            1
+        );
+        assert_eq!(
+            repo.count_embedded_notes("test-model", "2").await.unwrap(),
+            0
+        );
+        let summary = repo.summarize_model("test-model", "1").await.unwrap();
+        assert_eq!(summary.count, 3);
+        assert!(summary.last_created_at.is_some());
+        assert_eq!(
+            repo.summarize_model("test-model", "2").await.unwrap(),
+            EmbeddingSummary {
+                count: 0,
+                last_created_at: None,
+            }
+        );
+        let by_note = repo
+            .find_for_notes(&[note.id.clone(), "missing".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(by_note.len(), 1);
+
+        // Replacing drops passages that no longer exist
+        repo.replace_for_note(&note.id, vec![passage(&note.id, 0, vec![0.0, 1.0])])
+            .await
+            .unwrap();
+        assert!(repo.find_for_note(&note.id).await.unwrap().is_none());
+        assert_eq!(repo.find_all().await.unwrap().len(), 1);
+
+        repo.delete_for_note(&note.id).await.unwrap();
+        assert!(repo.find_all().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_quantized_embeddings_round_trip() {
+        let (repo, note) = setup(EmbeddingQuantization::Int8).await;
+        let vector = vec![0.8, -0.4, 0.1, 0.0];
+
+        repo.replace_for_note(
+            &note.id,
+            vec![Embedding::for_note(&note.id, vector.clone(), "test-model", "1").unwrap()],
+        )
+        .await
+        .unwrap();
+
+        let found = repo.find_for_note(&note.id).await.unwrap().unwrap();
+        assert_eq!(found.dimensions(), 4);
+        assert!(vector
+            .iter()
+            .zip(&found.vector)
+            .all(|(a, b)| (a - b).abs() < 0.01));
+    }
+
+    #[tokio::test]
+    async fn test_find_stale_note_ids() {
+        let (repo, note) = setup(EmbeddingQuantization::F32).await;
+        repo.replace_for_note(
+            &note.id,
+            vec![
+                Embedding::for_note(&note.id, vec![1.0, 0.0], "test-model", "1").unwrap(),
+                passage(&note.id, 0, vec![1.0, 0.0]),
+            ],
+        )
+        .await
+        .unwrap();
+
+        assert!(repo
+            .find_stale_note_ids("test-model", "1")
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            repo.find_stale_note_ids("test-model", "2").await.unwrap(),
+            vec![note.id.clone()]
+        );
+        assert_eq!(
+            repo.find_stale_note_ids("other-model", "1").await.unwrap(),
+            vec![note.id]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replace_rejects_other_notes() {
+        let (repo, note) = setup(EmbeddingQuantization::F32).await;
+        let other = Embedding::for_note("other", vec![1.0], "test-model", "1").unwrap();
+
+        let result = repo.replace_for_note(&note.id, vec![other]).await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+}
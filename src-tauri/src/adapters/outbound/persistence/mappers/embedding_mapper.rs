@@ -0,0 +1,78 @@
+//! Embedding Entity Mapper
+//!
+//! Maps between database schema and domain Embedding entity.
+
+use diesel::prelude::*;
+use crate::domain::entities::Embedding;
+use crate::shared::database::schema::embeddings;
+use super::super::utils::{
+    datetime_to_timestamp, dequantize_embedding, quantize_embedding, timestamp_to_datetime,
+    EmbeddingQuantization,
+};
+
+/// Database row struct for embeddings table
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = embeddings)]
+pub struct EmbeddingRow {
+    pub id: String,
+    pub note_id: String,
+    pub chunk_index: Option<i32>,
+    pub model_name: String,
+    pub model_version: String,
+    pub content_hash: Option<String>,
+    pub dimensions: i32,
+    pub quantization: String,
+    pub vector: Vec<u8>,
+    pub created_at: i64,
+}
+
+/// Insertable struct for embeddings table
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = embeddings)]
+pub struct InsertableEmbedding {
+    pub id: String,
+    pub note_id: String,
+    pub chunk_index: Option<i32>,
+    pub model_name: String,
+    pub model_version: String,
+    pub content_hash: Option<String>,
+    pub dimensions: i32,
+    pub quantization: String,
+    pub vector: Vec<u8>,
+    pub created_at: i64,
+}
+
+impl EmbeddingRow {
+    /// Convert database row to domain entity
+    pub fn to_domain(self) -> Embedding {
+        let quantization = EmbeddingQuantization::parse(&self.quantization).unwrap_or_default();
+
+        Embedding {
+            note_id: self.note_id,
+            chunk_index: self.chunk_index,
+            model_name: self.model_name,
+            model_version: self.model_version,
+            content_hash: self.content_hash,
+            vector: dequantize_embedding(&self.vector, quantization),
+            created_at: timestamp_to_datetime(self.created_at),
+        }
+    }
+}
+
+impl InsertableEmbedding {
+    /// Convert domain entity to insertable struct, storing the vector in the given format
+    pub fn from_domain(embedding: &Embedding, quantization: EmbeddingQuantization) -> Self {
+        Self {
+            id: embedding.key(),
+            note_id: embedding.note_id.clone(),
+            chunk_index: embedding.chunk_index,
+            model_name: embedding.model_name.clone(),
+            model_version: embedding.model_version.clone(),
+            content_hash: embedding.content_hash.clone(),
+            dimensions: embedding.dimensions() as i32,
+            quantization: quantization.as_str().to_string(),
+            vector: quantize_embedding(&embedding.vector, quantization),
+            created_at: datetime_to_timestamp(&embedding.created_at),
+        }
+    }
+}
@@ -4,6 +4,9 @@
 
 pub mod workspace_mapper;
 pub mod note_mapper;
+pub mod note_block_mapper;
+pub mod note_chunk_mapper;
+pub mod embedding_mapper;
 pub mod notebook_mapper;
 pub mod tag_mapper;
 pub mod topic_mapper;
@@ -14,6 +17,9 @@ pub mod settings_mapper;
 
 pub use workspace_mapper::*;
 pub use note_mapper::*;
+pub use note_block_mapper::*;
+pub use note_chunk_mapper::*;
+pub use embedding_mapper::*;
 pub use notebook_mapper::*;
 pub use tag_mapper::*;
 pub use topic_mapper::*;
@@ -0,0 +1,64 @@
+//! Note Block Entity Mapper
+//!
+//! Maps between database schema and domain NoteBlock entity.
+
+use diesel::prelude::*;
+use crate::domain::entities::NoteBlock;
+use crate::shared::database::schema::note_blocks;
+use super::super::utils::{datetime_to_timestamp, timestamp_to_datetime};
+
+/// Database row struct for note_blocks table
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = note_blocks)]
+pub struct NoteBlockRow {
+    pub note_id: String,
+    pub block_id: String,
+    pub content: String,
+    pub start_offset: i32,
+    pub end_offset: i32,
+    pub line_number: i32,
+    pub created_at: i64,
+}
+
+/// Insertable struct for note_blocks table
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = note_blocks)]
+pub struct InsertableNoteBlock {
+    pub note_id: String,
+    pub block_id: String,
+    pub content: String,
+    pub start_offset: i32,
+    pub end_offset: i32,
+    pub line_number: i32,
+    pub created_at: i64,
+}
+
+impl NoteBlockRow {
+    /// Convert database row to domain entity
+    pub fn to_domain(self) -> NoteBlock {
+        NoteBlock {
+            note_id: self.note_id,
+            block_id: self.block_id,
+            content: self.content,
+            start_offset: self.start_offset,
+            end_offset: self.end_offset,
+            line_number: self.line_number,
+            created_at: timestamp_to_datetime(self.created_at),
+        }
+    }
+}
+
+impl InsertableNoteBlock {
+    /// Convert domain entity to insertable struct
+    pub fn from_domain(block: &NoteBlock) -> Self {
+        Self {
+            note_id: block.note_id.clone(),
+            block_id: block.block_id.clone(),
+            content: block.content.clone(),
+            start_offset: block.start_offset,
+            end_offset: block.end_offset,
+            line_number: block.line_number,
+            created_at: datetime_to_timestamp(&block.created_at),
+        }
+    }
+}
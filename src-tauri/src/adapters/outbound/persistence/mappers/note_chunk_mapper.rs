@@ -0,0 +1,66 @@
+//! Note Chunk Entity Mapper
+//!
+//! Maps between database schema and domain NoteChunk entity.
+
+use diesel::prelude::*;
+use crate::domain::entities::NoteChunk;
+use crate::shared::database::schema::note_chunks;
+use super::super::utils::{datetime_to_timestamp, timestamp_to_datetime};
+
+/// Database row struct for note_chunks table
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = note_chunks)]
+pub struct NoteChunkRow {
+    pub note_id: String,
+    pub chunk_index: i32,
+    pub heading_path: String,
+    pub content: String,
+    pub start_offset: i32,
+    pub end_offset: i32,
+    pub created_at: i64,
+}
+
+/// Insertable struct for note_chunks table
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = note_chunks)]
+pub struct InsertableNoteChunk {
+    pub note_id: String,
+    pub chunk_index: i32,
+    pub heading_path: String,
+    pub content: String,
+    pub start_offset: i32,
+    pub end_offset: i32,
+    pub created_at: i64,
+}
+
+impl NoteChunkRow {
+    /// Convert database row to domain entity
+    pub fn to_domain(self) -> NoteChunk {
+        NoteChunk {
+            note_id: self.note_id,
+            chunk_index: self.chunk_index,
+            heading_path: serde_json::from_str(&self.heading_path).unwrap_or_default(),
+            content: self.content,
+            start_offset: self.start_offset,
+            end_offset: self.end_offset,
+            embedding: None, // Stored in the embeddings table
+            created_at: timestamp_to_datetime(self.created_at),
+        }
+    }
+}
+
+impl InsertableNoteChunk {
+    /// Convert domain entity to insertable struct
+    pub fn from_domain(chunk: &NoteChunk) -> Self {
+        Self {
+            note_id: chunk.note_id.clone(),
+            chunk_index: chunk.chunk_index,
+            heading_path: serde_json::to_string(&chunk.heading_path)
+                .unwrap_or_else(|_| "[]".to_string()),
+            content: chunk.content.clone(),
+            start_offset: chunk.start_offset,
+            end_offset: chunk.end_offset,
+            created_at: datetime_to_timestamp(&chunk.created_at),
+        }
+    }
+}
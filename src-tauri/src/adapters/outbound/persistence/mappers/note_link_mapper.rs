@@ -14,6 +14,7 @@ pub struct NoteLinkRow {
     pub source_note_id: String,
     pub target_note_id: String,
     pub created_at: i64,
+    pub anchor: String,
 }
 
 /// Insertable struct for note_links table
@@ -23,6 +24,7 @@ pub struct InsertableNoteLink {
     pub source_note_id: String,
     pub target_note_id: String,
     pub created_at: i64,
+    pub anchor: String,
 }
 
 impl NoteLinkRow {
@@ -31,6 +33,7 @@ impl NoteLinkRow {
         NoteLink {
             source_note_id: self.source_note_id,
             target_note_id: self.target_note_id,
+            anchor: Some(self.anchor).filter(|a| !a.is_empty()),
             created_at: timestamp_to_datetime(self.created_at),
         }
     }
@@ -43,6 +46,7 @@ impl InsertableNoteLink {
             source_note_id: link.source_note_id.clone(),
             target_note_id: link.target_note_id.clone(),
             created_at: datetime_to_timestamp(&link.created_at),
+            anchor: link.anchor.clone().unwrap_or_default(),
         }
     }
 }
@@ -7,8 +7,7 @@ use crate::domain::entities::Note;
 use crate::shared::database::schema::notes;
 use super::super::utils::{
     datetime_to_timestamp, timestamp_to_datetime, optional_datetime_to_timestamp,
-    optional_timestamp_to_datetime, serialize_embedding, deserialize_embedding,
-    bool_to_i32, i32_to_bool,
+    optional_timestamp_to_datetime, bool_to_i32, i32_to_bool,
 };
 
 /// Database row struct for notes table
@@ -25,9 +24,10 @@ pub struct NoteRow {
     pub is_archived: i32,
     pub is_deleted: i32,
     pub deleted_at: Option<i64>,
-    pub embedding: Option<Vec<u8>>,
     pub created_at: i64,
     pub updated_at: i64,
+    pub aliases: String,
+    pub properties: String,
 }
 
 /// Insertable struct for notes table
@@ -44,9 +44,10 @@ pub struct InsertableNote {
     pub is_archived: i32,
     pub is_deleted: i32,
     pub deleted_at: Option<i64>,
-    pub embedding: Option<Vec<u8>>,
     pub created_at: i64,
     pub updated_at: i64,
+    pub aliases: String,
+    pub properties: String,
 }
 
 impl NoteRow {
@@ -63,9 +64,10 @@ impl NoteRow {
             is_archived: i32_to_bool(self.is_archived),
             is_deleted: i32_to_bool(self.is_deleted),
             deleted_at: optional_timestamp_to_datetime(self.deleted_at),
-            embedding: self.embedding.map(|b| deserialize_embedding(&b)),
             created_at: timestamp_to_datetime(self.created_at),
             updated_at: timestamp_to_datetime(self.updated_at),
+            aliases: serde_json::from_str(&self.aliases).unwrap_or_default(),
+            properties: serde_json::from_str(&self.properties).unwrap_or_default(),
         }
     }
 }
@@ -88,9 +90,11 @@ impl InsertableNote {
             is_archived: bool_to_i32(note.is_archived),
             is_deleted: bool_to_i32(note.is_deleted),
             deleted_at: optional_datetime_to_timestamp(&note.deleted_at),
-            embedding: note.embedding.as_ref().map(|v| serialize_embedding(v)),
             created_at: datetime_to_timestamp(&note.created_at),
             updated_at: datetime_to_timestamp(&note.updated_at),
+            aliases: serde_json::to_string(&note.aliases).unwrap_or_else(|_| "[]".to_string()),
+            properties: serde_json::to_string(&note.properties)
+                .unwrap_or_else(|_| "{}".to_string()),
         }
     }
 }
@@ -113,9 +117,10 @@ mod tests {
             is_archived: false,
             is_deleted: false,
             deleted_at: None,
-            embedding: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            aliases: vec!["Test".to_string()],
+            properties: serde_json::Map::new(),
         };
 
         let insertable = InsertableNote::from_domain(&note);
@@ -134,15 +139,17 @@ mod tests {
             is_archived: insertable.is_archived,
             is_deleted: insertable.is_deleted,
             deleted_at: insertable.deleted_at,
-            embedding: None,
             created_at: insertable.created_at,
             updated_at: insertable.updated_at,
+            aliases: insertable.aliases.clone(),
+            properties: insertable.properties.clone(),
         };
 
         let domain = row.to_domain();
         assert_eq!(domain.id, note.id);
         assert_eq!(domain.title, note.title);
         assert_eq!(domain.is_favorite, note.is_favorite);
+        assert_eq!(domain.aliases, note.aliases);
     }
 
     #[test]
@@ -158,9 +165,10 @@ mod tests {
             is_archived: false,
             is_deleted: false,
             deleted_at: None,
-            embedding: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            aliases: Vec::new(),
+            properties: serde_json::Map::new(),
         };
 
         let insertable = InsertableNote::from_domain(&note);
@@ -10,6 +10,9 @@ pub mod mappers;
 // Repository implementations
 pub mod workspace_repository;
 pub mod note_repository;
+pub mod note_block_repository;
+pub mod note_chunk_repository;
+pub mod embedding_repository;
 pub mod notebook_repository;
 pub mod tag_repository;
 pub mod topic_repository;
@@ -24,6 +27,9 @@ pub use utils::*;
 pub use mappers::*;
 pub use workspace_repository::DieselWorkspaceRepository;
 pub use note_repository::DieselNoteRepository;
+pub use note_block_repository::DieselNoteBlockRepository;
+pub use note_chunk_repository::DieselNoteChunkRepository;
+pub use embedding_repository::DieselEmbeddingRepository;
 pub use notebook_repository::DieselNotebookRepository;
 pub use tag_repository::DieselTagRepository;
 pub use topic_repository::DieselTopicRepository;
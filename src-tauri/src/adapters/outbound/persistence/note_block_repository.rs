@@ -0,0 +1,190 @@
+//! NoteBlock Repository Implementation
+//!
+//! Diesel-based implementation of the NoteBlock repository port.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::domain::{
+    entities::NoteBlock,
+    errors::{DomainError, DomainResult},
+    ports::outbound::NoteBlockRepository,
+};
+use crate::shared::database::schema::note_blocks;
+
+use super::{
+    db_pool::{get_connection, DbPool},
+    mappers::{InsertableNoteBlock, NoteBlockRow},
+    utils::map_diesel_error,
+};
+
+/// Diesel implementation of NoteBlockRepository
+pub struct DieselNoteBlockRepository {
+    pool: Arc<DbPool>,
+}
+
+impl DieselNoteBlockRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NoteBlockRepository for DieselNoteBlockRepository {
+    /// Get all blocks of a note, in document order
+    async fn find_by_note(&self, note_id: &str) -> DomainResult<Vec<NoteBlock>> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let rows = note_blocks::table
+                .filter(note_blocks::note_id.eq(&note_id))
+                .order(note_blocks::start_offset.asc())
+                .load::<NoteBlockRow>(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(rows.into_iter().map(|row| row.to_domain()).collect())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Get a single block
+    async fn find_by_key(&self, note_id: &str, block_id: &str) -> DomainResult<Option<NoteBlock>> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+        let block_id = block_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let row = note_blocks::table
+                .filter(note_blocks::note_id.eq(&note_id))
+                .filter(note_blocks::block_id.eq(&block_id))
+                .first::<NoteBlockRow>(&mut conn)
+                .optional()
+                .map_err(map_diesel_error)?;
+
+            Ok(row.map(|row| row.to_domain()))
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Replace all blocks of a note
+    async fn replace_for_note(&self, note_id: &str, blocks: Vec<NoteBlock>) -> DomainResult<()> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        if blocks.iter().any(|block| block.note_id != note_id) {
+            return Err(DomainError::ValidationError(
+                "All blocks must belong to the note being replaced".to_string(),
+            ));
+        }
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            // Use transaction so readers never see a half-replaced note
+            conn.transaction(|conn| {
+                diesel::delete(note_blocks::table.filter(note_blocks::note_id.eq(&note_id)))
+                    .execute(conn)?;
+
+                let rows: Vec<InsertableNoteBlock> = blocks
+                    .iter()
+                    .map(InsertableNoteBlock::from_domain)
+                    .collect();
+                if !rows.is_empty() {
+                    diesel::insert_into(note_blocks::table)
+                        .values(&rows)
+                        .execute(conn)?;
+                }
+
+                Ok(())
+            })
+            .map_err(map_diesel_error)
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Remove all blocks of a note
+    async fn delete_for_note(&self, note_id: &str) -> DomainResult<()> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            diesel::delete(note_blocks::table.filter(note_blocks::note_id.eq(note_id)))
+                .execute(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::outbound::persistence::DieselNoteRepository;
+    use crate::domain::entities::Note;
+    use crate::domain::ports::outbound::NoteRepository;
+    use crate::domain::services::NoteAnchors;
+    use crate::infrastructure::run_migrations;
+    use crate::shared::database::{create_pool, DatabaseConfig};
+
+    async fn setup() -> (DieselNoteBlockRepository, Note) {
+        let pool = Arc::new(create_pool(DatabaseConfig::in_memory()).unwrap());
+        run_migrations(&pool).unwrap();
+
+        let note = Note::new("Blocks", None).unwrap();
+        DieselNoteRepository::new(pool.clone())
+            .save(&note)
+            .await
+            .unwrap();
+
+        (DieselNoteBlockRepository::new(pool), note)
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_find_blocks() {
+        let (repo, note) = setup().await;
+        let markdown = "# Blocks\n\nFirst claim. ^claim\n\n- an item ^item\n";
+        let blocks: Vec<NoteBlock> = NoteAnchors::extract_blocks(markdown)
+            .iter()
+            .map(|block| NoteBlock::new(&note.id, block).unwrap())
+            .collect();
+
+        repo.replace_for_note(&note.id, blocks).await.unwrap();
+
+        let blocks = repo.find_by_note(&note.id).await.unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].block_id, "claim");
+        assert_eq!(blocks[0].content, "First claim.");
+        assert_eq!(blocks[0].line_number, 3);
+        assert_eq!(blocks[1].content, "- an item");
+
+        let item = repo.find_by_key(&note.id, "item").await.unwrap().unwrap();
+        assert_eq!(
+            &markdown[item.start_offset as usize..item.end_offset as usize],
+            "- an item ^item"
+        );
+        assert!(repo
+            .find_by_key(&note.id, "missing")
+            .await
+            .unwrap()
+            .is_none());
+
+        // Replacing drops blocks whose marker is gone
+        repo.replace_for_note(&note.id, Vec::new()).await.unwrap();
+        assert!(repo.find_by_note(&note.id).await.unwrap().is_empty());
+    }
+}
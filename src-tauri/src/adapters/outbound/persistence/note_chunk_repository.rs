@@ -0,0 +1,211 @@
+//! NoteChunk Repository Implementation
+//!
+//! Diesel-based implementation of the NoteChunk repository port.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use diesel::prelude::*;
+
+use crate::domain::{
+    entities::NoteChunk,
+    errors::{DomainError, DomainResult},
+    ports::outbound::NoteChunkRepository,
+};
+use crate::shared::database::schema::note_chunks;
+
+use super::{
+    db_pool::{get_connection, DbPool},
+    mappers::{InsertableNoteChunk, NoteChunkRow},
+    utils::map_diesel_error,
+};
+
+/// Diesel implementation of NoteChunkRepository
+pub struct DieselNoteChunkRepository {
+    pool: Arc<DbPool>,
+}
+
+impl DieselNoteChunkRepository {
+    pub fn new(pool: Arc<DbPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NoteChunkRepository for DieselNoteChunkRepository {
+    /// Get all chunks of a note, in document order
+    async fn find_by_note(&self, note_id: &str) -> DomainResult<Vec<NoteChunk>> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let rows = note_chunks::table
+                .filter(note_chunks::note_id.eq(&note_id))
+                .order(note_chunks::chunk_index.asc())
+                .load::<NoteChunkRow>(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(rows.into_iter().map(|row| row.to_domain()).collect())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Get a single chunk
+    async fn find_by_key(
+        &self,
+        note_id: &str,
+        chunk_index: i32,
+    ) -> DomainResult<Option<NoteChunk>> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let row = note_chunks::table
+                .filter(note_chunks::note_id.eq(&note_id))
+                .filter(note_chunks::chunk_index.eq(chunk_index))
+                .first::<NoteChunkRow>(&mut conn)
+                .optional()
+                .map_err(map_diesel_error)?;
+
+            Ok(row.map(|row| row.to_domain()))
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Replace all chunks of a note
+    async fn replace_for_note(&self, note_id: &str, chunks: Vec<NoteChunk>) -> DomainResult<()> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        if chunks.iter().any(|chunk| chunk.note_id != note_id) {
+            return Err(DomainError::ValidationError(
+                "All chunks must belong to the note being replaced".to_string(),
+            ));
+        }
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            // Use transaction so readers never see a half-replaced note
+            conn.transaction(|conn| {
+                diesel::delete(note_chunks::table.filter(note_chunks::note_id.eq(&note_id)))
+                    .execute(conn)?;
+
+                let rows: Vec<InsertableNoteChunk> = chunks
+                    .iter()
+                    .map(InsertableNoteChunk::from_domain)
+                    .collect();
+                if !rows.is_empty() {
+                    diesel::insert_into(note_chunks::table)
+                        .values(&rows)
+                        .execute(conn)?;
+                }
+
+                Ok(())
+            })
+            .map_err(map_diesel_error)
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Remove all chunks of a note
+    async fn delete_for_note(&self, note_id: &str) -> DomainResult<()> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            diesel::delete(note_chunks::table.filter(note_chunks::note_id.eq(note_id)))
+                .execute(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::outbound::persistence::DieselNoteRepository;
+    use crate::domain::entities::Note;
+    use crate::domain::ports::outbound::NoteRepository;
+    use crate::domain::services::TextChunk;
+    use crate::infrastructure::run_migrations;
+    use crate::shared::database::{create_pool, DatabaseConfig};
+
+    async fn setup() -> (DieselNoteChunkRepository, Note) {
+        let pool = Arc::new(create_pool(DatabaseConfig::in_memory()).unwrap());
+        run_migrations(&pool).unwrap();
+
+        let note = Note::new("Chunked", None).unwrap();
+        DieselNoteRepository::new(pool.clone())
+            .save(&note)
+            .await
+            .unwrap();
+
+        (DieselNoteChunkRepository::new(pool), note)
+    }
+
+    fn chunk(note_id: &str, index: usize, heading: &str, embedding: Option<Vec<f32>>) -> NoteChunk {
+        let text_chunk = TextChunk {
+            index,
+            heading_path: vec!["Guide".to_string(), heading.to_string()],
+            text: format!("Passage {}", index),
+            start: index * 10,
+            end: index * 10 + 9,
+        };
+        let chunk = NoteChunk::new(note_id, &text_chunk).unwrap();
+        match embedding {
+            Some(embedding) => chunk.with_embedding(embedding),
+            None => chunk,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_find_chunks() {
+        let (repo, note) = setup().await;
+
+        repo.replace_for_note(
+            &note.id,
+            vec![
+                chunk(&note.id, 0, "Setup", Some(vec![1.0, 0.0])),
+                chunk(&note.id, 1, "Usage", None),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let chunks = repo.find_by_note(&note.id).await.unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].heading_path, vec!["Guide", "Setup"]);
+        assert_eq!((chunks[0].start_offset, chunks[0].end_offset), (0, 9));
+        // Vectors live in the embeddings table, not the chunk rows
+        assert!(chunks[0].embedding.is_none());
+
+        let second = repo.find_by_key(&note.id, 1).await.unwrap().unwrap();
+        assert_eq!(second.content, "Passage 1");
+        assert!(repo.find_by_key(&note.id, 7).await.unwrap().is_none());
+
+        // Replacing drops chunks that no longer exist
+        repo.replace_for_note(&note.id, vec![chunk(&note.id, 0, "Only", None)])
+            .await
+            .unwrap();
+        let chunks = repo.find_by_note(&note.id).await.unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].heading_path, vec!["Guide", "Only"]);
+
+        repo.delete_for_note(&note.id).await.unwrap();
+        assert!(repo.find_by_note(&note.id).await.unwrap().is_empty());
+    }
+}
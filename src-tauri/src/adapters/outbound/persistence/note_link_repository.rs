@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use chrono::Utc;
+use diesel::dsl::count;
 use diesel::prelude::*;
 
 use crate::domain::{
@@ -128,7 +129,11 @@ impl NoteLinkRepository for DieselNoteLinkRepository {
 
             diesel::insert_into(note_links::table)
                 .values(&insertable)
-                .on_conflict((note_links::source_note_id, note_links::target_note_id))
+                .on_conflict((
+                    note_links::source_note_id,
+                    note_links::target_note_id,
+                    note_links::anchor,
+                ))
                 .do_nothing()
                 .execute(&mut conn)
                 .map_err(map_diesel_error)?;
@@ -251,7 +256,7 @@ impl NoteLinkRepository for DieselNoteLinkRepository {
         .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
     }
 
-    /// Count links for a note
+    /// Count linked notes for a note; links into several anchors count once
     async fn count_for_note(&self, note_id: &str) -> DomainResult<LinkCount> {
         let pool = self.pool.clone();
         let note_id = note_id.to_string();
@@ -262,14 +267,14 @@ impl NoteLinkRepository for DieselNoteLinkRepository {
             // Count outgoing links (this note -> others)
             let outgoing: i64 = note_links::table
                 .filter(note_links::source_note_id.eq(&note_id))
-                .count()
+                .select(count(note_links::target_note_id).aggregate_distinct())
                 .get_result(&mut conn)
                 .map_err(map_diesel_error)?;
 
             // Count incoming links (others -> this note)
             let incoming: i64 = note_links::table
                 .filter(note_links::target_note_id.eq(&note_id))
-                .count()
+                .select(count(note_links::source_note_id).aggregate_distinct())
                 .get_result(&mut conn)
                 .map_err(map_diesel_error)?;
 
@@ -337,6 +342,7 @@ mod tests {
         NoteLink {
             source_note_id: source.to_string(),
             target_note_id: target.to_string(),
+            anchor: None,
             created_at: Utc::now(),
         }
     }
@@ -399,6 +405,55 @@ mod tests {
         assert_eq!(count.total(), 3);
     }
 
+    #[tokio::test]
+    async fn test_links_into_anchors() {
+        use crate::adapters::outbound::persistence::DieselNoteRepository;
+        use crate::domain::ports::outbound::NoteRepository;
+        use crate::infrastructure::run_migrations;
+        use crate::shared::database::{create_pool, DatabaseConfig};
+
+        let pool = Arc::new(create_pool(DatabaseConfig::in_memory()).unwrap());
+        run_migrations(&pool).unwrap();
+        let notes = DieselNoteRepository::new(pool.clone());
+        let source = Note::new("Source", None).unwrap();
+        let target = Note::new("Target", None).unwrap();
+        notes.save(&source).await.unwrap();
+        notes.save(&target).await.unwrap();
+        let repo = DieselNoteLinkRepository::new(pool);
+
+        let link = || create_test_link(&source.id, &target.id);
+        repo.save(&link()).await.unwrap();
+        repo.save(&link().with_anchor(Some("Setup".to_string())))
+            .await
+            .unwrap();
+        repo.save(&link().with_anchor(Some("^claim".to_string())))
+            .await
+            .unwrap();
+        repo.save(&link().with_anchor(Some("Setup".to_string())))
+            .await
+            .unwrap();
+
+        let mut anchors: Vec<Option<String>> = repo
+            .find_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|link| link.anchor)
+            .collect();
+        anchors.sort();
+        assert_eq!(
+            anchors,
+            vec![None, Some("Setup".to_string()), Some("^claim".to_string())]
+        );
+
+        // Still one linked note either way
+        let count = repo.count_for_note(&source.id).await.unwrap();
+        assert_eq!(count.outgoing, 1);
+        let count = repo.count_for_note(&target.id).await.unwrap();
+        assert_eq!(count.incoming, 1);
+        assert_eq!(repo.get_forward_links(&source.id).await.unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_delete_from_note() {
         let pool = Arc::new(create_pool(":memory:").unwrap());
@@ -429,7 +429,7 @@ impl NoteRepository for DieselNoteRepository {
         .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
     }
 
-    /// Search notes by title
+    /// Search notes by title or alias
     async fn search_by_title(
         &self,
         query: &str,
@@ -449,7 +449,11 @@ impl NoteRepository for DieselNoteRepository {
                 query = query.filter(notes::workspace_id.eq(ws_id));
             }
 
-            query = query.filter(notes::title.like(search_query));
+            query = query.filter(
+                notes::title
+                    .like(search_query.clone())
+                    .or(notes::aliases.like(search_query)),
+            );
             query = query.filter(notes::is_deleted.eq(0));
             query = query.order(notes::updated_at.desc());
 
@@ -484,9 +488,10 @@ mod tests {
             is_archived: false,
             is_deleted: false,
             deleted_at: None,
-            embedding: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            aliases: Vec::new(),
+            properties: serde_json::Map::new(),
         }
     }
 
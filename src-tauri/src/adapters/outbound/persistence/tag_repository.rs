@@ -2,7 +2,7 @@
 //!
 //! Diesel-based implementation of the Tag repository port with many-to-many relationships.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -10,9 +10,9 @@ use chrono::Utc;
 use diesel::prelude::*;
 
 use crate::domain::{
-    entities::Tag,
+    entities::{NoteTag, Tag, TagSource},
     errors::{DomainError, DomainResult},
-    ports::outbound::{TagRepository, TagWithCount},
+    ports::outbound::{TagMove, TagRepository, TagWithCount},
 };
 use crate::shared::database::schema::{note_tags, notes, tags};
 
@@ -31,6 +31,69 @@ impl DieselTagRepository {
     pub fn new(pool: Arc<DbPool>) -> Self {
         Self { pool }
     }
+
+    /// Insert or update a tag, creating the parents of a nested tag
+    fn upsert_tag(conn: &mut SqliteConnection, tag: &Tag) -> QueryResult<()> {
+        let insertable = InsertableTag::from_domain(tag);
+        diesel::insert_into(tags::table)
+            .values(&insertable)
+            .on_conflict(tags::id)
+            .do_update()
+            .set(&insertable)
+            .execute(conn)?;
+
+        // A nested tag needs its parents to exist
+        for ancestor in Tag::ancestor_names(&tag.name) {
+            let Ok(parent) = Tag::new(ancestor) else {
+                continue;
+            };
+            diesel::insert_into(tags::table)
+                .values(&InsertableTag::from_domain(&parent))
+                .on_conflict(tags::name)
+                .do_nothing()
+                .execute(conn)?;
+        }
+
+        Ok(())
+    }
+
+    /// Move the links of one tag onto another, then delete the first
+    ///
+    /// A note keeps a manual link if either of its links was manual.
+    fn merge_links(conn: &mut SqliteConnection, from_id: &str, into_id: &str) -> QueryResult<()> {
+        let links: Vec<(String, i64, String)> = note_tags::table
+            .filter(note_tags::tag_id.eq(from_id))
+            .select((note_tags::note_id, note_tags::created_at, note_tags::source))
+            .load(conn)?;
+
+        for (note_id, created_at, source) in links {
+            let source = TagSource::parse(&source);
+            diesel::insert_into(note_tags::table)
+                .values((
+                    note_tags::note_id.eq(&note_id),
+                    note_tags::tag_id.eq(into_id),
+                    note_tags::created_at.eq(created_at),
+                    note_tags::source.eq(source.as_str()),
+                ))
+                .on_conflict((note_tags::note_id, note_tags::tag_id))
+                .do_nothing()
+                .execute(conn)?;
+            if source == TagSource::Manual {
+                diesel::update(
+                    note_tags::table
+                        .filter(note_tags::note_id.eq(&note_id))
+                        .filter(note_tags::tag_id.eq(into_id)),
+                )
+                .set(note_tags::source.eq(source.as_str()))
+                .execute(conn)?;
+            }
+        }
+
+        diesel::delete(note_tags::table.filter(note_tags::tag_id.eq(from_id))).execute(conn)?;
+        diesel::delete(tags::table.filter(tags::id.eq(from_id))).execute(conn)?;
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -93,7 +156,7 @@ impl TagRepository for DieselTagRepository {
         .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
     }
 
-    /// Find all tags with note counts
+    /// Find all tags with note counts, nested under their parent tags
     async fn find_all_with_counts(&self) -> DomainResult<Vec<TagWithCount>> {
         let pool = self.pool.clone();
 
@@ -106,34 +169,46 @@ impl TagRepository for DieselTagRepository {
                 .load(&mut conn)
                 .map_err(map_diesel_error)?;
 
-            // Count notes for each tag
-            let mut tags_with_counts = Vec::new();
-
-            for tag_row in tag_rows {
-                let tag_id = tag_row.id.clone();
-
-                // Count non-deleted notes with this tag
-                let note_count: i64 = note_tags::table
-                    .inner_join(notes::table)
-                    .filter(note_tags::tag_id.eq(&tag_id))
-                    .filter(notes::is_deleted.eq(0))
-                    .count()
-                    .get_result(&mut conn)
-                    .map_err(map_diesel_error)?;
-
-                let tag = tag_row.to_domain();
-                tags_with_counts.push(TagWithCount {
-                    id: tag.id,
-                    name: tag.name,
-                    description: None,
-                    color: Some(tag.color),
-                    created_at: tag.created_at,
-                    updated_at: tag.updated_at,
-                    note_count: note_count as i32,
-                });
+            // Non-deleted notes for each tag name
+            let pairs: Vec<(String, String)> = note_tags::table
+                .inner_join(notes::table)
+                .inner_join(tags::table)
+                .filter(notes::is_deleted.eq(0))
+                .select((tags::name, note_tags::note_id))
+                .load(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            // Roll each note up into the tag's ancestors
+            let mut direct: HashMap<String, i32> = HashMap::new();
+            let mut within: HashMap<&str, HashSet<&str>> = HashMap::new();
+            for (name, note_id) in &pairs {
+                *direct.entry(name.clone()).or_insert(0) += 1;
+                for ancestor in Tag::ancestor_names(name).into_iter().chain([name.as_str()]) {
+                    within.entry(ancestor).or_default().insert(note_id);
+                }
             }
 
-            Ok(tags_with_counts)
+            let tags_with_counts = tag_rows
+                .into_iter()
+                .map(|tag_row| {
+                    let tag = tag_row.to_domain();
+                    TagWithCount {
+                        note_count: direct.get(&tag.name).copied().unwrap_or(0),
+                        total_note_count: within
+                            .get(tag.name.as_str())
+                            .map_or(0, |note_ids| note_ids.len() as i32),
+                        children: Vec::new(),
+                        id: tag.id,
+                        name: tag.name,
+                        description: None,
+                        color: Some(tag.color),
+                        created_at: tag.created_at,
+                        updated_at: tag.updated_at,
+                    }
+                })
+                .collect();
+
+            Ok(TagWithCount::into_tree(tags_with_counts))
         })
         .await
         .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
@@ -161,7 +236,7 @@ impl TagRepository for DieselTagRepository {
         .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
     }
 
-    /// Save a tag (create or update)
+    /// Save a tag (create or update), creating any missing parent tags
     async fn save(&self, tag: &Tag) -> DomainResult<()> {
         let pool = self.pool.clone();
         let tag = tag.clone();
@@ -169,17 +244,8 @@ impl TagRepository for DieselTagRepository {
         tokio::task::spawn_blocking(move || {
             let mut conn = get_connection(&pool)?;
 
-            let insertable = InsertableTag::from_domain(&tag);
-
-            diesel::insert_into(tags::table)
-                .values(&insertable)
-                .on_conflict(tags::id)
-                .do_update()
-                .set(&insertable)
-                .execute(&mut conn)
-                .map_err(map_diesel_error)?;
-
-            Ok(())
+            conn.transaction(|conn| Self::upsert_tag(conn, &tag))
+                .map_err(map_diesel_error)
         })
         .await
         .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
@@ -229,6 +295,11 @@ impl TagRepository for DieselTagRepository {
 
     /// Add a tag to a note
     async fn add_tag_to_note(&self, note_id: &str, tag_id: &str) -> DomainResult<()> {
+        self.link_tag(note_id, tag_id, TagSource::Manual).await
+    }
+
+    /// Link a tag to a note, or change where an existing link came from
+    async fn link_tag(&self, note_id: &str, tag_id: &str, source: TagSource) -> DomainResult<()> {
         let pool = self.pool.clone();
         let note_id = note_id.to_string();
         let tag_id = tag_id.to_string();
@@ -241,9 +312,11 @@ impl TagRepository for DieselTagRepository {
                     note_tags::note_id.eq(&note_id),
                     note_tags::tag_id.eq(&tag_id),
                     note_tags::created_at.eq(datetime_to_timestamp(&Utc::now())),
+                    note_tags::source.eq(source.as_str()),
                 ))
                 .on_conflict((note_tags::note_id, note_tags::tag_id))
-                .do_nothing()
+                .do_update()
+                .set(note_tags::source.eq(source.as_str()))
                 .execute(&mut conn)
                 .map_err(map_diesel_error)?;
 
@@ -282,6 +355,77 @@ impl TagRepository for DieselTagRepository {
         self.find_by_note_id(note_id).await
     }
 
+    /// Get all tags for a note, each with where its link came from
+    async fn find_note_tags_with_source(&self, note_id: &str) -> DomainResult<Vec<NoteTag>> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let rows = note_tags::table
+                .inner_join(tags::table)
+                .filter(note_tags::note_id.eq(note_id))
+                .select((tags::all_columns, note_tags::source))
+                .order(tags::name.asc())
+                .load::<(TagRow, String)>(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(rows
+                .into_iter()
+                .map(|(row, source)| NoteTag {
+                    tag: row.to_domain(),
+                    source: TagSource::parse(&source),
+                })
+                .collect())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// IDs of the notes carrying a tag
+    async fn find_note_ids(&self, tag_id: &str) -> DomainResult<Vec<String>> {
+        let pool = self.pool.clone();
+        let tag_id = tag_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            note_tags::table
+                .filter(note_tags::tag_id.eq(tag_id))
+                .select(note_tags::note_id)
+                .order(note_tags::note_id.asc())
+                .load::<String>(&mut conn)
+                .map_err(map_diesel_error)
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Apply renames and merges in order, all of them or none
+    async fn move_tags(&self, moves: Vec<TagMove>) -> DomainResult<()> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            conn.transaction(|conn| {
+                for tag_move in &moves {
+                    match tag_move {
+                        TagMove::Rename(tag) => Self::upsert_tag(conn, tag)?,
+                        TagMove::Merge { from_id, into_id } => {
+                            Self::merge_links(conn, from_id, into_id)?
+                        }
+                    }
+                }
+                Ok(())
+            })
+            .map_err(map_diesel_error)
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
     /// Set tags for a note (replaces all existing tags)
     async fn set_note_tags(&self, note_id: &str, tag_ids: Vec<String>) -> DomainResult<()> {
         let pool = self.pool.clone();
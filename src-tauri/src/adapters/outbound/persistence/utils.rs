@@ -6,7 +6,7 @@
 use chrono::{DateTime, TimeZone, Utc};
 use diesel::result::Error as DieselError;
 
-use crate::domain::errors::DomainError;
+use crate::domain::errors::{DomainError, DomainResult};
 
 /// Convert Diesel error to DomainError
 pub fn map_diesel_error(e: DieselError) -> DomainError {
@@ -88,6 +88,153 @@ pub fn deserialize_embedding(bytes: &[u8]) -> Vec<f32> {
         .collect()
 }
 
+/// Storage format of an embedding vector
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmbeddingQuantization {
+    /// 4 bytes per value, lossless
+    #[default]
+    F32,
+    /// 2 bytes per value (IEEE half precision)
+    F16,
+    /// 1 byte per value, scaled by the largest magnitude
+    Int8,
+}
+
+impl EmbeddingQuantization {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::F32 => "f32",
+            Self::F16 => "f16",
+            Self::Int8 => "int8",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "f32" => Some(Self::F32),
+            "f16" => Some(Self::F16),
+            "int8" | "i8" => Some(Self::Int8),
+            _ => None,
+        }
+    }
+
+    /// Parse the value stored under `EMBEDDING_QUANTIZATION_SETTING`
+    pub fn from_setting(value: &str) -> DomainResult<Self> {
+        Self::parse(value).ok_or_else(|| {
+            DomainError::ConfigurationError(format!(
+                "Invalid embedding quantization setting: {} (expected f32, f16 or int8)",
+                value
+            ))
+        })
+    }
+}
+
+/// Serialize an embedding in the given storage format
+///
+/// Int8 stores an f32 scale (largest magnitude / 127) before the values.
+pub fn quantize_embedding(embedding: &[f32], quantization: EmbeddingQuantization) -> Vec<u8> {
+    match quantization {
+        EmbeddingQuantization::F32 => serialize_embedding(embedding),
+        EmbeddingQuantization::F16 => embedding
+            .iter()
+            .flat_map(|&f| f32_to_f16_bits(f).to_le_bytes())
+            .collect(),
+        EmbeddingQuantization::Int8 => {
+            let max_abs = embedding.iter().fold(0.0_f32, |max, f| max.max(f.abs()));
+            let scale = if max_abs > 0.0 { max_abs / 127.0 } else { 1.0 };
+
+            let mut bytes = Vec::with_capacity(4 + embedding.len());
+            bytes.extend_from_slice(&scale.to_le_bytes());
+            bytes.extend(
+                embedding
+                    .iter()
+                    .map(|&f| (f / scale).round().clamp(-127.0, 127.0) as i8 as u8),
+            );
+            bytes
+        }
+    }
+}
+
+/// Deserialize an embedding stored in the given format
+pub fn dequantize_embedding(bytes: &[u8], quantization: EmbeddingQuantization) -> Vec<f32> {
+    match quantization {
+        EmbeddingQuantization::F32 => deserialize_embedding(bytes),
+        EmbeddingQuantization::F16 => bytes
+            .chunks_exact(2)
+            .map(|chunk| f16_bits_to_f32(u16::from_le_bytes([chunk[0], chunk[1]])))
+            .collect(),
+        EmbeddingQuantization::Int8 => {
+            if bytes.len() < 4 {
+                return Vec::new();
+            }
+            let scale = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            bytes[4..].iter().map(|&b| b as i8 as f32 * scale).collect()
+        }
+    }
+}
+
+/// Convert f32 to IEEE 754 half precision bits (round to nearest even)
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x007f_ffff;
+
+    if exponent == 0xff {
+        // Infinity or NaN
+        let nan = if mantissa != 0 { 0x0200 } else { 0 };
+        return sign | 0x7c00 | nan;
+    }
+
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1f {
+        return sign | 0x7c00; // Overflow to infinity
+    }
+    if half_exponent <= 0 {
+        if half_exponent < -10 {
+            return sign; // Too small, flush to zero
+        }
+        // Subnormal half
+        let mantissa = mantissa | 0x0080_0000;
+        let shift = (14 - half_exponent) as u32;
+        let half_mantissa = mantissa >> shift;
+        let remainder = mantissa & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        let round_up = remainder > halfway || (remainder == halfway && half_mantissa & 1 == 1);
+        return sign | (half_mantissa + round_up as u32) as u16;
+    }
+
+    let half = ((half_exponent as u32) << 10) | (mantissa >> 13);
+    let remainder = mantissa & 0x1fff;
+    let round_up = remainder > 0x1000 || (remainder == 0x1000 && half & 1 == 1);
+    // A carry out of the mantissa correctly bumps the exponent
+    sign | (half + round_up as u32) as u16
+}
+
+/// Convert IEEE 754 half precision bits to f32
+fn f16_bits_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exponent = ((half >> 10) & 0x1f) as u32;
+    let mantissa = (half & 0x03ff) as u32;
+
+    let bits = match (exponent, mantissa) {
+        (0, 0) => sign,
+        (0, _) => {
+            // Subnormal half: normalize the mantissa
+            let mut exponent = 127 - 15 + 1;
+            let mut mantissa = mantissa;
+            while mantissa & 0x0400 == 0 {
+                mantissa <<= 1;
+                exponent -= 1;
+            }
+            sign | (exponent << 23) | ((mantissa & 0x03ff) << 13)
+        }
+        (0x1f, _) => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(bits)
+}
+
 /// Convert boolean to SQLite integer (0 or 1)
 pub fn bool_to_i32(b: bool) -> i32 {
     if b { 1 } else { 0 }
@@ -132,6 +279,54 @@ mod tests {
         assert_eq!(original, deserialized);
     }
 
+    #[test]
+    fn test_embedding_quantization() {
+        let original = vec![0.5, -0.25, 0.1, -1.0, 0.0, 0.003];
+
+        let f32_bytes = quantize_embedding(&original, EmbeddingQuantization::F32);
+        assert_eq!(f32_bytes.len(), 24);
+        assert_eq!(
+            dequantize_embedding(&f32_bytes, EmbeddingQuantization::F32),
+            original
+        );
+
+        let f16_bytes = quantize_embedding(&original, EmbeddingQuantization::F16);
+        assert_eq!(f16_bytes.len(), 12);
+        let f16 = dequantize_embedding(&f16_bytes, EmbeddingQuantization::F16);
+        assert_eq!(&f16[..2], &[0.5, -0.25]); // Exactly representable
+        assert!(original.iter().zip(&f16).all(|(a, b)| (a - b).abs() < 1e-3));
+
+        let int8_bytes = quantize_embedding(&original, EmbeddingQuantization::Int8);
+        assert_eq!(int8_bytes.len(), 4 + 6);
+        let int8 = dequantize_embedding(&int8_bytes, EmbeddingQuantization::Int8);
+        assert_eq!(int8[3], -1.0); // Largest magnitude survives exactly
+        assert!(original
+            .iter()
+            .zip(&int8)
+            .all(|(a, b)| (a - b).abs() < 1.0 / 127.0));
+
+        assert_eq!(
+            dequantize_embedding(
+                &quantize_embedding(&[0.0, 0.0], EmbeddingQuantization::Int8),
+                EmbeddingQuantization::Int8
+            ),
+            vec![0.0, 0.0]
+        );
+        assert_eq!(
+            EmbeddingQuantization::parse("INT8"),
+            Some(EmbeddingQuantization::Int8)
+        );
+        assert_eq!(EmbeddingQuantization::parse("f64"), None);
+        assert_eq!(
+            EmbeddingQuantization::from_setting(" f16 ").unwrap(),
+            EmbeddingQuantization::F16
+        );
+        assert!(matches!(
+            EmbeddingQuantization::from_setting("f64"),
+            Err(DomainError::ConfigurationError(_))
+        ));
+    }
+
     #[test]
     fn test_bool_conversion() {
         assert_eq!(bool_to_i32(true), 1);
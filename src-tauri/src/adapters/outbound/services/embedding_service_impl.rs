@@ -1,256 +1,844 @@
 //! Embedding Service Implementation
 //!
-//! Implementation of the EmbeddingService port using FastEmbed.
-//! Uses the all-MiniLM-L6-v2 model for generating text embeddings.
+//! Implementation of the EmbeddingService port. Vectors come from a pluggable
+//! `EmbeddingProvider`: a FastEmbed model (all-MiniLM-L6-v2 by default), a
+//! model in a local directory, or an OpenAI-compatible endpoint.
+//!
+//! Notes are embedded passage by passage (see `NoteChunker`); the note's own
+//! embedding is the mean of its passages. Vectors are stored through the
+//! embedding repository, tagged with the model that produced them, and only
+//! vectors of the current model are read back; the rest are stale until the
+//! note is embedded again. Similarity queries go through an
+//! HNSW vector index over the passages, keyed `<note id>#<chunk index>`, plus
+//! whole-note entries for notes embedded before chunking. The index is
+//! persisted to disk when a path is configured, stamped with the model and the
+//! count and newest timestamp of its embeddings, and rebuilt from the database
+//! when the file is missing or unreadable, when its stamp no longer matches
+//! the stored embeddings, or when the model changes. Pending changes are
+//! flushed on exit through `flush_vector_index`.
+//!
+//! Topic classification compares a note's embedding with topic centroids
+//! (see `TopicClassifier`) and stores the result through the topic repository.
 
-use std::sync::{Arc, Mutex};
-use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use fastembed::{InitOptions, TextEmbedding, EmbeddingModel};
+use serde::{Deserialize, Serialize};
 use tokio::task;
 
 use crate::domain::{
+    entities::{Embedding, NoteChunk, Topic},
     errors::{DomainError, DomainResult},
     ports::outbound::{
-        ClassificationResult, EmbeddingService, EmbeddingStatus, SimilarNote, NoteRepository,
+        ClassificationResult, EmbeddingModelInfo, EmbeddingModelOption, EmbeddingProvider,
+        EmbeddingProviderConfig, EmbeddingRepository, EmbeddingSearchFilter, EmbeddingService,
+        EmbeddingStatus, EmbeddingSummary, NoteChunkRepository, NoteFindOptions, NotePassage,
+        NoteRepository, SimilarNote, TopicRepository,
+    },
+    services::{
+        mean_vector, CentroidMember, ClassificationConfig, HnswConfig, HnswIndex,
+        TopicClassifier, VectorHit,
     },
 };
 
-/// FastEmbed embedding service implementation
+use super::{FastEmbedProvider, OpenAiEmbeddingProvider};
+
+/// Passages fetched per requested note, since one note can match many times
+const PASSAGES_PER_NOTE: usize = 4;
+
+/// Below this many candidates an exact scan beats walking the graph
+const EXACT_SEARCH_THRESHOLD: usize = 2048;
+
+/// Persist the index after this many unsaved changes...
+const SAVE_AFTER_CHANGES: usize = 64;
+
+/// ...or once the oldest unsaved change is this old
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Start of an index file, ahead of its stamp and the index itself
+const INDEX_FILE_MAGIC: &[u8; 8] = b"STNVIDX1";
+
+/// Tracks changes not yet written to the index file
+struct PersistState {
+    unsaved_changes: usize,
+    last_saved: Instant,
+}
+
+/// The embeddings an index file was built from, checked against the database on load
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct IndexStamp {
+    model_name: String,
+    model_version: String,
+    embeddings: EmbeddingSummary,
+}
+
+impl IndexStamp {
+    /// Magic, stamp length, JSON stamp, then the index
+    fn encode(&self, index: &HnswIndex) -> DomainResult<Vec<u8>> {
+        let stamp = serde_json::to_vec(self).map_err(|e| {
+            DomainError::InternalError(format!("Failed to encode index stamp: {}", e))
+        })?;
+        let mut out = Vec::with_capacity(INDEX_FILE_MAGIC.len() + 4 + stamp.len());
+        out.extend_from_slice(INDEX_FILE_MAGIC);
+        out.extend_from_slice(&(stamp.len() as u32).to_le_bytes());
+        out.extend_from_slice(&stamp);
+        out.extend_from_slice(&index.to_bytes());
+        Ok(out)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, HnswIndex), String> {
+        let rest = bytes
+            .strip_prefix(INDEX_FILE_MAGIC.as_slice())
+            .ok_or_else(|| "Not a stamped vector index file".to_string())?;
+        let (length, rest) = rest
+            .split_first_chunk::<4>()
+            .ok_or_else(|| "Truncated index stamp".to_string())?;
+        let length = u32::from_le_bytes(*length) as usize;
+        if rest.len() < length {
+            return Err("Truncated index stamp".to_string());
+        }
+        let (stamp, index) = rest.split_at(length);
+        let stamp = serde_json::from_slice(stamp).map_err(|e| e.to_string())?;
+        Ok((stamp, HnswIndex::from_bytes(index)?))
+    }
+}
+
+/// Build the provider described by the embedding settings
+pub fn create_embedding_provider(
+    config: &EmbeddingProviderConfig,
+) -> DomainResult<Arc<dyn EmbeddingProvider>> {
+    config.validate()?;
+
+    Ok(match config {
+        EmbeddingProviderConfig::FastEmbed { model } => {
+            Arc::new(FastEmbedProvider::from_model_id(model)?)
+        }
+        EmbeddingProviderConfig::LocalModel { path } => {
+            Arc::new(FastEmbedProvider::from_directory(path.trim())?)
+        }
+        EmbeddingProviderConfig::OpenAiCompatible {
+            base_url,
+            model,
+            api_key,
+        } => Arc::new(OpenAiEmbeddingProvider::new(
+            base_url.as_str(),
+            model.as_str(),
+            api_key.clone(),
+        )),
+    })
+}
+
+/// Embedding service implementation, FastEmbed by default
 pub struct FastEmbedService {
-    model: Arc<Mutex<Option<TextEmbedding>>>,
+    provider: RwLock<Arc<dyn EmbeddingProvider>>,
     note_repository: Arc<dyn NoteRepository>,
-    ready: Arc<Mutex<bool>>,
+    chunk_repository: Arc<dyn NoteChunkRepository>,
+    embedding_repository: Arc<dyn EmbeddingRepository>,
+    topic_repository: Arc<dyn TopicRepository>,
+    classifier: TopicClassifier,
+    vector_index: Arc<RwLock<Option<HnswIndex>>>,
+    index_path: Option<PathBuf>,
+    /// Serialises loading/rebuilding so concurrent callers build the index once
+    index_load: tokio::sync::Mutex<()>,
+    persist_state: Mutex<PersistState>,
 }
 
 impl FastEmbedService {
-    pub fn new(note_repository: Arc<dyn NoteRepository>) -> Self {
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        chunk_repository: Arc<dyn NoteChunkRepository>,
+        embedding_repository: Arc<dyn EmbeddingRepository>,
+        topic_repository: Arc<dyn TopicRepository>,
+    ) -> Self {
         Self {
-            model: Arc::new(Mutex::new(None)),
+            provider: RwLock::new(Arc::new(FastEmbedProvider::default())),
             note_repository,
-            ready: Arc::new(Mutex::new(false)),
+            chunk_repository,
+            embedding_repository,
+            topic_repository,
+            classifier: TopicClassifier::default(),
+            vector_index: Arc::new(RwLock::new(None)),
+            index_path: None,
+            index_load: tokio::sync::Mutex::new(()),
+            persist_state: Mutex::new(PersistState {
+                unsaved_changes: 0,
+                last_saved: Instant::now(),
+            }),
         }
     }
 
-    /// Calculate cosine similarity between two vectors
-    fn cosine_similarity(v1: &[f32], v2: &[f32]) -> f32 {
-        if v1.len() != v2.len() {
-            return 0.0;
+    /// Embed with another provider than the default FastEmbed model
+    pub fn with_provider(mut self, provider: Arc<dyn EmbeddingProvider>) -> Self {
+        self.provider = RwLock::new(provider);
+        self
+    }
+
+    /// Persist the vector index at `path` (kept in memory only otherwise)
+    pub fn with_index_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.index_path = Some(path.into());
+        self
+    }
+
+    /// Override the classification threshold, top-k and manual weighting
+    pub fn with_classification_config(mut self, config: ClassificationConfig) -> Self {
+        self.classifier = TopicClassifier::new(config);
+        self
+    }
+
+    /// Rebuild the vector index from the embeddings stored in the database
+    ///
+    /// Only vectors of the current model are indexed.
+    pub async fn rebuild_vector_index(&self) -> DomainResult<()> {
+        let model = self.current_provider().model();
+        let embeddings: Vec<Embedding> = self
+            .embedding_repository
+            .find_all()
+            .await?
+            .into_iter()
+            .filter(|e| e.is_from_model(&model.name, &model.version))
+            .collect();
+        let chunked: HashSet<String> = embeddings
+            .iter()
+            .filter(|e| !e.is_note_level())
+            .map(|e| e.note_id.clone())
+            .collect();
+
+        // Passages, plus whole-note embeddings for notes without passages
+        let entries: Vec<(String, Vec<f32>)> = embeddings
+            .into_iter()
+            .filter(|e| !e.is_note_level() || !chunked.contains(&e.note_id))
+            .map(|e| (e.key(), e.vector))
+            .collect();
+
+        tracing::info!("Building vector index from {} embeddings...", entries.len());
+        let started = Instant::now();
+
+        let index = task::spawn_blocking(move || {
+            let mut index = HnswIndex::new(HnswConfig::default());
+            for (key, embedding) in entries {
+                if let Err(e) = index.insert(&key, &embedding) {
+                    tracing::warn!("Skipping embedding {}: {}", key, e);
+                }
+            }
+            index
+        })
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Task join error: {}", e)))?;
+
+        tracing::info!(
+            "Vector index built with {} entries in {:?}",
+            index.len(),
+            started.elapsed()
+        );
+
+        *self.write_index()? = Some(index);
+        self.persist_index(true).await
+    }
+
+    /// Load the vector index from disk, or rebuild it if that fails
+    async fn ensure_index(&self) -> DomainResult<()> {
+        if self.read_index()?.is_some() {
+            return Ok(());
         }
 
-        let dot_product: f32 = v1.iter().zip(v2.iter()).map(|(a, b)| a * b).sum();
-        let norm_a: f32 = v1.iter().map(|a| a * a).sum::<f32>().sqrt();
-        let norm_b: f32 = v2.iter().map(|b| b * b).sum::<f32>().sqrt();
+        let _guard = self.index_load.lock().await;
+        if self.read_index()?.is_some() {
+            return Ok(());
+        }
 
-        if norm_a == 0.0 || norm_b == 0.0 {
-            return 0.0;
+        if let Some(path) = self.index_path.clone() {
+            let loaded = task::spawn_blocking(move || {
+                std::fs::read(&path)
+                    .ok()
+                    .map(|bytes| IndexStamp::decode(&bytes))
+            })
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Task join error: {}", e)))?;
+
+            match loaded {
+                Some(Ok((stamp, index))) if !index.needs_compaction() => {
+                    // Changes made after the last save, or by another copy of the
+                    // app, leave the file behind the database
+                    if stamp == self.index_stamp().await? {
+                        tracing::info!("Loaded vector index with {} entries", index.len());
+                        *self.write_index()? = Some(index);
+                        return Ok(());
+                    }
+                    tracing::info!("Vector index is out of date with the database, rebuilding");
+                }
+                Some(Ok(_)) => tracing::info!("Vector index has many deletions, rebuilding"),
+                Some(Err(e)) => tracing::warn!("Vector index file is unreadable ({}), rebuilding", e),
+                None => tracing::info!("No vector index on disk, building one"),
+            }
         }
 
-        dot_product / (norm_a * norm_b)
+        self.rebuild_vector_index().await
     }
-}
 
-#[async_trait]
-impl EmbeddingService for FastEmbedService {
-    async fn initialize(&self) -> DomainResult<()> {
-        let model_ref = self.model.clone();
-        let ready_ref = self.ready.clone();
+    /// Write the index to disk if forced or enough changes have piled up
+    async fn persist_index(&self, force: bool) -> DomainResult<()> {
+        let Some(path) = self.index_path.clone() else {
+            return Ok(());
+        };
 
-        task::spawn_blocking(move || -> DomainResult<()> {
-            let mut model_lock = model_ref.lock().map_err(|e| {
-                DomainError::InternalError(format!("Failed to lock embedding model: {}", e))
+        {
+            let mut state = self.persist_state.lock().map_err(|e| {
+                DomainError::InternalError(format!("Failed to lock index state: {}", e))
             })?;
-
-            if model_lock.is_some() {
+            let due = state.unsaved_changes >= SAVE_AFTER_CHANGES
+                || (state.unsaved_changes > 0 && state.last_saved.elapsed() >= SAVE_INTERVAL);
+            if !force && !due {
                 return Ok(());
             }
+            state.unsaved_changes = 0;
+            state.last_saved = Instant::now();
+        }
 
-            tracing::info!("Initializing FastEmbed model...");
-            
-            let mut options = InitOptions::new(EmbeddingModel::AllMiniLML6V2);
-            options.show_download_progress = true;
+        // Stamped before reading the index, so a change landing in between
+        // makes the file look stale rather than complete
+        let stamp = self.index_stamp().await?;
+        let Some(bytes) = self
+            .read_index()?
+            .as_ref()
+            .map(|index| stamp.encode(index))
+            .transpose()?
+        else {
+            return Ok(());
+        };
 
-            let model = TextEmbedding::try_new(options).map_err(|e| {
-                DomainError::InternalError(format!("Failed to initialize FastEmbed: {}", e))
-            })?;
+        task::spawn_blocking(move || Self::write_index_file(&path, &bytes))
+            .await
+            .map_err(|e| DomainError::InternalError(format!("Task join error: {}", e)))?
+    }
 
-            *model_lock = Some(model);
-            
-            let mut ready_lock = ready_ref.lock().map_err(|e| {
-                DomainError::InternalError(format!("Failed to lock ready state: {}", e))
-            })?;
-            *ready_lock = true;
+    /// Write pending index changes to disk; called when the app exits
+    pub async fn flush_vector_index(&self) -> DomainResult<()> {
+        let pending = self
+            .persist_state
+            .lock()
+            .map(|state| state.unsaved_changes > 0)
+            .unwrap_or(false);
+        if pending {
+            self.persist_index(true).await?;
+        }
+        Ok(())
+    }
 
-            tracing::info!("FastEmbed model initialized successfully");
-            Ok(())
+    /// The current model's stored embeddings, as recorded in the index file
+    async fn index_stamp(&self) -> DomainResult<IndexStamp> {
+        let model = self.current_provider().model();
+        let embeddings = self
+            .embedding_repository
+            .summarize_model(&model.name, &model.version)
+            .await?;
+        Ok(IndexStamp {
+            model_name: model.name,
+            model_version: model.version,
+            embeddings,
         })
-        .await
-        .map_err(|e| DomainError::InternalError(format!("Task join error: {}", e)))?
     }
 
-    fn is_ready(&self) -> bool {
-        *self.ready.lock().unwrap_or_else(|e| e.into_inner())
+    /// Write via a temporary file so a crash never leaves a truncated index
+    fn write_index_file(path: &Path, bytes: &[u8]) -> DomainResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| DomainError::FileStorageError(e.to_string()))?;
+        }
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, bytes)
+            .and_then(|_| std::fs::rename(&temp_path, path))
+            .map_err(|e| DomainError::FileStorageError(format!("Failed to save vector index: {}", e)))
     }
 
-    async fn generate_embedding(&self, text: &str) -> DomainResult<Vec<f32>> {
-        let model_ref = self.model.clone();
-        let text = text.to_string();
+    /// Apply a change to the loaded index and schedule a save
+    async fn update_index<F>(&self, change: F) -> DomainResult<()>
+    where
+        F: FnOnce(&mut HnswIndex) -> Result<(), String>,
+    {
+        self.ensure_index().await?;
+        {
+            let mut guard = self.write_index()?;
+            if let Some(index) = guard.as_mut() {
+                change(index).map_err(DomainError::ValidationError)?;
+            }
+        }
 
-        task::spawn_blocking(move || -> DomainResult<Vec<f32>> {
-            let mut model_lock = model_ref.lock().map_err(|e| {
-                DomainError::InternalError(format!("Failed to lock embedding model: {}", e))
-            })?;
+        if let Ok(mut state) = self.persist_state.lock() {
+            state.unsaved_changes += 1;
+        }
+        self.persist_index(false).await
+    }
 
-            let model = model_lock.as_mut().ok_or_else(|| {
-                DomainError::InternalError("Embedding model not initialized".to_string())
-            })?;
+    /// Nearest notes to a vector, restricted by filter and excluding one note
+    ///
+    /// Each note appears once, with its best matching passage.
+    async fn nearest_notes(
+        &self,
+        vector: &[f32],
+        filter: Option<EmbeddingSearchFilter>,
+        limit: Option<i32>,
+        exclude_id: Option<&str>,
+    ) -> DomainResult<Vec<SimilarNote>> {
+        self.ensure_index().await?;
 
-            let embeddings = model.embed(vec![text], None).map_err(|e| {
-                DomainError::InternalError(format!("Failed to generate embedding: {}", e))
-            })?;
+        let allowed: Option<HashSet<String>> = match filter {
+            Some(filter) if !filter.is_empty() => Some(
+                self.note_repository
+                    .find_all(NoteFindOptions {
+                        workspace_id: filter.workspace_id,
+                        notebook_id: filter.notebook_id,
+                        ..Default::default()
+                    })
+                    .await?
+                    .into_iter()
+                    .map(|note| note.id)
+                    .collect(),
+            ),
+            _ => None,
+        };
 
-            embeddings.first().cloned().ok_or_else(|| {
-                DomainError::InternalError("No embedding generated".to_string())
-            })
+        // Best hit per note, in order of similarity
+        let best: Vec<(String, Option<i32>, f32)> = {
+            let guard = self.read_index()?;
+            let Some(index) = guard.as_ref() else {
+                return Ok(Vec::new());
+            };
+
+            let wanted = limit.map_or(index.len(), |l| l.max(0) as usize);
+            let predicate = |key: &str| {
+                let (note_id, _) = NoteChunk::parse_key(key);
+                exclude_id != Some(note_id)
+                    && allowed.as_ref().is_none_or(|ids| ids.contains(note_id))
+            };
+
+            // Small candidate sets are cheaper and exact to scan directly
+            let candidates = allowed.as_ref().map_or(index.len(), |ids| ids.len());
+            let exact = candidates <= EXACT_SEARCH_THRESHOLD || candidates * 10 <= index.len();
+
+            let mut k = wanted.saturating_mul(PASSAGES_PER_NOTE).min(index.len());
+            loop {
+                let hits: Vec<VectorHit> = if exact {
+                    index.exact_search(vector, k, Some(&predicate))
+                } else {
+                    index.search(vector, k, Some(&predicate))
+                };
+                let exhausted = hits.len() < k || k >= index.len();
+
+                let mut seen = HashSet::new();
+                let best: Vec<(String, Option<i32>, f32)> = hits
+                    .into_iter()
+                    .filter_map(|hit| {
+                        let (note_id, chunk_index) = NoteChunk::parse_key(&hit.id);
+                        seen.insert(note_id.to_string())
+                            .then(|| (note_id.to_string(), chunk_index, hit.similarity))
+                    })
+                    .take(wanted)
+                    .collect();
+
+                // Long notes can crowd out the rest; widen until enough notes
+                if best.len() >= wanted || exhausted {
+                    break best;
+                }
+                k = (k * 2).min(index.len());
+            }
+        };
+
+        let mut similar = Vec::with_capacity(best.len());
+        for (note_id, chunk_index, similarity) in best {
+            // Skip entries whose note was removed since it was indexed
+            let Some(note) = self.note_repository.find_by_id(&note_id).await? else {
+                continue;
+            };
+            if note.is_deleted {
+                continue;
+            }
+            let passage = match chunk_index {
+                Some(chunk_index) => self
+                    .chunk_repository
+                    .find_by_key(&note_id, chunk_index)
+                    .await?
+                    .map(NotePassage::from),
+                None => None,
+            };
+            similar.push(SimilarNote {
+                note_id: note.id,
+                title: note.title,
+                similarity,
+                distance: 1.0 - similarity,
+                passage,
+            });
+        }
+
+        Ok(similar)
+    }
+
+    /// Index keys currently stored for a note (whole-note entry and passages)
+    async fn index_keys(&self, note_id: &str) -> DomainResult<Vec<String>> {
+        let mut keys: Vec<String> = self
+            .chunk_repository
+            .find_by_note(note_id)
+            .await?
+            .iter()
+            .map(NoteChunk::key)
+            .collect();
+        keys.push(note_id.to_string());
+        Ok(keys)
+    }
+
+    /// The provider in use; cloned out so the lock isn't held while embedding
+    fn current_provider(&self) -> Arc<dyn EmbeddingProvider> {
+        self.provider
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn read_index(&self) -> DomainResult<std::sync::RwLockReadGuard<'_, Option<HnswIndex>>> {
+        self.vector_index.read().map_err(|e| {
+            DomainError::InternalError(format!("Failed to lock vector index: {}", e))
         })
-        .await
-        .map_err(|e| DomainError::InternalError(format!("Task join error: {}", e)))?
     }
 
-    async fn generate_embeddings(&self, texts: Vec<String>) -> DomainResult<Vec<Vec<f32>>> {
-        let model_ref = self.model.clone();
+    fn write_index(&self) -> DomainResult<std::sync::RwLockWriteGuard<'_, Option<HnswIndex>>> {
+        self.vector_index.write().map_err(|e| {
+            DomainError::InternalError(format!("Failed to lock vector index: {}", e))
+        })
+    }
+}
 
-        task::spawn_blocking(move || -> DomainResult<Vec<Vec<f32>>> {
-            let mut model_lock = model_ref.lock().map_err(|e| {
-                DomainError::InternalError(format!("Failed to lock embedding model: {}", e))
-            })?;
+#[async_trait]
+impl EmbeddingService for FastEmbedService {
+    async fn initialize(&self) -> DomainResult<()> {
+        self.current_provider().initialize().await
+    }
 
-            let model = model_lock.as_mut().ok_or_else(|| {
-                DomainError::InternalError("Embedding model not initialized".to_string())
-            })?;
+    fn is_ready(&self) -> bool {
+        self.current_provider().is_ready()
+    }
 
-            model.embed(texts, None).map_err(|e| {
-                DomainError::InternalError(format!("Failed to generate embeddings: {}", e))
-            })
+    fn model(&self) -> EmbeddingModelInfo {
+        self.current_provider().model()
+    }
+
+    fn available_models(&self) -> Vec<EmbeddingModelOption> {
+        FastEmbedProvider::supported_models()
+    }
+
+    async fn configure(&self, config: EmbeddingProviderConfig) -> DomainResult<EmbeddingModelInfo> {
+        let provider = create_embedding_provider(&config)?;
+        // Load the model or reach the endpoint before the current one is dropped
+        provider.initialize().await?;
+        let model = provider.model();
+        let previous = std::mem::replace(
+            &mut *self.provider.write().map_err(|e| {
+                DomainError::InternalError(format!("Failed to lock embedding provider: {}", e))
+            })?,
+            provider,
+        )
+        .model();
+
+        // Vectors of different models can't share an index
+        if previous.name != model.name || previous.version != model.version {
+            tracing::info!("Embedding model changed to {} ({})", model.name, model.version);
+            self.rebuild_vector_index().await?;
+        }
+
+        Ok(model)
+    }
+
+    async fn find_stale_note_ids(&self) -> DomainResult<Vec<String>> {
+        let model = self.model();
+        self.embedding_repository
+            .find_stale_note_ids(&model.name, &model.version)
+            .await
+    }
+
+    async fn generate_embedding(&self, text: &str) -> DomainResult<Vec<f32>> {
+        let embeddings = self.generate_embeddings(vec![text.to_string()]).await?;
+
+        embeddings.into_iter().next().ok_or_else(|| {
+            DomainError::InternalError("No embedding generated".to_string())
         })
-        .await
-        .map_err(|e| DomainError::InternalError(format!("Task join error: {}", e)))?
     }
 
-    async fn classify_note(&self, _note_id: &str) -> DomainResult<Vec<ClassificationResult>> {
-        // TODO: Implement classification based on topics
-        Ok(Vec::new())
+    async fn generate_embeddings(&self, texts: Vec<String>) -> DomainResult<Vec<Vec<f32>>> {
+        let provider = self.current_provider();
+        if !provider.is_ready() {
+            return Err(DomainError::InternalError(
+                "Embedding model not initialized".to_string(),
+            ));
+        }
+
+        provider.embed(texts).await
     }
 
-    async fn find_similar_notes(
-        &self,
-        note_id: &str,
-        limit: Option<i32>,
-    ) -> DomainResult<Vec<SimilarNote>> {
-        let note = self.note_repository.find_by_id(note_id).await?
+    async fn classify_note(&self, note_id: &str) -> DomainResult<Vec<ClassificationResult>> {
+        self.note_repository.find_by_id(note_id).await?
             .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
-
-        let source_embedding = note.embedding.ok_or_else(|| {
+        let embedding = self.get_embedding(note_id).await?.ok_or_else(|| {
             DomainError::ValidationError("Note has no embedding".to_string())
         })?;
 
-        let all_notes = self.note_repository.find_all(Default::default()).await?;
-        
-        let mut similarities: Vec<SimilarNote> = all_notes
-            .into_iter()
-            .filter(|n| n.id != note_id && n.embedding.is_some())
-            .map(|n| {
-                let similarity = Self::cosine_similarity(&source_embedding, n.embedding.as_ref().unwrap());
-                SimilarNote {
-                    note_id: n.id,
-                    title: n.title,
-                    similarity,
-                    distance: 1.0 - similarity,
-                }
-            })
+        let topics = self.topic_repository.find_all().await?;
+        let matches = self.classifier.classify(&embedding, &topics);
+
+        // Manual assignments are the user's call; only automatic ones are replaced
+        let existing = self.topic_repository.get_topics_for_note(note_id).await?;
+        let manual: HashSet<&str> = existing
+            .iter()
+            .filter(|t| t.is_manual)
+            .map(|t| t.topic_id.as_str())
             .collect();
 
-        // Sort by similarity descending
-        similarities.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(Ordering::Equal));
+        let assignments: Vec<(String, Option<f32>, Option<bool>)> = existing
+            .iter()
+            .filter(|t| t.is_manual)
+            .map(|t| (t.topic_id.clone(), Some(t.confidence), Some(true)))
+            .chain(
+                matches
+                    .iter()
+                    .filter(|m| !manual.contains(m.topic_id.as_str()))
+                    .map(|m| (m.topic_id.clone(), Some(m.confidence), Some(false))),
+            )
+            .collect();
+        self.topic_repository
+            .set_topics_for_note(note_id, assignments)
+            .await?;
 
-        if let Some(l) = limit {
-            similarities.truncate(l as usize);
+        // Refresh counts of topics gained or lost
+        let affected: HashSet<&str> = existing
+            .iter()
+            .map(|t| t.topic_id.as_str())
+            .chain(matches.iter().map(|m| m.topic_id.as_str()))
+            .collect();
+        for topic_id in affected {
+            self.topic_repository.update_note_count(topic_id).await?;
         }
 
-        Ok(similarities)
+        Ok(matches
+            .into_iter()
+            .map(|m| ClassificationResult {
+                topic_id: m.topic_id,
+                topic_name: m.topic_name,
+                confidence: m.confidence,
+            })
+            .collect())
+    }
+
+    async fn find_similar_notes(
+        &self,
+        note_id: &str,
+        filter: Option<EmbeddingSearchFilter>,
+        limit: Option<i32>,
+    ) -> DomainResult<Vec<SimilarNote>> {
+        let stored = self.get_embedding(note_id).await?;
+
+        self.ensure_index().await?;
+        let indexed = self.read_index()?
+            .as_ref()
+            .and_then(|index| index.get(note_id).map(<[f32]>::to_vec));
+
+        let source_embedding = stored.or(indexed).ok_or_else(|| {
+            DomainError::ValidationError("Note has no embedding".to_string())
+        })?;
+
+        self.nearest_notes(&source_embedding, filter, limit, Some(note_id))
+            .await
     }
 
     async fn semantic_search(
         &self,
         query: &str,
+        filter: Option<EmbeddingSearchFilter>,
         limit: Option<i32>,
     ) -> DomainResult<Vec<SimilarNote>> {
         let query_embedding = self.generate_embedding(query).await?;
 
-        let all_notes = self.note_repository.find_all(Default::default()).await?;
-        
-        let mut similarities: Vec<SimilarNote> = all_notes
-            .into_iter()
-            .filter(|n| n.embedding.is_some())
-            .map(|n| {
-                let similarity = Self::cosine_similarity(&query_embedding, n.embedding.as_ref().unwrap());
-                SimilarNote {
-                    note_id: n.id,
-                    title: n.title,
-                    similarity,
-                    distance: 1.0 - similarity,
-                }
-            })
-            .collect();
+        self.nearest_notes(&query_embedding, filter, limit, None).await
+    }
 
-        // Sort by similarity descending
-        similarities.sort_by(|a, b| b.similarity.partial_cmp(&a.similarity).unwrap_or(Ordering::Equal));
+    async fn store_embedding(&self, note_id: &str, embedding: Vec<f32>) -> DomainResult<()> {
+        self.note_repository.find_by_id(note_id).await?
+            .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
+        let model = self.model();
+        let stored = Embedding::for_note(note_id, embedding.clone(), model.name, model.version)?;
 
-        if let Some(l) = limit {
-            similarities.truncate(l as usize);
-        }
+        // A whole-note embedding supersedes any passages
+        let stale_keys = self.index_keys(note_id).await?;
+        self.chunk_repository.delete_for_note(note_id).await?;
+        self.embedding_repository
+            .replace_for_note(note_id, vec![stored])
+            .await?;
 
-        Ok(similarities)
+        self.update_index(|index| {
+            for key in &stale_keys {
+                index.remove(key);
+            }
+            index.insert(note_id, &embedding)
+        })
+        .await
     }
 
-    async fn store_embedding(&self, note_id: &str, embedding: Vec<f32>) -> DomainResult<()> {
-        let mut note = self.note_repository.find_by_id(note_id).await?
+    async fn store_chunk_embeddings(
+        &self,
+        note_id: &str,
+        chunks: Vec<NoteChunk>,
+    ) -> DomainResult<()> {
+        self.note_repository.find_by_id(note_id).await?
             .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
-        
-        note.embedding = Some(embedding);
-        self.note_repository.save(&note).await?;
-        
-        Ok(())
+
+        let mut vectors = Vec::with_capacity(chunks.len());
+        for chunk in &chunks {
+            if chunk.note_id != note_id {
+                return Err(DomainError::ValidationError(format!(
+                    "Chunk {} does not belong to note {}",
+                    chunk.key(),
+                    note_id
+                )));
+            }
+            let embedding = chunk.embedding.clone().ok_or_else(|| {
+                DomainError::ValidationError(format!("Chunk {} has no embedding", chunk.key()))
+            })?;
+            vectors.push(embedding);
+        }
+
+        let Some(note_embedding) = mean_vector(&vectors) else {
+            if chunks.is_empty() {
+                return self.delete_embedding(note_id).await;
+            }
+            return Err(DomainError::ValidationError(
+                "Chunk embeddings must share the same dimensions".to_string(),
+            ));
+        };
+
+        // The note-level row hashes all passages, so any edit marks it stale
+        let model = self.model();
+        let mut stored = vec![
+            Embedding::for_note(note_id, note_embedding, &model.name, &model.version)?
+                .with_content_hash(Embedding::hash_passages(&chunks)),
+        ];
+        for chunk in &chunks {
+            stored.push(Embedding::for_chunk(chunk, &model.name, &model.version)?);
+        }
+
+        let stale_keys = self.index_keys(note_id).await?;
+        let entries: Vec<(String, Vec<f32>)> =
+            chunks.iter().map(NoteChunk::key).zip(vectors).collect();
+
+        self.chunk_repository.replace_for_note(note_id, chunks).await?;
+        self.embedding_repository
+            .replace_for_note(note_id, stored)
+            .await?;
+
+        self.update_index(|index| {
+            for key in &stale_keys {
+                index.remove(key);
+            }
+            for (key, embedding) in &entries {
+                index.insert(key, embedding)?;
+            }
+            Ok(())
+        })
+        .await
     }
 
     async fn get_embedding(&self, note_id: &str) -> DomainResult<Option<Vec<f32>>> {
-        let note = self.note_repository.find_by_id(note_id).await?
+        self.note_repository.find_by_id(note_id).await?
             .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
-        
-        Ok(note.embedding)
+
+        let model = self.model();
+        Ok(self
+            .embedding_repository
+            .find_for_note(note_id)
+            .await?
+            .filter(|embedding| embedding.is_from_model(&model.name, &model.version))
+            .map(|embedding| embedding.vector))
+    }
+
+    async fn get_embeddings(&self, note_ids: &[String]) -> DomainResult<HashMap<String, Vec<f32>>> {
+        let model = self.model();
+        Ok(self
+            .embedding_repository
+            .find_for_notes(note_ids)
+            .await?
+            .into_iter()
+            .filter(|(_, embedding)| embedding.is_from_model(&model.name, &model.version))
+            .map(|(note_id, embedding)| (note_id, embedding.vector))
+            .collect())
+    }
+
+    async fn get_content_hashes(&self, note_ids: &[String]) -> DomainResult<HashMap<String, String>> {
+        let model = self.model();
+        Ok(self
+            .embedding_repository
+            .find_for_notes(note_ids)
+            .await?
+            .into_iter()
+            .filter(|(_, embedding)| embedding.is_from_model(&model.name, &model.version))
+            .filter_map(|(note_id, embedding)| Some((note_id, embedding.content_hash?)))
+            .collect())
     }
 
     async fn delete_embedding(&self, note_id: &str) -> DomainResult<()> {
-        let mut note = self.note_repository.find_by_id(note_id).await?
+        self.note_repository.find_by_id(note_id).await?
             .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
-        
-        note.embedding = None;
-        self.note_repository.save(&note).await?;
-        
-        Ok(())
+
+        let stale_keys = self.index_keys(note_id).await?;
+        self.chunk_repository.delete_for_note(note_id).await?;
+        self.embedding_repository.delete_for_note(note_id).await?;
+
+        self.update_index(|index| {
+            for key in &stale_keys {
+                index.remove(key);
+            }
+            Ok(())
+        })
+        .await
     }
 
     async fn recompute_centroids(&self) -> DomainResult<()> {
-        // TODO: Recompute topic centroids from embeddings
+        for topic in self.topic_repository.find_all().await? {
+            let records = self
+                .topic_repository
+                .get_notes_for_topic(&topic.id, None)
+                .await?;
+
+            let note_ids: Vec<String> = records.iter().map(|r| r.note_id.clone()).collect();
+            let mut embeddings = self.get_embeddings(&note_ids).await?;
+
+            let members: Vec<CentroidMember> = records
+                .into_iter()
+                .filter_map(|record| {
+                    embeddings.remove(&record.note_id).map(|embedding| CentroidMember {
+                        embedding,
+                        is_manual: record.is_manual,
+                        confidence: record.confidence,
+                    })
+                })
+                .collect();
+
+            // Topics without embedded notes keep their previous centroid
+            if let Some(centroid) = self.classifier.centroid(&members) {
+                self.topic_repository
+                    .update_centroid(&topic.id, Topic::encode_centroid(&centroid))
+                    .await?;
+            }
+        }
+
         Ok(())
     }
 
     async fn get_status(&self) -> DomainResult<EmbeddingStatus> {
-        let all_notes = self.note_repository.find_all(Default::default()).await?;
-        let total_notes = all_notes.len() as i32;
-        let embedded_notes = all_notes.iter().filter(|n| n.embedding.is_some()).count() as i32;
-        
+        let total_notes = self.note_repository.count(Default::default()).await? as i32;
+        let model = self.model();
+        let embedded_notes = self
+            .embedding_repository
+            .count_embedded_notes(&model.name, &model.version)
+            .await? as i32;
+
         Ok(EmbeddingStatus {
             ready: self.is_ready(),
             total_notes,
@@ -259,3 +847,401 @@ impl EmbeddingService for FastEmbedService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::outbound::persistence::{
+        DieselEmbeddingRepository, DieselNoteChunkRepository, DieselNoteRepository,
+        DieselTopicRepository, DieselWorkspaceRepository,
+    };
+    use crate::domain::entities::{Note, Workspace};
+    use crate::domain::ports::outbound::{TopicAssignmentOptions, WorkspaceRepository};
+    use crate::domain::services::NoteChunker;
+    use crate::infrastructure::run_migrations;
+    use crate::shared::database::{create_pool, DatabaseConfig};
+
+    struct Fixture {
+        repo: Arc<DieselNoteRepository>,
+        chunks: Arc<DieselNoteChunkRepository>,
+        embeddings: Arc<DieselEmbeddingRepository>,
+        topics: Arc<DieselTopicRepository>,
+        workspace: Workspace,
+    }
+
+    impl Fixture {
+        fn service(&self) -> FastEmbedService {
+            FastEmbedService::new(
+                self.repo.clone(),
+                self.chunks.clone(),
+                self.embeddings.clone(),
+                self.topics.clone(),
+            )
+        }
+    }
+
+    async fn setup() -> Fixture {
+        let pool = Arc::new(create_pool(DatabaseConfig::in_memory()).unwrap());
+        run_migrations(&pool).unwrap();
+
+        let workspace = Workspace::new("Vectors", "/tmp/vectors").unwrap();
+        DieselWorkspaceRepository::new(pool.clone())
+            .save(&workspace)
+            .await
+            .unwrap();
+
+        Fixture {
+            repo: Arc::new(DieselNoteRepository::new(pool.clone())),
+            chunks: Arc::new(DieselNoteChunkRepository::new(pool.clone())),
+            embeddings: Arc::new(DieselEmbeddingRepository::new(pool.clone())),
+            topics: Arc::new(DieselTopicRepository::new(pool)),
+            workspace,
+        }
+    }
+
+    async fn embedded_note(
+        service: &FastEmbedService,
+        repo: &DieselNoteRepository,
+        title: &str,
+        workspace_id: Option<String>,
+        embedding: Vec<f32>,
+    ) -> Note {
+        let note = Note::new(title, workspace_id).unwrap();
+        repo.save(&note).await.unwrap();
+        service.store_embedding(&note.id, embedding).await.unwrap();
+        note
+    }
+
+    fn titles(similar: &[SimilarNote]) -> Vec<&str> {
+        similar.iter().map(|s| s.title.as_str()).collect()
+    }
+
+    /// An OpenAI-compatible endpoint on a free local port that embeds
+    /// every input as [1, 0]
+    async fn embedding_endpoint() -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    // Read the whole request before answering
+                    let mut request = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    loop {
+                        let read = socket.read(&mut chunk).await.unwrap_or(0);
+                        if read == 0 {
+                            return;
+                        }
+                        request.extend_from_slice(&chunk[..read]);
+                        let text = String::from_utf8_lossy(&request);
+                        let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                            continue;
+                        };
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break;
+                        }
+                    }
+
+                    let body = r#"{"data":[{"index":0,"embedding":[1.0,0.0]}]}"#;
+                    let reply = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = socket.write_all(reply.as_bytes()).await;
+                });
+            }
+        });
+        base_url
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_notes_uses_index_and_filters() {
+        let fx = setup().await;
+        let (repo, workspace) = (&fx.repo, &fx.workspace);
+        let service = fx.service();
+        let ws = Some(workspace.id.clone());
+
+        let source = embedded_note(&service, repo, "Source", ws.clone(), vec![1.0, 0.0, 0.0]).await;
+        embedded_note(&service, repo, "Close", ws.clone(), vec![0.9, 0.1, 0.0]).await;
+        embedded_note(&service, repo, "Far", ws.clone(), vec![0.0, 0.0, 1.0]).await;
+        embedded_note(&service, repo, "Elsewhere", None, vec![0.95, 0.05, 0.0]).await;
+
+        let all = service.find_similar_notes(&source.id, None, Some(2)).await.unwrap();
+        assert_eq!(titles(&all), vec!["Elsewhere", "Close"]);
+        assert!(all[0].similarity > all[1].similarity);
+
+        let scoped = EmbeddingSearchFilter {
+            workspace_id: ws,
+            notebook_id: None,
+        };
+        let in_workspace = service
+            .find_similar_notes(&source.id, Some(scoped), None)
+            .await
+            .unwrap();
+        assert_eq!(titles(&in_workspace), vec!["Close", "Far"]);
+    }
+
+    #[tokio::test]
+    async fn test_deleted_embeddings_leave_the_index() {
+        let fx = setup().await;
+        let repo = &fx.repo;
+        let service = fx.service();
+
+        let source = embedded_note(&service, repo, "Source", None, vec![1.0, 0.0]).await;
+        let gone = embedded_note(&service, repo, "Gone", None, vec![1.0, 0.1]).await;
+        embedded_note(&service, repo, "Kept", None, vec![0.5, 0.5]).await;
+
+        service.delete_embedding(&gone.id).await.unwrap();
+
+        let similar = service.find_similar_notes(&source.id, None, None).await.unwrap();
+        assert_eq!(titles(&similar), vec!["Kept"]);
+    }
+
+    #[tokio::test]
+    async fn test_index_is_rebuilt_when_file_is_missing_or_corrupt() {
+        let fx = setup().await;
+        let repo = &fx.repo;
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("embeddings.hnsw");
+
+        let writer = fx.service();
+        let source = embedded_note(&writer, repo, "Source", None, vec![1.0, 0.0]).await;
+        embedded_note(&writer, repo, "Neighbour", None, vec![0.9, 0.1]).await;
+
+        // Missing file: built from the stored embeddings and saved
+        let service = fx.service().with_index_path(&path);
+        let similar = service.find_similar_notes(&source.id, None, None).await.unwrap();
+        assert_eq!(titles(&similar), vec!["Neighbour"]);
+        let (stamp, saved) = IndexStamp::decode(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved.len(), 2);
+        assert_eq!(stamp.embeddings.count, 2);
+
+        // Corrupt file: rebuilt and overwritten
+        std::fs::write(&path, b"not an index").unwrap();
+        let service = fx.service().with_index_path(&path);
+        let similar = service.find_similar_notes(&source.id, None, None).await.unwrap();
+        assert_eq!(titles(&similar), vec!["Neighbour"]);
+        assert!(IndexStamp::decode(&std::fs::read(&path).unwrap()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_index_file_behind_the_database_is_rebuilt() {
+        let fx = setup().await;
+        let repo = &fx.repo;
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("embeddings.hnsw");
+
+        let writer = fx.service().with_index_path(&path);
+        let source = embedded_note(&writer, repo, "Source", None, vec![1.0, 0.0]).await;
+        writer.flush_vector_index().await.unwrap();
+
+        // Stored after the last save, as if the app had been killed
+        embedded_note(&fx.service(), repo, "Unsaved", None, vec![0.9, 0.1]).await;
+
+        let service = fx.service().with_index_path(&path);
+        let similar = service.find_similar_notes(&source.id, None, None).await.unwrap();
+        assert_eq!(titles(&similar), vec!["Unsaved"]);
+        let (stamp, saved) = IndexStamp::decode(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved.len(), 2);
+        assert_eq!(stamp, service.index_stamp().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_chunk_embeddings_return_best_passage_once_per_note() {
+        let fx = setup().await;
+        let repo = &fx.repo;
+        let service = fx.service();
+
+        let markdown = "# Guide\n\nIntro text.\n\n## Setup\n\nInstall it.\n\n## Usage\n\nRun it.\n";
+        let long = Note::new("Long", None).unwrap();
+        repo.save(&long).await.unwrap();
+        let passages = NoteChunker::default().chunk(markdown);
+        assert_eq!(passages.len(), 3);
+
+        let vectors = [vec![0.0, 0.0, 1.0], vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]];
+        let note_chunks: Vec<NoteChunk> = passages
+            .iter()
+            .zip(vectors)
+            .map(|(passage, v)| NoteChunk::new(&long.id, passage).unwrap().with_embedding(v))
+            .collect();
+        service.store_chunk_embeddings(&long.id, note_chunks).await.unwrap();
+        embedded_note(&service, repo, "Other", None, vec![0.6, 0.6, 0.0]).await;
+
+        let similar = service.nearest_notes(&[0.0, 1.0, 0.0], None, None, None).await.unwrap();
+        assert_eq!(titles(&similar), vec!["Long", "Other"]);
+        let passage = similar[0].passage.as_ref().unwrap();
+        assert_eq!(passage.chunk_index, 2);
+        assert_eq!(passage.heading_path, vec!["Guide", "Usage"]);
+        assert_eq!(
+            &markdown[passage.start_offset as usize..passage.end_offset as usize],
+            "## Usage\n\nRun it."
+        );
+        assert!(similar[1].passage.is_none());
+
+        // The note's own embedding is the mean of its passages
+        let mean = service.get_embedding(&long.id).await.unwrap().unwrap();
+        let expected = 1.0 / 3f32.sqrt();
+        assert!(mean.iter().all(|v| (v - expected).abs() < 1e-6));
+
+        // Stored with the model that produced them, outside the notes table
+        let stored = fx.embeddings.find_chunks_for_note(&long.id).await.unwrap();
+        assert_eq!(stored.len(), 3);
+        let model = service.model();
+        assert!(stored.iter().all(|e| e.is_from_model(&model.name, &model.version)));
+        assert_eq!(stored[2].content_hash, Some(Embedding::hash_content("## Usage\n\nRun it.")));
+
+        // The note-level hash covers all passages, so any edit shows up
+        let chunks: Vec<NoteChunk> =
+            passages.iter().map(|passage| NoteChunk::new(&long.id, passage).unwrap()).collect();
+        let hashes = service.get_content_hashes(std::slice::from_ref(&long.id)).await.unwrap();
+        assert_eq!(hashes.get(&long.id), Some(&Embedding::hash_passages(&chunks)));
+
+        // Re-chunking drops passages that no longer exist
+        let shorter = NoteChunker::default().chunk("Just one line.");
+        let note_chunks = vec![NoteChunk::new(&long.id, &shorter[0])
+            .unwrap()
+            .with_embedding(vec![1.0, 0.0, 0.0])];
+        service.store_chunk_embeddings(&long.id, note_chunks).await.unwrap();
+
+        let similar = service.nearest_notes(&[0.0, 1.0, 0.0], None, Some(1), None).await.unwrap();
+        assert_eq!(titles(&similar), vec!["Other"]);
+        let similar = service.nearest_notes(&[1.0, 0.0, 0.0], None, Some(1), None).await.unwrap();
+        assert_eq!(similar[0].passage.as_ref().unwrap().text, "Just one line.");
+    }
+
+    #[tokio::test]
+    async fn test_changing_model_ignores_and_reports_stale_embeddings() {
+        let fx = setup().await;
+        let repo = &fx.repo;
+        let service = fx.service();
+
+        let old = embedded_note(&service, repo, "Old", None, vec![1.0, 0.0]).await;
+        let redone = embedded_note(&service, repo, "Redone", None, vec![0.9, 0.1]).await;
+        assert!(service.find_stale_note_ids().await.unwrap().is_empty());
+
+        // The endpoint is probed before it takes over
+        let model = service
+            .configure(EmbeddingProviderConfig::OpenAiCompatible {
+                base_url: embedding_endpoint().await,
+                model: "nomic-embed-text".to_string(),
+                api_key: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(model.name, "nomic-embed-text");
+        assert!(service.is_ready());
+
+        let mut stale = service.find_stale_note_ids().await.unwrap();
+        stale.sort();
+        let mut expected = vec![old.id.clone(), redone.id.clone()];
+        expected.sort();
+        assert_eq!(stale, expected);
+        assert!(service.get_embedding(&old.id).await.unwrap().is_none());
+        assert_eq!(service.get_status().await.unwrap().embedded_notes, 0);
+        assert!(service
+            .nearest_notes(&[1.0, 0.0], None, None, None)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Re-embedded notes count again; the rest stay stale
+        service.store_embedding(&redone.id, vec![0.0, 1.0]).await.unwrap();
+        assert_eq!(service.find_stale_note_ids().await.unwrap(), vec![old.id]);
+        let similar = service.nearest_notes(&[1.0, 0.0], None, None, None).await.unwrap();
+        assert_eq!(titles(&similar), vec!["Redone"]);
+
+        let invalid = service
+            .configure(EmbeddingProviderConfig::OpenAiCompatible {
+                base_url: "https://example.com".to_string(),
+                model: "remote".to_string(),
+                api_key: None,
+            })
+            .await;
+        assert!(matches!(invalid, Err(DomainError::ValidationError(_))));
+        assert_eq!(service.model().name, "nomic-embed-text");
+    }
+
+    #[tokio::test]
+    async fn test_classify_note_against_weighted_centroids() {
+        let fx = setup().await;
+        let repo = &fx.repo;
+        let service = fx.service().with_classification_config(ClassificationConfig {
+            threshold: 0.3,
+            top_k: 1,
+            manual_weight: 3.0,
+        });
+
+        let mut topics = Vec::new();
+        for name in ["Rust", "Cooking", "Travel"] {
+            let topic = Topic::new(name).unwrap();
+            fx.topics.save(&topic).await.unwrap();
+            topics.push(topic);
+        }
+        let (rust, cooking, travel) = (&topics[0], &topics[1], &topics[2]);
+
+        // A manual member outweighs a low-confidence automatic one
+        let manual = embedded_note(&service, repo, "Ownership", None, vec![1.0, 0.0, 0.0]).await;
+        let auto = embedded_note(&service, repo, "Recipes?", None, vec![0.0, 1.0, 0.0]).await;
+        let baking = embedded_note(&service, repo, "Baking", None, vec![0.0, 1.0, 0.0]).await;
+        let manual_topic = Some(TopicAssignmentOptions {
+            confidence: Some(1.0),
+            is_manual: Some(true),
+        });
+        let auto_topic = Some(TopicAssignmentOptions {
+            confidence: Some(0.2),
+            is_manual: Some(false),
+        });
+        fx.topics.assign_to_note(&manual.id, &rust.id, manual_topic.clone()).await.unwrap();
+        fx.topics.assign_to_note(&auto.id, &rust.id, auto_topic).await.unwrap();
+        fx.topics.assign_to_note(&baking.id, &cooking.id, manual_topic.clone()).await.unwrap();
+
+        service.recompute_centroids().await.unwrap();
+        let topics = fx.topics.find_all().await.unwrap();
+        let centroid = |id: &str| {
+            topics.iter().find(|t| t.id == id).unwrap().centroid_vector()
+        };
+        let rust_centroid = centroid(rust.id.as_str()).unwrap();
+        assert!(rust_centroid[0] > 0.99 && rust_centroid[1] > 0.0);
+        assert!(centroid(travel.id.as_str()).is_none());
+
+        // Cooking also clears the threshold, but only the best match is kept;
+        // the manual Travel assignment survives
+        let note = embedded_note(&service, repo, "Lifetimes", None, vec![0.9, 0.3, 0.0]).await;
+        fx.topics.assign_to_note(&note.id, &travel.id, manual_topic).await.unwrap();
+        let results = service.classify_note(&note.id).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].topic_id, rust.id);
+
+        let mut assigned: Vec<(String, bool)> = fx
+            .topics
+            .get_topics_for_note(&note.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.topic_name, t.is_manual))
+            .collect();
+        assigned.sort();
+        assert_eq!(
+            assigned,
+            vec![("Rust".to_string(), false), ("Travel".to_string(), true)]
+        );
+
+        // Nothing close enough: automatic topics are cleared
+        service.store_embedding(&note.id, vec![0.0, 0.0, 1.0]).await.unwrap();
+        assert!(service.classify_note(&note.id).await.unwrap().is_empty());
+        let assigned = fx.topics.get_topics_for_note(&note.id).await.unwrap();
+        assert_eq!(assigned.len(), 1);
+        assert!(assigned[0].is_manual);
+    }
+}
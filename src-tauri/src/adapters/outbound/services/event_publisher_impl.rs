@@ -8,7 +8,7 @@ use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Emitter};
 use tokio::sync::broadcast;
 
-use crate::domain::ports::outbound::{DomainEvent, EventHandler, EventPublisher};
+use crate::domain::ports::outbound::{DomainEvent, EventHandler, EventPublisher, LagHandler};
 
 /// In-process event publisher using broadcast channels
 pub struct TokioEventPublisher {
@@ -56,6 +56,31 @@ impl TokioEventPublisher {
             .clone()
     }
 
+    /// Hand received events to a handler until the channel closes
+    ///
+    /// A receiver that falls more than the channel capacity behind skips the
+    /// oldest events; it keeps listening and reports how many it missed.
+    fn forward(
+        mut rx: broadcast::Receiver<DomainEvent>,
+        handler: EventHandler,
+        on_lagged: Option<LagHandler>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => handler(event),
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        tracing::warn!("Event subscriber fell behind and missed {} events", missed);
+                        if let Some(on_lagged) = &on_lagged {
+                            on_lagged(missed);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
     /// Emit event to Tauri frontend if app_handle is available
     fn emit_to_frontend(&self, event: &DomainEvent) {
         if let Some(app_handle) = &self.app_handle {
@@ -111,13 +136,22 @@ impl EventPublisher for TokioEventPublisher {
 
     fn subscribe(&self, event_type: &str, handler: EventHandler) -> Box<dyn Fn() + Send + Sync> {
         let type_tx = self.get_or_create_channel(event_type);
-        let mut rx = type_tx.subscribe();
+        let handle = Self::forward(type_tx.subscribe(), handler, None);
 
-        let handle = tokio::spawn(async move {
-            while let Ok(event) = rx.recv().await {
-                handler(event);
-            }
-        });
+        // Return unsubscribe function
+        Box::new(move || {
+            handle.abort();
+        })
+    }
+
+    fn subscribe_with_lag(
+        &self,
+        event_type: &str,
+        handler: EventHandler,
+        on_lagged: LagHandler,
+    ) -> Box<dyn Fn() + Send + Sync> {
+        let type_tx = self.get_or_create_channel(event_type);
+        let handle = Self::forward(type_tx.subscribe(), handler, Some(on_lagged));
 
         // Return unsubscribe function
         Box::new(move || {
@@ -126,13 +160,7 @@ impl EventPublisher for TokioEventPublisher {
     }
 
     fn subscribe_all(&self, handler: EventHandler) -> Box<dyn Fn() + Send + Sync> {
-        let mut rx = self.all_events_tx.subscribe();
-
-        let handle = tokio::spawn(async move {
-            while let Ok(event) = rx.recv().await {
-                handler(event);
-            }
-        });
+        let handle = Self::forward(self.all_events_tx.subscribe(), handler, None);
 
         // Return unsubscribe function
         Box::new(move || {
@@ -293,4 +321,47 @@ mod tests {
         assert_eq!(note_counter.load(Ordering::SeqCst), 1);
         assert_eq!(tag_counter.load(Ordering::SeqCst), 1);
     }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_keeps_receiving() {
+        let publisher = TokioEventPublisher::new();
+        let received = Arc::new(AtomicUsize::new(0));
+        let missed = Arc::new(AtomicUsize::new(0));
+        let received_clone = received.clone();
+        let missed_clone = missed.clone();
+
+        let _unsubscribe = publisher.subscribe_with_lag(
+            "tag:created",
+            Arc::new(move |_event| {
+                received_clone.fetch_add(1, Ordering::SeqCst);
+            }),
+            Arc::new(move |count| {
+                missed_clone.fetch_add(count as usize, Ordering::SeqCst);
+            }),
+        );
+
+        sleep(Duration::from_millis(10)).await;
+
+        // More events than the channel holds, before the subscriber gets to run
+        let tag_created = || DomainEvent::TagCreated {
+            timestamp: Utc::now(),
+            id: "tag-1".to_string(),
+            name: "Test Tag".to_string(),
+        };
+        for _ in 0..1100 {
+            publisher.publish(tag_created());
+        }
+        sleep(Duration::from_millis(50)).await;
+
+        let (missed_count, received_count) = (
+            missed.load(Ordering::SeqCst),
+            received.load(Ordering::SeqCst),
+        );
+        assert!(missed_count > 0);
+        assert_eq!(missed_count + received_count, 1100);
+
+        publisher.publish(tag_created());
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(received.load(Ordering::SeqCst), received_count + 1);
+    }
 }
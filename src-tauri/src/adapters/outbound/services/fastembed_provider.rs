@@ -0,0 +1,276 @@
+//! FastEmbed Embedding Provider
+//!
+//! Runs an ONNX embedding model in-process with FastEmbed: either a model
+//! from the FastEmbed catalogue, downloaded on first use, or model files in a
+//! local directory for machines without network access.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use fastembed::{
+    EmbeddingModel, InitOptionsUserDefined, Pooling, TextEmbedding, TextInitOptions,
+    TokenizerFiles, UserDefinedEmbeddingModel,
+};
+use tokio::task;
+
+use crate::domain::{
+    errors::{DomainError, DomainResult},
+    ports::outbound::{EmbeddingModelInfo, EmbeddingModelOption, EmbeddingProvider},
+};
+
+/// Version recorded for FastEmbed models, bumped if embedding output changes
+const FASTEMBED_VERSION: &str = "fastembed-5";
+
+/// ONNX model file a local model directory must contain
+const MODEL_FILE: &str = "model.onnx";
+
+/// Tokenizer files a local model directory must contain, as exported by Hugging Face
+const TOKENIZER_FILES: [&str; 4] = [
+    "tokenizer.json",
+    "config.json",
+    "special_tokens_map.json",
+    "tokenizer_config.json",
+];
+
+/// Where the model is loaded from
+#[derive(Debug, Clone)]
+enum ModelSource {
+    Catalogue(EmbeddingModel),
+    Directory(PathBuf),
+}
+
+/// Embedding provider running a FastEmbed model in-process
+pub struct FastEmbedProvider {
+    source: ModelSource,
+    name: String,
+    version: String,
+    dimensions: Mutex<Option<usize>>,
+    model: Arc<Mutex<Option<TextEmbedding>>>,
+    /// Kept apart from the model lock, which is held while the model loads
+    ready: Arc<AtomicBool>,
+}
+
+impl Default for FastEmbedProvider {
+    fn default() -> Self {
+        Self::from_catalogue(EmbeddingModel::AllMiniLML6V2)
+    }
+}
+
+impl FastEmbedProvider {
+    /// Use a catalogue model by its ID, e.g. `BGESmallENV15`
+    pub fn from_model_id(model_id: &str) -> DomainResult<Self> {
+        EmbeddingModel::from_str(model_id.trim())
+            .map(Self::from_catalogue)
+            .map_err(DomainError::ConfigurationError)
+    }
+
+    /// Use the model files in a directory
+    ///
+    /// The model file's size and modification time stand in for a version, so
+    /// swapping the files marks existing embeddings as stale.
+    pub fn from_directory(path: impl Into<PathBuf>) -> DomainResult<Self> {
+        let path = path.into();
+        for file in std::iter::once(MODEL_FILE).chain(TOKENIZER_FILES) {
+            if !path.join(file).is_file() {
+                return Err(DomainError::ConfigurationError(format!(
+                    "Model directory {} has no {}",
+                    path.display(),
+                    file
+                )));
+            }
+        }
+
+        let metadata = std::fs::metadata(path.join(MODEL_FILE))
+            .map_err(|e| DomainError::FileStorageError(e.to_string()))?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let directory_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        Ok(Self {
+            name: format!("local:{}", directory_name),
+            version: format!("{}-{}", metadata.len(), modified),
+            dimensions: Mutex::new(None),
+            model: Arc::new(Mutex::new(None)),
+            ready: Arc::new(AtomicBool::new(false)),
+            source: ModelSource::Directory(path),
+        })
+    }
+
+    /// Models in the FastEmbed catalogue
+    pub fn supported_models() -> Vec<EmbeddingModelOption> {
+        TextEmbedding::list_supported_models()
+            .into_iter()
+            .map(|info| EmbeddingModelOption {
+                id: info.model.to_string(),
+                description: info.description,
+                dimensions: info.dim,
+            })
+            .collect()
+    }
+
+    fn from_catalogue(model: EmbeddingModel) -> Self {
+        let dimensions = TextEmbedding::get_model_info(&model)
+            .ok()
+            .map(|info| info.dim);
+
+        Self {
+            name: model.to_string(),
+            version: FASTEMBED_VERSION.to_string(),
+            dimensions: Mutex::new(dimensions),
+            model: Arc::new(Mutex::new(None)),
+            ready: Arc::new(AtomicBool::new(false)),
+            source: ModelSource::Catalogue(model),
+        }
+    }
+
+    /// Load the model (blocking: may download or read large files)
+    fn load(source: ModelSource) -> DomainResult<TextEmbedding> {
+        match source {
+            ModelSource::Catalogue(model) => {
+                let options = TextInitOptions::new(model).with_show_download_progress(true);
+                TextEmbedding::try_new(options)
+            }
+            ModelSource::Directory(path) => {
+                let read = |file: &str| {
+                    std::fs::read(path.join(file)).map_err(|e| {
+                        DomainError::FileStorageError(format!("Failed to read {}: {}", file, e))
+                    })
+                };
+                let tokenizer_files = TokenizerFiles {
+                    tokenizer_file: read(TOKENIZER_FILES[0])?,
+                    config_file: read(TOKENIZER_FILES[1])?,
+                    special_tokens_map_file: read(TOKENIZER_FILES[2])?,
+                    tokenizer_config_file: read(TOKENIZER_FILES[3])?,
+                };
+                let model = UserDefinedEmbeddingModel::new(read(MODEL_FILE)?, tokenizer_files)
+                    .with_pooling(Pooling::Mean);
+                TextEmbedding::try_new_from_user_defined(model, InitOptionsUserDefined::new())
+            }
+        }
+        .map_err(|e| DomainError::InternalError(format!("Failed to initialize FastEmbed: {}", e)))
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for FastEmbedProvider {
+    fn model(&self) -> EmbeddingModelInfo {
+        EmbeddingModelInfo {
+            name: self.name.clone(),
+            version: self.version.clone(),
+            dimensions: *self.dimensions.lock().unwrap_or_else(|e| e.into_inner()),
+        }
+    }
+
+    async fn initialize(&self) -> DomainResult<()> {
+        let model_ref = self.model.clone();
+        let ready_ref = self.ready.clone();
+        let source = self.source.clone();
+        let name = self.name.clone();
+
+        task::spawn_blocking(move || -> DomainResult<()> {
+            let mut model_lock = model_ref.lock().map_err(|e| {
+                DomainError::InternalError(format!("Failed to lock embedding model: {}", e))
+            })?;
+
+            if model_lock.is_some() {
+                return Ok(());
+            }
+
+            tracing::info!("Initializing FastEmbed model {}...", name);
+            *model_lock = Some(Self::load(source)?);
+            ready_ref.store(true, Ordering::SeqCst);
+            tracing::info!("FastEmbed model initialized successfully");
+            Ok(())
+        })
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Task join error: {}", e)))?
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> DomainResult<Vec<Vec<f32>>> {
+        let model_ref = self.model.clone();
+
+        let vectors = task::spawn_blocking(move || -> DomainResult<Vec<Vec<f32>>> {
+            let mut model_lock = model_ref.lock().map_err(|e| {
+                DomainError::InternalError(format!("Failed to lock embedding model: {}", e))
+            })?;
+
+            let model = model_lock.as_mut().ok_or_else(|| {
+                DomainError::InternalError("Embedding model not initialized".to_string())
+            })?;
+
+            model.embed(texts, None).map_err(|e| {
+                DomainError::InternalError(format!("Failed to generate embeddings: {}", e))
+            })
+        })
+        .await
+        .map_err(|e| DomainError::InternalError(format!("Task join error: {}", e)))??;
+
+        // Directory models only reveal their size once they have embedded something
+        if let (Some(first), Ok(mut dimensions)) = (vectors.first(), self.dimensions.lock()) {
+            *dimensions = Some(first.len());
+        }
+        Ok(vectors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogue_model_identity() {
+        let provider = FastEmbedProvider::from_model_id("bgesmallenv15").unwrap();
+        assert_eq!(
+            provider.model(),
+            EmbeddingModelInfo {
+                name: "BGESmallENV15".to_string(),
+                version: FASTEMBED_VERSION.to_string(),
+                dimensions: Some(384),
+            }
+        );
+        assert!(!provider.is_ready());
+
+        assert!(matches!(
+            FastEmbedProvider::from_model_id("not-a-model"),
+            Err(DomainError::ConfigurationError(_))
+        ));
+        assert!(FastEmbedProvider::supported_models()
+            .iter()
+            .any(|option| option.id == "AllMiniLML6V2"));
+    }
+
+    #[test]
+    fn test_directory_model_requires_tokenizer_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join(MODEL_FILE), b"onnx").unwrap();
+
+        let missing = FastEmbedProvider::from_directory(dir.path());
+        assert!(
+            matches!(missing, Err(DomainError::ConfigurationError(ref m)) if m.contains("tokenizer.json"))
+        );
+
+        for file in TOKENIZER_FILES {
+            std::fs::write(dir.path().join(file), b"{}").unwrap();
+        }
+        let provider = FastEmbedProvider::from_directory(dir.path()).unwrap();
+        let model = provider.model();
+        let directory_name = dir.path().file_name().unwrap().to_string_lossy();
+        assert_eq!(model.name, format!("local:{}", directory_name));
+        assert!(model.version.starts_with("4-"));
+        assert_eq!(model.dimensions, None);
+    }
+}
@@ -228,6 +228,29 @@ impl GitService for Git2Service {
         .map_err(|e| DomainError::ExternalServiceError(format!("Task join error: {}", e)))?
     }
 
+    async fn stage_rename(&self, path: &str, from: &str, to: &str) -> DomainResult<GitOperationResult> {
+        let path = path.to_string();
+        let from = from.to_string();
+        let to = to.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let repo = Self::get_repo(&path)?;
+            let mut index = repo.index().map_err(Self::map_git_error)?;
+
+            // Untracked files stay untracked
+            if index.get_path(Path::new(&from), 0).is_none() {
+                return Ok(GitOperationResult { success: true, message: Some("File not tracked".to_string()), error: None });
+            }
+
+            index.remove_path(Path::new(&from)).map_err(Self::map_git_error)?;
+            index.add_path(Path::new(&to)).map_err(Self::map_git_error)?;
+            index.write().map_err(Self::map_git_error)?;
+            Ok(GitOperationResult { success: true, message: Some(format!("Renamed {} to {}", from, to)), error: None })
+        })
+        .await
+        .map_err(|e| DomainError::ExternalServiceError(format!("Task join error: {}", e)))?
+    }
+
     async fn commit(&self, path: &str, message: &str) -> DomainResult<GitOperationResult> {
         let path = path.to_string();
         let message = message.to_string();
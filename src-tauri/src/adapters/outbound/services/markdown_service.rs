@@ -11,6 +11,7 @@ use std::collections::HashMap;
 use crate::domain::{
     errors::DomainResult,
     ports::outbound::{MarkdownLink, MarkdownMetadata, MarkdownProcessor, ParsedMarkdown},
+    services::{Frontmatter, YamlValue, TAG_KEYS},
 };
 
 /// Markdown processor implementation using pulldown-cmark
@@ -23,7 +24,6 @@ use crate::domain::{
 /// - Title extraction
 pub struct PulldownMarkdownService {
     wiki_link_regex: Regex,
-    frontmatter_regex: Regex,
 }
 
 impl PulldownMarkdownService {
@@ -31,67 +31,28 @@ impl PulldownMarkdownService {
         Self {
             // Matches [[link]] or [[link|display text]]
             wiki_link_regex: Regex::new(r"\[\[([^\]|]+)(?:\|([^\]]+))?\]\]").unwrap(),
-            // Matches YAML frontmatter (--- ... ---)
-            frontmatter_regex: Regex::new(r"^---\s*\n(.*?)\n---\s*\n").unwrap(),
         }
     }
 
-    /// Parse YAML frontmatter from content
-    fn parse_yaml_frontmatter(&self, yaml: &str) -> DomainResult<MarkdownMetadata> {
+    /// Read the fields the app knows from parsed frontmatter
+    fn metadata_from(frontmatter: &Frontmatter) -> MarkdownMetadata {
+        let text = |key: &str| frontmatter.get(key).and_then(YamlValue::as_text);
         let mut metadata = MarkdownMetadata {
-            title: None,
-            tags: None,
-            created: None,
-            modified: None,
+            title: text("title"),
+            tags: frontmatter
+                .get_any(&TAG_KEYS)
+                .map(|(_, value)| value.as_string_list()),
+            created: text("created"),
+            modified: text("modified").or_else(|| text("updated")),
             extra: HashMap::new(),
         };
 
-        // Simple YAML parser for common fields
-        for line in yaml.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
-
-            if let Some((key, value)) = line.split_once(':') {
-                let key = key.trim();
-                let value = value.trim().trim_matches('"').trim_matches('\'');
-
-                match key {
-                    "title" => metadata.title = Some(value.to_string()),
-                    "created" => metadata.created = Some(value.to_string()),
-                    "modified" | "updated" => metadata.modified = Some(value.to_string()),
-                    "tags" => {
-                        // Handle both array format and comma-separated
-                        if value.starts_with('[') && value.ends_with(']') {
-                            let tags_str = value.trim_matches(|c| c == '[' || c == ']');
-                            let tags: Vec<String> = tags_str
-                                .split(',')
-                                .map(|t| t.trim().trim_matches('"').trim_matches('\'').to_string())
-                                .filter(|t| !t.is_empty())
-                                .collect();
-                            metadata.tags = Some(tags);
-                        } else {
-                            let tags: Vec<String> = value
-                                .split(',')
-                                .map(|t| t.trim().to_string())
-                                .filter(|t| !t.is_empty())
-                                .collect();
-                            metadata.tags = Some(tags);
-                        }
-                    }
-                    _ => {
-                        // Store other fields in extra
-                        metadata.extra.insert(
-                            key.to_string(),
-                            serde_json::Value::String(value.to_string()),
-                        );
-                    }
-                }
+        for (key, value) in frontmatter.entries() {
+            if !matches!(key, "title" | "tags" | "tag" | "created" | "modified" | "updated") {
+                metadata.extra.insert(key.to_string(), value.to_json());
             }
         }
-
-        Ok(metadata)
+        metadata
     }
 
     /// Remove HTML tags from text
@@ -216,27 +177,23 @@ impl MarkdownProcessor for PulldownMarkdownService {
     }
 
     fn parse_frontmatter(&self, markdown: &str) -> DomainResult<ParsedMarkdown> {
-        if let Some(captures) = self.frontmatter_regex.captures(markdown) {
-            let yaml = captures.get(1).map(|m| m.as_str()).unwrap_or("");
-            let metadata = self.parse_yaml_frontmatter(yaml)?;
-
-            // Remove frontmatter from content
-            let content = self.frontmatter_regex.replace(markdown, "").to_string();
-
-            Ok(ParsedMarkdown { content, metadata })
-        } else {
+        let (yaml, content) = Frontmatter::split(markdown);
+        let metadata = match yaml {
+            Some(yaml) => Self::metadata_from(&Frontmatter::parse(yaml)?),
             // No frontmatter found
-            Ok(ParsedMarkdown {
-                content: markdown.to_string(),
-                metadata: MarkdownMetadata {
-                    title: None,
-                    tags: None,
-                    created: None,
-                    modified: None,
-                    extra: HashMap::new(),
-                },
-            })
-        }
+            None => MarkdownMetadata {
+                title: None,
+                tags: None,
+                created: None,
+                modified: None,
+                extra: HashMap::new(),
+            },
+        };
+
+        Ok(ParsedMarkdown {
+            content: content.to_string(),
+            metadata,
+        })
     }
 
     fn update_frontmatter(
@@ -244,50 +201,35 @@ impl MarkdownProcessor for PulldownMarkdownService {
         markdown: &str,
         metadata: &MarkdownMetadata,
     ) -> DomainResult<String> {
-        // Build YAML frontmatter
-        let mut yaml = String::from("---\n");
+        // Edit the existing frontmatter in place, keeping keys not mentioned
+        let mut frontmatter = Frontmatter::from_markdown(markdown)?.unwrap_or_default();
 
         if let Some(title) = &metadata.title {
-            yaml.push_str(&format!("title: \"{}\"\n", title));
+            frontmatter.set("title", YamlValue::String(title.clone()));
         }
 
         if let Some(tags) = &metadata.tags {
-            let tags_str = tags
-                .iter()
-                .map(|t| format!("\"{}\"", t))
-                .collect::<Vec<_>>()
-                .join(", ");
-            yaml.push_str(&format!("tags: [{}]\n", tags_str));
+            let key = frontmatter
+                .get_any(&TAG_KEYS)
+                .map(|(key, _)| key)
+                .unwrap_or("tags");
+            frontmatter.set(key, YamlValue::string_list(tags));
         }
 
         if let Some(created) = &metadata.created {
-            yaml.push_str(&format!("created: \"{}\"\n", created));
+            frontmatter.set("created", YamlValue::String(created.clone()));
         }
 
         if let Some(modified) = &metadata.modified {
-            yaml.push_str(&format!("modified: \"{}\"\n", modified));
+            frontmatter.set("modified", YamlValue::String(modified.clone()));
         }
 
         // Add extra fields
         for (key, value) in &metadata.extra {
-            match value {
-                serde_json::Value::String(s) => yaml.push_str(&format!("{}: \"{}\"\n", key, s)),
-                serde_json::Value::Number(n) => yaml.push_str(&format!("{}: {}\n", key, n)),
-                serde_json::Value::Bool(b) => yaml.push_str(&format!("{}: {}\n", key, b)),
-                _ => yaml.push_str(&format!("{}: {}\n", key, value)),
-            }
+            frontmatter.set(key, YamlValue::from_json(value));
         }
 
-        yaml.push_str("---\n\n");
-
-        // Remove existing frontmatter if present
-        let content_without_frontmatter = if self.frontmatter_regex.is_match(markdown) {
-            self.frontmatter_regex.replace(markdown, "").to_string()
-        } else {
-            markdown.to_string()
-        };
-
-        Ok(format!("{}{}", yaml, content_without_frontmatter))
+        Ok(frontmatter.write_to(markdown))
     }
 
     fn extract_title(&self, markdown: &str) -> DomainResult<Option<String>> {
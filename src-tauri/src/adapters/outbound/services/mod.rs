@@ -8,8 +8,10 @@
 //! - Git2Service: Git operations via git2/libgit2
 //! - TokioEventPublisher: Event publishing using broadcast channels
 //! - NotifyFileWatcher: File system watching using notify crate
-//! - StubSearchService: Search engine (stub implementation)
-//! - StubEmbeddingService: ML embedding generation (stub implementation)
+//! - Fts5SearchService: Full-text search using SQLite FTS5 with BM25 ranking
+//! - FastEmbedService: Embedding storage and vector search
+//! - FastEmbedProvider / OpenAiEmbeddingProvider: Embedding models (in-process or local endpoint)
+//! - OpenAiChatService: Chat completions from a local OpenAI-compatible endpoint
 //! - StubExportService: PDF/HTML export (stub implementation)
 
 // Core Services (Phase 1)
@@ -26,6 +28,9 @@ pub mod event_publisher_impl;
 pub mod file_watcher_impl;
 pub mod search_service;
 pub mod embedding_service_impl;
+pub mod fastembed_provider;
+pub mod openai_embedding_provider;
+pub mod openai_chat_service;
 pub mod export_service_impl;
 
 // Re-exports for convenience
@@ -36,6 +41,9 @@ pub use system_service_impl::TauriSystemService;
 pub use git_service_impl::Git2Service;
 pub use event_publisher_impl::TokioEventPublisher;
 pub use file_watcher_impl::NotifyFileWatcher;
-pub use search_service::StubSearchService;
-pub use embedding_service_impl::FastEmbedService;
+pub use search_service::Fts5SearchService;
+pub use embedding_service_impl::{create_embedding_provider, FastEmbedService};
+pub use fastembed_provider::FastEmbedProvider;
+pub use openai_embedding_provider::OpenAiEmbeddingProvider;
+pub use openai_chat_service::OpenAiChatService;
 pub use export_service_impl::StubExportService;
@@ -0,0 +1,410 @@
+//! OpenAI-Compatible Chat Service
+//!
+//! Streams chat completions from a `/v1/chat/completions` endpoint, as served
+//! by local model runners such as llama.cpp, Ollama or LM Studio. Replies
+//! arrive as server-sent events; servers that ignore `stream` and answer
+//! with a single JSON body work too.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    errors::{DomainError, DomainResult},
+    ports::outbound::{ChatDeltaHandler, ChatMessage, ChatModelConfig, ChatService},
+};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest wait for the next piece of a reply; local runners can take a
+/// while to load a model before the first one
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: &'a [ChatMessage],
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+}
+
+/// A complete (non-streamed) reply
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatChoiceMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatChoiceMessage {
+    content: Option<String>,
+}
+
+/// One server-sent event of a streamed reply
+#[derive(Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    choices: Vec<ChatChunkChoice>,
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ChatChunkChoice {
+    delta: ChatChunkDelta,
+}
+
+#[derive(Deserialize)]
+struct ChatChunkDelta {
+    content: Option<String>,
+}
+
+/// Chat service backed by an OpenAI-compatible HTTP endpoint
+pub struct OpenAiChatService {
+    client: reqwest::Client,
+}
+
+impl OpenAiChatService {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .connect_timeout(CONNECT_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// `base_url` may include the `/v1` prefix or leave it out
+    fn endpoint(base_url: &str) -> String {
+        let base_url = base_url.trim().trim_end_matches('/');
+        if base_url.ends_with("/v1") {
+            format!("{}/chat/completions", base_url)
+        } else {
+            format!("{}/v1/chat/completions", base_url)
+        }
+    }
+
+    /// Handle one line of a server-sent event stream
+    ///
+    /// Returns true once the stream says it is done.
+    fn read_event_line(
+        line: &str,
+        reply: &mut String,
+        on_delta: ChatDeltaHandler<'_>,
+    ) -> DomainResult<bool> {
+        let Some(data) = line.strip_prefix("data:") else {
+            return Ok(false); // Comments, event names and blank separators
+        };
+        let data = data.trim();
+        if data == "[DONE]" {
+            return Ok(true);
+        }
+
+        let chunk: ChatChunk = serde_json::from_str(data).map_err(|e| {
+            DomainError::ExternalServiceError(format!("Invalid chat stream event: {}", e))
+        })?;
+        if let Some(error) = chunk.error {
+            return Err(DomainError::ExternalServiceError(format!(
+                "Chat endpoint failed mid-reply: {}",
+                error
+            )));
+        }
+
+        for choice in chunk.choices {
+            if let Some(content) = choice.delta.content.filter(|c| !c.is_empty()) {
+                on_delta(&content);
+                reply.push_str(&content);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl Default for OpenAiChatService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ChatService for OpenAiChatService {
+    async fn stream_chat(
+        &self,
+        config: &ChatModelConfig,
+        messages: Vec<ChatMessage>,
+        on_delta: ChatDeltaHandler<'_>,
+    ) -> DomainResult<String> {
+        let endpoint = Self::endpoint(&config.base_url);
+        let mut request = self.client.post(&endpoint).json(&ChatRequest {
+            model: config.model.trim(),
+            messages: &messages,
+            stream: true,
+            temperature: config.temperature,
+        });
+        if let Some(api_key) = config.api_key.as_ref().filter(|key| !key.trim().is_empty()) {
+            request = request.bearer_auth(api_key);
+        }
+
+        let mut response = tokio::time::timeout(CHUNK_TIMEOUT, request.send())
+            .await
+            .map_err(|_| {
+                DomainError::ExternalServiceError(format!(
+                    "Chat endpoint {} did not answer in time",
+                    endpoint
+                ))
+            })?
+            .map_err(|e| {
+                DomainError::ExternalServiceError(format!(
+                    "Chat endpoint {} is unreachable: {}",
+                    endpoint, e
+                ))
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(DomainError::ExternalServiceError(format!(
+                "Chat endpoint returned {}: {}",
+                status,
+                body.trim()
+            )));
+        }
+
+        let is_event_stream = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+        if !is_event_stream {
+            let reply = response
+                .json::<ChatResponse>()
+                .await
+                .map_err(|e| {
+                    DomainError::ExternalServiceError(format!("Invalid chat response: {}", e))
+                })?
+                .choices
+                .into_iter()
+                .next()
+                .and_then(|choice| choice.message.content)
+                .unwrap_or_default();
+            if !reply.is_empty() {
+                on_delta(&reply);
+            }
+            return Ok(reply);
+        }
+
+        // Events may be split across chunks, so only complete lines are read
+        let mut reply = String::new();
+        let mut buffer: Vec<u8> = Vec::new();
+        loop {
+            let chunk = tokio::time::timeout(CHUNK_TIMEOUT, response.chunk())
+                .await
+                .map_err(|_| {
+                    DomainError::ExternalServiceError(
+                        "Chat endpoint stopped sending the reply".to_string(),
+                    )
+                })?
+                .map_err(|e| {
+                    DomainError::ExternalServiceError(format!("Chat stream broke off: {}", e))
+                })?;
+
+            let Some(chunk) = chunk else {
+                // Some servers close the stream without `[DONE]`
+                let rest = String::from_utf8_lossy(&buffer).to_string();
+                Self::read_event_line(rest.trim(), &mut reply, on_delta)?;
+                return Ok(reply);
+            };
+            buffer.extend_from_slice(&chunk);
+
+            while let Some(end) = buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                if Self::read_event_line(line.trim(), &mut reply, on_delta)? {
+                    return Ok(reply);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve HTTP on a free local port, answering each request with the
+    /// status, content type and body parts from `respond`
+    ///
+    /// Each body part is written separately, so streamed replies arrive in
+    /// pieces as they would from a model.
+    async fn stub_server<F>(respond: F) -> (String, Arc<Mutex<Vec<Value>>>)
+    where
+        F: Fn(&Value) -> (u16, &'static str, Vec<String>) + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let respond = Arc::new(respond);
+
+        let log = received.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let (log, respond) = (log.clone(), respond.clone());
+                tokio::spawn(async move {
+                    let mut buffer = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    let body = loop {
+                        let read = socket.read(&mut chunk).await.unwrap();
+                        if read == 0 {
+                            return;
+                        }
+                        buffer.extend_from_slice(&chunk[..read]);
+
+                        let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") else {
+                            continue;
+                        };
+                        let head = String::from_utf8_lossy(&buffer[..end]).to_string();
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if buffer.len() >= end + 4 + length {
+                            break buffer[end + 4..end + 4 + length].to_vec();
+                        }
+                    };
+
+                    let request: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+                    let (status, content_type, parts) = respond(&request);
+                    log.lock().unwrap().push(request);
+
+                    let head = format!(
+                        "HTTP/1.1 {} Stub\r\nContent-Type: {}\r\nConnection: close\r\n\r\n",
+                        status, content_type
+                    );
+                    let _ = socket.write_all(head.as_bytes()).await;
+                    for part in parts {
+                        let _ = socket.write_all(part.as_bytes()).await;
+                        let _ = socket.flush().await;
+                        tokio::time::sleep(Duration::from_millis(5)).await;
+                    }
+                });
+            }
+        });
+
+        (base_url, received)
+    }
+
+    fn config(base_url: &str) -> ChatModelConfig {
+        ChatModelConfig {
+            base_url: base_url.to_string(),
+            model: "llama3.2".to_string(),
+            api_key: None,
+            temperature: Some(0.1),
+        }
+    }
+
+    fn delta(content: &str) -> String {
+        let event = json!({ "choices": [{ "index": 0, "delta": { "content": content } }] });
+        format!("data: {}\n\n", event)
+    }
+
+    async fn collect(
+        service: &OpenAiChatService,
+        config: &ChatModelConfig,
+    ) -> (DomainResult<String>, Vec<String>) {
+        let deltas = Mutex::new(Vec::new());
+        let on_delta = |piece: &str| deltas.lock().unwrap().push(piece.to_string());
+        let result = service
+            .stream_chat(config, vec![ChatMessage::user("Hi")], &on_delta)
+            .await;
+        (result, deltas.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_streams_reply_in_pieces() {
+        let (base_url, received) = stub_server(|_| {
+            // The second event is split mid-line across two writes
+            let second = delta("lo, ");
+            let (head, tail) = second.split_at(10);
+            let parts = vec![
+                ": keep-alive\n\n".to_string(),
+                delta("Hel"),
+                head.to_string(),
+                tail.to_string(),
+                delta("world"),
+                "data: [DONE]\n\n".to_string(),
+            ];
+            (200, "text/event-stream", parts)
+        })
+        .await;
+
+        let service = OpenAiChatService::new();
+        let (reply, deltas) = collect(&service, &config(&format!("{}/v1/", base_url))).await;
+        assert_eq!(reply.unwrap(), "Hello, world");
+        assert_eq!(deltas, vec!["Hel", "lo, ", "world"]);
+
+        let received = received.lock().unwrap();
+        assert_eq!(received[0]["model"], "llama3.2");
+        assert_eq!(received[0]["stream"], true);
+        assert_eq!(received[0]["messages"][0]["role"], "user");
+        assert_eq!(received[0]["messages"][0]["content"], "Hi");
+        assert!((received[0]["temperature"].as_f64().unwrap() - 0.1).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_accepts_a_single_json_reply() {
+        let (base_url, _) = stub_server(|_| {
+            let body = json!({ "choices": [{ "message": { "role": "assistant", "content": "All at once" } }] });
+            (200, "application/json", vec![body.to_string()])
+        })
+        .await;
+
+        let service = OpenAiChatService::new();
+        let (reply, deltas) = collect(&service, &config(&base_url)).await;
+        assert_eq!(reply.unwrap(), "All at once");
+        assert_eq!(deltas, vec!["All at once"]);
+    }
+
+    #[tokio::test]
+    async fn test_reports_endpoint_errors() {
+        let (base_url, _) = stub_server(|request| {
+            if request["model"] == "missing" {
+                let body = json!({ "error": { "message": "model not found" } });
+                (404, "application/json", vec![body.to_string()])
+            } else {
+                let error = json!({ "error": { "message": "out of memory" } });
+                let parts = vec![delta("Partial"), format!("data: {}\n\n", error)];
+                (200, "text/event-stream", parts)
+            }
+        })
+        .await;
+        let service = OpenAiChatService::new();
+
+        let missing = ChatModelConfig {
+            model: "missing".to_string(),
+            ..config(&base_url)
+        };
+        let (reply, _) = collect(&service, &missing).await;
+        assert!(
+            matches!(reply, Err(DomainError::ExternalServiceError(ref m)) if m.contains("model not found"))
+        );
+
+        let (reply, deltas) = collect(&service, &config(&base_url)).await;
+        assert!(
+            matches!(reply, Err(DomainError::ExternalServiceError(ref m)) if m.contains("out of memory"))
+        );
+        assert_eq!(deltas, vec!["Partial"]);
+    }
+}
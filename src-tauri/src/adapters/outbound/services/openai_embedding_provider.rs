@@ -0,0 +1,358 @@
+//! OpenAI-Compatible Embedding Provider
+//!
+//! Embeds text through a `/v1/embeddings` endpoint, as served by local model
+//! runners such as Ollama, LM Studio or llama.cpp. Texts are sent in batches
+//! and the vectors are returned in input order.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    errors::{DomainError, DomainResult},
+    ports::outbound::{EmbeddingModelInfo, EmbeddingProvider},
+};
+
+/// Texts sent per request
+const BATCH_SIZE: usize = 64;
+
+/// Local runners can take a while to load a model on the first request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Serialize)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    index: Option<usize>,
+}
+
+/// Embedding provider backed by an OpenAI-compatible HTTP endpoint
+pub struct OpenAiEmbeddingProvider {
+    client: reqwest::Client,
+    base_url: String,
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+    dimensions: Mutex<Option<usize>>,
+    ready: AtomicBool,
+}
+
+impl OpenAiEmbeddingProvider {
+    /// `base_url` may include the `/v1` prefix or leave it out
+    pub fn new(
+        base_url: impl Into<String>,
+        model: impl Into<String>,
+        api_key: Option<String>,
+    ) -> Self {
+        let base_url = base_url.into().trim().trim_end_matches('/').to_string();
+        let endpoint = if base_url.ends_with("/v1") {
+            format!("{}/embeddings", base_url)
+        } else {
+            format!("{}/v1/embeddings", base_url)
+        };
+
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            base_url,
+            endpoint,
+            model: model.into().trim().to_string(),
+            api_key: api_key.filter(|key| !key.trim().is_empty()),
+            dimensions: Mutex::new(None),
+            ready: AtomicBool::new(false),
+        }
+    }
+
+    /// Embed one batch with a single request
+    async fn embed_batch(&self, texts: &[String]) -> DomainResult<Vec<Vec<f32>>> {
+        let mut request = self.client.post(&self.endpoint).json(&EmbeddingsRequest {
+            model: &self.model,
+            input: texts,
+        });
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            DomainError::ExternalServiceError(format!(
+                "Embedding endpoint {} is unreachable: {}",
+                self.endpoint, e
+            ))
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(DomainError::ExternalServiceError(format!(
+                "Embedding endpoint returned {}: {}",
+                status,
+                body.trim()
+            )));
+        }
+
+        let mut data = response
+            .json::<EmbeddingsResponse>()
+            .await
+            .map_err(|e| {
+                DomainError::ExternalServiceError(format!("Invalid embedding response: {}", e))
+            })?
+            .data;
+        if data.len() != texts.len() {
+            return Err(DomainError::ExternalServiceError(format!(
+                "Embedding endpoint returned {} vectors for {} texts",
+                data.len(),
+                texts.len()
+            )));
+        }
+
+        // Servers may answer out of order; `index` says which input each vector is for
+        data.sort_by_key(|item| item.index);
+        Ok(data.into_iter().map(|item| item.embedding).collect())
+    }
+
+    fn record_dimensions(&self, dimensions: usize) {
+        if let Ok(mut guard) = self.dimensions.lock() {
+            *guard = Some(dimensions);
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAiEmbeddingProvider {
+    /// The endpoint is part of the identity, since two servers may serve
+    /// different weights under the same model name
+    fn model(&self) -> EmbeddingModelInfo {
+        EmbeddingModelInfo {
+            name: self.model.clone(),
+            version: format!("openai:{}", self.base_url),
+            dimensions: *self.dimensions.lock().unwrap_or_else(|e| e.into_inner()),
+        }
+    }
+
+    async fn initialize(&self) -> DomainResult<()> {
+        if self.is_ready() {
+            return Ok(());
+        }
+
+        // Probe the endpoint so a wrong URL or model shows up straight away
+        let probe = self.embed_batch(&["Stone".to_string()]).await?;
+        let dimensions = probe.first().map_or(0, Vec::len);
+        if dimensions == 0 {
+            return Err(DomainError::ExternalServiceError(
+                "Embedding endpoint returned an empty vector".to_string(),
+            ));
+        }
+        self.record_dimensions(dimensions);
+        self.ready.store(true, Ordering::SeqCst);
+
+        tracing::info!(
+            "Embedding endpoint {} ready with model {} ({} dimensions)",
+            self.endpoint,
+            self.model,
+            dimensions
+        );
+        Ok(())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> DomainResult<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(BATCH_SIZE) {
+            vectors.extend(self.embed_batch(batch).await?);
+        }
+
+        if let Some(first) = vectors.first() {
+            self.record_dimensions(first.len());
+        }
+        Ok(vectors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// A request received by the stub server: authorization header and JSON body
+    type Received = Arc<Mutex<Vec<(Option<String>, Value)>>>;
+
+    /// Serve HTTP on a free local port, answering each request with `respond`
+    async fn stub_server<F>(respond: F) -> (String, Received)
+    where
+        F: Fn(&Value) -> (u16, Value) + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let received: Received = Arc::new(Mutex::new(Vec::new()));
+        let respond = Arc::new(respond);
+
+        let log = received.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let (log, respond) = (log.clone(), respond.clone());
+                tokio::spawn(async move {
+                    let mut buffer = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    let (head, body) = loop {
+                        let read = socket.read(&mut chunk).await.unwrap();
+                        if read == 0 {
+                            return;
+                        }
+                        buffer.extend_from_slice(&chunk[..read]);
+
+                        let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") else {
+                            continue;
+                        };
+                        let head = String::from_utf8_lossy(&buffer[..end]).to_string();
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        if buffer.len() >= end + 4 + length {
+                            break (head, buffer[end + 4..end + 4 + length].to_vec());
+                        }
+                    };
+
+                    let authorization = head.lines().find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("authorization")
+                            .then(|| value.trim().to_string())
+                    });
+                    let request: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+                    let (status, response) = respond(&request);
+                    log.lock().unwrap().push((authorization, request));
+
+                    let response = response.to_string();
+                    let reply = format!(
+                        "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        response.len(),
+                        response
+                    );
+                    let _ = socket.write_all(reply.as_bytes()).await;
+                });
+            }
+        });
+
+        (base_url, received)
+    }
+
+    /// Embeds each input as [its length, batch position], listed in reverse
+    fn reversed_embeddings(request: &Value) -> (u16, Value) {
+        let inputs = request["input"].as_array().cloned().unwrap_or_default();
+        let data: Vec<Value> = inputs
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, text)| {
+                let length = text.as_str().unwrap_or_default().len();
+                json!({ "object": "embedding", "index": index, "embedding": [length as f32, index as f32] })
+            })
+            .collect();
+        (200, json!({ "object": "list", "data": data }))
+    }
+
+    #[tokio::test]
+    async fn test_embeds_in_batches_and_input_order() {
+        let (base_url, received) = stub_server(reversed_embeddings).await;
+        let provider =
+            OpenAiEmbeddingProvider::new(format!("{}/v1/", base_url), "nomic-embed-text", None);
+
+        provider.initialize().await.unwrap();
+        assert!(provider.is_ready());
+        assert_eq!(provider.model().dimensions, Some(2));
+        assert_eq!(provider.model().version, format!("openai:{}/v1", base_url));
+
+        let texts: Vec<String> = (0..BATCH_SIZE + 6).map(|i| "x".repeat(i + 1)).collect();
+        let vectors = provider.embed(texts.clone()).await.unwrap();
+        assert_eq!(vectors.len(), texts.len());
+        assert_eq!(vectors[0], vec![1.0, 0.0]);
+        assert_eq!(vectors[BATCH_SIZE], vec![(BATCH_SIZE + 1) as f32, 0.0]);
+        assert_eq!(vectors[BATCH_SIZE + 5], vec![(BATCH_SIZE + 6) as f32, 5.0]);
+
+        // Probe, then one request per batch
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 3);
+        assert_eq!(received[1].1["model"], "nomic-embed-text");
+        assert_eq!(received[1].1["input"].as_array().unwrap().len(), BATCH_SIZE);
+        assert_eq!(received[2].1["input"].as_array().unwrap().len(), 6);
+        assert!(received
+            .iter()
+            .all(|(authorization, _)| authorization.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_sends_api_key_and_reports_errors() {
+        let (base_url, received) = stub_server(|request| {
+            if request["model"] == "missing" {
+                (404, json!({ "error": { "message": "model not found" } }))
+            } else {
+                (200, json!({ "data": [] }))
+            }
+        })
+        .await;
+
+        let missing =
+            OpenAiEmbeddingProvider::new(&base_url, "missing", Some("secret".to_string()));
+        let error = missing.initialize().await.unwrap_err();
+        assert!(
+            matches!(error, DomainError::ExternalServiceError(ref m) if m.contains("model not found"))
+        );
+        assert!(!missing.is_ready());
+
+        // A response without one vector per input is rejected
+        let empty = OpenAiEmbeddingProvider::new(&base_url, "empty", None);
+        let error = empty.embed(vec!["a".to_string()]).await.unwrap_err();
+        assert!(
+            matches!(error, DomainError::ExternalServiceError(ref m) if m.contains("0 vectors for 1"))
+        );
+
+        assert_eq!(
+            received.lock().unwrap()[0].0.as_deref(),
+            Some("Bearer secret")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint() {
+        // Bind and drop a listener to find a port nothing listens on
+        let port = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let provider =
+            OpenAiEmbeddingProvider::new(format!("http://127.0.0.1:{}", port), "model", None);
+
+        let error = provider.initialize().await.unwrap_err();
+        assert!(
+            matches!(error, DomainError::ExternalServiceError(ref m) if m.contains("unreachable"))
+        );
+    }
+}
@@ -1,45 +1,493 @@
 //! Search Service Implementation
 //!
-//! Stub implementation of the SearchEngine port.
-//! Full implementation would use Tantivy for full-text search.
+//! SQLite FTS5 implementation of the SearchEngine port.
+//! Notes are indexed into the `notes_fts` virtual table (title + plain-text body)
+//! and ranked with BM25, weighting title hits above body hits.
 
 use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::query_builder::{BoxedSqlQuery, SqlQuery};
+use diesel::sql_types::{BigInt, Double, Integer, Nullable, Text};
+use diesel::sqlite::Sqlite;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::sync::Arc;
 
 use crate::{
-    adapters::outbound::persistence::DbPool,
+    adapters::outbound::persistence::{
+        bool_to_i32, datetime_to_timestamp, get_connection, map_diesel_error, DbPool, NoteRow,
+    },
     domain::{
-        entities::Note,
-        errors::DomainResult,
+        entities::{Note, Tag},
+        errors::{DomainError, DomainResult},
         ports::outbound::{
-            DateRangeOptions, HybridSearchOptions, SearchEngine, SearchOptions, SearchResult,
-            SemanticSearchResult, TagSearchOptions,
+            DateRangeField, DateRangeOptions, EmbeddingSearchFilter, EmbeddingService, FileStorage,
+            HybridSearchOptions, MarkdownProcessor, SearchEngine, SearchHighlights, SearchMatchType, SearchOptions,
+            SearchResult, SearchRetriever, SemanticSearchResult, TagSearchOptions,
         },
+        services::{FusedHit, RankFusion, RankedHit},
     },
+    shared::database::schema::{note_tags, notes, tags, workspaces},
 };
 
-/// Stub search service implementation
-/// TODO: Implement full-text search with Tantivy
-pub struct StubSearchService {
-    _pool: Arc<DbPool>,
+/// BM25 column weights for (note_id, title, content)
+const BM25_WEIGHTS: &str = "0.0, 10.0, 1.0";
+
+/// Markers wrapped around matched terms in highlight snippets
+const HIGHLIGHT_START: &str = "<mark>";
+const HIGHLIGHT_END: &str = "</mark>";
+
+/// Default page size when no limit is given
+const DEFAULT_LIMIT: i32 = 50;
+
+/// Row returned by the FTS match query
+#[derive(QueryableByName, Debug)]
+struct FtsMatchRow {
+    #[diesel(sql_type = Text)]
+    note_id: String,
+    #[diesel(sql_type = Double)]
+    bm25_rank: f64,
+    #[diesel(sql_type = Nullable<Text>)]
+    title_snippet: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    content_snippet: Option<String>,
+}
+
+/// Row holding a single note id
+#[derive(QueryableByName, Debug)]
+struct NoteIdRow {
+    #[diesel(sql_type = Text)]
+    note_id: String,
 }
 
-impl StubSearchService {
-    pub fn new(pool: Arc<DbPool>) -> Self {
-        Self { _pool: pool }
+/// SQLite FTS5 search engine
+///
+/// Hybrid search fuses FTS hits with the embedding service's semantic matches.
+pub struct Fts5SearchService {
+    pool: Arc<DbPool>,
+    file_storage: Arc<dyn FileStorage>,
+    markdown_processor: Arc<dyn MarkdownProcessor>,
+    embedding_service: Arc<dyn EmbeddingService>,
+}
+
+impl Fts5SearchService {
+    pub fn new(
+        pool: Arc<DbPool>,
+        file_storage: Arc<dyn FileStorage>,
+        markdown_processor: Arc<dyn MarkdownProcessor>,
+        embedding_service: Arc<dyn EmbeddingService>,
+    ) -> Self {
+        Self {
+            pool,
+            file_storage,
+            markdown_processor,
+            embedding_service,
+        }
+    }
+
+    /// Convert parsed user input into a safe FTS5 MATCH expression.
+    ///
+    /// Every term is quoted so FTS5 syntax characters in user input are treated
+    /// literally; terms are implicitly AND-ed and the last free-text term is
+    /// prefix-matched so results update while the user is typing. Phrases must
+    /// match verbatim and excluded terms are appended as `NOT` clauses.
+    ///
+    /// Returns None when there is nothing positive to match on.
+    fn build_match_expression(query: &str, options: &SearchOptions) -> Option<String> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|term| term.replace('"', ""))
+            .filter(|term| term.chars().any(char::is_alphanumeric))
+            .collect();
+
+        let mut positive: Vec<String> = Vec::new();
+        let last = terms.len().saturating_sub(1);
+        for (i, term) in terms.iter().enumerate() {
+            if i == last {
+                positive.push(format!("\"{}\"*", term));
+            } else {
+                positive.push(Self::quote(term));
+            }
+        }
+        for phrase in options.phrases.iter().flatten() {
+            positive.push(Self::quote(phrase));
+        }
+
+        if positive.is_empty() {
+            return None;
+        }
+
+        let mut expression = format!("({})", positive.join(" "));
+        for excluded in options.excluded_terms.iter().flatten() {
+            expression.push_str(" NOT ");
+            expression.push_str(&Self::quote(excluded));
+        }
+
+        Some(expression)
+    }
+
+    /// Quote a term or phrase as an FTS5 string
+    fn quote(text: &str) -> String {
+        format!("\"{}\"", text.replace('"', ""))
+    }
+
+    /// Whether the options narrow results down enough to list notes without a text match
+    fn has_filters(options: &SearchOptions) -> bool {
+        options.notebook_id.is_some()
+            || options.notebook_name.is_some()
+            || options.tag_ids.as_ref().is_some_and(|ids| !ids.is_empty())
+            || options.tag_names.as_ref().is_some_and(|names| !names.is_empty())
+            || options.is_pinned.is_some()
+            || options.is_favorite.is_some()
+            || options.is_archived.is_some()
+            || options.date_ranges.as_ref().is_some_and(|ranges| !ranges.is_empty())
+    }
+
+    /// Clause requiring a note to carry the tag named by `wanted`, a query with one
+    /// bound parameter, or any tag nested under it
+    fn tag_subtree_filter(wanted: &str) -> String {
+        format!(
+            " AND EXISTS (SELECT 1 FROM note_tags \
+             JOIN tags ON tags.id = note_tags.tag_id \
+             JOIN ({}) AS wanted \
+             WHERE note_tags.note_id = notes.id \
+             AND (tags.name = wanted.name \
+             OR substr(tags.name, 1, length(wanted.name) + 1) = wanted.name || '/'))",
+            wanted
+        )
+    }
+
+    /// Append WHERE clauses for every filter in `options` to a query over `notes`
+    fn push_filters(
+        mut sql_query: BoxedSqlQuery<'static, Sqlite, SqlQuery>,
+        options: &SearchOptions,
+    ) -> BoxedSqlQuery<'static, Sqlite, SqlQuery> {
+        if options.exclude_deleted.unwrap_or(true) {
+            sql_query = sql_query.sql(" AND notes.is_deleted = 0");
+        }
+
+        if let Some(workspace_id) = &options.workspace_id {
+            sql_query = sql_query
+                .sql(" AND notes.workspace_id = ?")
+                .bind::<Text, _>(workspace_id.clone());
+        }
+
+        if let Some(notebook_id) = &options.notebook_id {
+            sql_query = sql_query
+                .sql(" AND notes.notebook_id = ?")
+                .bind::<Text, _>(notebook_id.clone());
+        }
+
+        if let Some(notebook_name) = &options.notebook_name {
+            sql_query = sql_query
+                .sql(" AND notes.notebook_id IN (SELECT id FROM notebooks WHERE name = ? COLLATE NOCASE)")
+                .bind::<Text, _>(notebook_name.clone());
+        }
+
+        for (flag, column) in [
+            (options.is_pinned, "is_pinned"),
+            (options.is_favorite, "is_favorite"),
+            (options.is_archived, "is_archived"),
+        ] {
+            if let Some(value) = flag {
+                sql_query = sql_query
+                    .sql(format!(" AND notes.{} = ?", column))
+                    .bind::<Integer, _>(bool_to_i32(value));
+            }
+        }
+
+        // Notes must carry every requested tag, or a tag nested under it
+        for tag_id in options.tag_ids.iter().flatten() {
+            sql_query = sql_query
+                .sql(Self::tag_subtree_filter("SELECT name FROM tags WHERE id = ?"))
+                .bind::<Text, _>(tag_id.clone());
+        }
+
+        for tag_name in options.tag_names.iter().flatten() {
+            sql_query = sql_query
+                .sql(Self::tag_subtree_filter("SELECT ? AS name"))
+                .bind::<Text, _>(tag_name.clone());
+        }
+
+        for range in options.date_ranges.iter().flatten() {
+            let column = match range.field.unwrap_or(DateRangeField::Created) {
+                DateRangeField::Created => "created_at",
+                DateRangeField::Updated => "updated_at",
+            };
+            sql_query = sql_query
+                .sql(format!(" AND notes.{} BETWEEN ? AND ?", column))
+                .bind::<BigInt, _>(datetime_to_timestamp(&range.start_date))
+                .bind::<BigInt, _>(datetime_to_timestamp(&range.end_date));
+        }
+
+        sql_query
+    }
+
+    /// Semantic candidates for hybrid search, restricted to notes passing `options`.
+    ///
+    /// Returns an empty list when the embedding model is unavailable so hybrid
+    /// search degrades to plain FTS.
+    async fn semantic_candidates(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+        limit: i32,
+    ) -> DomainResult<Vec<RankedHit>> {
+        if query.trim().is_empty() || !self.embedding_service.is_ready() {
+            return Ok(Vec::new());
+        }
+
+        // Scope by workspace/notebook in the vector index; over-fetch for the
+        // remaining filters, which are applied afterwards
+        let filter = EmbeddingSearchFilter {
+            workspace_id: options.workspace_id.clone(),
+            notebook_id: options.notebook_id.clone(),
+        };
+        let similar = match self
+            .embedding_service
+            .semantic_search(query, Some(filter), Some(limit.saturating_mul(2)))
+            .await
+        {
+            Ok(similar) => similar,
+            Err(e) => {
+                tracing::warn!("Semantic retrieval failed, using FTS only: {}", e);
+                return Ok(Vec::new());
+            }
+        };
+
+        if similar.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pool = self.pool.clone();
+        let options = options.clone();
+        let ids: Vec<String> = similar.iter().map(|s| s.note_id.clone()).collect();
+
+        let allowed: HashSet<String> = tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let placeholders = vec!["?"; ids.len()].join(", ");
+            let mut sql_query = diesel::sql_query(format!(
+                "SELECT notes.id AS note_id FROM notes WHERE notes.id IN ({})",
+                placeholders
+            ))
+            .into_boxed::<Sqlite>();
+            for id in ids {
+                sql_query = sql_query.bind::<Text, _>(id);
+            }
+
+            let excluded: Vec<String> = options
+                .excluded_terms
+                .iter()
+                .flatten()
+                .map(|term| Self::quote(term))
+                .collect();
+            if !excluded.is_empty() {
+                sql_query = sql_query
+                    .sql(" AND notes.id NOT IN (SELECT note_id FROM notes_fts WHERE notes_fts MATCH ?)")
+                    .bind::<Text, _>(excluded.join(" OR "));
+            }
+
+            let rows = Self::push_filters(sql_query, &options)
+                .load::<NoteIdRow>(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok::<_, DomainError>(rows.into_iter().map(|row| row.note_id).collect())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))??;
+
+        Ok(similar
+            .into_iter()
+            .filter(|s| allowed.contains(&s.note_id))
+            .take(limit.max(0) as usize)
+            .map(|s| RankedHit {
+                note_id: s.note_id,
+                score: s.similarity,
+            })
+            .collect())
+    }
+
+    /// Load notes by id
+    async fn load_notes(&self, ids: Vec<String>) -> DomainResult<HashMap<String, Note>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let rows = notes::table
+                .filter(notes::id.eq_any(&ids))
+                .load::<NoteRow>(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(rows
+                .into_iter()
+                .map(|row| {
+                    let note = row.to_domain();
+                    (note.id.clone(), note)
+                })
+                .collect())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
+    }
+
+    /// Prepare note body for indexing: drop the leading `# Title` heading
+    /// (already indexed as the title column) and strip markdown syntax.
+    fn prepare_content(&self, content: &str) -> String {
+        let trimmed = content.trim_start();
+        let body = match trimmed.strip_prefix("# ") {
+            Some(rest) => rest.split_once('\n').map(|(_, body)| body).unwrap_or(""),
+            None => trimmed,
+        };
+
+        self.markdown_processor
+            .extract_plain_text(body)
+            .unwrap_or_else(|_| body.to_string())
+    }
+
+    /// Replace the index entry for a note on an open connection
+    fn write_index_entry(
+        conn: &mut SqliteConnection,
+        note_id: &str,
+        title: &str,
+        content: &str,
+    ) -> QueryResult<()> {
+        diesel::sql_query("DELETE FROM notes_fts WHERE note_id = ?")
+            .bind::<Text, _>(note_id)
+            .execute(conn)?;
+
+        diesel::sql_query("INSERT INTO notes_fts (note_id, title, content) VALUES (?, ?, ?)")
+            .bind::<Text, _>(note_id)
+            .bind::<Text, _>(title)
+            .bind::<Text, _>(content)
+            .execute(conn)?;
+
+        Ok(())
     }
 }
 
 #[async_trait]
-impl SearchEngine for StubSearchService {
+impl SearchEngine for Fts5SearchService {
     async fn search_full_text(
         &self,
-        _query: &str,
-        _options: Option<SearchOptions>,
+        query: &str,
+        options: Option<SearchOptions>,
     ) -> DomainResult<Vec<SearchResult>> {
-        // TODO: Implement full-text search using Tantivy
-        // For now, return empty results
-        Ok(Vec::new())
+        let options = options.unwrap_or_default();
+        let match_expression = Self::build_match_expression(query, &options);
+
+        // Nothing to match and nothing to filter by
+        if match_expression.is_none() && !Self::has_filters(&options) {
+            return Ok(Vec::new());
+        }
+
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let mut sql_query = match match_expression {
+                Some(match_expression) => diesel::sql_query(format!(
+                    "SELECT notes_fts.note_id AS note_id, \
+                     bm25(notes_fts, {weights}) AS bm25_rank, \
+                     snippet(notes_fts, 1, '{start}', '{end}', '…', 12) AS title_snippet, \
+                     snippet(notes_fts, 2, '{start}', '{end}', '…', 24) AS content_snippet \
+                     FROM notes_fts \
+                     JOIN notes ON notes.id = notes_fts.note_id \
+                     WHERE notes_fts MATCH ?",
+                    weights = BM25_WEIGHTS,
+                    start = HIGHLIGHT_START,
+                    end = HIGHLIGHT_END,
+                ))
+                .into_boxed::<Sqlite>()
+                .bind::<Text, _>(match_expression),
+                // Filter-only query (e.g. `tag:work is:pinned`): list matching notes
+                None => {
+                    let mut filter_query = diesel::sql_query(
+                        "SELECT notes.id AS note_id, 0.0 AS bm25_rank, \
+                         NULL AS title_snippet, NULL AS content_snippet \
+                         FROM notes WHERE 1 = 1",
+                    )
+                    .into_boxed::<Sqlite>();
+
+                    let excluded: Vec<String> = options
+                        .excluded_terms
+                        .iter()
+                        .flatten()
+                        .map(|term| Self::quote(term))
+                        .collect();
+                    if !excluded.is_empty() {
+                        filter_query = filter_query
+                            .sql(" AND notes.id NOT IN (SELECT note_id FROM notes_fts WHERE notes_fts MATCH ?)")
+                            .bind::<Text, _>(excluded.join(" OR "));
+                    }
+                    filter_query
+                }
+            };
+
+            sql_query = Self::push_filters(sql_query, &options);
+
+            sql_query = sql_query
+                .sql(" ORDER BY bm25_rank, notes.updated_at DESC LIMIT ? OFFSET ?")
+                .bind::<Integer, _>(options.limit.unwrap_or(DEFAULT_LIMIT).max(0))
+                .bind::<Integer, _>(options.offset.unwrap_or(0).max(0));
+
+            let matches = sql_query
+                .load::<FtsMatchRow>(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            if matches.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let ids: Vec<String> = matches.iter().map(|m| m.note_id.clone()).collect();
+            let mut notes_by_id: HashMap<String, Note> = notes::table
+                .filter(notes::id.eq_any(&ids))
+                .load::<NoteRow>(&mut conn)
+                .map_err(map_diesel_error)?
+                .into_iter()
+                .map(|row| {
+                    let note = row.to_domain();
+                    (note.id.clone(), note)
+                })
+                .collect();
+
+            // Preserve BM25 ordering from the match query
+            let results = matches
+                .into_iter()
+                .filter_map(|m| {
+                    let note = notes_by_id.remove(&m.note_id)?;
+
+                    let title = m.title_snippet.filter(|s| s.contains(HIGHLIGHT_START));
+                    let content = m.content_snippet.filter(|s| s.contains(HIGHLIGHT_START));
+                    let match_type = match (&title, &content) {
+                        (Some(_), Some(_)) => SearchMatchType::Both,
+                        (Some(_), None) => SearchMatchType::Title,
+                        _ => SearchMatchType::Content,
+                    };
+                    let highlights = (title.is_some() || content.is_some())
+                        .then_some(SearchHighlights { title, content });
+
+                    Some(SearchResult {
+                        note,
+                        // bm25() is lower-is-better; expose a higher-is-better score
+                        relevance: (-m.bm25_rank) as f32,
+                        match_type,
+                        highlights,
+                        retrievers: vec![SearchRetriever::Fts],
+                    })
+                })
+                .collect();
+
+            Ok(results)
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
     }
 
     async fn search_semantic(
@@ -47,45 +495,344 @@ impl SearchEngine for StubSearchService {
         _query: &str,
         _options: Option<SearchOptions>,
     ) -> DomainResult<Vec<SemanticSearchResult>> {
-        // TODO: Implement semantic search using embeddings
+        // Semantic search is served by the EmbeddingService
         Ok(Vec::new())
     }
 
     async fn search_hybrid(
         &self,
-        _query: &str,
-        _options: Option<HybridSearchOptions>,
+        query: &str,
+        options: Option<HybridSearchOptions>,
     ) -> DomainResult<Vec<SearchResult>> {
-        // TODO: Implement hybrid search combining FTS and semantic
-        Ok(Vec::new())
+        let options = options.unwrap_or(HybridSearchOptions {
+            base: SearchOptions::default(),
+            weights: None,
+            fusion: None,
+        });
+        let weights = options.weights.unwrap_or_default();
+        let method = options.fusion.unwrap_or_default();
+        let base = options.base;
+
+        let limit = base.limit.unwrap_or(DEFAULT_LIMIT).max(0);
+        let offset = base.offset.unwrap_or(0).max(0);
+        // Each retriever contributes enough candidates to fill the requested page
+        let candidates = limit.saturating_add(offset);
+
+        let fts_results = if weights.fts > 0.0 {
+            let fts_options = SearchOptions {
+                limit: Some(candidates),
+                offset: Some(0),
+                ..base.clone()
+            };
+            self.search_full_text(query, Some(fts_options)).await?
+        } else {
+            Vec::new()
+        };
+
+        let semantic_hits = if weights.semantic > 0.0 {
+            // Phrases carry meaning for the embedding model too
+            let semantic_query = std::iter::once(query.to_string())
+                .chain(base.phrases.iter().flatten().cloned())
+                .collect::<Vec<_>>()
+                .join(" ");
+            self.semantic_candidates(&semantic_query, &base, candidates)
+                .await?
+        } else {
+            Vec::new()
+        };
+
+        let fts_hits: Vec<RankedHit> = fts_results
+            .iter()
+            .map(|r| RankedHit {
+                note_id: r.note.id.clone(),
+                score: r.relevance,
+            })
+            .collect();
+
+        let fused = RankFusion::fuse(&fts_hits, &semantic_hits, &weights, method);
+
+        let mut fts_by_id: HashMap<String, SearchResult> = fts_results
+            .into_iter()
+            .map(|r| (r.note.id.clone(), r))
+            .collect();
+        let page: Vec<FusedHit> = fused
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect();
+
+        let semantic_only: Vec<String> = page
+            .iter()
+            .filter(|hit| !fts_by_id.contains_key(&hit.note_id))
+            .map(|hit| hit.note_id.clone())
+            .collect();
+        let mut semantic_notes = self.load_notes(semantic_only).await?;
+
+        let results = page
+            .into_iter()
+            .filter_map(|hit| match fts_by_id.remove(&hit.note_id) {
+                Some(result) => Some(SearchResult {
+                    relevance: hit.score,
+                    retrievers: hit.retrievers,
+                    ..result
+                }),
+                None => semantic_notes.remove(&hit.note_id).map(|note| SearchResult {
+                    note,
+                    relevance: hit.score,
+                    match_type: SearchMatchType::Semantic,
+                    highlights: None,
+                    retrievers: hit.retrievers,
+                }),
+            })
+            .collect();
+
+        Ok(results)
     }
 
     async fn search_by_tags(
         &self,
-        _tag_ids: Vec<String>,
-        _options: Option<TagSearchOptions>,
+        tag_ids: Vec<String>,
+        options: Option<TagSearchOptions>,
     ) -> DomainResult<Vec<Note>> {
-        // TODO: Implement tag-based search
-        Ok(Vec::new())
+        let mut tag_ids = tag_ids;
+        tag_ids.sort();
+        tag_ids.dedup();
+        if tag_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pool = self.pool.clone();
+        let match_all = options.as_ref().and_then(|o| o.match_all).unwrap_or(false);
+        let include_descendants = options
+            .as_ref()
+            .and_then(|o| o.include_descendants)
+            .unwrap_or(false);
+        let base = options.map(|o| o.base).unwrap_or_default();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            // Each tag that satisfies a requested tag, with the requests it satisfies
+            let mut requested_by: HashMap<String, Vec<usize>> = HashMap::new();
+            if include_descendants {
+                let names: Vec<(String, String)> = tags::table
+                    .select((tags::id, tags::name))
+                    .load(&mut conn)
+                    .map_err(map_diesel_error)?;
+                for (index, tag_id) in tag_ids.iter().enumerate() {
+                    let Some((_, wanted)) = names.iter().find(|(id, _)| id == tag_id) else {
+                        continue;
+                    };
+                    for (id, name) in &names {
+                        if Tag::is_within(name, wanted) {
+                            requested_by.entry(id.clone()).or_default().push(index);
+                        }
+                    }
+                }
+            } else {
+                for (index, tag_id) in tag_ids.iter().enumerate() {
+                    requested_by.entry(tag_id.clone()).or_default().push(index);
+                }
+            }
+
+            let candidate_ids: Vec<&String> = requested_by.keys().collect();
+            let pairs: Vec<(String, String)> = note_tags::table
+                .filter(note_tags::tag_id.eq_any(candidate_ids))
+                .select((note_tags::note_id, note_tags::tag_id))
+                .load(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            // Which of the requested tags each note carries
+            let mut matched: HashMap<String, HashSet<usize>> = HashMap::new();
+            for (note_id, tag_id) in pairs {
+                let requests = requested_by.get(&tag_id).into_iter().flatten();
+                matched.entry(note_id).or_default().extend(requests);
+            }
+            let note_ids: Vec<String> = matched
+                .into_iter()
+                .filter(|(_, requests)| !match_all || requests.len() == tag_ids.len())
+                .map(|(note_id, _)| note_id)
+                .collect();
+
+            if note_ids.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let mut query = notes::table
+                .filter(notes::id.eq_any(note_ids))
+                .into_boxed();
+
+            if base.exclude_deleted.unwrap_or(true) {
+                query = query.filter(notes::is_deleted.eq(0));
+            }
+            if let Some(workspace_id) = base.workspace_id {
+                query = query.filter(notes::workspace_id.eq(workspace_id));
+            }
+            if let Some(notebook_id) = base.notebook_id {
+                query = query.filter(notes::notebook_id.eq(notebook_id));
+            }
+
+            query = query.order((notes::updated_at.desc(), notes::id.asc()));
+            if let Some(limit) = base.limit {
+                query = query.limit(limit.max(0) as i64);
+            }
+            if let Some(offset) = base.offset {
+                query = query.offset(offset.max(0) as i64);
+            }
+
+            let rows = query.load::<NoteRow>(&mut conn).map_err(map_diesel_error)?;
+
+            Ok(rows.into_iter().map(|row| row.to_domain()).collect())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
     }
 
-    async fn search_by_date_range(&self, _options: DateRangeOptions) -> DomainResult<Vec<Note>> {
-        // TODO: Implement date range search
-        Ok(Vec::new())
+    async fn search_by_date_range(&self, options: DateRangeOptions) -> DomainResult<Vec<Note>> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            let start = datetime_to_timestamp(&options.start_date);
+            let end = datetime_to_timestamp(&options.end_date);
+
+            let mut query = notes::table.filter(notes::is_deleted.eq(0)).into_boxed();
+
+            query = match options.field.unwrap_or(DateRangeField::Created) {
+                DateRangeField::Created => query
+                    .filter(notes::created_at.between(start, end))
+                    .order((notes::created_at.desc(), notes::id.asc())),
+                DateRangeField::Updated => query
+                    .filter(notes::updated_at.between(start, end))
+                    .order((notes::updated_at.desc(), notes::id.asc())),
+            };
+
+            if let Some(workspace_id) = options.workspace_id {
+                query = query.filter(notes::workspace_id.eq(workspace_id));
+            }
+            if let Some(limit) = options.limit {
+                query = query.limit(limit.max(0) as i64);
+            }
+            if let Some(offset) = options.offset {
+                query = query.offset(offset.max(0) as i64);
+            }
+
+            let rows = query.load::<NoteRow>(&mut conn).map_err(map_diesel_error)?;
+
+            Ok(rows.into_iter().map(|row| row.to_domain()).collect())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
     }
 
-    async fn index_note(&self, _note_id: &str, _title: &str, _content: &str) -> DomainResult<()> {
-        // TODO: Index note in Tantivy
-        Ok(())
+    async fn index_note(&self, note_id: &str, title: &str, content: &str) -> DomainResult<()> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+        let title = title.to_string();
+        let content = self.prepare_content(content);
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            // Take the write lock up front: the FTS delete reads before it writes,
+            // and a deferred transaction can't wait out a concurrent writer then
+            conn.immediate_transaction(|conn| {
+                Self::write_index_entry(conn, &note_id, &title, &content)
+            })
+            .map_err(map_diesel_error)?;
+
+            Ok(())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
     }
 
-    async fn remove_from_index(&self, _note_id: &str) -> DomainResult<()> {
-        // TODO: Remove note from Tantivy index
-        Ok(())
+    async fn remove_from_index(&self, note_id: &str) -> DomainResult<()> {
+        let pool = self.pool.clone();
+        let note_id = note_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            diesel::sql_query("DELETE FROM notes_fts WHERE note_id = ?")
+                .bind::<Text, _>(note_id)
+                .execute(&mut conn)
+                .map_err(map_diesel_error)?;
+
+            Ok(())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))?
     }
 
     async fn rebuild_index(&self) -> DomainResult<()> {
-        // TODO: Rebuild entire Tantivy index
+        let pool = self.pool.clone();
+
+        // Load every live note together with its workspace root
+        let sources: Vec<(String, Option<String>, Option<String>, String)> =
+            tokio::task::spawn_blocking(move || {
+                let mut conn = get_connection(&pool)?;
+
+                notes::table
+                    .inner_join(workspaces::table)
+                    .filter(notes::is_deleted.eq(0))
+                    .select((
+                        notes::id,
+                        notes::title,
+                        notes::file_path,
+                        workspaces::folder_path,
+                    ))
+                    .load(&mut conn)
+                    .map_err(map_diesel_error)
+            })
+            .await
+            .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))??;
+
+        let mut entries = Vec::with_capacity(sources.len());
+        for (note_id, title, file_path, folder_path) in sources {
+            let content = match file_path {
+                Some(file_path) => {
+                    let absolute_path = Path::new(&folder_path)
+                        .join(&file_path)
+                        .to_string_lossy()
+                        .to_string();
+                    match self.file_storage.read(&absolute_path).await {
+                        Ok(content) => content.unwrap_or_default(),
+                        Err(e) => {
+                            tracing::warn!("Skipping unreadable note {} during reindex: {}", note_id, e);
+                            String::new()
+                        }
+                    }
+                }
+                None => String::new(),
+            };
+
+            entries.push((note_id, title.unwrap_or_default(), self.prepare_content(&content)));
+        }
+
+        let pool = self.pool.clone();
+        let indexed = entries.len();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = get_connection(&pool)?;
+
+            conn.immediate_transaction(|conn| {
+                diesel::sql_query("DELETE FROM notes_fts").execute(conn)?;
+                for (note_id, title, content) in &entries {
+                    Self::write_index_entry(conn, note_id, title, content)?;
+                }
+                Ok::<_, diesel::result::Error>(())
+            })
+            .map_err(map_diesel_error)?;
+
+            Ok::<_, DomainError>(())
+        })
+        .await
+        .map_err(|e| DomainError::DatabaseError(format!("Task join error: {}", e)))??;
+
+        tracing::info!("Search index rebuilt: {} notes indexed", indexed);
+
         Ok(())
     }
 }
@@ -93,18 +840,206 @@ impl SearchEngine for StubSearchService {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::adapters::outbound::persistence::db_pool::create_pool;
+    use crate::adapters::outbound::{
+        persistence::{
+            DieselEmbeddingRepository, DieselNoteChunkRepository, DieselNoteRepository,
+            DieselTopicRepository,
+        },
+        services::{FastEmbedService, PulldownMarkdownService},
+        storage::TokioFileStorage,
+    };
+    use crate::domain::ports::outbound::NoteRepository;
+    use crate::infrastructure::run_migrations;
+    use crate::shared::database::{create_pool, DatabaseConfig};
+
+    async fn setup() -> (Fts5SearchService, DieselNoteRepository) {
+        let pool = Arc::new(create_pool(DatabaseConfig::in_memory()).unwrap());
+        run_migrations(&pool).unwrap();
+
+        // Embedding model is never initialised, so hybrid search runs on FTS alone
+        let embedding_service = Arc::new(FastEmbedService::new(
+            Arc::new(DieselNoteRepository::new(pool.clone())),
+            Arc::new(DieselNoteChunkRepository::new(pool.clone())),
+            Arc::new(DieselEmbeddingRepository::new(pool.clone())),
+            Arc::new(DieselTopicRepository::new(pool.clone())),
+        ));
+        let service = Fts5SearchService::new(
+            pool.clone(),
+            Arc::new(TokioFileStorage::new()),
+            Arc::new(PulldownMarkdownService::new()),
+            embedding_service,
+        );
+        (service, DieselNoteRepository::new(pool))
+    }
+
+    async fn create_indexed_note(
+        service: &Fts5SearchService,
+        repo: &DieselNoteRepository,
+        title: &str,
+        content: &str,
+    ) -> Note {
+        let note = Note::new(title, None).unwrap();
+        repo.save(&note).await.unwrap();
+        service.index_note(&note.id, title, content).await.unwrap();
+        note
+    }
+
+    #[test]
+    fn test_build_match_expression() {
+        let none = SearchOptions::default();
+        assert_eq!(Fts5SearchService::build_match_expression("   ", &none), None);
+        assert_eq!(
+            Fts5SearchService::build_match_expression("rust async", &none),
+            Some("(\"rust\" \"async\"*)".to_string())
+        );
+        // FTS5 operators in user input are neutralised
+        assert_eq!(
+            Fts5SearchService::build_match_expression("a\"b OR", &none),
+            Some("(\"ab\" \"OR\"*)".to_string())
+        );
+
+        let options = SearchOptions {
+            phrases: Some(vec!["exact phrase".to_string()]),
+            excluded_terms: Some(vec!["draft".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            Fts5SearchService::build_match_expression("", &options),
+            Some("(\"exact phrase\") NOT \"draft\"".to_string())
+        );
+    }
 
     #[tokio::test]
-    async fn test_stub_search_service() {
-        let pool = Arc::new(create_pool(":memory:").unwrap());
-        let service = StubSearchService::new(pool);
+    async fn test_search_ranks_title_matches_first() {
+        let (service, repo) = setup().await;
 
-        // Test that stub methods return empty results
-        let results = service
-            .search_full_text("test", None)
-            .await
-            .unwrap();
+        let body_note =
+            create_indexed_note(&service, &repo, "Groceries", "# Groceries\n\nBuy rust remover").await;
+        let title_note =
+            create_indexed_note(&service, &repo, "Rust Ownership", "# Rust Ownership\n\nBorrowing rules").await;
+
+        let results = service.search_full_text("rust", None).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].note.id, title_note.id);
+        assert_eq!(results[0].match_type, SearchMatchType::Title);
+        assert_eq!(results[1].note.id, body_note.id);
+        assert_eq!(results[1].match_type, SearchMatchType::Content);
+
+        let highlights = results[1].highlights.as_ref().unwrap();
+        assert!(highlights.content.as_ref().unwrap().contains("<mark>rust</mark>"));
+        assert!(highlights.title.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prefix_and_stemming() {
+        let (service, repo) = setup().await;
+        create_indexed_note(&service, &repo, "Meeting", "Discussed running costs").await;
+
+        assert_eq!(service.search_full_text("run", None).await.unwrap().len(), 1);
+        assert_eq!(service.search_full_text("cos", None).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_options_and_pagination() {
+        let (service, repo) = setup().await;
+        for i in 0..3 {
+            create_indexed_note(&service, &repo, &format!("Note {}", i), "shared keyword").await;
+        }
+
+        let options = SearchOptions {
+            limit: Some(2),
+            offset: Some(2),
+            ..Default::default()
+        };
+        let results = service.search_full_text("keyword", Some(options)).await.unwrap();
+        assert_eq!(results.len(), 1);
+
+        let options = SearchOptions {
+            workspace_id: Some("other".to_string()),
+            ..Default::default()
+        };
+        let results = service.search_full_text("keyword", Some(options)).await.unwrap();
         assert!(results.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_reindex_and_remove() {
+        let (service, repo) = setup().await;
+        let note = create_indexed_note(&service, &repo, "Draft", "alpha").await;
+
+        service.index_note(&note.id, "Draft", "beta").await.unwrap();
+        assert!(service.search_full_text("alpha", None).await.unwrap().is_empty());
+        assert_eq!(service.search_full_text("beta", None).await.unwrap().len(), 1);
+
+        service.remove_from_index(&note.id).await.unwrap();
+        assert!(service.search_full_text("beta", None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deleted_notes_are_excluded() {
+        let (service, repo) = setup().await;
+        let mut note = create_indexed_note(&service, &repo, "Old", "obsolete content").await;
+
+        note.delete();
+        repo.save(&note).await.unwrap();
+
+        assert!(service.search_full_text("obsolete", None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_phrases_exclusions_and_filter_only_queries() {
+        let (service, repo) = setup().await;
+        let mut pinned =
+            create_indexed_note(&service, &repo, "Plan", "the exact phrase lives here").await;
+        pinned.pin();
+        repo.save(&pinned).await.unwrap();
+        create_indexed_note(&service, &repo, "Draft plan", "phrase exact but reordered draft").await;
+
+        let options = SearchOptions {
+            phrases: Some(vec!["exact phrase".to_string()]),
+            ..Default::default()
+        };
+        let results = service.search_full_text("", Some(options)).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].note.id, pinned.id);
+
+        let options = SearchOptions {
+            excluded_terms: Some(vec!["draft".to_string()]),
+            ..Default::default()
+        };
+        let results = service.search_full_text("plan", Some(options)).await.unwrap();
+        assert_eq!(results.len(), 1);
+
+        // Filters alone list matching notes without highlights
+        let options = SearchOptions {
+            is_pinned: Some(true),
+            ..Default::default()
+        };
+        let results = service.search_full_text("", Some(options)).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].note.id, pinned.id);
+        assert!(results[0].highlights.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_without_embeddings_uses_fts() {
+        let (service, repo) = setup().await;
+        create_indexed_note(&service, &repo, "Alpha", "hybrid retrieval").await;
+        create_indexed_note(&service, &repo, "Beta", "hybrid ranking").await;
+
+        let options = HybridSearchOptions {
+            base: SearchOptions {
+                limit: Some(1),
+                offset: Some(1),
+                ..Default::default()
+            },
+            weights: None,
+            fusion: None,
+        };
+        let results = service.search_hybrid("hybrid", Some(options)).await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].retrievers, vec![SearchRetriever::Fts]);
+        assert!(results[0].relevance > 0.0);
+    }
 }
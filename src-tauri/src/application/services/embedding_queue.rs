@@ -0,0 +1,517 @@
+/// Embedding Queue
+///
+/// Background worker that brings stored embeddings up to date in bulk. A run
+/// scans every note, compares the hash of its passages with the hash recorded
+/// with its current-model embedding, and embeds the notes that are missing or
+/// out of date in batches. Time spent embedding is capped to a share of wall
+/// time so a large backlog doesn't monopolise the CPU. Runs can be paused,
+/// resumed and cancelled; progress is reported through
+/// `DomainEvent::EmbeddingQueueProgress`.
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::Notify;
+
+use super::{NoteEmbedder, PreparedNote};
+use crate::domain::{
+    entities::Note,
+    errors::{DomainError, DomainResult},
+    ports::outbound::{
+        DomainEvent, EmbeddingQueueState, EmbeddingQueueStatus, EmbeddingService, EventPublisher,
+        FileStorage, MarkdownProcessor, NoteFindOptions, NoteRepository, WorkspaceRepository,
+    },
+};
+
+/// Default number of passages embedded per `generate_embeddings` call
+pub const DEFAULT_EMBEDDING_BATCH_SIZE: usize = 32;
+
+/// Default share of wall time spent embedding, in percent
+pub const DEFAULT_MAX_CPU_PERCENT: u8 = 50;
+
+/// How a queue run batches and throttles its work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmbeddingQueueOptions {
+    /// Passages per batch; a note's passages always share a batch, so a long
+    /// note can make a batch larger than this
+    pub batch_size: usize,
+    /// Share of wall time spent embedding (1-100); the worker sleeps in between
+    pub max_cpu_percent: u8,
+}
+
+impl Default for EmbeddingQueueOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_EMBEDDING_BATCH_SIZE,
+            max_cpu_percent: DEFAULT_MAX_CPU_PERCENT,
+        }
+    }
+}
+
+impl EmbeddingQueueOptions {
+    /// Build options from optional overrides, rejecting values out of range
+    pub fn new(batch_size: Option<usize>, max_cpu_percent: Option<u8>) -> DomainResult<Self> {
+        let defaults = Self::default();
+        let options = Self {
+            batch_size: batch_size.unwrap_or(defaults.batch_size),
+            max_cpu_percent: max_cpu_percent.unwrap_or(defaults.max_cpu_percent),
+        };
+
+        if options.batch_size == 0 {
+            return Err(DomainError::ValidationError(
+                "Batch size must be at least 1".to_string(),
+            ));
+        }
+        if !(1..=100).contains(&options.max_cpu_percent) {
+            return Err(DomainError::ValidationError(
+                "CPU limit must be between 1 and 100 percent".to_string(),
+            ));
+        }
+        Ok(options)
+    }
+
+    /// Pause after spending `busy` embedding, so embedding stays within the limit
+    fn throttle_delay(&self, busy: Duration) -> Duration {
+        let percent = u32::from(self.max_cpu_percent.clamp(1, 100));
+        busy * (100 - percent) / percent
+    }
+}
+
+/// Run state shared between the worker and the controls
+#[derive(Default)]
+struct QueueControl {
+    status: EmbeddingQueueStatus,
+    cancelled: bool,
+    /// Options of a run to start once the cancelled one stops
+    restart: Option<EmbeddingQueueOptions>,
+}
+
+/// Background embedding worker with pause, resume and cancel
+pub struct EmbeddingQueue {
+    note_repository: Arc<dyn NoteRepository>,
+    workspace_repository: Arc<dyn WorkspaceRepository>,
+    file_storage: Arc<dyn FileStorage>,
+    embedding_service: Arc<dyn EmbeddingService>,
+    embedder: NoteEmbedder,
+    event_publisher: Arc<dyn EventPublisher>,
+    control: Mutex<QueueControl>,
+    /// Wakes a paused worker on resume or cancel
+    resumed: Notify,
+}
+
+impl EmbeddingQueue {
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        workspace_repository: Arc<dyn WorkspaceRepository>,
+        file_storage: Arc<dyn FileStorage>,
+        markdown_processor: Arc<dyn MarkdownProcessor>,
+        embedding_service: Arc<dyn EmbeddingService>,
+        event_publisher: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            note_repository,
+            workspace_repository,
+            file_storage,
+            embedder: NoteEmbedder::new(embedding_service.clone(), markdown_processor),
+            embedding_service,
+            event_publisher,
+            control: Mutex::new(QueueControl::default()),
+            resumed: Notify::new(),
+        }
+    }
+
+    /// State and progress of the current run
+    pub fn status(&self) -> EmbeddingQueueStatus {
+        self.control.lock().unwrap().status.clone()
+    }
+
+    /// Start a run in the background; a run already in progress carries on
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn start(
+        self: &Arc<Self>,
+        options: EmbeddingQueueOptions,
+    ) -> DomainResult<EmbeddingQueueStatus> {
+        if !self.embedding_service.is_ready() {
+            return Err(DomainError::ValidationError(
+                "Embedding model is not ready".to_string(),
+            ));
+        }
+
+        let status = {
+            let mut control = self.control.lock().unwrap();
+            if control.status.state != EmbeddingQueueState::Idle {
+                return Ok(control.status.clone());
+            }
+            control.cancelled = false;
+            control.status = EmbeddingQueueStatus {
+                state: EmbeddingQueueState::Running,
+                ..Default::default()
+            };
+            control.status.clone()
+        };
+        self.publish(&status);
+
+        let queue = self.clone();
+        tokio::spawn(async move {
+            let mut options = options;
+            loop {
+                if let Err(e) = queue.run(options).await {
+                    tracing::warn!("[EmbeddingQueue] Run stopped: {}", e);
+                }
+                match queue.finish() {
+                    Some(next) => options = next,
+                    None => break,
+                }
+            }
+        });
+
+        Ok(status)
+    }
+
+    /// Start a run from a fresh scan, cancelling the run in progress
+    ///
+    /// A run in progress scanned with the previous model, so after a model
+    /// change it stops after its current batch and a new run takes over.
+    pub fn restart(
+        self: &Arc<Self>,
+        options: EmbeddingQueueOptions,
+    ) -> DomainResult<EmbeddingQueueStatus> {
+        if !self.embedding_service.is_ready() {
+            return Err(DomainError::ValidationError(
+                "Embedding model is not ready".to_string(),
+            ));
+        }
+
+        let running = {
+            let mut control = self.control.lock().unwrap();
+            if control.status.state == EmbeddingQueueState::Idle {
+                None
+            } else {
+                control.cancelled = true;
+                control.restart = Some(options);
+                Some(control.status.clone())
+            }
+        };
+        match running {
+            Some(status) => {
+                self.resumed.notify_waiters();
+                Ok(status)
+            }
+            None => self.start(options),
+        }
+    }
+
+    /// Pause after the batch in progress
+    pub fn pause(&self) -> EmbeddingQueueStatus {
+        self.transition(EmbeddingQueueState::Running, EmbeddingQueueState::Paused)
+    }
+
+    /// Continue a paused run
+    pub fn resume(&self) -> EmbeddingQueueStatus {
+        let status = self.transition(EmbeddingQueueState::Paused, EmbeddingQueueState::Running);
+        self.resumed.notify_waiters();
+        status
+    }
+
+    /// Stop the run after the batch in progress; notes already embedded stay so
+    pub fn cancel(&self) -> EmbeddingQueueStatus {
+        let status = {
+            let mut control = self.control.lock().unwrap();
+            if control.status.state != EmbeddingQueueState::Idle {
+                control.cancelled = true;
+                control.restart = None;
+            }
+            control.status.clone()
+        };
+        self.resumed.notify_waiters();
+        status
+    }
+
+    fn transition(
+        &self,
+        from: EmbeddingQueueState,
+        to: EmbeddingQueueState,
+    ) -> EmbeddingQueueStatus {
+        let (status, changed) = {
+            let mut control = self.control.lock().unwrap();
+            let changed = control.status.state == from && !control.cancelled;
+            if changed {
+                control.status.state = to;
+            }
+            (control.status.clone(), changed)
+        };
+        if changed {
+            self.publish(&status);
+        }
+        status
+    }
+
+    /// Scan for pending notes, then embed them batch by batch
+    async fn run(&self, options: EmbeddingQueueOptions) -> DomainResult<()> {
+        let pending = self.find_pending().await?;
+        tracing::info!("[EmbeddingQueue] {} notes to embed", pending.len());
+        let status = self.update(|status| status.total = pending.len() as i32);
+        self.publish(&status);
+
+        let mut batch: Vec<PreparedNote> = Vec::new();
+        let mut batch_inputs = 0;
+        for note in &pending {
+            if !self.checkpoint().await {
+                return Ok(());
+            }
+
+            // Read again: the note may have changed since the scan
+            let prepared = match self.read_content(note).await {
+                Ok(markdown) => self
+                    .embedder
+                    .prepare(&note.id, &markdown.unwrap_or_default()),
+                Err(e) => Err(e),
+            };
+            match prepared {
+                Ok(prepared) if prepared.is_empty() => {
+                    let result = self.embedder.store(prepared, Vec::new()).await;
+                    self.record(&note.id, result);
+                }
+                Ok(prepared) => {
+                    batch_inputs += prepared.inputs().len();
+                    batch.push(prepared);
+                    if batch_inputs >= options.batch_size {
+                        self.embed_batch(std::mem::take(&mut batch), options).await;
+                        batch_inputs = 0;
+                    }
+                }
+                Err(e) => self.record(&note.id, Err(e)),
+            }
+        }
+
+        if !batch.is_empty() && self.checkpoint().await {
+            self.embed_batch(batch, options).await;
+        }
+        Ok(())
+    }
+
+    /// Notes without an up-to-date embedding from the current model
+    ///
+    /// Trashed notes are left out. A note that can't be read or split into
+    /// passages is skipped with a warning rather than ending the scan.
+    async fn find_pending(&self) -> DomainResult<Vec<Note>> {
+        let notes = self
+            .note_repository
+            .find_all(NoteFindOptions {
+                is_deleted: Some(false),
+                ..Default::default()
+            })
+            .await?;
+        let note_ids: Vec<String> = notes.iter().map(|note| note.id.clone()).collect();
+        let hashes = self.embedding_service.get_content_hashes(&note_ids).await?;
+
+        let mut pending = Vec::new();
+        for note in notes {
+            if !self.checkpoint().await {
+                return Ok(Vec::new());
+            }
+
+            let markdown = match self.read_content(&note).await {
+                Ok(markdown) => markdown.unwrap_or_default(),
+                Err(e) => {
+                    tracing::warn!("[EmbeddingQueue] Could not read note {}: {}", note.id, e);
+                    continue;
+                }
+            };
+            let prepared = match self.embedder.prepare(&note.id, &markdown) {
+                Ok(prepared) => prepared,
+                Err(e) => {
+                    tracing::warn!("[EmbeddingQueue] Could not prepare note {}: {}", note.id, e);
+                    continue;
+                }
+            };
+            let stored_hash = hashes.get(&note.id);
+
+            // Emptied notes only need their old embedding removed
+            let stale = if prepared.is_empty() {
+                stored_hash.is_some()
+            } else {
+                stored_hash != Some(&prepared.content_hash)
+            };
+            if stale {
+                pending.push(note);
+            }
+        }
+
+        Ok(pending)
+    }
+
+    /// Embed several notes with one `generate_embeddings` call, then throttle
+    async fn embed_batch(&self, batch: Vec<PreparedNote>, options: EmbeddingQueueOptions) {
+        let started = Instant::now();
+        let inputs: Vec<String> = batch
+            .iter()
+            .flat_map(|prepared| prepared.inputs().iter().cloned())
+            .collect();
+        let expected = inputs.len();
+
+        match self.embedding_service.generate_embeddings(inputs).await {
+            Ok(mut embeddings) if embeddings.len() == expected => {
+                for prepared in batch {
+                    let rest = embeddings.split_off(prepared.inputs().len());
+                    let note_embeddings = std::mem::replace(&mut embeddings, rest);
+                    let note_id = prepared.note_id.clone();
+                    let result = self.embedder.store(prepared, note_embeddings).await;
+                    self.record(&note_id, result);
+                }
+            }
+            Ok(embeddings) => {
+                let error = DomainError::InternalError(format!(
+                    "Expected {} embeddings, got {}",
+                    expected,
+                    embeddings.len()
+                ));
+                self.record_batch_failure(&batch, &error);
+            }
+            Err(error) => self.record_batch_failure(&batch, &error),
+        }
+
+        self.publish(&self.status());
+        tokio::time::sleep(options.throttle_delay(started.elapsed())).await;
+    }
+
+    fn record_batch_failure(&self, batch: &[PreparedNote], error: &DomainError) {
+        tracing::warn!(
+            "[EmbeddingQueue] Failed to embed a batch of {} notes: {}",
+            batch.len(),
+            error
+        );
+        self.update(|status| status.failed += batch.len() as i32);
+    }
+
+    fn record(&self, note_id: &str, result: DomainResult<()>) {
+        match result {
+            Ok(()) => {
+                self.update(|status| status.processed += 1);
+            }
+            Err(e) => {
+                tracing::warn!("[EmbeddingQueue] Failed to embed note {}: {}", note_id, e);
+                self.update(|status| status.failed += 1);
+            }
+        }
+    }
+
+    /// Wait while paused; false once the run is cancelled
+    async fn checkpoint(&self) -> bool {
+        loop {
+            // Register before checking, so a resume in between isn't missed
+            let resumed = self.resumed.notified();
+            {
+                let control = self.control.lock().unwrap();
+                if control.cancelled {
+                    return false;
+                }
+                if control.status.state != EmbeddingQueueState::Paused {
+                    return true;
+                }
+            }
+            resumed.await;
+        }
+    }
+
+    /// Return to idle and report the final counts
+    ///
+    /// Returns the options of the next run when a restart was requested.
+    fn finish(&self) -> Option<EmbeddingQueueOptions> {
+        let (finished, restart) = {
+            let mut control = self.control.lock().unwrap();
+            control.cancelled = false;
+            let finished = control.status.clone();
+            let restart = control.restart.take();
+            control.status = match restart {
+                Some(_) => EmbeddingQueueStatus {
+                    state: EmbeddingQueueState::Running,
+                    ..Default::default()
+                },
+                None => EmbeddingQueueStatus {
+                    state: EmbeddingQueueState::Idle,
+                    ..finished.clone()
+                },
+            };
+            (finished, restart)
+        };
+        tracing::info!(
+            "[EmbeddingQueue] Embedded {}/{} notes ({} failed)",
+            finished.processed,
+            finished.total,
+            finished.failed
+        );
+        if restart.is_some() {
+            tracing::info!("[EmbeddingQueue] Restarting with a fresh scan");
+        }
+        self.publish(&self.status());
+        restart
+    }
+
+    fn update(&self, change: impl FnOnce(&mut EmbeddingQueueStatus)) -> EmbeddingQueueStatus {
+        let mut control = self.control.lock().unwrap();
+        change(&mut control.status);
+        control.status.clone()
+    }
+
+    fn publish(&self, status: &EmbeddingQueueStatus) {
+        self.event_publisher
+            .publish(DomainEvent::EmbeddingQueueProgress {
+                timestamp: chrono::Utc::now(),
+                state: status.state,
+                processed: status.processed,
+                total: status.total,
+                failed: status.failed,
+            });
+    }
+
+    async fn read_content(&self, note: &Note) -> DomainResult<Option<String>> {
+        let (Some(file_path), Some(workspace_id)) = (&note.file_path, &note.workspace_id) else {
+            return Ok(None);
+        };
+
+        let Some(workspace) = self.workspace_repository.find_by_id(workspace_id).await? else {
+            return Ok(None);
+        };
+
+        let absolute_path = Path::new(&workspace.folder_path).join(file_path);
+        self.file_storage
+            .read(&absolute_path.to_string_lossy())
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_are_validated() {
+        assert_eq!(
+            EmbeddingQueueOptions::new(None, None).unwrap(),
+            EmbeddingQueueOptions::default()
+        );
+        let options = EmbeddingQueueOptions::new(Some(8), Some(100)).unwrap();
+        assert_eq!(options.batch_size, 8);
+        assert_eq!(options.max_cpu_percent, 100);
+
+        assert!(EmbeddingQueueOptions::new(Some(0), None).is_err());
+        assert!(EmbeddingQueueOptions::new(None, Some(0)).is_err());
+        assert!(EmbeddingQueueOptions::new(None, Some(101)).is_err());
+    }
+
+    #[test]
+    fn test_throttle_keeps_embedding_within_cpu_share() {
+        let busy = Duration::from_millis(300);
+        let delay = |percent| {
+            EmbeddingQueueOptions::new(None, Some(percent))
+                .unwrap()
+                .throttle_delay(busy)
+        };
+
+        assert_eq!(delay(100), Duration::ZERO);
+        assert_eq!(delay(50), busy);
+        assert_eq!(delay(25), Duration::from_millis(900));
+    }
+}
@@ -0,0 +1,426 @@
+/// Note Indexing Service
+///
+/// Keeps the full-text index, the `^block-id` blocks of each note and stored
+/// embeddings in step with note changes.
+/// Subscribes to note and file sync events; re-embedding after a model change
+/// is left to the embedding queue, which batches and throttles it. Coalesces
+/// bursts of events per note and re-indexes each note once it has been quiet
+/// for the debounce window. When a burst overflows the event channel, every
+/// note is re-indexed, since the missed changes can't be told apart.
+/// Progress is reported through `DomainEvent::IndexingProgress`.
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use super::NoteEmbedder;
+use crate::domain::{
+    entities::{Note, NoteBlock},
+    errors::DomainResult,
+    ports::outbound::{
+        DomainEvent, EmbeddingService, EventPublisher, FileStorage, FileSyncOperation,
+        MarkdownProcessor, NoteBlockRepository, NoteFindOptions, NoteRepository, SearchEngine,
+        WorkspaceRepository,
+    },
+    services::NoteAnchors,
+};
+
+/// Default quiet period before a changed note is re-indexed
+pub const DEFAULT_INDEX_DEBOUNCE_MS: u64 = 500;
+
+/// Events that trigger re-indexing
+const INDEXED_EVENTS: &[&str] = &[
+    "note:created",
+    "note:updated",
+    "note:deleted",
+    "file:synced",
+];
+
+/// Pending work and progress counters for the current burst of changes
+#[derive(Default)]
+struct IndexingState {
+    /// Latest scheduled generation per note; older timers are ignored
+    pending: HashMap<String, u64>,
+    next_generation: u64,
+    total: i32,
+    processed: i32,
+    failed: i32,
+    /// A full re-index is waiting to start
+    rebuild_scheduled: bool,
+}
+
+/// Background indexer driven by domain events
+pub struct NoteIndexingService {
+    note_repository: Arc<dyn NoteRepository>,
+    workspace_repository: Arc<dyn WorkspaceRepository>,
+    block_repository: Arc<dyn NoteBlockRepository>,
+    file_storage: Arc<dyn FileStorage>,
+    search_engine: Arc<dyn SearchEngine>,
+    embedding_service: Arc<dyn EmbeddingService>,
+    embedder: NoteEmbedder,
+    event_publisher: Arc<dyn EventPublisher>,
+    debounce: Duration,
+    state: Mutex<IndexingState>,
+    subscriptions: Mutex<Vec<Box<dyn Fn() + Send + Sync>>>,
+}
+
+impl NoteIndexingService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        workspace_repository: Arc<dyn WorkspaceRepository>,
+        block_repository: Arc<dyn NoteBlockRepository>,
+        file_storage: Arc<dyn FileStorage>,
+        markdown_processor: Arc<dyn MarkdownProcessor>,
+        search_engine: Arc<dyn SearchEngine>,
+        embedding_service: Arc<dyn EmbeddingService>,
+        event_publisher: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            note_repository,
+            workspace_repository,
+            block_repository,
+            file_storage,
+            embedder: NoteEmbedder::new(embedding_service.clone(), markdown_processor),
+            search_engine,
+            embedding_service,
+            event_publisher,
+            debounce: Duration::from_millis(DEFAULT_INDEX_DEBOUNCE_MS),
+            state: Mutex::new(IndexingState::default()),
+            subscriptions: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Override the debounce window
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Subscribe to note and file sync events
+    ///
+    /// Must be called from within a Tokio runtime. Calling it again is a no-op.
+    pub fn start(self: &Arc<Self>) {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        if !subscriptions.is_empty() {
+            return;
+        }
+
+        for event_type in INDEXED_EVENTS {
+            // Hold a weak reference so the subscriptions don't keep the service alive
+            let indexer: Weak<Self> = Arc::downgrade(self);
+            let lagging: Weak<Self> = Arc::downgrade(self);
+            let unsubscribe = self.event_publisher.subscribe_with_lag(
+                event_type,
+                Arc::new(move |event| {
+                    if let Some(indexer) = indexer.upgrade() {
+                        indexer.handle_event(event);
+                    }
+                }),
+                Arc::new(move |_missed| {
+                    if let Some(indexer) = lagging.upgrade() {
+                        indexer.schedule_rebuild();
+                    }
+                }),
+            );
+            subscriptions.push(unsubscribe);
+        }
+
+        tracing::info!("[NoteIndexingService] Listening for note changes");
+    }
+
+    /// Unsubscribe from all events; pending timers still run to completion
+    pub fn stop(&self) {
+        for unsubscribe in self.subscriptions.lock().unwrap().drain(..) {
+            unsubscribe();
+        }
+    }
+
+    /// Number of notes waiting to be indexed
+    pub fn pending_count(&self) -> usize {
+        self.state.lock().unwrap().pending.len()
+    }
+
+    fn handle_event(self: &Arc<Self>, event: DomainEvent) {
+        match event {
+            DomainEvent::NoteCreated { id, .. }
+            | DomainEvent::NoteUpdated { id, .. }
+            | DomainEvent::NoteDeleted { id, .. } => self.schedule(id),
+            DomainEvent::FileSynced {
+                file_path,
+                operation,
+                ..
+            } => {
+                if !file_path.ends_with(".md") {
+                    return;
+                }
+                let indexer = self.clone();
+                tokio::spawn(async move {
+                    match indexer.resolve_file(&file_path).await {
+                        Ok(Some(note_id)) => indexer.schedule(note_id),
+                        Ok(None) => {
+                            // Untracked files are picked up by the next workspace sync
+                            if operation != FileSyncOperation::Deleted {
+                                tracing::debug!(
+                                    "[NoteIndexingService] No note for synced file {}",
+                                    file_path
+                                );
+                            }
+                        }
+                        Err(e) => tracing::warn!(
+                            "[NoteIndexingService] Failed to resolve {}: {}",
+                            file_path,
+                            e
+                        ),
+                    }
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// (Re)start the debounce timer for a note
+    fn schedule(self: &Arc<Self>, note_id: String) {
+        let generation = {
+            let mut state = self.state.lock().unwrap();
+            state.next_generation += 1;
+            let generation = state.next_generation;
+            if state.pending.insert(note_id.clone(), generation).is_none() {
+                state.total += 1;
+            }
+            generation
+        };
+
+        let indexer = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(indexer.debounce).await;
+
+            if !indexer.is_current(&note_id, generation) {
+                return; // Superseded by a later change
+            }
+
+            let result = indexer.index_note(&note_id).await;
+            if let Err(ref e) = result {
+                tracing::warn!(
+                    "[NoteIndexingService] Failed to index note {}: {}",
+                    note_id,
+                    e
+                );
+            }
+            indexer.finish(&note_id, generation, result.is_ok());
+        });
+    }
+
+    /// Re-index every note once the burst that overflowed the channel settles
+    fn schedule_rebuild(self: &Arc<Self>) {
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.rebuild_scheduled {
+                return; // Every event type lags in the same burst
+            }
+            state.rebuild_scheduled = true;
+        }
+
+        let indexer = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(indexer.debounce).await;
+            indexer.state.lock().unwrap().rebuild_scheduled = false;
+
+            if let Err(e) = indexer.rebuild().await {
+                tracing::warn!("[NoteIndexingService] Failed to re-index notes: {}", e);
+            }
+        });
+    }
+
+    /// Index every note, trashed ones included, one at a time
+    async fn rebuild(&self) -> DomainResult<()> {
+        let notes = self
+            .note_repository
+            .find_all(NoteFindOptions {
+                is_deleted: None,
+                ..Default::default()
+            })
+            .await?;
+        tracing::warn!(
+            "[NoteIndexingService] Missed note changes, re-indexing {} notes",
+            notes.len()
+        );
+
+        let jobs: Vec<(String, u64)> = {
+            let mut state = self.state.lock().unwrap();
+            notes
+                .into_iter()
+                .map(|note| {
+                    state.next_generation += 1;
+                    let generation = state.next_generation;
+                    if state.pending.insert(note.id.clone(), generation).is_none() {
+                        state.total += 1;
+                    }
+                    (note.id, generation)
+                })
+                .collect()
+        };
+
+        for (note_id, generation) in jobs {
+            if !self.is_current(&note_id, generation) {
+                continue; // Changed again; its own timer indexes it
+            }
+            let result = self.index_note(&note_id).await;
+            if let Err(ref e) = result {
+                tracing::warn!(
+                    "[NoteIndexingService] Failed to index note {}: {}",
+                    note_id,
+                    e
+                );
+            }
+            self.finish(&note_id, generation, result.is_ok());
+        }
+
+        Ok(())
+    }
+
+    fn is_current(&self, note_id: &str, generation: u64) -> bool {
+        self.state.lock().unwrap().pending.get(note_id) == Some(&generation)
+    }
+
+    /// Record a finished job and publish progress for the current burst
+    fn finish(&self, note_id: &str, generation: u64, succeeded: bool) {
+        let (processed, total, failed) = {
+            let mut state = self.state.lock().unwrap();
+            if state.pending.get(note_id) != Some(&generation) {
+                return; // Changed again while indexing; the newer job reports
+            }
+            state.pending.remove(note_id);
+            if succeeded {
+                state.processed += 1;
+            } else {
+                state.failed += 1;
+            }
+            let snapshot = (state.processed, state.total, state.failed);
+            if state.pending.is_empty() {
+                state.total = 0;
+                state.processed = 0;
+                state.failed = 0;
+            }
+            snapshot
+        };
+
+        self.event_publisher.publish(DomainEvent::IndexingProgress {
+            timestamp: chrono::Utc::now(),
+            note_id: note_id.to_string(),
+            processed,
+            total,
+            failed,
+        });
+    }
+
+    /// Bring the index and embedding for one note up to date
+    async fn index_note(&self, note_id: &str) -> DomainResult<()> {
+        let note = self.note_repository.find_by_id(note_id).await?;
+
+        let content = match note {
+            Some(ref note) if !note.is_deleted => self.read_content(note).await?,
+            _ => None,
+        };
+
+        match (note, content) {
+            (Some(note), Some(content)) => {
+                self.search_engine
+                    .index_note(&note.id, &note.title, &content)
+                    .await?;
+
+                let blocks = NoteAnchors::extract_blocks(&content)
+                    .iter()
+                    .map(|block| NoteBlock::new(&note.id, block))
+                    .collect::<DomainResult<Vec<_>>>()?;
+                self.block_repository
+                    .replace_for_note(&note.id, blocks)
+                    .await?;
+
+                if self.embedding_service.is_ready()
+                    && self.embedding_is_stale(&note.id, &content).await?
+                {
+                    self.embedder.embed_note(&note.id, &content).await?;
+                }
+            }
+            (note, _) => {
+                // Deleted note or missing file
+                self.search_engine.remove_from_index(note_id).await?;
+
+                // Also drops vectors of an earlier model, which get_embedding ignores
+                if let Some(note) = note {
+                    self.block_repository.delete_for_note(&note.id).await?;
+                    self.embedding_service.delete_embedding(&note.id).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether a note's embedding is missing or older than its text
+    ///
+    /// Unchanged notes keep their embedding, so a full re-index only embeds
+    /// what actually changed.
+    async fn embedding_is_stale(&self, note_id: &str, content: &str) -> DomainResult<bool> {
+        let prepared = self.embedder.prepare(note_id, content)?;
+        let hashes = self
+            .embedding_service
+            .get_content_hashes(&[note_id.to_string()])
+            .await?;
+        let stored_hash = hashes.get(note_id);
+
+        Ok(if prepared.is_empty() {
+            stored_hash.is_some()
+        } else {
+            stored_hash != Some(&prepared.content_hash)
+        })
+    }
+
+    async fn read_content(&self, note: &Note) -> DomainResult<Option<String>> {
+        let (Some(file_path), Some(workspace_id)) = (&note.file_path, &note.workspace_id) else {
+            return Ok(None);
+        };
+
+        let Some(workspace) = self.workspace_repository.find_by_id(workspace_id).await? else {
+            return Ok(None);
+        };
+
+        let absolute_path = Path::new(&workspace.folder_path).join(file_path);
+        self.file_storage
+            .read(&absolute_path.to_string_lossy())
+            .await
+    }
+
+    /// Map an absolute file path from the watcher to a note ID
+    async fn resolve_file(&self, file_path: &str) -> DomainResult<Option<String>> {
+        let path = Path::new(file_path);
+
+        for workspace in self.workspace_repository.find_all().await? {
+            let Ok(relative) = path.strip_prefix(&workspace.folder_path) else {
+                continue;
+            };
+            let relative = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            if let Some(note) = self
+                .note_repository
+                .find_by_file_path(&relative, Some(&workspace.id))
+                .await?
+            {
+                return Ok(Some(note.id));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl Drop for NoteIndexingService {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
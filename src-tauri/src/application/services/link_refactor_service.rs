@@ -0,0 +1,253 @@
+/// Link Refactor Service
+///
+/// Plans and applies the file rewrites that keep links working when notes are
+/// renamed or folders move. A plan holds each file's content as read, so it can
+/// be previewed first; applying it writes every file or, when a write fails,
+/// puts back the ones already written.
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::domain::{
+    entities::{Note, Workspace},
+    errors::{DomainError, DomainResult},
+    ports::{inbound::LineEdit, outbound::FileStorage},
+    services::RelativeLinks,
+};
+
+/// A file and the content it is rewritten to
+#[derive(Debug, Clone)]
+pub struct FileRewrite {
+    /// Absolute path the file is written to
+    pub path: String,
+    /// Relative to the workspace root, after any move
+    pub file_path: String,
+    pub original: String,
+    pub updated: String,
+}
+
+impl FileRewrite {
+    /// Lines that differ
+    ///
+    /// Rewriting links keeps every line; a rewritten tags list may lose one.
+    /// Between the unchanged lines at the start and the end, lines are paired
+    /// in order and a line with no counterpart shows as empty.
+    pub fn edits(&self) -> Vec<LineEdit> {
+        let before: Vec<&str> = self.original.lines().collect();
+        let after: Vec<&str> = self.updated.lines().collect();
+        let prefix = before
+            .iter()
+            .zip(&after)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let suffix = before[prefix..]
+            .iter()
+            .rev()
+            .zip(after[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let (before, after) = (
+            &before[prefix..before.len() - suffix],
+            &after[prefix..after.len() - suffix],
+        );
+
+        (0..before.len().max(after.len()))
+            .map(|index| (index, before.get(index), after.get(index)))
+            .filter(|(_, before, after)| before != after)
+            .map(|(index, before, after)| LineEdit {
+                line_number: prefix + index + 1,
+                before: before.copied().unwrap_or_default().to_string(),
+                after: after.copied().unwrap_or_default().to_string(),
+            })
+            .collect()
+    }
+}
+
+/// A file or folder that moved, with the rewrites that followed it
+#[derive(Debug, Clone)]
+pub struct FileMove {
+    /// Absolute path before the move
+    pub from: String,
+    /// Absolute path after the move
+    pub to: String,
+    pub rewrites: Vec<FileRewrite>,
+}
+
+pub struct LinkRefactorService {
+    file_storage: Arc<dyn FileStorage>,
+}
+
+impl LinkRefactorService {
+    pub fn new(file_storage: Arc<dyn FileStorage>) -> Self {
+        Self { file_storage }
+    }
+
+    /// Read a note's file and plan its rewrite
+    ///
+    /// `rewrite` returns the updated markdown, or `None` to leave the file be.
+    /// Notes without a file, or whose file is missing, are skipped.
+    pub async fn plan_note_rewrite(
+        &self,
+        workspace: &Workspace,
+        note: &Note,
+        rewrite: impl Fn(&str) -> Option<String>,
+    ) -> DomainResult<Option<FileRewrite>> {
+        let Some(file_path) = &note.file_path else {
+            return Ok(None);
+        };
+        let path = Path::new(&workspace.folder_path)
+            .join(file_path)
+            .to_string_lossy()
+            .to_string();
+        let Some(original) = self.file_storage.read(&path).await? else {
+            return Ok(None);
+        };
+
+        Ok(rewrite(&original).map(|updated| FileRewrite {
+            path,
+            file_path: file_path.clone(),
+            original,
+            updated,
+        }))
+    }
+
+    /// Rewrites keeping relative markdown links working once `from` moves to `to`
+    ///
+    /// Both the notes inside the moved file or folder and the notes linking
+    /// into it are rewritten. Call it before the move: files are read where
+    /// they are and written where they will be.
+    pub async fn plan_move(
+        &self,
+        workspace_folder: &str,
+        from: &str,
+        to: &str,
+    ) -> DomainResult<Vec<FileRewrite>> {
+        let mut rewrites = Vec::new();
+
+        for file_path in self.file_storage.glob("**/*.md", workspace_folder).await? {
+            let file_path = file_path.replace('\\', "/");
+            let new_location =
+                RelativeLinks::moved_path(&file_path, from, to).unwrap_or(file_path.clone());
+
+            let old_path = Path::new(workspace_folder).join(&file_path);
+            let Some(original) = self.file_storage.read(&old_path.to_string_lossy()).await? else {
+                continue;
+            };
+            let Some(updated) = RelativeLinks::rewrite(&original, &file_path, &new_location, |p| {
+                RelativeLinks::moved_path(p, from, to)
+            }) else {
+                continue;
+            };
+
+            rewrites.push(FileRewrite {
+                path: Path::new(workspace_folder)
+                    .join(&new_location)
+                    .to_string_lossy()
+                    .to_string(),
+                file_path: new_location,
+                original,
+                updated,
+            });
+        }
+
+        Ok(rewrites)
+    }
+
+    /// Move a file or folder, rewriting the relative links it breaks
+    ///
+    /// If the links can't be rewritten the move is undone. Paths outside the
+    /// workspace are moved without rewriting anything.
+    pub async fn move_path(
+        &self,
+        workspace_folder: &str,
+        from: &str,
+        to: &str,
+    ) -> DomainResult<FileMove> {
+        let relative = |path: &str| {
+            Path::new(path)
+                .strip_prefix(workspace_folder)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .ok()
+        };
+        let rewrites = match (relative(from), relative(to)) {
+            (Some(from), Some(to)) => self.plan_move(workspace_folder, &from, &to).await?,
+            _ => Vec::new(),
+        };
+
+        self.file_storage.rename(from, to).await?;
+
+        if let Err(error) = self.apply(&rewrites).await {
+            self.file_storage.rename(to, from).await?;
+            return Err(error);
+        }
+
+        Ok(FileMove {
+            from: from.to_string(),
+            to: to.to_string(),
+            rewrites,
+        })
+    }
+
+    /// Put back a move and the links rewritten for it
+    pub async fn undo_move(&self, file_move: &FileMove) {
+        self.revert(&file_move.rewrites).await;
+        if let Err(error) = self.file_storage.rename(&file_move.to, &file_move.from).await {
+            tracing::error!(
+                "[LinkRefactorService] Could not move {} back: {}",
+                file_move.to,
+                error
+            );
+        }
+    }
+
+    /// Write every rewrite, or none of them
+    ///
+    /// Fails without writing anything if a file changed since it was planned.
+    /// If a write fails, the files already written get their content back.
+    pub async fn apply(&self, rewrites: &[FileRewrite]) -> DomainResult<()> {
+        for rewrite in rewrites {
+            let current = self.file_storage.read(&rewrite.path).await?;
+            if current.as_deref() != Some(rewrite.original.as_str()) {
+                return Err(DomainError::ValidationError(format!(
+                    "{} changed since the refactoring was planned",
+                    rewrite.file_path
+                )));
+            }
+        }
+
+        for (index, rewrite) in rewrites.iter().enumerate() {
+            if let Err(error) = self
+                .file_storage
+                .write(&rewrite.path, &rewrite.updated)
+                .await
+            {
+                tracing::warn!(
+                    "[LinkRefactorService] Writing {} failed, rolling back: {}",
+                    rewrite.file_path,
+                    error
+                );
+                self.revert(&rewrites[..index]).await;
+                return Err(error);
+            }
+        }
+
+        tracing::info!("[LinkRefactorService] Rewrote {} files", rewrites.len());
+        Ok(())
+    }
+
+    /// Put back the content files had before they were rewritten
+    pub async fn revert(&self, rewrites: &[FileRewrite]) {
+        for rewrite in rewrites.iter().rev() {
+            if let Err(error) = self
+                .file_storage
+                .write(&rewrite.path, &rewrite.original)
+                .await
+            {
+                tracing::error!(
+                    "[LinkRefactorService] Could not restore {}: {}",
+                    rewrite.file_path,
+                    error
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,21 @@
+pub mod embedding_queue;
+pub mod indexing_service;
+pub mod link_refactor_service;
+pub mod note_embedder;
+pub mod note_filename_service;
+pub mod note_metadata_service;
+pub mod note_rename_service;
+pub mod periodic_note_service;
+pub mod transclusion_service;
+
+pub use embedding_queue::{
+    EmbeddingQueue, EmbeddingQueueOptions, DEFAULT_EMBEDDING_BATCH_SIZE, DEFAULT_MAX_CPU_PERCENT,
+};
+pub use indexing_service::{NoteIndexingService, DEFAULT_INDEX_DEBOUNCE_MS};
+pub use link_refactor_service::{FileMove, FileRewrite, LinkRefactorService};
+pub use note_embedder::{NoteEmbedder, PreparedNote};
+pub use note_filename_service::NoteFilenameService;
+pub use note_metadata_service::NoteMetadataService;
+pub use note_rename_service::NoteRenameService;
+pub use periodic_note_service::{PeriodicNote, PeriodicNoteService};
+pub use transclusion_service::{TransclusionService, MAX_EMBED_DEPTH};
@@ -0,0 +1,135 @@
+/// Note Embedder
+///
+/// Splits a note's markdown into passages, embeds each one and stores them
+/// through the embedding service. Shared by the background indexer, the
+/// embedding queue and topic classification so all embed notes the same way.
+use std::sync::Arc;
+
+use crate::domain::{
+    entities::{Embedding, NoteChunk},
+    errors::{DomainError, DomainResult},
+    ports::outbound::{EmbeddingService, MarkdownProcessor},
+    services::NoteChunker,
+};
+
+/// A note split into passages, ready to be embedded
+pub struct PreparedNote {
+    pub note_id: String,
+    /// Hash of the passages, as recorded with the note's embedding
+    pub content_hash: String,
+    chunks: Vec<NoteChunk>,
+    inputs: Vec<String>,
+}
+
+impl PreparedNote {
+    /// Texts to embed, one per passage
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// Whether the note has no text to embed
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+}
+
+pub struct NoteEmbedder {
+    embedding_service: Arc<dyn EmbeddingService>,
+    markdown_processor: Arc<dyn MarkdownProcessor>,
+    chunker: NoteChunker,
+}
+
+impl NoteEmbedder {
+    pub fn new(
+        embedding_service: Arc<dyn EmbeddingService>,
+        markdown_processor: Arc<dyn MarkdownProcessor>,
+    ) -> Self {
+        Self {
+            embedding_service,
+            markdown_processor,
+            chunker: NoteChunker::default(),
+        }
+    }
+
+    /// Embed a note passage by passage
+    ///
+    /// Returns the note-level embedding, or None when the note has no text
+    /// (in which case any stored embedding is removed).
+    pub async fn embed_note(
+        &self,
+        note_id: &str,
+        markdown: &str,
+    ) -> DomainResult<Option<Vec<f32>>> {
+        let prepared = self.prepare(note_id, markdown)?;
+        if prepared.is_empty() {
+            self.store(prepared, Vec::new()).await?;
+            return Ok(None);
+        }
+
+        let embeddings = self
+            .embedding_service
+            .generate_embeddings(prepared.inputs.clone())
+            .await?;
+        self.store(prepared, embeddings).await?;
+        self.embedding_service.get_embedding(note_id).await
+    }
+
+    /// Split a note into the passages that would be embedded
+    pub fn prepare(&self, note_id: &str, markdown: &str) -> DomainResult<PreparedNote> {
+        let mut chunks = Vec::new();
+        let mut inputs = Vec::new();
+
+        for passage in self.chunker.chunk(markdown) {
+            let plain_text = self.markdown_processor.extract_plain_text(&passage.text)?;
+            if plain_text.trim().is_empty() {
+                continue;
+            }
+            let chunk = NoteChunk::new(note_id, &passage)?;
+            inputs.push(chunk.embedding_input(plain_text.trim()));
+            chunks.push(chunk);
+        }
+
+        Ok(PreparedNote {
+            note_id: note_id.to_string(),
+            content_hash: Embedding::hash_passages(&chunks),
+            chunks,
+            inputs,
+        })
+    }
+
+    /// Store the vectors of a prepared note, one per input
+    ///
+    /// A note without passages has any stored embedding removed.
+    pub async fn store(
+        &self,
+        prepared: PreparedNote,
+        embeddings: Vec<Vec<f32>>,
+    ) -> DomainResult<()> {
+        if prepared.is_empty() {
+            return self
+                .embedding_service
+                .delete_embedding(&prepared.note_id)
+                .await;
+        }
+
+        if embeddings.len() != prepared.chunks.len() {
+            return Err(DomainError::InternalError(format!(
+                "Expected {} embeddings for note {}, got {}",
+                prepared.chunks.len(),
+                prepared.note_id,
+                embeddings.len()
+            )));
+        }
+
+        let chunks: Vec<NoteChunk> = prepared
+            .chunks
+            .into_iter()
+            .zip(embeddings)
+            .map(|(chunk, embedding)| chunk.with_embedding(embedding))
+            .collect();
+
+        self.embedding_service
+            .store_chunk_embeddings(&prepared.note_id, chunks)
+            .await
+    }
+}
@@ -0,0 +1,249 @@
+/// Note Filename Service
+///
+/// Names the files of new notes following the workspace's filename settings,
+/// and renames a note's file when its title changes if the workspace asks for
+/// it. Renames go through the link refactoring, so relative links keep
+/// working, and are staged in git when the workspace is a repository.
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::application::services::{FileMove, LinkRefactorService, PeriodicNoteService};
+use crate::domain::{
+    entities::{Note, Workspace},
+    errors::DomainResult,
+    ports::{
+        inbound::FilenameSettings,
+        outbound::{FileStorage, GitService, NoteRepository, SettingsRepository},
+    },
+    services::{FilenameStrategy, NoteFilenames},
+};
+
+pub struct NoteFilenameService {
+    note_repository: Arc<dyn NoteRepository>,
+    settings_repository: Arc<dyn SettingsRepository>,
+    file_storage: Arc<dyn FileStorage>,
+    periodic_notes: Arc<PeriodicNoteService>,
+    git_service: Arc<dyn GitService>,
+    link_refactor: Arc<LinkRefactorService>,
+}
+
+impl NoteFilenameService {
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        settings_repository: Arc<dyn SettingsRepository>,
+        file_storage: Arc<dyn FileStorage>,
+        periodic_notes: Arc<PeriodicNoteService>,
+        git_service: Arc<dyn GitService>,
+        link_refactor: Arc<LinkRefactorService>,
+    ) -> Self {
+        Self {
+            note_repository,
+            settings_repository,
+            file_storage,
+            periodic_notes,
+            git_service,
+            link_refactor,
+        }
+    }
+
+    pub async fn settings(&self, workspace_id: &str) -> DomainResult<FilenameSettings> {
+        match self
+            .settings_repository
+            .get(&FilenameSettings::setting_key(workspace_id))
+            .await?
+        {
+            Some(setting) => FilenameSettings::from_setting(&setting.value),
+            None => Ok(FilenameSettings::default()),
+        }
+    }
+
+    pub async fn save_settings(
+        &self,
+        workspace_id: &str,
+        settings: &FilenameSettings,
+    ) -> DomainResult<()> {
+        self.settings_repository
+            .set(
+                &FilenameSettings::setting_key(workspace_id),
+                &settings.to_setting()?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Path for a new note's file, relative to the workspace root
+    ///
+    /// Periodic notes are always named after their title, which is the date.
+    pub async fn new_file_path(
+        &self,
+        workspace: &Workspace,
+        folder: &str,
+        title: &str,
+    ) -> DomainResult<String> {
+        let periodic_settings = self.periodic_notes.settings(&workspace.id).await?;
+        if PeriodicNoteService::period_of_folder(&periodic_settings, folder).is_some() {
+            return Ok(Self::join(folder, &format!("{}.md", title.trim())));
+        }
+
+        let settings = self.settings(&workspace.id).await?;
+        let stem = NoteFilenames::stem(settings.strategy, title, chrono::Utc::now());
+        self.free_path(workspace, folder, settings.strategy, &stem, None)
+            .await
+    }
+
+    /// Rename a note's file after its title, if the workspace asks for it
+    ///
+    /// Call it once the title has changed. The note's file path is updated
+    /// but not saved; hand the returned move to `undo_rename` if saving fails.
+    pub async fn rename_for_title(
+        &self,
+        workspace: &Workspace,
+        note: &mut Note,
+    ) -> DomainResult<Option<FileMove>> {
+        let settings = self.settings(&workspace.id).await?;
+        if !settings.rename_on_title_change || settings.strategy == FilenameStrategy::Timestamp {
+            return Ok(None);
+        }
+        let Some(file_path) = note.file_path.clone() else {
+            return Ok(None);
+        };
+
+        let periodic_settings = self.periodic_notes.settings(&workspace.id).await?;
+        if PeriodicNoteService::period_of_path(&periodic_settings, &file_path).is_some() {
+            return Ok(None);
+        }
+
+        let path = Path::new(&file_path);
+        let folder = path
+            .parent()
+            .map(|parent| parent.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let current_stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let stem = NoteFilenames::stem(settings.strategy, &note.title, chrono::Utc::now());
+        if NoteFilenames::is_variant_of(settings.strategy, &current_stem, &stem) {
+            return Ok(None);
+        }
+
+        let new_file_path = self
+            .free_path(
+                workspace,
+                &folder,
+                settings.strategy,
+                &stem,
+                Some(&file_path),
+            )
+            .await?;
+        let file_move = self
+            .link_refactor
+            .move_path(
+                &workspace.folder_path,
+                &Self::absolute(workspace, &file_path),
+                &Self::absolute(workspace, &new_file_path),
+            )
+            .await?;
+        self.stage_rename(workspace, &file_path, &new_file_path)
+            .await;
+        note.set_file_path(Some(new_file_path.clone()))?;
+
+        tracing::info!(
+            "[NoteFilenameService] Renamed {} to {}",
+            file_path,
+            new_file_path
+        );
+        Ok(Some(file_move))
+    }
+
+    /// Move a renamed file back, with its links and its git index entry
+    pub async fn undo_rename(&self, workspace: &Workspace, file_move: &FileMove) {
+        self.link_refactor.undo_move(file_move).await;
+
+        let relative = |path: &str| {
+            Path::new(path)
+                .strip_prefix(&workspace.folder_path)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .ok()
+        };
+        if let (Some(from), Some(to)) = (relative(&file_move.from), relative(&file_move.to)) {
+            self.stage_rename(workspace, &to, &from).await;
+        }
+    }
+
+    /// First of `stem`, `stem-2`, `stem-3`... not used by a file or a note
+    ///
+    /// `current` is the note's own file, which doesn't count as taken, so a
+    /// rename that only changes case is possible.
+    async fn free_path(
+        &self,
+        workspace: &Workspace,
+        folder: &str,
+        strategy: FilenameStrategy,
+        stem: &str,
+        current: Option<&str>,
+    ) -> DomainResult<String> {
+        let mut n = 1;
+        loop {
+            let candidate = match n {
+                1 => stem.to_string(),
+                n => NoteFilenames::with_suffix(strategy, stem, n),
+            };
+            let file_path = Self::join(folder, &format!("{}.md", candidate));
+            n += 1;
+
+            if current.is_some_and(|current| current.to_lowercase() == file_path.to_lowercase()) {
+                return Ok(file_path);
+            }
+            if self
+                .file_storage
+                .exists(&Self::absolute(workspace, &file_path))
+                .await?
+            {
+                continue;
+            }
+            if self
+                .note_repository
+                .find_by_file_path(&file_path, Some(&workspace.id))
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            return Ok(file_path);
+        }
+    }
+
+    /// Stage a rename in the workspace's repository; failures are only logged
+    async fn stage_rename(&self, workspace: &Workspace, from: &str, to: &str) {
+        match self.git_service.is_repository(&workspace.folder_path).await {
+            Ok(true) => {}
+            _ => return,
+        }
+        if let Err(error) = self
+            .git_service
+            .stage_rename(&workspace.folder_path, from, to)
+            .await
+        {
+            tracing::warn!(
+                "[NoteFilenameService] Could not stage rename of {}: {}",
+                from,
+                error
+            );
+        }
+    }
+
+    fn join(folder: &str, filename: &str) -> String {
+        match folder.replace('\\', "/").trim_matches('/') {
+            "" => filename.to_string(),
+            folder => format!("{}/{}", folder, filename),
+        }
+    }
+
+    fn absolute(workspace: &Workspace, file_path: &str) -> String {
+        Path::new(&workspace.folder_path)
+            .join(file_path)
+            .to_string_lossy()
+            .to_string()
+    }
+}
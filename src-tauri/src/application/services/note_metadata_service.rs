@@ -0,0 +1,206 @@
+/// Note Metadata Service
+///
+/// Keeps a note's tags and its file in agreement. Tags listed under `tags:` in
+/// a note's frontmatter and `#hashtags` in its content become its tags in the
+/// database, and tags changed from the app are written back into the
+/// frontmatter list, keeping how the other keys and the tags that stay were
+/// written. Each link remembers whether it came from the content or was added
+/// by hand, so a hashtag deleted from the text takes its tag with it.
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::domain::{
+    entities::{Tag, TagSource},
+    errors::{DomainError, DomainResult},
+    ports::outbound::{FileStorage, NoteRepository, TagRepository, WorkspaceRepository},
+    services::{Frontmatter, HashtagExtractor, NoteFrontmatter, YamlValue, TAG_KEYS},
+};
+
+pub struct NoteMetadataService {
+    note_repository: Arc<dyn NoteRepository>,
+    workspace_repository: Arc<dyn WorkspaceRepository>,
+    tag_repository: Arc<dyn TagRepository>,
+    file_storage: Arc<dyn FileStorage>,
+}
+
+impl NoteMetadataService {
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        workspace_repository: Arc<dyn WorkspaceRepository>,
+        tag_repository: Arc<dyn TagRepository>,
+        file_storage: Arc<dyn FileStorage>,
+    ) -> Self {
+        Self {
+            note_repository,
+            workspace_repository,
+            tag_repository,
+            file_storage,
+        }
+    }
+
+    /// Give a saved note the tags its frontmatter lists and its content uses
+    ///
+    /// Frontmatter tags are manual links and hashtags in `markdown` are
+    /// content links; a tag in both counts as manual. Without a `tags` key the
+    /// note's manual tags are left alone. Tags that don't exist yet are
+    /// created. Returns whether any tag was added, removed or changed source.
+    pub async fn sync_tags(
+        &self,
+        note_id: &str,
+        frontmatter: &NoteFrontmatter,
+        markdown: &str,
+    ) -> DomainResult<bool> {
+        let listed = frontmatter.tags.as_ref().map(|names| Self::normalize(names));
+        let hashtags = Self::normalize(&HashtagExtractor::extract(markdown));
+
+        let wanted = |name: &String, current: Option<TagSource>| -> Option<TagSource> {
+            match &listed {
+                Some(listed) if listed.contains(name) => Some(TagSource::Manual),
+                None if current == Some(TagSource::Manual) => Some(TagSource::Manual),
+                _ if hashtags.contains(name) => Some(TagSource::Content),
+                _ => None,
+            }
+        };
+
+        let current = self
+            .tag_repository
+            .find_note_tags_with_source(note_id)
+            .await?;
+        let mut changed = false;
+
+        for link in &current {
+            match wanted(&link.tag.name, Some(link.source)) {
+                Some(source) if source == link.source => continue,
+                Some(source) => {
+                    self.tag_repository
+                        .link_tag(note_id, &link.tag.id, source)
+                        .await?
+                }
+                None => {
+                    self.tag_repository
+                        .remove_tag_from_note(note_id, &link.tag.id)
+                        .await?
+                }
+            }
+            changed = true;
+        }
+
+        let names = listed.iter().flatten().chain(hashtags.iter());
+        let mut added: Vec<&String> = Vec::new();
+        for name in names {
+            if added.contains(&name) || current.iter().any(|link| &link.tag.name == name) {
+                continue;
+            }
+            let Some(source) = wanted(name, None) else {
+                continue;
+            };
+            let tag = match self.tag_repository.find_by_name(name).await? {
+                Some(tag) => tag,
+                None => {
+                    let tag = Tag::new(name.as_str())?;
+                    self.tag_repository.save(&tag).await?;
+                    tag
+                }
+            };
+            self.tag_repository.link_tag(note_id, &tag.id, source).await?;
+            added.push(name);
+            changed = true;
+        }
+
+        if changed {
+            tracing::debug!(
+                "[NoteMetadataService] Synced tags of note {}: {:?} listed, {:?} in content",
+                note_id,
+                listed,
+                hashtags
+            );
+        }
+        Ok(changed)
+    }
+
+    /// Write a note's manual tags into the `tags` list of its file's frontmatter
+    ///
+    /// Tags that come from hashtags in the content stay out of the list. Tags
+    /// still on the note keep the spelling the file gives them and new ones
+    /// are appended. Files without frontmatter get one only if the note has
+    /// manual tags. Returns whether the file was written.
+    pub async fn write_tags(&self, note_id: &str) -> DomainResult<bool> {
+        let note = self
+            .note_repository
+            .find_by_id(note_id)
+            .await?
+            .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
+        let (Some(file_path), Some(workspace_id)) = (&note.file_path, &note.workspace_id) else {
+            return Ok(false);
+        };
+        let Some(workspace) = self.workspace_repository.find_by_id(workspace_id).await? else {
+            return Ok(false);
+        };
+        let absolute_path = Path::new(&workspace.folder_path)
+            .join(file_path)
+            .to_string_lossy()
+            .to_string();
+        let Some(content) = self.file_storage.read(&absolute_path).await? else {
+            return Ok(false);
+        };
+
+        let names: Vec<String> = self
+            .tag_repository
+            .find_note_tags_with_source(note_id)
+            .await?
+            .into_iter()
+            .filter(|link| link.source == TagSource::Manual)
+            .map(|link| link.tag.name)
+            .collect();
+
+        let mut frontmatter = Frontmatter::from_markdown(&content)?.unwrap_or_default();
+        let (key, listed) = match frontmatter.get_any(&TAG_KEYS) {
+            Some((key, value)) => (key, value.as_string_list()),
+            None if names.is_empty() => return Ok(false),
+            None => ("tags", Vec::new()),
+        };
+
+        let normalized = |item: &str| Tag::normalize_name(item.trim_start_matches('#')).ok();
+        let mut tags: Vec<String> = listed
+            .iter()
+            .filter(|item| normalized(item).is_some_and(|name| names.contains(&name)))
+            .cloned()
+            .collect();
+        for name in &names {
+            if !tags
+                .iter()
+                .any(|item| normalized(item).as_ref() == Some(name))
+            {
+                tags.push(name.clone());
+            }
+        }
+        if tags == listed {
+            return Ok(false);
+        }
+
+        frontmatter.set(key, YamlValue::string_list(&tags));
+        self.file_storage
+            .write(&absolute_path, &frontmatter.write_to(&content))
+            .await?;
+
+        tracing::info!(
+            "[NoteMetadataService] Wrote {} tags into {}",
+            tags.len(),
+            file_path
+        );
+        Ok(true)
+    }
+
+    /// Normalized tag names, without duplicates or invalid names
+    fn normalize(names: &[String]) -> Vec<String> {
+        let mut normalized: Vec<String> = Vec::new();
+        for name in names {
+            if let Ok(name) = Tag::normalize_name(name) {
+                if !normalized.contains(&name) {
+                    normalized.push(name);
+                }
+            }
+        }
+        normalized
+    }
+}
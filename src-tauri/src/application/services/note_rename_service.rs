@@ -0,0 +1,171 @@
+/// Note Rename Service
+///
+/// Changes a note's title along with the files that name it: the title
+/// heading of its own file, the wiki links of every note linking to it, found
+/// through the stored backlinks, and its filename when the workspace's
+/// filename settings ask for it. Renaming a note and editing its title both go
+/// through here, so either way `[[Old Title]]` links follow the note.
+use std::sync::Arc;
+
+use crate::application::services::{
+    FileMove, FileRewrite, LinkRefactorService, NoteFilenameService,
+};
+use crate::domain::{
+    entities::{Note, Workspace},
+    errors::{DomainError, DomainResult},
+    ports::outbound::{NoteFindOptions, NoteLinkRepository, NoteRepository},
+    services::{Frontmatter, LinkExtractor},
+};
+
+pub struct NoteRenameService {
+    note_repository: Arc<dyn NoteRepository>,
+    note_link_repository: Arc<dyn NoteLinkRepository>,
+    link_refactor: Arc<LinkRefactorService>,
+    note_filenames: Arc<NoteFilenameService>,
+}
+
+impl NoteRenameService {
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        note_link_repository: Arc<dyn NoteLinkRepository>,
+        link_refactor: Arc<LinkRefactorService>,
+        note_filenames: Arc<NoteFilenameService>,
+    ) -> Self {
+        Self {
+            note_repository,
+            note_link_repository,
+            link_refactor,
+            note_filenames,
+        }
+    }
+
+    /// Replace the `# Title` heading of a note's file, if it shows the old title
+    fn retitle_heading(markdown: &str, old_title: &str, new_title: &str) -> Option<String> {
+        let start = Frontmatter::body_offset(markdown);
+        let mut offset = start;
+
+        for line in markdown[start..].split_inclusive('\n') {
+            let text = line.trim_end_matches(['\r', '\n']);
+            if let Some(title) = text.strip_prefix("# ") {
+                if title.trim() != old_title.trim() {
+                    return None;
+                }
+                let mut retitled = markdown.to_string();
+                retitled.replace_range(offset..offset + text.len(), &format!("# {}", new_title));
+                return Some(retitled);
+            }
+            offset += line.len();
+        }
+
+        None
+    }
+
+    /// The files to rewrite for `note` to be titled `new_title`
+    ///
+    /// Each rewrite comes with the note stored in the file, the renamed note's
+    /// own file first. Fails if another note in the workspace has the title.
+    pub async fn plan(
+        &self,
+        workspace: &Workspace,
+        note: &Note,
+        new_title: &str,
+    ) -> DomainResult<Vec<(Note, FileRewrite)>> {
+        // Validates the title the same way the rename itself will
+        note.clone().update_title(new_title)?;
+
+        // Links written with the new title must keep resolving to one note
+        let taken = self
+            .note_repository
+            .find_all(NoteFindOptions {
+                workspace_id: Some(workspace.id.clone()),
+                is_deleted: Some(false),
+                ..Default::default()
+            })
+            .await?
+            .into_iter()
+            .any(|other| {
+                other.id != note.id && other.title.to_lowercase() == new_title.to_lowercase()
+            });
+        if taken {
+            return Err(DomainError::NoteAlreadyExists(new_title.to_string()));
+        }
+
+        let old_title = note.title.as_str();
+        let mut rewrites = Vec::new();
+
+        // The note's own file: its heading and any links to itself
+        if let Some(rewrite) = self
+            .link_refactor
+            .plan_note_rewrite(workspace, note, |markdown| {
+                let linked = LinkExtractor::rename_wiki_links(markdown, old_title, new_title);
+                let markdown = linked.as_deref().unwrap_or(markdown);
+                Self::retitle_heading(markdown, old_title, new_title).or(linked)
+            })
+            .await?
+        {
+            rewrites.push((note.clone(), rewrite));
+        }
+
+        let mut backlinks = self.note_link_repository.get_backlinks(&note.id).await?;
+        backlinks.retain(|source| source.id != note.id);
+        backlinks.sort_by(|a, b| a.title.cmp(&b.title));
+
+        for source in backlinks {
+            if let Some(rewrite) = self
+                .link_refactor
+                .plan_note_rewrite(workspace, &source, |markdown| {
+                    LinkExtractor::rename_wiki_links(markdown, old_title, new_title)
+                })
+                .await?
+            {
+                rewrites.push((source, rewrite));
+            }
+        }
+
+        Ok(rewrites)
+    }
+
+    /// Write the planned files, then retitle the note and rename its file
+    ///
+    /// The note is updated but not saved; hand the returned move to `undo` if
+    /// saving fails. If the file can't be renamed the files are put back.
+    pub async fn apply(
+        &self,
+        workspace: &Workspace,
+        note: &mut Note,
+        new_title: String,
+        rewrites: &[(Note, FileRewrite)],
+    ) -> DomainResult<Option<FileMove>> {
+        let files = Self::files(rewrites);
+        self.link_refactor.apply(&files).await?;
+
+        let renamed = match note.update_title(new_title) {
+            Ok(()) => self.note_filenames.rename_for_title(workspace, note).await,
+            Err(error) => Err(error),
+        };
+        if renamed.is_err() {
+            self.link_refactor.revert(&files).await;
+        }
+        renamed
+    }
+
+    /// Put back the renamed file and the rewritten files
+    pub async fn undo(
+        &self,
+        workspace: &Workspace,
+        rewrites: &[(Note, FileRewrite)],
+        file_move: Option<&FileMove>,
+    ) {
+        if let Some(file_move) = file_move {
+            self.note_filenames.undo_rename(workspace, file_move).await;
+        }
+        self.link_refactor.revert(&Self::files(rewrites)).await;
+    }
+
+    fn files(rewrites: &[(Note, FileRewrite)]) -> Vec<FileRewrite> {
+        rewrites
+            .iter()
+            .map(|(_, rewrite)| rewrite.clone())
+            .collect()
+    }
+}
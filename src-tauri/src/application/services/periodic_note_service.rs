@@ -0,0 +1,246 @@
+/// Periodic Note Service
+///
+/// Finds or creates the note of a day, week, month or quarter according to
+/// the workspace's periodic notes settings. Shared by the periodic note use
+/// cases, quick capture and note creation so all agree on where these notes
+/// live and what they are called.
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::domain::{
+    entities::{Note, Workspace},
+    errors::{DomainError, DomainResult},
+    ports::{
+        inbound::{PeriodicNotesSettings, TemplateSettings},
+        outbound::{
+            DomainEvent, EventPublisher, FileStorage, NoteRepository, SettingsRepository,
+            WorkspaceRepository,
+        },
+    },
+    services::{NoteFrontmatter, NotePeriod, PeriodicNotes, TemplateContext, TemplateRenderer},
+};
+
+/// The note of one period
+pub struct PeriodicNote {
+    pub note: Note,
+    pub period: NotePeriod,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Absolute path of the note's file
+    pub absolute_path: String,
+    /// Whether the note was created by this call
+    pub created: bool,
+}
+
+pub struct PeriodicNoteService {
+    note_repository: Arc<dyn NoteRepository>,
+    workspace_repository: Arc<dyn WorkspaceRepository>,
+    settings_repository: Arc<dyn SettingsRepository>,
+    file_storage: Arc<dyn FileStorage>,
+    event_publisher: Option<Arc<dyn EventPublisher>>,
+}
+
+impl PeriodicNoteService {
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        workspace_repository: Arc<dyn WorkspaceRepository>,
+        settings_repository: Arc<dyn SettingsRepository>,
+        file_storage: Arc<dyn FileStorage>,
+        event_publisher: Option<Arc<dyn EventPublisher>>,
+    ) -> Self {
+        Self {
+            note_repository,
+            workspace_repository,
+            settings_repository,
+            file_storage,
+            event_publisher,
+        }
+    }
+
+    /// The given workspace, or the active one
+    pub async fn resolve_workspace(&self, workspace_id: Option<&str>) -> DomainResult<Workspace> {
+        match workspace_id {
+            Some(id) => self.workspace_repository.find_by_id(id).await?,
+            None => self.workspace_repository.find_active().await?,
+        }
+        .ok_or_else(|| DomainError::ValidationError("No active workspace".to_string()))
+    }
+
+    pub async fn settings(&self, workspace_id: &str) -> DomainResult<PeriodicNotesSettings> {
+        match self
+            .settings_repository
+            .get(&PeriodicNotesSettings::setting_key(workspace_id))
+            .await?
+        {
+            Some(setting) => PeriodicNotesSettings::from_setting(&setting.value),
+            None => Ok(PeriodicNotesSettings::default()),
+        }
+    }
+
+    pub async fn save_settings(
+        &self,
+        workspace_id: &str,
+        settings: &PeriodicNotesSettings,
+    ) -> DomainResult<()> {
+        settings.validate()?;
+        self.settings_repository
+            .set(
+                &PeriodicNotesSettings::setting_key(workspace_id),
+                &settings.to_setting()?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// The day it is now, after the configured rollover
+    pub fn today(settings: &PeriodicNotesSettings, now: NaiveDateTime) -> DomainResult<NaiveDate> {
+        Ok(PeriodicNotes::current_date(now, settings.day_start()?))
+    }
+
+    /// The period whose notes are kept in a folder of the workspace
+    pub fn period_of_folder(settings: &PeriodicNotesSettings, folder: &str) -> Option<NotePeriod> {
+        let folder = folder.replace('\\', "/");
+        NotePeriod::ALL.into_iter().find(|period| {
+            let config = settings.period(*period);
+            config.enabled && config.folder.trim_matches('/') == folder.trim_matches('/')
+        })
+    }
+
+    /// The period a file of the workspace is the note of, judged by its folder
+    ///
+    /// Only the folder is compared, so a note named by hand still counts.
+    pub fn period_of_path(settings: &PeriodicNotesSettings, file_path: &str) -> Option<NotePeriod> {
+        let folder = Path::new(file_path)
+            .parent()
+            .map(|parent| parent.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Self::period_of_folder(settings, &folder)
+    }
+
+    /// Find the note of the period containing `date`, creating it if needed
+    pub async fn open(
+        &self,
+        workspace: &Workspace,
+        period: NotePeriod,
+        date: NaiveDate,
+    ) -> DomainResult<PeriodicNote> {
+        let settings = self.settings(&workspace.id).await?;
+        let config = settings.period(period);
+        if !config.enabled {
+            return Err(DomainError::ValidationError(format!(
+                "{} notes are turned off",
+                period.as_str()
+            )));
+        }
+
+        let start = PeriodicNotes::start_of(period, date);
+        let end = PeriodicNotes::end_of(period, date);
+        let title = PeriodicNotes::note_name(&config.format, start);
+        let relative_path = format!("{}/{}.md", config.folder.trim_matches('/'), title);
+        let absolute_path = Path::new(&workspace.folder_path)
+            .join(&relative_path)
+            .to_string_lossy()
+            .to_string();
+
+        let mut periodic = PeriodicNote {
+            note: Note::new(&title, Some(workspace.id.clone()))?,
+            period,
+            start,
+            end,
+            absolute_path,
+            created: false,
+        };
+
+        if let Some(note) = self
+            .note_repository
+            .find_by_file_path(&relative_path, Some(&workspace.id))
+            .await?
+        {
+            periodic.note = note;
+            return Ok(periodic);
+        }
+
+        // A file written outside the app only needs registering
+        if !self.file_storage.exists(&periodic.absolute_path).await? {
+            let content = self
+                .initial_content(workspace, config.template.as_deref(), &title, start)
+                .await?;
+            let folder = Path::new(&workspace.folder_path).join(config.folder.trim_matches('/'));
+            self.file_storage
+                .create_directory(&folder.to_string_lossy())
+                .await?;
+            self.file_storage
+                .write(&periodic.absolute_path, &content)
+                .await?;
+            NoteFrontmatter::from_markdown(&content).apply_to(&mut periodic.note);
+            periodic.created = true;
+        }
+
+        periodic.note.set_file_path(Some(relative_path))?;
+        self.note_repository.save(&periodic.note).await?;
+
+        if let Some(ref publisher) = self.event_publisher {
+            publisher.publish(DomainEvent::NoteCreated {
+                timestamp: chrono::Utc::now(),
+                id: periodic.note.id.clone(),
+                title: periodic.note.title.clone(),
+                workspace_id: periodic.note.workspace_id.clone(),
+                notebook_id: periodic.note.notebook_id.clone(),
+                file_path: periodic.note.file_path.clone(),
+            });
+        }
+
+        Ok(periodic)
+    }
+
+    /// Text of a new periodic note: its template, or just a title heading
+    async fn initial_content(
+        &self,
+        workspace: &Workspace,
+        template: Option<&str>,
+        title: &str,
+        start: NaiveDate,
+    ) -> DomainResult<String> {
+        let Some(template) = template.filter(|t| !t.trim().is_empty()) else {
+            return Ok(format!("# {}\n", title));
+        };
+
+        let template_settings = match self
+            .settings_repository
+            .get(&TemplateSettings::setting_key(&workspace.id))
+            .await?
+        {
+            Some(setting) => TemplateSettings::from_setting(&setting.value)?,
+            None => TemplateSettings::default(),
+        };
+        let template_path = Path::new(&workspace.folder_path)
+            .join(&template_settings.folder)
+            .join(template);
+        let Some(text) = self
+            .file_storage
+            .read(&template_path.to_string_lossy())
+            .await?
+        else {
+            tracing::warn!(
+                "[PeriodicNoteService] Template {} not found, creating {} without it",
+                template,
+                title
+            );
+            return Ok(format!("# {}\n", title));
+        };
+
+        // Placeholders refer to the period's first day, at the current time
+        let now = start.and_time(chrono::Local::now().time());
+        let rendered = TemplateRenderer::render(
+            &text,
+            &TemplateContext {
+                title: title.to_string(),
+                workspace: workspace.name.clone(),
+                now,
+            },
+        );
+        Ok(rendered.content)
+    }
+}
@@ -0,0 +1,222 @@
+/// Transclusion Service
+///
+/// Resolves embeds, `![[Note]]`, `![[Note#Heading]]` and `![[Note#^block-id]]`,
+/// to the markdown they transclude. Export expands them inline before
+/// rendering; the editor resolves one at a time to preview it. Embeds inside
+/// transcluded markdown are expanded too, up to `MAX_EMBED_DEPTH` levels and
+/// never into a section that is already being expanded.
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::domain::{
+    entities::Note,
+    errors::DomainResult,
+    ports::{
+        inbound::EmbedContent,
+        outbound::{FileStorage, NoteFindOptions, NoteRepository, WorkspaceRepository},
+    },
+    services::{
+        LinkAnchor, LinkExtractor, LinkResolution, LinkResolver, MentionTarget, NoteAnchors,
+        NoteReference,
+    },
+};
+
+/// Levels of embeds within embeds that are expanded
+pub const MAX_EMBED_DEPTH: usize = 4;
+
+/// Notes embeds of one workspace resolve against
+struct EmbedScope {
+    folder_path: String,
+    notes: Vec<Note>,
+    resolver: LinkResolver,
+}
+
+/// (note ID, anchor) of each embed being expanded, outermost first
+type EmbedStack = Vec<(String, Option<LinkAnchor>)>;
+
+type ExpandFuture<'a> = Pin<Box<dyn Future<Output = DomainResult<String>> + Send + 'a>>;
+
+pub struct TransclusionService {
+    note_repository: Arc<dyn NoteRepository>,
+    workspace_repository: Arc<dyn WorkspaceRepository>,
+    file_storage: Arc<dyn FileStorage>,
+}
+
+impl TransclusionService {
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        workspace_repository: Arc<dyn WorkspaceRepository>,
+        file_storage: Arc<dyn FileStorage>,
+    ) -> Self {
+        Self {
+            note_repository,
+            workspace_repository,
+            file_storage,
+        }
+    }
+
+    /// Resolve one embed of a note, written as `Note#Heading` or `Note#^id`
+    ///
+    /// `#Heading` alone points into the note itself.
+    pub async fn resolve_embed(
+        &self,
+        note: &Note,
+        target: &str,
+    ) -> DomainResult<Option<EmbedContent>> {
+        let Some(scope) = self.scope(note).await? else {
+            return Ok(None);
+        };
+
+        let mut stack: EmbedStack = vec![(note.id.clone(), None)];
+        self.embed(&scope, &NoteReference::parse(target), &mut stack)
+            .await
+    }
+
+    /// A note's markdown with its embeds replaced by what they transclude
+    ///
+    /// Embeds that don't resolve, or would embed a section into itself, are
+    /// left as written.
+    pub async fn expand_embeds(&self, note: &Note, markdown: &str) -> DomainResult<String> {
+        let Some(scope) = self.scope(note).await? else {
+            return Ok(markdown.to_string());
+        };
+
+        let mut stack: EmbedStack = vec![(note.id.clone(), None)];
+        self.expand(&scope, markdown, &mut stack).await
+    }
+
+    /// Live notes of the note's workspace
+    async fn scope(&self, note: &Note) -> DomainResult<Option<EmbedScope>> {
+        let Some(workspace_id) = &note.workspace_id else {
+            return Ok(None);
+        };
+        let Some(workspace) = self.workspace_repository.find_by_id(workspace_id).await? else {
+            return Ok(None);
+        };
+
+        let notes = self
+            .note_repository
+            .find_all(NoteFindOptions {
+                workspace_id: Some(workspace.id.clone()),
+                is_deleted: Some(false),
+                ..Default::default()
+            })
+            .await?;
+        let targets: Vec<MentionTarget> = notes
+            .iter()
+            .map(|n| MentionTarget {
+                note_id: n.id.clone(),
+                title: n.title.clone(),
+                aliases: n.aliases.clone(),
+            })
+            .collect();
+
+        Ok(Some(EmbedScope {
+            folder_path: workspace.folder_path,
+            resolver: LinkResolver::new(&targets),
+            notes,
+        }))
+    }
+
+    /// Replace the embeds of `markdown`, last to first so offsets stay valid
+    fn expand<'a>(
+        &'a self,
+        scope: &'a EmbedScope,
+        markdown: &'a str,
+        stack: &'a mut EmbedStack,
+    ) -> ExpandFuture<'a> {
+        Box::pin(async move {
+            let mut expanded = markdown.to_string();
+            if stack.len() > MAX_EMBED_DEPTH {
+                return Ok(expanded);
+            }
+
+            // Offsets assume `\n` line breaks; skip embeds they don't land on
+            let embeds = LinkExtractor::extract_wiki_links(markdown)
+                .into_iter()
+                .filter(|link| link.embed)
+                .filter(|link| {
+                    markdown
+                        .get(link.start_index..link.end_index)
+                        .is_some_and(|span| span.starts_with("![["))
+                });
+            for link in embeds.rev() {
+                let reference = NoteReference {
+                    title: link.target.clone(),
+                    anchor: link.anchor.clone(),
+                };
+                if let Some(embed) = self.embed(scope, &reference, stack).await? {
+                    expanded.replace_range(link.start_index..link.end_index, &embed.markdown);
+                }
+            }
+
+            Ok(expanded)
+        })
+    }
+
+    async fn embed(
+        &self,
+        scope: &EmbedScope,
+        reference: &NoteReference,
+        stack: &mut EmbedStack,
+    ) -> DomainResult<Option<EmbedContent>> {
+        // An empty target points into the note being expanded
+        let note_id = if reference.title.is_empty() {
+            match stack.last() {
+                Some((note_id, _)) => note_id.clone(),
+                None => return Ok(None),
+            }
+        } else {
+            match scope.resolver.resolve(&reference.title) {
+                LinkResolution::Resolved(note_id) => note_id,
+                _ => return Ok(None),
+            }
+        };
+
+        let key = (note_id, reference.anchor.clone());
+        if stack.contains(&key) {
+            tracing::debug!(
+                "[TransclusionService] Not embedding {} into itself",
+                reference.title
+            );
+            return Ok(None);
+        }
+
+        let Some(note) = scope.notes.iter().find(|n| n.id == key.0) else {
+            return Ok(None);
+        };
+        let Some(markdown) = self.read(scope, note).await? else {
+            return Ok(None);
+        };
+        let section = match &reference.anchor {
+            Some(anchor) => NoteAnchors::section(&markdown, anchor),
+            None => Some(NoteAnchors::body(&markdown)),
+        };
+        let Some(section) = section else {
+            return Ok(None);
+        };
+
+        stack.push(key);
+        let expanded = self.expand(scope, &section, stack).await;
+        stack.pop();
+
+        Ok(Some(EmbedContent {
+            note_id: note.id.clone(),
+            title: note.title.clone(),
+            anchor: reference.anchor.as_ref().map(|a| a.to_string()),
+            markdown: expanded?,
+        }))
+    }
+
+    async fn read(&self, scope: &EmbedScope, note: &Note) -> DomainResult<Option<String>> {
+        let Some(file_path) = &note.file_path else {
+            return Ok(None);
+        };
+        let absolute_path = Path::new(&scope.folder_path).join(file_path);
+        self.file_storage
+            .read(&absolute_path.to_string_lossy())
+            .await
+    }
+}
@@ -0,0 +1,452 @@
+/// Duplicate Use Cases Implementation
+///
+/// Application layer implementations for finding and merging duplicate notes.
+/// Merging carries tags, topics and attachments over to the surviving note,
+/// points wiki links at it and moves the merged notes to trash.
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::{Note, NoteLink, Workspace},
+    errors::{DomainError, DomainResult},
+    ports::{
+        inbound::{
+            DuplicateCluster, DuplicateNoteInfo, DuplicatePairInfo, DuplicateUseCases,
+            FindDuplicatesRequest, MergeNotesRequest, MergeNotesResponse, MergeStrategy,
+        },
+        outbound::{
+            AttachmentRepository, DomainEvent, EmbeddingRepository, EventPublisher, FileStorage,
+            MarkdownProcessor, NoteFindOptions, NoteLinkRepository, NoteRepository, TagRepository,
+            TopicAssignmentOptions, TopicRepository, WorkspaceRepository,
+        },
+    },
+    services::{DuplicateConfig, DuplicateDetector, DuplicateDocument, Frontmatter, LinkExtractor},
+};
+
+/// Implementation of all Duplicate use cases
+pub struct DuplicateUseCasesImpl {
+    note_repository: Arc<dyn NoteRepository>,
+    workspace_repository: Arc<dyn WorkspaceRepository>,
+    tag_repository: Arc<dyn TagRepository>,
+    topic_repository: Arc<dyn TopicRepository>,
+    attachment_repository: Arc<dyn AttachmentRepository>,
+    note_link_repository: Arc<dyn NoteLinkRepository>,
+    embedding_repository: Arc<dyn EmbeddingRepository>,
+    file_storage: Arc<dyn FileStorage>,
+    markdown_processor: Arc<dyn MarkdownProcessor>,
+    event_publisher: Option<Arc<dyn EventPublisher>>,
+}
+
+impl DuplicateUseCasesImpl {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        workspace_repository: Arc<dyn WorkspaceRepository>,
+        tag_repository: Arc<dyn TagRepository>,
+        topic_repository: Arc<dyn TopicRepository>,
+        attachment_repository: Arc<dyn AttachmentRepository>,
+        note_link_repository: Arc<dyn NoteLinkRepository>,
+        embedding_repository: Arc<dyn EmbeddingRepository>,
+        file_storage: Arc<dyn FileStorage>,
+        markdown_processor: Arc<dyn MarkdownProcessor>,
+        event_publisher: Option<Arc<dyn EventPublisher>>,
+    ) -> Self {
+        Self {
+            note_repository,
+            workspace_repository,
+            tag_repository,
+            topic_repository,
+            attachment_repository,
+            note_link_repository,
+            embedding_repository,
+            file_storage,
+            markdown_processor,
+            event_publisher,
+        }
+    }
+
+    /// Absolute path of a note's file, if it has one
+    fn note_path(workspace: &Workspace, note: &Note) -> Option<String> {
+        note.file_path.as_ref().map(|file_path| {
+            Path::new(&workspace.folder_path)
+                .join(file_path)
+                .to_string_lossy()
+                .to_string()
+        })
+    }
+
+    async fn read_markdown(&self, workspace: &Workspace, note: &Note) -> DomainResult<String> {
+        match Self::note_path(workspace, note) {
+            Some(path) => Ok(self.file_storage.read(&path).await?.unwrap_or_default()),
+            None => Ok(String::new()),
+        }
+    }
+
+    /// Markdown without frontmatter and the leading `# Title` heading
+    fn body_of(markdown: &str) -> &str {
+        let (_, body) = Frontmatter::split(markdown);
+        let mut body = body.trim_start_matches(['\r', '\n']);
+        if body.starts_with("# ") {
+            body = body.split_once('\n').map_or("", |(_, rest)| rest);
+        }
+        body.trim()
+    }
+
+    fn publish_updated(&self, note: &Note, changes: &[&str]) {
+        if let Some(ref publisher) = self.event_publisher {
+            publisher.publish(DomainEvent::NoteUpdated {
+                timestamp: chrono::Utc::now(),
+                id: note.id.clone(),
+                title: note.title.clone(),
+                changes: changes.iter().map(|c| c.to_string()).collect(),
+            });
+        }
+    }
+
+    /// Copy tags, topics and attachments of a merged note to the target
+    async fn carry_over(&self, target: &Note, source: &Note) -> DomainResult<HashSet<String>> {
+        let target_tags: HashSet<String> = self
+            .tag_repository
+            .get_note_tags(&target.id)
+            .await?
+            .into_iter()
+            .map(|tag| tag.id)
+            .collect();
+        for tag in self.tag_repository.get_note_tags(&source.id).await? {
+            if !target_tags.contains(&tag.id) {
+                self.tag_repository
+                    .add_tag_to_note(&target.id, &tag.id)
+                    .await?;
+            }
+        }
+
+        let target_topics: HashSet<String> = self
+            .topic_repository
+            .get_topics_for_note(&target.id)
+            .await?
+            .into_iter()
+            .map(|topic| topic.topic_id)
+            .collect();
+        let mut touched_topics = HashSet::new();
+        for topic in self
+            .topic_repository
+            .get_topics_for_note(&source.id)
+            .await?
+        {
+            if !target_topics.contains(&topic.topic_id) {
+                self.topic_repository
+                    .assign_to_note(
+                        &target.id,
+                        &topic.topic_id,
+                        Some(TopicAssignmentOptions {
+                            confidence: Some(topic.confidence),
+                            is_manual: Some(topic.is_manual),
+                        }),
+                    )
+                    .await?;
+            }
+            touched_topics.insert(topic.topic_id);
+        }
+
+        for mut attachment in self
+            .attachment_repository
+            .find_by_note_id(&source.id)
+            .await?
+        {
+            attachment.note_id = target.id.clone();
+            self.attachment_repository.save(&attachment).await?;
+        }
+
+        Ok(touched_topics)
+    }
+}
+
+#[async_trait]
+impl DuplicateUseCases for DuplicateUseCasesImpl {
+    /// Find groups of near-duplicate notes by wording and embedding
+    async fn find_duplicates(
+        &self,
+        request: FindDuplicatesRequest,
+    ) -> DomainResult<Vec<DuplicateCluster>> {
+        let workspace = match request.workspace_id {
+            Some(id) => Some(
+                self.workspace_repository
+                    .find_by_id(&id)
+                    .await?
+                    .ok_or(DomainError::WorkspaceNotFound(id))?,
+            ),
+            None => self.workspace_repository.find_active().await?,
+        };
+        let Some(workspace) = workspace else {
+            return Ok(Vec::new());
+        };
+
+        let notes = self
+            .note_repository
+            .find_all(NoteFindOptions {
+                workspace_id: Some(workspace.id.clone()),
+                is_deleted: Some(false),
+                ..Default::default()
+            })
+            .await?;
+        let note_ids: Vec<String> = notes.iter().map(|note| note.id.clone()).collect();
+        let mut embeddings = self.embedding_repository.find_for_notes(&note_ids).await?;
+
+        let mut documents = Vec::with_capacity(notes.len());
+        for note in &notes {
+            let markdown = self.read_markdown(&workspace, note).await?;
+            let body = self
+                .markdown_processor
+                .extract_plain_text(Self::body_of(&markdown))?;
+            documents.push(DuplicateDocument {
+                text: format!("{}\n{}", note.title, body),
+                embedding: embeddings.remove(&note.id).map(|e| e.vector),
+            });
+        }
+
+        let defaults = DuplicateConfig::default();
+        let detector = DuplicateDetector::new(DuplicateConfig {
+            min_score: request.min_similarity.unwrap_or(defaults.min_score),
+            ..defaults
+        });
+
+        let clusters: Vec<DuplicateCluster> = detector
+            .find_groups(&documents)
+            .into_iter()
+            .map(|group| {
+                let mut members: Vec<&Note> = group.members.iter().map(|i| &notes[*i]).collect();
+                members.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+                DuplicateCluster {
+                    notes: members
+                        .into_iter()
+                        .map(|note| DuplicateNoteInfo {
+                            note_id: note.id.clone(),
+                            title: note.title.clone(),
+                            file_path: note.file_path.clone(),
+                            updated_at: note.updated_at,
+                        })
+                        .collect(),
+                    pairs: group
+                        .pairs
+                        .iter()
+                        .map(|pair| DuplicatePairInfo {
+                            note_id: notes[pair.a].id.clone(),
+                            other_note_id: notes[pair.b].id.clone(),
+                            similarity: pair.score,
+                            text_similarity: pair.text_similarity,
+                            embedding_similarity: pair.embedding_similarity,
+                        })
+                        .collect(),
+                    similarity: group.score,
+                }
+            })
+            .collect();
+
+        tracing::info!(
+            "[DuplicateUseCases] Found {} duplicate groups among {} notes",
+            clusters.len(),
+            notes.len()
+        );
+        Ok(clusters)
+    }
+
+    /// Merge notes into the target
+    ///
+    /// Tags, topics and attachments are unioned onto the target, wiki links
+    /// to the merged notes are pointed at it, and the merged notes are moved
+    /// to trash (so they can still be restored).
+    async fn merge_notes(&self, request: MergeNotesRequest) -> DomainResult<MergeNotesResponse> {
+        let target = self
+            .note_repository
+            .find_by_id(&request.target_id)
+            .await?
+            .ok_or_else(|| DomainError::NoteNotFound(request.target_id.clone()))?;
+        if target.is_deleted {
+            return Err(DomainError::ValidationError(
+                "Cannot merge into a deleted note".to_string(),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        let mut sources = Vec::new();
+        for source_id in &request.source_ids {
+            if *source_id == target.id || !seen.insert(source_id.clone()) {
+                continue;
+            }
+            let source = self
+                .note_repository
+                .find_by_id(source_id)
+                .await?
+                .ok_or_else(|| DomainError::NoteNotFound(source_id.clone()))?;
+            if source.workspace_id != target.workspace_id {
+                return Err(DomainError::ValidationError(format!(
+                    "Note {} is in a different workspace",
+                    source.title
+                )));
+            }
+            sources.push(source);
+        }
+        if sources.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Select at least one other note to merge".to_string(),
+            ));
+        }
+
+        let workspace_id = target
+            .workspace_id
+            .clone()
+            .ok_or_else(|| DomainError::ValidationError("Note has no workspace".to_string()))?;
+        let workspace = self
+            .workspace_repository
+            .find_by_id(&workspace_id)
+            .await?
+            .ok_or(DomainError::WorkspaceNotFound(workspace_id))?;
+
+        // 1. Content
+        if request.strategy == MergeStrategy::Concatenate {
+            let target_path = Self::note_path(&workspace, &target)
+                .ok_or_else(|| DomainError::ValidationError("Note has no file path".to_string()))?;
+            let mut markdown = self.read_markdown(&workspace, &target).await?;
+            for source in &sources {
+                let source_markdown = self.read_markdown(&workspace, source).await?;
+                let body = Self::body_of(&source_markdown);
+                if !body.is_empty() {
+                    markdown = format!(
+                        "{}\n\n## {}\n\n{}\n",
+                        markdown.trim_end(),
+                        source.title,
+                        body
+                    );
+                }
+            }
+            self.file_storage.write(&target_path, &markdown).await?;
+
+            // The merged content links wherever the merged notes did
+            for source in &sources {
+                for linked in self
+                    .note_link_repository
+                    .get_forward_links(&source.id)
+                    .await?
+                {
+                    if linked.id != target.id && !seen.contains(&linked.id) {
+                        self.note_link_repository
+                            .save(&NoteLink::new(target.id.clone(), linked.id)?)
+                            .await?;
+                    }
+                }
+            }
+        }
+
+        // 2. Tags, topics and attachments
+        let mut touched_topics = HashSet::new();
+        for source in &sources {
+            touched_topics.extend(self.carry_over(&target, source).await?);
+        }
+
+        // 3. Wiki links to the merged notes, by title or alias, now point at the target
+        let target_name = target.title.trim().to_lowercase();
+        let renames: HashMap<String, String> = sources
+            .iter()
+            .flat_map(|source| std::iter::once(&source.title).chain(&source.aliases))
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty() && *name != target_name)
+            .map(|name| (name, target.title.clone()))
+            .collect();
+        let notes = self
+            .note_repository
+            .find_all(NoteFindOptions {
+                workspace_id: Some(workspace.id.clone()),
+                is_deleted: Some(false),
+                ..Default::default()
+            })
+            .await?;
+
+        let mut relinked_note_ids = Vec::new();
+        for note in notes.iter().filter(|note| !seen.contains(&note.id)) {
+            let Some(path) = Self::note_path(&workspace, note) else {
+                continue;
+            };
+            let markdown = self.read_markdown(&workspace, note).await?;
+            let rewritten = LinkExtractor::retarget_wiki_links(&markdown, &renames);
+            let mut linked_sources = Vec::new();
+            for source in &sources {
+                if self
+                    .note_link_repository
+                    .exists(&note.id, &source.id)
+                    .await?
+                {
+                    linked_sources.push(&source.id);
+                }
+            }
+            if rewritten.is_none() && linked_sources.is_empty() {
+                continue;
+            }
+
+            if let Some(ref content) = rewritten {
+                self.file_storage.write(&path, content).await?;
+            }
+            for source_id in linked_sources {
+                self.note_link_repository
+                    .delete(&note.id, source_id)
+                    .await?;
+            }
+            if note.id != target.id {
+                self.note_link_repository
+                    .save(&NoteLink::new(note.id.clone(), target.id.clone())?)
+                    .await?;
+            }
+
+            if rewritten.is_some() {
+                relinked_note_ids.push(note.id.clone());
+                if note.id != target.id {
+                    self.publish_updated(note, &["content", "links"]);
+                }
+            }
+        }
+
+        // 4. Trash the merged notes
+        let mut merged_ids = Vec::with_capacity(sources.len());
+        for mut source in sources {
+            source.delete();
+            self.note_repository.save(&source).await?;
+
+            if let Some(ref publisher) = self.event_publisher {
+                publisher.publish(DomainEvent::NoteDeleted {
+                    timestamp: chrono::Utc::now(),
+                    id: source.id.clone(),
+                    title: source.title.clone(),
+                    permanent: false,
+                });
+            }
+            merged_ids.push(source.id);
+        }
+
+        for topic_id in &touched_topics {
+            self.topic_repository.update_note_count(topic_id).await?;
+        }
+
+        let note = self
+            .note_repository
+            .find_by_id(&target.id)
+            .await?
+            .ok_or_else(|| DomainError::NoteNotFound(target.id.clone()))?;
+        self.publish_updated(&note, &["content", "tags", "topics", "attachments"]);
+
+        tracing::info!(
+            "[DuplicateUseCases] Merged {} notes into {} ({} notes relinked)",
+            merged_ids.len(),
+            note.id,
+            relinked_note_ids.len()
+        );
+
+        Ok(MergeNotesResponse {
+            note,
+            merged_ids,
+            relinked_note_ids,
+        })
+    }
+}
@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
+use crate::application::services::TransclusionService;
 use crate::domain::{
     errors::{DomainError, DomainResult},
     ports::{
@@ -25,6 +26,7 @@ pub struct ExportUseCasesImpl {
     file_storage: Arc<dyn FileStorage>,
     markdown_processor: Arc<dyn MarkdownProcessor>,
     export_service: Arc<dyn ExportService>,
+    transclusion_service: Arc<TransclusionService>,
 }
 
 impl ExportUseCasesImpl {
@@ -34,6 +36,7 @@ impl ExportUseCasesImpl {
         file_storage: Arc<dyn FileStorage>,
         markdown_processor: Arc<dyn MarkdownProcessor>,
         export_service: Arc<dyn ExportService>,
+        transclusion_service: Arc<TransclusionService>,
     ) -> Self {
         Self {
             note_repository,
@@ -41,6 +44,7 @@ impl ExportUseCasesImpl {
             file_storage,
             markdown_processor,
             export_service,
+            transclusion_service,
         }
     }
 
@@ -105,6 +109,9 @@ impl ExportUseCases for ExportUseCasesImpl {
                 .await?
                 .ok_or_else(|| DomainError::ValidationError("Could not read note content".to_string()))?;
 
+            // Render embedded sections inline
+            let markdown = self.transclusion_service.expand_embeds(&note, &markdown).await?;
+
             // Convert markdown to HTML
             self.markdown_processor.markdown_to_html(&markdown).await?
         };
@@ -200,6 +207,9 @@ impl ExportUseCases for ExportUseCasesImpl {
                 .await?
                 .ok_or_else(|| DomainError::ValidationError("Could not read note content".to_string()))?;
 
+            // Render embedded sections inline
+            let markdown = self.transclusion_service.expand_embeds(&note, &markdown).await?;
+
             // Convert markdown to HTML
             self.markdown_processor.markdown_to_html(&markdown).await?
         };
@@ -295,6 +305,9 @@ impl ExportUseCases for ExportUseCasesImpl {
             .await?
             .ok_or_else(|| DomainError::ValidationError("Could not read note content".to_string()))?;
 
+        // Inline embedded sections so the file stands on its own
+        markdown = self.transclusion_service.expand_embeds(&note, &markdown).await?;
+
         // Optionally add frontmatter
         if options
             .as_ref()
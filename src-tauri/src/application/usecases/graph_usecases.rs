@@ -2,37 +2,199 @@
 ///
 /// Application layer implementations for graph and link operations.
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 
+use crate::application::services::TransclusionService;
 use crate::domain::{
-    entities::NoteLink,
+    entities::{Note, NoteBlock, NoteChunk, NoteLink, Workspace},
     errors::{DomainError, DomainResult},
     ports::{
-        inbound::{GraphDataOptions, GraphUseCases, NoteLinkInfo, GraphData, GraphNode, GraphLink},
-        outbound::{NoteFindOptions, NoteLinkRepository, NoteRepository, WorkspaceRepository},
+        inbound::{
+            AmbiguousLink, ApplyLinkSuggestionRequest, EmbedContent, GraphData, GraphDataOptions,
+            GraphLink, GraphNode, GraphUseCases, LinkCandidate, LinkSuggestion,
+            LinkSuggestionKind, LinkSuggestionsRequest, NoteLinkInfo,
+        },
+        outbound::{
+            DomainEvent, EmbeddingRepository, EmbeddingSearchFilter, EmbeddingService,
+            EventPublisher, FileStorage, NoteBlockRepository, NoteChunkRepository,
+            NoteFindOptions, NoteLinkRepository, NoteRepository, WorkspaceRepository,
+        },
+    },
+    services::{
+        cosine_similarity, LinkExtractor, LinkResolution, LinkResolver, LinkSuggester,
+        MentionTarget, NoteReference,
     },
-    services::LinkExtractor,
 };
 
+/// Related notes suggested when the request doesn't say
+const DEFAULT_RELATED_SUGGESTIONS: usize = 5;
+
 /// Implementation of all Graph use cases
 pub struct GraphUseCasesImpl {
     note_repository: Arc<dyn NoteRepository>,
     note_link_repository: Arc<dyn NoteLinkRepository>,
     workspace_repository: Arc<dyn WorkspaceRepository>,
+    chunk_repository: Arc<dyn NoteChunkRepository>,
+    block_repository: Arc<dyn NoteBlockRepository>,
+    embedding_repository: Arc<dyn EmbeddingRepository>,
+    file_storage: Arc<dyn FileStorage>,
+    embedding_service: Arc<dyn EmbeddingService>,
+    transclusion_service: Arc<TransclusionService>,
+    event_publisher: Option<Arc<dyn EventPublisher>>,
 }
 
 impl GraphUseCasesImpl {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         note_repository: Arc<dyn NoteRepository>,
         note_link_repository: Arc<dyn NoteLinkRepository>,
         workspace_repository: Arc<dyn WorkspaceRepository>,
+        chunk_repository: Arc<dyn NoteChunkRepository>,
+        block_repository: Arc<dyn NoteBlockRepository>,
+        embedding_repository: Arc<dyn EmbeddingRepository>,
+        file_storage: Arc<dyn FileStorage>,
+        embedding_service: Arc<dyn EmbeddingService>,
+        transclusion_service: Arc<TransclusionService>,
+        event_publisher: Option<Arc<dyn EventPublisher>>,
     ) -> Self {
         Self {
             note_repository,
             note_link_repository,
             workspace_repository,
+            chunk_repository,
+            block_repository,
+            embedding_repository,
+            file_storage,
+            embedding_service,
+            transclusion_service,
+            event_publisher,
+        }
+    }
+
+    /// Load a note with its workspace and absolute file path
+    async fn note_file(&self, note_id: &str) -> DomainResult<(Note, Workspace, String)> {
+        let note = self
+            .note_repository
+            .find_by_id(note_id)
+            .await?
+            .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
+        let workspace_id = note
+            .workspace_id
+            .clone()
+            .ok_or_else(|| DomainError::ValidationError("Note has no workspace".to_string()))?;
+        let workspace = self
+            .workspace_repository
+            .find_by_id(&workspace_id)
+            .await?
+            .ok_or(DomainError::WorkspaceNotFound(workspace_id))?;
+        let file_path = note
+            .file_path
+            .as_ref()
+            .ok_or_else(|| DomainError::ValidationError("Note has no file path".to_string()))?;
+        let path = Path::new(&workspace.folder_path)
+            .join(file_path)
+            .to_string_lossy()
+            .to_string();
+
+        Ok((note, workspace, path))
+    }
+
+    /// Live notes of a workspace
+    async fn workspace_notes(&self, workspace_id: &str) -> DomainResult<Vec<Note>> {
+        self.note_repository
+            .find_all(NoteFindOptions {
+                workspace_id: Some(workspace_id.to_string()),
+                is_deleted: Some(false),
+                ..Default::default()
+            })
+            .await
+    }
+
+    /// Notes as link and mention targets, under their titles and aliases
+    fn link_targets(notes: &[Note]) -> Vec<MentionTarget> {
+        notes
+            .iter()
+            .map(|n| MentionTarget {
+                note_id: n.id.clone(),
+                title: n.title.clone(),
+                aliases: n.aliases.clone(),
+            })
+            .collect()
+    }
+
+    /// Resolve the links of a note's content to target note IDs and anchors
+    ///
+    /// Self-links are dropped. Returns the resolved targets and the links
+    /// several notes answer to.
+    fn resolve_links(
+        note_id: &str,
+        content: &str,
+        notes: &[Note],
+    ) -> (Vec<(String, Option<String>)>, Vec<AmbiguousLink>) {
+        let resolver = LinkResolver::new(&Self::link_targets(notes));
+        let by_id: HashMap<&str, &Note> = notes.iter().map(|n| (n.id.as_str(), n)).collect();
+
+        let mut targets: Vec<(String, Option<String>)> = Vec::new();
+        let mut ambiguous: Vec<AmbiguousLink> = Vec::new();
+        for NoteReference { title, anchor } in LinkExtractor::get_note_references(content) {
+            match resolver.resolve(&title) {
+                LinkResolution::Resolved(target_id) => {
+                    let target = (target_id, anchor.map(|a| a.to_string()));
+                    if target.0 != note_id && !targets.contains(&target) {
+                        targets.push(target);
+                    }
+                }
+                // Reported once per target, whatever anchors it is linked with
+                LinkResolution::Ambiguous(_) if ambiguous.iter().any(|a| a.target == title) => {}
+                LinkResolution::Ambiguous(note_ids) => ambiguous.push(AmbiguousLink {
+                    target: title,
+                    candidates: note_ids
+                        .iter()
+                        .filter_map(|id| by_id.get(id.as_str()))
+                        .map(|n| LinkCandidate {
+                            note_id: n.id.clone(),
+                            title: n.title.clone(),
+                            file_path: n.file_path.clone(),
+                        })
+                        .collect(),
+                }),
+                LinkResolution::Unresolved => {}
+            }
+        }
+
+        (targets, ambiguous)
+    }
+
+    /// Span of the passage closest to a related note's embedding
+    ///
+    /// Falls back to the end of the note when it has no usable passages.
+    fn closest_passage(
+        markdown: &str,
+        chunks: &[NoteChunk],
+        target_embedding: Option<&[f32]>,
+    ) -> (usize, usize) {
+        let best = target_embedding.and_then(|target| {
+            chunks
+                .iter()
+                .filter_map(|chunk| {
+                    let embedding = chunk.embedding.as_ref()?;
+                    let (start, end) = (chunk.start_offset as usize, chunk.end_offset as usize);
+                    markdown.get(start..end)?;
+                    Some((cosine_similarity(embedding, target).ok()?, start, end))
+                })
+                .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        });
+
+        match best {
+            // Links go right after the passage text, before trailing blank lines
+            Some((_, start, end)) => (start, start + markdown[start..end].trim_end().len()),
+            None => {
+                let end = markdown.trim_end().len();
+                (end, end)
+            }
         }
     }
 }
@@ -122,7 +284,13 @@ impl GraphUseCases for GraphUseCasesImpl {
             })
             .await?;
 
-        let all_links = self.note_link_repository.find_all().await?;
+        let mut all_links = self.note_link_repository.find_all().await?;
+
+        // One edge per pair of notes, whatever anchors the links point at
+        let mut linked_pairs = HashSet::new();
+        all_links.retain(|link| {
+            linked_pairs.insert((link.source_note_id.clone(), link.target_note_id.clone()))
+        });
 
         // Build link counts
         let mut link_counts: HashMap<String, i32> = HashMap::new();
@@ -209,16 +377,17 @@ impl GraphUseCases for GraphUseCasesImpl {
     }
 
     /// Update links for a note (called after content change)
-    async fn update_note_links(&self, note_id: &str, content: &str) -> DomainResult<()> {
+    async fn update_note_links(
+        &self,
+        note_id: &str,
+        content: &str,
+    ) -> DomainResult<Vec<AmbiguousLink>> {
         let _note = self
             .note_repository
             .find_by_id(note_id)
             .await?
             .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
 
-        // Extract referenced note titles from content
-        let referenced_titles = LinkExtractor::get_referenced_note_titles(content);
-
         // Delete existing links from this note
         self.note_link_repository
             .delete_from_note(note_id)
@@ -227,37 +396,221 @@ impl GraphUseCases for GraphUseCasesImpl {
         // Get active workspace
         let active_workspace = self.workspace_repository.find_active().await?;
         if active_workspace.is_none() {
-            return Ok(());
+            return Ok(Vec::new());
         }
         let workspace = active_workspace.unwrap();
 
-        // Find target notes by title and create links
-        let all_notes = self
+        // Find target notes by title or alias and create links
+        let all_notes = self.workspace_notes(&workspace.id).await?;
+        let (targets, ambiguous) = Self::resolve_links(note_id, content, &all_notes);
+
+        for (target_id, anchor) in targets {
+            let link = NoteLink::new(note_id, target_id)?.with_anchor(anchor);
+            self.note_link_repository.save(&link).await?;
+        }
+
+        for link in &ambiguous {
+            tracing::warn!(
+                "[GraphUseCases] [[{}]] in {} matches {} notes, not linking it",
+                link.target,
+                note_id,
+                link.candidates.len()
+            );
+        }
+
+        Ok(ambiguous)
+    }
+
+    /// Links in a note whose target several notes answer to
+    async fn get_ambiguous_links(&self, note_id: &str) -> DomainResult<Vec<AmbiguousLink>> {
+        let (note, workspace, path) = self.note_file(note_id).await?;
+        let markdown = self.file_storage.read(&path).await?.unwrap_or_default();
+        let notes = self.workspace_notes(&workspace.id).await?;
+
+        Ok(Self::resolve_links(&note.id, &markdown, &notes).1)
+    }
+
+    /// Blocks of a note marked with `^block-id`, as indexed
+    async fn get_note_blocks(&self, note_id: &str) -> DomainResult<Vec<NoteBlock>> {
+        self.block_repository.find_by_note(note_id).await
+    }
+
+    /// Resolve an embed in a note to the markdown it transcludes
+    async fn resolve_embed(
+        &self,
+        note_id: &str,
+        target: &str,
+    ) -> DomainResult<Option<EmbedContent>> {
+        let note = self
             .note_repository
-            .find_all(NoteFindOptions {
-                workspace_id: Some(workspace.id),
-                is_deleted: Some(false),
-                ..Default::default()
-            })
-            .await?;
+            .find_by_id(note_id)
+            .await?
+            .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
 
-        for title in referenced_titles {
-            let title_lower = title.to_lowercase();
+        self.transclusion_service.resolve_embed(&note, target).await
+    }
 
-            // Find target note with case-insensitive match
-            let target_note = all_notes.iter().find(|n| {
-                n.title.to_lowercase() == title_lower
+    /// Suggest links: unlinked mentions in document order, then related notes
+    async fn get_link_suggestions(
+        &self,
+        request: LinkSuggestionsRequest,
+    ) -> DomainResult<Vec<LinkSuggestion>> {
+        let (note, workspace, path) = self.note_file(&request.note_id).await?;
+        let markdown = self.file_storage.read(&path).await?.unwrap_or_default();
+
+        let notes = self.workspace_notes(&workspace.id).await?;
+        let titles: HashMap<&str, &str> = notes
+            .iter()
+            .map(|n| (n.id.as_str(), n.title.as_str()))
+            .collect();
+
+        // 1. Plain-text mentions, of titles and aliases
+        let targets: Vec<MentionTarget> = Self::link_targets(&notes)
+            .into_iter()
+            .filter(|target| target.note_id != note.id)
+            .collect();
+
+        let mut suggestions: Vec<LinkSuggestion> =
+            LinkSuggester::find_unlinked_mentions(&markdown, &targets)
+                .into_iter()
+                .map(|mention| LinkSuggestion {
+                    kind: LinkSuggestionKind::Mention,
+                    target_title: titles
+                        .get(mention.note_id.as_str())
+                        .map_or_else(|| mention.name.clone(), |t| t.to_string()),
+                    target_note_id: mention.note_id,
+                    text: mention.text,
+                    start_offset: mention.start,
+                    end_offset: mention.end,
+                    line_number: mention.line_number,
+                    similarity: None,
+                    target_passage: None,
+                })
+                .collect();
+
+        // 2. Semantically related notes that aren't linked or mentioned
+        let limit = request.related_limit.unwrap_or(DEFAULT_RELATED_SUGGESTIONS);
+        if limit == 0 {
+            return Ok(suggestions);
+        }
+
+        let mut skip: HashSet<String> = self
+            .note_link_repository
+            .get_forward_links(&note.id)
+            .await?
+            .into_iter()
+            .map(|n| n.id)
+            .collect();
+        skip.extend(suggestions.iter().map(|s| s.target_note_id.clone()));
+
+        let similar = match self
+            .embedding_service
+            .find_similar_notes(
+                &note.id,
+                Some(EmbeddingSearchFilter {
+                    workspace_id: Some(workspace.id.clone()),
+                    notebook_id: None,
+                }),
+                Some((limit + skip.len()) as i32),
+            )
+            .await
+        {
+            Ok(similar) => similar,
+            Err(e) => {
+                tracing::debug!("[GraphUseCases] No related notes for {}: {}", note.id, e);
+                Vec::new()
+            }
+        };
+
+        let mut chunks = self.chunk_repository.find_by_note(&note.id).await?;
+        let mut vectors: HashMap<i32, Vec<f32>> = self
+            .embedding_repository
+            .find_chunks_for_note(&note.id)
+            .await?
+            .into_iter()
+            .filter_map(|e| Some((e.chunk_index?, e.vector)))
+            .collect();
+        for chunk in &mut chunks {
+            chunk.embedding = vectors.remove(&chunk.chunk_index);
+        }
+        for related in similar
+            .into_iter()
+            .filter(|s| !skip.contains(&s.note_id))
+            .take(limit)
+        {
+            let target_embedding = self
+                .embedding_service
+                .get_embedding(&related.note_id)
+                .await?;
+            let (start, end) =
+                Self::closest_passage(&markdown, &chunks, target_embedding.as_deref());
+
+            suggestions.push(LinkSuggestion {
+                kind: LinkSuggestionKind::Related,
+                target_note_id: related.note_id,
+                target_title: related.title,
+                text: markdown[start..end].to_string(),
+                start_offset: start,
+                end_offset: end,
+                line_number: markdown[..start].matches('\n').count() + 1,
+                similarity: Some(related.similarity),
+                target_passage: related.passage,
             });
+        }
 
-            if let Some(target) = target_note {
-                // Don't create self-referencing links
-                if target.id != note_id {
-                    let link = NoteLink::new(note_id, target.id.clone())?;
-                    self.note_link_repository.save(&link).await?;
-                }
+        Ok(suggestions)
+    }
+
+    /// Turn a suggestion into a wiki link in the note's file
+    async fn apply_link_suggestion(
+        &self,
+        request: ApplyLinkSuggestionRequest,
+    ) -> DomainResult<String> {
+        let (note, _workspace, path) = self.note_file(&request.note_id).await?;
+        let target = self
+            .note_repository
+            .find_by_id(&request.target_note_id)
+            .await?
+            .filter(|target| !target.is_deleted)
+            .ok_or_else(|| DomainError::NoteNotFound(request.target_note_id.clone()))?;
+        let link = NoteLink::new(note.id.clone(), target.id.clone())?;
+
+        let markdown = self.file_storage.read(&path).await?.unwrap_or_default();
+        if let Some(ref text) = request.text {
+            if markdown.get(request.start_offset..request.end_offset) != Some(text.as_str()) {
+                return Err(DomainError::ValidationError(
+                    "The note changed since the suggestion was made".to_string(),
+                ));
             }
         }
 
-        Ok(())
+        let (start, end) = match request.kind {
+            LinkSuggestionKind::Mention => (request.start_offset, request.end_offset),
+            LinkSuggestionKind::Related => (request.end_offset, request.end_offset),
+        };
+        let rewritten =
+            LinkSuggester::link_span(&markdown, start, end, &target.title).ok_or_else(|| {
+                DomainError::ValidationError("This part of the note can't be linked".to_string())
+            })?;
+
+        self.file_storage.write(&path, &rewritten).await?;
+        self.note_link_repository.save(&link).await?;
+
+        if let Some(ref publisher) = self.event_publisher {
+            publisher.publish(DomainEvent::NoteUpdated {
+                timestamp: chrono::Utc::now(),
+                id: note.id.clone(),
+                title: note.title.clone(),
+                changes: vec!["content".to_string(), "links".to_string()],
+            });
+        }
+
+        tracing::info!(
+            "[GraphUseCases] Linked {} to {} ({:?})",
+            note.id,
+            target.id,
+            request.kind
+        );
+        Ok(rewritten)
     }
 }
@@ -1,33 +1,43 @@
 pub mod attachment_usecases;
 pub mod database_usecases;
+pub mod duplicate_usecases;
 pub mod export_usecases;
 pub mod git_usecases;
 pub mod graph_usecases;
 pub mod note_usecases;
 pub mod notebook_usecases;
+pub mod periodic_note_usecases;
+pub mod question_answering_usecases;
 pub mod quick_capture_usecases;
+pub mod refactor_usecases;
 pub mod search_usecases;
 pub mod settings_usecases;
 pub mod system_usecases;
 pub mod tag_usecases;
 pub mod task_usecases;
+pub mod template_usecases;
 pub mod topic_usecases;
 pub mod version_usecases;
 pub mod workspace_usecases;
 
 pub use attachment_usecases::AttachmentUseCasesImpl;
 pub use database_usecases::DatabaseUseCasesImpl;
+pub use duplicate_usecases::DuplicateUseCasesImpl;
 pub use export_usecases::ExportUseCasesImpl;
 pub use git_usecases::GitUseCasesImpl;
 pub use graph_usecases::GraphUseCasesImpl;
 pub use note_usecases::NoteUseCasesImpl;
 pub use notebook_usecases::NotebookUseCasesImpl;
+pub use periodic_note_usecases::PeriodicNoteUseCasesImpl;
+pub use question_answering_usecases::QuestionAnsweringUseCasesImpl;
 pub use quick_capture_usecases::QuickCaptureUseCasesImpl;
+pub use refactor_usecases::RefactorUseCasesImpl;
 pub use search_usecases::SearchUseCasesImpl;
 pub use settings_usecases::SettingsUseCasesImpl;
 pub use system_usecases::SystemUseCasesImpl;
 pub use tag_usecases::TagUseCasesImpl;
 pub use task_usecases::TaskUseCasesImpl;
+pub use template_usecases::TemplateUseCasesImpl;
 pub use topic_usecases::TopicUseCasesImpl;
 pub use version_usecases::VersionUseCasesImpl;
 pub use workspace_usecases::WorkspaceUseCasesImpl;
@@ -7,15 +7,20 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
+use crate::application::services::{
+    NoteFilenameService, NoteMetadataService, NoteRenameService, PeriodicNoteService,
+};
 use crate::domain::{
     entities::Note,
     errors::{DomainError, DomainResult},
     ports::{
         inbound::{CreateNoteInput, NoteQuery, NoteUseCases, UpdateNoteInput},
         outbound::{
-            EventPublisher, FileStorage, MarkdownProcessor, NoteRepository, WorkspaceRepository,
+            DomainEvent, EventPublisher, FileStorage, MarkdownProcessor, NoteRepository,
+            WorkspaceRepository,
         },
     },
+    services::{Frontmatter, NoteFrontmatter},
 };
 
 /// Implementation of all Note use cases
@@ -24,15 +29,24 @@ pub struct NoteUseCasesImpl {
     workspace_repository: Arc<dyn WorkspaceRepository>,
     file_storage: Arc<dyn FileStorage>,
     markdown_processor: Arc<dyn MarkdownProcessor>,
+    periodic_notes: Arc<PeriodicNoteService>,
+    note_filenames: Arc<NoteFilenameService>,
+    note_metadata: Arc<NoteMetadataService>,
+    note_renames: Arc<NoteRenameService>,
     event_publisher: Option<Arc<dyn EventPublisher>>,
 }
 
 impl NoteUseCasesImpl {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         note_repository: Arc<dyn NoteRepository>,
         workspace_repository: Arc<dyn WorkspaceRepository>,
         file_storage: Arc<dyn FileStorage>,
         markdown_processor: Arc<dyn MarkdownProcessor>,
+        periodic_notes: Arc<PeriodicNoteService>,
+        note_filenames: Arc<NoteFilenameService>,
+        note_metadata: Arc<NoteMetadataService>,
+        note_renames: Arc<NoteRenameService>,
         event_publisher: Option<Arc<dyn EventPublisher>>,
     ) -> Self {
         Self {
@@ -40,10 +54,29 @@ impl NoteUseCasesImpl {
             workspace_repository,
             file_storage,
             markdown_processor,
+            periodic_notes,
+            note_filenames,
+            note_metadata,
+            note_renames,
             event_publisher,
         }
     }
 
+    /// Full file markdown for a note body: the title heading, then the body
+    ///
+    /// Frontmatter at the start of the body stays at the top of the file.
+    fn compose_markdown(title: &str, body: &str) -> String {
+        match Frontmatter::split(body) {
+            (Some(frontmatter), rest) => format!(
+                "---\n{}\n---\n# {}\n\n{}",
+                frontmatter,
+                title,
+                rest.trim_start_matches(['\r', '\n'])
+            ),
+            (None, _) => format!("# {}\n\n{}", title, body),
+        }
+    }
+
     /// Strip the first H1 heading from markdown content
     fn strip_first_heading(&self, markdown: &str) -> String {
         let lines: Vec<&str> = markdown.lines().collect();
@@ -102,22 +135,16 @@ impl NoteUseCases for NoteUseCasesImpl {
             .clone()
             .unwrap_or_else(|| "Personal".to_string());
 
-        // Generate filename unless provided as relative_path
-        // For Journal entries, use the title (date) as the filename
-        // For other notes, use timestamp-based filename
-        let filename = if folder_path == "Journal" {
-            // Use title as filename for journal entries (e.g., "2026-01-15.md")
-            format!("{}.md", input.title.trim())
-        } else {
-            // Use timestamp for regular notes
-            format!("{}.md", chrono::Utc::now().format("%Y%m%d-%H%M%S-%3f"))
+        // Generate filename unless provided as relative_path, following the
+        // workspace's filename settings
+        let relative_path = match input.relative_path.clone() {
+            Some(relative_path) => relative_path,
+            None => {
+                self.note_filenames
+                    .new_file_path(&workspace, &folder_path, &input.title)
+                    .await?
+            }
         };
-
-        // Construct relative path: folderPath/filename.md
-        let relative_path = input
-            .relative_path
-            .clone()
-            .unwrap_or_else(|| format!("{}/{}", folder_path, filename));
         note.set_file_path(Some(relative_path.clone()))?;
 
         // Construct absolute path for file operations
@@ -130,12 +157,24 @@ impl NoteUseCases for NoteUseCasesImpl {
             .write(absolute_path.to_str().unwrap(), &content)
             .await?;
 
-        // Save to repository
+        // Save to repository, with what the frontmatter says about the note
+        let frontmatter = NoteFrontmatter::from_markdown(&content);
+        frontmatter.apply_to(&mut note);
         self.note_repository.save(&note).await?;
+        self.note_metadata
+            .sync_tags(&note.id, &frontmatter, &content)
+            .await?;
 
         // Publish event
         if let Some(ref publisher) = self.event_publisher {
-            publisher.emit("note:created", serde_json::json!({"id": note.id}));
+            publisher.publish(DomainEvent::NoteCreated {
+                timestamp: chrono::Utc::now(),
+                id: note.id.clone(),
+                title: note.title.clone(),
+                workspace_id: note.workspace_id.clone(),
+                notebook_id: note.notebook_id.clone(),
+                file_path: note.file_path.clone(),
+            });
         }
 
         Ok(note)
@@ -182,12 +221,37 @@ impl NoteUseCases for NoteUseCasesImpl {
             .await?
             .ok_or_else(|| DomainError::NoteNotFound(input.id.clone()))?;
 
+        let mut changes = Vec::new();
+
+        // Workspace for file path resolution
+        let workspace = match note.workspace_id {
+            Some(ref workspace_id) => self.workspace_repository.find_by_id(workspace_id).await?,
+            None => None,
+        };
+
+        // A new title rewrites the links to the note, as renaming it does
+        let mut rewrites = Vec::new();
+        let mut file_move = None;
         if let Some(title) = input.title {
-            note.update_title(title)?;
+            changes.push("title".to_string());
+            match workspace {
+                Some(ref workspace) if title != note.title => {
+                    rewrites = self.note_renames.plan(workspace, &note, &title).await?;
+                    file_move = self
+                        .note_renames
+                        .apply(workspace, &mut note, title, &rewrites)
+                        .await?;
+                    if file_move.is_some() {
+                        changes.push("filePath".to_string());
+                    }
+                }
+                _ => note.update_title(title)?,
+            }
         }
 
         if let Some(notebook_id) = input.notebook_id {
             note.move_to_notebook(Some(notebook_id));
+            changes.push("notebookId".to_string());
         }
 
         if let Some(is_favorite) = input.is_favorite {
@@ -214,50 +278,80 @@ impl NoteUseCases for NoteUseCasesImpl {
             }
         }
 
-        // Update content if provided
-        if let Some(content) = input.content {
-            if let Some(ref file_path) = note.file_path {
-                // Get workspace for file path resolution
-                let workspace_id = note
-                    .workspace_id
-                    .clone()
-                    .or_else(|| {
-                        // This is a bit awkward but we can't await in a closure
-                        None
-                    })
-                    .ok_or_else(|| {
-                        DomainError::ValidationError("Note has no workspace".to_string())
-                    })?;
-
-                let workspace = self
-                    .workspace_repository
-                    .find_by_id(&workspace_id)
-                    .await?
-                    .ok_or_else(|| {
+        let mut frontmatter = None;
+        let saved = async {
+            // Update content if provided
+            if let Some(content) = input.content {
+                changes.push("content".to_string());
+                if let Some(ref file_path) = note.file_path {
+                    if note.workspace_id.is_none() {
+                        return Err(DomainError::ValidationError(
+                            "Note has no workspace".to_string(),
+                        ));
+                    }
+                    let workspace = workspace.as_ref().ok_or_else(|| {
                         DomainError::ValidationError("Workspace not found".to_string())
                     })?;
 
-                let absolute_path = Path::new(&workspace.folder_path).join(file_path);
-                
-                // Content is already Markdown from the frontend (via serializeMarkdown), no conversion needed
-                // Calling html_to_markdown would incorrectly convert literal <br/> tags in code blocks
-                let body_markdown = content;
+                    let absolute_path = Path::new(&workspace.folder_path).join(file_path);
+
+                    // Content is already Markdown from the frontend (via serializeMarkdown), no conversion needed
+                    // Calling html_to_markdown would incorrectly convert literal <br/> tags in code blocks
+                    let body_markdown = content;
+
+                    // Prepend title heading
+                    let full_markdown = Self::compose_markdown(&note.title, &body_markdown);
 
-                // Prepend title heading
-                let title_heading = format!("# {}\n\n", note.title);
-                let full_markdown = title_heading + &body_markdown;
+                    let metadata = NoteFrontmatter::from_markdown(&full_markdown);
+                    changes.extend(metadata.apply_to(&mut note));
 
-                self.file_storage
-                    .write(absolute_path.to_str().unwrap(), &full_markdown)
-                    .await?;
+                    self.file_storage
+                        .write(absolute_path.to_str().unwrap(), &full_markdown)
+                        .await?;
+                    frontmatter = Some((metadata, full_markdown));
+                }
             }
+
+            self.note_repository.save(&note).await
+        }
+        .await;
+
+        // A renamed file goes back where the stored note says it is
+        if let Err(error) = saved {
+            if let Some(ref workspace) = workspace {
+                self.note_renames
+                    .undo(workspace, &rewrites, file_move.as_ref())
+                    .await;
+            }
+            return Err(error);
         }
 
-        self.note_repository.save(&note).await?;
+        if let Some((ref frontmatter, ref markdown)) = frontmatter {
+            if self
+                .note_metadata
+                .sync_tags(&note.id, frontmatter, markdown)
+                .await?
+            {
+                changes.push("tags".to_string());
+            }
+        }
 
         // Publish event
         if let Some(ref publisher) = self.event_publisher {
-            publisher.emit("note:updated", serde_json::json!({"id": note.id}));
+            publisher.publish(DomainEvent::NoteUpdated {
+                timestamp: chrono::Utc::now(),
+                id: note.id.clone(),
+                title: note.title.clone(),
+                changes,
+            });
+            for (source, _) in rewrites.iter().filter(|(n, _)| n.id != note.id) {
+                publisher.publish(DomainEvent::NoteUpdated {
+                    timestamp: chrono::Utc::now(),
+                    id: source.id.clone(),
+                    title: source.title.clone(),
+                    changes: vec!["content".to_string(), "links".to_string()],
+                });
+            }
         }
 
         Ok(note)
@@ -276,7 +370,12 @@ impl NoteUseCases for NoteUseCasesImpl {
 
         // Publish event
         if let Some(ref publisher) = self.event_publisher {
-            publisher.emit("note:deleted", serde_json::json!({"id": id}));
+            publisher.publish(DomainEvent::NoteDeleted {
+                timestamp: chrono::Utc::now(),
+                id: id.to_string(),
+                title: note.title.clone(),
+                permanent: false,
+            });
         }
 
         Ok(())
@@ -312,7 +411,12 @@ impl NoteUseCases for NoteUseCasesImpl {
 
         // Publish event
         if let Some(ref publisher) = self.event_publisher {
-            publisher.emit("note:deleted", serde_json::json!({"id": id}));
+            publisher.publish(DomainEvent::NoteDeleted {
+                timestamp: chrono::Utc::now(),
+                id: id.to_string(),
+                title: note.title.clone(),
+                permanent: true,
+            });
         }
 
         Ok(())
@@ -331,7 +435,12 @@ impl NoteUseCases for NoteUseCasesImpl {
 
         // Publish event
         if let Some(ref publisher) = self.event_publisher {
-            publisher.emit("note:updated", serde_json::json!({"id": id}));
+            publisher.publish(DomainEvent::NoteUpdated {
+                timestamp: chrono::Utc::now(),
+                id: id.to_string(),
+                title: note.title.clone(),
+                changes: vec!["isDeleted".to_string()],
+            });
         }
 
         Ok(())
@@ -552,7 +661,7 @@ impl NoteUseCases for NoteUseCasesImpl {
 
     /// Save note content to file
     async fn save_note_content(&self, id: &str, content: &str) -> DomainResult<()> {
-        let note = self
+        let mut note = self
             .note_repository
             .find_by_id(id)
             .await?
@@ -560,10 +669,12 @@ impl NoteUseCases for NoteUseCasesImpl {
 
         let file_path = note
             .file_path
+            .clone()
             .ok_or_else(|| DomainError::ValidationError("Note has no file path".to_string()))?;
 
         let workspace_id = note
             .workspace_id
+            .clone()
             .ok_or_else(|| DomainError::ValidationError("Note has no workspace".to_string()))?;
 
         let workspace = self
@@ -578,13 +689,37 @@ impl NoteUseCases for NoteUseCasesImpl {
         let body_markdown = content;
 
         // Prepend title heading
-        let title_heading = format!("# {}\n\n", note.title);
-        let full_markdown = title_heading + &body_markdown;
+        let full_markdown = Self::compose_markdown(&note.title, body_markdown);
 
         self.file_storage
             .write(absolute_path.to_str().unwrap(), &full_markdown)
             .await?;
 
+        let mut changes = vec!["content".to_string()];
+        let frontmatter = NoteFrontmatter::from_markdown(&full_markdown);
+        let metadata_changes = frontmatter.apply_to(&mut note);
+        if !metadata_changes.is_empty() {
+            self.note_repository.save(&note).await?;
+            changes.extend(metadata_changes);
+        }
+        if self
+            .note_metadata
+            .sync_tags(&note.id, &frontmatter, &full_markdown)
+            .await?
+        {
+            changes.push("tags".to_string());
+        }
+
+        // Publish event
+        if let Some(ref publisher) = self.event_publisher {
+            publisher.publish(DomainEvent::NoteUpdated {
+                timestamp: chrono::Utc::now(),
+                id: note.id.clone(),
+                title: note.title.clone(),
+                changes,
+            });
+        }
+
         Ok(())
     }
 
@@ -623,8 +758,10 @@ impl NoteUseCases for NoteUseCasesImpl {
             .and_then(|s| s.to_str())
             .unwrap_or("Untitled");
 
-        let is_journal_file = file_path.starts_with("Journal/");
-        let title = if is_journal_file {
+        let periodic_settings = self.periodic_notes.settings(&workspace.id).await?;
+        let is_periodic_file =
+            PeriodicNoteService::period_of_path(&periodic_settings, file_path).is_some();
+        let title = if is_periodic_file {
             filename_without_ext.to_string()
         } else {
             self.markdown_processor
@@ -635,11 +772,23 @@ impl NoteUseCases for NoteUseCasesImpl {
         let mut note = Note::new(&title, Some(workspace.id.clone()))?;
         note.set_file_path(Some(file_path.to_string()))?;
 
+        let frontmatter = NoteFrontmatter::from_markdown(&file_content);
+        frontmatter.apply_to(&mut note);
         self.note_repository.save(&note).await?;
+        self.note_metadata
+            .sync_tags(&note.id, &frontmatter, &file_content)
+            .await?;
 
         // Publish event
         if let Some(ref publisher) = self.event_publisher {
-            publisher.emit("note:created", serde_json::json!({"id": note.id}));
+            publisher.publish(DomainEvent::NoteCreated {
+                timestamp: chrono::Utc::now(),
+                id: note.id.clone(),
+                title: note.title.clone(),
+                workspace_id: note.workspace_id.clone(),
+                notebook_id: note.notebook_id.clone(),
+                file_path: note.file_path.clone(),
+            });
         }
 
         Ok(Some(note))
@@ -0,0 +1,97 @@
+/// Periodic Note Use Cases Implementation
+///
+/// Application layer implementation of daily, weekly, monthly and quarterly
+/// notes. Each period has its own folder, file name format and template,
+/// configured per workspace.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::application::services::PeriodicNoteService;
+use crate::domain::{
+    errors::{DomainError, DomainResult},
+    ports::inbound::{
+        OpenPeriodicNoteRequest, PeriodicNoteResponse, PeriodicNoteUseCases, PeriodicNotesSettings,
+    },
+    services::PeriodicNotes,
+};
+
+/// Implementation of all Periodic Note use cases
+pub struct PeriodicNoteUseCasesImpl {
+    periodic_notes: Arc<PeriodicNoteService>,
+}
+
+impl PeriodicNoteUseCasesImpl {
+    pub fn new(periodic_notes: Arc<PeriodicNoteService>) -> Self {
+        Self { periodic_notes }
+    }
+}
+
+#[async_trait]
+impl PeriodicNoteUseCases for PeriodicNoteUseCasesImpl {
+    /// Open the note of a period, creating it from its template if needed
+    ///
+    /// Previous and next periods are reached with a negative or positive
+    /// offset from the given date.
+    async fn open_periodic_note(
+        &self,
+        request: OpenPeriodicNoteRequest,
+    ) -> DomainResult<PeriodicNoteResponse> {
+        let workspace = self
+            .periodic_notes
+            .resolve_workspace(request.workspace_id.as_deref())
+            .await?;
+
+        let date = match request.date.as_deref() {
+            Some(date) => NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|_| DomainError::ValidationError(format!("Invalid date: {}", date)))?,
+            None => {
+                let settings = self.periodic_notes.settings(&workspace.id).await?;
+                PeriodicNoteService::today(&settings, chrono::Local::now().naive_local())?
+            }
+        };
+        let date = PeriodicNotes::shift(request.period, date, request.offset.unwrap_or(0));
+
+        let periodic = self
+            .periodic_notes
+            .open(&workspace, request.period, date)
+            .await?;
+
+        Ok(PeriodicNoteResponse {
+            note: periodic.note,
+            period: periodic.period,
+            start_date: periodic.start.format("%Y-%m-%d").to_string(),
+            end_date: periodic.end.format("%Y-%m-%d").to_string(),
+            created: periodic.created,
+        })
+    }
+
+    /// Get the periodic notes settings of a workspace
+    async fn get_periodic_settings(
+        &self,
+        workspace_id: Option<String>,
+    ) -> DomainResult<PeriodicNotesSettings> {
+        let workspace = self
+            .periodic_notes
+            .resolve_workspace(workspace_id.as_deref())
+            .await?;
+        self.periodic_notes.settings(&workspace.id).await
+    }
+
+    /// Change the periodic notes settings of a workspace
+    async fn update_periodic_settings(
+        &self,
+        workspace_id: Option<String>,
+        settings: PeriodicNotesSettings,
+    ) -> DomainResult<PeriodicNotesSettings> {
+        let workspace = self
+            .periodic_notes
+            .resolve_workspace(workspace_id.as_deref())
+            .await?;
+        self.periodic_notes
+            .save_settings(&workspace.id, &settings)
+            .await?;
+        Ok(settings)
+    }
+}
@@ -0,0 +1,362 @@
+/// Question Answering Use Cases Implementation
+///
+/// Application layer implementation of answers grounded in the user's notes.
+/// Passages are retrieved by semantic or hybrid search and sent with the
+/// question to the configured chat endpoint; the answer streams back as
+/// events and cites the passages it used.
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::{
+    entities::Note,
+    errors::{DomainError, DomainResult},
+    ports::{
+        inbound::{
+            AnswerCitation, AnswerResponse, AnswerSource, AskQuestionRequest,
+            QuestionAnsweringUseCases, RetrievalMode,
+        },
+        outbound::{
+            ChatMessage, ChatModelConfig, ChatService, DomainEvent, EmbeddingSearchFilter,
+            EmbeddingService, EventPublisher, FileStorage, HybridSearchOptions, NotePassage,
+            NoteRepository, SearchEngine, SearchOptions, SettingsRepository, WorkspaceRepository,
+            CHAT_MODEL_SETTING,
+        },
+    },
+    services::{AnswerPrompt, ContextPassage, NoteChunker, ANSWER_SYSTEM_PROMPT},
+};
+
+/// Passages given to the model when the request doesn't say
+const DEFAULT_PASSAGE_LIMIT: i32 = 6;
+
+/// Upper bound on passages, to stay within small local context windows
+const MAX_PASSAGE_LIMIT: i32 = 20;
+
+/// Answer when no note matches the question; the model isn't asked then
+const NO_SOURCES_ANSWER: &str = "I couldn't find anything in your notes about this.";
+
+/// Implementation of all Question Answering use cases
+pub struct QuestionAnsweringUseCasesImpl {
+    note_repository: Arc<dyn NoteRepository>,
+    workspace_repository: Arc<dyn WorkspaceRepository>,
+    file_storage: Arc<dyn FileStorage>,
+    search_engine: Arc<dyn SearchEngine>,
+    embedding_service: Arc<dyn EmbeddingService>,
+    chat_service: Arc<dyn ChatService>,
+    settings_repository: Arc<dyn SettingsRepository>,
+    event_publisher: Option<Arc<dyn EventPublisher>>,
+    chunker: NoteChunker,
+}
+
+impl QuestionAnsweringUseCasesImpl {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        workspace_repository: Arc<dyn WorkspaceRepository>,
+        file_storage: Arc<dyn FileStorage>,
+        search_engine: Arc<dyn SearchEngine>,
+        embedding_service: Arc<dyn EmbeddingService>,
+        chat_service: Arc<dyn ChatService>,
+        settings_repository: Arc<dyn SettingsRepository>,
+        event_publisher: Option<Arc<dyn EventPublisher>>,
+    ) -> Self {
+        Self {
+            note_repository,
+            workspace_repository,
+            file_storage,
+            search_engine,
+            embedding_service,
+            chat_service,
+            settings_repository,
+            event_publisher,
+            chunker: NoteChunker::default(),
+        }
+    }
+
+    fn publish(&self, event: DomainEvent) {
+        if let Some(ref publisher) = self.event_publisher {
+            publisher.publish(event);
+        }
+    }
+
+    fn publish_delta(&self, question_id: &str, delta: &str) {
+        self.publish(DomainEvent::AnswerDelta {
+            timestamp: chrono::Utc::now(),
+            question_id: question_id.to_string(),
+            delta: delta.to_string(),
+        });
+    }
+
+    /// Find passages for a question, best first, at most one per note
+    async fn retrieve(
+        &self,
+        question: &str,
+        request: &AskQuestionRequest,
+        limit: i32,
+    ) -> DomainResult<Vec<AnswerSource>> {
+        let filter = EmbeddingSearchFilter {
+            workspace_id: request.workspace_id.clone(),
+            notebook_id: request.notebook_id.clone(),
+        };
+
+        let hits: Vec<(String, String, Option<NotePassage>)> =
+            match request.retrieval.unwrap_or_default() {
+                RetrievalMode::Semantic => {
+                    if !self.embedding_service.is_ready() {
+                        return Err(DomainError::ValidationError(
+                            "Embedding model is not ready".to_string(),
+                        ));
+                    }
+                    self.embedding_service
+                        .semantic_search(question, Some(filter), Some(limit))
+                        .await?
+                        .into_iter()
+                        .map(|hit| (hit.note_id, hit.title, hit.passage))
+                        .collect()
+                }
+                RetrievalMode::Hybrid => {
+                    let options = HybridSearchOptions {
+                        base: SearchOptions {
+                            limit: Some(limit),
+                            workspace_id: request.workspace_id.clone(),
+                            notebook_id: request.notebook_id.clone(),
+                            exclude_deleted: Some(true),
+                            ..Default::default()
+                        },
+                        weights: None,
+                        fusion: None,
+                    };
+                    let results = self
+                        .search_engine
+                        .search_hybrid(
+                            &AnswerPrompt::search_terms(question).join(" "),
+                            Some(options),
+                        )
+                        .await?;
+
+                    // Semantic hits know which passage matched; the rest are
+                    // looked up in the note's text
+                    let mut passages = self.semantic_passages(question, filter, limit).await;
+                    results
+                        .into_iter()
+                        .map(|result| {
+                            let passage = passages.remove(&result.note.id);
+                            (result.note.id, result.note.title, passage)
+                        })
+                        .collect()
+                }
+            };
+
+        let terms = AnswerPrompt::search_terms(question);
+        let mut sources = Vec::new();
+        for (note_id, title, passage) in hits {
+            let passage = match passage {
+                Some(passage) => Some(passage),
+                None => self.keyword_passage(&note_id, &terms).await?,
+            };
+            let Some(passage) = passage else {
+                continue; // Nothing left of the note to quote
+            };
+
+            sources.push(AnswerSource {
+                number: sources.len() as i32 + 1,
+                note_id,
+                title,
+                heading_path: passage.heading_path,
+                text: passage.text,
+                start_offset: passage.start_offset,
+                end_offset: passage.end_offset,
+            });
+        }
+
+        Ok(sources)
+    }
+
+    /// Best passage per note by embedding similarity, when embeddings are available
+    async fn semantic_passages(
+        &self,
+        question: &str,
+        filter: EmbeddingSearchFilter,
+        limit: i32,
+    ) -> HashMap<String, NotePassage> {
+        if !self.embedding_service.is_ready() {
+            return HashMap::new();
+        }
+
+        match self
+            .embedding_service
+            .semantic_search(question, Some(filter), Some(limit.saturating_mul(2)))
+            .await
+        {
+            Ok(hits) => hits
+                .into_iter()
+                .filter_map(|hit| Some((hit.note_id, hit.passage?)))
+                .collect(),
+            Err(e) => {
+                tracing::warn!(
+                    "[QuestionAnsweringUseCases] Semantic retrieval failed: {}",
+                    e
+                );
+                HashMap::new()
+            }
+        }
+    }
+
+    /// The passage of a note that mentions the most search terms
+    async fn keyword_passage(
+        &self,
+        note_id: &str,
+        terms: &[String],
+    ) -> DomainResult<Option<NotePassage>> {
+        let Some(note) = self.note_repository.find_by_id(note_id).await? else {
+            return Ok(None);
+        };
+        let Some(markdown) = self.read_markdown(&note).await? else {
+            return Ok(None);
+        };
+
+        let chunks = self.chunker.chunk(&markdown);
+        Ok(
+            AnswerPrompt::best_passage(&chunks, terms).map(|chunk| NotePassage {
+                chunk_index: chunk.index as i32,
+                heading_path: chunk.heading_path.clone(),
+                text: chunk.text.clone(),
+                start_offset: chunk.start as i32,
+                end_offset: chunk.end as i32,
+            }),
+        )
+    }
+
+    async fn read_markdown(&self, note: &Note) -> DomainResult<Option<String>> {
+        let (Some(file_path), Some(workspace_id)) = (&note.file_path, &note.workspace_id) else {
+            return Ok(None);
+        };
+        let Some(workspace) = self.workspace_repository.find_by_id(workspace_id).await? else {
+            return Ok(None);
+        };
+
+        let absolute_path = Path::new(&workspace.folder_path).join(file_path);
+        self.file_storage
+            .read(&absolute_path.to_string_lossy())
+            .await
+    }
+
+    /// Group the answer's citation markers by source, in order of first citation
+    fn citations(answer: &str, sources: &[AnswerSource]) -> Vec<AnswerCitation> {
+        let mut citations: Vec<AnswerCitation> = Vec::new();
+
+        for marker in AnswerPrompt::find_citations(answer, sources.len()) {
+            let source = &sources[marker.source - 1];
+            let range = (marker.start as i32, marker.end as i32);
+            match citations.iter_mut().find(|c| c.number == source.number) {
+                Some(citation) => citation.answer_ranges.push(range),
+                None => citations.push(AnswerCitation {
+                    number: source.number,
+                    note_id: source.note_id.clone(),
+                    title: source.title.clone(),
+                    start_offset: source.start_offset,
+                    end_offset: source.end_offset,
+                    answer_ranges: vec![range],
+                }),
+            }
+        }
+
+        citations
+    }
+}
+
+#[async_trait]
+impl QuestionAnsweringUseCases for QuestionAnsweringUseCasesImpl {
+    /// Answer a question from retrieved passages
+    ///
+    /// The answer streams as `qa:answer-delta` events tagged with the
+    /// question ID, followed by one `qa:answered` event.
+    async fn ask_question(&self, request: AskQuestionRequest) -> DomainResult<AnswerResponse> {
+        let question = request.question.trim();
+        if question.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Question cannot be empty".to_string(),
+            ));
+        }
+
+        let config = self.get_chat_settings().await?;
+        let question_id = request
+            .question_id
+            .clone()
+            .filter(|id| !id.trim().is_empty())
+            .unwrap_or_else(|| nanoid::nanoid!());
+        let limit = request
+            .limit
+            .unwrap_or(DEFAULT_PASSAGE_LIMIT)
+            .clamp(1, MAX_PASSAGE_LIMIT);
+
+        let sources = self.retrieve(question, &request, limit).await?;
+
+        let answer = if sources.is_empty() {
+            self.publish_delta(&question_id, NO_SOURCES_ANSWER);
+            NO_SOURCES_ANSWER.to_string()
+        } else {
+            let passages: Vec<ContextPassage> = sources
+                .iter()
+                .map(|source| ContextPassage {
+                    title: source.title.clone(),
+                    heading_path: source.heading_path.clone(),
+                    text: source.text.clone(),
+                })
+                .collect();
+            let messages = vec![
+                ChatMessage::system(ANSWER_SYSTEM_PROMPT),
+                ChatMessage::user(AnswerPrompt::user_prompt(question, &passages)),
+            ];
+
+            let on_delta = |delta: &str| self.publish_delta(&question_id, delta);
+            self.chat_service
+                .stream_chat(&config, messages, &on_delta)
+                .await?
+        };
+
+        let citations = Self::citations(&answer, &sources);
+        let mut cited_note_ids: Vec<String> = Vec::new();
+        for citation in &citations {
+            if !cited_note_ids.contains(&citation.note_id) {
+                cited_note_ids.push(citation.note_id.clone());
+            }
+        }
+        self.publish(DomainEvent::QuestionAnswered {
+            timestamp: chrono::Utc::now(),
+            question_id: question_id.clone(),
+            cited_note_ids,
+        });
+
+        Ok(AnswerResponse {
+            question_id,
+            answer,
+            citations,
+            sources,
+        })
+    }
+
+    /// Get the chat endpoint questions are sent to
+    async fn get_chat_settings(&self) -> DomainResult<ChatModelConfig> {
+        match self.settings_repository.get(CHAT_MODEL_SETTING).await? {
+            Some(setting) => ChatModelConfig::from_setting(&setting.value),
+            None => Ok(ChatModelConfig::default()),
+        }
+    }
+
+    /// Change the chat endpoint questions are sent to
+    async fn update_chat_settings(&self, config: ChatModelConfig) -> DomainResult<ChatModelConfig> {
+        config.validate()?;
+        self.settings_repository
+            .set(CHAT_MODEL_SETTING, &config.to_setting()?)
+            .await?;
+        tracing::info!(
+            "[QuestionAnsweringUseCases] Chat model set to {} at {}",
+            config.model,
+            config.base_url
+        );
+        Ok(config)
+    }
+}
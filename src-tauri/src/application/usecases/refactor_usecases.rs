@@ -0,0 +1,427 @@
+/// Refactor Use Cases Implementation
+///
+/// Application layer implementations for refactorings across notes. Renaming
+/// a note rewrites its title heading and the wiki links of every note linking
+/// to it, found through the stored backlinks, and renames its file when the
+/// workspace's filename settings ask for it. Renaming a tag, or merging it
+/// into another, rewrites the frontmatter tags lists and hashtags of the notes
+/// carrying it or any tag nested under it.
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::application::services::{FileRewrite, LinkRefactorService, NoteRenameService};
+use crate::domain::{
+    entities::{Note, Tag, Workspace},
+    errors::{DomainError, DomainResult},
+    ports::{
+        inbound::{
+            FileChange, RefactorUseCases, RenameNotePlan, RenameNoteRequest, RenameNoteResponse,
+            RenameTagPlan, RenameTagRequest, RenameTagResponse, TagRename,
+        },
+        outbound::{
+            DomainEvent, EventPublisher, NoteRepository, TagMove, TagRepository,
+            WorkspaceRepository,
+        },
+    },
+    services::{Frontmatter, HashtagExtractor},
+};
+
+/// A planned rename: the note, its new title and the files to rewrite
+struct RenamePlan {
+    note: Note,
+    new_title: String,
+    /// The note's workspace, once the title changes
+    workspace: Option<Workspace>,
+    /// Each rewrite with the note stored in the file
+    rewrites: Vec<(Note, FileRewrite)>,
+}
+
+impl RenamePlan {
+    fn changes(&self) -> Vec<FileChange> {
+        file_changes(&self.rewrites)
+    }
+}
+
+/// A planned tag rename: the tags it moves and the files to rewrite
+struct TagPlan {
+    tag: Tag,
+    new_name: String,
+    /// The tag itself first, then the tags nested under it
+    renames: Vec<(Tag, TagRename)>,
+    note_count: usize,
+    /// Each rewrite with the note stored in the file
+    rewrites: Vec<(Note, FileRewrite)>,
+}
+
+impl TagPlan {
+    fn changes(&self) -> Vec<FileChange> {
+        file_changes(&self.rewrites)
+    }
+
+    fn tag_renames(&self) -> Vec<TagRename> {
+        self.renames
+            .iter()
+            .map(|(_, rename)| rename.clone())
+            .collect()
+    }
+}
+
+fn file_changes(rewrites: &[(Note, FileRewrite)]) -> Vec<FileChange> {
+    rewrites
+        .iter()
+        .map(|(note, rewrite)| FileChange {
+            note_id: Some(note.id.clone()),
+            title: Some(note.title.clone()),
+            file_path: rewrite.file_path.clone(),
+            edits: rewrite.edits(),
+        })
+        .collect()
+}
+
+/// Implementation of all Refactor use cases
+pub struct RefactorUseCasesImpl {
+    note_repository: Arc<dyn NoteRepository>,
+    tag_repository: Arc<dyn TagRepository>,
+    workspace_repository: Arc<dyn WorkspaceRepository>,
+    link_refactor: Arc<LinkRefactorService>,
+    note_renames: Arc<NoteRenameService>,
+    event_publisher: Option<Arc<dyn EventPublisher>>,
+}
+
+impl RefactorUseCasesImpl {
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        tag_repository: Arc<dyn TagRepository>,
+        workspace_repository: Arc<dyn WorkspaceRepository>,
+        link_refactor: Arc<LinkRefactorService>,
+        note_renames: Arc<NoteRenameService>,
+        event_publisher: Option<Arc<dyn EventPublisher>>,
+    ) -> Self {
+        Self {
+            note_repository,
+            tag_repository,
+            workspace_repository,
+            link_refactor,
+            note_renames,
+            event_publisher,
+        }
+    }
+
+    async fn plan_rename(&self, request: &RenameNoteRequest) -> DomainResult<RenamePlan> {
+        let note = self
+            .note_repository
+            .find_by_id(&request.note_id)
+            .await?
+            .ok_or_else(|| DomainError::NoteNotFound(request.note_id.clone()))?;
+        if note.is_deleted {
+            return Err(DomainError::ValidationError(
+                "Cannot rename a deleted note".to_string(),
+            ));
+        }
+
+        let new_title = request.new_title.trim().to_string();
+        // Validates the title the same way the rename itself will
+        note.clone().update_title(new_title.clone())?;
+
+        let mut plan = RenamePlan {
+            note,
+            new_title,
+            workspace: None,
+            rewrites: Vec::new(),
+        };
+        if plan.new_title == plan.note.title {
+            return Ok(plan);
+        }
+
+        let workspace_id = plan
+            .note
+            .workspace_id
+            .clone()
+            .ok_or_else(|| DomainError::ValidationError("Note has no workspace".to_string()))?;
+        let workspace = self
+            .workspace_repository
+            .find_by_id(&workspace_id)
+            .await?
+            .ok_or(DomainError::WorkspaceNotFound(workspace_id))?;
+
+        plan.rewrites = self
+            .note_renames
+            .plan(&workspace, &plan.note, &plan.new_title)
+            .await?;
+        plan.workspace = Some(workspace);
+        Ok(plan)
+    }
+
+    /// Rename the tag `from` in a note's frontmatter tags list and hashtags
+    fn retag(markdown: &str, from: &str, to: &str) -> Option<String> {
+        let tagged = HashtagExtractor::rename_tags(markdown, from, to);
+        let markdown = tagged.as_deref().unwrap_or(markdown);
+
+        match Frontmatter::from_markdown(markdown) {
+            Ok(Some(mut frontmatter)) => match frontmatter.rename_tags(from, to) {
+                true => Some(frontmatter.write_to(markdown)),
+                false => tagged,
+            },
+            _ => tagged,
+        }
+    }
+
+    async fn plan_tag_rename(&self, request: &RenameTagRequest) -> DomainResult<TagPlan> {
+        let tag = self
+            .tag_repository
+            .find_by_id(&request.tag_id)
+            .await?
+            .ok_or_else(|| {
+                DomainError::ValidationError(format!("Tag not found with id: {}", request.tag_id))
+            })?;
+        let new_name = Tag::normalize_name(&request.new_name)?;
+        let old_name = tag.name.clone();
+
+        let mut plan = TagPlan {
+            tag,
+            new_name,
+            renames: Vec::new(),
+            note_count: 0,
+            rewrites: Vec::new(),
+        };
+        if plan.new_name == old_name {
+            return Ok(plan);
+        }
+        if Tag::is_within(&plan.new_name, &old_name) {
+            return Err(DomainError::ValidationError(format!(
+                "Cannot move tag {} under itself",
+                old_name
+            )));
+        }
+
+        let all_tags = self.tag_repository.find_all().await?;
+        let mut moved: Vec<&Tag> = all_tags
+            .iter()
+            .filter(|other| Tag::is_within(&other.name, &old_name))
+            .collect();
+        moved.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut note_ids = BTreeSet::new();
+        for tag in moved {
+            let Some(to) = Tag::rebase_name(&tag.name, &old_name, &plan.new_name) else {
+                continue;
+            };
+            // Validates the name the same way the rename itself will
+            tag.clone().rename(to.clone())?;
+            let merged_into = all_tags
+                .iter()
+                .find(|existing| existing.name == to)
+                .map(|existing| existing.id.clone());
+
+            note_ids.extend(self.tag_repository.find_note_ids(&tag.id).await?);
+            plan.renames.push((
+                tag.clone(),
+                TagRename {
+                    tag_id: tag.id.clone(),
+                    from: tag.name.clone(),
+                    to,
+                    merged_into,
+                },
+            ));
+        }
+        plan.note_count = note_ids.len();
+
+        let mut workspaces: HashMap<String, Option<Workspace>> = HashMap::new();
+        for note_id in note_ids {
+            let Some(note) = self.note_repository.find_by_id(&note_id).await? else {
+                continue;
+            };
+            let Some(workspace_id) = note.workspace_id.clone() else {
+                continue;
+            };
+            if !workspaces.contains_key(&workspace_id) {
+                let workspace = self.workspace_repository.find_by_id(&workspace_id).await?;
+                workspaces.insert(workspace_id.clone(), workspace);
+            }
+            let Some(Some(workspace)) = workspaces.get(&workspace_id) else {
+                continue;
+            };
+
+            let new_name = plan.new_name.clone();
+            if let Some(rewrite) = self
+                .link_refactor
+                .plan_note_rewrite(workspace, &note, |markdown| {
+                    Self::retag(markdown, &old_name, &new_name)
+                })
+                .await?
+            {
+                plan.rewrites.push((note, rewrite));
+            }
+        }
+        plan.rewrites
+            .sort_by(|(_, a), (_, b)| a.file_path.cmp(&b.file_path));
+
+        Ok(plan)
+    }
+
+    /// Rename or merge every tag in the plan, in one transaction
+    async fn move_tags(&self, plan: &TagPlan) -> DomainResult<()> {
+        let mut moves = Vec::with_capacity(plan.renames.len());
+        for (tag, rename) in &plan.renames {
+            moves.push(match &rename.merged_into {
+                Some(into_id) => TagMove::Merge {
+                    from_id: tag.id.clone(),
+                    into_id: into_id.clone(),
+                },
+                None => {
+                    let mut tag = tag.clone();
+                    tag.rename(rename.to.clone())?;
+                    TagMove::Rename(tag)
+                }
+            });
+        }
+        self.tag_repository.move_tags(moves).await
+    }
+
+    fn publish_updated(&self, note: &Note, changes: &[&str]) {
+        if let Some(ref publisher) = self.event_publisher {
+            publisher.publish(DomainEvent::NoteUpdated {
+                timestamp: chrono::Utc::now(),
+                id: note.id.clone(),
+                title: note.title.clone(),
+                changes: changes.iter().map(|c| c.to_string()).collect(),
+            });
+        }
+    }
+}
+
+#[async_trait]
+impl RefactorUseCases for RefactorUseCasesImpl {
+    /// Plan a note rename without writing anything
+    async fn preview_note_rename(
+        &self,
+        request: RenameNoteRequest,
+    ) -> DomainResult<RenameNotePlan> {
+        let plan = self.plan_rename(&request).await?;
+
+        Ok(RenameNotePlan {
+            note_id: plan.note.id.clone(),
+            old_title: plan.note.title.clone(),
+            new_title: plan.new_title.clone(),
+            changes: plan.changes(),
+        })
+    }
+
+    /// Rename a note and rewrite the links to it
+    ///
+    /// The plan is made again from the files as they are now. Files are
+    /// written and the note's file renamed first; both are put back if the
+    /// title can't be saved.
+    async fn rename_note(&self, request: RenameNoteRequest) -> DomainResult<RenameNoteResponse> {
+        let plan = self.plan_rename(&request).await?;
+        let changes = plan.changes();
+        let mut note = plan.note.clone();
+        let Some(ref workspace) = plan.workspace else {
+            return Ok(RenameNoteResponse { note, changes });
+        };
+
+        let file_move = self
+            .note_renames
+            .apply(workspace, &mut note, plan.new_title.clone(), &plan.rewrites)
+            .await?;
+
+        if let Err(error) = self.note_repository.save(&note).await {
+            self.note_renames
+                .undo(workspace, &plan.rewrites, file_move.as_ref())
+                .await;
+            return Err(error);
+        }
+
+        self.publish_updated(&note, &["title"]);
+        for (source, _) in plan.rewrites.iter().filter(|(n, _)| n.id != note.id) {
+            self.publish_updated(source, &["content", "links"]);
+        }
+
+        tracing::info!(
+            "[RefactorUseCases] Renamed {} to {} ({} files rewritten)",
+            plan.note.title,
+            note.title,
+            plan.rewrites.len()
+        );
+
+        Ok(RenameNoteResponse { note, changes })
+    }
+
+    /// Plan a tag rename or merge without writing anything
+    async fn preview_tag_rename(&self, request: RenameTagRequest) -> DomainResult<RenameTagPlan> {
+        let plan = self.plan_tag_rename(&request).await?;
+
+        Ok(RenameTagPlan {
+            tag_id: plan.tag.id.clone(),
+            old_name: plan.tag.name.clone(),
+            new_name: plan.new_name.clone(),
+            tags: plan.tag_renames(),
+            note_count: plan.note_count,
+            changes: plan.changes(),
+        })
+    }
+
+    /// Rename a tag or merge it into another, rewriting the files that use it
+    ///
+    /// The files are written first and put back if the tags can't be moved.
+    /// One event sums up the whole rename.
+    async fn rename_tag(&self, request: RenameTagRequest) -> DomainResult<RenameTagResponse> {
+        let plan = self.plan_tag_rename(&request).await?;
+        let changes = plan.changes();
+        let tags = plan.tag_renames();
+        if plan.renames.is_empty() {
+            return Ok(RenameTagResponse {
+                tag: plan.tag,
+                tags,
+                changes,
+            });
+        }
+
+        let rewrites: Vec<FileRewrite> = plan
+            .rewrites
+            .iter()
+            .map(|(_, rewrite)| rewrite.clone())
+            .collect();
+        self.link_refactor.apply(&rewrites).await?;
+
+        if let Err(error) = self.move_tags(&plan).await {
+            self.link_refactor.revert(&rewrites).await;
+            return Err(error);
+        }
+
+        let tag = self
+            .tag_repository
+            .find_by_name(&plan.new_name)
+            .await?
+            .ok_or_else(|| {
+                DomainError::ValidationError(format!("Tag not found: {}", plan.new_name))
+            })?;
+        let merged = tags[0].merged_into.is_some();
+
+        if let Some(ref publisher) = self.event_publisher {
+            publisher.publish(DomainEvent::TagRenamed {
+                timestamp: chrono::Utc::now(),
+                id: tag.id.clone(),
+                from: plan.tag.name.clone(),
+                to: tag.name.clone(),
+                merged,
+                tag_count: tags.len(),
+                note_count: plan.note_count,
+                file_count: rewrites.len(),
+            });
+        }
+
+        tracing::info!(
+            "[RefactorUseCases] {} tag {} into {} ({} tags, {} files rewritten)",
+            if merged { "Merged" } else { "Renamed" },
+            plan.tag.name,
+            tag.name,
+            tags.len(),
+            rewrites.len()
+        );
+
+        Ok(RenameTagResponse { tag, tags, changes })
+    }
+}
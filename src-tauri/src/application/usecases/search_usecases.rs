@@ -9,17 +9,21 @@ use chrono::{DateTime, Utc};
 
 use crate::domain::{
     entities::Note,
-    errors::DomainResult,
+    errors::{DomainError, DomainResult},
     ports::{
         inbound::{
-            HybridSearchResultItem, HybridSearchWeights, SearchByDateRangeRequest, SearchRequest,
+            HybridSearchResultItem, HybridSearchWeights, SearchByDateRangeRequest,
+            SearchByTagsRequest, SearchRequest,
             SearchType, SearchUseCases, VectorSearchResult,
         },
         outbound::{
-            DateRangeField, DateRangeOptions, EmbeddingService, NoteRepository, SearchEngine,
-            SearchOptions, SearchResult, TagSearchOptions,
+            DateRangeField, DateRangeOptions, EmbeddingSearchFilter, EmbeddingService,
+            HybridSearchOptions,
+            NoteRepository, SearchEngine, SearchOptions, SearchResult, SearchRetriever,
+            SearchWeights, TagSearchOptions,
         },
     },
+    services::SearchQueryParser,
 };
 
 /// Implementation of all Search use cases
@@ -44,7 +48,14 @@ impl SearchUseCasesImpl {
 #[async_trait]
 impl SearchUseCases for SearchUseCasesImpl {
     /// Full-text search across notes
+    ///
+    /// The query may contain structured filters (`tag:`, `notebook:`, `is:`,
+    /// `created:`/`updated:`), quoted phrases and `-exclusions`.
     async fn full_text_search(&self, request: SearchRequest) -> DomainResult<Vec<SearchResult>> {
+        let parsed =
+            SearchQueryParser::parse(&request.query).map_err(DomainError::InvalidSearchQuery)?;
+        let text = parsed.text();
+
         let options = SearchOptions {
             limit: request.limit,
             offset: request.offset,
@@ -52,11 +63,12 @@ impl SearchUseCases for SearchUseCasesImpl {
             tag_ids: request.tag_ids.clone(),
             workspace_id: request.workspace_id.clone(),
             exclude_deleted: Some(true),
+            ..parsed.options
         };
 
         let results = self
             .search_engine
-            .search_full_text(&request.query, Some(options))
+            .search_full_text(&text, Some(options))
             .await?;
 
         Ok(results)
@@ -68,11 +80,15 @@ impl SearchUseCases for SearchUseCasesImpl {
         request: SearchRequest,
     ) -> DomainResult<Vec<VectorSearchResult>> {
         let limit = request.limit.or(Some(10));
+        let filter = EmbeddingSearchFilter {
+            workspace_id: request.workspace_id.clone(),
+            notebook_id: request.notebook_id.clone(),
+        };
 
         // Use embedding service's semantic search
         let results = self
             .embedding_service
-            .semantic_search(&request.query, limit)
+            .semantic_search(&request.query, Some(filter), limit)
             .await?;
 
         // Convert SimilarNote to VectorSearchResult
@@ -82,6 +98,7 @@ impl SearchUseCases for SearchUseCasesImpl {
                 note_id: similar_note.note_id,
                 title: similar_note.title,
                 distance: similar_note.distance,
+                passage: similar_note.passage,
             })
             .collect();
 
@@ -99,7 +116,7 @@ impl SearchUseCases for SearchUseCasesImpl {
         // Use embedding service's find similar notes
         let results = self
             .embedding_service
-            .find_similar_notes(note_id, limit)
+            .find_similar_notes(note_id, None, limit)
             .await?;
 
         // Convert SimilarNote to VectorSearchResult
@@ -109,6 +126,7 @@ impl SearchUseCases for SearchUseCasesImpl {
                 note_id: similar_note.note_id,
                 title: similar_note.title,
                 distance: similar_note.distance,
+                passage: similar_note.passage,
             })
             .collect();
 
@@ -121,37 +139,50 @@ impl SearchUseCases for SearchUseCasesImpl {
     }
 
     /// Hybrid search (FTS + semantic)
+    ///
+    /// Keyword and semantic matches are fused into one ranked list using the
+    /// requested weights and fusion method.
     async fn hybrid_search(
         &self,
         query: &str,
-        _weights: Option<HybridSearchWeights>,
+        weights: Option<HybridSearchWeights>,
         limit: Option<i32>,
     ) -> DomainResult<Vec<HybridSearchResultItem>> {
-        let limit = limit.or(Some(50));
+        let parsed = SearchQueryParser::parse(query).map_err(DomainError::InvalidSearchQuery)?;
+        let text = parsed.text();
 
-        // For now, use full-text search as primary
-        // (can be enhanced with semantic search later)
-        let options = SearchOptions {
-            limit,
-            offset: None,
-            notebook_id: None,
-            tag_ids: None,
-            workspace_id: None,
-            exclude_deleted: Some(true),
+        let options = HybridSearchOptions {
+            base: SearchOptions {
+                limit: limit.or(Some(50)),
+                exclude_deleted: Some(true),
+                ..parsed.options
+            },
+            fusion: weights.as_ref().and_then(|w| w.fusion),
+            weights: weights.map(|w| SearchWeights {
+                fts: w.fts,
+                semantic: w.semantic,
+            }),
         };
 
-        let fts_results = self
+        let results = self
             .search_engine
-            .search_full_text(query, Some(options))
+            .search_hybrid(&text, Some(options))
             .await?;
 
         // Convert SearchResult to HybridSearchResultItem
-        let hybrid_results = fts_results
+        let hybrid_results = results
             .into_iter()
-            .map(|search_result| HybridSearchResultItem {
-                note: search_result.note,
-                score: 1.0, // Fixed score for now
-                search_type: SearchType::Fts,
+            .map(|search_result| {
+                let search_type = match search_result.retrievers.as_slice() {
+                    [SearchRetriever::Semantic] => SearchType::Semantic,
+                    [SearchRetriever::Fts] => SearchType::Fts,
+                    _ => SearchType::Hybrid,
+                };
+                HybridSearchResultItem {
+                    note: search_result.note,
+                    score: search_result.relevance,
+                    search_type,
+                }
             })
             .collect();
 
@@ -159,27 +190,22 @@ impl SearchUseCases for SearchUseCasesImpl {
     }
 
     /// Search by tags
-    async fn search_by_tags(
-        &self,
-        tag_ids: Vec<String>,
-        match_all: Option<bool>,
-        limit: Option<i32>,
-    ) -> DomainResult<Vec<Note>> {
+    async fn search_by_tags(&self, request: SearchByTagsRequest) -> DomainResult<Vec<Note>> {
         let options = TagSearchOptions {
             base: SearchOptions {
-                limit,
-                offset: None,
-                notebook_id: None,
-                tag_ids: None,
-                workspace_id: None,
+                limit: request.limit,
+                offset: request.offset,
+                workspace_id: request.workspace_id,
                 exclude_deleted: Some(true),
+                ..Default::default()
             },
-            match_all,
+            match_all: request.match_all,
+            include_descendants: request.include_descendants,
         };
 
         let results = self
             .search_engine
-            .search_by_tags(tag_ids, Some(options))
+            .search_by_tags(request.tag_ids, Some(options))
             .await?;
 
         Ok(results)
@@ -213,6 +239,7 @@ impl SearchUseCases for SearchUseCasesImpl {
             workspace_id: request.workspace_id.clone(),
             field: Some(field),
             limit: request.limit,
+            offset: request.offset,
         };
 
         let results = self.search_engine.search_by_date_range(options).await?;
@@ -5,8 +5,9 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
+use crate::application::services::NoteMetadataService;
 use crate::domain::{
-    entities::Tag,
+    entities::{NoteTag, Tag},
     errors::{DomainError, DomainResult},
     ports::{
         inbound::{CreateTagRequest, ListTagsRequest, TagList, TagUseCases, UpdateTagRequest},
@@ -17,19 +18,35 @@ use crate::domain::{
 /// Implementation of all Tag use cases
 pub struct TagUseCasesImpl {
     tag_repository: Arc<dyn TagRepository>,
+    note_metadata: Arc<NoteMetadataService>,
     event_publisher: Option<Arc<dyn EventPublisher>>,
 }
 
 impl TagUseCasesImpl {
     pub fn new(
         tag_repository: Arc<dyn TagRepository>,
+        note_metadata: Arc<NoteMetadataService>,
         event_publisher: Option<Arc<dyn EventPublisher>>,
     ) -> Self {
         Self {
             tag_repository,
+            note_metadata,
             event_publisher,
         }
     }
+
+    /// Write a note's tags back into its file's frontmatter
+    ///
+    /// The tag change itself stands if the file can't be written.
+    async fn write_tags(&self, note_id: &str) {
+        if let Err(error) = self.note_metadata.write_tags(note_id).await {
+            tracing::warn!(
+                "[TagUseCases] Could not write tags into note {}: {}",
+                note_id,
+                error
+            );
+        }
+    }
 }
 
 #[async_trait]
@@ -67,6 +84,9 @@ impl TagUseCases for TagUseCasesImpl {
     }
 
     /// Update an existing tag
+    ///
+    /// Renames go through `RefactorUseCases::rename_tag`, which also moves the
+    /// tags nested under it and rewrites the notes using them.
     async fn update_tag(&self, request: UpdateTagRequest) -> DomainResult<Tag> {
         let mut tag = self
             .tag_repository
@@ -76,9 +96,14 @@ impl TagUseCases for TagUseCasesImpl {
                 DomainError::ValidationError(format!("Tag not found with id: {}", request.id))
             })?;
 
-        // Update name if provided
+        // Only an unchanged name is accepted here
         if let Some(name) = request.name {
-            tag.rename(name)?;  // Returns Result
+            if Tag::normalize_name(&name)? != tag.name {
+                return Err(DomainError::ValidationError(format!(
+                    "Tag {} must be renamed with rename_tag",
+                    tag.name
+                )));
+            }
         }
 
         // Update color if provided
@@ -150,6 +175,7 @@ impl TagUseCases for TagUseCasesImpl {
     async fn add_tag_to_note(&self, note_id: &str, tag_id: &str) -> DomainResult<()> {
         // ✅ ASYNC
         self.tag_repository.add_tag_to_note(note_id, tag_id).await?;
+        self.write_tags(note_id).await;
 
         // Publish event - ❌ SYNC - NO AWAIT!
         if let Some(ref publisher) = self.event_publisher {
@@ -168,6 +194,7 @@ impl TagUseCases for TagUseCasesImpl {
         self.tag_repository
             .remove_tag_from_note(note_id, tag_id)
             .await?;
+        self.write_tags(note_id).await;
 
         // Publish event - ❌ SYNC - NO AWAIT!
         if let Some(ref publisher) = self.event_publisher {
@@ -181,8 +208,7 @@ impl TagUseCases for TagUseCasesImpl {
     }
 
     /// Get all tags for a note
-    async fn get_note_tags(&self, note_id: &str) -> DomainResult<Vec<Tag>> {
-        // ⚠️ Use get_note_tags() not find_by_note_id() - ✅ ASYNC
-        self.tag_repository.get_note_tags(note_id).await
+    async fn get_note_tags(&self, note_id: &str) -> DomainResult<Vec<NoteTag>> {
+        self.tag_repository.find_note_tags_with_source(note_id).await
     }
 }
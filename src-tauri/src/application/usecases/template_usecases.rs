@@ -0,0 +1,421 @@
+/// Template Use Cases Implementation
+///
+/// Application layer implementation of note templates. Templates are
+/// markdown files in a configurable folder of the workspace; a new note gets
+/// the template's text with its placeholders expanded, and the tags and
+/// notebook its frontmatter names.
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::application::services::NoteFilenameService;
+use crate::domain::{
+    entities::{Note, Notebook, Tag, Workspace},
+    errors::{DomainError, DomainResult},
+    ports::{
+        inbound::{
+            CreateNoteFromTemplateRequest, CreateNoteFromTemplateResponse, TemplateInfo,
+            TemplateSettings, TemplateUseCases,
+        },
+        outbound::{
+            DomainEvent, EventPublisher, FileStorage, NoteRepository, NotebookRepository,
+            SettingsRepository, TagRepository, WorkspaceRepository,
+        },
+    },
+    services::{Frontmatter, NoteFrontmatter, TemplateContext, TemplateRenderer},
+};
+
+/// Implementation of all Template use cases
+pub struct TemplateUseCasesImpl {
+    note_repository: Arc<dyn NoteRepository>,
+    notebook_repository: Arc<dyn NotebookRepository>,
+    workspace_repository: Arc<dyn WorkspaceRepository>,
+    tag_repository: Arc<dyn TagRepository>,
+    settings_repository: Arc<dyn SettingsRepository>,
+    file_storage: Arc<dyn FileStorage>,
+    note_filenames: Arc<NoteFilenameService>,
+    event_publisher: Option<Arc<dyn EventPublisher>>,
+}
+
+impl TemplateUseCasesImpl {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        note_repository: Arc<dyn NoteRepository>,
+        notebook_repository: Arc<dyn NotebookRepository>,
+        workspace_repository: Arc<dyn WorkspaceRepository>,
+        tag_repository: Arc<dyn TagRepository>,
+        settings_repository: Arc<dyn SettingsRepository>,
+        file_storage: Arc<dyn FileStorage>,
+        note_filenames: Arc<NoteFilenameService>,
+        event_publisher: Option<Arc<dyn EventPublisher>>,
+    ) -> Self {
+        Self {
+            note_repository,
+            notebook_repository,
+            workspace_repository,
+            tag_repository,
+            settings_repository,
+            file_storage,
+            note_filenames,
+            event_publisher,
+        }
+    }
+
+    /// The given workspace, or the active one
+    async fn resolve_workspace(&self, workspace_id: Option<&str>) -> DomainResult<Workspace> {
+        match workspace_id {
+            Some(id) => self.workspace_repository.find_by_id(id).await?,
+            None => self.workspace_repository.find_active().await?,
+        }
+        .ok_or_else(|| DomainError::ValidationError("No active workspace".to_string()))
+    }
+
+    async fn load_settings(&self, workspace_id: &str) -> DomainResult<TemplateSettings> {
+        match self
+            .settings_repository
+            .get(&TemplateSettings::setting_key(workspace_id))
+            .await?
+        {
+            Some(setting) => TemplateSettings::from_setting(&setting.value),
+            None => Ok(TemplateSettings::default()),
+        }
+    }
+
+    async fn save_settings(
+        &self,
+        workspace_id: &str,
+        settings: &TemplateSettings,
+    ) -> DomainResult<()> {
+        settings.validate()?;
+        self.settings_repository
+            .set(
+                &TemplateSettings::setting_key(workspace_id),
+                &settings.to_setting()?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Reject template paths that would leave the templates folder
+    fn check_template_path(template_path: &str) -> DomainResult<()> {
+        let escapes = template_path.starts_with(['/', '\\'])
+            || template_path.split(['/', '\\']).any(|part| part == "..");
+        if template_path.trim().is_empty() || escapes {
+            return Err(DomainError::ValidationError(format!(
+                "Invalid template path: {}",
+                template_path
+            )));
+        }
+        Ok(())
+    }
+
+    /// Default template of a notebook, inherited from the nearest ancestor
+    async fn notebook_template(
+        &self,
+        settings: &TemplateSettings,
+        notebook_id: &str,
+    ) -> DomainResult<Option<String>> {
+        if let Some(path) = settings.notebook_templates.get(notebook_id) {
+            return Ok(Some(path.clone()));
+        }
+        for ancestor_id in self
+            .notebook_repository
+            .get_ancestor_ids(notebook_id)
+            .await?
+        {
+            if let Some(path) = settings.notebook_templates.get(&ancestor_id) {
+                return Ok(Some(path.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Notebook of the workspace with the given ID or name
+    async fn find_notebook(
+        &self,
+        workspace_id: &str,
+        id_or_name: &str,
+    ) -> DomainResult<Option<Notebook>> {
+        let notebooks = self
+            .notebook_repository
+            .find_by_workspace_id(workspace_id)
+            .await?;
+        Ok(notebooks
+            .iter()
+            .find(|notebook| notebook.id == id_or_name)
+            .or_else(|| {
+                notebooks
+                    .iter()
+                    .find(|notebook| notebook.name.eq_ignore_ascii_case(id_or_name))
+            })
+            .cloned())
+    }
+
+    /// Tag a note, creating tags that don't exist yet
+    async fn apply_tags(&self, note: &Note, names: &[String]) -> DomainResult<()> {
+        let mut applied: Vec<String> = Vec::new();
+        for name in names {
+            let Ok(normalized) = Tag::normalize_name(name) else {
+                continue;
+            };
+            if applied.contains(&normalized) {
+                continue;
+            }
+            applied.push(normalized.clone());
+            let tag = match self.tag_repository.find_by_name(&normalized).await? {
+                Some(tag) => tag,
+                None => {
+                    let tag = Tag::new(normalized)?;
+                    self.tag_repository.save(&tag).await?;
+                    tag
+                }
+            };
+            self.tag_repository
+                .add_tag_to_note(&note.id, &tag.id)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Put a `# Title` heading after the frontmatter unless the template has one
+    fn with_title_heading(
+        content: &str,
+        title: &str,
+        cursor: Option<usize>,
+    ) -> (String, Option<usize>) {
+        let (_, body) = Frontmatter::split(content);
+        if body.lines().any(|line| line.starts_with("# ")) {
+            return (content.to_string(), cursor);
+        }
+
+        let split = content.len() - body.len();
+        let heading = format!("# {}\n\n", title);
+        let trimmed = body.trim_start_matches(['\r', '\n']);
+        let skipped = body.len() - trimmed.len();
+
+        let cursor = cursor.map(|offset| match offset.checked_sub(split) {
+            Some(in_body) => split + heading.len() + in_body.saturating_sub(skipped),
+            None => offset,
+        });
+        (
+            format!("{}{}{}", &content[..split], heading, trimmed),
+            cursor,
+        )
+    }
+}
+
+#[async_trait]
+impl TemplateUseCases for TemplateUseCasesImpl {
+    /// List templates of a workspace (the active one when not given)
+    async fn list_templates(
+        &self,
+        workspace_id: Option<String>,
+    ) -> DomainResult<Vec<TemplateInfo>> {
+        let workspace = self.resolve_workspace(workspace_id.as_deref()).await?;
+        let settings = self.load_settings(&workspace.id).await?;
+
+        let folder = Path::new(&workspace.folder_path).join(&settings.folder);
+        let folder = folder.to_string_lossy();
+        if !self.file_storage.exists(&folder).await? {
+            return Ok(Vec::new());
+        }
+
+        let mut templates: Vec<TemplateInfo> = self
+            .file_storage
+            .glob("**/*.md", &folder)
+            .await?
+            .into_iter()
+            .map(|path| {
+                let path = path.replace('\\', "/");
+                TemplateInfo {
+                    name: Path::new(&path)
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    path,
+                }
+            })
+            .collect();
+        templates.sort_by_key(|template| template.path.to_lowercase());
+
+        Ok(templates)
+    }
+
+    /// Get the template settings of a workspace
+    async fn get_template_settings(
+        &self,
+        workspace_id: Option<String>,
+    ) -> DomainResult<TemplateSettings> {
+        let workspace = self.resolve_workspace(workspace_id.as_deref()).await?;
+        self.load_settings(&workspace.id).await
+    }
+
+    /// Change the template settings of a workspace
+    async fn update_template_settings(
+        &self,
+        workspace_id: Option<String>,
+        mut settings: TemplateSettings,
+    ) -> DomainResult<TemplateSettings> {
+        let workspace = self.resolve_workspace(workspace_id.as_deref()).await?;
+        settings.folder = settings.folder.trim().trim_matches('/').to_string();
+        for path in settings.notebook_templates.values() {
+            Self::check_template_path(path)?;
+        }
+
+        self.save_settings(&workspace.id, &settings).await?;
+        Ok(settings)
+    }
+
+    /// Set or clear the default template of a notebook
+    async fn set_notebook_template(
+        &self,
+        notebook_id: &str,
+        template_path: Option<String>,
+    ) -> DomainResult<TemplateSettings> {
+        let notebook = self
+            .notebook_repository
+            .find_by_id(notebook_id)
+            .await?
+            .ok_or_else(|| DomainError::NotebookNotFound(notebook_id.to_string()))?;
+        let workspace = self
+            .resolve_workspace(notebook.workspace_id.as_deref())
+            .await?;
+
+        let mut settings = self.load_settings(&workspace.id).await?;
+        match template_path.filter(|path| !path.trim().is_empty()) {
+            Some(path) => {
+                Self::check_template_path(&path)?;
+                settings
+                    .notebook_templates
+                    .insert(notebook.id.clone(), path);
+            }
+            None => {
+                settings.notebook_templates.remove(&notebook.id);
+            }
+        }
+
+        self.save_settings(&workspace.id, &settings).await?;
+        Ok(settings)
+    }
+
+    /// Create a note from a template with its placeholders expanded
+    ///
+    /// Without a template path, the default template of the notebook (or its
+    /// nearest ancestor) is used.
+    async fn create_note_from_template(
+        &self,
+        request: CreateNoteFromTemplateRequest,
+    ) -> DomainResult<CreateNoteFromTemplateResponse> {
+        let workspace = self
+            .resolve_workspace(request.workspace_id.as_deref())
+            .await?;
+        let settings = self.load_settings(&workspace.id).await?;
+
+        let template_path = match request.template_path.clone() {
+            Some(path) => Some(path),
+            None => match request.notebook_id.as_deref() {
+                Some(notebook_id) => self.notebook_template(&settings, notebook_id).await?,
+                None => None,
+            },
+        }
+        .ok_or_else(|| {
+            DomainError::ValidationError(
+                "No template given and the notebook has no default template".to_string(),
+            )
+        })?;
+        Self::check_template_path(&template_path)?;
+
+        let absolute_template = Path::new(&workspace.folder_path)
+            .join(&settings.folder)
+            .join(&template_path);
+        let template = self
+            .file_storage
+            .read(&absolute_template.to_string_lossy())
+            .await?
+            .ok_or_else(|| {
+                DomainError::ValidationError(format!("Template not found: {}", template_path))
+            })?;
+
+        // Validates the title before anything is written
+        let mut note = Note::new(&request.title, Some(workspace.id.clone()))?;
+
+        let rendered = TemplateRenderer::render(
+            &template,
+            &TemplateContext {
+                title: note.title.clone(),
+                workspace: workspace.name.clone(),
+                now: chrono::Local::now().naive_local(),
+            },
+        );
+
+        // The request's notebook wins over the template's
+        let notebook = match (&request.notebook_id, &rendered.notebook) {
+            (Some(notebook_id), _) => Some(
+                self.notebook_repository
+                    .find_by_id(notebook_id)
+                    .await?
+                    .ok_or_else(|| DomainError::NotebookNotFound(notebook_id.clone()))?,
+            ),
+            (None, Some(id_or_name)) => {
+                let notebook = self.find_notebook(&workspace.id, id_or_name).await?;
+                if notebook.is_none() {
+                    tracing::warn!(
+                        "[TemplateUseCases] Template {} names unknown notebook {}",
+                        template_path,
+                        id_or_name
+                    );
+                }
+                notebook
+            }
+            (None, None) => None,
+        };
+        if let Some(ref notebook) = notebook {
+            note.move_to_notebook(Some(notebook.id.clone()));
+        }
+
+        let folder_path = request
+            .folder_path
+            .clone()
+            .or_else(|| notebook.as_ref().and_then(|n| n.folder_path.clone()))
+            .unwrap_or_else(|| "Personal".to_string());
+        let relative_path = self
+            .note_filenames
+            .new_file_path(&workspace, &folder_path, &note.title)
+            .await?;
+        note.set_file_path(Some(relative_path.clone()))?;
+
+        let (content, cursor) =
+            Self::with_title_heading(&rendered.content, &note.title, rendered.cursor);
+        let absolute_path = Path::new(&workspace.folder_path).join(&relative_path);
+        self.file_storage
+            .write(&absolute_path.to_string_lossy(), &content)
+            .await?;
+
+        NoteFrontmatter::from_markdown(&content).apply_to(&mut note);
+        self.note_repository.save(&note).await?;
+        self.apply_tags(&note, &rendered.tags).await?;
+
+        if let Some(ref publisher) = self.event_publisher {
+            publisher.publish(DomainEvent::NoteCreated {
+                timestamp: chrono::Utc::now(),
+                id: note.id.clone(),
+                title: note.title.clone(),
+                workspace_id: note.workspace_id.clone(),
+                notebook_id: note.notebook_id.clone(),
+                file_path: note.file_path.clone(),
+            });
+        }
+
+        tracing::info!(
+            "[TemplateUseCases] Created note {} from template {}",
+            note.id,
+            template_path
+        );
+
+        Ok(CreateNoteFromTemplateResponse {
+            note,
+            cursor_offset: cursor.map(|offset| offset as i32),
+        })
+    }
+}
@@ -7,24 +7,34 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
+use crate::application::services::{EmbeddingQueue, EmbeddingQueueOptions, NoteEmbedder};
 use crate::domain::{
     entities::{Note, Topic},
     errors::{DomainError, DomainResult},
     ports::{
         inbound::{
-            ClassifyAllResponse, ClassifyNoteResponse, CreateTopicRequest, EmbeddingStatusResponse,
-            NoteTopicInfo, SimilarNoteResult, TopicClassification, TopicUseCases,
-            UpdateTopicRequest,
+            AcceptTopicCandidateRequest, ClassifyAllResponse, ClassifyNoteResponse,
+            CreateTopicRequest, DiscoverTopicsRequest, DiscoverTopicsResponse,
+            EmbeddingSettingsResponse, EmbeddingStatusResponse, NoteTopicInfo, SimilarNoteResult,
+            StartEmbeddingQueueRequest, TopicCandidate, TopicCandidateMember, TopicClassification,
+            TopicUseCases, UpdateTopicRequest,
         },
         outbound::{
-            EmbeddingService, EventPublisher, FileStorage, FindAllWithCountsOptions,
-            GetNotesForTopicOptions, MarkdownProcessor, NoteFindOptions, NoteRepository,
+            DomainEvent, EmbeddingProviderConfig, EmbeddingQueueStatus, EmbeddingSearchFilter,
+            EmbeddingService, EventPublisher, FileStorage, FindAllWithCountsOptions, GetNotesForTopicOptions,
+            MarkdownProcessor, NoteFindOptions, NoteRepository, SettingsRepository,
             TopicAssignmentOptions, TopicRepository, TopicWithCount, WorkspaceRepository,
+            EMBEDDING_PROVIDER_SETTING,
         },
     },
-    services::similarity_calculator,
+    services::{
+        cosine_similarity, CentroidMember, DiscoveryConfig, TopicClassifier, TopicDiscovery,
+    },
 };
 
+/// Candidates this close to an existing topic's centroid are not proposed
+const EXISTING_TOPIC_SIMILARITY: f32 = 0.9;
+
 /// Implementation of all Topic use cases
 pub struct TopicUseCasesImpl {
     topic_repository: Arc<dyn TopicRepository>,
@@ -33,10 +43,13 @@ pub struct TopicUseCasesImpl {
     file_storage: Arc<dyn FileStorage>,
     embedding_service: Arc<dyn EmbeddingService>,
     markdown_processor: Arc<dyn MarkdownProcessor>,
+    settings_repository: Arc<dyn SettingsRepository>,
+    embedding_queue: Arc<EmbeddingQueue>,
     event_publisher: Option<Arc<dyn EventPublisher>>,
 }
 
 impl TopicUseCasesImpl {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         topic_repository: Arc<dyn TopicRepository>,
         note_repository: Arc<dyn NoteRepository>,
@@ -44,6 +57,8 @@ impl TopicUseCasesImpl {
         file_storage: Arc<dyn FileStorage>,
         embedding_service: Arc<dyn EmbeddingService>,
         markdown_processor: Arc<dyn MarkdownProcessor>,
+        settings_repository: Arc<dyn SettingsRepository>,
+        embedding_queue: Arc<EmbeddingQueue>,
         event_publisher: Option<Arc<dyn EventPublisher>>,
     ) -> Self {
         Self {
@@ -53,31 +68,88 @@ impl TopicUseCasesImpl {
             file_storage,
             embedding_service,
             markdown_processor,
+            settings_repository,
+            embedding_queue,
             event_publisher,
         }
     }
 
-    /// Calculate the centroid (average) of a set of embeddings
-    fn calculate_centroid(embeddings: &[Vec<f32>]) -> Vec<f32> {
-        if embeddings.is_empty() {
-            return Vec::new();
+    /// Re-embed notes embedded with another model through the embedding queue
+    ///
+    /// The queue batches and throttles the work; a run in progress is
+    /// restarted since it scanned with the previous model.
+    async fn queue_stale_notes(&self) -> DomainResult<usize> {
+        let stale_note_ids = self.embedding_service.find_stale_note_ids().await?;
+        if stale_note_ids.is_empty() {
+            return Ok(0);
         }
 
-        let dimensions = embeddings[0].len();
-        let mut centroid = vec![0.0; dimensions];
-
-        for embedding in embeddings {
-            for (i, &value) in embedding.iter().enumerate() {
-                centroid[i] += value;
-            }
+        let count = stale_note_ids.len();
+        let model = self.embedding_service.model();
+        tracing::info!(
+            "[TopicUseCases] Queueing {} notes for re-embedding with {}",
+            count,
+            model.name
+        );
+        if let Some(ref publisher) = self.event_publisher {
+            publisher.publish(DomainEvent::EmbeddingModelChanged {
+                timestamp: chrono::Utc::now(),
+                model_name: model.name,
+                model_version: model.version,
+                stale_note_ids,
+            });
         }
+        self.embedding_queue
+            .restart(EmbeddingQueueOptions::default())?;
+
+        Ok(count)
+    }
+
+    /// Read a note's markdown from its file (None when it has no file)
+    async fn read_markdown(&self, note: &Note) -> DomainResult<Option<String>> {
+        let (Some(file_path), Some(workspace_id)) = (&note.file_path, &note.workspace_id) else {
+            return Ok(None);
+        };
 
-        let count = embeddings.len() as f32;
-        for value in centroid.iter_mut() {
-            *value /= count;
+        let workspace = self
+            .workspace_repository
+            .find_by_id(workspace_id)
+            .await?
+            .ok_or_else(|| DomainError::WorkspaceNotFound(workspace_id.clone()))?;
+
+        let absolute_path = Path::new(&workspace.folder_path).join(file_path);
+        self.file_storage
+            .read(&absolute_path.to_string_lossy())
+            .await
+    }
+
+    /// Embed a note from its file; returns false when it has no content
+    async fn embed_note(&self, note: &Note) -> DomainResult<bool> {
+        let markdown = self.read_markdown(note).await?.unwrap_or_default();
+        if markdown.is_empty() {
+            return Ok(false);
         }
 
-        centroid
+        // Embed the note passage by passage; the note embedding is their mean
+        let embedder =
+            NoteEmbedder::new(self.embedding_service.clone(), self.markdown_processor.clone());
+        Ok(embedder.embed_note(&note.id, &markdown).await?.is_some())
+    }
+
+    /// Title and plain text of a note, for labelling discovered topics
+    async fn note_text(&self, note: &Note) -> String {
+        let body = match self.read_markdown(note).await {
+            Ok(Some(markdown)) => self
+                .markdown_processor
+                .extract_plain_text(&markdown)
+                .unwrap_or_default(),
+            Ok(None) => String::new(),
+            Err(e) => {
+                tracing::warn!("[TopicUseCases] Could not read note {}: {:?}", note.id, e);
+                String::new()
+            }
+        };
+        format!("{}\n{}", note.title, body)
     }
 }
 
@@ -88,6 +160,9 @@ impl TopicUseCases for TopicUseCasesImpl {
         self.embedding_service.initialize().await?;
         let ready = self.embedding_service.is_ready();
         tracing::info!("[TopicUseCases] Embedding service initialized, ready: {}", ready);
+
+        // Catches up on notes edited, added or left on another model while closed
+        self.embedding_queue.start(EmbeddingQueueOptions::default())?;
         Ok(())
     }
 
@@ -198,6 +273,8 @@ impl TopicUseCases for TopicUseCasesImpl {
     }
 
     /// Classify a note into topics based on its embedding
+    ///
+    /// Notes without an embedding are embedded from their file first.
     async fn classify_note(&self, note_id: &str) -> DomainResult<ClassifyNoteResponse> {
         // Get the note
         let note = self
@@ -206,100 +283,28 @@ impl TopicUseCases for TopicUseCasesImpl {
             .await?
             .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
 
-        // Check if already has embedding - if so, skip unless force is enabled
-        let existing_embedding = self.embedding_service.get_embedding(note_id).await?;
-        if existing_embedding.is_some() {
+        let embedded = self.embedding_service.get_embedding(note_id).await?.is_some();
+        if !embedded && !self.embed_note(&note).await? {
             return Ok(ClassifyNoteResponse {
                 note_id: note_id.to_string(),
                 topics: Vec::new(),
             });
         }
 
-        // Get note content
-        if note.file_path.is_none() || note.workspace_id.is_none() {
-            return Ok(ClassifyNoteResponse {
-                note_id: note_id.to_string(),
-                topics: Vec::new(),
-            });
-        }
-
-        let workspace = self
-            .workspace_repository
-            .find_by_id(note.workspace_id.as_ref().unwrap())
-            .await?
-            .ok_or_else(|| {
-                DomainError::WorkspaceNotFound(
-                    note.workspace_id.as_ref().unwrap().clone()
-                )
-            })?;
-
-        let absolute_path = Path::new(&workspace.folder_path).join(note.file_path.as_ref().unwrap());
-        let markdown = self
-            .file_storage
-            .read(absolute_path.to_str().unwrap())
+        // Scores against topic centroids and persists automatic assignments
+        let matched_topics: Vec<TopicClassification> = self
+            .embedding_service
+            .classify_note(note_id)
             .await?
-            .unwrap_or_default();
-
-        if markdown.is_empty() {
-            return Ok(ClassifyNoteResponse {
-                note_id: note_id.to_string(),
-                topics: Vec::new(),
-            });
-        }
-
-        // Convert to plain text and generate embedding
-        let plain_text = self.markdown_processor.extract_plain_text(&markdown)?;
-        let embedding = self.embedding_service.generate_embedding(&plain_text).await?;
-
-        // Save embedding
-        self.embedding_service
-            .store_embedding(note_id, embedding.clone())
-            .await?;
-
-        // Find all matching topics above threshold
-        let topics = self.topic_repository.find_all().await?;
-        let mut matched_topics: Vec<TopicClassification> = Vec::new();
-
-        for topic in topics {
-            if let Some(centroid_bytes) = topic.centroid {
-                // Convert Vec<u8> to Vec<f32> - reinterpret bytes as f32 values
-                if centroid_bytes.len() % 4 != 0 {
-                    continue; // Skip malformed centroids
-                }
-                let centroid_f32: Vec<f32> = centroid_bytes
-                    .chunks_exact(4)
-                    .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
-                    .collect();
-
-                let similarity = similarity_calculator::cosine_similarity(&embedding, &centroid_f32)
-                    .map_err(|e| DomainError::ValidationError(e))?;
-
-                if similarity > 0.5 {
-                    matched_topics.push(TopicClassification {
-                        topic_id: topic.id,
-                        topic_name: topic.name,
-                        confidence: similarity,
-                    });
-                }
-            }
-        }
-
-        // Sort by confidence descending
-        matched_topics.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
+            .into_iter()
+            .map(|result| TopicClassification {
+                topic_id: result.topic_id,
+                topic_name: result.topic_name,
+                confidence: result.confidence,
+            })
+            .collect();
 
-        // Assign the best matching topic if any
         if let Some(best_topic) = matched_topics.first() {
-            self.topic_repository
-                .assign_to_note(
-                    note_id,
-                    &best_topic.topic_id,
-                    Some(TopicAssignmentOptions {
-                        confidence: Some(best_topic.confidence),
-                        is_manual: Some(false),
-                    }),
-                )
-                .await?;
-
             // Emit event (SYNC - no .await)
             if let Some(ref publisher) = self.event_publisher {
                 publisher.emit(
@@ -327,9 +332,13 @@ impl TopicUseCases for TopicUseCasesImpl {
                 processed: 0,
                 total: 0,
                 failed: 0,
+                results: Vec::new(),
             });
         }
 
+        // Pick up manual assignments made since the last run
+        self.embedding_service.recompute_centroids().await?;
+
         let workspace = active_workspace.unwrap();
         let notes = self
             .note_repository
@@ -343,11 +352,15 @@ impl TopicUseCases for TopicUseCasesImpl {
         let total = notes.len() as i32;
         let mut processed = 0;
         let mut failed = 0;
+        let mut results = Vec::new();
 
         for note in notes {
             match self.classify_note(&note.id).await {
-                Ok(_) => {
+                Ok(response) => {
                     processed += 1;
+                    if !response.topics.is_empty() {
+                        results.push(response);
+                    }
 
                     // Emit progress event (SYNC - no .await)
                     if let Some(ref publisher) = self.event_publisher {
@@ -379,9 +392,233 @@ impl TopicUseCases for TopicUseCasesImpl {
             processed,
             total,
             failed,
+            results,
+        })
+    }
+
+    /// Propose new topics from clusters of similar notes
+    ///
+    /// Clusters that match an existing topic are left out.
+    async fn discover_topics(
+        &self,
+        request: DiscoverTopicsRequest,
+    ) -> DomainResult<DiscoverTopicsResponse> {
+        let workspace = match request.workspace_id {
+            Some(id) => Some(
+                self.workspace_repository
+                    .find_by_id(&id)
+                    .await?
+                    .ok_or(DomainError::WorkspaceNotFound(id))?,
+            ),
+            None => self.workspace_repository.find_active().await?,
+        };
+        let Some(workspace) = workspace else {
+            return Ok(DiscoverTopicsResponse {
+                candidates: Vec::new(),
+                notes_considered: 0,
+            });
+        };
+
+        let workspace_notes: Vec<Note> = self
+            .note_repository
+            .find_all(NoteFindOptions {
+                workspace_id: Some(workspace.id),
+                is_deleted: Some(false),
+                ..Default::default()
+            })
+            .await?;
+        let note_ids: Vec<String> = workspace_notes.iter().map(|note| note.id.clone()).collect();
+        let mut by_note = self.embedding_service.get_embeddings(&note_ids).await?;
+
+        let mut notes: Vec<Note> = Vec::with_capacity(by_note.len());
+        let mut embeddings: Vec<Vec<f32>> = Vec::with_capacity(by_note.len());
+        for note in workspace_notes {
+            if let Some(embedding) = by_note.remove(&note.id) {
+                notes.push(note);
+                embeddings.push(embedding);
+            }
+        }
+
+        let defaults = DiscoveryConfig::default();
+        let discovery = TopicDiscovery::new(DiscoveryConfig {
+            max_clusters: request.max_topics.unwrap_or(defaults.max_clusters),
+            min_cluster_size: request.min_notes.unwrap_or(defaults.min_cluster_size),
+            ..defaults
+        });
+        let existing: Vec<Vec<f32>> = self
+            .topic_repository
+            .find_all()
+            .await?
+            .iter()
+            .filter_map(Topic::centroid_vector)
+            .collect();
+
+        let clusters: Vec<_> = discovery
+            .cluster(&embeddings)
+            .into_iter()
+            .filter(|cluster| {
+                !existing.iter().any(|centroid| {
+                    cosine_similarity(&cluster.centroid, centroid)
+                        .is_ok_and(|similarity| similarity >= EXISTING_TOPIC_SIMILARITY)
+                })
+            })
+            .collect();
+
+        // Terms are weighed against every clustered note, not just the members
+        let mut documents = Vec::with_capacity(notes.len());
+        if !clusters.is_empty() {
+            for note in &notes {
+                documents.push(self.note_text(note).await);
+            }
+        }
+        let labels = discovery.label(&documents, &clusters);
+
+        let mut candidates = Vec::new();
+        for (cluster, terms) in clusters.into_iter().zip(labels) {
+            let name = TopicDiscovery::name_for(&terms)
+                .unwrap_or_else(|| notes[cluster.members[0]].title.clone());
+            let mut topic = Topic::new(name)?;
+            topic.update_description(Some(format!(
+                "Discovered from {} similar notes",
+                cluster.members.len()
+            )));
+            topic.update_centroid(Topic::encode_centroid(&cluster.centroid));
+
+            let mut members: Vec<TopicCandidateMember> = cluster
+                .members
+                .iter()
+                .zip(&cluster.similarities)
+                .map(|(index, similarity)| TopicCandidateMember {
+                    note_id: notes[*index].id.clone(),
+                    title: notes[*index].title.clone(),
+                    similarity: *similarity,
+                })
+                .collect();
+            members.sort_by(|a, b| {
+                b.similarity
+                    .partial_cmp(&a.similarity)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+            candidates.push(TopicCandidate {
+                topic,
+                terms,
+                members,
+                cohesion: cluster.cohesion,
+            });
+        }
+
+        tracing::info!(
+            "[TopicUseCases] Discovered {} topic candidates from {} notes",
+            candidates.len(),
+            notes.len()
+        );
+
+        Ok(DiscoverTopicsResponse {
+            candidates,
+            notes_considered: notes.len() as i32,
         })
     }
 
+    /// Create a discovered topic and assign its notes
+    ///
+    /// The centroid is computed from the accepted notes, which count as
+    /// manual assignments since the user confirmed them.
+    async fn accept_topic_candidate(
+        &self,
+        request: AcceptTopicCandidateRequest,
+    ) -> DomainResult<Topic> {
+        let mut topic = Topic::new(&request.name)?;
+        if self
+            .topic_repository
+            .find_by_name(&topic.name)
+            .await?
+            .is_some()
+        {
+            return Err(DomainError::ValidationError(format!(
+                "Topic already exists: {}",
+                topic.name
+            )));
+        }
+
+        if let Some(description) = request.description {
+            topic.update_description(Some(description));
+        }
+
+        if let Some(color) = request.color {
+            topic.change_color(color)?;
+        }
+
+        let mut notes = Vec::with_capacity(request.note_ids.len());
+        for note_id in &request.note_ids {
+            let note = self
+                .note_repository
+                .find_by_id(note_id)
+                .await?
+                .ok_or_else(|| DomainError::NoteNotFound(note_id.clone()))?;
+            notes.push(note);
+        }
+
+        let embeddings = self
+            .embedding_service
+            .get_embeddings(&request.note_ids)
+            .await?;
+        let members: Vec<CentroidMember> = notes
+            .iter()
+            .filter_map(|note| embeddings.get(&note.id).cloned())
+            .map(|embedding| CentroidMember {
+                embedding,
+                is_manual: true,
+                confidence: 1.0,
+            })
+            .collect();
+        let centroid = TopicClassifier::default().centroid(&members);
+        if let Some(ref centroid) = centroid {
+            topic.update_centroid(Topic::encode_centroid(centroid));
+        }
+
+        self.topic_repository.save(&topic).await?;
+
+        for note in &notes {
+            let confidence = match (embeddings.get(&note.id), &centroid) {
+                (Some(embedding), Some(centroid)) => {
+                    cosine_similarity(embedding, centroid).unwrap_or(1.0)
+                }
+                _ => 1.0,
+            };
+            self.topic_repository
+                .assign_to_note(
+                    &note.id,
+                    &topic.id,
+                    Some(TopicAssignmentOptions {
+                        confidence: Some(confidence),
+                        is_manual: Some(true),
+                    }),
+                )
+                .await?;
+        }
+        self.topic_repository.update_note_count(&topic.id).await?;
+        topic.set_note_count(notes.len() as i32);
+
+        // Emit event (SYNC - no .await)
+        if let Some(ref publisher) = self.event_publisher {
+            publisher.emit(
+                "topic:created",
+                serde_json::json!({
+                    "id": topic.id,
+                    "name": topic.name,
+                }),
+            );
+        }
+
+        tracing::info!(
+            "[TopicUseCases] Accepted topic {} with {} notes",
+            topic.name,
+            notes.len()
+        );
+        Ok(topic)
+    }
+
     /// Manually assign a topic to a note
     async fn assign_topic_to_note(&self, note_id: &str, topic_id: &str) -> DomainResult<()> {
         self.topic_repository
@@ -441,16 +678,20 @@ impl TopicUseCases for TopicUseCasesImpl {
         limit: Option<i32>,
     ) -> DomainResult<Vec<SimilarNoteResult>> {
         // Verify note exists
-        let _note = self
+        let note = self
             .note_repository
             .find_by_id(note_id)
             .await?
             .ok_or_else(|| DomainError::NoteNotFound(note_id.to_string()))?;
 
-        // Use EmbeddingService to find similar notes
+        // Use EmbeddingService to find similar notes in the same workspace
+        let filter = EmbeddingSearchFilter {
+            workspace_id: note.workspace_id,
+            notebook_id: None,
+        };
         let similar_notes = self
             .embedding_service
-            .find_similar_notes(note_id, limit)
+            .find_similar_notes(note_id, Some(filter), limit)
             .await?;
 
         // Convert to SimilarNoteResult
@@ -462,6 +703,7 @@ impl TopicUseCases for TopicUseCasesImpl {
                 note_id: sn.note_id,
                 title: sn.title,
                 distance: 1.0 - sn.similarity, // Convert similarity to distance
+                passage: sn.passage,
             })
             .collect();
 
@@ -475,16 +717,20 @@ impl TopicUseCases for TopicUseCasesImpl {
         limit: Option<i32>,
     ) -> DomainResult<Vec<SimilarNoteResult>> {
         // Check for active workspace
-        let _workspace = self
+        let workspace = self
             .workspace_repository
             .find_active()
             .await?
             .ok_or_else(|| DomainError::ValidationError("No active workspace".to_string()))?;
 
-        // Use EmbeddingService to perform semantic search
+        // Use EmbeddingService to perform semantic search within it
+        let filter = EmbeddingSearchFilter {
+            workspace_id: Some(workspace.id),
+            notebook_id: None,
+        };
         let results = self
             .embedding_service
-            .semantic_search(query, limit)
+            .semantic_search(query, Some(filter), limit)
             .await?;
 
         // Convert to SimilarNoteResult
@@ -494,6 +740,7 @@ impl TopicUseCases for TopicUseCasesImpl {
                 note_id: sn.note_id,
                 title: sn.title,
                 distance: 1.0 - sn.similarity, // Convert similarity to distance
+                passage: sn.passage,
             })
             .collect();
 
@@ -502,35 +749,7 @@ impl TopicUseCases for TopicUseCasesImpl {
 
     /// Recompute centroids for all topics
     async fn recompute_centroids(&self) -> DomainResult<()> {
-        let topics = self.topic_repository.find_all().await?;
-
-        for topic in topics {
-            let notes_for_topic = self
-                .topic_repository
-                .get_notes_for_topic(&topic.id, None)
-                .await?;
-
-            let note_ids: Vec<String> = notes_for_topic.iter().map(|n| n.note_id.clone()).collect();
-            let mut embeddings: Vec<Vec<f32>> = Vec::new();
-
-            for note_id in note_ids {
-                if let Some(embedding) = self.embedding_service.get_embedding(&note_id).await? {
-                    embeddings.push(embedding);
-                }
-            }
-
-            if !embeddings.is_empty() {
-                let centroid = Self::calculate_centroid(&embeddings);
-                // Convert Vec<f32> to Vec<u8> - convert each f32 to 4 bytes
-                let centroid_bytes: Vec<u8> = centroid
-                    .iter()
-                    .flat_map(|&f| f.to_le_bytes())
-                    .collect();
-                self.topic_repository
-                    .update_centroid(&topic.id, centroid_bytes)
-                    .await?;
-            }
-        }
+        self.embedding_service.recompute_centroids().await?;
 
         tracing::info!("[TopicUseCases] Recomputed all topic centroids");
         Ok(())
@@ -547,6 +766,7 @@ impl TopicUseCases for TopicUseCasesImpl {
                 total_notes: 0,
                 embedded_notes: 0,
                 pending_notes: 0,
+                queue: self.embedding_queue.status(),
             });
         }
 
@@ -575,9 +795,72 @@ impl TopicUseCases for TopicUseCasesImpl {
             total_notes,
             embedded_notes,
             pending_notes,
+            queue: self.embedding_queue.status(),
         })
     }
 
+    /// Embed notes that are un-embedded or out of date in the background
+    async fn start_embedding_queue(
+        &self,
+        request: StartEmbeddingQueueRequest,
+    ) -> DomainResult<EmbeddingQueueStatus> {
+        let options = EmbeddingQueueOptions::new(request.batch_size, request.max_cpu_percent)?;
+        self.embedding_queue.start(options)
+    }
+
+    /// Pause the embedding queue after the batch in progress
+    async fn pause_embedding_queue(&self) -> DomainResult<EmbeddingQueueStatus> {
+        Ok(self.embedding_queue.pause())
+    }
+
+    /// Resume a paused embedding queue
+    async fn resume_embedding_queue(&self) -> DomainResult<EmbeddingQueueStatus> {
+        Ok(self.embedding_queue.resume())
+    }
+
+    /// Cancel the embedding queue after the batch in progress
+    async fn cancel_embedding_queue(&self) -> DomainResult<EmbeddingQueueStatus> {
+        Ok(self.embedding_queue.cancel())
+    }
+
+    /// Get the embedding provider and model in use
+    async fn get_embedding_settings(&self) -> DomainResult<EmbeddingSettingsResponse> {
+        let provider = match self.settings_repository.get(EMBEDDING_PROVIDER_SETTING).await? {
+            Some(setting) => EmbeddingProviderConfig::from_setting(&setting.value)?,
+            None => EmbeddingProviderConfig::default(),
+        };
+        let stale_notes = self.embedding_service.find_stale_note_ids().await?.len() as i32;
+
+        Ok(EmbeddingSettingsResponse {
+            provider,
+            model: self.embedding_service.model(),
+            ready: self.embedding_service.is_ready(),
+            available_models: self.embedding_service.available_models(),
+            stale_notes,
+        })
+    }
+
+    /// Switch embedding provider and queue notes embedded with another model
+    async fn update_embedding_settings(
+        &self,
+        provider: EmbeddingProviderConfig,
+    ) -> DomainResult<EmbeddingSettingsResponse> {
+        // Configure first so a provider that can't be built or reached is never saved
+        let model = self.embedding_service.configure(provider.clone()).await?;
+        self.settings_repository
+            .set(EMBEDDING_PROVIDER_SETTING, &provider.to_setting()?)
+            .await?;
+        tracing::info!(
+            "[TopicUseCases] Embedding provider set to {} ({})",
+            model.name,
+            model.version
+        );
+
+        self.queue_stale_notes().await?;
+
+        self.get_embedding_settings().await
+    }
+
     /// Get notes for a topic
     async fn get_notes_for_topic(
         &self,
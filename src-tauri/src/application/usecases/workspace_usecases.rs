@@ -7,23 +7,25 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
+use crate::application::services::{LinkRefactorService, NoteFilenameService, NoteMetadataService};
 use crate::domain::{
     entities::{Note, Workspace},
     errors::{DomainError, DomainResult},
     ports::{
         inbound::{
             CreateFolderRequest, CreateFolderResponse, CreateWorkspaceRequest,
-            FileSystemEntryType, MoveFolderRequest, MoveFolderResponse, RenameFolderRequest,
+            FileSystemEntryType, FilenameSettings, MoveFolderRequest, MoveFolderResponse, RenameFolderRequest,
             RenameFolderResponse, ScanWorkspaceFolderStructure, ScanWorkspaceFileEntry,
             ScanWorkspaceResponse, SelectFolderRequest, SelectFolderResponse,
             SyncWorkspaceResponse, SyncWorkspaceStats, UpdateWorkspaceRequest,
             ValidatePathResponse, WorkspaceUseCases,
         },
         outbound::{
-            EventPublisher, FileStorage, FolderPickerOptions, MarkdownProcessor, NoteRepository,
-            NoteFindOptions, SystemService, WorkspaceRepository,
+            DomainEvent, EventPublisher, FileStorage, FolderPickerOptions, MarkdownProcessor,
+            NoteRepository, NoteFindOptions, SystemService, WorkspaceRepository,
         },
     },
+    services::NoteFrontmatter,
 };
 
 /// Implementation of all Workspace use cases
@@ -33,16 +35,23 @@ pub struct WorkspaceUseCasesImpl {
     file_storage: Arc<dyn FileStorage>,
     system_service: Arc<dyn SystemService>,
     markdown_processor: Arc<dyn MarkdownProcessor>,
+    link_refactor: Arc<LinkRefactorService>,
+    note_filenames: Arc<NoteFilenameService>,
+    note_metadata: Arc<NoteMetadataService>,
     event_publisher: Option<Arc<dyn EventPublisher>>,
 }
 
 impl WorkspaceUseCasesImpl {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         workspace_repository: Arc<dyn WorkspaceRepository>,
         note_repository: Arc<dyn NoteRepository>,
         file_storage: Arc<dyn FileStorage>,
         system_service: Arc<dyn SystemService>,
         markdown_processor: Arc<dyn MarkdownProcessor>,
+        link_refactor: Arc<LinkRefactorService>,
+        note_filenames: Arc<NoteFilenameService>,
+        note_metadata: Arc<NoteMetadataService>,
         event_publisher: Option<Arc<dyn EventPublisher>>,
     ) -> Self {
         Self {
@@ -51,10 +60,22 @@ impl WorkspaceUseCasesImpl {
             file_storage,
             system_service,
             markdown_processor,
+            link_refactor,
+            note_filenames,
+            note_metadata,
             event_publisher,
         }
     }
 
+    /// The given workspace, or the active one
+    async fn resolve_workspace(&self, workspace_id: Option<&str>) -> DomainResult<Workspace> {
+        match workspace_id {
+            Some(id) => self.workspace_repository.find_by_id(id).await?,
+            None => self.workspace_repository.find_active().await?,
+        }
+        .ok_or_else(|| DomainError::ValidationError("No active workspace".to_string()))
+    }
+
     /// Build folder structure recursively for scan operation
     fn build_folder_structure<'a>(
         &'a self,
@@ -456,12 +477,31 @@ impl WorkspaceUseCases for WorkspaceUseCasesImpl {
 
                 let mut note = Note::new(&title, Some(workspace.id.clone()))?;
                 note.set_file_path(Some(relative_path.clone()))?;
+                let frontmatter = file_content
+                    .as_deref()
+                    .map(NoteFrontmatter::from_markdown)
+                    .unwrap_or_default();
+                frontmatter.apply_to(&mut note);
 
                 self.note_repository.save(&note).await?;
+                self.note_metadata
+                    .sync_tags(
+                        &note.id,
+                        &frontmatter,
+                        file_content.as_deref().unwrap_or_default(),
+                    )
+                    .await?;
 
                 // Publish event
                 if let Some(ref publisher) = self.event_publisher {
-                    publisher.emit("note:created", serde_json::json!({"id": note.id}));
+                    publisher.publish(DomainEvent::NoteCreated {
+                        timestamp: chrono::Utc::now(),
+                        id: note.id.clone(),
+                        title: note.title.clone(),
+                        workspace_id: note.workspace_id.clone(),
+                        notebook_id: note.notebook_id.clone(),
+                        file_path: note.file_path.clone(),
+                    });
                 }
 
                 created += 1;
@@ -473,13 +513,16 @@ impl WorkspaceUseCases for WorkspaceUseCasesImpl {
 
                     // Re-extract title in case it changed
                     let file_content = self.file_storage.read(&path_str).await?;
-                    if let Some(content) = file_content {
+                    let mut frontmatter = NoteFrontmatter::default();
+                    if let Some(ref content) = file_content {
                         // ❌ SYNC - NO AWAIT!
-                        if let Some(new_title) = self.markdown_processor.extract_title(&content)? {
+                        if let Some(new_title) = self.markdown_processor.extract_title(content)? {
                             if new_title != existing.title {
                                 note_entity.update_title(new_title)?;
                             }
                         }
+                        frontmatter = NoteFrontmatter::from_markdown(content);
+                        frontmatter.apply_to(&mut note_entity);
                     }
 
                     // Force update timestamp by re-setting file path
@@ -488,10 +531,22 @@ impl WorkspaceUseCases for WorkspaceUseCasesImpl {
                     }
 
                     self.note_repository.save(&note_entity).await?;
+                    self.note_metadata
+                        .sync_tags(
+                            &note_entity.id,
+                            &frontmatter,
+                            file_content.as_deref().unwrap_or_default(),
+                        )
+                        .await?;
 
                     // Publish event
                     if let Some(ref publisher) = self.event_publisher {
-                        publisher.emit("note:updated", serde_json::json!({"id": existing.id}));
+                        publisher.publish(DomainEvent::NoteUpdated {
+                            timestamp: chrono::Utc::now(),
+                            id: existing.id.clone(),
+                            title: note_entity.title.clone(),
+                            changes: vec!["content".to_string()],
+                        });
                     }
 
                     updated += 1;
@@ -510,7 +565,12 @@ impl WorkspaceUseCases for WorkspaceUseCasesImpl {
 
                     // Publish event
                     if let Some(ref publisher) = self.event_publisher {
-                        publisher.emit("note:deleted", serde_json::json!({"id": note_entity.id}));
+                        publisher.publish(DomainEvent::NoteDeleted {
+                            timestamp: chrono::Utc::now(),
+                            id: note_entity.id.clone(),
+                            title: note_entity.title.clone(),
+                            permanent: false,
+                        });
                     }
 
                     deleted += 1;
@@ -606,9 +666,14 @@ impl WorkspaceUseCases for WorkspaceUseCasesImpl {
             .to_string_lossy()
             .to_string();
 
-        self.file_storage
-            .rename(&absolute_path, &new_absolute_path)
-            .await?;
+        let rewritten_files = self
+            .link_refactor
+            .move_path(&active_workspace.folder_path, &absolute_path, &new_absolute_path)
+            .await?
+            .rewrites
+            .into_iter()
+            .map(|rewrite| rewrite.file_path)
+            .collect();
 
         let new_relative_path = Path::new(&new_absolute_path)
             .strip_prefix(&active_workspace.folder_path)
@@ -618,6 +683,7 @@ impl WorkspaceUseCases for WorkspaceUseCasesImpl {
         Ok(RenameFolderResponse {
             old_path: request.path,
             new_path: new_relative_path,
+            rewritten_files,
         })
     }
 
@@ -707,9 +773,18 @@ impl WorkspaceUseCases for WorkspaceUseCasesImpl {
             ));
         }
 
-        self.file_storage
-            .rename(&source_absolute_path, &dest_absolute_path)
-            .await?;
+        let rewritten_files = self
+            .link_refactor
+            .move_path(
+                &active_workspace.folder_path,
+                &source_absolute_path,
+                &dest_absolute_path,
+            )
+            .await?
+            .rewrites
+            .into_iter()
+            .map(|rewrite| rewrite.file_path)
+            .collect();
 
         let new_relative_path = Path::new(&dest_absolute_path)
             .strip_prefix(&active_workspace.folder_path)
@@ -719,6 +794,32 @@ impl WorkspaceUseCases for WorkspaceUseCasesImpl {
         Ok(MoveFolderResponse {
             old_path: request.source_path,
             new_path: new_relative_path,
+            rewritten_files,
         })
     }
+
+    /// Get how a workspace names note files
+    async fn get_filename_settings(
+        &self,
+        workspace_id: Option<String>,
+    ) -> DomainResult<FilenameSettings> {
+        let workspace = self.resolve_workspace(workspace_id.as_deref()).await?;
+        self.note_filenames.settings(&workspace.id).await
+    }
+
+    /// Update how a workspace names note files
+    ///
+    /// Existing files keep their names; only new notes and later title
+    /// changes follow the new settings.
+    async fn update_filename_settings(
+        &self,
+        workspace_id: Option<String>,
+        settings: FilenameSettings,
+    ) -> DomainResult<FilenameSettings> {
+        let workspace = self.resolve_workspace(workspace_id.as_deref()).await?;
+        self.note_filenames
+            .save_settings(&workspace.id, &settings)
+            .await?;
+        Ok(settings)
+    }
 }
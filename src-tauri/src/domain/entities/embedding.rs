@@ -0,0 +1,125 @@
+/// Embedding Domain Entity
+///
+/// Represents a stored embedding vector of a note, or of one of its passages,
+/// together with the model that produced it and a hash of the embedded text.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::errors::{DomainError, DomainResult};
+
+use super::NoteChunk;
+
+/// Embedding entity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Embedding {
+    pub note_id: String,
+    /// Passage the vector belongs to; None for the note as a whole
+    pub chunk_index: Option<i32>,
+    pub model_name: String,
+    pub model_version: String,
+    /// Hash of the embedded text, to tell whether it needs re-embedding
+    pub content_hash: Option<String>,
+    #[serde(skip_serializing, default)]
+    pub vector: Vec<f32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Embedding {
+    /// Create a note-level embedding with validation
+    pub fn for_note(
+        note_id: impl Into<String>,
+        vector: Vec<f32>,
+        model_name: impl Into<String>,
+        model_version: impl Into<String>,
+    ) -> DomainResult<Self> {
+        let note_id = note_id.into();
+
+        if note_id.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Note ID is required".to_string(),
+            ));
+        }
+
+        if vector.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Embedding cannot be empty".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            note_id,
+            chunk_index: None,
+            model_name: model_name.into(),
+            model_version: model_version.into(),
+            content_hash: None,
+            vector,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Create the embedding of a passage from its embedded chunk
+    pub fn for_chunk(
+        chunk: &NoteChunk,
+        model_name: impl Into<String>,
+        model_version: impl Into<String>,
+    ) -> DomainResult<Self> {
+        let vector = chunk.embedding.clone().ok_or_else(|| {
+            DomainError::ValidationError(format!("Chunk {} has no embedding", chunk.key()))
+        })?;
+
+        let mut embedding = Self::for_note(&chunk.note_id, vector, model_name, model_version)?;
+        embedding.chunk_index = Some(chunk.chunk_index);
+        embedding.content_hash = Some(Self::hash_content(&chunk.content));
+        Ok(embedding)
+    }
+
+    /// Record the hash of the embedded text
+    pub fn with_content_hash(mut self, content_hash: impl Into<String>) -> Self {
+        self.content_hash = Some(content_hash.into());
+        self
+    }
+
+    /// Key of the embedded item: the note ID or its chunk key
+    pub fn key(&self) -> String {
+        match self.chunk_index {
+            Some(index) => NoteChunk::make_key(&self.note_id, index),
+            None => self.note_id.clone(),
+        }
+    }
+
+    pub fn is_note_level(&self) -> bool {
+        self.chunk_index.is_none()
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.vector.len()
+    }
+
+    /// Whether this vector was produced by the given model
+    pub fn is_from_model(&self, model_name: &str, model_version: &str) -> bool {
+        self.model_name == model_name && self.model_version == model_version
+    }
+
+    /// Stable hash of embedded text (FNV-1a, hex encoded)
+    pub fn hash_content(text: &str) -> String {
+        let hash = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        format!("{:016x}", hash)
+    }
+
+    /// Hash of all passages of a note, recorded with its note-level embedding
+    ///
+    /// Any edit to an embedded passage changes it, so comparing it with the
+    /// hash of the note's current passages tells whether the note is stale.
+    pub fn hash_passages(chunks: &[NoteChunk]) -> String {
+        let content = chunks
+            .iter()
+            .map(|chunk| chunk.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        Self::hash_content(&content)
+    }
+}
+
@@ -1,5 +1,8 @@
 pub mod attachment;
+pub mod embedding;
 pub mod note;
+pub mod note_block;
+pub mod note_chunk;
 pub mod note_link;
 pub mod notebook;
 pub mod tag;
@@ -8,10 +11,13 @@ pub mod version;
 pub mod workspace;
 
 pub use attachment::Attachment;
+pub use embedding::Embedding;
 pub use note::Note;
+pub use note_block::NoteBlock;
+pub use note_chunk::NoteChunk;
 pub use note_link::{LinkCount, NoteLink};
 pub use notebook::Notebook;
-pub use tag::Tag;
+pub use tag::{NoteTag, Tag, TagSource};
 pub use topic::{Topic, TopicSummary};
 pub use version::{Version, VersionSummary};
 pub use workspace::Workspace;
@@ -21,9 +21,14 @@ pub struct Note {
     pub is_archived: bool,
     pub is_deleted: bool,
     pub deleted_at: Option<DateTime<Utc>>,
-    pub embedding: Option<Vec<f32>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Other names the note answers to in links, from its frontmatter
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// Custom frontmatter keys and their values
+    #[serde(default)]
+    pub properties: serde_json::Map<String, serde_json::Value>,
 }
 
 impl Note {
@@ -58,9 +63,10 @@ impl Note {
             is_archived: false,
             is_deleted: false,
             deleted_at: None,
-            embedding: None,
             created_at: now,
             updated_at: now,
+            aliases: Vec::new(),
+            properties: serde_json::Map::new(),
         })
     }
 
@@ -135,6 +141,24 @@ impl Note {
         self.updated_at = Utc::now();
     }
 
+    /// Replace the note's aliases
+    pub fn set_aliases(&mut self, aliases: Vec<String>) {
+        self.aliases = aliases;
+        self.updated_at = Utc::now();
+    }
+
+    /// Replace the note's custom properties
+    pub fn set_properties(&mut self, properties: serde_json::Map<String, serde_json::Value>) {
+        self.properties = properties;
+        self.updated_at = Utc::now();
+    }
+
+    /// Backdate the note, as its frontmatter's `created` says
+    pub fn set_created_at(&mut self, created_at: DateTime<Utc>) {
+        self.created_at = created_at;
+        self.updated_at = Utc::now();
+    }
+
     /// Move note to a notebook
     pub fn move_to_notebook(&mut self, notebook_id: Option<String>) {
         self.notebook_id = notebook_id;
@@ -0,0 +1,52 @@
+/// NoteBlock Domain Entity
+///
+/// Represents a block of a note marked with `^block-id`, the target of
+/// [[Note#^block-id]] links. Located by byte range.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::services::MarkedBlock;
+
+/// NoteBlock entity representing one marked block of a note
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteBlock {
+    pub note_id: String,
+    pub block_id: String,
+    /// Text of the block without its marker
+    pub content: String,
+    pub start_offset: i32, // Byte offset in the note's markdown
+    pub end_offset: i32,   // Byte offset in the note's markdown (exclusive)
+    pub line_number: i32,  // 1-based
+    pub created_at: DateTime<Utc>,
+}
+
+impl NoteBlock {
+    /// Create a block record from a marked block of the note's markdown
+    pub fn new(note_id: impl Into<String>, block: &MarkedBlock) -> DomainResult<Self> {
+        let note_id = note_id.into();
+
+        if note_id.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Note ID is required".to_string(),
+            ));
+        }
+
+        if block.block_id.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Block ID is required".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            note_id,
+            block_id: block.block_id.clone(),
+            content: block.text.clone(),
+            start_offset: block.start as i32,
+            end_offset: block.end as i32,
+            line_number: block.line_number as i32,
+            created_at: Utc::now(),
+        })
+    }
+}
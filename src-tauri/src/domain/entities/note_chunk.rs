@@ -0,0 +1,92 @@
+/// NoteChunk Domain Entity
+///
+/// Represents an embedded passage of a note, located by byte range.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::services::TextChunk;
+
+/// Separator between note ID and chunk index in chunk keys
+const KEY_SEPARATOR: char = '#';
+
+/// NoteChunk entity representing one passage of a note and its embedding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteChunk {
+    pub note_id: String,
+    pub chunk_index: i32,
+    /// Headings enclosing the passage, outermost first
+    pub heading_path: Vec<String>,
+    pub content: String,
+    pub start_offset: i32, // Byte offset in the note's markdown
+    pub end_offset: i32,   // Byte offset in the note's markdown (exclusive)
+    #[serde(skip_serializing)]
+    pub embedding: Option<Vec<f32>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NoteChunk {
+    /// Create a chunk record from a chunker passage
+    pub fn new(note_id: impl Into<String>, chunk: &TextChunk) -> DomainResult<Self> {
+        let note_id = note_id.into();
+
+        if note_id.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Note ID is required".to_string(),
+            ));
+        }
+
+        if chunk.end < chunk.start {
+            return Err(DomainError::ValidationError(
+                "Chunk end must not precede its start".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            note_id,
+            chunk_index: chunk.index as i32,
+            heading_path: chunk.heading_path.clone(),
+            content: chunk.text.clone(),
+            start_offset: chunk.start as i32,
+            end_offset: chunk.end as i32,
+            embedding: None,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Attach an embedding
+    pub fn with_embedding(mut self, embedding: Vec<f32>) -> Self {
+        self.embedding = Some(embedding);
+        self
+    }
+
+    /// Unique key for this chunk, e.g. in a vector index
+    pub fn key(&self) -> String {
+        Self::make_key(&self.note_id, self.chunk_index)
+    }
+
+    pub fn make_key(note_id: &str, chunk_index: i32) -> String {
+        format!("{}{}{}", note_id, KEY_SEPARATOR, chunk_index)
+    }
+
+    /// Split a key into note ID and chunk index; plain note IDs have no index
+    pub fn parse_key(key: &str) -> (&str, Option<i32>) {
+        match key.rsplit_once(KEY_SEPARATOR) {
+            Some((note_id, index)) => match index.parse() {
+                Ok(index) => (note_id, Some(index)),
+                Err(_) => (key, None),
+            },
+            None => (key, None),
+        }
+    }
+
+    /// Text sent to the embedding model: heading context, then the passage
+    pub fn embedding_input(&self, plain_text: &str) -> String {
+        if self.heading_path.is_empty() {
+            plain_text.to_string()
+        } else {
+            format!("{}\n\n{}", self.heading_path.join(" > "), plain_text)
+        }
+    }
+}
@@ -1,6 +1,7 @@
 /// NoteLink Domain Entity
 ///
-/// Represents a wiki-style link between two notes.
+/// Represents a wiki-style link between two notes, optionally into a heading
+/// or block of the target.
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +12,9 @@ use crate::domain::errors::{DomainError, DomainResult};
 pub struct NoteLink {
     pub source_note_id: String,
     pub target_note_id: String,
+    /// Heading or `^block-id` the link points at, as written after `#`
+    #[serde(default)]
+    pub anchor: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -40,10 +44,17 @@ impl NoteLink {
         Ok(Self {
             source_note_id: source,
             target_note_id: target,
+            anchor: None,
             created_at: Utc::now(),
         })
     }
 
+    /// Point the link at a heading or block of the target
+    pub fn with_anchor(mut self, anchor: Option<String>) -> Self {
+        self.anchor = anchor.filter(|a| !a.trim().is_empty());
+        self
+    }
+
     /// Check if this is a self-link
     pub fn is_self_link(&self) -> bool {
         self.source_note_id == self.target_note_id
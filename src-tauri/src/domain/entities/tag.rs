@@ -1,11 +1,40 @@
 /// Tag Domain Entity
 ///
-/// Pure domain object representing a tag with its business rules.
+/// Pure domain object representing a tag with its business rules. Tags nest by
+/// name: `project/alpha` is a child of `project`.
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::domain::errors::{DomainError, DomainResult};
 
+/// How a tag came to be on a note
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagSource {
+    /// Added from the app or listed in the note's frontmatter
+    #[default]
+    Manual,
+    /// Written as a `#hashtag` in the note's content
+    Content,
+}
+
+impl TagSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Content => "content",
+        }
+    }
+
+    /// Parse a stored source, falling back to manual
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "content" => Self::Content,
+            _ => Self::Manual,
+        }
+    }
+}
+
 /// Tag entity
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -35,16 +64,21 @@ impl Tag {
     }
 
     /// Normalize tag name (lowercase, trim, remove special chars, replace spaces with hyphens)
+    ///
+    /// `/` separates nested tags, as in `area/sub-tag`; empty segments are dropped.
     pub fn normalize_name(name: &str) -> DomainResult<String> {
         let normalized = name
             .to_lowercase()
-            .trim()
             .chars()
-            .filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '-' || *c == '_')
+            .filter(|c| {
+                c.is_alphanumeric() || c.is_whitespace() || *c == '-' || *c == '_' || *c == '/'
+            })
             .collect::<String>()
-            .split_whitespace()
-            .collect::<Vec<&str>>()
-            .join("-");
+            .split('/')
+            .map(|segment| segment.split_whitespace().collect::<Vec<&str>>().join("-"))
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<String>>()
+            .join("/");
 
         if normalized.is_empty() {
             return Err(DomainError::ValidationError(
@@ -55,6 +89,34 @@ impl Tag {
         Ok(normalized)
     }
 
+    /// Name of the parent tag, if the name is nested
+    pub fn parent_name(name: &str) -> Option<&str> {
+        name.rsplit_once('/').map(|(parent, _)| parent)
+    }
+
+    /// Names of every ancestor, outermost first
+    pub fn ancestor_names(name: &str) -> Vec<&str> {
+        name.match_indices('/').map(|(index, _)| &name[..index]).collect()
+    }
+
+    /// Whether `name` is `ancestor` itself or nested anywhere under it
+    pub fn is_within(name: &str, ancestor: &str) -> bool {
+        name == ancestor
+            || name
+                .strip_prefix(ancestor)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// The name `name` takes when the tag `from` is renamed to `to`
+    ///
+    /// Returns `None` for names outside `from`.
+    pub fn rebase_name(name: &str, from: &str, to: &str) -> Option<String> {
+        if !Self::is_within(name, from) {
+            return None;
+        }
+        Some(format!("{}{}", to, &name[from.len()..]))
+    }
+
     /// Rename the tag
     pub fn rename(&mut self, new_name: impl Into<String>) -> DomainResult<()> {
         let normalized_name = Self::normalize_name(&new_name.into())?;
@@ -99,3 +161,11 @@ impl Tag {
     }
 }
 
+/// A tag on a note, with how it got there
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteTag {
+    #[serde(flatten)]
+    pub tag: Tag,
+    pub source: TagSource,
+}
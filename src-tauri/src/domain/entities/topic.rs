@@ -102,6 +102,25 @@ impl Topic {
         self.updated_at = Utc::now();
     }
 
+    /// Decode the centroid blob (little-endian f32s)
+    pub fn centroid_vector(&self) -> Option<Vec<f32>> {
+        let bytes = self.centroid.as_ref()?;
+        if bytes.is_empty() || bytes.len() % 4 != 0 {
+            return None; // Missing or malformed
+        }
+        Some(
+            bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect(),
+        )
+    }
+
+    /// Encode a centroid vector as stored in the database
+    pub fn encode_centroid(centroid: &[f32]) -> Vec<u8> {
+        centroid.iter().flat_map(|f| f.to_le_bytes()).collect()
+    }
+
     /// Increment note count
     pub fn increment_note_count(&mut self) {
         self.note_count += 1;
@@ -4,6 +4,8 @@
 /// They should NOT contain infrastructure concerns (DB, HTTP, etc.)
 use thiserror::Error;
 
+use crate::domain::services::SearchQueryError;
+
 #[derive(Error, Debug)]
 pub enum DomainError {
     #[error("Note not found: {0}")]
@@ -27,6 +29,9 @@ pub enum DomainError {
     #[error("Validation error: {0}")]
     ValidationError(String),
 
+    #[error("Invalid search query: {0}")]
+    InvalidSearchQuery(SearchQueryError),
+
     #[error("Domain logic error: {0}")]
     DomainLogicError(String),
 
@@ -0,0 +1,89 @@
+use crate::domain::{entities::Note, errors::DomainResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindDuplicatesRequest {
+    /// Defaults to the active workspace
+    pub workspace_id: Option<String>,
+    /// Minimum similarity (0..1) for two notes to count as duplicates
+    pub min_similarity: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateNoteInfo {
+    pub note_id: String,
+    pub title: String,
+    pub file_path: Option<String>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePairInfo {
+    pub note_id: String,
+    pub other_note_id: String,
+    pub similarity: f32,
+    /// Overlap of the notes' wording
+    pub text_similarity: f32,
+    /// Cosine similarity of the embeddings, when both notes have one
+    pub embedding_similarity: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateCluster {
+    /// Most recently updated first
+    pub notes: Vec<DuplicateNoteInfo>,
+    pub pairs: Vec<DuplicatePairInfo>,
+    /// Mean similarity of the pairs
+    pub similarity: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeStrategy {
+    /// Keep the target's content as is
+    #[default]
+    Keep,
+    /// Append each merged note's content under its title
+    Concatenate,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeNotesRequest {
+    /// Note that survives the merge
+    pub target_id: String,
+    /// Notes merged into the target and moved to trash
+    pub source_ids: Vec<String>,
+    #[serde(default)]
+    pub strategy: MergeStrategy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeNotesResponse {
+    pub note: Note,
+    /// Notes moved to trash
+    pub merged_ids: Vec<String>,
+    /// Notes whose wiki links were pointed at the target
+    pub relinked_note_ids: Vec<String>,
+}
+
+/// Duplicate Use Cases Port (Inbound)
+///
+/// Defines the contract for finding and merging duplicate notes.
+#[async_trait]
+pub trait DuplicateUseCases: Send + Sync {
+    /// Find groups of near-duplicate notes
+    async fn find_duplicates(
+        &self,
+        request: FindDuplicatesRequest,
+    ) -> DomainResult<Vec<DuplicateCluster>>;
+
+    /// Merge notes into one, relink references and trash the rest
+    async fn merge_notes(&self, request: MergeNotesRequest) -> DomainResult<MergeNotesResponse>;
+}
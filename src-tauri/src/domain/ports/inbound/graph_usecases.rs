@@ -1,4 +1,4 @@
-use crate::domain::errors::DomainResult;
+use crate::domain::{entities::NoteBlock, errors::DomainResult, ports::outbound::NotePassage};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -38,6 +38,84 @@ pub struct GraphDataOptions {
     pub include_orphans: Option<bool>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkSuggestionsRequest {
+    pub note_id: String,
+    /// Maximum number of related notes (default 5, 0 to skip)
+    pub related_limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkSuggestionKind {
+    /// The note's title or alias appears in the text without a link
+    Mention,
+    /// The note is semantically close to a passage of the text
+    Related,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkSuggestion {
+    pub kind: LinkSuggestionKind,
+    pub target_note_id: String,
+    pub target_title: String,
+    /// Text of the span: the mention, or the passage it relates to
+    pub text: String,
+    pub start_offset: usize, // Byte offset in the note's markdown
+    pub end_offset: usize,   // Byte offset in the note's markdown (exclusive)
+    pub line_number: usize,  // 1-based
+    /// Embedding similarity, for related notes
+    pub similarity: Option<f32>,
+    /// Best matching passage of the related note
+    pub target_passage: Option<NotePassage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyLinkSuggestionRequest {
+    pub note_id: String,
+    pub target_note_id: String,
+    pub kind: LinkSuggestionKind,
+    pub start_offset: usize,
+    pub end_offset: usize,
+    /// Text the span held when suggested; the apply fails if it changed
+    pub text: Option<String>,
+}
+
+/// A note a link target could refer to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkCandidate {
+    pub note_id: String,
+    pub title: String,
+    pub file_path: Option<String>,
+}
+
+/// A link whose target is the title or alias of several notes
+///
+/// Ambiguous links are left unresolved rather than pointed at one of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmbiguousLink {
+    /// Target as written in the link
+    pub target: String,
+    pub candidates: Vec<LinkCandidate>,
+}
+
+/// Markdown an embed like `![[Note#Heading]]` transcludes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbedContent {
+    pub note_id: String,
+    pub title: String,
+    /// Heading or `^block-id`, as written after `#`
+    pub anchor: Option<String>,
+    /// The section, block or whole note, with nested embeds expanded
+    pub markdown: String,
+}
+
 /// Graph Use Cases Port (Inbound)
 ///
 /// Defines the contract for link/graph operations.
@@ -56,5 +134,43 @@ pub trait GraphUseCases: Send + Sync {
     ) -> DomainResult<GraphData>;
 
     /// Update links for a note (called after save)
-    async fn update_note_links(&self, note_id: &str, content: &str) -> DomainResult<()>;
+    ///
+    /// Targets resolve by title, then by alias. Returns the links that could
+    /// not be resolved because several notes answer to their target.
+    async fn update_note_links(
+        &self,
+        note_id: &str,
+        content: &str,
+    ) -> DomainResult<Vec<AmbiguousLink>>;
+
+    /// Links in a note whose target several notes answer to
+    async fn get_ambiguous_links(&self, note_id: &str) -> DomainResult<Vec<AmbiguousLink>>;
+
+    /// Blocks of a note marked with `^block-id`, as indexed
+    async fn get_note_blocks(&self, note_id: &str) -> DomainResult<Vec<NoteBlock>>;
+
+    /// Resolve an embed in a note, written as `Note`, `Note#Heading` or
+    /// `Note#^block-id`, to the markdown it transcludes
+    ///
+    /// Returns None when the target note, heading or block doesn't exist.
+    async fn resolve_embed(
+        &self,
+        note_id: &str,
+        target: &str,
+    ) -> DomainResult<Option<EmbedContent>>;
+
+    /// Suggest links: unlinked mentions in document order, then related notes
+    async fn get_link_suggestions(
+        &self,
+        request: LinkSuggestionsRequest,
+    ) -> DomainResult<Vec<LinkSuggestion>>;
+
+    /// Turn a suggestion into a wiki link in the note's file
+    ///
+    /// Mentions are rewritten in place; related notes are linked at the end
+    /// of their passage. Returns the updated markdown.
+    async fn apply_link_suggestion(
+        &self,
+        request: ApplyLinkSuggestionRequest,
+    ) -> DomainResult<String>;
 }
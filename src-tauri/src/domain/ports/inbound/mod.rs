@@ -1,16 +1,21 @@
 pub mod attachment_usecases;
 pub mod database_usecases;
+pub mod duplicate_usecases;
 pub mod export_usecases;
 pub mod git_usecases;
 pub mod graph_usecases;
 pub mod note_usecases;
 pub mod notebook_usecases;
+pub mod periodic_note_usecases;
+pub mod question_answering_usecases;
 pub mod quick_capture_usecases;
+pub mod refactor_usecases;
 pub mod search_usecases;
 pub mod settings_usecases;
 pub mod system_usecases;
 pub mod tag_usecases;
 pub mod task_usecases;
+pub mod template_usecases;
 pub mod topic_usecases;
 pub mod version_usecases;
 pub mod workspace_usecases;
@@ -19,10 +24,16 @@ pub use attachment_usecases::{
     AddAttachmentRequest, AttachmentUseCases, UploadImageRequest, UploadImageResponse,
 };
 pub use database_usecases::{DatabaseStatus, DatabaseUseCases, IntegrityCheckResult};
+pub use duplicate_usecases::{
+    DuplicateCluster, DuplicateNoteInfo, DuplicatePairInfo, DuplicateUseCases,
+    FindDuplicatesRequest, MergeNotesRequest, MergeNotesResponse, MergeStrategy,
+};
 pub use export_usecases::{ExportOptions, ExportResult, ExportTheme, ExportUseCases};
 pub use git_usecases::{GitCommitInfo, GitStatusResponse, GitSyncResponse, GitUseCases};
 pub use graph_usecases::{
-    GraphData, GraphDataOptions, GraphLink, GraphNode, GraphUseCases, NoteLinkInfo,
+    AmbiguousLink, ApplyLinkSuggestionRequest, EmbedContent, GraphData, GraphDataOptions,
+    GraphLink, GraphNode, GraphUseCases, LinkCandidate, LinkSuggestion, LinkSuggestionKind,
+    LinkSuggestionsRequest, NoteLinkInfo,
 };
 pub use note_usecases::{
     CreateNoteInput, NoteFilter, NoteQuery, NoteUseCases, UpdateNoteInput,
@@ -31,10 +42,22 @@ pub use notebook_usecases::{
     CreateNotebookRequest, DeleteNotebookRequest, ListNotebooksRequest, MoveNotebookRequest,
     NotebookList, NotebookUseCases, UpdateNotebookRequest,
 };
+pub use periodic_note_usecases::{
+    OpenPeriodicNoteRequest, PeriodConfig, PeriodicNoteResponse, PeriodicNoteUseCases,
+    PeriodicNotesSettings, PERIODIC_NOTES_SETTINGS_PREFIX,
+};
+pub use question_answering_usecases::{
+    AnswerCitation, AnswerResponse, AnswerSource, AskQuestionRequest, QuestionAnsweringUseCases,
+    RetrievalMode,
+};
 pub use quick_capture_usecases::{AppendToJournalResponse, QuickCaptureUseCases};
+pub use refactor_usecases::{
+    FileChange, LineEdit, RefactorUseCases, RenameNotePlan, RenameNoteRequest, RenameNoteResponse,
+    RenameTagPlan, RenameTagRequest, RenameTagResponse, TagRename,
+};
 pub use search_usecases::{
-    HybridSearchResultItem, HybridSearchWeights, SearchByDateRangeRequest, SearchRequest,
-    SearchType, SearchUseCases, VectorSearchResult,
+    HybridSearchResultItem, HybridSearchWeights, SearchByDateRangeRequest, SearchByTagsRequest,
+    SearchRequest, SearchType, SearchUseCases, VectorSearchResult,
 };
 pub use settings_usecases::SettingsUseCases;
 pub use system_usecases::{SelectFolderOptions, SystemUseCases};
@@ -42,15 +65,21 @@ pub use tag_usecases::{
     CreateTagRequest, ListTagsRequest, TagList, TagUseCases, UpdateTagRequest,
 };
 pub use task_usecases::{TaskItem, TaskUseCases};
+pub use template_usecases::{
+    CreateNoteFromTemplateRequest, CreateNoteFromTemplateResponse, TemplateInfo, TemplateSettings,
+    TemplateUseCases, DEFAULT_TEMPLATES_FOLDER, TEMPLATE_SETTINGS_PREFIX,
+};
 pub use topic_usecases::{
-    ClassifyAllResponse, ClassifyNoteResponse, CreateTopicRequest, EmbeddingStatusResponse,
-    NoteTopicInfo, SimilarNoteResult, TopicClassification, TopicUseCases, UpdateTopicRequest,
+    AcceptTopicCandidateRequest, ClassifyAllResponse, ClassifyNoteResponse, CreateTopicRequest,
+    DiscoverTopicsRequest, DiscoverTopicsResponse, EmbeddingSettingsResponse,
+    EmbeddingStatusResponse, NoteTopicInfo, SimilarNoteResult, StartEmbeddingQueueRequest,
+    TopicCandidate, TopicCandidateMember, TopicClassification, TopicUseCases, UpdateTopicRequest,
 };
 pub use version_usecases::VersionUseCases;
 pub use workspace_usecases::{
     CreateFolderRequest, CreateFolderResponse, CreateWorkspaceRequest, FileSystemEntryType,
-    MoveFolderRequest, MoveFolderResponse, RenameFolderRequest, RenameFolderResponse,
+    FilenameSettings, MoveFolderRequest, MoveFolderResponse, RenameFolderRequest, RenameFolderResponse,
     ScanWorkspaceFileEntry, ScanWorkspaceFolderStructure, ScanWorkspaceResponse,
     SelectFolderRequest, SelectFolderResponse, SyncWorkspaceResponse, SyncWorkspaceStats,
-    UpdateWorkspaceRequest, ValidatePathResponse, WorkspaceUseCases,
+    UpdateWorkspaceRequest, ValidatePathResponse, WorkspaceUseCases, FILENAME_SETTINGS_PREFIX,
 };
@@ -0,0 +1,182 @@
+use crate::domain::{
+    entities::Note,
+    errors::{DomainError, DomainResult},
+    services::{NotePeriod, PeriodicNotes},
+};
+use async_trait::async_trait;
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the per-workspace periodic notes settings key
+pub const PERIODIC_NOTES_SETTINGS_PREFIX: &str = "periodicNotes.";
+
+/// Where and how the notes of one period are kept
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodConfig {
+    pub enabled: bool,
+    /// Folder relative to the workspace root
+    pub folder: String,
+    /// Moment-style file name format, like `YYYY-MM-DD` or `GGGG-[W]WW`
+    pub format: String,
+    /// Template path relative to the templates folder
+    pub template: Option<String>,
+}
+
+impl PeriodConfig {
+    fn new(enabled: bool, folder: &str, format: &str) -> Self {
+        Self {
+            enabled,
+            folder: folder.to_string(),
+            format: format.to_string(),
+            template: None,
+        }
+    }
+}
+
+/// Periodic notes settings of one workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodicNotesSettings {
+    /// Time (`HH:MM`) the day rolls over; earlier moments belong to the day before
+    pub day_starts_at: String,
+    pub daily: PeriodConfig,
+    pub weekly: PeriodConfig,
+    pub monthly: PeriodConfig,
+    pub quarterly: PeriodConfig,
+}
+
+impl Default for PeriodicNotesSettings {
+    fn default() -> Self {
+        Self {
+            day_starts_at: "00:00".to_string(),
+            daily: PeriodConfig::new(true, "Journal", "YYYY-MM-DD"),
+            weekly: PeriodConfig::new(true, "Journal/Weekly", "GGGG-[W]WW"),
+            monthly: PeriodConfig::new(true, "Journal/Monthly", "YYYY-MM"),
+            quarterly: PeriodConfig::new(true, "Journal/Quarterly", "YYYY-[Q]Q"),
+        }
+    }
+}
+
+impl PeriodicNotesSettings {
+    /// Settings key of a workspace's periodic notes settings
+    pub fn setting_key(workspace_id: &str) -> String {
+        format!("{}{}", PERIODIC_NOTES_SETTINGS_PREFIX, workspace_id)
+    }
+
+    /// Parse the JSON stored in settings
+    pub fn from_setting(value: &str) -> DomainResult<Self> {
+        serde_json::from_str(value).map_err(|e| {
+            DomainError::ConfigurationError(format!("Invalid periodic notes settings: {}", e))
+        })
+    }
+
+    /// Serialize for storage in settings
+    pub fn to_setting(&self) -> DomainResult<String> {
+        serde_json::to_string(self).map_err(|e| DomainError::InternalError(e.to_string()))
+    }
+
+    pub fn period(&self, period: NotePeriod) -> &PeriodConfig {
+        match period {
+            NotePeriod::Daily => &self.daily,
+            NotePeriod::Weekly => &self.weekly,
+            NotePeriod::Monthly => &self.monthly,
+            NotePeriod::Quarterly => &self.quarterly,
+        }
+    }
+
+    /// Time of day the day rolls over
+    pub fn day_start(&self) -> DomainResult<NaiveTime> {
+        PeriodicNotes::parse_day_start(&self.day_starts_at).ok_or_else(|| {
+            DomainError::ValidationError(format!(
+                "Day start must be a time like 04:00, got {}",
+                self.day_starts_at
+            ))
+        })
+    }
+
+    /// Check times, folders and formats before the settings are saved
+    pub fn validate(&self) -> DomainResult<()> {
+        self.day_start()?;
+
+        for period in NotePeriod::ALL {
+            let config = self.period(period);
+            let folder = config.folder.trim().trim_matches('/');
+            if folder.is_empty()
+                || folder.starts_with('\\')
+                || folder.split(['/', '\\']).any(|part| part == "..")
+            {
+                return Err(DomainError::ValidationError(format!(
+                    "The {} notes folder must be inside the workspace",
+                    period.as_str()
+                )));
+            }
+            let template_escapes = config.template.as_deref().is_some_and(|template| {
+                template.starts_with(['/', '\\'])
+                    || template.split(['/', '\\']).any(|part| part == "..")
+            });
+            if template_escapes {
+                return Err(DomainError::ValidationError(format!(
+                    "The {} notes template must be inside the templates folder",
+                    period.as_str()
+                )));
+            }
+            if config.format.trim().is_empty() {
+                return Err(DomainError::ValidationError(format!(
+                    "The {} notes file name format cannot be empty",
+                    period.as_str()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenPeriodicNoteRequest {
+    pub period: NotePeriod,
+    /// Any day (`YYYY-MM-DD`) of the period; the current one when not given
+    pub date: Option<String>,
+    /// Periods to step from `date`, e.g. -1 for the previous one
+    pub offset: Option<i32>,
+    pub workspace_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodicNoteResponse {
+    pub note: Note,
+    pub period: NotePeriod,
+    /// First day of the period (`YYYY-MM-DD`)
+    pub start_date: String,
+    /// Last day of the period (`YYYY-MM-DD`)
+    pub end_date: String,
+    /// Whether the note was created by this call
+    pub created: bool,
+}
+
+/// Periodic Note Use Cases Port (Inbound)
+///
+/// Defines the contract for daily, weekly, monthly and quarterly notes.
+#[async_trait]
+pub trait PeriodicNoteUseCases: Send + Sync {
+    /// Open the note of a period, creating it from its template if needed
+    async fn open_periodic_note(
+        &self,
+        request: OpenPeriodicNoteRequest,
+    ) -> DomainResult<PeriodicNoteResponse>;
+
+    /// Get the periodic notes settings of a workspace
+    async fn get_periodic_settings(
+        &self,
+        workspace_id: Option<String>,
+    ) -> DomainResult<PeriodicNotesSettings>;
+
+    /// Change the periodic notes settings of a workspace
+    async fn update_periodic_settings(
+        &self,
+        workspace_id: Option<String>,
+        settings: PeriodicNotesSettings,
+    ) -> DomainResult<PeriodicNotesSettings>;
+}
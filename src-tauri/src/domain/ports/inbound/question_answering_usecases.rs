@@ -0,0 +1,82 @@
+use crate::domain::{errors::DomainResult, ports::outbound::ChatModelConfig};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// How passages are found for a question
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RetrievalMode {
+    /// Embedding similarity only
+    Semantic,
+    /// Full-text and embedding rankings fused
+    #[default]
+    Hybrid,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AskQuestionRequest {
+    pub question: String,
+    /// Echoed in the streamed events; generated when not given
+    pub question_id: Option<String>,
+    pub retrieval: Option<RetrievalMode>,
+    /// Number of passages given to the model
+    pub limit: Option<i32>,
+    pub workspace_id: Option<String>,
+    pub notebook_id: Option<String>,
+}
+
+/// A passage the answer was based on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnswerSource {
+    /// Number the model cites the passage with
+    pub number: i32,
+    pub note_id: String,
+    pub title: String,
+    /// Headings enclosing the passage, outermost first
+    pub heading_path: Vec<String>,
+    pub text: String,
+    pub start_offset: i32, // Byte offset in the note's markdown
+    pub end_offset: i32,   // Byte offset in the note's markdown (exclusive)
+}
+
+/// A source cited in the answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnswerCitation {
+    pub number: i32,
+    pub note_id: String,
+    pub title: String,
+    pub start_offset: i32, // Byte offset in the note's markdown
+    pub end_offset: i32,   // Byte offset in the note's markdown (exclusive)
+    /// Byte ranges of the `[n]` markers citing the source in the answer
+    pub answer_ranges: Vec<(i32, i32)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnswerResponse {
+    pub question_id: String,
+    pub answer: String,
+    /// Cited sources, in order of first citation
+    pub citations: Vec<AnswerCitation>,
+    /// Every passage given to the model
+    pub sources: Vec<AnswerSource>,
+}
+
+/// Question Answering Use Cases Port (Inbound)
+///
+/// Defines the contract for answering questions from the user's notes.
+#[async_trait]
+pub trait QuestionAnsweringUseCases: Send + Sync {
+    /// Answer a question from retrieved passages, streaming the answer as
+    /// `qa:answer-delta` events
+    async fn ask_question(&self, request: AskQuestionRequest) -> DomainResult<AnswerResponse>;
+
+    /// Get the chat endpoint questions are sent to
+    async fn get_chat_settings(&self) -> DomainResult<ChatModelConfig>;
+
+    /// Change the chat endpoint questions are sent to
+    async fn update_chat_settings(&self, config: ChatModelConfig) -> DomainResult<ChatModelConfig>;
+}
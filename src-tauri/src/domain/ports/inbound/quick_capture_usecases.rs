@@ -1,4 +1,4 @@
-use crate::domain::errors::DomainResult;
+use crate::domain::{errors::DomainResult, services::NotePeriod};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -20,4 +20,12 @@ pub trait QuickCaptureUseCases: Send + Sync {
         content: &str,
         workspace_id: Option<&str>,
     ) -> DomainResult<AppendToJournalResponse>;
+
+    /// Append content to the current note of a period
+    async fn append_to_periodic_note(
+        &self,
+        content: &str,
+        period: NotePeriod,
+        workspace_id: Option<&str>,
+    ) -> DomainResult<AppendToJournalResponse>;
 }
@@ -0,0 +1,117 @@
+use crate::domain::{
+    entities::{Note, Tag},
+    errors::DomainResult,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameNoteRequest {
+    pub note_id: String,
+    pub new_title: String,
+}
+
+/// One line of a file as it is and as it would be rewritten
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineEdit {
+    pub line_number: usize, // 1-based
+    pub before: String,
+    pub after: String,
+}
+
+/// A file the refactoring rewrites
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChange {
+    /// Note stored in the file, if it is indexed
+    pub note_id: Option<String>,
+    pub title: Option<String>,
+    /// Relative to the workspace root
+    pub file_path: String,
+    pub edits: Vec<LineEdit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameNotePlan {
+    pub note_id: String,
+    pub old_title: String,
+    pub new_title: String,
+    /// The renamed note's own file first, then the notes linking to it
+    pub changes: Vec<FileChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameNoteResponse {
+    pub note: Note,
+    pub changes: Vec<FileChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameTagRequest {
+    pub tag_id: String,
+    /// An existing tag's name merges the tag into it
+    pub new_name: String,
+}
+
+/// A tag a rename moves, and the name it takes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagRename {
+    pub tag_id: String,
+    pub from: String,
+    pub to: String,
+    /// Tag already named `to` that this one merges into
+    pub merged_into: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameTagPlan {
+    pub tag_id: String,
+    pub old_name: String,
+    pub new_name: String,
+    /// The tag itself first, then the tags nested under it
+    pub tags: Vec<TagRename>,
+    /// Notes carrying any of the tags
+    pub note_count: usize,
+    /// Files whose frontmatter tags or hashtags are rewritten
+    pub changes: Vec<FileChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameTagResponse {
+    /// The tag now carrying the new name
+    pub tag: Tag,
+    pub tags: Vec<TagRename>,
+    pub changes: Vec<FileChange>,
+}
+
+/// Refactor Use Cases Port (Inbound)
+///
+/// Defines the contract for refactorings that rewrite several notes at once.
+#[async_trait]
+pub trait RefactorUseCases: Send + Sync {
+    /// Files a note rename would rewrite, without writing anything
+    async fn preview_note_rename(&self, request: RenameNoteRequest)
+        -> DomainResult<RenameNotePlan>;
+
+    /// Rename a note and rewrite the wiki links to it
+    ///
+    /// All files are rewritten or none are.
+    async fn rename_note(&self, request: RenameNoteRequest) -> DomainResult<RenameNoteResponse>;
+
+    /// Files and tags a tag rename or merge would change, without writing anything
+    async fn preview_tag_rename(&self, request: RenameTagRequest) -> DomainResult<RenameTagPlan>;
+
+    /// Rename a tag, or merge it into the tag already named that way
+    ///
+    /// Tags nested under it move along. Frontmatter tags lists and hashtags
+    /// in every affected file are rewritten, all files or none.
+    async fn rename_tag(&self, request: RenameTagRequest) -> DomainResult<RenameTagResponse>;
+}
@@ -1,4 +1,8 @@
-use crate::domain::{entities::Note, errors::DomainResult, ports::outbound::SearchResult};
+use crate::domain::{
+    entities::Note,
+    errors::DomainResult,
+    ports::outbound::{FusionMethod, NotePassage, SearchResult},
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +12,8 @@ pub struct VectorSearchResult {
     pub note_id: String,
     pub title: String,
     pub distance: f32,
+    /// Passage that matched best, for jumping to the relevant section
+    pub passage: Option<NotePassage>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -26,6 +32,9 @@ pub struct SearchRequest {
 pub struct HybridSearchWeights {
     pub fts: f32,
     pub semantic: f32,
+    /// Fusion strategy (defaults to reciprocal rank fusion)
+    #[serde(default)]
+    pub fusion: Option<FusionMethod>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +61,20 @@ pub struct SearchByDateRangeRequest {
     pub workspace_id: Option<String>,
     pub field: Option<String>,
     pub limit: Option<i32>,
+    pub offset: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchByTagsRequest {
+    pub tag_ids: Vec<String>,
+    /// Require every tag (AND) instead of any tag (OR)
+    pub match_all: Option<bool>,
+    /// Also match notes carrying tags nested under a requested tag
+    pub include_descendants: Option<bool>,
+    pub workspace_id: Option<String>,
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
 }
 
 /// Search Use Cases Port (Inbound)
@@ -85,12 +108,7 @@ pub trait SearchUseCases: Send + Sync {
     ) -> DomainResult<Vec<HybridSearchResultItem>>;
 
     /// Search by tags
-    async fn search_by_tags(
-        &self,
-        tag_ids: Vec<String>,
-        match_all: Option<bool>,
-        limit: Option<i32>,
-    ) -> DomainResult<Vec<Note>>;
+    async fn search_by_tags(&self, request: SearchByTagsRequest) -> DomainResult<Vec<Note>>;
 
     /// Search by date range
     async fn search_by_date_range(
@@ -1,4 +1,8 @@
-use crate::domain::{entities::Tag, errors::DomainResult, ports::outbound::TagWithCount};
+use crate::domain::{
+    entities::{NoteTag, Tag},
+    errors::DomainResult,
+    ports::outbound::TagWithCount,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -38,13 +42,13 @@ pub trait TagUseCases: Send + Sync {
     /// Create a new tag
     async fn create_tag(&self, request: CreateTagRequest) -> DomainResult<Tag>;
 
-    /// Update an existing tag
+    /// Update an existing tag's color; renames go through `RefactorUseCases::rename_tag`
     async fn update_tag(&self, request: UpdateTagRequest) -> DomainResult<Tag>;
 
     /// Get a tag by ID
     async fn get_tag(&self, id: &str) -> DomainResult<Tag>;
 
-    /// List all tags
+    /// List all tags; with note counts they come nested under their parents
     async fn list_tags(&self, request: Option<ListTagsRequest>) -> DomainResult<TagList>;
 
     /// Delete a tag
@@ -56,6 +60,6 @@ pub trait TagUseCases: Send + Sync {
     /// Remove a tag from a note
     async fn remove_tag_from_note(&self, note_id: &str, tag_id: &str) -> DomainResult<()>;
 
-    /// Get all tags for a note
-    async fn get_note_tags(&self, note_id: &str) -> DomainResult<Vec<Tag>>;
+    /// Get all tags for a note, with whether each came from its content
+    async fn get_note_tags(&self, note_id: &str) -> DomainResult<Vec<NoteTag>>;
 }
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use crate::domain::{
+    entities::Note,
+    errors::{DomainError, DomainResult},
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the per-workspace template settings key
+pub const TEMPLATE_SETTINGS_PREFIX: &str = "templates.";
+
+/// Folder templates are read from when none is configured
+pub const DEFAULT_TEMPLATES_FOLDER: &str = "Templates";
+
+/// Template settings of one workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateSettings {
+    /// Folder holding the templates, relative to the workspace root
+    pub folder: String,
+    /// Default template path (relative to `folder`) by notebook ID
+    #[serde(default)]
+    pub notebook_templates: HashMap<String, String>,
+}
+
+impl Default for TemplateSettings {
+    fn default() -> Self {
+        Self {
+            folder: DEFAULT_TEMPLATES_FOLDER.to_string(),
+            notebook_templates: HashMap::new(),
+        }
+    }
+}
+
+impl TemplateSettings {
+    /// Settings key of a workspace's template settings
+    pub fn setting_key(workspace_id: &str) -> String {
+        format!("{}{}", TEMPLATE_SETTINGS_PREFIX, workspace_id)
+    }
+
+    /// Parse the JSON stored in settings
+    pub fn from_setting(value: &str) -> DomainResult<Self> {
+        serde_json::from_str(value).map_err(|e| {
+            DomainError::ConfigurationError(format!("Invalid template settings: {}", e))
+        })
+    }
+
+    /// Serialize for storage in settings
+    pub fn to_setting(&self) -> DomainResult<String> {
+        serde_json::to_string(self).map_err(|e| DomainError::InternalError(e.to_string()))
+    }
+
+    /// Check the folder stays inside the workspace
+    pub fn validate(&self) -> DomainResult<()> {
+        let folder = self.folder.trim().trim_matches('/');
+        if folder.is_empty() {
+            return Err(DomainError::ValidationError(
+                "Templates folder cannot be empty".to_string(),
+            ));
+        }
+        if folder.starts_with('\\') || folder.split(['/', '\\']).any(|part| part == "..") {
+            return Err(DomainError::ValidationError(
+                "Templates folder must be inside the workspace".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A template file in the templates folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateInfo {
+    /// File name without extension
+    pub name: String,
+    /// Path relative to the templates folder
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateNoteFromTemplateRequest {
+    pub title: String,
+    /// Template path relative to the templates folder; the notebook's
+    /// default template when not given
+    pub template_path: Option<String>,
+    pub notebook_id: Option<String>,
+    pub workspace_id: Option<String>,
+    pub folder_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateNoteFromTemplateResponse {
+    pub note: Note,
+    /// Byte offset in the note's markdown where the template put `{{cursor}}`
+    pub cursor_offset: Option<i32>,
+}
+
+/// Template Use Cases Port (Inbound)
+///
+/// Defines the contract for note templates.
+#[async_trait]
+pub trait TemplateUseCases: Send + Sync {
+    /// List templates of a workspace (the active one when not given)
+    async fn list_templates(&self, workspace_id: Option<String>)
+        -> DomainResult<Vec<TemplateInfo>>;
+
+    /// Get the template settings of a workspace
+    async fn get_template_settings(
+        &self,
+        workspace_id: Option<String>,
+    ) -> DomainResult<TemplateSettings>;
+
+    /// Change the template settings of a workspace
+    async fn update_template_settings(
+        &self,
+        workspace_id: Option<String>,
+        settings: TemplateSettings,
+    ) -> DomainResult<TemplateSettings>;
+
+    /// Set or clear the default template of a notebook
+    async fn set_notebook_template(
+        &self,
+        notebook_id: &str,
+        template_path: Option<String>,
+    ) -> DomainResult<TemplateSettings>;
+
+    /// Create a note from a template with its placeholders expanded
+    async fn create_note_from_template(
+        &self,
+        request: CreateNoteFromTemplateRequest,
+    ) -> DomainResult<CreateNoteFromTemplateResponse>;
+}
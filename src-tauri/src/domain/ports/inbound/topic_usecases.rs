@@ -1,4 +1,11 @@
-use crate::domain::{entities::{Note, Topic}, errors::DomainResult, ports::outbound::TopicWithCount};
+use crate::domain::{
+    entities::{Note, Topic},
+    errors::DomainResult,
+    ports::outbound::{
+        EmbeddingModelInfo, EmbeddingModelOption, EmbeddingProviderConfig, EmbeddingQueueStatus,
+        NotePassage, TopicWithCount,
+    },
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -40,6 +47,58 @@ pub struct ClassifyAllResponse {
     pub processed: i32,
     pub total: i32,
     pub failed: i32,
+    /// Notes that were assigned at least one topic
+    pub results: Vec<ClassifyNoteResponse>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoverTopicsRequest {
+    /// Defaults to the active workspace
+    pub workspace_id: Option<String>,
+    /// Maximum number of candidates (and clusters tried)
+    pub max_topics: Option<usize>,
+    /// Smallest number of notes a candidate may have
+    pub min_notes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicCandidateMember {
+    pub note_id: String,
+    pub title: String,
+    /// Cosine similarity to the candidate's centroid
+    pub similarity: f32,
+}
+
+/// A proposed topic; not saved until accepted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicCandidate {
+    pub topic: Topic,
+    /// Terms that set the members apart, most distinctive first
+    pub terms: Vec<String>,
+    pub members: Vec<TopicCandidateMember>,
+    /// Mean similarity of the members to the centroid
+    pub cohesion: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoverTopicsResponse {
+    pub candidates: Vec<TopicCandidate>,
+    /// Embedded notes that were clustered
+    pub notes_considered: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcceptTopicCandidateRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub color: Option<String>,
+    /// Notes to assign to the new topic
+    pub note_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +107,8 @@ pub struct SimilarNoteResult {
     pub note_id: String,
     pub title: String,
     pub distance: f32,
+    /// Passage that matched best, for jumping to the relevant section
+    pub passage: Option<NotePassage>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +118,31 @@ pub struct EmbeddingStatusResponse {
     pub total_notes: i32,
     pub embedded_notes: i32,
     pub pending_notes: i32,
+    /// Background embedding queue
+    pub queue: EmbeddingQueueStatus,
+}
+
+/// Overrides for an embedding queue run; unset fields use the defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartEmbeddingQueueRequest {
+    /// Passages embedded per batch
+    pub batch_size: Option<usize>,
+    /// Share of wall time spent embedding, in percent
+    pub max_cpu_percent: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingSettingsResponse {
+    pub provider: EmbeddingProviderConfig,
+    /// Model new embeddings are made with
+    pub model: EmbeddingModelInfo,
+    pub ready: bool,
+    /// Models that can be picked for the FastEmbed provider
+    pub available_models: Vec<EmbeddingModelOption>,
+    /// Notes embedded with another model, waiting to be embedded again
+    pub stale_notes: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,6 +186,18 @@ pub trait TopicUseCases: Send + Sync {
     /// Classify all notes
     async fn classify_all_notes(&self) -> DomainResult<ClassifyAllResponse>;
 
+    /// Propose new topics by clustering note embeddings
+    async fn discover_topics(
+        &self,
+        request: DiscoverTopicsRequest,
+    ) -> DomainResult<DiscoverTopicsResponse>;
+
+    /// Create a topic from a candidate and assign its notes
+    async fn accept_topic_candidate(
+        &self,
+        request: AcceptTopicCandidateRequest,
+    ) -> DomainResult<Topic>;
+
     /// Assign topic to note (manual)
     async fn assign_topic_to_note(&self, note_id: &str, topic_id: &str) -> DomainResult<()>;
 
@@ -126,6 +224,30 @@ pub trait TopicUseCases: Send + Sync {
     /// Get embedding status
     async fn get_embedding_status(&self) -> DomainResult<EmbeddingStatusResponse>;
 
+    /// Embed notes that are un-embedded or out of date in the background
+    async fn start_embedding_queue(
+        &self,
+        request: StartEmbeddingQueueRequest,
+    ) -> DomainResult<EmbeddingQueueStatus>;
+
+    /// Pause the embedding queue after the batch in progress
+    async fn pause_embedding_queue(&self) -> DomainResult<EmbeddingQueueStatus>;
+
+    /// Resume a paused embedding queue
+    async fn resume_embedding_queue(&self) -> DomainResult<EmbeddingQueueStatus>;
+
+    /// Cancel the embedding queue after the batch in progress
+    async fn cancel_embedding_queue(&self) -> DomainResult<EmbeddingQueueStatus>;
+
+    /// Get the embedding provider and model in use
+    async fn get_embedding_settings(&self) -> DomainResult<EmbeddingSettingsResponse>;
+
+    /// Switch embedding provider and queue notes embedded with another model
+    async fn update_embedding_settings(
+        &self,
+        provider: EmbeddingProviderConfig,
+    ) -> DomainResult<EmbeddingSettingsResponse>;
+
     /// Get notes for a topic
     async fn get_notes_for_topic(
         &self,
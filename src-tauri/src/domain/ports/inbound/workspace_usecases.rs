@@ -1,8 +1,15 @@
-use crate::domain::{entities::Workspace, errors::DomainResult};
+use crate::domain::{
+    entities::Workspace,
+    errors::{DomainError, DomainResult},
+    services::FilenameStrategy,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Prefix of the per-workspace filename settings key
+pub const FILENAME_SETTINGS_PREFIX: &str = "filenames.";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateWorkspaceRequest {
@@ -113,6 +120,9 @@ pub struct RenameFolderRequest {
 pub struct RenameFolderResponse {
     pub old_path: String,
     pub new_path: String,
+    /// Notes whose relative links were rewritten, relative to the workspace root
+    #[serde(default)]
+    pub rewritten_files: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -127,6 +137,39 @@ pub struct MoveFolderRequest {
 pub struct MoveFolderResponse {
     pub old_path: String,
     pub new_path: String,
+    /// Notes whose relative links were rewritten, relative to the workspace root
+    #[serde(default)]
+    pub rewritten_files: Vec<String>,
+}
+
+/// How one workspace names note files
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilenameSettings {
+    #[serde(default)]
+    pub strategy: FilenameStrategy,
+    /// Rename a note's file when its title changes; never done for timestamps
+    #[serde(default)]
+    pub rename_on_title_change: bool,
+}
+
+impl FilenameSettings {
+    /// Settings key of a workspace's filename settings
+    pub fn setting_key(workspace_id: &str) -> String {
+        format!("{}{}", FILENAME_SETTINGS_PREFIX, workspace_id)
+    }
+
+    /// Parse the JSON stored in settings
+    pub fn from_setting(value: &str) -> DomainResult<Self> {
+        serde_json::from_str(value).map_err(|e| {
+            DomainError::ConfigurationError(format!("Invalid filename settings: {}", e))
+        })
+    }
+
+    /// Serialize for storage in settings
+    pub fn to_setting(&self) -> DomainResult<String> {
+        serde_json::to_string(self).map_err(|e| DomainError::InternalError(e.to_string()))
+    }
 }
 
 /// Workspace Use Cases Port (Inbound)
@@ -184,4 +227,17 @@ pub trait WorkspaceUseCases: Send + Sync {
 
     /// Move a folder to a different location
     async fn move_folder(&self, request: MoveFolderRequest) -> DomainResult<MoveFolderResponse>;
+
+    /// Get how a workspace (or the active one) names note files
+    async fn get_filename_settings(
+        &self,
+        workspace_id: Option<String>,
+    ) -> DomainResult<FilenameSettings>;
+
+    /// Update how a workspace (or the active one) names note files
+    async fn update_filename_settings(
+        &self,
+        workspace_id: Option<String>,
+        settings: FilenameSettings,
+    ) -> DomainResult<FilenameSettings>;
 }
@@ -0,0 +1,126 @@
+use super::embedding_provider::is_local_url;
+use crate::domain::errors::{DomainError, DomainResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Settings key holding the chat endpoint used to answer questions, as JSON
+pub const CHAT_MODEL_SETTING: &str = "qa.chatModel";
+
+/// Ollama's OpenAI-compatible endpoint, used when nothing else is configured
+pub const DEFAULT_CHAT_BASE_URL: &str = "http://localhost:11434/v1";
+
+/// Model asked when nothing else is configured
+pub const DEFAULT_CHAT_MODEL: &str = "llama3.2";
+
+/// An OpenAI-compatible `/v1/chat/completions` endpoint on this machine,
+/// such as llama.cpp's server, Ollama or LM Studio
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatModelConfig {
+    pub base_url: String,
+    pub model: String,
+    pub api_key: Option<String>,
+    /// Sampling temperature; the server's default when unset
+    pub temperature: Option<f32>,
+}
+
+impl Default for ChatModelConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_CHAT_BASE_URL.to_string(),
+            model: DEFAULT_CHAT_MODEL.to_string(),
+            api_key: None,
+            temperature: None,
+        }
+    }
+}
+
+impl ChatModelConfig {
+    /// Parse the value stored under `CHAT_MODEL_SETTING`
+    pub fn from_setting(value: &str) -> DomainResult<Self> {
+        serde_json::from_str(value).map_err(|e| {
+            DomainError::ConfigurationError(format!("Invalid chat model setting: {}", e))
+        })
+    }
+
+    /// Serialize for storing under `CHAT_MODEL_SETTING`
+    pub fn to_setting(&self) -> DomainResult<String> {
+        serde_json::to_string(self).map_err(|e| DomainError::InternalError(e.to_string()))
+    }
+
+    /// Check the settings before they are saved
+    ///
+    /// Passages from notes are sent with every question, so the endpoint must
+    /// be on this machine.
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.model.trim().is_empty() {
+            return Err(DomainError::ValidationError(
+                "Chat model is required".to_string(),
+            ));
+        }
+        if !is_local_url(&self.base_url) {
+            return Err(DomainError::ValidationError(format!(
+                "Chat endpoint must be an http(s) URL on this machine: {}",
+                self.base_url
+            )));
+        }
+        if let Some(temperature) = self.temperature {
+            if !(0.0..=2.0).contains(&temperature) {
+                return Err(DomainError::ValidationError(
+                    "Temperature must be between 0 and 2".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::System,
+            content: content.into(),
+        }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self {
+            role: ChatRole::User,
+            content: content.into(),
+        }
+    }
+}
+
+/// Receives each piece of a reply as it is generated
+pub type ChatDeltaHandler<'a> = &'a (dyn Fn(&str) + Send + Sync);
+
+/// Chat Service Port (Outbound)
+///
+/// Defines the contract for generating text with a chat model.
+#[async_trait]
+pub trait ChatService: Send + Sync {
+    /// Send a conversation to the configured endpoint and stream the reply
+    ///
+    /// `on_delta` is called for every piece as it arrives; the whole reply is
+    /// returned once the model is done.
+    async fn stream_chat(
+        &self,
+        config: &ChatModelConfig,
+        messages: Vec<ChatMessage>,
+        on_delta: ChatDeltaHandler<'_>,
+    ) -> DomainResult<String>;
+}
@@ -0,0 +1,145 @@
+use crate::domain::errors::{DomainError, DomainResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Settings key holding the chosen provider, as JSON
+pub const EMBEDDING_PROVIDER_SETTING: &str = "embeddings.provider";
+
+/// FastEmbed model used when nothing else is configured
+pub const DEFAULT_FASTEMBED_MODEL: &str = "AllMiniLML6V2";
+
+/// Identity of the model that produced a vector
+///
+/// Vectors from different models can't be compared, so every stored vector
+/// records the identity it was made with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingModelInfo {
+    pub name: String,
+    pub version: String,
+    /// Known once the model is loaded or the endpoint has answered
+    pub dimensions: Option<usize>,
+}
+
+/// A model that can be picked from the FastEmbed catalogue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingModelOption {
+    pub id: String,
+    pub description: String,
+    pub dimensions: usize,
+}
+
+/// Where embeddings come from, as chosen in settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum EmbeddingProviderConfig {
+    /// A FastEmbed catalogue model, downloaded on first use
+    FastEmbed { model: String },
+    /// ONNX model and tokenizer files in a local directory, for offline machines
+    LocalModel { path: String },
+    /// An OpenAI-compatible `/v1/embeddings` endpoint on this machine
+    OpenAiCompatible {
+        base_url: String,
+        model: String,
+        api_key: Option<String>,
+    },
+}
+
+impl Default for EmbeddingProviderConfig {
+    fn default() -> Self {
+        Self::FastEmbed {
+            model: DEFAULT_FASTEMBED_MODEL.to_string(),
+        }
+    }
+}
+
+impl EmbeddingProviderConfig {
+    /// Parse the value stored under `EMBEDDING_PROVIDER_SETTING`
+    pub fn from_setting(value: &str) -> DomainResult<Self> {
+        serde_json::from_str(value).map_err(|e| {
+            DomainError::ConfigurationError(format!("Invalid embedding provider setting: {}", e))
+        })
+    }
+
+    /// Serialize for storing under `EMBEDDING_PROVIDER_SETTING`
+    pub fn to_setting(&self) -> DomainResult<String> {
+        serde_json::to_string(self).map_err(|e| DomainError::InternalError(e.to_string()))
+    }
+
+    /// Check the settings before they are saved
+    ///
+    /// Endpoints must be on this machine so note text never leaves it.
+    pub fn validate(&self) -> DomainResult<()> {
+        match self {
+            Self::FastEmbed { model } if model.trim().is_empty() => Err(
+                DomainError::ValidationError("Embedding model is required".to_string()),
+            ),
+            Self::LocalModel { path } if path.trim().is_empty() => Err(
+                DomainError::ValidationError("Model directory is required".to_string()),
+            ),
+            Self::OpenAiCompatible {
+                base_url, model, ..
+            } => {
+                if model.trim().is_empty() {
+                    return Err(DomainError::ValidationError(
+                        "Embedding model is required".to_string(),
+                    ));
+                }
+                if !is_local_url(base_url) {
+                    return Err(DomainError::ValidationError(format!(
+                        "Embedding endpoint must be an http(s) URL on this machine: {}",
+                        base_url
+                    )));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Whether a URL points at a loopback host
+pub(crate) fn is_local_url(url: &str) -> bool {
+    let url = url.trim();
+    let Some(rest) = url
+        .strip_prefix("http://")
+        .or_else(|| url.strip_prefix("https://"))
+    else {
+        return false;
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let authority = authority.rsplit('@').next().unwrap_or_default();
+    let host = match authority.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    };
+
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Embedding Provider Port (Outbound)
+///
+/// Defines the contract for a backend that turns text into vectors. The
+/// embedding service stores and searches what a provider produces.
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Identity recorded with every vector this provider produces
+    fn model(&self) -> EmbeddingModelInfo;
+
+    /// Load the model or check the endpoint; calling it again is a no-op
+    async fn initialize(&self) -> DomainResult<()>;
+
+    /// Check if the provider can embed text
+    fn is_ready(&self) -> bool;
+
+    /// Embed texts, returning one vector per text in the same order
+    async fn embed(&self, texts: Vec<String>) -> DomainResult<Vec<Vec<f32>>>;
+}
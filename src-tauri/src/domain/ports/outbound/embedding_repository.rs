@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use crate::domain::{entities::Embedding, errors::DomainResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Settings key holding the storage format of new vectors: f32, f16 or int8
+pub const EMBEDDING_QUANTIZATION_SETTING: &str = "embeddings.quantization";
+
+/// How many embeddings a model has stored, and when the newest was written
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingSummary {
+    pub count: i64,
+    pub last_created_at: Option<DateTime<Utc>>,
+}
+
+/// Embedding Repository Port (Outbound)
+///
+/// Defines the contract for persisting embedding vectors of notes and their
+/// passages, kept apart from the notes themselves so listing notes never
+/// loads vectors.
+#[async_trait]
+pub trait EmbeddingRepository: Send + Sync {
+    /// Get the note-level embedding of a note
+    async fn find_for_note(&self, note_id: &str) -> DomainResult<Option<Embedding>>;
+
+    /// Get the note-level embeddings of several notes, keyed by note ID
+    async fn find_for_notes(&self, note_ids: &[String])
+        -> DomainResult<HashMap<String, Embedding>>;
+
+    /// Get the passage embeddings of a note, in document order
+    async fn find_chunks_for_note(&self, note_id: &str) -> DomainResult<Vec<Embedding>>;
+
+    /// Get every stored embedding, note-level and passage
+    async fn find_all(&self) -> DomainResult<Vec<Embedding>>;
+
+    /// Replace all embeddings of a note
+    async fn replace_for_note(&self, note_id: &str, embeddings: Vec<Embedding>)
+        -> DomainResult<()>;
+
+    /// Remove all embeddings of a note
+    async fn delete_for_note(&self, note_id: &str) -> DomainResult<()>;
+
+    /// Count notes with a note-level embedding from the given model
+    async fn count_embedded_notes(&self, model_name: &str, model_version: &str)
+        -> DomainResult<i64>;
+
+    /// Count the note-level and passage embeddings of a model and find the newest
+    async fn summarize_model(&self, model_name: &str, model_version: &str)
+        -> DomainResult<EmbeddingSummary>;
+
+    /// Get the notes with embeddings from any other model
+    async fn find_stale_note_ids(
+        &self,
+        model_name: &str,
+        model_version: &str,
+    ) -> DomainResult<Vec<String>>;
+}
@@ -1,4 +1,9 @@
-use crate::domain::errors::DomainResult;
+use std::collections::HashMap;
+
+use super::embedding_provider::{
+    EmbeddingModelInfo, EmbeddingModelOption, EmbeddingProviderConfig,
+};
+use crate::domain::{entities::NoteChunk, errors::DomainResult};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -23,6 +28,46 @@ pub struct SimilarNote {
     pub title: String,
     pub similarity: f32,
     pub distance: f32,
+    /// Best matching passage, for notes embedded in chunks
+    pub passage: Option<NotePassage>,
+}
+
+/// Passage of a note that matched a similarity query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotePassage {
+    pub chunk_index: i32,
+    /// Headings enclosing the passage, outermost first
+    pub heading_path: Vec<String>,
+    pub text: String,
+    pub start_offset: i32, // Byte offset in the note's markdown
+    pub end_offset: i32,   // Byte offset in the note's markdown (exclusive)
+}
+
+impl From<NoteChunk> for NotePassage {
+    fn from(chunk: NoteChunk) -> Self {
+        Self {
+            chunk_index: chunk.chunk_index,
+            heading_path: chunk.heading_path,
+            text: chunk.content,
+            start_offset: chunk.start_offset,
+            end_offset: chunk.end_offset,
+        }
+    }
+}
+
+/// Restricts similarity search to notes in a workspace and/or notebook
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingSearchFilter {
+    pub workspace_id: Option<String>,
+    pub notebook_id: Option<String>,
+}
+
+impl EmbeddingSearchFilter {
+    pub fn is_empty(&self) -> bool {
+        self.workspace_id.is_none() && self.notebook_id.is_none()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +78,27 @@ pub struct EmbeddingStatus {
     pub pending_notes: i32,
 }
 
+/// What the background embedding queue is doing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingQueueState {
+    #[default]
+    Idle,
+    Running,
+    Paused,
+}
+
+/// Progress of the current embedding queue run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingQueueStatus {
+    pub state: EmbeddingQueueState,
+    /// Notes found un-embedded or out of date when the run started
+    pub total: i32,
+    pub processed: i32,
+    pub failed: i32,
+}
+
 /// Embedding Service Port (Outbound)
 ///
 /// Defines the contract for ML embedding operations.
@@ -44,6 +110,23 @@ pub trait EmbeddingService: Send + Sync {
     /// Check if service is ready
     fn is_ready(&self) -> bool;
 
+    /// Identity of the model new embeddings are made with
+    fn model(&self) -> EmbeddingModelInfo;
+
+    /// Models that can be picked from the built-in catalogue
+    fn available_models(&self) -> Vec<EmbeddingModelOption>;
+
+    /// Switch to another provider
+    ///
+    /// The new provider is initialized first; if that fails the current one
+    /// stays in use.
+    /// Embeddings made by other models are ignored from then on and reported
+    /// by `find_stale_note_ids` until the notes are embedded again.
+    async fn configure(&self, config: EmbeddingProviderConfig) -> DomainResult<EmbeddingModelInfo>;
+
+    /// Notes whose stored embeddings were made by a different model
+    async fn find_stale_note_ids(&self) -> DomainResult<Vec<String>>;
+
     /// Generate embedding for text
     async fn generate_embedding(&self, text: &str) -> DomainResult<Vec<f32>>;
 
@@ -57,6 +140,7 @@ pub trait EmbeddingService: Send + Sync {
     async fn find_similar_notes(
         &self,
         note_id: &str,
+        filter: Option<EmbeddingSearchFilter>,
         limit: Option<i32>,
     ) -> DomainResult<Vec<SimilarNote>>;
 
@@ -64,16 +148,29 @@ pub trait EmbeddingService: Send + Sync {
     async fn semantic_search(
         &self,
         query: &str,
+        filter: Option<EmbeddingSearchFilter>,
         limit: Option<i32>,
     ) -> DomainResult<Vec<SimilarNote>>;
 
     /// Store embedding for a note
     async fn store_embedding(&self, note_id: &str, embedding: Vec<f32>) -> DomainResult<()>;
 
-    /// Get embedding for a note
+    /// Store passage embeddings for a note, replacing any earlier ones
+    ///
+    /// The note's own embedding becomes the normalized mean of its passages.
+    async fn store_chunk_embeddings(&self, note_id: &str, chunks: Vec<NoteChunk>)
+        -> DomainResult<()>;
+
+    /// Get embedding for a note, if made by the current model
     async fn get_embedding(&self, note_id: &str) -> DomainResult<Option<Vec<f32>>>;
 
-    /// Delete embedding for a note
+    /// Get the embeddings of several notes, keyed by note ID; notes without one are left out
+    async fn get_embeddings(&self, note_ids: &[String]) -> DomainResult<HashMap<String, Vec<f32>>>;
+
+    /// Get the content hashes recorded with notes' current-model embeddings, keyed by note ID
+    async fn get_content_hashes(&self, note_ids: &[String]) -> DomainResult<HashMap<String, String>>;
+
+    /// Delete embedding and passage embeddings for a note
     async fn delete_embedding(&self, note_id: &str) -> DomainResult<()>;
 
     /// Recompute topic centroids
@@ -2,6 +2,8 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use super::embedding_service::EmbeddingQueueState;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum FileSyncOperation {
@@ -78,6 +80,19 @@ pub enum DomainEvent {
         id: String,
         name: String,
     },
+    #[serde(rename = "tag:renamed")]
+    TagRenamed {
+        timestamp: chrono::DateTime<chrono::Utc>,
+        /// The tag that now carries the new name
+        id: String,
+        from: String,
+        to: String,
+        /// Whether the new name was an existing tag the old one merged into
+        merged: bool,
+        tag_count: usize,
+        note_count: usize,
+        file_count: usize,
+    },
     #[serde(rename = "tag:deleted")]
     TagDeleted {
         timestamp: chrono::DateTime<chrono::Utc>,
@@ -141,6 +156,43 @@ pub enum DomainEvent {
         total: i32,
         failed: i32,
     },
+    #[serde(rename = "embedding:queue-progress")]
+    EmbeddingQueueProgress {
+        timestamp: chrono::DateTime<chrono::Utc>,
+        state: EmbeddingQueueState,
+        processed: i32,
+        total: i32,
+        failed: i32,
+    },
+    #[serde(rename = "embedding:model-changed")]
+    EmbeddingModelChanged {
+        timestamp: chrono::DateTime<chrono::Utc>,
+        model_name: String,
+        model_version: String,
+        /// Notes embedded with another model, to be embedded again
+        stale_note_ids: Vec<String>,
+    },
+    #[serde(rename = "index:progress")]
+    IndexingProgress {
+        timestamp: chrono::DateTime<chrono::Utc>,
+        note_id: String,
+        processed: i32,
+        total: i32,
+        failed: i32,
+    },
+    #[serde(rename = "qa:answer-delta")]
+    AnswerDelta {
+        timestamp: chrono::DateTime<chrono::Utc>,
+        question_id: String,
+        /// Next piece of the answer text
+        delta: String,
+    },
+    #[serde(rename = "qa:answered")]
+    QuestionAnswered {
+        timestamp: chrono::DateTime<chrono::Utc>,
+        question_id: String,
+        cited_note_ids: Vec<String>,
+    },
     #[serde(rename = "db:vacuum:progress")]
     DbVacuumProgress {
         timestamp: chrono::DateTime<chrono::Utc>,
@@ -162,6 +214,7 @@ impl DomainEvent {
             DomainEvent::NotebookUpdated { .. } => "notebook:updated",
             DomainEvent::NotebookDeleted { .. } => "notebook:deleted",
             DomainEvent::TagCreated { .. } => "tag:created",
+            DomainEvent::TagRenamed { .. } => "tag:renamed",
             DomainEvent::TagDeleted { .. } => "tag:deleted",
             DomainEvent::NoteTagged { .. } => "note:tagged",
             DomainEvent::NoteUntagged { .. } => "note:untagged",
@@ -172,6 +225,11 @@ impl DomainEvent {
             DomainEvent::TopicDeleted { .. } => "topic:deleted",
             DomainEvent::NoteClassified { .. } => "note:classified",
             DomainEvent::EmbeddingProgress { .. } => "embedding:progress",
+            DomainEvent::EmbeddingQueueProgress { .. } => "embedding:queue-progress",
+            DomainEvent::EmbeddingModelChanged { .. } => "embedding:model-changed",
+            DomainEvent::IndexingProgress { .. } => "index:progress",
+            DomainEvent::AnswerDelta { .. } => "qa:answer-delta",
+            DomainEvent::QuestionAnswered { .. } => "qa:answered",
             DomainEvent::DbVacuumProgress { .. } => "db:vacuum:progress",
             DomainEvent::DbVacuumComplete { .. } => "db:vacuum:complete",
         }
@@ -180,6 +238,9 @@ impl DomainEvent {
 
 pub type EventHandler = Arc<dyn Fn(DomainEvent) + Send + Sync>;
 
+/// Called with the number of events a subscriber fell too far behind to see
+pub type LagHandler = Arc<dyn Fn(u64) + Send + Sync>;
+
 /// Event Publisher Port (Outbound)
 ///
 /// Defines the contract for publishing domain events.
@@ -198,6 +259,17 @@ pub trait EventPublisher: Send + Sync {
     /// Subscribe to events of a specific type
     fn subscribe(&self, event_type: &str, handler: EventHandler) -> Box<dyn Fn() + Send + Sync>;
 
+    /// Subscribe to events of a specific type, hearing about missed events
+    ///
+    /// A subscriber that falls behind a burst skips the oldest events and
+    /// keeps receiving; `on_lagged` lets it catch up some other way.
+    fn subscribe_with_lag(
+        &self,
+        event_type: &str,
+        handler: EventHandler,
+        on_lagged: LagHandler,
+    ) -> Box<dyn Fn() + Send + Sync>;
+
     /// Subscribe to all events
     fn subscribe_all(&self, handler: EventHandler) -> Box<dyn Fn() + Send + Sync>;
 }
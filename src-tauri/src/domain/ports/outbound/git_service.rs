@@ -65,6 +65,12 @@ pub trait GitService: Send + Sync {
     /// Stage files for commit
     async fn stage(&self, path: &str, files: Option<Vec<String>>) -> DomainResult<GitOperationResult>;
 
+    /// Record a renamed file in the index, if the old path is tracked
+    ///
+    /// `from` and `to` are relative to the repository root. Staging both sides
+    /// lets git show the change as a rename rather than a delete and an add.
+    async fn stage_rename(&self, path: &str, from: &str, to: &str) -> DomainResult<GitOperationResult>;
+
     /// Create a commit
     async fn commit(&self, path: &str, message: &str) -> DomainResult<GitOperationResult>;
 
@@ -1,5 +1,8 @@
 // Repository ports
 pub mod attachment_repository;
+pub mod embedding_repository;
+pub mod note_block_repository;
+pub mod note_chunk_repository;
 pub mod note_link_repository;
 pub mod note_repository;
 pub mod notebook_repository;
@@ -10,7 +13,9 @@ pub mod version_repository;
 pub mod workspace_repository;
 
 // Service ports
+pub mod chat_service;
 pub mod database_service;
+pub mod embedding_provider;
 pub mod embedding_service;
 pub mod event_publisher;
 pub mod export_service;
@@ -23,13 +28,18 @@ pub mod system_service;
 
 // Repository exports
 pub use attachment_repository::AttachmentRepository;
+pub use embedding_repository::{
+    EmbeddingRepository, EmbeddingSummary, EMBEDDING_QUANTIZATION_SETTING,
+};
+pub use note_block_repository::NoteBlockRepository;
+pub use note_chunk_repository::NoteChunkRepository;
 pub use note_link_repository::NoteLinkRepository;
 pub use note_repository::{NoteFindOptions, NoteRepository};
 pub use notebook_repository::{
     NotebookFindOptions, NotebookPositionUpdate, NotebookRepository, NotebookWithCount,
 };
 pub use settings_repository::{Setting, SettingsRepository};
-pub use tag_repository::{TagRepository, TagWithCount};
+pub use tag_repository::{TagMove, TagRepository, TagWithCount};
 pub use topic_repository::{
     FindAllWithCountsOptions, GetNotesForTopicOptions, NoteTopicAssignment,
     NoteTopicWithDetails, TopicNoteRecord, TopicAssignmentOptions, TopicRepository, TopicWithCount,
@@ -38,11 +48,23 @@ pub use version_repository::{VersionListItem, VersionRepository};
 pub use workspace_repository::WorkspaceRepository;
 
 // Service exports
+pub use chat_service::{
+    ChatDeltaHandler, ChatMessage, ChatModelConfig, ChatRole, ChatService, CHAT_MODEL_SETTING,
+    DEFAULT_CHAT_BASE_URL, DEFAULT_CHAT_MODEL,
+};
 pub use database_service::DatabaseService;
+pub use embedding_provider::{
+    EmbeddingModelInfo, EmbeddingModelOption, EmbeddingProvider, EmbeddingProviderConfig,
+    DEFAULT_FASTEMBED_MODEL, EMBEDDING_PROVIDER_SETTING,
+};
 pub use embedding_service::{
-    ClassificationResult, EmbeddingResult, EmbeddingService, EmbeddingStatus, SimilarNote,
+    ClassificationResult, EmbeddingQueueState, EmbeddingQueueStatus, EmbeddingResult,
+    EmbeddingSearchFilter, EmbeddingService, EmbeddingStatus, NotePassage, SimilarNote,
+};
+pub use event_publisher::{
+    DomainEvent, EventHandler, EventPublisher, EventTopicClassification, FileSyncOperation,
+    LagHandler,
 };
-pub use event_publisher::{DomainEvent, EventHandler, EventPublisher, EventTopicClassification, FileSyncOperation};
 pub use export_service::{ExportService, HtmlOptions, HtmlTheme, PdfFormat, PdfMargin, PdfOptions};
 pub use file_storage::{FileInfo, FileStorage, FileWatchEvent};
 pub use file_watcher::FileWatcher;
@@ -51,9 +73,9 @@ pub use git_service::{
 };
 pub use markdown_processor::{MarkdownLink, MarkdownMetadata, MarkdownProcessor, ParsedMarkdown};
 pub use search_engine::{
-    DateRangeField, DateRangeOptions, HybridSearchOptions, SearchEngine, SearchHighlights,
-    SearchMatchType, SearchOptions, SearchResult, SearchWeights, SemanticSearchResult,
-    TagSearchOptions,
+    DateRangeField, DateRangeOptions, FusionMethod, HybridSearchOptions, SearchEngine,
+    SearchHighlights, SearchMatchType, SearchOptions, SearchResult, SearchRetriever,
+    SearchWeights, SemanticSearchResult, TagSearchOptions,
 };
 pub use system_service::{
     FileFilter, FilePickerOptions, FilePickerResult, FolderPickerOptions, SystemService,
@@ -0,0 +1,20 @@
+use crate::domain::{entities::NoteBlock, errors::DomainResult};
+use async_trait::async_trait;
+
+/// NoteBlock Repository Port (Outbound)
+///
+/// Defines the contract for persisting the `^block-id` blocks of notes.
+#[async_trait]
+pub trait NoteBlockRepository: Send + Sync {
+    /// Get all blocks of a note, in document order
+    async fn find_by_note(&self, note_id: &str) -> DomainResult<Vec<NoteBlock>>;
+
+    /// Get a single block
+    async fn find_by_key(&self, note_id: &str, block_id: &str) -> DomainResult<Option<NoteBlock>>;
+
+    /// Replace all blocks of a note
+    async fn replace_for_note(&self, note_id: &str, blocks: Vec<NoteBlock>) -> DomainResult<()>;
+
+    /// Remove all blocks of a note
+    async fn delete_for_note(&self, note_id: &str) -> DomainResult<()>;
+}
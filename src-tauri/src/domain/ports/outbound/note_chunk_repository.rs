@@ -0,0 +1,22 @@
+use crate::domain::{entities::NoteChunk, errors::DomainResult};
+use async_trait::async_trait;
+
+/// NoteChunk Repository Port (Outbound)
+///
+/// Defines the contract for persisting note passages. Their embeddings are
+/// stored separately (see `EmbeddingRepository`).
+#[async_trait]
+pub trait NoteChunkRepository: Send + Sync {
+    /// Get all chunks of a note, in document order
+    async fn find_by_note(&self, note_id: &str) -> DomainResult<Vec<NoteChunk>>;
+
+    /// Get a single chunk
+    async fn find_by_key(&self, note_id: &str, chunk_index: i32)
+        -> DomainResult<Option<NoteChunk>>;
+
+    /// Replace all chunks of a note
+    async fn replace_for_note(&self, note_id: &str, chunks: Vec<NoteChunk>) -> DomainResult<()>;
+
+    /// Remove all chunks of a note
+    async fn delete_for_note(&self, note_id: &str) -> DomainResult<()>;
+}
@@ -90,7 +90,7 @@ pub trait NoteRepository: Send + Sync {
     /// Find deleted notes (trash)
     async fn find_deleted(&self, workspace_id: Option<&str>) -> DomainResult<Vec<Note>>;
 
-    /// Search notes by title
+    /// Search notes by title or alias
     async fn search_by_title(
         &self,
         query: &str,
@@ -8,6 +8,16 @@ pub enum SearchMatchType {
     Title,
     Content,
     Both,
+    /// Matched by embedding similarity only
+    Semantic,
+}
+
+/// Retriever that contributed a search hit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchRetriever {
+    Fts,
+    Semantic,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +34,9 @@ pub struct SearchResult {
     pub relevance: f32,
     pub match_type: SearchMatchType,
     pub highlights: Option<SearchHighlights>,
+    /// Retrievers that matched this note (hybrid search may list both)
+    #[serde(default)]
+    pub retrievers: Vec<SearchRetriever>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,9 +54,23 @@ pub struct SearchOptions {
     pub limit: Option<i32>,
     pub offset: Option<i32>,
     pub notebook_id: Option<String>,
+    /// Tags the note must carry (all of them), directly or through a nested tag
     pub tag_ids: Option<Vec<String>>,
     pub workspace_id: Option<String>,
     pub exclude_deleted: Option<bool>,
+    /// Tag names the note must carry (all of them), directly or through a nested tag
+    pub tag_names: Option<Vec<String>>,
+    /// Notebook name (case-insensitive)
+    pub notebook_name: Option<String>,
+    pub is_pinned: Option<bool>,
+    pub is_favorite: Option<bool>,
+    pub is_archived: Option<bool>,
+    /// Phrases that must appear verbatim
+    pub phrases: Option<Vec<String>>,
+    /// Terms or phrases that must not appear
+    pub excluded_terms: Option<Vec<String>>,
+    /// Date constraints; every range must hold
+    pub date_ranges: Option<Vec<DateRangeOptions>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -61,6 +88,8 @@ pub struct DateRangeOptions {
     pub workspace_id: Option<String>,
     pub field: Option<DateRangeField>,
     pub limit: Option<i32>,
+    #[serde(default)]
+    pub offset: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,12 +99,33 @@ pub struct SearchWeights {
     pub semantic: f32,
 }
 
+impl Default for SearchWeights {
+    fn default() -> Self {
+        Self {
+            fts: 0.5,
+            semantic: 0.5,
+        }
+    }
+}
+
+/// How hybrid search combines the FTS and semantic rankings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FusionMethod {
+    /// Reciprocal rank fusion: sum of weight / (k + rank)
+    #[default]
+    Rrf,
+    /// Weighted sum of retriever scores, each scaled by its list's best score
+    Weighted,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HybridSearchOptions {
     #[serde(flatten)]
     pub base: SearchOptions,
     pub weights: Option<SearchWeights>,
+    pub fusion: Option<FusionMethod>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,6 +134,8 @@ pub struct TagSearchOptions {
     #[serde(flatten)]
     pub base: SearchOptions,
     pub match_all: Option<bool>,
+    /// Let tags nested under a requested tag stand in for it
+    pub include_descendants: Option<bool>,
 }
 
 /// Search Engine Port (Outbound)
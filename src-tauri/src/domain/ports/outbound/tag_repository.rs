@@ -1,7 +1,10 @@
-use crate::domain::{entities::Tag, errors::DomainResult};
+use crate::domain::{
+    entities::{NoteTag, Tag, TagSource},
+    errors::DomainResult,
+};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -13,6 +16,51 @@ pub struct TagWithCount {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub note_count: i32,
+    /// Notes carrying this tag or any tag nested under it, each counted once
+    #[serde(default)]
+    pub total_note_count: i32,
+    /// Tags nested directly under this one
+    #[serde(default)]
+    pub children: Vec<TagWithCount>,
+}
+
+impl TagWithCount {
+    /// Nest a flat list of tags under their parents, keeping the list order
+    ///
+    /// Tags whose parent is missing stay at the top level.
+    pub fn into_tree(tags: Vec<TagWithCount>) -> Vec<TagWithCount> {
+        let names: HashSet<String> = tags.iter().map(|tag| tag.name.clone()).collect();
+        let mut children: HashMap<String, Vec<TagWithCount>> = HashMap::new();
+        let mut roots = Vec::new();
+        for tag in tags {
+            match Tag::parent_name(&tag.name).filter(|parent| names.contains(*parent)) {
+                Some(parent) => children.entry(parent.to_string()).or_default().push(tag),
+                None => roots.push(tag),
+            }
+        }
+
+        fn attach(tag: &mut TagWithCount, children: &mut HashMap<String, Vec<TagWithCount>>) {
+            tag.children = children.remove(&tag.name).unwrap_or_default();
+            for child in &mut tag.children {
+                attach(child, children);
+            }
+        }
+        for root in &mut roots {
+            attach(root, &mut children);
+        }
+        roots
+    }
+}
+
+/// A change applied by `TagRepository::move_tags`
+#[derive(Debug, Clone)]
+pub enum TagMove {
+    /// Save a tag under its new name, keeping its ID and links
+    Rename(Tag),
+    /// Move the links of tag `from_id` onto tag `into_id`, then delete `from_id`
+    ///
+    /// A note keeps a manual link if either of its links was manual.
+    Merge { from_id: String, into_id: String },
 }
 
 /// Tag Repository Port (Outbound)
@@ -29,7 +77,7 @@ pub trait TagRepository: Send + Sync {
     /// Find all tags
     async fn find_all(&self) -> DomainResult<Vec<Tag>>;
 
-    /// Find all tags with note counts
+    /// Find all tags with note counts, nested under their parent tags
     async fn find_all_with_counts(&self) -> DomainResult<Vec<TagWithCount>>;
 
     /// Find tags by note ID
@@ -47,14 +95,28 @@ pub trait TagRepository: Send + Sync {
     // Note-Tag associations
 
     /// Add a tag to a note
+    ///
+    /// Marks the link manual, including a link that came from the note's content.
     async fn add_tag_to_note(&self, note_id: &str, tag_id: &str) -> DomainResult<()>;
 
+    /// Link a tag to a note, or change where an existing link came from
+    async fn link_tag(&self, note_id: &str, tag_id: &str, source: TagSource) -> DomainResult<()>;
+
     /// Remove a tag from a note
     async fn remove_tag_from_note(&self, note_id: &str, tag_id: &str) -> DomainResult<()>;
 
     /// Get all tags for a note
     async fn get_note_tags(&self, note_id: &str) -> DomainResult<Vec<Tag>>;
 
+    /// Get all tags for a note, each with where its link came from
+    async fn find_note_tags_with_source(&self, note_id: &str) -> DomainResult<Vec<NoteTag>>;
+
+    /// IDs of the notes carrying a tag
+    async fn find_note_ids(&self, tag_id: &str) -> DomainResult<Vec<String>>;
+
+    /// Apply renames and merges in order, all of them or none
+    async fn move_tags(&self, moves: Vec<TagMove>) -> DomainResult<()>;
+
     /// Set tags for a note (replaces all existing tags)
     async fn set_note_tags(&self, note_id: &str, tag_ids: Vec<String>) -> DomainResult<()>;
 
@@ -0,0 +1,136 @@
+/// AnswerPrompt - Pure domain service for answering questions from notes
+///
+/// Builds the instructions and numbered passages sent to a chat model, and
+/// finds the `[n]` markers the model cites those passages with.
+use regex::Regex;
+
+use super::note_chunker::TextChunk;
+
+/// Instructions sent ahead of every question
+pub const ANSWER_SYSTEM_PROMPT: &str = "You answer questions using only the numbered note \
+passages you are given. Cite the passages that support each statement with their number in \
+square brackets, like [1] or [2, 3]. If the passages don't contain the answer, say so instead \
+of guessing. Keep the answer short.";
+
+/// Words that carry no meaning for keyword retrieval
+const STOP_WORDS: &[&str] = &[
+    "a", "about", "an", "and", "are", "as", "at", "be", "but", "by", "can", "could", "did", "do",
+    "does", "for", "from", "had", "has", "have", "how", "i", "in", "is", "it", "its", "me", "my",
+    "of", "on", "or", "our", "should", "so", "that", "the", "their", "them", "there", "these",
+    "they", "this", "to", "us", "was", "we", "were", "what", "when", "where", "which", "who",
+    "why", "will", "with", "would", "you", "your",
+];
+
+/// A passage offered to the model as context
+#[derive(Debug, Clone)]
+pub struct ContextPassage {
+    pub title: String,
+    /// Headings enclosing the passage, outermost first
+    pub heading_path: Vec<String>,
+    pub text: String,
+}
+
+/// A citation of one passage in an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CitationMarker {
+    /// 1-based number of the cited passage
+    pub source: usize,
+    pub start: usize, // Byte offset of the marker in the answer
+    pub end: usize,   // Byte offset of the marker in the answer (exclusive)
+}
+
+/// AnswerPrompt - Pure functions for grounded answers
+pub struct AnswerPrompt;
+
+impl AnswerPrompt {
+    /// The user message: numbered passages followed by the question
+    pub fn user_prompt(question: &str, passages: &[ContextPassage]) -> String {
+        let mut prompt = String::from("Note passages:\n\n");
+
+        for (index, passage) in passages.iter().enumerate() {
+            // Notes usually open with their title as a heading
+            let headings = match passage.heading_path.split_first() {
+                Some((first, rest)) if first.trim().eq_ignore_ascii_case(passage.title.trim()) => {
+                    rest
+                }
+                _ => &passage.heading_path[..],
+            };
+            let location = std::iter::once(passage.title.as_str())
+                .chain(headings.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" > ");
+            prompt.push_str(&format!(
+                "[{}] {}\n{}\n\n",
+                index + 1,
+                location,
+                passage.text.trim()
+            ));
+        }
+
+        prompt.push_str(&format!("Question: {}", question.trim()));
+        prompt
+    }
+
+    /// Keywords of a question, for full-text retrieval
+    ///
+    /// Questions are phrased in full sentences, and requiring every word of
+    /// one would rule out most notes.
+    pub fn search_terms(question: &str) -> Vec<String> {
+        let mut terms: Vec<String> = Vec::new();
+        for word in question.split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_') {
+            let word = word.trim_matches(['-', '_']).to_lowercase();
+            if word.chars().count() < 2 || STOP_WORDS.contains(&word.as_str()) {
+                continue;
+            }
+            if !terms.contains(&word) {
+                terms.push(word);
+            }
+        }
+        terms
+    }
+
+    /// The chunk mentioning the most search terms, the earliest on a tie
+    pub fn best_passage<'a>(chunks: &'a [TextChunk], terms: &[String]) -> Option<&'a TextChunk> {
+        let mut best: Option<(&TextChunk, usize)> = None;
+        for chunk in chunks {
+            let text = chunk.text.to_lowercase();
+            let hits = terms
+                .iter()
+                .filter(|term| text.contains(term.as_str()))
+                .count();
+            if best.is_none_or(|(_, most)| hits > most) {
+                best = Some((chunk, hits));
+            }
+        }
+        best.map(|(chunk, _)| chunk)
+    }
+
+    /// Find the passages an answer cites, in order of appearance
+    ///
+    /// A group like `[1, 3]` yields one marker per number, all spanning the
+    /// group. Numbers without a matching passage are ignored.
+    pub fn find_citations(answer: &str, passage_count: usize) -> Vec<CitationMarker> {
+        let pattern = Regex::new(r"\[(\d+(?:\s*,\s*\d+)*)\]").unwrap();
+
+        let mut markers = Vec::new();
+        for caps in pattern.captures_iter(answer) {
+            let (Some(group), Some(numbers)) = (caps.get(0), caps.get(1)) else {
+                continue;
+            };
+            for number in numbers.as_str().split(',') {
+                let Ok(source) = number.trim().parse::<usize>() else {
+                    continue;
+                };
+                if (1..=passage_count).contains(&source) {
+                    markers.push(CitationMarker {
+                        source,
+                        start: group.start(),
+                        end: group.end(),
+                    });
+                }
+            }
+        }
+
+        markers
+    }
+}
@@ -0,0 +1,311 @@
+/// DuplicateDetector - Pure domain service for finding near-duplicate notes
+///
+/// Notes are compared by their word shingles (overlapping runs of words) and,
+/// when both have one, by embedding similarity. MinHash signatures bucketed by
+/// locality-sensitive hashing pick the pairs worth comparing, so large
+/// workspaces don't need every pair of notes scored.
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::similarity_calculator::cosine_similarity;
+
+/// Number of MinHash functions per signature
+const NUM_HASHES: usize = 128;
+/// Signature rows per LSH band; notes sharing any band are compared
+const ROWS_PER_BAND: usize = 4;
+
+/// Duplicate detection tuning
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateConfig {
+    /// Minimum combined score for two notes to count as duplicates
+    pub min_score: f32,
+    /// Words per shingle
+    pub shingle_size: usize,
+    /// Share of the score taken by text overlap when embeddings are available
+    pub text_weight: f32,
+}
+
+impl Default for DuplicateConfig {
+    fn default() -> Self {
+        Self {
+            min_score: 0.75,
+            shingle_size: 3,
+            text_weight: 0.5,
+        }
+    }
+}
+
+/// A note to compare
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateDocument {
+    pub text: String,
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// Two notes that look like duplicates
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicatePair {
+    /// Indices into the compared documents (`a < b`)
+    pub a: usize,
+    pub b: usize,
+    /// Jaccard similarity of the word shingles
+    pub text_similarity: f32,
+    pub embedding_similarity: Option<f32>,
+    pub score: f32,
+}
+
+/// Notes connected by duplicate pairs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    /// Indices into the compared documents, ascending
+    pub members: Vec<usize>,
+    pub pairs: Vec<DuplicatePair>,
+    /// Mean score of the pairs
+    pub score: f32,
+}
+
+/// MinHash signatures over hashed shingles
+pub struct MinHasher {
+    seeds: Vec<u64>,
+}
+
+impl Default for MinHasher {
+    fn default() -> Self {
+        Self::new(NUM_HASHES)
+    }
+}
+
+impl MinHasher {
+    pub fn new(num_hashes: usize) -> Self {
+        let mut state = 0x5eed_u64;
+        let seeds = (0..num_hashes.max(1))
+            .map(|_| {
+                state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                mix(state)
+            })
+            .collect();
+        Self { seeds }
+    }
+
+    /// Minimum hash of the shingles under each hash function
+    pub fn signature(&self, shingles: &HashSet<u64>) -> Vec<u64> {
+        self.seeds
+            .iter()
+            .map(|seed| {
+                shingles
+                    .iter()
+                    .map(|shingle| mix(shingle ^ seed))
+                    .min()
+                    .unwrap_or(u64::MAX)
+            })
+            .collect()
+    }
+
+    /// Estimated Jaccard similarity: the share of matching signature slots
+    pub fn similarity(a: &[u64], b: &[u64]) -> f32 {
+        if a.is_empty() || a.len() != b.len() {
+            return 0.0;
+        }
+        let matching = a.iter().zip(b).filter(|(x, y)| x == y).count();
+        matching as f32 / a.len() as f32
+    }
+}
+
+/// DuplicateDetector - Groups near-identical notes
+pub struct DuplicateDetector {
+    config: DuplicateConfig,
+    hasher: MinHasher,
+}
+
+impl Default for DuplicateDetector {
+    fn default() -> Self {
+        Self::new(DuplicateConfig::default())
+    }
+}
+
+impl DuplicateDetector {
+    pub fn new(config: DuplicateConfig) -> Self {
+        Self {
+            config,
+            hasher: MinHasher::default(),
+        }
+    }
+
+    pub fn config(&self) -> &DuplicateConfig {
+        &self.config
+    }
+
+    /// Hashed word shingles of a text, case-insensitive
+    ///
+    /// Texts shorter than one shingle become a single shingle; texts without
+    /// words have none.
+    pub fn shingles(&self, text: &str) -> HashSet<u64> {
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        let size = self.config.shingle_size.max(1).min(words.len().max(1));
+
+        words
+            .windows(size)
+            .map(|window| fnv1a(window.join(" ").as_bytes()))
+            .collect()
+    }
+
+    /// Pairs of documents scoring at least `min_score`, best first
+    pub fn find_pairs(&self, documents: &[DuplicateDocument]) -> Vec<DuplicatePair> {
+        let shingles: Vec<HashSet<u64>> =
+            documents.iter().map(|d| self.shingles(&d.text)).collect();
+        let signatures: Vec<Vec<u64>> = shingles.iter().map(|s| self.hasher.signature(s)).collect();
+
+        let mut pairs: Vec<DuplicatePair> = self
+            .candidate_pairs(&shingles, &signatures)
+            .into_iter()
+            .filter_map(|(a, b)| {
+                let text_similarity = jaccard(&shingles[a], &shingles[b]);
+                let embedding_similarity = match (&documents[a].embedding, &documents[b].embedding)
+                {
+                    (Some(x), Some(y)) => cosine_similarity(x, y).ok(),
+                    _ => None,
+                };
+                let score = match embedding_similarity {
+                    Some(cosine) => {
+                        let weight = self.config.text_weight.clamp(0.0, 1.0);
+                        weight * text_similarity + (1.0 - weight) * cosine
+                    }
+                    None => text_similarity,
+                };
+
+                (score >= self.config.min_score).then_some(DuplicatePair {
+                    a,
+                    b,
+                    text_similarity,
+                    embedding_similarity,
+                    score,
+                })
+            })
+            .collect();
+
+        pairs.sort_by(|x, y| {
+            y.score
+                .partial_cmp(&x.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| (x.a, x.b).cmp(&(y.a, y.b)))
+        });
+        pairs
+    }
+
+    /// Groups of duplicates, highest scoring first
+    ///
+    /// Notes are grouped transitively: if A matches B and B matches C, all
+    /// three form one group even when A and C differ more.
+    pub fn find_groups(&self, documents: &[DuplicateDocument]) -> Vec<DuplicateGroup> {
+        let pairs = self.find_pairs(documents);
+
+        let mut parent: Vec<usize> = (0..documents.len()).collect();
+        for pair in &pairs {
+            let (root_a, root_b) = (
+                find_root(&mut parent, pair.a),
+                find_root(&mut parent, pair.b),
+            );
+            if root_a != root_b {
+                parent[root_a.max(root_b)] = root_a.min(root_b);
+            }
+        }
+
+        let mut groups: HashMap<usize, DuplicateGroup> = HashMap::new();
+        for pair in pairs {
+            let root = find_root(&mut parent, pair.a);
+            let group = groups.entry(root).or_insert_with(|| DuplicateGroup {
+                members: Vec::new(),
+                pairs: Vec::new(),
+                score: 0.0,
+            });
+            group.members.extend([pair.a, pair.b]);
+            group.pairs.push(pair);
+        }
+
+        let mut groups: Vec<DuplicateGroup> = groups
+            .into_values()
+            .map(|mut group| {
+                group.members.sort_unstable();
+                group.members.dedup();
+                group.score =
+                    group.pairs.iter().map(|p| p.score).sum::<f32>() / group.pairs.len() as f32;
+                group
+            })
+            .collect();
+
+        groups.sort_by(|x, y| {
+            y.score
+                .partial_cmp(&x.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| x.members.cmp(&y.members))
+        });
+        groups
+    }
+
+    /// Pairs sharing at least one LSH band of their signatures
+    fn candidate_pairs(
+        &self,
+        shingles: &[HashSet<u64>],
+        signatures: &[Vec<u64>],
+    ) -> Vec<(usize, usize)> {
+        let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+        for (document, signature) in signatures.iter().enumerate() {
+            if shingles[document].is_empty() {
+                continue; // Nothing to compare
+            }
+            for (band, rows) in signature.chunks(ROWS_PER_BAND).enumerate() {
+                let key = rows.iter().fold(band as u64, |hash, row| mix(hash ^ row));
+                buckets.entry((band, key)).or_default().push(document);
+            }
+        }
+
+        let mut candidates = HashSet::new();
+        for documents in buckets.values() {
+            for (i, a) in documents.iter().enumerate() {
+                for b in &documents[i + 1..] {
+                    candidates.insert((*a.min(b), *a.max(b)));
+                }
+            }
+        }
+
+        let mut candidates: Vec<(usize, usize)> = candidates.into_iter().collect();
+        candidates.sort_unstable();
+        candidates
+    }
+}
+
+fn find_root(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
+
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+/// SplitMix64 finalizer, used as a cheap, well-mixed hash of a u64
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// FNV-1a, stable across runs and platforms
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
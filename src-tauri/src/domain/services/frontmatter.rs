@@ -0,0 +1,1427 @@
+/// Frontmatter - Pure domain service for the YAML frontmatter of notes
+///
+/// Parses the `---` block at the top of a note and edits it in place. Setting
+/// a key rewrites only that key's lines, in the style they were written in,
+/// so other keys, comments and their order survive a round trip untouched.
+///
+/// Covers the YAML found in frontmatter: nested block mappings and lists,
+/// flow `[...]` and `{...}` collections, plain, quoted and block (`|`, `>`)
+/// scalars, and comments. Anchors, aliases and explicit tags are rejected.
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde_json::{Map, Value};
+
+use crate::domain::{
+    entities::{Note, Tag},
+    errors::{DomainError, DomainResult},
+};
+
+/// Frontmatter keys holding a note's tags
+pub const TAG_KEYS: [&str; 2] = ["tags", "tag"];
+
+/// Frontmatter keys holding a note's aliases
+pub const ALIAS_KEYS: [&str; 2] = ["aliases", "alias"];
+
+/// Keys the app reads itself, left out of a note's custom properties
+const RESERVED_KEYS: [&str; 8] = [
+    "title", "tags", "tag", "aliases", "alias", "created", "modified", "updated",
+];
+
+/// A YAML value
+#[derive(Debug, Clone, PartialEq)]
+pub enum YamlValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Sequence(Vec<YamlValue>),
+    /// Entries in the order they were written
+    Mapping(Vec<(String, YamlValue)>),
+}
+
+impl YamlValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            YamlValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// A scalar as the text it was written as; `None` for collections and null
+    pub fn as_text(&self) -> Option<String> {
+        match self {
+            YamlValue::Null | YamlValue::Sequence(_) | YamlValue::Mapping(_) => None,
+            YamlValue::Bool(b) => Some(b.to_string()),
+            YamlValue::Integer(i) => Some(i.to_string()),
+            YamlValue::Float(f) => Some(f.to_string()),
+            YamlValue::String(s) => Some(s.clone()),
+        }
+    }
+
+    /// Items of a list, or of a comma-separated string, trimmed
+    pub fn as_string_list(&self) -> Vec<String> {
+        let items: Vec<String> = match self {
+            YamlValue::Sequence(items) => items.iter().filter_map(YamlValue::as_text).collect(),
+            YamlValue::String(s) => s.split(',').map(str::to_string).collect(),
+            other => other.as_text().into_iter().collect(),
+        };
+        items
+            .into_iter()
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    }
+
+    pub fn to_json(&self) -> Value {
+        match self {
+            YamlValue::Null => Value::Null,
+            YamlValue::Bool(b) => Value::Bool(*b),
+            YamlValue::Integer(i) => Value::from(*i),
+            YamlValue::Float(f) => serde_json::Number::from_f64(*f)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+            YamlValue::String(s) => Value::String(s.clone()),
+            YamlValue::Sequence(items) => Value::Array(items.iter().map(Self::to_json).collect()),
+            YamlValue::Mapping(entries) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_json()))
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn from_json(value: &Value) -> Self {
+        match value {
+            Value::Null => YamlValue::Null,
+            Value::Bool(b) => YamlValue::Bool(*b),
+            Value::Number(n) => match n.as_i64() {
+                Some(i) => YamlValue::Integer(i),
+                None => YamlValue::Float(n.as_f64().unwrap_or_default()),
+            },
+            Value::String(s) => YamlValue::String(s.clone()),
+            Value::Array(items) => YamlValue::Sequence(items.iter().map(Self::from_json).collect()),
+            Value::Object(entries) => YamlValue::Mapping(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Self::from_json(value)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// A list of strings
+    pub fn string_list<S: AsRef<str>>(items: &[S]) -> Self {
+        YamlValue::Sequence(
+            items
+                .iter()
+                .map(|item| YamlValue::String(item.as_ref().to_string()))
+                .collect(),
+        )
+    }
+
+    fn is_scalar(&self) -> bool {
+        !matches!(self, YamlValue::Sequence(_) | YamlValue::Mapping(_))
+    }
+}
+
+/// A top-level key and the lines it spans
+#[derive(Debug, Clone)]
+struct Entry {
+    key: String,
+    value: YamlValue,
+    start: usize,
+    /// One past the entry's last line
+    end: usize,
+}
+
+/// Frontmatter - A parsed frontmatter block that remembers how it was written
+#[derive(Debug, Clone, Default)]
+pub struct Frontmatter {
+    lines: Vec<String>,
+    entries: Vec<Entry>,
+}
+
+impl Frontmatter {
+    /// Parse the YAML between the `---` fences
+    pub fn parse(yaml: &str) -> DomainResult<Self> {
+        let lines: Vec<String> = yaml.lines().map(str::to_string).collect();
+        let mut parser = Parser::new(lines.clone());
+        let entries = parser.parse_entries(0)?;
+        if parser.peek()?.is_some() {
+            return Err(parser.error("frontmatter must be a mapping of keys"));
+        }
+        Ok(Self { lines, entries })
+    }
+
+    /// Split a leading `---` block from the rest of the markdown
+    ///
+    /// Returns the YAML between the fences and the body after them. Lines
+    /// may end in `\n` or `\r\n`; without a closing fence it is all body.
+    pub fn split(markdown: &str) -> (Option<&str>, &str) {
+        let mut lines = markdown.split_inclusive('\n');
+        let Some(opening) = lines.next().filter(|line| line.trim_end() == "---") else {
+            return (None, markdown);
+        };
+
+        let mut offset = opening.len();
+        for line in lines {
+            if line.trim_end() == "---" {
+                let yaml = markdown[opening.len()..offset].trim_end_matches(['\r', '\n']);
+                return (Some(yaml), &markdown[offset + line.len()..]);
+            }
+            offset += line.len();
+        }
+        (None, markdown)
+    }
+
+    /// Byte offset where the body starts, just past the frontmatter (0 if none)
+    pub fn body_offset(markdown: &str) -> usize {
+        match Self::split(markdown) {
+            (Some(_), body) => markdown.len() - body.len(),
+            (None, _) => 0,
+        }
+    }
+
+    /// The frontmatter of a note, if it has one
+    pub fn from_markdown(markdown: &str) -> DomainResult<Option<Self>> {
+        match Self::split(markdown) {
+            (Some(yaml), _) => Self::parse(yaml).map(Some),
+            (None, _) => Ok(None),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&YamlValue> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| &entry.value)
+    }
+
+    /// The first of `keys` present, with its value
+    pub fn get_any<'a>(&self, keys: &[&'a str]) -> Option<(&'a str, &YamlValue)> {
+        keys.iter()
+            .find_map(|key| self.get(key).map(|value| (*key, value)))
+    }
+
+    /// Top-level keys and values, in order
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &YamlValue)> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.key.as_str(), &entry.value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Set a key, rewriting only its own lines
+    ///
+    /// An existing key keeps its position, its list style (flow or block),
+    /// its quoting and a trailing comment. A new key is appended.
+    pub fn set(&mut self, key: &str, value: YamlValue) {
+        match self.entries.iter().position(|entry| entry.key == key) {
+            Some(index) => {
+                if self.entries[index].value == value {
+                    return;
+                }
+                let (start, end) = (self.entries[index].start, self.entries[index].end);
+                let style = Style::of(&self.lines[start..end]);
+                let lines = render(&style.prefix, 0, &value, &style);
+                self.splice(index, start..end, lines);
+                self.entries[index].value = value;
+            }
+            None => {
+                let style = Style::new_key(key);
+                let lines = render(&style.prefix, 0, &value, &style);
+                let start = self.lines.len();
+                let end = start + lines.len();
+                self.lines.extend(lines);
+                self.entries.push(Entry {
+                    key: key.to_string(),
+                    value,
+                    start,
+                    end,
+                });
+            }
+        }
+    }
+
+    /// Remove a key and its lines; returns whether it was there
+    pub fn remove(&mut self, key: &str) -> bool {
+        let Some(index) = self.entries.iter().position(|entry| entry.key == key) else {
+            return false;
+        };
+        let (start, end) = (self.entries[index].start, self.entries[index].end);
+        self.splice(index, start..end, Vec::new());
+        self.entries.remove(index);
+        true
+    }
+
+    /// Rename the tag `from`, and the tags nested under it, in the tags list
+    ///
+    /// Renamed items are written normalized, keeping a leading `#`; an item
+    /// renamed onto a tag the list already has is dropped. Returns whether the
+    /// list changed.
+    pub fn rename_tags(&mut self, from: &str, to: &str) -> bool {
+        let Some((key, value)) = self.get_any(&TAG_KEYS) else {
+            return false;
+        };
+        let listed = value.as_string_list();
+        let normalized = |item: &str| Tag::normalize_name(item.trim_start_matches('#')).ok();
+
+        let mut tags: Vec<String> = Vec::new();
+        for item in &listed {
+            let renamed = normalized(item)
+                .and_then(|name| Tag::rebase_name(&name, from, to))
+                .map(|name| match item.starts_with('#') {
+                    true => format!("#{}", name),
+                    false => name,
+                });
+            let item = renamed.unwrap_or_else(|| item.clone());
+            if !tags
+                .iter()
+                .any(|tag| normalized(tag).is_some() && normalized(tag) == normalized(&item))
+            {
+                tags.push(item);
+            }
+        }
+        if tags == listed {
+            return false;
+        }
+
+        self.set(key, YamlValue::string_list(&tags));
+        true
+    }
+
+    /// The YAML, without fences
+    pub fn to_yaml(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// The markdown with this frontmatter in place of the one it has
+    ///
+    /// An empty frontmatter removes the block; a new one is followed by a
+    /// blank line.
+    pub fn write_to(&self, markdown: &str) -> String {
+        let (existing, body) = Self::split(markdown);
+        if self.lines.iter().all(|line| line.trim().is_empty()) {
+            return body.to_string();
+        }
+        let separator = match existing.is_none() && !body.is_empty() && !body.starts_with('\n') {
+            true => "\n",
+            false => "",
+        };
+        format!("---\n{}\n---\n{}{}", self.to_yaml(), separator, body)
+    }
+
+    /// Replace an entry's lines, shifting the entries after it
+    fn splice(&mut self, index: usize, range: std::ops::Range<usize>, lines: Vec<String>) {
+        let delta = lines.len() as isize - range.len() as isize;
+        let start = range.start;
+        self.lines.splice(range, lines);
+        self.entries[index].end = (self.entries[index].end as isize + delta) as usize;
+        self.entries[index].start = start;
+        for entry in &mut self.entries[index + 1..] {
+            entry.start = (entry.start as isize + delta) as usize;
+            entry.end = (entry.end as isize + delta) as usize;
+        }
+    }
+}
+
+/// NoteFrontmatter - What a note's frontmatter says about the note
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NoteFrontmatter {
+    /// `None` when there is no `tags` key, so tags are left as they are
+    pub tags: Option<Vec<String>>,
+    pub aliases: Vec<String>,
+    pub created: Option<DateTime<Utc>>,
+    /// Every other key
+    pub properties: Map<String, Value>,
+}
+
+impl NoteFrontmatter {
+    /// Read a note's frontmatter; frontmatter that isn't valid YAML is ignored
+    pub fn from_markdown(markdown: &str) -> Self {
+        match Frontmatter::from_markdown(markdown) {
+            Ok(Some(frontmatter)) => Self::from_frontmatter(&frontmatter),
+            _ => Self::default(),
+        }
+    }
+
+    /// Accepts lists and comma-separated strings for tags and aliases, under
+    /// `tags`/`tag` and `aliases`/`alias`. Tags lose a leading `#`.
+    pub fn from_frontmatter(frontmatter: &Frontmatter) -> Self {
+        let tags = frontmatter.get_any(&TAG_KEYS).map(|(_, value)| {
+            Self::dedup(
+                value
+                    .as_string_list()
+                    .into_iter()
+                    .map(|tag| tag.trim_start_matches('#').to_string())
+                    .collect(),
+            )
+        });
+        let aliases = frontmatter
+            .get_any(&ALIAS_KEYS)
+            .map(|(_, value)| Self::dedup(value.as_string_list()))
+            .unwrap_or_default();
+        let created = frontmatter
+            .get("created")
+            .and_then(YamlValue::as_text)
+            .and_then(|text| Self::parse_date(&text));
+        let properties = frontmatter
+            .entries()
+            .filter(|(key, _)| !RESERVED_KEYS.contains(key))
+            .map(|(key, value)| (key.to_string(), value.to_json()))
+            .collect();
+
+        Self {
+            tags,
+            aliases,
+            created,
+            properties,
+        }
+    }
+
+    /// Copy aliases, creation date and properties onto a note
+    ///
+    /// Returns the names of the fields that changed. Tags live in their own
+    /// table and are synced separately.
+    pub fn apply_to(&self, note: &mut Note) -> Vec<String> {
+        let mut changes = Vec::new();
+        if self.aliases != note.aliases {
+            note.set_aliases(self.aliases.clone());
+            changes.push("aliases".to_string());
+        }
+        if let Some(created) = self.created.filter(|created| *created != note.created_at) {
+            note.set_created_at(created);
+            changes.push("createdAt".to_string());
+        }
+        if self.properties != note.properties {
+            note.set_properties(self.properties.clone());
+            changes.push("properties".to_string());
+        }
+        changes
+    }
+
+    /// `2026-01-17T09:30:00Z`, `2026-01-17 09:30[:00]` or `2026-01-17`
+    pub fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+        let text = text.trim();
+        if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+            return Some(date.with_timezone(&Utc));
+        }
+        for format in [
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%d %H:%M",
+        ] {
+            if let Ok(date) = NaiveDateTime::parse_from_str(text, format) {
+                return Some(date.and_utc());
+            }
+        }
+        NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|date| date.and_utc())
+    }
+
+    fn dedup(values: Vec<String>) -> Vec<String> {
+        let mut unique: Vec<String> = Vec::new();
+        for value in values {
+            if !value.is_empty()
+                && !unique
+                    .iter()
+                    .any(|u| u.to_lowercase() == value.to_lowercase())
+            {
+                unique.push(value);
+            }
+        }
+        unique
+    }
+}
+
+/// How an entry was written, reused when it is rewritten
+struct Style {
+    /// The key as written, through its `:`
+    prefix: String,
+    block_list: bool,
+    item_indent: usize,
+    quote: Option<char>,
+    comment: Option<String>,
+}
+
+impl Style {
+    /// New keys are written in flow style with double-quoted strings
+    fn new_key(key: &str) -> Self {
+        Self {
+            prefix: format!("{}:", key_text(key)),
+            block_list: false,
+            item_indent: 2,
+            quote: Some('"'),
+            comment: None,
+        }
+    }
+
+    fn of(lines: &[String]) -> Self {
+        let first = &lines[0];
+        let rest = split_key(first).map(|(_, rest)| rest).unwrap_or("");
+        let prefix = first[..first.len() - rest.len()].to_string();
+        let (value, comment) = split_comment(rest);
+
+        let first_item = lines[1..]
+            .iter()
+            .find(|line| line.trim_start().starts_with('-'));
+        let block_list = value.trim().is_empty() && first_item.is_some();
+        let item_indent = first_item.map(|line| indent_of(line)).unwrap_or(2);
+
+        let quote = if block_list {
+            lines[1..].iter().find_map(|line| quote_style(line))
+        } else {
+            quote_style(value)
+        };
+
+        Self {
+            prefix,
+            block_list,
+            item_indent,
+            quote,
+            comment: comment.map(str::to_string),
+        }
+    }
+
+    fn nested(&self, indent: usize) -> Self {
+        Self {
+            prefix: String::new(),
+            block_list: true,
+            item_indent: indent,
+            quote: self.quote,
+            comment: None,
+        }
+    }
+}
+
+/// The quote that starts the first quoted item of a value, if any
+fn quote_style(text: &str) -> Option<char> {
+    let (text, _) = split_comment(text);
+    let mut boundary = true;
+    for c in text.chars() {
+        if boundary && (c == '"' || c == '\'') {
+            return Some(c);
+        }
+        if matches!(c, '[' | '{' | ',' | '-' | ':') {
+            boundary = true;
+        } else if !c.is_whitespace() {
+            boundary = false;
+        }
+    }
+    None
+}
+
+/// Lines for `prefix` followed by `value`, with nested lines indented past `indent`
+fn render(prefix: &str, indent: usize, value: &YamlValue, style: &Style) -> Vec<String> {
+    let comment = style
+        .comment
+        .as_ref()
+        .map(|comment| format!(" {}", comment))
+        .unwrap_or_default();
+
+    match value {
+        YamlValue::Sequence(items)
+            if !items.is_empty()
+                && (style.block_list || !items.iter().all(YamlValue::is_scalar)) =>
+        {
+            let item_indent = style.item_indent.max(indent);
+            let mut lines = vec![format!("{}{}", prefix, comment)];
+            for item in items {
+                lines.extend(render_item(item, item_indent, style));
+            }
+            lines
+        }
+        YamlValue::Mapping(entries) if !entries.is_empty() => {
+            let mut lines = vec![format!("{}{}", prefix, comment)];
+            let pad = " ".repeat(indent + 2);
+            for (key, value) in entries {
+                let nested = style.nested(indent + 2);
+                let key_prefix = format!("{}{}:", pad, key_text(key));
+                lines.extend(render(&key_prefix, indent + 2, value, &nested));
+            }
+            lines
+        }
+        YamlValue::String(s) if s.contains('\n') => {
+            let chomp = match s.len() - s.trim_end_matches('\n').len() {
+                0 => "-",
+                1 => "",
+                _ => "+",
+            };
+            let pad = " ".repeat(indent + 2);
+            let mut lines = vec![format!("{} |{}{}", prefix, chomp, comment)];
+            let body = match chomp {
+                "+" => s.strip_suffix('\n').unwrap_or(s),
+                _ => s.trim_end_matches('\n'),
+            };
+            lines.extend(body.split('\n').map(|line| match line {
+                "" => String::new(),
+                line => format!("{}{}", pad, line),
+            }));
+            lines
+        }
+        value => vec![format!(
+            "{} {}{}",
+            prefix,
+            inline(value, style.quote, false),
+            comment
+        )],
+    }
+}
+
+/// Lines for one `- item` of a block list
+fn render_item(item: &YamlValue, indent: usize, style: &Style) -> Vec<String> {
+    let pad = " ".repeat(indent);
+    match item {
+        YamlValue::Mapping(entries) if !entries.is_empty() => {
+            let inner = " ".repeat(indent + 2);
+            let mut lines = Vec::new();
+            for (key, value) in entries {
+                let nested = style.nested(indent + 2);
+                let key_prefix = format!("{}{}:", inner, key_text(key));
+                lines.extend(render(&key_prefix, indent + 2, value, &nested));
+            }
+            lines[0] = format!("{}- {}", pad, &lines[0][indent + 2..]);
+            lines
+        }
+        YamlValue::Sequence(items) if !items.is_empty() => {
+            let mut lines = vec![format!("{}-", pad)];
+            for item in items {
+                lines.extend(render_item(item, indent + 2, style));
+            }
+            lines
+        }
+        item => vec![format!("{}- {}", pad, inline(item, style.quote, false))],
+    }
+}
+
+/// A value on one line
+fn inline(value: &YamlValue, quote: Option<char>, in_flow: bool) -> String {
+    match value {
+        YamlValue::Null => "null".to_string(),
+        YamlValue::Bool(b) => b.to_string(),
+        YamlValue::Integer(i) => i.to_string(),
+        YamlValue::Float(f) if f.is_finite() && f.fract() == 0.0 => format!("{:.1}", f),
+        YamlValue::Float(f) => f.to_string(),
+        YamlValue::String(s) => match quote {
+            Some('"') => double_quoted(s),
+            Some('\'') if !s.contains('\n') => format!("'{}'", s.replace('\'', "''")),
+            _ if is_plain_safe(s, in_flow) => s.clone(),
+            _ => double_quoted(s),
+        },
+        YamlValue::Sequence(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(|item| inline(item, quote, true))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        YamlValue::Mapping(entries) => format!(
+            "{{{}}}",
+            entries
+                .iter()
+                .map(|(key, value)| format!("{}: {}", key_text(key), inline(value, quote, true)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn key_text(key: &str) -> String {
+    if is_plain_safe(key, true) && !key.contains(':') {
+        key.to_string()
+    } else {
+        double_quoted(key)
+    }
+}
+
+fn double_quoted(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Whether a string reads back as the same string without quotes
+fn is_plain_safe(s: &str, in_flow: bool) -> bool {
+    let Some(first) = s.chars().next() else {
+        return false;
+    };
+    !"?:,[]{}#&*!|>'\"%@`".contains(first)
+        && s != "-"
+        && !s.starts_with("- ")
+        && s.trim() == s
+        && !s.contains(['\n', '\r', '\t'])
+        && !s.contains(": ")
+        && !s.ends_with(':')
+        && !s.contains(" #")
+        && !(in_flow && s.contains([',', '[', ']', '{', '}']))
+        && matches!(resolve_plain(s), YamlValue::String(_))
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_blank(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+fn is_item(content: &str) -> bool {
+    content == "-" || content.starts_with("- ")
+}
+
+/// Split a trailing `# comment` off a line, ignoring `#` inside quotes
+fn split_comment(text: &str) -> (&str, Option<&str>) {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut previous = ' ';
+    for (index, c) in text.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '#' && previous.is_whitespace() => {
+                return (text[..index].trim_end(), Some(&text[index..]));
+            }
+            None if (c == '"' || c == '\'')
+                && (previous.is_whitespace() || "[{,:".contains(previous)) =>
+            {
+                quote = Some(c)
+            }
+            None => {}
+        }
+        previous = c;
+    }
+    (text, None)
+}
+
+/// The key of a `key: value` line and what follows its `:`
+fn split_key(content: &str) -> Option<(String, &str)> {
+    let content = content.trim_start();
+    if content.starts_with(['"', '\'']) {
+        let (key, end) = scan_quoted(content)?;
+        let rest = content[end..].trim_start_matches(' ');
+        let rest = rest.strip_prefix(':')?;
+        return (rest.is_empty() || rest.starts_with([' ', '\t'])).then_some((key, rest));
+    }
+    if content.starts_with(['[', '{', '#', '?', '&', '*', '!', '|', '>', '-']) {
+        return None;
+    }
+
+    let mut chars = content.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        if c != ':' {
+            continue;
+        }
+        let at_end = chars
+            .peek()
+            .is_none_or(|(_, next)| *next == ' ' || *next == '\t');
+        if at_end {
+            let key = content[..index].trim_end();
+            if key.is_empty() || key.contains(" #") {
+                return None;
+            }
+            return Some((key.to_string(), &content[index + 1..]));
+        }
+    }
+    None
+}
+
+/// Read a quoted scalar at the start of `text`
+///
+/// Returns the value and the byte offset after the closing quote, or `None`
+/// if the quote isn't closed. Line breaks fold into spaces, as in YAML.
+fn scan_quoted(text: &str) -> Option<(String, usize)> {
+    let quote = text.chars().next()?;
+    let mut value = String::new();
+    let mut chars = text.char_indices().skip(1).peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            c if c == quote => {
+                if quote == '\'' && chars.peek().map(|(_, next)| *next) == Some('\'') {
+                    chars.next();
+                    value.push('\'');
+                    continue;
+                }
+                return Some((value, index + 1));
+            }
+            '\\' if quote == '"' => {
+                let (_, escape) = chars.next()?;
+                match escape {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    'r' => value.push('\r'),
+                    '0' => value.push('\0'),
+                    'x' | 'u' | 'U' => {
+                        let digits = match escape {
+                            'x' => 2,
+                            'u' => 4,
+                            _ => 8,
+                        };
+                        let code: String = (0..digits)
+                            .filter_map(|_| chars.next())
+                            .map(|(_, c)| c)
+                            .collect();
+                        let c = u32::from_str_radix(&code, 16)
+                            .ok()
+                            .and_then(char::from_u32)?;
+                        value.push(c);
+                    }
+                    '\n' => {
+                        while chars
+                            .peek()
+                            .is_some_and(|(_, next)| *next == ' ' || *next == '\t')
+                        {
+                            chars.next();
+                        }
+                    }
+                    other => value.push(other),
+                }
+            }
+            '\n' => {
+                let trimmed = value.trim_end_matches([' ', '\t']).len();
+                value.truncate(trimmed);
+                let mut breaks = 0;
+                while let Some((_, next)) = chars.peek() {
+                    match next {
+                        ' ' | '\t' | '\r' => {}
+                        '\n' => breaks += 1,
+                        _ => break,
+                    }
+                    chars.next();
+                }
+                match breaks {
+                    0 => value.push(' '),
+                    n => value.extend(std::iter::repeat_n('\n', n)),
+                }
+            }
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// The value of an unquoted scalar
+fn resolve_plain(text: &str) -> YamlValue {
+    match text {
+        "" | "~" | "null" | "Null" | "NULL" => return YamlValue::Null,
+        "true" | "True" | "TRUE" => return YamlValue::Bool(true),
+        "false" | "False" | "FALSE" => return YamlValue::Bool(false),
+        _ => {}
+    }
+
+    let digits = text.strip_prefix(['-', '+']).unwrap_or(text);
+    if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+        if let Ok(i) = text.parse::<i64>() {
+            return YamlValue::Integer(i);
+        }
+    }
+    if digits.chars().any(|c| c.is_ascii_digit())
+        && digits.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && text
+            .chars()
+            .all(|c| c.is_ascii_digit() || "+-.eE".contains(c))
+    {
+        if let Ok(f) = text.parse::<f64>() {
+            return YamlValue::Float(f);
+        }
+    }
+    YamlValue::String(text.to_string())
+}
+
+/// Line-based parser for block YAML
+struct Parser {
+    lines: Vec<String>,
+    pos: usize,
+    /// Last line holding content, so entries don't claim the blank lines after them
+    last: usize,
+}
+
+impl Parser {
+    fn new(lines: Vec<String>) -> Self {
+        Self {
+            lines,
+            pos: 0,
+            last: 0,
+        }
+    }
+
+    fn error(&self, message: &str) -> DomainError {
+        DomainError::ValidationError(format!(
+            "Invalid frontmatter on line {}: {}",
+            self.pos + 1,
+            message
+        ))
+    }
+
+    /// Indentation and content of the next line that isn't blank or a comment
+    fn peek(&mut self) -> DomainResult<Option<(usize, String)>> {
+        while self.pos < self.lines.len() && is_blank(&self.lines[self.pos]) {
+            self.pos += 1;
+        }
+        let Some(line) = self.lines.get(self.pos) else {
+            return Ok(None);
+        };
+        let indent = indent_of(line);
+        let content = line[indent..].to_string();
+        if content.starts_with('\t') {
+            return Err(self.error("tabs can't indent YAML"));
+        }
+        Ok(Some((indent, content)))
+    }
+
+    fn consume(&mut self) {
+        self.last = self.pos;
+        self.pos += 1;
+    }
+
+    fn parse_entries(&mut self, indent: usize) -> DomainResult<Vec<Entry>> {
+        let mut entries: Vec<Entry> = Vec::new();
+        while let Some((line_indent, content)) = self.peek()? {
+            if line_indent < indent || (line_indent == indent && is_item(&content)) {
+                break;
+            }
+            if line_indent > indent {
+                return Err(self.error("unexpected indentation"));
+            }
+            let Some((key, rest)) = split_key(&content) else {
+                return Err(self.error("expected `key: value`"));
+            };
+            if entries.iter().any(|entry| entry.key == key) {
+                return Err(self.error(&format!("duplicate key `{}`", key)));
+            }
+
+            let start = self.pos;
+            let rest = rest.to_string();
+            self.consume();
+            let value = self.parse_value(&rest, indent, true)?;
+            entries.push(Entry {
+                key,
+                value,
+                start,
+                end: self.last + 1,
+            });
+        }
+        Ok(entries)
+    }
+
+    fn parse_mapping(&mut self, indent: usize) -> DomainResult<YamlValue> {
+        let entries = self.parse_entries(indent)?;
+        Ok(YamlValue::Mapping(
+            entries
+                .into_iter()
+                .map(|entry| (entry.key, entry.value))
+                .collect(),
+        ))
+    }
+
+    fn parse_sequence(&mut self, indent: usize) -> DomainResult<YamlValue> {
+        let mut items = Vec::new();
+        while let Some((line_indent, content)) = self.peek()? {
+            if line_indent != indent || !is_item(&content) {
+                if line_indent > indent {
+                    return Err(self.error("unexpected indentation"));
+                }
+                break;
+            }
+
+            let after = content[1..].trim_start_matches(' ');
+            let column = indent + content.len() - after.len();
+            let nested_item = is_item(after);
+            if nested_item || (!after.starts_with('#') && split_key(after).is_some()) {
+                // A collection starting on the item's own line: parse it as
+                // if it started on a line of its own at the same column
+                self.lines[self.pos] = format!("{}{}", " ".repeat(column), after);
+                items.push(match nested_item {
+                    true => self.parse_sequence(column)?,
+                    false => self.parse_mapping(column)?,
+                });
+            } else {
+                let after = after.to_string();
+                self.consume();
+                items.push(self.parse_value(&after, indent, false)?);
+            }
+        }
+        Ok(YamlValue::Sequence(items))
+    }
+
+    /// The value after a key's `:` or an item's `-`, and any lines it continues on
+    fn parse_value(
+        &mut self,
+        rest: &str,
+        indent: usize,
+        in_mapping: bool,
+    ) -> DomainResult<YamlValue> {
+        let text = rest.trim();
+        let (without_comment, _) = split_comment(text);
+        if without_comment.is_empty() {
+            return match self.peek()? {
+                Some((line_indent, content)) if line_indent > indent => match is_item(&content) {
+                    true => self.parse_sequence(line_indent),
+                    false => self.parse_mapping(line_indent),
+                },
+                Some((line_indent, content))
+                    if line_indent == indent && in_mapping && is_item(&content) =>
+                {
+                    self.parse_sequence(line_indent)
+                }
+                _ => Ok(YamlValue::Null),
+            };
+        }
+
+        match text.chars().next() {
+            Some('|') | Some('>') => self.parse_block_scalar(without_comment, indent),
+            Some('[') | Some('{') => self.parse_flow(text, indent),
+            Some('"') | Some('\'') => self.parse_quoted(text),
+            Some('&') | Some('*') | Some('!') => {
+                Err(self.error("anchors, aliases and tags are not supported"))
+            }
+            _ => Ok(self.parse_plain(without_comment, indent)),
+        }
+    }
+
+    /// A plain scalar, folding in more-indented continuation lines
+    fn parse_plain(&mut self, text: &str, indent: usize) -> YamlValue {
+        let mut value = text.to_string();
+        let mut continued = false;
+        while let Some(line) = self.lines.get(self.pos) {
+            if is_blank(line) || indent_of(line) <= indent {
+                break;
+            }
+            let (part, comment) = split_comment(line.trim());
+            let commented = comment.is_some();
+            value.push(' ');
+            value.push_str(part);
+            continued = true;
+            self.consume();
+            if commented {
+                break;
+            }
+        }
+        match continued {
+            true => YamlValue::String(value),
+            false => resolve_plain(text),
+        }
+    }
+
+    fn parse_quoted(&mut self, text: &str) -> DomainResult<YamlValue> {
+        let mut text = text.to_string();
+        loop {
+            if let Some((value, end)) = scan_quoted(&text) {
+                let (after, _) = split_comment(&text[end..]);
+                if !after.trim().is_empty() {
+                    return Err(self.error("unexpected text after a quoted string"));
+                }
+                return Ok(YamlValue::String(value));
+            }
+            let Some(line) = self.lines.get(self.pos).cloned() else {
+                return Err(self.error("unclosed quote"));
+            };
+            text.push('\n');
+            text.push_str(line.trim());
+            self.consume();
+        }
+    }
+
+    fn parse_flow(&mut self, text: &str, indent: usize) -> DomainResult<YamlValue> {
+        let mut joined = split_comment(text).0.to_string();
+        while flow_depth(&joined) > 0 {
+            let Some(line) = self.lines.get(self.pos).cloned() else {
+                return Err(self.error("unclosed flow collection"));
+            };
+            if !is_blank(&line)
+                && indent_of(&line) <= indent
+                && !line.trim_start().starts_with([']', '}'])
+            {
+                return Err(self.error("unclosed flow collection"));
+            }
+            joined.push(' ');
+            joined.push_str(split_comment(line.trim()).0);
+            self.consume();
+        }
+
+        let mut flow = Flow {
+            text: &joined,
+            pos: 0,
+        };
+        let value = flow.value().map_err(|message| self.error(message))?;
+        flow.skip_whitespace();
+        if flow.pos < joined.len() {
+            return Err(self.error("unexpected text after a flow collection"));
+        }
+        Ok(value)
+    }
+
+    fn parse_block_scalar(&mut self, header: &str, indent: usize) -> DomainResult<YamlValue> {
+        let literal = header.starts_with('|');
+        let chomp = header[1..].chars().find(|c| *c == '-' || *c == '+');
+        let explicit = header[1..]
+            .chars()
+            .find_map(|c| c.to_digit(10))
+            .map(|digit| indent + digit as usize);
+
+        let mut raw: Vec<&str> = Vec::new();
+        let start = self.pos;
+        while let Some(line) = self.lines.get(self.pos) {
+            if !line.trim().is_empty() && indent_of(line) <= indent {
+                break;
+            }
+            if !line.trim().is_empty() {
+                self.last = self.pos;
+            }
+            self.pos += 1;
+        }
+        raw.extend(self.lines[start..self.pos].iter().map(String::as_str));
+
+        let content_indent = explicit.unwrap_or_else(|| {
+            raw.iter()
+                .find(|line| !line.trim().is_empty())
+                .map(|line| indent_of(line))
+                .unwrap_or(indent + 2)
+        });
+        // Strip only the spaces of the indentation, so the cut always falls
+        // on a character boundary
+        let mut body: Vec<&str> = raw
+            .iter()
+            .map(|line| &line[indent_of(line).min(content_indent)..])
+            .collect();
+        let mut trailing = 0;
+        while body.last().is_some_and(|line| line.is_empty()) {
+            body.pop();
+            trailing += 1;
+        }
+
+        let mut text = String::new();
+        for (index, line) in body.iter().enumerate() {
+            if index > 0 {
+                let previous = body[index - 1];
+                let more_indented = |l: &str| l.starts_with([' ', '\t']);
+                if literal || line.is_empty() || more_indented(line) || more_indented(previous) {
+                    text.push('\n');
+                } else if !previous.is_empty() {
+                    text.push(' ');
+                }
+            }
+            text.push_str(line);
+        }
+
+        if !body.is_empty() {
+            match chomp {
+                Some('-') => {}
+                Some('+') => text.push_str(&"\n".repeat(trailing + 1)),
+                _ => text.push('\n'),
+            }
+        }
+        Ok(YamlValue::String(text))
+    }
+}
+
+/// How many flow collections are still open at the end of `text`
+fn flow_depth(text: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for c in text.chars() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+    depth
+}
+
+/// Character-level parser for flow collections
+struct Flow<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Flow<'_> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    /// Step past the next character, which may take more than one byte
+    fn advance(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.advance();
+        }
+    }
+
+    fn value(&mut self) -> Result<YamlValue, &'static str> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => self.sequence(),
+            Some('{') => self.mapping(),
+            Some('"') | Some('\'') => {
+                let (value, end) = scan_quoted(&self.text[self.pos..]).ok_or("unclosed quote")?;
+                self.pos += end;
+                Ok(YamlValue::String(value))
+            }
+            Some('&') | Some('*') | Some('!') => Err("anchors, aliases and tags are not supported"),
+            _ => Ok(self.plain()),
+        }
+    }
+
+    fn plain(&mut self) -> YamlValue {
+        let start = self.pos;
+        let mut chars = self.text[start..].char_indices().peekable();
+        let mut end = self.text.len();
+        while let Some((index, c)) = chars.next() {
+            let next = chars.peek().map(|(_, next)| *next);
+            let ends_key = c == ':' && next.is_none_or(|n| n.is_whitespace() || ",]}".contains(n));
+            if matches!(c, ',' | ']' | '}') || ends_key {
+                end = start + index;
+                break;
+            }
+        }
+        self.pos = end;
+        resolve_plain(self.text[start..end].trim())
+    }
+
+    fn sequence(&mut self) -> Result<YamlValue, &'static str> {
+        self.advance();
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(']') {
+                self.advance();
+                return Ok(YamlValue::Sequence(items));
+            }
+            let item = self.value()?;
+            self.skip_whitespace();
+            let item = if self.peek() == Some(':') {
+                // A single-pair mapping, as in `[a: 1]`
+                self.advance();
+                let key = item.as_text().ok_or("mapping keys must be scalars")?;
+                YamlValue::Mapping(vec![(key, self.value()?)])
+            } else {
+                item
+            };
+            items.push(item);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.advance(),
+                Some(']') => {}
+                _ => return Err("expected `,` or `]`"),
+            }
+        }
+    }
+
+    fn mapping(&mut self) -> Result<YamlValue, &'static str> {
+        self.advance();
+        let mut entries: Vec<(String, YamlValue)> = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.advance();
+                return Ok(YamlValue::Mapping(entries));
+            }
+            let key = self
+                .value()?
+                .as_text()
+                .ok_or("mapping keys must be scalars")?;
+            self.skip_whitespace();
+            let value = match self.peek() {
+                Some(':') => {
+                    self.advance();
+                    self.value()?
+                }
+                _ => YamlValue::Null,
+            };
+            entries.push((key, value));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.advance(),
+                Some('}') => {}
+                _ => return Err("expected `,` or `}`"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(yaml: &str, key: &str) -> YamlValue {
+        Frontmatter::parse(yaml).unwrap().get(key).unwrap().clone()
+    }
+
+    #[test]
+    fn test_flow_list_with_multibyte_whitespace() {
+        // A non-breaking space, as pasted from a web page, and an ideographic space
+        assert_eq!(
+            value("tags: [a,\u{a0}b, c\u{3000}]", "tags").as_string_list(),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(
+            value("project: {name:\u{a0}Stone,\u{2003}year: 2026}", "project"),
+            YamlValue::Mapping(vec![
+                ("name".to_string(), YamlValue::String("Stone".to_string())),
+                ("year".to_string(), YamlValue::Integer(2026)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_block_scalar_keeps_lines_indented_with_multibyte_spaces() {
+        let yaml = "summary: |\n    first\n  \u{3000}second\n";
+        assert_eq!(
+            value(yaml, "summary").as_str(),
+            Some("first\n\u{3000}second\n")
+        );
+    }
+
+    #[test]
+    fn test_flow_lists() {
+        assert_eq!(
+            value("tags: [a, \"b, c\", 'it''s', [1, 2], {k: v}]", "tags"),
+            YamlValue::Sequence(vec![
+                YamlValue::String("a".to_string()),
+                YamlValue::String("b, c".to_string()),
+                YamlValue::String("it's".to_string()),
+                YamlValue::Sequence(vec![YamlValue::Integer(1), YamlValue::Integer(2)]),
+                YamlValue::Mapping(vec![("k".to_string(), YamlValue::String("v".to_string()))]),
+            ])
+        );
+        assert_eq!(value("tags: []", "tags"), YamlValue::Sequence(Vec::new()));
+
+        let multi_line = "tags: [rust, # the language\n  web,\n  cli\n]\nnext: 1";
+        let frontmatter = Frontmatter::parse(multi_line).unwrap();
+        assert_eq!(
+            frontmatter.get("tags").unwrap().as_string_list(),
+            vec!["rust", "web", "cli"]
+        );
+        assert_eq!(frontmatter.get("next"), Some(&YamlValue::Integer(1)));
+    }
+
+    #[test]
+    fn test_quoted_and_escaped_scalars() {
+        assert_eq!(
+            value(r#"title: "tab\there \"quoted\" é\x21""#, "title").as_str(),
+            Some("tab\there \"quoted\" é!")
+        );
+        assert_eq!(
+            value("title: 'single ''quotes'' # kept'", "title").as_str(),
+            Some("single 'quotes' # kept")
+        );
+        assert_eq!(
+            value("title: \"folded\n  over\n\n  lines\"", "title").as_str(),
+            Some("folded over\nlines")
+        );
+        // Quoting keeps scalars that would otherwise resolve as other types
+        assert_eq!(value("version: \"1.0\"", "version").as_str(), Some("1.0"));
+        assert_eq!(value("version: 1.0", "version"), YamlValue::Float(1.0));
+        assert_eq!(value("draft: 'true'", "draft").as_str(), Some("true"));
+        assert_eq!(value("draft: true", "draft"), YamlValue::Bool(true));
+    }
+
+    #[test]
+    fn test_comments() {
+        let yaml = "# Written by hand\ntitle: Hello # trailing\nurl: https://example.com/#top\nquoted: \"a # b\" # c\n\n# Between keys\ntags:\n  - one # first\n  # skipped\n  - two";
+        let frontmatter = Frontmatter::parse(yaml).unwrap();
+        assert_eq!(frontmatter.get("title").unwrap().as_str(), Some("Hello"));
+        assert_eq!(
+            frontmatter.get("url").unwrap().as_str(),
+            Some("https://example.com/#top")
+        );
+        assert_eq!(frontmatter.get("quoted").unwrap().as_str(), Some("a # b"));
+        assert_eq!(
+            frontmatter.get("tags").unwrap().as_string_list(),
+            vec!["one", "two"]
+        );
+    }
+
+    #[test]
+    fn test_crlf_line_endings() {
+        let markdown = "---\r\ntitle: Notes\r\ntags:\r\n  - a\r\n  - b\r\nsummary: |\r\n  one\r\n  two\r\n---\r\nBody\r\n";
+        let frontmatter = Frontmatter::from_markdown(markdown).unwrap().unwrap();
+        assert_eq!(frontmatter.get("title").unwrap().as_str(), Some("Notes"));
+        assert_eq!(
+            frontmatter.get("tags").unwrap().as_string_list(),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            frontmatter.get("summary").unwrap().as_str(),
+            Some("one\ntwo\n")
+        );
+        assert_eq!(Frontmatter::split(markdown).1, "Body\r\n");
+    }
+
+    #[test]
+    fn test_non_ascii_keys_and_values() {
+        let yaml = "título: Café\ntags: [日本語, émigré]\naliases:\n  - Ünïcödé\n  - \"Straße\"";
+        let frontmatter = Frontmatter::parse(yaml).unwrap();
+        assert_eq!(frontmatter.get("título").unwrap().as_str(), Some("Café"));
+        assert_eq!(
+            frontmatter.get("tags").unwrap().as_string_list(),
+            vec!["日本語", "émigré"]
+        );
+        assert_eq!(
+            frontmatter.get("aliases").unwrap().as_string_list(),
+            vec!["Ünïcödé", "Straße"]
+        );
+    }
+
+    #[test]
+    fn test_set_rewrites_only_its_own_lines() {
+        let yaml = "# Written by hand\ntitle: Hello # keep me\ntags:\n  - 'one'\nend: true";
+        let mut frontmatter = Frontmatter::parse(yaml).unwrap();
+        frontmatter.set("tags", YamlValue::string_list(&["one", "two"]));
+        frontmatter.set("title", YamlValue::String("Bonjour".to_string()));
+        assert_eq!(
+            frontmatter.to_yaml(),
+            "# Written by hand\ntitle: Bonjour # keep me\ntags:\n  - 'one'\n  - 'two'\nend: true"
+        );
+    }
+
+    #[test]
+    fn test_invalid_yaml_is_an_error() {
+        for yaml in [
+            "title: \"unclosed",
+            "tags: [a, b",
+            "title: a\n\tnested: b",
+            "title: a\ntitle: b",
+            "title: *anchor",
+        ] {
+            assert!(Frontmatter::parse(yaml).is_err(), "{:?} parsed", yaml);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+/// HashtagExtractor - Pure domain service for extracting inline tags from markdown
+///
+/// Finds `#tag` and nested `#area/sub-tag` hashtags in a note's content. A
+/// hashtag starts a word, has at least one non-digit, and is made of letters,
+/// digits, `_`, `-` and `/`. Anything that isn't prose (see `NonProse`) is
+/// skipped, so `# Heading`, `` `#define` ``, `[[Note#Section]]` and
+/// `https://example.com/#top` are not tags.
+use regex::Regex;
+
+use super::non_prose::NonProse;
+use crate::domain::entities::Tag;
+
+/// A hashtag found in markdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hashtag {
+    /// The tag as written, without `#`
+    pub name: String,
+    /// Byte offset of the `#`
+    pub start_index: usize,
+    /// Byte offset just past the tag
+    pub end_index: usize,
+}
+
+pub struct HashtagExtractor;
+
+impl HashtagExtractor {
+    /// Names of the hashtags in markdown, without `#`, in order of first use
+    pub fn extract(markdown: &str) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for hashtag in Self::find(markdown) {
+            if !names.contains(&hashtag.name) {
+                names.push(hashtag.name);
+            }
+        }
+        names
+    }
+
+    /// Every hashtag occurrence in markdown, with its position
+    pub fn find(markdown: &str) -> Vec<Hashtag> {
+        let excluded = NonProse::ranges(markdown);
+        let pattern = Regex::new(r"#([\p{L}\p{N}_/-]+)").unwrap();
+
+        pattern
+            .captures_iter(markdown)
+            .filter_map(|caps| {
+                let whole = caps.get(0)?;
+                let start = whole.start();
+                if excluded.iter().any(|range| range.contains(&start)) {
+                    return None;
+                }
+                if !Self::starts_word(markdown, start) {
+                    return None;
+                }
+                let name = caps[1].trim_end_matches(['/', '-']);
+                if name.is_empty() || name.chars().all(|c| c.is_numeric() || c == '/') {
+                    return None;
+                }
+                Some(Hashtag {
+                    name: name.to_string(),
+                    start_index: start,
+                    end_index: start + 1 + name.len(),
+                })
+            })
+            .collect()
+    }
+
+    /// Rewrite hashtags after the tag `from` was renamed to `to`
+    ///
+    /// Hashtags naming `from` or a tag nested under it, compared normalized,
+    /// are written with the new name: `#Project/Alpha` becomes `#work/alpha`
+    /// when `project` is renamed to `work`. Returns None when no hashtag was
+    /// changed.
+    pub fn rename_tags(markdown: &str, from: &str, to: &str) -> Option<String> {
+        let mut rewritten = markdown.to_string();
+        let mut changed = false;
+
+        for hashtag in Self::find(markdown).into_iter().rev() {
+            let renamed = Tag::normalize_name(&hashtag.name)
+                .ok()
+                .and_then(|name| Tag::rebase_name(&name, from, to));
+            if let Some(name) = renamed {
+                if name != hashtag.name {
+                    rewritten.replace_range(
+                        hashtag.start_index..hashtag.end_index,
+                        &format!("#{}", name),
+                    );
+                    changed = true;
+                }
+            }
+        }
+
+        changed.then_some(rewritten)
+    }
+
+    /// `#` must begin a word: not `C#`, `&#39;` or `##`
+    fn starts_word(markdown: &str, start: usize) -> bool {
+        match markdown[..start].chars().next_back() {
+            None => true,
+            Some(c) => {
+                c.is_whitespace()
+                    || matches!(
+                        c,
+                        '(' | '[' | '{' | ',' | ';' | ':' | '"' | '\'' | '*' | '_' | '>'
+                    )
+            }
+        }
+    }
+}
@@ -0,0 +1,623 @@
+/// HnswIndex - Pure domain service for approximate nearest-neighbour search
+///
+/// Hierarchical Navigable Small World graph over note embeddings using cosine
+/// distance. Vectors are normalised on insert. Removals leave a tombstone so
+/// the graph stays navigable; `compacted` rebuilds without them.
+///
+/// The index serialises to a small little-endian binary format so adapters
+/// can persist it next to the database.
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+const FORMAT_MAGIC: &[u8; 8] = b"STNHNSW1";
+
+/// Tuning parameters for graph construction and search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HnswConfig {
+    /// Neighbours per node on upper layers (layer 0 keeps twice as many)
+    pub m: usize,
+    /// Candidate list size while inserting
+    pub ef_construction: usize,
+    /// Minimum candidate list size while searching
+    pub ef_search: usize,
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 100,
+            ef_search: 64,
+        }
+    }
+}
+
+/// A search hit: node ID and cosine similarity to the query
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorHit {
+    pub id: String,
+    pub similarity: f32,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    id: String,
+    vector: Vec<f32>,
+    /// Neighbour lists, one per layer from 0 up to the node's level
+    neighbors: Vec<Vec<u32>>,
+    deleted: bool,
+}
+
+/// Heap entry ordered by distance
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    distance: f32,
+    index: u32,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then_with(|| self.index.cmp(&other.index))
+    }
+}
+
+/// In-memory HNSW graph
+#[derive(Debug, Clone)]
+pub struct HnswIndex {
+    config: HnswConfig,
+    dimensions: usize,
+    nodes: Vec<Node>,
+    /// Live (non-deleted) node per ID
+    ids: HashMap<String, u32>,
+    entry_point: Option<u32>,
+    rng_state: u64,
+}
+
+impl HnswIndex {
+    /// Create an empty index; dimensions are fixed by the first insert
+    pub fn new(config: HnswConfig) -> Self {
+        Self {
+            config: HnswConfig {
+                m: config.m.max(2),
+                ef_construction: config.ef_construction.max(1),
+                ef_search: config.ef_search.max(1),
+            },
+            dimensions: 0,
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            entry_point: None,
+            rng_state: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    pub fn config(&self) -> HnswConfig {
+        self.config
+    }
+
+    /// Vector dimensions (0 while empty)
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Number of live vectors
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Number of tombstoned nodes still in the graph
+    pub fn deleted_count(&self) -> usize {
+        self.nodes.len() - self.ids.len()
+    }
+
+    /// Whether tombstones make up a large enough share to warrant `compacted`
+    pub fn needs_compaction(&self) -> bool {
+        self.deleted_count() > 0 && self.deleted_count() * 4 > self.nodes.len()
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.contains_key(id)
+    }
+
+    /// Normalised vector stored for an ID
+    pub fn get(&self, id: &str) -> Option<&[f32]> {
+        self.ids
+            .get(id)
+            .map(|&index| self.nodes[index as usize].vector.as_slice())
+    }
+
+    /// Insert or replace the vector for an ID
+    pub fn insert(&mut self, id: &str, vector: &[f32]) -> Result<(), String> {
+        if vector.is_empty() {
+            return Err("Cannot index an empty vector".to_string());
+        }
+        if self.dimensions == 0 {
+            self.dimensions = vector.len();
+        } else if vector.len() != self.dimensions {
+            return Err(format!(
+                "Vector has {} dimensions, index expects {}",
+                vector.len(),
+                self.dimensions
+            ));
+        }
+
+        self.remove(id);
+
+        let vector = Self::normalized(vector);
+        let level = self.random_level();
+        let new_index = self.nodes.len() as u32;
+        self.nodes.push(Node {
+            id: id.to_string(),
+            vector,
+            neighbors: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.ids.insert(id.to_string(), new_index);
+
+        let Some(entry) = self.entry_point else {
+            self.entry_point = Some(new_index);
+            return Ok(());
+        };
+
+        let query = self.nodes[new_index as usize].vector.clone();
+        let top_level = self.level_of(entry);
+
+        // Greedy descent through the layers above the new node
+        let mut entry_points = vec![entry];
+        for layer in (level + 1..=top_level).rev() {
+            let nearest = self.search_layer(&query, &entry_points, 1, layer);
+            entry_points = vec![nearest[0].index];
+        }
+
+        for layer in (0..=level.min(top_level)).rev() {
+            let candidates =
+                self.search_layer(&query, &entry_points, self.config.ef_construction, layer);
+            let max_neighbors = self.max_neighbors(layer);
+
+            let neighbors = self.select_neighbors(&candidates, max_neighbors);
+            self.nodes[new_index as usize].neighbors[layer] = neighbors.clone();
+
+            for neighbor in neighbors {
+                self.connect(neighbor, new_index, layer, max_neighbors);
+            }
+
+            entry_points = candidates.iter().map(|c| c.index).collect();
+        }
+
+        if level > top_level {
+            self.entry_point = Some(new_index);
+        }
+
+        Ok(())
+    }
+
+    /// Tombstone the vector for an ID; returns whether it was present
+    pub fn remove(&mut self, id: &str) -> bool {
+        match self.ids.remove(id) {
+            Some(index) => {
+                self.nodes[index as usize].deleted = true;
+                if self.ids.is_empty() {
+                    // Nothing left to navigate to
+                    self.nodes.clear();
+                    self.entry_point = None;
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Approximate k nearest neighbours, optionally restricted by ID
+    ///
+    /// When the filter rejects most candidates the search widens its
+    /// candidate list until `k` hits are found or the graph is exhausted.
+    pub fn search(
+        &self,
+        query: &[f32],
+        k: usize,
+        filter: Option<&dyn Fn(&str) -> bool>,
+    ) -> Vec<VectorHit> {
+        if k == 0 || query.len() != self.dimensions {
+            return Vec::new();
+        }
+        let Some(entry) = self.entry_point else {
+            return Vec::new();
+        };
+
+        let query = Self::normalized(query);
+        let mut entry_points = vec![entry];
+        for layer in (1..=self.level_of(entry)).rev() {
+            let nearest = self.search_layer(&query, &entry_points, 1, layer);
+            entry_points = vec![nearest[0].index];
+        }
+
+        let mut ef = self.config.ef_search.max(k);
+        loop {
+            let candidates = self.search_layer(&query, &entry_points, ef, 0);
+            let exhausted = candidates.len() < ef || ef >= self.nodes.len();
+            let hits: Vec<VectorHit> = candidates
+                .into_iter()
+                .filter(|c| self.is_match(c.index, filter))
+                .take(k)
+                .map(|c| self.hit(c))
+                .collect();
+
+            if hits.len() >= k || exhausted {
+                return hits;
+            }
+            ef = (ef * 2).min(self.nodes.len());
+        }
+    }
+
+    /// Exact k nearest neighbours by scanning every vector
+    pub fn exact_search(
+        &self,
+        query: &[f32],
+        k: usize,
+        filter: Option<&dyn Fn(&str) -> bool>,
+    ) -> Vec<VectorHit> {
+        if k == 0 || query.len() != self.dimensions {
+            return Vec::new();
+        }
+
+        let query = Self::normalized(query);
+        let mut candidates: Vec<Candidate> = self
+            .ids
+            .values()
+            .filter(|&&index| self.is_match(index, filter))
+            .map(|&index| Candidate {
+                distance: self.distance(&query, index),
+                index,
+            })
+            .collect();
+        candidates.sort();
+        candidates.truncate(k);
+
+        candidates.into_iter().map(|c| self.hit(c)).collect()
+    }
+
+    /// Rebuild the graph from live vectors, dropping tombstones
+    pub fn compacted(&self) -> Self {
+        let mut index = Self::new(self.config);
+        let mut live: Vec<&Node> = self.nodes.iter().filter(|n| !n.deleted).collect();
+        live.sort_by(|a, b| a.id.cmp(&b.id));
+        for node in live {
+            // Dimensions already match, so this cannot fail
+            let _ = index.insert(&node.id, &node.vector);
+        }
+        index
+    }
+
+    /// Serialise the index to bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(FORMAT_MAGIC);
+        for value in [
+            self.config.m,
+            self.config.ef_construction,
+            self.config.ef_search,
+            self.dimensions,
+            self.nodes.len(),
+        ] {
+            out.extend_from_slice(&(value as u32).to_le_bytes());
+        }
+        out.extend_from_slice(&self.entry_point.map_or(u32::MAX, |e| e).to_le_bytes());
+        out.extend_from_slice(&self.rng_state.to_le_bytes());
+
+        for node in &self.nodes {
+            out.extend_from_slice(&(node.id.len() as u32).to_le_bytes());
+            out.extend_from_slice(node.id.as_bytes());
+            out.push(node.deleted as u8);
+            for value in &node.vector {
+                out.extend_from_slice(&value.to_le_bytes());
+            }
+            out.extend_from_slice(&(node.neighbors.len() as u32).to_le_bytes());
+            for layer in &node.neighbors {
+                out.extend_from_slice(&(layer.len() as u32).to_le_bytes());
+                for neighbor in layer {
+                    out.extend_from_slice(&neighbor.to_le_bytes());
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Deserialise an index written by `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = ByteReader { bytes, offset: 0 };
+        if reader.take(FORMAT_MAGIC.len())? != FORMAT_MAGIC {
+            return Err("Not a vector index file".to_string());
+        }
+
+        let config = HnswConfig {
+            m: reader.u32()? as usize,
+            ef_construction: reader.u32()? as usize,
+            ef_search: reader.u32()? as usize,
+        };
+        let dimensions = reader.u32()? as usize;
+        let node_count = reader.u32()? as usize;
+        let entry_point = match reader.u32()? {
+            u32::MAX => None,
+            entry => Some(entry),
+        };
+        let rng_state = reader.u64()?;
+
+        let mut nodes = Vec::with_capacity(node_count.min(bytes.len()));
+        let mut ids = HashMap::new();
+        for index in 0..node_count {
+            let id_len = reader.u32()? as usize;
+            let id = String::from_utf8(reader.take(id_len)?.to_vec())
+                .map_err(|_| "Invalid node ID".to_string())?;
+            let deleted = reader.take(1)?[0] != 0;
+            let mut vector = Vec::with_capacity(dimensions);
+            for _ in 0..dimensions {
+                vector.push(reader.f32()?);
+            }
+            let layers = reader.u32()? as usize;
+            let mut neighbors = Vec::with_capacity(layers.min(64));
+            for _ in 0..layers {
+                let count = reader.u32()? as usize;
+                let mut layer = Vec::with_capacity(count.min(1024));
+                for _ in 0..count {
+                    let neighbor = reader.u32()?;
+                    if neighbor as usize >= node_count {
+                        return Err("Neighbour out of range".to_string());
+                    }
+                    layer.push(neighbor);
+                }
+                neighbors.push(layer);
+            }
+            if neighbors.is_empty() {
+                return Err("Node without layers".to_string());
+            }
+            if !deleted {
+                ids.insert(id.clone(), index as u32);
+            }
+            nodes.push(Node {
+                id,
+                vector,
+                neighbors,
+                deleted,
+            });
+        }
+
+        if reader.offset != bytes.len() {
+            return Err("Trailing data after vector index".to_string());
+        }
+        if entry_point.map_or(node_count > 0, |e| e as usize >= node_count) {
+            return Err("Invalid entry point".to_string());
+        }
+
+        Ok(Self {
+            config,
+            dimensions,
+            nodes,
+            ids,
+            entry_point,
+            rng_state,
+        })
+    }
+
+    /// Best-first search within one layer; returns up to `ef` nodes by distance
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry_points: &[u32],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited = vec![false; self.nodes.len()];
+        for &index in entry_points {
+            visited[index as usize] = true;
+        }
+        // Min-heap of nodes to expand, max-heap of the best `ef` found so far
+        let mut to_visit: BinaryHeap<std::cmp::Reverse<Candidate>> = BinaryHeap::new();
+        let mut best: BinaryHeap<Candidate> = BinaryHeap::new();
+
+        for &index in entry_points {
+            let candidate = Candidate {
+                distance: self.distance(query, index),
+                index,
+            };
+            to_visit.push(std::cmp::Reverse(candidate));
+            best.push(candidate);
+        }
+        while best.len() > ef {
+            best.pop();
+        }
+
+        while let Some(std::cmp::Reverse(current)) = to_visit.pop() {
+            let worst = best.peek().map_or(f32::INFINITY, |c| c.distance);
+            if current.distance > worst && best.len() >= ef {
+                break;
+            }
+
+            let node = &self.nodes[current.index as usize];
+            let Some(neighbors) = node.neighbors.get(layer) else {
+                continue;
+            };
+            for &neighbor in neighbors {
+                if std::mem::replace(&mut visited[neighbor as usize], true) {
+                    continue;
+                }
+                let candidate = Candidate {
+                    distance: self.distance(query, neighbor),
+                    index: neighbor,
+                };
+                let worst = best.peek().map_or(f32::INFINITY, |c| c.distance);
+                if best.len() < ef || candidate.distance < worst {
+                    to_visit.push(std::cmp::Reverse(candidate));
+                    best.push(candidate);
+                    if best.len() > ef {
+                        best.pop();
+                    }
+                }
+            }
+        }
+
+        best.into_sorted_vec()
+    }
+
+    /// Add an edge from `from` to `to`, pruning `from` back to `max_neighbors`
+    fn connect(&mut self, from: u32, to: u32, layer: usize, max_neighbors: usize) {
+        let neighbors = &mut self.nodes[from as usize].neighbors[layer];
+        if neighbors.contains(&to) {
+            return;
+        }
+        neighbors.push(to);
+        // Let lists overflow a little so pruning runs once per several inserts
+        if neighbors.len() <= max_neighbors + max_neighbors / 4 {
+            return;
+        }
+
+        let base = self.nodes[from as usize].vector.clone();
+        let mut ranked: Vec<Candidate> = self.nodes[from as usize].neighbors[layer]
+            .iter()
+            .map(|&index| Candidate {
+                distance: self.distance(&base, index),
+                index,
+            })
+            .collect();
+        ranked.sort();
+        self.nodes[from as usize].neighbors[layer] = self.select_neighbors(&ranked, max_neighbors);
+    }
+
+    /// Pick up to `max` neighbours from candidates sorted by distance
+    ///
+    /// A candidate is skipped when it is closer to an already selected
+    /// neighbour than to the base node, which keeps edges pointing in
+    /// different directions and the graph connected across clusters.
+    /// Skipped candidates fill any remaining slots.
+    fn select_neighbors(&self, candidates: &[Candidate], max: usize) -> Vec<u32> {
+        let mut selected: Vec<u32> = Vec::with_capacity(max);
+        let mut skipped: Vec<u32> = Vec::new();
+
+        for candidate in candidates {
+            if selected.len() >= max {
+                break;
+            }
+            let vector = &self.nodes[candidate.index as usize].vector;
+            let diverse = selected
+                .iter()
+                .all(|&chosen| self.distance(vector, chosen) > candidate.distance);
+            if diverse {
+                selected.push(candidate.index);
+            } else {
+                skipped.push(candidate.index);
+            }
+        }
+
+        for index in skipped {
+            if selected.len() >= max {
+                break;
+            }
+            selected.push(index);
+        }
+
+        selected
+    }
+
+    fn is_match(&self, index: u32, filter: Option<&dyn Fn(&str) -> bool>) -> bool {
+        let node = &self.nodes[index as usize];
+        !node.deleted && filter.is_none_or(|f| f(&node.id))
+    }
+
+    fn hit(&self, candidate: Candidate) -> VectorHit {
+        VectorHit {
+            id: self.nodes[candidate.index as usize].id.clone(),
+            similarity: 1.0 - candidate.distance,
+        }
+    }
+
+    fn level_of(&self, index: u32) -> usize {
+        self.nodes[index as usize].neighbors.len() - 1
+    }
+
+    fn max_neighbors(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.config.m * 2
+        } else {
+            self.config.m
+        }
+    }
+
+    /// Cosine distance between a normalised query and a stored vector
+    fn distance(&self, query: &[f32], index: u32) -> f32 {
+        let vector = &self.nodes[index as usize].vector;
+        let dot: f32 = query.iter().zip(vector).map(|(a, b)| a * b).sum();
+        1.0 - dot
+    }
+
+    /// Draw a level with probability decaying by 1/m per layer
+    fn random_level(&mut self) -> usize {
+        // SplitMix64: deterministic so rebuilt indexes are reproducible
+        self.rng_state = self.rng_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        let uniform = ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let level_multiplier = 1.0 / (self.config.m as f64).ln();
+        ((-uniform.ln() * level_multiplier) as usize).min(16)
+    }
+
+    fn normalized(vector: &[f32]) -> Vec<f32> {
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm == 0.0 {
+            return vector.to_vec();
+        }
+        vector.iter().map(|v| v / norm).collect()
+    }
+}
+
+/// Bounds-checked little-endian reader
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| "Truncated vector index".to_string())?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
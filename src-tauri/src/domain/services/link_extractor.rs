@@ -1,6 +1,11 @@
 /// LinkExtractor - Pure domain service for extracting links from markdown
 ///
-/// Handles wiki-style links [[Note Title]] and standard markdown links [text](url)
+/// Handles wiki-style links [[Note Title]] and standard markdown links [text](url).
+/// Links may point into a note: [[Note#Heading]] targets a section and
+/// [[Note#^block-id]] a block marked with `^block-id`. A leading `!` makes a
+/// wiki link an embed, which transcludes its target.
+use std::fmt;
+
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
@@ -13,47 +18,121 @@ pub enum LinkType {
     Url,
 }
 
+/// Wiki link, optionally an embed, with target, anchor and display text
+const WIKI_LINK_PATTERN: &str = r"(!)?\[\[([^\]|#]*)(?:#([^\]|]*))?(?:\|([^\]]+))?\]\]";
+
+/// Part of a note a link points at
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+pub enum LinkAnchor {
+    /// Section under a heading, matched case-insensitively
+    Heading(String),
+    /// Block ending with a `^block-id` marker
+    Block(String),
+}
+
+impl LinkAnchor {
+    /// Parse the text after `#`; `^id` is a block, anything else a heading
+    pub fn parse(anchor: &str) -> Option<Self> {
+        let anchor = anchor.trim();
+        match anchor.strip_prefix('^') {
+            Some(block_id) if !block_id.trim().is_empty() => {
+                Some(Self::Block(block_id.trim().to_string()))
+            }
+            Some(_) => None,
+            None if anchor.is_empty() => None,
+            None => Some(Self::Heading(anchor.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for LinkAnchor {
+    /// The anchor as written after `#`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Heading(heading) => write!(f, "{}", heading),
+            Self::Block(block_id) => write!(f, "^{}", block_id),
+        }
+    }
+}
+
 /// Extracted link from markdown content
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtractedLink {
     pub link_type: LinkType,
     pub target: String,     // Link target (note title for wiki, path for markdown, URL for external)
+    /// Heading or block within the target
+    pub anchor: Option<LinkAnchor>,
     pub text: String,       // Display text
+    /// `![[...]]` transcludes the target instead of linking to it
+    pub embed: bool,
     pub line_number: usize, // 1-based
     pub start_index: usize, // Character offset in entire document
     pub end_index: usize,   // Character offset in entire document
 }
 
+/// A note referenced by a link, and the part of it the link points at
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NoteReference {
+    pub title: String,
+    pub anchor: Option<LinkAnchor>,
+}
+
+impl NoteReference {
+    /// Parse a link target as written between the brackets: `Note#Heading`
+    pub fn parse(target: &str) -> Self {
+        let (title, anchor) = match target.split_once('#') {
+            Some((title, anchor)) => (title, LinkAnchor::parse(anchor)),
+            None => (target, None),
+        };
+        Self {
+            title: title.trim().to_string(),
+            anchor,
+        }
+    }
+}
+
 /// LinkExtractor - Pure functions for link extraction
 pub struct LinkExtractor;
 
 impl LinkExtractor {
     /// Extract wiki-style links [[Note Title]] or [[Note Title|Display Text]]
+    ///
+    /// Targets may carry an anchor, [[Note#Heading]] or [[Note#^block-id]];
+    /// [[#Heading]] points into the note itself and has an empty target.
     pub fn extract_wiki_links(markdown: &str) -> Vec<ExtractedLink> {
         let mut links = Vec::new();
-        let pattern = Regex::new(r"\[\[([^\]|]+)(?:\|([^\]]+))?\]\]").unwrap();
+        let pattern = Regex::new(WIKI_LINK_PATTERN).unwrap();
 
         let mut char_offset = 0;
 
         for (line_num, line) in markdown.lines().enumerate() {
             for caps in pattern.captures_iter(line) {
-                if let Some(target_match) = caps.get(1) {
-                    let target = target_match.as_str().trim().to_string();
-                    let text = caps
-                        .get(2)
-                        .map(|m| m.as_str().trim().to_string())
-                        .unwrap_or_else(|| target.clone());
+                let target = caps.get(2).map_or("", |m| m.as_str().trim()).to_string();
+                let anchor = caps.get(3).and_then(|m| LinkAnchor::parse(m.as_str()));
+                if target.is_empty() && anchor.is_none() {
+                    continue;
+                }
 
-                    if let Some(full_match) = caps.get(0) {
-                        links.push(ExtractedLink {
-                            link_type: LinkType::Wiki,
-                            target,
-                            text,
-                            line_number: line_num + 1,
-                            start_index: char_offset + full_match.start(),
-                            end_index: char_offset + full_match.end(),
-                        });
-                    }
+                let text = caps
+                    .get(4)
+                    .map(|m| m.as_str().trim().to_string())
+                    .unwrap_or_else(|| match &anchor {
+                        Some(anchor) => format!("{}#{}", target, anchor),
+                        None => target.clone(),
+                    });
+
+                if let Some(full_match) = caps.get(0) {
+                    links.push(ExtractedLink {
+                        link_type: LinkType::Wiki,
+                        target,
+                        anchor,
+                        text,
+                        embed: caps.get(1).is_some(),
+                        line_number: line_num + 1,
+                        start_index: char_offset + full_match.start(),
+                        end_index: char_offset + full_match.end(),
+                    });
                 }
             }
 
@@ -75,9 +154,18 @@ impl LinkExtractor {
             for caps in pattern.captures_iter(line) {
                 if let (Some(text_match), Some(target_match)) = (caps.get(1), caps.get(2)) {
                     let text = text_match.as_str().trim().to_string();
-                    let target = target_match.as_str().trim().to_string();
+                    let mut target = target_match.as_str().trim().to_string();
                     let is_external = url_pattern.is_match(&target);
 
+                    // Local links may point into the note: note.md#heading
+                    let mut anchor = None;
+                    if !is_external {
+                        if let Some((path, fragment)) = target.split_once('#') {
+                            anchor = LinkAnchor::parse(fragment);
+                            target = path.trim().to_string();
+                        }
+                    }
+
                     if let Some(full_match) = caps.get(0) {
                         links.push(ExtractedLink {
                             link_type: if is_external {
@@ -86,7 +174,9 @@ impl LinkExtractor {
                                 LinkType::Markdown
                             },
                             target,
+                            anchor,
                             text,
+                            embed: false,
                             line_number: line_num + 1,
                             start_index: char_offset + full_match.start(),
                             end_index: char_offset + full_match.end(),
@@ -122,28 +212,120 @@ impl LinkExtractor {
 
     /// Get unique note titles referenced in the content
     pub fn get_referenced_note_titles(markdown: &str) -> Vec<String> {
-        let internal_links = Self::extract_internal_links(markdown);
-        let mut titles = std::collections::HashSet::new();
+        let titles: std::collections::HashSet<String> = Self::get_note_references(markdown)
+            .into_iter()
+            .map(|reference| reference.title)
+            .collect();
 
-        for link in internal_links {
-            match link.link_type {
-                LinkType::Wiki => {
-                    titles.insert(link.target);
-                }
+        titles.into_iter().collect()
+    }
+
+    /// Get unique (title, anchor) pairs referenced in the content, sorted
+    ///
+    /// Links into the note itself, like [[#Heading]], are not references.
+    pub fn get_note_references(markdown: &str) -> Vec<NoteReference> {
+        let mut references = std::collections::BTreeSet::new();
+
+        for link in Self::extract_internal_links(markdown) {
+            let title = match link.link_type {
+                LinkType::Wiki => link.target,
                 LinkType::Markdown => {
                     // For markdown links, extract filename without extension
-                    if !link.target.starts_with('/') {
-                        if let Some(filename) = link.target.split('/').last() {
-                            let name = filename.trim_end_matches(".md");
-                            titles.insert(name.to_string());
-                        }
+                    if link.target.starts_with('/') {
+                        continue;
+                    }
+                    match link.target.split('/').next_back() {
+                        Some(filename) => filename.trim_end_matches(".md").to_string(),
+                        None => continue,
                     }
                 }
-                _ => {}
+                _ => continue,
+            };
+
+            if !title.is_empty() {
+                references.insert(NoteReference {
+                    title,
+                    anchor: link.anchor,
+                });
             }
         }
 
-        titles.into_iter().collect()
+        references.into_iter().collect()
+    }
+
+    /// Point wiki links at different notes
+    ///
+    /// `targets` maps lowercased old titles to new titles. The visible text is
+    /// kept: `[[Old]]` becomes `[[New|Old]]`, `[[Old#Heading]]` becomes
+    /// `[[New#Heading|Old#Heading]]` and `[[Old|text]]` becomes `[[New|text]]`.
+    /// Returns None when no link was changed.
+    pub fn retarget_wiki_links(
+        markdown: &str,
+        targets: &std::collections::HashMap<String, String>,
+    ) -> Option<String> {
+        Self::rewrite_wiki_targets(markdown, true, |target| {
+            targets.get(&target.to_lowercase()).cloned()
+        })
+    }
+
+    /// Rewrite wiki links after a note's title changed
+    ///
+    /// Links written with the old title, matched case-insensitively, are
+    /// written with the new one; anchors, embeds and display text are kept:
+    /// `[[Old#^id|text]]` becomes `[[New#^id|text]]`. Links through aliases
+    /// still resolve and are left alone. Returns None when no link was changed.
+    pub fn rename_wiki_links(markdown: &str, old_title: &str, new_title: &str) -> Option<String> {
+        let old_title = old_title.trim().to_lowercase();
+        Self::rewrite_wiki_targets(markdown, false, |target| {
+            (target.to_lowercase() == old_title).then(|| new_title.trim().to_string())
+        })
+    }
+
+    /// Replace the targets of wiki links, line by line as they are extracted
+    ///
+    /// With `keep_text`, links without display text get the text they showed
+    /// before; embeds show no text and never get one.
+    fn rewrite_wiki_targets(
+        markdown: &str,
+        keep_text: bool,
+        new_target: impl Fn(&str) -> Option<String>,
+    ) -> Option<String> {
+        let pattern = Regex::new(WIKI_LINK_PATTERN).unwrap();
+        let mut changed = false;
+        let mut rewritten = String::with_capacity(markdown.len());
+
+        for line in markdown.split_inclusive('\n') {
+            let line = pattern.replace_all(line, |caps: &regex::Captures| {
+                let target = caps.get(2).map_or("", |m| m.as_str().trim());
+                let replacement = match new_target(target) {
+                    Some(replacement) if !target.is_empty() && replacement != target => {
+                        replacement
+                    }
+                    _ => return caps[0].to_string(),
+                };
+                changed = true;
+
+                let embed = caps.get(1).is_some();
+                let anchor = caps.get(3).map(|m| format!("#{}", m.as_str()));
+                let anchor = anchor.as_deref().unwrap_or("");
+                let text = match caps.get(4) {
+                    Some(text) => Some(text.as_str().trim().to_string()),
+                    None if keep_text && !embed => Some(format!("{}{}", target, anchor)),
+                    None => None,
+                };
+
+                format!(
+                    "{}[[{}{}{}]]",
+                    if embed { "!" } else { "" },
+                    replacement,
+                    anchor,
+                    text.map(|text| format!("|{}", text)).unwrap_or_default()
+                )
+            });
+            rewritten.push_str(&line);
+        }
+
+        changed.then_some(rewritten)
     }
 
     /// Check if content contains a link to a specific note
@@ -0,0 +1,83 @@
+/// LinkResolver - Pure domain service for resolving link targets to notes
+///
+/// A note answers to its title and to the aliases listed under `aliases:` in
+/// its frontmatter. Titles take precedence over aliases; when several notes
+/// answer to the same name at the same precedence the link is ambiguous and
+/// is not resolved to any of them.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::frontmatter::NoteFrontmatter;
+use super::link_suggester::MentionTarget;
+
+/// Outcome of resolving one link target
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "noteIds", rename_all = "lowercase")]
+pub enum LinkResolution {
+    Resolved(String),
+    /// Every note answering to the name, in index order
+    Ambiguous(Vec<String>),
+    Unresolved,
+}
+
+/// LinkResolver - Index of notes by title and alias
+#[derive(Debug, Default)]
+pub struct LinkResolver {
+    titles: HashMap<String, Vec<String>>,
+    aliases: HashMap<String, Vec<String>>,
+}
+
+impl LinkResolver {
+    /// Index notes by their lowercased titles and aliases
+    pub fn new(notes: &[MentionTarget]) -> Self {
+        let mut resolver = Self::default();
+        for note in notes {
+            Self::insert(&mut resolver.titles, &note.title, &note.note_id);
+            for alias in &note.aliases {
+                Self::insert(&mut resolver.aliases, alias, &note.note_id);
+            }
+        }
+        resolver
+    }
+
+    /// Resolve a link target, like `Note` in `[[Note]]`, case-insensitively
+    pub fn resolve(&self, target: &str) -> LinkResolution {
+        let key = Self::key(target);
+        let matches = self
+            .titles
+            .get(&key)
+            .or_else(|| self.aliases.get(&key))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        match matches {
+            [] => LinkResolution::Unresolved,
+            [note_id] => LinkResolution::Resolved(note_id.clone()),
+            note_ids => LinkResolution::Ambiguous(note_ids.to_vec()),
+        }
+    }
+
+    /// Aliases listed in a note's frontmatter
+    ///
+    /// Accepts `aliases: [A, B]`, `aliases: A, B` and a block list of
+    /// `- A` items, under `aliases` or `alias`. Duplicates are dropped.
+    pub fn aliases_from_markdown(markdown: &str) -> Vec<String> {
+        NoteFrontmatter::from_markdown(markdown).aliases
+    }
+
+    fn insert(index: &mut HashMap<String, Vec<String>>, name: &str, note_id: &str) {
+        let key = Self::key(name);
+        if key.is_empty() {
+            return;
+        }
+        let note_ids = index.entry(key).or_default();
+        if !note_ids.iter().any(|id| id == note_id) {
+            note_ids.push(note_id.to_string());
+        }
+    }
+
+    fn key(name: &str) -> String {
+        name.trim().to_lowercase()
+    }
+}
@@ -0,0 +1,190 @@
+/// LinkSuggester - Pure domain service for finding link opportunities
+///
+/// Finds plain-text mentions of other notes' titles (and aliases) that are not
+/// linked yet, and rewrites a span of markdown into a wiki link. Link text and
+/// anything that isn't prose (see `NonProse`) never produce mentions.
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use super::link_extractor::LinkExtractor;
+use super::non_prose::NonProse;
+
+/// Names shorter than this (in characters) are too noisy to suggest
+pub const MIN_MENTION_CHARS: usize = 3;
+
+/// A note that can be mentioned, under its title or any alias
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MentionTarget {
+    pub note_id: String,
+    pub title: String,
+    /// Other names the note goes by
+    pub aliases: Vec<String>,
+}
+
+/// Plain-text mention of a note
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MentionSpan {
+    pub note_id: String,
+    /// Title or alias that matched
+    pub name: String,
+    /// Text as written in the document
+    pub text: String,
+    pub line_number: usize, // 1-based
+    pub start: usize,       // Byte offset in the document
+    pub end: usize,         // Byte offset in the document (exclusive)
+}
+
+/// LinkSuggester - Pure functions for link suggestions
+pub struct LinkSuggester;
+
+impl LinkSuggester {
+    /// Find unlinked mentions of the targets, in document order
+    ///
+    /// Matching is case-insensitive on whole words, and the longest name wins
+    /// where names overlap. Targets the document already links to are skipped.
+    pub fn find_unlinked_mentions(markdown: &str, targets: &[MentionTarget]) -> Vec<MentionSpan> {
+        let linked: HashSet<String> = LinkExtractor::get_referenced_note_titles(markdown)
+            .into_iter()
+            .map(|title| title.to_lowercase())
+            .collect();
+
+        // Lowercased name -> (target, name as the target spells it)
+        let mut names: HashMap<String, (usize, &str)> = HashMap::new();
+        for (index, target) in targets.iter().enumerate() {
+            let all_names: Vec<&str> = std::iter::once(target.title.as_str())
+                .chain(target.aliases.iter().map(String::as_str))
+                .map(str::trim)
+                .collect();
+            if all_names
+                .iter()
+                .any(|name| linked.contains(&name.to_lowercase()))
+            {
+                continue;
+            }
+            for name in all_names {
+                if name.chars().count() >= MIN_MENTION_CHARS {
+                    names.entry(name.to_lowercase()).or_insert((index, name));
+                }
+            }
+        }
+        let Some(pattern) = Self::names_pattern(names.keys()) else {
+            return Vec::new();
+        };
+
+        let excluded = Self::excluded_ranges(markdown);
+        let mut mentions = Vec::new();
+        let mut position = 0;
+        while let Some(found) = pattern.find_at(markdown, position) {
+            let (start, end) = (found.start(), found.end());
+            let is_word = Self::is_word_boundary(markdown, start, end);
+            let is_excluded = excluded.iter().any(|r| r.start < end && start < r.end);
+
+            match names.get(&found.as_str().to_lowercase()) {
+                Some((index, name)) if is_word && !is_excluded => {
+                    mentions.push(MentionSpan {
+                        note_id: targets[*index].note_id.clone(),
+                        name: name.to_string(),
+                        text: found.as_str().to_string(),
+                        line_number: markdown[..start].matches('\n').count() + 1,
+                        start,
+                        end,
+                    });
+                    position = end;
+                }
+                _ => {
+                    // Try again from the next character
+                    position = start + markdown[start..].chars().next().map_or(1, char::len_utf8);
+                }
+            }
+        }
+
+        mentions
+    }
+
+    /// Rewrite `markdown[start..end]` into a wiki link to `title`
+    ///
+    /// The written text is kept as display text when it differs from the
+    /// title. An empty span inserts `[[title]]` at that offset, after a space
+    /// if it would otherwise touch the preceding word. Returns None when the
+    /// span is out of bounds, splits a character or touches code or a link.
+    pub fn link_span(markdown: &str, start: usize, end: usize, title: &str) -> Option<String> {
+        if start > end
+            || end > markdown.len()
+            || !markdown.is_char_boundary(start)
+            || !markdown.is_char_boundary(end)
+            || title.trim().is_empty()
+        {
+            return None;
+        }
+        let inside_excluded = Self::excluded_ranges(markdown).iter().any(|r| {
+            if start == end {
+                r.start < start && start < r.end
+            } else {
+                r.start < end && start < r.end
+            }
+        });
+        if inside_excluded {
+            return None;
+        }
+
+        let title = title.trim();
+        let text = markdown[start..end].trim();
+        let link = if start == end {
+            let touches_word = markdown[..start]
+                .chars()
+                .next_back()
+                .is_some_and(|c| !c.is_whitespace());
+            format!("{}[[{}]]", if touches_word { " " } else { "" }, title)
+        } else if text.is_empty() || text == title {
+            format!("[[{}]]", title)
+        } else {
+            format!("[[{}|{}]]", title, text)
+        };
+
+        let mut rewritten = String::with_capacity(markdown.len() + link.len());
+        rewritten.push_str(&markdown[..start]);
+        rewritten.push_str(&link);
+        rewritten.push_str(&markdown[end..]);
+        Some(rewritten)
+    }
+
+    /// Case-insensitive alternation of the names, longest first
+    fn names_pattern<'a>(names: impl Iterator<Item = &'a String>) -> Option<Regex> {
+        let mut names: Vec<&String> = names.collect();
+        if names.is_empty() {
+            return None;
+        }
+        names.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+        let alternation = names
+            .iter()
+            .map(|name| regex::escape(name))
+            .collect::<Vec<_>>()
+            .join("|");
+        RegexBuilder::new(&alternation)
+            .case_insensitive(true)
+            .build()
+            .ok()
+    }
+
+    fn is_word_boundary(markdown: &str, start: usize, end: usize) -> bool {
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let before = markdown[..start].chars().next_back();
+        let after = markdown[end..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    }
+
+    /// Byte ranges that must not be turned into links: anything that isn't
+    /// prose, and the text of markdown links
+    fn excluded_ranges(markdown: &str) -> Vec<Range<usize>> {
+        let mut ranges = NonProse::ranges(markdown);
+        let link_text = Regex::new(r"!?\[[^\]\n]*\]\(").unwrap();
+        ranges.extend(link_text.find_iter(markdown).map(|m| m.range()));
+        ranges
+    }
+}
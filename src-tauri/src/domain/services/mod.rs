@@ -1,12 +1,54 @@
 // Domain services go here
 // These are pure business logic services with NO external dependencies
 
+pub mod answer_prompt;
+pub mod duplicate_detector;
+pub mod frontmatter;
+pub mod hashtag_extractor;
+pub mod hnsw_index;
 pub mod link_extractor;
+pub mod link_resolver;
+pub mod link_suggester;
+pub mod non_prose;
+pub mod note_anchors;
+pub mod note_chunker;
+pub mod note_filenames;
+pub mod periodic_notes;
+pub mod rank_fusion;
+pub mod relative_links;
+pub mod search_query_parser;
 pub mod similarity_calculator;
 pub mod task_extractor;
+pub mod template_renderer;
+pub mod topic_classifier;
+pub mod topic_discovery;
 
-pub use link_extractor::{ExtractedLink, LinkExtractor, LinkType};
+pub use answer_prompt::{AnswerPrompt, CitationMarker, ContextPassage, ANSWER_SYSTEM_PROMPT};
+pub use duplicate_detector::{
+    DuplicateConfig, DuplicateDetector, DuplicateDocument, DuplicateGroup, DuplicatePair, MinHasher,
+};
+pub use frontmatter::{Frontmatter, NoteFrontmatter, YamlValue, ALIAS_KEYS, TAG_KEYS};
+pub use hashtag_extractor::{Hashtag, HashtagExtractor};
+pub use hnsw_index::{HnswConfig, HnswIndex, VectorHit};
+pub use link_extractor::{ExtractedLink, LinkAnchor, LinkExtractor, LinkType, NoteReference};
+pub use link_resolver::{LinkResolution, LinkResolver};
+pub use link_suggester::{LinkSuggester, MentionSpan, MentionTarget, MIN_MENTION_CHARS};
+pub use non_prose::NonProse;
+pub use note_anchors::{MarkedBlock, NoteAnchors};
+pub use note_chunker::{NoteChunker, TextChunk, DEFAULT_MAX_CHUNK_BYTES};
+pub use note_filenames::{FilenameStrategy, NoteFilenames, MAX_FILENAME_CHARS};
+pub use periodic_notes::{NotePeriod, PeriodicNotes};
+pub use rank_fusion::{FusedHit, RankFusion, RankedHit, RRF_K};
+pub use relative_links::RelativeLinks;
+pub use search_query_parser::{ParsedSearchQuery, SearchQueryError, SearchQueryParser};
 pub use similarity_calculator::{
-    cosine_similarity, euclidean_distance, manhattan_distance, normalize_vector,
+    cosine_similarity, euclidean_distance, manhattan_distance, mean_vector, normalize_vector,
 };
 pub use task_extractor::{RawTask, TaskExtractor, TaskState};
+pub use template_renderer::{
+    RenderedTemplate, TemplateContext, TemplateRenderer, DEFAULT_DATE_FORMAT, DEFAULT_TIME_FORMAT,
+};
+pub use topic_classifier::{
+    CentroidMember, ClassificationConfig, TopicClassifier, TopicMatch, CLASSIFICATION_SETTING,
+};
+pub use topic_discovery::{DiscoveryConfig, EmbeddingCluster, TopicDiscovery};
@@ -0,0 +1,80 @@
+/// NonProse - Pure domain service for the parts of markdown that aren't prose
+///
+/// Finds the byte ranges that hold markup or code rather than the writer's
+/// text: frontmatter, headings, fenced and indented code blocks, code spans,
+/// wiki links, link targets, URLs and autolinks. Extractors skip these so a
+/// `#define` in code is not a tag and a title in a URL is not a mention.
+use std::ops::Range;
+
+use regex::Regex;
+
+use super::frontmatter::Frontmatter;
+
+pub struct NonProse;
+
+impl NonProse {
+    /// Byte ranges of markdown that aren't prose, in no particular order
+    pub fn ranges(markdown: &str) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+
+        let body_start = Frontmatter::body_offset(markdown);
+        if body_start > 0 {
+            ranges.push(0..body_start);
+        }
+
+        // Fenced and indented code blocks, and headings
+        let mut offset = body_start;
+        let mut fence: Option<(&str, usize)> = None;
+        let mut previous_blank = true;
+        let mut in_indented_code = false;
+        for line in markdown[body_start..].split_inclusive('\n') {
+            let trimmed = line.trim_start();
+            let blank = trimmed.trim_end().is_empty();
+            match fence {
+                Some((marker, start)) => {
+                    if trimmed.starts_with(marker) {
+                        ranges.push(start..offset + line.len());
+                        fence = None;
+                    }
+                }
+                None => {
+                    let indented = line.starts_with("    ") || line.starts_with('\t');
+                    in_indented_code = !blank && indented && (previous_blank || in_indented_code);
+                    if in_indented_code {
+                        ranges.push(offset..offset + line.len());
+                    } else if trimmed.starts_with("```") {
+                        fence = Some(("```", offset));
+                    } else if trimmed.starts_with("~~~") {
+                        fence = Some(("~~~", offset));
+                    } else if Self::is_heading(trimmed) {
+                        ranges.push(offset..offset + line.len());
+                    }
+                }
+            }
+            previous_blank = blank;
+            offset += line.len();
+        }
+        if let Some((_, start)) = fence {
+            ranges.push(start..markdown.len()); // Unclosed fence runs to the end
+        }
+
+        // Inline code, wiki links, link targets, URLs and autolinks
+        let inline = Regex::new(
+            r"`[^`\n]+`|!?\[\[[^\]]*\]\]|\]\([^)\n]*\)|[a-zA-Z][a-zA-Z0-9+.-]*://[^\s)>\]]+|<[^>\n]+>",
+        )
+        .unwrap();
+        ranges.extend(inline.find_iter(markdown).map(|m| m.range()));
+
+        ranges
+    }
+
+    /// An ATX heading: one to six `#` followed by a space or the line end
+    fn is_heading(trimmed: &str) -> bool {
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        (1..=6).contains(&level)
+            && trimmed[level..]
+                .chars()
+                .next()
+                .is_none_or(char::is_whitespace)
+    }
+}
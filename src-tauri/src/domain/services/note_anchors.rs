@@ -0,0 +1,221 @@
+/// NoteAnchors - Pure domain service for locating headings and blocks in notes
+///
+/// Resolves the anchors of [[Note#Heading]] and [[Note#^block-id]] links to
+/// parts of the target's markdown. A block is the paragraph or list item whose
+/// last line ends with a `^block-id` marker; a marker on a line of its own
+/// marks the lines directly above it, as used for tables and quotes. Code
+/// blocks and frontmatter hold neither headings nor blocks.
+use std::ops::Range;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::frontmatter::Frontmatter;
+use super::link_extractor::LinkAnchor;
+use super::note_chunker::NoteChunker;
+
+/// `^block-id` at the end of a line
+const BLOCK_MARKER_PATTERN: &str = r"(?:^|\s)\^([A-Za-z0-9][A-Za-z0-9-]*)\s*$";
+
+/// Start of a list item: `- `, `* `, `+ `, `1. ` or `1) `
+const LIST_ITEM_PATTERN: &str = r"^(?:[-*+]|\d+[.)])\s";
+
+/// A block marked with `^block-id`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarkedBlock {
+    pub block_id: String,
+    /// Text of the block without its marker
+    pub text: String,
+    pub line_number: usize, // 1-based, first line of the block
+    pub start: usize,       // Byte offset in the markdown
+    pub end: usize,         // Byte offset in the markdown (exclusive), past the marker
+}
+
+/// One line of markdown, without its line break
+struct Line<'a> {
+    start: usize,
+    number: usize,
+    text: &'a str,
+    /// Inside a code block, fences included
+    code: bool,
+}
+
+/// NoteAnchors - Pure functions for heading and block anchors
+pub struct NoteAnchors;
+
+impl NoteAnchors {
+    /// Blocks marked with `^block-id`, in document order
+    ///
+    /// Ids are unique within a note; later blocks reusing one are ignored.
+    pub fn extract_blocks(markdown: &str) -> Vec<MarkedBlock> {
+        let marker = Regex::new(BLOCK_MARKER_PATTERN).unwrap();
+        let list_item = Regex::new(LIST_ITEM_PATTERN).unwrap();
+
+        let mut blocks: Vec<MarkedBlock> = Vec::new();
+        // First line of the current paragraph: (byte offset, line number)
+        let mut paragraph: Option<(usize, usize)> = None;
+        let mut paragraph_end = 0;
+
+        for line in Self::lines(markdown, Frontmatter::body_offset(markdown)) {
+            let trimmed = line.text.trim();
+            if line.code || trimmed.is_empty() || NoteChunker::parse_heading(trimmed).is_some() {
+                paragraph = None;
+                continue;
+            }
+
+            let caps = marker.captures(line.text);
+            let line_end = line.start + line.text.len();
+
+            // A marker on its own line marks the lines above it
+            if let Some(caps) = caps.as_ref().filter(|_| trimmed.starts_with('^')) {
+                if let Some((start, number)) = paragraph.take() {
+                    Self::push_block(
+                        &mut blocks,
+                        MarkedBlock {
+                            block_id: caps[1].to_string(),
+                            text: markdown[start..paragraph_end].to_string(),
+                            line_number: number,
+                            start,
+                            end: line_end,
+                        },
+                    );
+                }
+                continue;
+            }
+
+            if paragraph.is_none() || list_item.is_match(trimmed) {
+                let indent = line.text.len() - line.text.trim_start().len();
+                paragraph = Some((line.start + indent, line.number));
+            }
+            paragraph_end = line_end;
+
+            if let (Some(caps), Some((start, number))) = (caps, paragraph) {
+                let marker_start = line.start + caps.get(0).map_or(0, |m| m.start());
+                Self::push_block(
+                    &mut blocks,
+                    MarkedBlock {
+                        block_id: caps[1].to_string(),
+                        text: markdown[start..marker_start].trim_end().to_string(),
+                        line_number: number,
+                        start,
+                        end: line_end,
+                    },
+                );
+                paragraph = None;
+            }
+        }
+
+        blocks
+    }
+
+    /// Byte range of the section under a heading, heading line included
+    ///
+    /// The heading is matched case-insensitively; the section runs until the
+    /// next heading of the same or a higher level.
+    pub fn heading_section(markdown: &str, heading: &str) -> Option<Range<usize>> {
+        let wanted = heading.trim().to_lowercase();
+        let mut found: Option<(usize, usize)> = None; // (byte offset, level)
+
+        for line in Self::lines(markdown, Frontmatter::body_offset(markdown)) {
+            if line.code {
+                continue;
+            }
+            let Some((level, title)) = NoteChunker::parse_heading(line.text.trim_start()) else {
+                continue;
+            };
+
+            match found {
+                Some((start, found_level)) if level <= found_level => {
+                    return Some(start..line.start);
+                }
+                None if title.to_lowercase() == wanted => found = Some((line.start, level)),
+                _ => {}
+            }
+        }
+
+        found.map(|(start, _)| start..markdown.len())
+    }
+
+    /// Markdown a link anchor points at, without block markers
+    pub fn section(markdown: &str, anchor: &LinkAnchor) -> Option<String> {
+        match anchor {
+            LinkAnchor::Heading(heading) => Self::heading_section(markdown, heading)
+                .map(|range| Self::strip_block_markers(markdown[range].trim_end())),
+            LinkAnchor::Block(block_id) => Self::extract_blocks(markdown)
+                .into_iter()
+                .find(|block| block.block_id.eq_ignore_ascii_case(block_id))
+                .map(|block| block.text),
+        }
+    }
+
+    /// A whole note's markdown as transcluded: no frontmatter or block markers
+    pub fn body(markdown: &str) -> String {
+        let body = &markdown[Frontmatter::body_offset(markdown)..];
+        Self::strip_block_markers(body.trim())
+    }
+
+    /// Remove `^block-id` markers, and lines holding nothing else
+    pub fn strip_block_markers(markdown: &str) -> String {
+        let marker = Regex::new(BLOCK_MARKER_PATTERN).unwrap();
+
+        let mut lines: Vec<&str> = Vec::new();
+        for line in Self::lines(markdown, 0) {
+            match marker.find(line.text).filter(|_| !line.code) {
+                Some(_) if line.text.trim().starts_with('^') => {}
+                Some(m) => lines.push(line.text[..m.start()].trim_end()),
+                None => lines.push(line.text),
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn push_block(blocks: &mut Vec<MarkedBlock>, block: MarkedBlock) {
+        if !blocks
+            .iter()
+            .any(|b| b.block_id.eq_ignore_ascii_case(&block.block_id))
+        {
+            blocks.push(block);
+        }
+    }
+
+    /// Lines from a byte offset on, flagging those in code blocks
+    fn lines(markdown: &str, from: usize) -> Vec<Line<'_>> {
+        let mut lines = Vec::new();
+        let mut fence: Option<&str> = None;
+        let mut offset = from;
+        let first_line = markdown[..from].matches('\n').count() + 1;
+
+        for (index, raw) in markdown[from..].split_inclusive('\n').enumerate() {
+            let text = raw.trim_end_matches(['\n', '\r']);
+            let trimmed = text.trim_start();
+
+            let code = match fence {
+                Some(marker) => {
+                    if trimmed.starts_with(marker) {
+                        fence = None;
+                    }
+                    true
+                }
+                None if trimmed.starts_with("```") => {
+                    fence = Some("```");
+                    true
+                }
+                None if trimmed.starts_with("~~~") => {
+                    fence = Some("~~~");
+                    true
+                }
+                None => false,
+            };
+
+            lines.push(Line {
+                start: offset,
+                number: first_line + index,
+                text,
+                code,
+            });
+            offset += raw.len();
+        }
+
+        lines
+    }
+}
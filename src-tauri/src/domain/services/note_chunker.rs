@@ -0,0 +1,245 @@
+/// NoteChunker - Pure domain service for splitting notes into passages
+///
+/// Splits markdown into heading-aware chunks small enough for the embedding
+/// model. Sections are split at headings first, then packed paragraph by
+/// paragraph up to the size limit. Each chunk records its byte range in the
+/// original markdown and the headings it sits under.
+use serde::{Deserialize, Serialize};
+
+use super::frontmatter::Frontmatter;
+
+/// Default maximum chunk size in bytes (~200 tokens for MiniLM)
+pub const DEFAULT_MAX_CHUNK_BYTES: usize = 800;
+
+/// A passage of a note
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextChunk {
+    pub index: usize,
+    /// Headings enclosing the chunk, outermost first
+    pub heading_path: Vec<String>,
+    pub text: String,
+    pub start: usize, // Byte offset in the markdown
+    pub end: usize,   // Byte offset in the markdown (exclusive)
+}
+
+/// Line-level block within a section
+struct Block {
+    start: usize,
+    end: usize,
+}
+
+/// Section of a note that starts at a heading (or at the top of the note)
+struct Section {
+    heading_path: Vec<String>,
+    blocks: Vec<Block>,
+    /// Whether the first block is the heading line itself
+    has_heading: bool,
+}
+
+/// NoteChunker - Splits markdown into passages
+pub struct NoteChunker {
+    max_bytes: usize,
+}
+
+impl Default for NoteChunker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CHUNK_BYTES)
+    }
+}
+
+impl NoteChunker {
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes: max_bytes.max(1),
+        }
+    }
+
+    /// Split markdown into chunks, skipping frontmatter and empty sections
+    pub fn chunk(&self, markdown: &str) -> Vec<TextChunk> {
+        let mut chunks = Vec::new();
+
+        for section in Self::sections(markdown) {
+            // A heading with nothing under it says nothing on its own
+            if section.has_heading && section.blocks.len() == 1 {
+                continue;
+            }
+
+            // Keep a heading with the text that follows it
+            let heading = section
+                .has_heading
+                .then(|| (section.blocks[0].start, section.blocks[0].end));
+
+            let mut current: Option<(usize, usize)> = None;
+            for block in &section.blocks {
+                for (start, end) in self.split_block(markdown, block) {
+                    current = match current {
+                        Some((chunk_start, _))
+                            if end - chunk_start <= self.max_bytes || current == heading =>
+                        {
+                            Some((chunk_start, end))
+                        }
+                        Some(range) => {
+                            Self::push(&mut chunks, markdown, &section.heading_path, range);
+                            Some((start, end))
+                        }
+                        None => Some((start, end)),
+                    };
+                }
+            }
+            if let Some(range) = current {
+                Self::push(&mut chunks, markdown, &section.heading_path, range);
+            }
+        }
+
+        chunks
+    }
+
+    fn push(
+        chunks: &mut Vec<TextChunk>,
+        markdown: &str,
+        heading_path: &[String],
+        (start, end): (usize, usize),
+    ) {
+        chunks.push(TextChunk {
+            index: chunks.len(),
+            heading_path: heading_path.to_vec(),
+            text: markdown[start..end].to_string(),
+            start,
+            end,
+        });
+    }
+
+    /// Group lines into sections at headings and into blocks at blank lines
+    fn sections(markdown: &str) -> Vec<Section> {
+        let mut sections = vec![Section {
+            heading_path: Vec::new(),
+            blocks: Vec::new(),
+            has_heading: false,
+        }];
+        let mut headings: Vec<(usize, String)> = Vec::new();
+        let mut block: Option<Block> = None;
+        let mut fence: Option<&str> = None;
+
+        let mut offset = Frontmatter::body_offset(markdown);
+        for line in markdown[offset..].split_inclusive('\n') {
+            let line_start = offset;
+            offset += line.len();
+            let content = line.trim_end();
+            let trimmed = content.trim_start();
+
+            // Code blocks stay in one block, blank lines and all
+            if let Some(marker) = fence {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                if let Some(current) = block.as_mut() {
+                    current.end = line_start + content.len();
+                }
+                continue;
+            }
+
+            if let Some((level, title)) = Self::parse_heading(trimmed) {
+                Self::close_block(&mut sections, &mut block);
+                headings.retain(|(l, _)| *l < level);
+                headings.push((level, title));
+                sections.push(Section {
+                    heading_path: headings.iter().map(|(_, t)| t.clone()).collect(),
+                    blocks: vec![Block {
+                        start: line_start,
+                        end: line_start + content.len(),
+                    }],
+                    has_heading: true,
+                });
+                continue;
+            }
+
+            if trimmed.is_empty() {
+                Self::close_block(&mut sections, &mut block);
+                continue;
+            }
+
+            if trimmed.starts_with("```") {
+                fence = Some("```");
+            } else if trimmed.starts_with("~~~") {
+                fence = Some("~~~");
+            }
+
+            let leading = content.len() - trimmed.len();
+            match block.as_mut() {
+                Some(current) => current.end = line_start + content.len(),
+                None => {
+                    block = Some(Block {
+                        start: line_start + leading,
+                        end: line_start + content.len(),
+                    })
+                }
+            }
+        }
+        Self::close_block(&mut sections, &mut block);
+
+        sections.retain(|section| !section.blocks.is_empty());
+        sections
+    }
+
+    fn close_block(sections: &mut [Section], block: &mut Option<Block>) {
+        if let (Some(block), Some(section)) = (block.take(), sections.last_mut()) {
+            section.blocks.push(block);
+        }
+    }
+
+    /// Parse an ATX heading (`## Title`) into its level and text
+    pub(crate) fn parse_heading(line: &str) -> Option<(usize, String)> {
+        let level = line.chars().take_while(|c| *c == '#').count();
+        if level == 0 || level > 6 {
+            return None;
+        }
+        let rest = &line[level..];
+        if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+            return None; // #hashtag, not a heading
+        }
+        let title = rest.trim().trim_end_matches('#').trim();
+        Some((level, title.to_string()))
+    }
+
+    /// Split a block that is too large at line, then word, boundaries
+    fn split_block(&self, markdown: &str, block: &Block) -> Vec<(usize, usize)> {
+        let mut pieces = Vec::new();
+        let mut start = block.start;
+
+        while block.end - start > self.max_bytes {
+            let mut window_end = Self::floor_char_boundary(markdown, start + self.max_bytes);
+            if window_end == start {
+                // The limit is narrower than this character: take it whole
+                window_end += markdown[start..].chars().next().map_or(1, char::len_utf8);
+            }
+            let window = &markdown[start..window_end];
+            let cut = window
+                .rfind('\n')
+                .or_else(|| window.rfind(char::is_whitespace))
+                .filter(|cut| *cut > 0)
+                .map_or(window_end, |cut| start + cut);
+
+            pieces.push((start, cut));
+            start = cut;
+            // Skip the whitespace we split on
+            while start < block.end {
+                match markdown[start..].chars().next() {
+                    Some(c) if c.is_whitespace() => start += c.len_utf8(),
+                    _ => break,
+                }
+            }
+        }
+        if start < block.end {
+            pieces.push((start, block.end));
+        }
+
+        pieces
+    }
+
+    fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+        while !text.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    }
+}
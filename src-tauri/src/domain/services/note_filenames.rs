@@ -0,0 +1,225 @@
+/// NoteFilenames - Pure domain service for naming note files
+///
+/// A workspace names the files of new notes after the time they were created,
+/// after a slug of their title (transliterated to ASCII, so `Crème Brûlée`
+/// becomes `creme-brulee`), or after the title itself with the characters
+/// file systems reject replaced. Names that are taken get a numbered suffix.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Longest file stem generated from a title, in characters
+pub const MAX_FILENAME_CHARS: usize = 100;
+
+/// Stem used when a title leaves nothing to name a file after
+const UNTITLED: &str = "Untitled";
+
+/// How the files of new notes are named
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilenameStrategy {
+    /// `20260117-093012-345.md`
+    #[default]
+    Timestamp,
+    /// `creme-brulee.md`
+    Slug,
+    /// `Crème Brûlée.md`
+    Title,
+}
+
+/// NoteFilenames - Pure functions for note file names
+pub struct NoteFilenames;
+
+impl NoteFilenames {
+    /// File stem for a note, without extension or collision suffix
+    pub fn stem(strategy: FilenameStrategy, title: &str, now: DateTime<Utc>) -> String {
+        match strategy {
+            FilenameStrategy::Timestamp => now.format("%Y%m%d-%H%M%S-%3f").to_string(),
+            FilenameStrategy::Slug => Self::slugify(title),
+            FilenameStrategy::Title => Self::sanitize(title),
+        }
+    }
+
+    /// The `n`th alternative of a taken stem: `rust-2` or `Rust 2`
+    pub fn with_suffix(strategy: FilenameStrategy, stem: &str, n: usize) -> String {
+        match strategy {
+            FilenameStrategy::Title => format!("{} {}", stem, n),
+            _ => format!("{}-{}", stem, n),
+        }
+    }
+
+    /// Whether a stem is `stem` itself or one of its numbered alternatives
+    pub fn is_variant_of(strategy: FilenameStrategy, candidate: &str, stem: &str) -> bool {
+        let separator = match strategy {
+            FilenameStrategy::Title => ' ',
+            _ => '-',
+        };
+        candidate == stem
+            || candidate
+                .strip_prefix(stem)
+                .and_then(|rest| rest.strip_prefix(separator))
+                .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    }
+
+    /// Lowercase ASCII words joined by `-`
+    ///
+    /// Letters without an ASCII spelling, like those of CJK scripts, are kept.
+    pub fn slugify(title: &str) -> String {
+        let mut slug = String::with_capacity(title.len());
+        let mut pending_dash = false;
+
+        for c in title.chars().flat_map(char::to_lowercase) {
+            let parts: Vec<char> = match Self::transliterate(c) {
+                // Signs and accents that are not spelled at all
+                Some("") => continue,
+                Some(ascii) => ascii.chars().collect(),
+                None if c.is_ascii_alphanumeric() || (!c.is_ascii() && c.is_alphanumeric()) => {
+                    vec![c]
+                }
+                None => Vec::new(),
+            };
+
+            if parts.is_empty() {
+                pending_dash = !slug.is_empty();
+                continue;
+            }
+            if pending_dash {
+                slug.push('-');
+                pending_dash = false;
+            }
+            slug.extend(parts);
+        }
+
+        let slug = Self::truncate(&slug, MAX_FILENAME_CHARS);
+        let slug = slug.trim_end_matches('-');
+        if slug.is_empty() {
+            UNTITLED.to_lowercase()
+        } else {
+            slug.to_string()
+        }
+    }
+
+    /// The title with characters file systems reject replaced
+    pub fn sanitize(title: &str) -> String {
+        let replaced: String = title
+            .chars()
+            .map(|c| match c {
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+                c if c.is_control() => ' ',
+                c => c,
+            })
+            .collect();
+        let collapsed = replaced.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        // Leading dots hide files; trailing dots and spaces are dropped on Windows
+        let name = Self::truncate(collapsed.trim_start_matches('.'), MAX_FILENAME_CHARS);
+        let name = name.trim_end_matches(['.', ' ']);
+        if name.is_empty() {
+            UNTITLED.to_string()
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn truncate(text: &str, max_chars: usize) -> &str {
+        match text.char_indices().nth(max_chars) {
+            Some((index, _)) => &text[..index],
+            None => text,
+        }
+    }
+
+    /// ASCII spelling of a lowercase letter, for those that have one
+    fn transliterate(c: char) -> Option<&'static str> {
+        let ascii = match c {
+            'a'..='z' | '0'..='9' => return None,
+            // Combining accents, as in decomposed text
+            '\u{0300}'..='\u{036f}' => "",
+            // Latin
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' | 'ǎ' => "a",
+            'æ' => "ae",
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+            'ď' | 'đ' | 'ð' => "d",
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+            'ĥ' | 'ħ' => "h",
+            'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' | 'ǐ' => "i",
+            'ĵ' => "j",
+            'ķ' => "k",
+            'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+            'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' | 'ǒ' => "o",
+            'œ' => "oe",
+            'ŕ' | 'ŗ' | 'ř' => "r",
+            'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+            'ß' => "ss",
+            'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+            'þ' => "th",
+            'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' | 'ǔ' => "u",
+            'ŵ' => "w",
+            'ý' | 'ÿ' | 'ŷ' => "y",
+            'ź' | 'ż' | 'ž' => "z",
+            // Greek
+            'α' | 'ά' => "a",
+            'β' => "v",
+            'γ' => "g",
+            'δ' => "d",
+            'ε' | 'έ' => "e",
+            'ζ' => "z",
+            'η' | 'ή' => "i",
+            'θ' => "th",
+            'ι' | 'ί' | 'ϊ' | 'ΐ' => "i",
+            'κ' => "k",
+            'λ' => "l",
+            'μ' => "m",
+            'ν' => "n",
+            'ξ' => "x",
+            'ο' | 'ό' => "o",
+            'π' => "p",
+            'ρ' => "r",
+            'σ' | 'ς' => "s",
+            'τ' => "t",
+            'υ' | 'ύ' | 'ϋ' | 'ΰ' => "y",
+            'φ' => "f",
+            'χ' => "ch",
+            'ψ' => "ps",
+            'ω' | 'ώ' => "o",
+            // Cyrillic
+            'а' => "a",
+            'б' => "b",
+            'в' => "v",
+            'г' | 'ґ' => "g",
+            'д' => "d",
+            'е' | 'э' => "e",
+            'ё' => "yo",
+            'є' => "ye",
+            'ж' => "zh",
+            'з' => "z",
+            'и' | 'і' | 'й' => "i",
+            'ї' => "yi",
+            'к' => "k",
+            'л' => "l",
+            'м' => "m",
+            'н' => "n",
+            'о' => "o",
+            'п' => "p",
+            'р' => "r",
+            'с' => "s",
+            'т' => "t",
+            'у' | 'ў' => "u",
+            'ф' => "f",
+            'х' => "kh",
+            'ц' => "ts",
+            'ч' => "ch",
+            'ш' => "sh",
+            'щ' => "shch",
+            'ы' => "y",
+            'ъ' | 'ь' => "",
+            'ю' => "yu",
+            'я' => "ya",
+            // Punctuation that reads as a word
+            '&' => "and",
+            '@' => "at",
+            _ => return None,
+        };
+        Some(ascii)
+    }
+}
@@ -0,0 +1,98 @@
+/// PeriodicNotes - Pure domain service for daily, weekly, monthly and quarterly notes
+///
+/// Works out which period a moment belongs to, where periods start and end,
+/// and how to step to the previous or next one. Weeks are ISO weeks,
+/// starting on Monday.
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+use super::template_renderer::TemplateRenderer;
+
+/// Length of time a periodic note covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotePeriod {
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+}
+
+impl NotePeriod {
+    pub const ALL: [NotePeriod; 4] = [
+        NotePeriod::Daily,
+        NotePeriod::Weekly,
+        NotePeriod::Monthly,
+        NotePeriod::Quarterly,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotePeriod::Daily => "daily",
+            NotePeriod::Weekly => "weekly",
+            NotePeriod::Monthly => "monthly",
+            NotePeriod::Quarterly => "quarterly",
+        }
+    }
+}
+
+/// PeriodicNotes - Pure functions over note periods
+pub struct PeriodicNotes;
+
+impl PeriodicNotes {
+    /// The calendar day a moment counts as, for days starting at `day_start`
+    ///
+    /// With a day start of 04:00, 01:30 on the 15th still belongs to the 14th.
+    pub fn current_date(now: NaiveDateTime, day_start: NaiveTime) -> NaiveDate {
+        if now.time() < day_start {
+            now.date().pred_opt().unwrap_or(now.date())
+        } else {
+            now.date()
+        }
+    }
+
+    /// First day of the period containing `date`
+    pub fn start_of(period: NotePeriod, date: NaiveDate) -> NaiveDate {
+        match period {
+            NotePeriod::Daily => date,
+            NotePeriod::Weekly => date - Days::new(date.weekday().num_days_from_monday() as u64),
+            NotePeriod::Monthly => date.with_day(1).unwrap_or(date),
+            NotePeriod::Quarterly => {
+                NaiveDate::from_ymd_opt(date.year(), date.month0() / 3 * 3 + 1, 1).unwrap_or(date)
+            }
+        }
+    }
+
+    /// Last day of the period containing `date`
+    pub fn end_of(period: NotePeriod, date: NaiveDate) -> NaiveDate {
+        let next = Self::shift(period, date, 1);
+        next.pred_opt().unwrap_or(next)
+    }
+
+    /// First day of the period `offset` periods away from the one containing `date`
+    pub fn shift(period: NotePeriod, date: NaiveDate, offset: i32) -> NaiveDate {
+        let start = Self::start_of(period, date);
+        let steps = offset.unsigned_abs();
+        let shifted = match period {
+            NotePeriod::Daily if offset < 0 => start.checked_sub_days(Days::new(steps as u64)),
+            NotePeriod::Daily => start.checked_add_days(Days::new(steps as u64)),
+            NotePeriod::Weekly if offset < 0 => start.checked_sub_days(Days::new(steps as u64 * 7)),
+            NotePeriod::Weekly => start.checked_add_days(Days::new(steps as u64 * 7)),
+            NotePeriod::Monthly if offset < 0 => start.checked_sub_months(Months::new(steps)),
+            NotePeriod::Monthly => start.checked_add_months(Months::new(steps)),
+            NotePeriod::Quarterly if offset < 0 => start.checked_sub_months(Months::new(steps * 3)),
+            NotePeriod::Quarterly => start.checked_add_months(Months::new(steps * 3)),
+        };
+        shifted.unwrap_or(start)
+    }
+
+    /// Name of a period's note (without extension), from a Moment-style format
+    pub fn note_name(format: &str, date: NaiveDate) -> String {
+        TemplateRenderer::format_date(&date.and_time(NaiveTime::MIN), format)
+    }
+
+    /// Parse an `HH:MM` time of day
+    pub fn parse_day_start(value: &str) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+    }
+}
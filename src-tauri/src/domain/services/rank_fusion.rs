@@ -0,0 +1,106 @@
+/// RankFusion - Pure domain service for merging ranked result lists
+///
+/// Combines the FTS and semantic rankings of hybrid search into one
+/// deduplicated list, either by reciprocal rank fusion or by a weighted
+/// sum of scores normalised against each list's best hit.
+use std::collections::HashMap;
+
+use crate::domain::ports::outbound::{FusionMethod, SearchRetriever, SearchWeights};
+
+/// Smoothing constant for reciprocal rank fusion (Cormack et al.)
+pub const RRF_K: f32 = 60.0;
+
+/// A single retriever hit: note id and the retriever's own score (higher is better)
+#[derive(Debug, Clone)]
+pub struct RankedHit {
+    pub note_id: String,
+    pub score: f32,
+}
+
+/// A fused hit with the retrievers that produced it
+#[derive(Debug, Clone)]
+pub struct FusedHit {
+    pub note_id: String,
+    pub score: f32,
+    pub retrievers: Vec<SearchRetriever>,
+}
+
+/// Pure domain service for rank fusion
+pub struct RankFusion;
+
+impl RankFusion {
+    /// Fuse FTS and semantic hits (each ordered best-first) into one ranking.
+    ///
+    /// Notes found by both retrievers appear once, with both retrievers recorded.
+    /// Ties are broken by the FTS order, then the semantic order.
+    pub fn fuse(
+        fts: &[RankedHit],
+        semantic: &[RankedHit],
+        weights: &SearchWeights,
+        method: FusionMethod,
+    ) -> Vec<FusedHit> {
+        let mut fused: Vec<FusedHit> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+
+        let lists = [
+            (SearchRetriever::Fts, fts, weights.fts.max(0.0)),
+            (SearchRetriever::Semantic, semantic, weights.semantic.max(0.0)),
+        ];
+
+        for (retriever, hits, weight) in lists {
+            let normalised = match method {
+                FusionMethod::Weighted => Self::max_normalise(hits),
+                FusionMethod::Rrf => Vec::new(),
+            };
+
+            for (rank, hit) in hits.iter().enumerate() {
+                let contribution = match method {
+                    FusionMethod::Rrf => weight / (RRF_K + rank as f32 + 1.0),
+                    FusionMethod::Weighted => weight * normalised[rank],
+                };
+
+                match positions.get(&hit.note_id) {
+                    Some(&index) => {
+                        let entry = &mut fused[index];
+                        entry.score += contribution;
+                        if !entry.retrievers.contains(&retriever) {
+                            entry.retrievers.push(retriever);
+                        }
+                    }
+                    None => {
+                        positions.insert(hit.note_id.clone(), fused.len());
+                        fused.push(FusedHit {
+                            note_id: hit.note_id.clone(),
+                            score: contribution,
+                            retrievers: vec![retriever],
+                        });
+                    }
+                }
+            }
+        }
+
+        // Stable sort keeps first-seen order for equal scores
+        fused.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        fused
+    }
+
+    /// Scale scores to [0, 1] relative to the list's best score
+    fn max_normalise(hits: &[RankedHit]) -> Vec<f32> {
+        let max = hits.iter().map(|h| h.score).fold(0.0_f32, f32::max);
+
+        hits.iter()
+            .map(|h| {
+                if max > f32::EPSILON {
+                    h.score.max(0.0) / max
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+}
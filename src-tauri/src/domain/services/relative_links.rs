@@ -0,0 +1,161 @@
+/// RelativeLinks - Pure domain service for relative markdown links
+///
+/// Markdown links and images, [text](../Ideas/Rust.md#setup), point at files
+/// relative to the note they are written in. When notes or folders move, the
+/// links that would no longer reach their file are rewritten from the new
+/// location. Paths are relative to the workspace root and use `/`.
+use regex::Regex;
+
+/// Markdown link or image: [text](destination "title")
+const MARKDOWN_LINK_PATTERN: &str = r"\[([^\]]*)\]\(([^)]+)\)";
+
+/// `scheme:` of an external link, like `https:` or `mailto:`
+const SCHEME_PATTERN: &str = r"^[A-Za-z][A-Za-z0-9+.-]*:";
+
+/// Destination of a markdown link, split into its parts
+struct Destination<'a> {
+    /// Path as written, without brackets or fragment
+    path: &'a str,
+    /// `#fragment`, if any
+    fragment: &'a str,
+    /// Title and whitespace after the destination
+    rest: &'a str,
+    /// Written as `<path>`
+    angle_brackets: bool,
+}
+
+/// RelativeLinks - Pure functions for resolving and rewriting relative links
+pub struct RelativeLinks;
+
+impl RelativeLinks {
+    /// Workspace path a link in `from_file` points at
+    ///
+    /// None for external links, links within the note, absolute paths and
+    /// paths leading out of the workspace.
+    pub fn resolve(from_file: &str, href: &str) -> Option<String> {
+        let scheme = Regex::new(SCHEME_PATTERN).unwrap();
+        let href = href.trim();
+        if href.is_empty() || href.starts_with(['#', '/', '\\']) || scheme.is_match(href) {
+            return None;
+        }
+
+        let mut segments = Self::segments(from_file);
+        segments.pop();
+        for segment in href.split('/') {
+            match segment {
+                "" | "." => {}
+                ".." => {
+                    segments.pop()?;
+                }
+                segment => segments.push(segment),
+            }
+        }
+
+        (!segments.is_empty()).then(|| segments.join("/"))
+    }
+
+    /// Relative path from `from_file` to the workspace path `to_path`
+    pub fn relative(from_file: &str, to_path: &str) -> String {
+        let mut from_dir = Self::segments(from_file);
+        from_dir.pop();
+        let to = Self::segments(to_path);
+
+        let common = from_dir.iter().zip(&to).take_while(|(a, b)| a == b).count();
+        let mut parts = vec![".."; from_dir.len() - common];
+        parts.extend(&to[common..]);
+        parts.join("/")
+    }
+
+    /// Where `path` ends up when the file or folder `from` moves to `to`
+    ///
+    /// None when the move doesn't touch `path`.
+    pub fn moved_path(path: &str, from: &str, to: &str) -> Option<String> {
+        let from = from.trim_matches('/');
+        let to = to.trim_matches('/');
+        if path == from {
+            return Some(to.to_string());
+        }
+        path.strip_prefix(from)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .map(|rest| format!("{}/{}", to, rest))
+    }
+
+    /// Rewrite the relative links of a note that moved, or whose targets did
+    ///
+    /// `old_location` and `new_location` are the note's paths before and after
+    /// the move; `moved` maps each linked path to where it is now. Links that
+    /// still reach their file are kept as written, as are fragments and link
+    /// titles. Returns None when no link was changed.
+    pub fn rewrite(
+        markdown: &str,
+        old_location: &str,
+        new_location: &str,
+        moved: impl Fn(&str) -> Option<String>,
+    ) -> Option<String> {
+        let pattern = Regex::new(MARKDOWN_LINK_PATTERN).unwrap();
+        let mut changed = false;
+        let mut rewritten = String::with_capacity(markdown.len());
+
+        for line in markdown.split_inclusive('\n') {
+            let line = pattern.replace_all(line, |caps: &regex::Captures| {
+                let Some(destination) = Self::parse_destination(&caps[2]) else {
+                    return caps[0].to_string();
+                };
+                let path = destination.path.replace("%20", " ");
+                let Some(target) = Self::resolve(old_location, &path) else {
+                    return caps[0].to_string();
+                };
+                let target = moved(&target).unwrap_or(target);
+                if Self::resolve(new_location, &path).as_deref() == Some(target.as_str()) {
+                    return caps[0].to_string();
+                }
+                changed = true;
+
+                let mut new_path = Self::relative(new_location, &target);
+                if !destination.angle_brackets {
+                    new_path = new_path.replace(' ', "%20");
+                }
+                let new_destination = if destination.angle_brackets {
+                    format!("<{}{}>", new_path, destination.fragment)
+                } else {
+                    format!("{}{}", new_path, destination.fragment)
+                };
+                format!("[{}]({}{})", &caps[1], new_destination, destination.rest)
+            });
+            rewritten.push_str(&line);
+        }
+
+        changed.then_some(rewritten)
+    }
+
+    fn parse_destination(raw: &str) -> Option<Destination<'_>> {
+        let raw = raw.trim();
+        let (destination, rest, angle_brackets) = match raw.strip_prefix('<') {
+            Some(inner) => {
+                let end = inner.find('>')?;
+                (&inner[..end], &inner[end + 1..], true)
+            }
+            None => {
+                let end = raw.find(char::is_whitespace).unwrap_or(raw.len());
+                (&raw[..end], &raw[end..], false)
+            }
+        };
+        let (path, fragment) = match destination.find('#') {
+            Some(index) => destination.split_at(index),
+            None => (destination, ""),
+        };
+
+        Some(Destination {
+            path,
+            fragment,
+            rest,
+            angle_brackets,
+        })
+    }
+
+    fn segments(path: &str) -> Vec<&str> {
+        path.split(['/', '\\'])
+            .filter(|segment| !segment.is_empty() && *segment != ".")
+            .collect()
+    }
+}
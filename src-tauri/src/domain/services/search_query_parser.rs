@@ -0,0 +1,392 @@
+/// SearchQueryParser - Pure domain service for structured search queries
+///
+/// Turns a search box string such as
+/// `tag:project/alpha notebook:Work is:pinned -draft "exact phrase" updated:>2026-01-01`
+/// into free-text terms plus `SearchOptions` filters.
+///
+/// Supported syntax:
+/// - `word` free-text term, `"exact phrase"` verbatim phrase
+/// - `-word` / `-"phrase"` exclusion
+/// - `tag:name` (repeatable, all must match), `notebook:Name` or `notebook:"Two Words"`
+/// - `is:pinned`, `is:favorite`, `is:archived`
+/// - `created:` / `updated:` with `2026-01-01`, `>`, `>=`, `<`, `<=` or `2026-01-01..2026-01-31`
+///
+/// Error positions are character offsets into the original query.
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::domain::ports::outbound::{DateRangeField, DateRangeOptions, SearchOptions};
+
+/// Structured parse error with the offending span
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQueryError {
+    pub message: String,
+    /// Start character offset (inclusive)
+    pub start: usize,
+    /// End character offset (exclusive)
+    pub end: usize,
+}
+
+impl SearchQueryError {
+    fn new(message: impl Into<String>, start: usize, end: usize) -> Self {
+        Self {
+            message: message.into(),
+            start,
+            end,
+        }
+    }
+}
+
+impl fmt::Display for SearchQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at {}..{})", self.message, self.start, self.end)
+    }
+}
+
+impl std::error::Error for SearchQueryError {}
+
+/// Result of parsing a search query
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedSearchQuery {
+    /// Free-text terms in query order
+    pub terms: Vec<String>,
+    /// Filters, phrases and exclusions extracted from the query
+    pub options: SearchOptions,
+}
+
+impl ParsedSearchQuery {
+    /// Free-text terms joined back into a single query string
+    pub fn text(&self) -> String {
+        self.terms.join(" ")
+    }
+}
+
+/// Raw token produced by the scanner
+#[derive(Debug)]
+enum Token {
+    Phrase {
+        text: String,
+        negated: bool,
+        start: usize,
+        end: usize,
+    },
+    Word {
+        text: String,
+        negated: bool,
+        start: usize,
+        end: usize,
+    },
+    Filter {
+        key: String,
+        value: String,
+        negated: bool,
+        start: usize,
+        value_start: usize,
+        end: usize,
+    },
+}
+
+const FILTER_KEYS: &[&str] = &["tag", "notebook", "is", "created", "updated"];
+
+/// Pure domain service for parsing search queries
+pub struct SearchQueryParser;
+
+impl SearchQueryParser {
+    /// Parse a query string into terms and search options
+    pub fn parse(query: &str) -> Result<ParsedSearchQuery, SearchQueryError> {
+        let mut parsed = ParsedSearchQuery::default();
+
+        for token in Self::tokenize(query)? {
+            match token {
+                Token::Phrase {
+                    text,
+                    negated,
+                    start,
+                    end,
+                } => {
+                    if text.trim().is_empty() {
+                        return Err(SearchQueryError::new("Empty phrase", start, end));
+                    }
+                    let target = if negated {
+                        &mut parsed.options.excluded_terms
+                    } else {
+                        &mut parsed.options.phrases
+                    };
+                    target.get_or_insert_with(Vec::new).push(text.trim().to_string());
+                }
+                Token::Word {
+                    text,
+                    negated,
+                    start,
+                    end,
+                } => {
+                    // Bare punctuation carries no searchable content
+                    if !text.chars().any(char::is_alphanumeric) {
+                        if negated {
+                            return Err(SearchQueryError::new(
+                                "Nothing to exclude after '-'",
+                                start,
+                                end,
+                            ));
+                        }
+                        continue;
+                    }
+                    if negated {
+                        parsed
+                            .options
+                            .excluded_terms
+                            .get_or_insert_with(Vec::new)
+                            .push(text);
+                    } else {
+                        parsed.terms.push(text);
+                    }
+                }
+                Token::Filter {
+                    key,
+                    value,
+                    negated,
+                    start,
+                    value_start,
+                    end,
+                } => {
+                    if negated {
+                        return Err(SearchQueryError::new(
+                            format!("Filter '{}:' cannot be negated", key),
+                            start,
+                            end,
+                        ));
+                    }
+                    if value.trim().is_empty() {
+                        return Err(SearchQueryError::new(
+                            format!("Missing value for '{}:'", key),
+                            start,
+                            end,
+                        ));
+                    }
+                    Self::apply_filter(&mut parsed.options, &key, value.trim(), start, value_start, end)?;
+                }
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Split the query into phrases, words and key:value filters
+    fn tokenize(query: &str) -> Result<Vec<Token>, SearchQueryError> {
+        let chars: Vec<char> = query.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i].is_whitespace() {
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            let negated = chars[i] == '-' && i + 1 < chars.len() && !chars[i + 1].is_whitespace();
+            if negated {
+                i += 1;
+            }
+
+            if chars[i] == '"' {
+                let (text, end) = Self::read_quoted(&chars, i, start)?;
+                tokens.push(Token::Phrase {
+                    text,
+                    negated,
+                    start,
+                    end,
+                });
+                i = end;
+                continue;
+            }
+
+            let word_start = i;
+            while i < chars.len() && !chars[i].is_whitespace() && chars[i] != ':' {
+                i += 1;
+            }
+            let head: String = chars[word_start..i].iter().collect();
+            let key = head.to_lowercase();
+
+            if i < chars.len() && chars[i] == ':' && FILTER_KEYS.contains(&key.as_str()) {
+                let value_start = i + 1;
+                let (value, end) = if value_start < chars.len() && chars[value_start] == '"' {
+                    Self::read_quoted(&chars, value_start, start)?
+                } else {
+                    let mut j = value_start;
+                    while j < chars.len() && !chars[j].is_whitespace() {
+                        j += 1;
+                    }
+                    (chars[value_start..j].iter().collect(), j)
+                };
+                tokens.push(Token::Filter {
+                    key,
+                    value,
+                    negated,
+                    start,
+                    value_start,
+                    end,
+                });
+                i = end;
+                continue;
+            }
+
+            // Not a filter: consume the rest of the word (e.g. URLs, times)
+            while i < chars.len() && !chars[i].is_whitespace() {
+                i += 1;
+            }
+            tokens.push(Token::Word {
+                text: chars[word_start..i].iter().collect(),
+                negated,
+                start,
+                end: i,
+            });
+        }
+
+        Ok(tokens)
+    }
+
+    /// Read a double-quoted string starting at `quote`; returns (content, end offset)
+    fn read_quoted(
+        chars: &[char],
+        quote: usize,
+        token_start: usize,
+    ) -> Result<(String, usize), SearchQueryError> {
+        let content_start = quote + 1;
+        match chars[content_start..].iter().position(|&c| c == '"') {
+            Some(len) => Ok((
+                chars[content_start..content_start + len].iter().collect(),
+                content_start + len + 1,
+            )),
+            None => Err(SearchQueryError::new(
+                "Unterminated quote",
+                token_start,
+                chars.len(),
+            )),
+        }
+    }
+
+    fn apply_filter(
+        options: &mut SearchOptions,
+        key: &str,
+        value: &str,
+        start: usize,
+        value_start: usize,
+        end: usize,
+    ) -> Result<(), SearchQueryError> {
+        match key {
+            "tag" => {
+                let name = value.trim_start_matches('#').to_lowercase();
+                options.tag_names.get_or_insert_with(Vec::new).push(name);
+            }
+            "notebook" => {
+                if options.notebook_name.is_some() {
+                    return Err(SearchQueryError::new(
+                        "Only one 'notebook:' filter is allowed",
+                        start,
+                        end,
+                    ));
+                }
+                options.notebook_name = Some(value.to_string());
+            }
+            "is" => match value.to_lowercase().as_str() {
+                "pinned" => options.is_pinned = Some(true),
+                "favorite" | "favourite" | "starred" => options.is_favorite = Some(true),
+                "archived" => options.is_archived = Some(true),
+                other => {
+                    return Err(SearchQueryError::new(
+                        format!(
+                            "Unknown value '{}' for 'is:' (expected pinned, favorite or archived)",
+                            other
+                        ),
+                        value_start,
+                        end,
+                    ))
+                }
+            },
+            "created" | "updated" => {
+                let field = if key == "created" {
+                    DateRangeField::Created
+                } else {
+                    DateRangeField::Updated
+                };
+                let range = Self::parse_date_range(value, field, value_start, end)?;
+                options.date_ranges.get_or_insert_with(Vec::new).push(range);
+            }
+            _ => unreachable!("filter keys are checked by the tokenizer"),
+        }
+
+        Ok(())
+    }
+
+    /// Parse `D`, `>D`, `>=D`, `<D`, `<=D` or `D1..D2` into an inclusive range
+    fn parse_date_range(
+        value: &str,
+        field: DateRangeField,
+        value_start: usize,
+        end: usize,
+    ) -> Result<DateRangeOptions, SearchQueryError> {
+        let parse_date = |text: &str, offset: usize| -> Result<NaiveDate, SearchQueryError> {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(|_| {
+                SearchQueryError::new(
+                    format!("Invalid date '{}' (expected YYYY-MM-DD)", text),
+                    offset,
+                    offset + text.chars().count(),
+                )
+            })
+        };
+
+        let (start_date, end_date) = if let Some((from, to)) = value.split_once("..") {
+            let to_offset = value_start + from.chars().count() + 2;
+            (
+                Self::start_of_day(parse_date(from, value_start)?),
+                Self::end_of_day(parse_date(to, to_offset)?),
+            )
+        } else if let Some(date) = value.strip_prefix(">=") {
+            (Self::start_of_day(parse_date(date, value_start + 2)?), DateTime::<Utc>::MAX_UTC)
+        } else if let Some(date) = value.strip_prefix("<=") {
+            (DateTime::<Utc>::MIN_UTC, Self::end_of_day(parse_date(date, value_start + 2)?))
+        } else if let Some(date) = value.strip_prefix('>') {
+            (
+                Self::end_of_day(parse_date(date, value_start + 1)?) + Duration::seconds(1),
+                DateTime::<Utc>::MAX_UTC,
+            )
+        } else if let Some(date) = value.strip_prefix('<') {
+            (
+                DateTime::<Utc>::MIN_UTC,
+                Self::start_of_day(parse_date(date, value_start + 1)?) - Duration::seconds(1),
+            )
+        } else {
+            let date = parse_date(value, value_start)?;
+            (Self::start_of_day(date), Self::end_of_day(date))
+        };
+
+        if start_date > end_date {
+            return Err(SearchQueryError::new(
+                "Date range ends before it starts",
+                value_start,
+                end,
+            ));
+        }
+
+        Ok(DateRangeOptions {
+            start_date,
+            end_date,
+            workspace_id: None,
+            field: Some(field),
+            limit: None,
+            offset: None,
+        })
+    }
+
+    fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+        date.and_hms_opt(0, 0, 0).unwrap().and_utc()
+    }
+
+    fn end_of_day(date: NaiveDate) -> DateTime<Utc> {
+        date.and_hms_opt(23, 59, 59).unwrap().and_utc()
+    }
+}
@@ -96,3 +96,22 @@ pub fn normalize_vector(vec: &[f32]) -> Vec<f32> {
     vec.iter().map(|x| x / magnitude).collect()
 }
 
+
+/// Average a set of vectors and normalize the result to unit length
+///
+/// Returns None when there are no vectors or their lengths differ.
+pub fn mean_vector(vectors: &[Vec<f32>]) -> Option<Vec<f32>> {
+    let dimensions = vectors.first()?.len();
+    if dimensions == 0 || vectors.iter().any(|v| v.len() != dimensions) {
+        return None;
+    }
+
+    let mut sum = vec![0.0f32; dimensions];
+    for vector in vectors {
+        for (total, value) in sum.iter_mut().zip(vector) {
+            *total += value;
+        }
+    }
+
+    Some(normalize_vector(&sum))
+}
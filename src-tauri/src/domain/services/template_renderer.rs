@@ -0,0 +1,184 @@
+/// TemplateRenderer - Pure domain service for note templates
+///
+/// Expands `{{...}}` placeholders in a template and reads the frontmatter
+/// defaults it carries. Dates use Moment-style formats (`YYYY-MM-DD`,
+/// `HH:mm`), with `[...]` for literal text.
+use chrono::{Datelike, NaiveDateTime};
+
+use super::frontmatter::{Frontmatter, NoteFrontmatter, YamlValue};
+
+/// Format of `{{date}}` without an explicit format
+pub const DEFAULT_DATE_FORMAT: &str = "YYYY-MM-DD";
+
+/// Format of `{{time}}` without an explicit format
+pub const DEFAULT_TIME_FORMAT: &str = "HH:mm";
+
+/// Frontmatter keys that configure the new note and are not copied into it
+const TEMPLATE_ONLY_KEYS: &[&str] = &["notebook"];
+
+/// Format tokens, longest first so `MMMM` wins over `MM`
+const DATE_TOKENS: &[(&str, &str)] = &[
+    ("YYYY", "%Y"),
+    ("GGGG", "%G"),
+    ("MMMM", "%B"),
+    ("dddd", "%A"),
+    ("DDDD", "%j"),
+    ("MMM", "%b"),
+    ("ddd", "%a"),
+    ("YY", "%y"),
+    ("MM", "%m"),
+    ("DD", "%d"),
+    ("WW", "%V"),
+    ("HH", "%H"),
+    ("hh", "%I"),
+    ("mm", "%M"),
+    ("ss", "%S"),
+    ("M", "%-m"),
+    ("D", "%-d"),
+    ("W", "%-V"),
+    ("H", "%-H"),
+    ("h", "%-I"),
+    ("d", "%w"),
+    ("A", "%p"),
+    ("a", "%P"),
+];
+
+/// Values placeholders expand to
+#[derive(Debug, Clone)]
+pub struct TemplateContext {
+    pub title: String,
+    /// Name of the workspace the note is created in
+    pub workspace: String,
+    pub now: NaiveDateTime,
+}
+
+/// A template with its placeholders expanded
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderedTemplate {
+    /// Markdown of the new note, without template-only frontmatter keys
+    pub content: String,
+    /// Byte offset in `content` where `{{cursor}}` stood
+    pub cursor: Option<usize>,
+    /// Tags from the template's frontmatter
+    pub tags: Vec<String>,
+    /// Notebook (ID or name) from the template's frontmatter
+    pub notebook: Option<String>,
+}
+
+/// TemplateRenderer - Pure functions for note templates
+pub struct TemplateRenderer;
+
+impl TemplateRenderer {
+    /// Expand a template for a new note
+    ///
+    /// Placeholders are `{{title}}`, `{{workspace}}`, `{{date}}`, `{{time}}`
+    /// (both optionally with a format, like `{{date:dddd D MMMM}}`) and
+    /// `{{cursor}}`, which is removed and reported as an offset. Unknown
+    /// placeholders are left as they are.
+    pub fn render(template: &str, context: &TemplateContext) -> RenderedTemplate {
+        let (frontmatter, body) = Frontmatter::split(template);
+
+        let mut rendered = RenderedTemplate::default();
+        if let Some(frontmatter) = frontmatter {
+            let (yaml, _) = Self::expand(frontmatter, context);
+            match Frontmatter::parse(&yaml) {
+                Ok(mut parsed) => {
+                    rendered.tags = NoteFrontmatter::from_frontmatter(&parsed)
+                        .tags
+                        .unwrap_or_default();
+                    rendered.notebook = parsed
+                        .get("notebook")
+                        .and_then(YamlValue::as_text)
+                        .map(|notebook| notebook.trim().to_string())
+                        .filter(|notebook| !notebook.is_empty());
+                    for key in TEMPLATE_ONLY_KEYS {
+                        parsed.remove(key);
+                    }
+                    rendered.content = parsed.write_to("");
+                }
+                // Frontmatter that isn't valid YAML is copied as it is
+                Err(_) => rendered.content = format!("---\n{}\n---\n", yaml),
+            }
+        }
+
+        let (body, cursor) = Self::expand(body, context);
+        rendered.cursor = cursor.map(|offset| rendered.content.len() + offset);
+        rendered.content.push_str(&body);
+        rendered
+    }
+
+    /// Format a date with a Moment-style pattern
+    ///
+    /// Supported tokens are `YYYY YY GGGG Q MMMM MMM MM M DDDD DD D dddd ddd d
+    /// WW W HH H hh h mm ss A a`; anything in `[...]` is copied verbatim.
+    pub fn format_date(date: &NaiveDateTime, pattern: &str) -> String {
+        let mut output = String::new();
+        let mut rest = pattern;
+
+        'outer: while let Some(c) = rest.chars().next() {
+            if c == '[' {
+                if let Some(end) = rest.find(']') {
+                    output.push_str(&rest[1..end]);
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+            if c == 'Q' {
+                output.push_str(&((date.month0() / 3) + 1).to_string());
+                rest = &rest[1..];
+                continue;
+            }
+            for (token, strftime) in DATE_TOKENS {
+                if let Some(after) = rest.strip_prefix(token) {
+                    output.push_str(&date.format(strftime).to_string());
+                    rest = after;
+                    continue 'outer;
+                }
+            }
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+
+        output
+    }
+
+    /// Expand placeholders, returning the text and where `{{cursor}}` stood
+    fn expand(text: &str, context: &TemplateContext) -> (String, Option<usize>) {
+        let mut output = String::with_capacity(text.len());
+        let mut cursor = None;
+        let mut rest = text;
+
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            output.push_str(&rest[..start]);
+            let placeholder = &rest[start + 2..start + 2 + len];
+            let (name, format) = match placeholder.split_once(':') {
+                Some((name, format)) => (name.trim(), Some(format.trim())),
+                None => (placeholder.trim(), None),
+            };
+
+            match name {
+                "title" => output.push_str(&context.title),
+                "workspace" => output.push_str(&context.workspace),
+                "date" => output.push_str(&Self::format_date(
+                    &context.now,
+                    format.unwrap_or(DEFAULT_DATE_FORMAT),
+                )),
+                "time" => output.push_str(&Self::format_date(
+                    &context.now,
+                    format.unwrap_or(DEFAULT_TIME_FORMAT),
+                )),
+                "cursor" => {
+                    cursor.get_or_insert(output.len());
+                }
+                _ => output.push_str(&rest[start..start + len + 4]),
+            }
+            rest = &rest[start + len + 4..];
+        }
+
+        output.push_str(rest);
+        (output, cursor)
+    }
+}
@@ -0,0 +1,171 @@
+/// TopicClassifier - Pure domain service for centroid-based topic classification
+///
+/// A topic's centroid is the weighted mean of its notes' embeddings. Manual
+/// assignments carry more weight than automatic ones so that user decisions
+/// shape the topic rather than the classifier's own guesses. Notes are
+/// classified by cosine similarity to each centroid.
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{
+    entities::Topic,
+    errors::{DomainError, DomainResult},
+};
+
+use super::similarity_calculator::{cosine_similarity, normalize_vector};
+
+/// Settings key holding the classification tuning, as JSON; read at startup
+pub const CLASSIFICATION_SETTING: &str = "topics.classification";
+
+/// Classification tuning
+///
+/// Fields left out of a stored setting keep their defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClassificationConfig {
+    /// Minimum cosine similarity for a topic to be assigned
+    pub threshold: f32,
+    /// Maximum number of topics assigned to a note
+    pub top_k: usize,
+    /// Weight of a manually assigned note in its topic's centroid
+    /// (automatic assignments are weighted by their confidence, at most 1.0)
+    pub manual_weight: f32,
+}
+
+impl Default for ClassificationConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            top_k: 3,
+            manual_weight: 3.0,
+        }
+    }
+}
+
+impl ClassificationConfig {
+    /// Parse and check the value stored under `CLASSIFICATION_SETTING`
+    pub fn from_setting(value: &str) -> DomainResult<Self> {
+        let config: Self = serde_json::from_str(value).map_err(|e| {
+            DomainError::ConfigurationError(format!("Invalid classification setting: {}", e))
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check the tuning is usable
+    pub fn validate(&self) -> DomainResult<()> {
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(DomainError::ValidationError(format!(
+                "Classification threshold must be between 0 and 1: {}",
+                self.threshold
+            )));
+        }
+        if self.top_k == 0 {
+            return Err(DomainError::ValidationError(
+                "At least one topic must be assignable".to_string(),
+            ));
+        }
+        if !(self.manual_weight.is_finite() && self.manual_weight > 0.0) {
+            return Err(DomainError::ValidationError(format!(
+                "Manual weight must be positive: {}",
+                self.manual_weight
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A topic that matched a note
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopicMatch {
+    pub topic_id: String,
+    pub topic_name: String,
+    pub confidence: f32,
+}
+
+/// A note's contribution to a topic centroid
+#[derive(Debug, Clone)]
+pub struct CentroidMember {
+    pub embedding: Vec<f32>,
+    pub is_manual: bool,
+    pub confidence: f32,
+}
+
+/// TopicClassifier - Scores notes against topic centroids
+pub struct TopicClassifier {
+    config: ClassificationConfig,
+}
+
+impl Default for TopicClassifier {
+    fn default() -> Self {
+        Self::new(ClassificationConfig::default())
+    }
+}
+
+impl TopicClassifier {
+    pub fn new(config: ClassificationConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &ClassificationConfig {
+        &self.config
+    }
+
+    /// Best matching topics above the threshold, highest confidence first
+    ///
+    /// Topics without a centroid, or with one of different dimensions, are skipped.
+    pub fn classify(&self, embedding: &[f32], topics: &[Topic]) -> Vec<TopicMatch> {
+        let mut matches: Vec<TopicMatch> = topics
+            .iter()
+            .filter_map(|topic| {
+                let centroid = topic.centroid_vector()?;
+                let confidence = cosine_similarity(embedding, &centroid).ok()?;
+                (confidence >= self.config.threshold).then(|| TopicMatch {
+                    topic_id: topic.id.clone(),
+                    topic_name: topic.name.clone(),
+                    confidence,
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.confidence
+                .partial_cmp(&a.confidence)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        matches.truncate(self.config.top_k);
+        matches
+    }
+
+    /// Weighted, normalized mean of a topic's member embeddings
+    ///
+    /// Returns None when there are no usable members.
+    pub fn centroid(&self, members: &[CentroidMember]) -> Option<Vec<f32>> {
+        let dimensions = members.first()?.embedding.len();
+        let mut sum = vec![0.0f32; dimensions];
+        let mut total_weight = 0.0f32;
+
+        for member in members {
+            if member.embedding.len() != dimensions {
+                continue;
+            }
+            let weight = self.weight(member);
+            for (total, value) in sum.iter_mut().zip(&member.embedding) {
+                *total += value * weight;
+            }
+            total_weight += weight;
+        }
+
+        if dimensions == 0 || total_weight <= 0.0 {
+            return None;
+        }
+        Some(normalize_vector(&sum))
+    }
+
+    fn weight(&self, member: &CentroidMember) -> f32 {
+        if member.is_manual {
+            self.config.manual_weight.max(0.0)
+        } else {
+            member.confidence.clamp(0.0, 1.0)
+        }
+    }
+}
@@ -0,0 +1,391 @@
+/// TopicDiscovery - Pure domain service for proposing topics from note embeddings
+///
+/// Clusters note embeddings with spherical k-means (cosine similarity),
+/// choosing k by the simplified silhouette score, and labels each cluster
+/// with the terms that are frequent in its notes but rare elsewhere (TF-IDF).
+use std::collections::{HashMap, HashSet};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::similarity_calculator::normalize_vector;
+
+/// Words too common to describe a topic
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "again", "all", "also", "and", "any", "are", "because", "been", "before",
+    "being", "between", "both", "but", "can", "could", "did", "does", "doing", "done", "down",
+    "each", "even", "few", "for", "from", "further", "get", "got", "had", "has", "have", "having",
+    "her", "here", "hers", "him", "his", "how", "http", "https", "into", "its", "just", "like",
+    "make", "many", "more", "most", "much", "must", "need", "not", "now", "off", "once", "one",
+    "only", "other", "our", "out", "over", "own", "same", "she", "should", "some", "such", "than",
+    "that", "the", "their", "them", "then", "there", "these", "they", "this", "those", "through",
+    "too", "two", "under", "until", "use", "used", "using", "very", "was", "way", "were", "what",
+    "when", "where", "which", "while", "who", "why", "will", "with", "would", "www", "yet", "you",
+    "your",
+];
+
+/// Clustering and labelling tuning
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveryConfig {
+    /// Smallest number of clusters tried
+    pub min_clusters: usize,
+    /// Largest number of clusters tried
+    pub max_clusters: usize,
+    /// Clusters with fewer notes are not proposed
+    pub min_cluster_size: usize,
+    pub max_iterations: usize,
+    /// Number of label terms per cluster
+    pub label_terms: usize,
+    /// Seed for centroid initialisation, so results are reproducible
+    pub seed: u64,
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            min_clusters: 2,
+            max_clusters: 12,
+            min_cluster_size: 3,
+            max_iterations: 50,
+            label_terms: 3,
+            seed: 42,
+        }
+    }
+}
+
+/// A group of similar embeddings
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddingCluster {
+    /// Indices into the clustered embeddings
+    pub members: Vec<usize>,
+    /// Cosine similarity of each member to the centroid, in member order
+    pub similarities: Vec<f32>,
+    /// Normalized mean of the members
+    pub centroid: Vec<f32>,
+    /// Mean similarity of the members to the centroid (0..1 for related notes)
+    pub cohesion: f32,
+}
+
+/// TopicDiscovery - Clusters embeddings and labels the clusters
+pub struct TopicDiscovery {
+    config: DiscoveryConfig,
+}
+
+impl Default for TopicDiscovery {
+    fn default() -> Self {
+        Self::new(DiscoveryConfig::default())
+    }
+}
+
+impl TopicDiscovery {
+    pub fn new(config: DiscoveryConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &DiscoveryConfig {
+        &self.config
+    }
+
+    /// Cluster embeddings, most cohesive cluster first
+    ///
+    /// The number of clusters is picked automatically within the configured
+    /// range. Embeddings that are empty, zero or of a different dimension than
+    /// the first are ignored, as are clusters below the minimum size.
+    pub fn cluster(&self, embeddings: &[Vec<f32>]) -> Vec<EmbeddingCluster> {
+        let dimensions = embeddings.first().map_or(0, Vec::len);
+        let points: Vec<(usize, Vec<f32>)> = embeddings
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.len() == dimensions && e.iter().any(|v| *v != 0.0))
+            .map(|(i, e)| (i, normalize_vector(e)))
+            .collect();
+
+        let min_size = self.config.min_cluster_size.max(1);
+        let max_k = self.config.max_clusters.min(points.len() / min_size);
+        let min_k = self.config.min_clusters.max(1);
+        if dimensions == 0 || max_k < min_k {
+            return Vec::new();
+        }
+
+        let vectors: Vec<&[f32]> = points.iter().map(|(_, v)| v.as_slice()).collect();
+        let mut best: Option<(f32, Vec<usize>, Vec<Vec<f32>>)> = None;
+        for k in min_k..=max_k {
+            let (assignments, centroids) = self.kmeans(&vectors, k);
+            let score = Self::silhouette(&vectors, &assignments, &centroids);
+            // Ties go to the smaller k
+            if best
+                .as_ref()
+                .is_none_or(|(best_score, _, _)| score > *best_score)
+            {
+                best = Some((score, assignments, centroids));
+            }
+        }
+        let Some((_, assignments, centroids)) = best else {
+            return Vec::new();
+        };
+
+        let mut clusters: Vec<EmbeddingCluster> = centroids
+            .into_iter()
+            .enumerate()
+            .filter_map(|(cluster, centroid)| {
+                let (members, similarities): (Vec<usize>, Vec<f32>) = assignments
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, assigned)| **assigned == cluster)
+                    .map(|(point, _)| (points[point].0, dot(vectors[point], &centroid)))
+                    .unzip();
+                if members.len() < min_size {
+                    return None;
+                }
+                let cohesion = similarities.iter().sum::<f32>() / similarities.len() as f32;
+                Some(EmbeddingCluster {
+                    members,
+                    similarities,
+                    centroid,
+                    cohesion,
+                })
+            })
+            .collect();
+
+        clusters.sort_by(|a, b| {
+            b.cohesion
+                .partial_cmp(&a.cohesion)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.members.cmp(&b.members))
+        });
+        clusters
+    }
+
+    /// Top TF-IDF terms of each cluster
+    ///
+    /// `documents` holds the text of every clustered note, indexed like the
+    /// embeddings passed to `cluster`. A term must occur in at least two
+    /// members of a cluster (unless it has only one) to label it.
+    pub fn label(&self, documents: &[String], clusters: &[EmbeddingCluster]) -> Vec<Vec<String>> {
+        let terms: Vec<HashMap<String, usize>> = documents
+            .iter()
+            .map(|document| {
+                let mut counts = HashMap::new();
+                for term in Self::tokenize(document) {
+                    *counts.entry(term).or_insert(0) += 1;
+                }
+                counts
+            })
+            .collect();
+
+        let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+        for counts in &terms {
+            for term in counts.keys() {
+                *document_frequency.entry(term.as_str()).or_insert(0) += 1;
+            }
+        }
+        let total_documents = documents.len() as f32;
+
+        clusters
+            .iter()
+            .map(|cluster| {
+                let mut scores: HashMap<&str, (f32, usize)> = HashMap::new();
+                for counts in cluster.members.iter().filter_map(|m| terms.get(*m)) {
+                    let length = counts.values().sum::<usize>().max(1) as f32;
+                    for (term, count) in counts {
+                        let entry = scores.entry(term.as_str()).or_insert((0.0, 0));
+                        entry.0 += *count as f32 / length;
+                        entry.1 += 1;
+                    }
+                }
+
+                let min_members = cluster.members.len().min(2);
+                let mut ranked: Vec<(&str, f32)> = scores
+                    .into_iter()
+                    .filter(|(_, (_, members))| *members >= min_members)
+                    .map(|(term, (tf, _))| {
+                        let idf = (total_documents / document_frequency[term] as f32).ln();
+                        (term, tf * idf)
+                    })
+                    .filter(|(_, score)| *score > 0.0)
+                    .collect();
+                ranked.sort_by(|a, b| {
+                    b.1.partial_cmp(&a.1)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                        .then_with(|| a.0.cmp(b.0))
+                });
+
+                ranked
+                    .into_iter()
+                    .take(self.config.label_terms)
+                    .map(|(term, _)| term.to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Topic name for a set of label terms, e.g. "Rust, Ownership"
+    pub fn name_for(terms: &[String]) -> Option<String> {
+        let words: Vec<String> = terms
+            .iter()
+            .map(|term| {
+                let mut chars = term.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .filter(|word| !word.is_empty())
+            .collect();
+        (!words.is_empty()).then(|| words.join(", "))
+    }
+
+    /// Lowercased words of three or more letters, minus stop words and numbers
+    pub fn tokenize(text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| word.chars().count() >= 3)
+            .filter(|word| !word.chars().all(|c| c.is_numeric()))
+            .map(str::to_lowercase)
+            .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+            .collect()
+    }
+
+    /// Spherical k-means on normalized vectors
+    fn kmeans(&self, vectors: &[&[f32]], k: usize) -> (Vec<usize>, Vec<Vec<f32>>) {
+        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        let mut centroids = Self::initial_centroids(vectors, k, &mut rng);
+        let mut assignments = vec![usize::MAX; vectors.len()];
+
+        for _ in 0..self.config.max_iterations.max(1) {
+            let mut changed = false;
+            for (point, vector) in vectors.iter().enumerate() {
+                let nearest = Self::nearest(vector, &centroids);
+                if assignments[point] != nearest {
+                    assignments[point] = nearest;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+
+            let mut used = HashSet::new();
+            for (cluster, centroid) in centroids.iter_mut().enumerate() {
+                let mut sum = vec![0.0f32; centroid.len()];
+                let mut count = 0;
+                for (point, vector) in vectors.iter().enumerate() {
+                    if assignments[point] == cluster {
+                        for (total, value) in sum.iter_mut().zip(vector.iter()) {
+                            *total += value;
+                        }
+                        count += 1;
+                    }
+                }
+                if count > 0 {
+                    *centroid = normalize_vector(&sum);
+                    used.insert(cluster);
+                }
+            }
+
+            // Restart empty clusters at the point that fits its cluster worst
+            for cluster in 0..k {
+                if used.contains(&cluster) {
+                    continue;
+                }
+                let worst = (0..vectors.len())
+                    .filter(|point| assignments[*point] != usize::MAX)
+                    .min_by(|a, b| {
+                        let fit_a = dot(vectors[*a], &centroids[assignments[*a]]);
+                        let fit_b = dot(vectors[*b], &centroids[assignments[*b]]);
+                        fit_a
+                            .partial_cmp(&fit_b)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    });
+                if let Some(point) = worst {
+                    centroids[cluster] = vectors[point].to_vec();
+                    assignments[point] = cluster;
+                }
+            }
+        }
+
+        (assignments, centroids)
+    }
+
+    /// k-means++ seeding: spread initial centroids apart
+    fn initial_centroids(vectors: &[&[f32]], k: usize, rng: &mut StdRng) -> Vec<Vec<f32>> {
+        let mut centroids = vec![vectors[rng.gen_range(0..vectors.len())].to_vec()];
+        let mut distances: Vec<f32> = vectors
+            .iter()
+            .map(|v| cosine_distance(v, &centroids[0]))
+            .collect();
+
+        while centroids.len() < k {
+            let weights: Vec<f32> = distances.iter().map(|d| d * d).collect();
+            let total: f32 = weights.iter().sum();
+            let next = if total <= 0.0 {
+                rng.gen_range(0..vectors.len())
+            } else {
+                let mut target = rng.gen::<f32>() * total;
+                weights
+                    .iter()
+                    .position(|w| {
+                        target -= w;
+                        target <= 0.0
+                    })
+                    .unwrap_or(vectors.len() - 1)
+            };
+
+            let centroid = vectors[next].to_vec();
+            for (distance, vector) in distances.iter_mut().zip(vectors) {
+                *distance = distance.min(cosine_distance(vector, &centroid));
+            }
+            centroids.push(centroid);
+        }
+
+        centroids
+    }
+
+    fn nearest(vector: &[f32], centroids: &[Vec<f32>]) -> usize {
+        centroids
+            .iter()
+            .map(|centroid| dot(vector, centroid))
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map_or(0, |(cluster, _)| cluster)
+    }
+
+    /// Mean simplified silhouette: distance to the own centroid against the
+    /// distance to the nearest other centroid, from -1 (wrong) to 1 (clear)
+    fn silhouette(vectors: &[&[f32]], assignments: &[usize], centroids: &[Vec<f32>]) -> f32 {
+        if centroids.len() < 2 || vectors.is_empty() {
+            return 0.0;
+        }
+
+        let total: f32 = vectors
+            .iter()
+            .zip(assignments)
+            .map(|(vector, own)| {
+                let a = cosine_distance(vector, &centroids[*own]);
+                let b = centroids
+                    .iter()
+                    .enumerate()
+                    .filter(|(cluster, _)| cluster != own)
+                    .map(|(_, centroid)| cosine_distance(vector, centroid))
+                    .fold(f32::INFINITY, f32::min);
+                let scale = a.max(b);
+                if scale > 0.0 {
+                    (b - a) / scale
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+
+        total / vectors.len() as f32
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Cosine distance between normalized vectors
+fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
+    (1.0 - dot(a, b)).max(0.0)
+}
@@ -14,20 +14,43 @@ use crate::{
             storage::TokioFileStorage,
         },
     },
-    application::usecases::*,
-    domain::errors::DomainResult,
+    application::{
+        services::{
+            EmbeddingQueue, LinkRefactorService, NoteFilenameService, NoteIndexingService,
+            NoteMetadataService, NoteRenameService, PeriodicNoteService, TransclusionService,
+        },
+        usecases::*,
+    },
+    domain::{
+        errors::DomainResult,
+        ports::outbound::{
+            EmbeddingProvider, EmbeddingProviderConfig, SettingsRepository,
+            EMBEDDING_PROVIDER_SETTING, EMBEDDING_QUANTIZATION_SETTING,
+        },
+        services::{ClassificationConfig, CLASSIFICATION_SETTING},
+    },
+    infrastructure::AppPaths,
     shared::database::DbPool,
 };
 
+/// File name of the persisted embedding index inside the search index directory
+const VECTOR_INDEX_FILE: &str = "embeddings.hnsw";
+
 /// Container for all application dependencies
 pub struct Container {
     pub app_state: AppState,
     pub file_watcher: Arc<dyn crate::domain::ports::outbound::FileWatcher>,
+    pub indexing_service: Arc<NoteIndexingService>,
+    pub embedding_service: Arc<FastEmbedService>,
 }
 
 impl Container {
     /// Build the dependency injection container
-    pub async fn build(pool: Arc<DbPool>, app_handle: AppHandle) -> DomainResult<Self> {
+    pub async fn build(
+        pool: Arc<DbPool>,
+        app_handle: AppHandle,
+        paths: &AppPaths,
+    ) -> DomainResult<Self> {
         tracing::info!("Building dependency injection container...");
 
         // === Repositories ===
@@ -40,14 +63,39 @@ impl Container {
         let version_repository = Arc::new(DieselVersionRepository::new(pool.clone()));
         let settings_repository = Arc::new(DieselSettingsRepository::new(pool.clone()));
         let link_repository = Arc::new(DieselNoteLinkRepository::new(pool.clone()));
+        let chunk_repository = Arc::new(DieselNoteChunkRepository::new(pool.clone()));
+        let block_repository = Arc::new(DieselNoteBlockRepository::new(pool.clone()));
+        let embedding_quantization =
+            Self::embedding_quantization(settings_repository.as_ref()).await?;
+        let embedding_repository = Arc::new(
+            DieselEmbeddingRepository::new(pool.clone()).with_quantization(embedding_quantization),
+        );
 
         // === Services ===
         let file_storage = Arc::new(TokioFileStorage::new());
         let markdown_processor = Arc::new(PulldownMarkdownService::new());
         let system_service = Arc::new(TauriSystemService::new(app_handle.clone()));
         let git_service = Arc::new(Git2Service::new());
-        let search_engine = Arc::new(StubSearchService::new(pool.clone()));
-        let embedding_service = Arc::new(FastEmbedService::new(note_repository.clone()));
+        let embedding_provider = Self::embedding_provider(settings_repository.as_ref()).await;
+        let classification = Self::classification_config(settings_repository.as_ref()).await;
+        let embedding_service = Arc::new(
+            FastEmbedService::new(
+                note_repository.clone(),
+                chunk_repository.clone(),
+                embedding_repository.clone(),
+                topic_repository.clone(),
+            )
+                .with_provider(embedding_provider)
+                .with_classification_config(classification)
+                .with_index_path(paths.search_index_dir.join(VECTOR_INDEX_FILE)),
+        );
+        let search_engine = Arc::new(Fts5SearchService::new(
+            pool.clone(),
+            file_storage.clone(),
+            markdown_processor.clone(),
+            embedding_service.clone(),
+        ));
+        let chat_service = Arc::new(OpenAiChatService::new());
         let export_service = Arc::new(StubExportService::new());
         let database_path = std::env::var("STONE_DB_PATH")
             .unwrap_or_else(|_| "stone.db".to_string());
@@ -58,12 +106,81 @@ impl Container {
         let event_publisher_opt: Option<Arc<dyn crate::domain::ports::outbound::EventPublisher>> = Some(event_publisher.clone());
         let file_watcher = Arc::new(NotifyFileWatcher::new(event_publisher.clone()));
 
+        // Background indexing (started once the initial sync is done)
+        let indexing_service = Arc::new(NoteIndexingService::new(
+            note_repository.clone(),
+            workspace_repository.clone(),
+            block_repository.clone(),
+            file_storage.clone(),
+            markdown_processor.clone(),
+            search_engine.clone(),
+            embedding_service.clone(),
+            event_publisher.clone(),
+        ));
+
+        // Bulk embedding of un-embedded and out-of-date notes
+        let embedding_queue = Arc::new(EmbeddingQueue::new(
+            note_repository.clone(),
+            workspace_repository.clone(),
+            file_storage.clone(),
+            markdown_processor.clone(),
+            embedding_service.clone(),
+            event_publisher.clone(),
+        ));
+
+        // Daily, weekly, monthly and quarterly notes
+        let periodic_note_service = Arc::new(PeriodicNoteService::new(
+            note_repository.clone(),
+            workspace_repository.clone(),
+            settings_repository.clone(),
+            file_storage.clone(),
+            event_publisher_opt.clone(),
+        ));
+
+        // Sections and blocks embedded with ![[Note#Heading]]
+        let transclusion_service = Arc::new(TransclusionService::new(
+            note_repository.clone(),
+            workspace_repository.clone(),
+            file_storage.clone(),
+        ));
+        let link_refactor_service = Arc::new(LinkRefactorService::new(file_storage.clone()));
+
+        // File names of new and retitled notes
+        let note_filename_service = Arc::new(NoteFilenameService::new(
+            note_repository.clone(),
+            settings_repository.clone(),
+            file_storage.clone(),
+            periodic_note_service.clone(),
+            git_service.clone(),
+            link_refactor_service.clone(),
+        ));
+
+        // Tags and properties from frontmatter, and tags written back into it
+        let note_metadata_service = Arc::new(NoteMetadataService::new(
+            note_repository.clone(),
+            workspace_repository.clone(),
+            tag_repository.clone(),
+            file_storage.clone(),
+        ));
+
+        // Titles changed along with the links to them
+        let note_rename_service = Arc::new(NoteRenameService::new(
+            note_repository.clone(),
+            link_repository.clone(),
+            link_refactor_service.clone(),
+            note_filename_service.clone(),
+        ));
+
         // === Use Cases ===
         let note_usecases = Arc::new(NoteUseCasesImpl::new(
             note_repository.clone(),
             workspace_repository.clone(),
             file_storage.clone(),
             markdown_processor.clone(),
+            periodic_note_service.clone(),
+            note_filename_service.clone(),
+            note_metadata_service.clone(),
+            note_rename_service.clone(),
             event_publisher_opt.clone(),
         ));
 
@@ -78,11 +195,15 @@ impl Container {
             file_storage.clone(),
             system_service.clone(),
             markdown_processor.clone(),
+            link_refactor_service.clone(),
+            note_filename_service.clone(),
+            note_metadata_service.clone(),
             event_publisher_opt.clone(),
         ));
 
         let tag_usecases = Arc::new(TagUseCasesImpl::new(
             tag_repository.clone(),
+            note_metadata_service.clone(),
             event_publisher_opt.clone(),
         ));
 
@@ -93,6 +214,8 @@ impl Container {
             file_storage.clone(),
             embedding_service.clone(),
             markdown_processor.clone(),
+            settings_repository.clone(),
+            embedding_queue,
             event_publisher_opt.clone(),
         ));
 
@@ -124,21 +247,76 @@ impl Container {
             note_repository.clone(),
             link_repository.clone(),
             workspace_repository.clone(),
+            chunk_repository.clone(),
+            block_repository.clone(),
+            embedding_repository.clone(),
+            file_storage.clone(),
+            embedding_service.clone(),
+            transclusion_service.clone(),
+            event_publisher_opt.clone(),
         ));
 
         let quick_capture_usecases = Arc::new(QuickCaptureUseCasesImpl::new(
             note_repository.clone(),
-            workspace_repository.clone(),
             file_storage.clone(),
+            periodic_note_service.clone(),
             event_publisher.clone(),
         ));
 
+        let periodic_note_usecases = Arc::new(PeriodicNoteUseCasesImpl::new(
+            periodic_note_service,
+        ));
+
         let task_usecases = Arc::new(TaskUseCasesImpl::new(
             note_repository.clone(),
             workspace_repository.clone(),
             file_storage.clone(),
         ));
 
+        let template_usecases = Arc::new(TemplateUseCasesImpl::new(
+            note_repository.clone(),
+            notebook_repository.clone(),
+            workspace_repository.clone(),
+            tag_repository.clone(),
+            settings_repository.clone(),
+            file_storage.clone(),
+            note_filename_service,
+            event_publisher_opt.clone(),
+        ));
+
+        let duplicate_usecases = Arc::new(DuplicateUseCasesImpl::new(
+            note_repository.clone(),
+            workspace_repository.clone(),
+            tag_repository.clone(),
+            topic_repository.clone(),
+            attachment_repository.clone(),
+            link_repository.clone(),
+            embedding_repository.clone(),
+            file_storage.clone(),
+            markdown_processor.clone(),
+            event_publisher_opt.clone(),
+        ));
+
+        let question_answering_usecases = Arc::new(QuestionAnsweringUseCasesImpl::new(
+            note_repository.clone(),
+            workspace_repository.clone(),
+            file_storage.clone(),
+            search_engine.clone(),
+            embedding_service.clone(),
+            chat_service.clone(),
+            settings_repository.clone(),
+            event_publisher_opt.clone(),
+        ));
+
+        let refactor_usecases = Arc::new(RefactorUseCasesImpl::new(
+            note_repository.clone(),
+            tag_repository.clone(),
+            workspace_repository.clone(),
+            link_refactor_service,
+            note_rename_service,
+            event_publisher_opt.clone(),
+        ));
+
         let database_usecases = Arc::new(DatabaseUseCasesImpl::new(
             database_service.clone(),
         ));
@@ -154,6 +332,7 @@ impl Container {
             file_storage.clone(),
             markdown_processor.clone(),
             export_service.clone(),
+            transclusion_service,
         ));
 
         let system_usecases = Arc::new(SystemUseCasesImpl::new(
@@ -173,7 +352,12 @@ impl Container {
             search_usecases,
             graph_usecases,
             quick_capture_usecases,
+            periodic_note_usecases,
             task_usecases,
+            template_usecases,
+            duplicate_usecases,
+            refactor_usecases,
+            question_answering_usecases,
             database_usecases,
             git_usecases,
             export_usecases,
@@ -182,6 +366,65 @@ impl Container {
 
         tracing::info!("Dependency injection container built successfully");
 
-        Ok(Self { app_state, file_watcher })
+        Ok(Self {
+            app_state,
+            file_watcher,
+            indexing_service,
+            embedding_service,
+        })
+    }
+
+    /// Embedding provider chosen in settings, or the default FastEmbed model
+    ///
+    /// A broken setting must not keep the app from starting, so it falls back
+    /// to the default with a warning.
+    async fn embedding_provider(
+        settings_repository: &dyn SettingsRepository,
+    ) -> Arc<dyn EmbeddingProvider> {
+        let config = match settings_repository.get(EMBEDDING_PROVIDER_SETTING).await {
+            Ok(Some(setting)) => EmbeddingProviderConfig::from_setting(&setting.value),
+            Ok(None) => Ok(EmbeddingProviderConfig::default()),
+            Err(e) => Err(e),
+        };
+
+        match config.and_then(|config| create_embedding_provider(&config)) {
+            Ok(provider) => provider,
+            Err(e) => {
+                tracing::warn!("Using the default embedding model: {}", e);
+                Arc::new(FastEmbedProvider::default())
+            }
+        }
+    }
+
+    /// Storage format of new embedding vectors from settings, f32 by default
+    ///
+    /// Unlike the provider, an unknown format is an error rather than a silent
+    /// fallback: the setting exists to save space, and storing full-size
+    /// vectors without saying so would defeat it.
+    async fn embedding_quantization(
+        settings_repository: &dyn SettingsRepository,
+    ) -> DomainResult<EmbeddingQuantization> {
+        match settings_repository.get(EMBEDDING_QUANTIZATION_SETTING).await? {
+            Some(setting) => EmbeddingQuantization::from_setting(&setting.value),
+            None => Ok(EmbeddingQuantization::default()),
+        }
+    }
+
+    /// Classification threshold, top-k and manual weight from settings
+    ///
+    /// Falls back to the defaults with a warning, like the embedding provider.
+    async fn classification_config(
+        settings_repository: &dyn SettingsRepository,
+    ) -> ClassificationConfig {
+        let config = match settings_repository.get(CLASSIFICATION_SETTING).await {
+            Ok(Some(setting)) => ClassificationConfig::from_setting(&setting.value),
+            Ok(None) => Ok(ClassificationConfig::default()),
+            Err(e) => Err(e),
+        };
+
+        config.unwrap_or_else(|e| {
+            tracing::warn!("Using the default classification settings: {}", e);
+            ClassificationConfig::default()
+        })
     }
 }
@@ -47,6 +47,7 @@ impl DatabaseManager {
             max_pool_size: config.max_connections,
             min_idle: Some(1),
             connection_timeout_secs: config.connection_timeout_secs,
+            busy_timeout_ms: Some(config.busy_timeout_ms),
         };
         let pool = create_pool(db_config).map_err(|e| {
             DomainError::DatabaseError(format!("Failed to create database pool: {}", e))
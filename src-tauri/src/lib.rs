@@ -26,10 +26,12 @@ pub use shared::database::{create_pool, DbPool};
 
 // Import all command modules
 use adapters::inbound::{
-    attachment_commands, database_commands, export_commands, git_commands, graph_commands,
-    note_commands, notebook_commands, quick_capture_commands, search_commands, settings_commands,
-    system_commands, tag_commands, task_commands, topic_commands, version_commands,
-    workspace_commands, performance_commands,
+    attachment_commands, database_commands, duplicate_commands, export_commands, git_commands,
+    graph_commands, note_commands, notebook_commands, periodic_note_commands,
+    question_answering_commands,
+    quick_capture_commands, refactor_commands, search_commands, settings_commands,
+    system_commands, tag_commands, task_commands, template_commands, topic_commands,
+    version_commands, workspace_commands, performance_commands,
 };
 
 /// Initialize the application
@@ -83,7 +85,7 @@ async fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error
 
     // Build dependency injection container
     tracing::info!("Building dependency injection container...");
-    let container = Container::build(pool.clone(), app_handle.clone()).await?;
+    let container = Container::build(pool.clone(), app_handle.clone(), &config.paths).await?;
 
     // Perform initial workspace sync
     tracing::info!("Syncing workspace with filesystem...");
@@ -101,6 +103,15 @@ async fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error
         }
     }
 
+    // Rebuild full-text search index from the synced notes
+    tracing::info!("Rebuilding search index...");
+    if let Err(e) = container.app_state.search_usecases.rebuild_index().await {
+        tracing::warn!("Search index rebuild failed: {}", e);
+    }
+
+    // Keep the index up to date as notes change
+    container.indexing_service.start();
+
     // Start file watcher for active workspace
     tracing::info!("Starting file watcher...");
     match container.app_state.workspace_usecases.get_active_workspace().await {
@@ -125,6 +136,8 @@ async fn setup_app(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error
     // Register app state
     app.manage(container.app_state);
     app.manage(container.file_watcher);
+    app.manage(container.indexing_service);
+    app.manage(container.embedding_service);
     app.manage(db_manager);
     app.manage(performance_commands::PerformanceState::new());
     app.manage(adapters::inbound::ui::quick_capture_window::QuickCaptureState::new());
@@ -186,6 +199,8 @@ pub fn run() {
             workspace_commands::delete_workspace,
             workspace_commands::scan_workspace,
             workspace_commands::sync_workspace,
+            workspace_commands::get_filename_settings,
+            workspace_commands::update_filename_settings,
             // Notebook commands
             notebook_commands::create_notebook,
             notebook_commands::get_notebook,
@@ -210,18 +225,28 @@ pub fn run() {
             topic_commands::delete_topic,
             topic_commands::classify_note,
             topic_commands::classify_all_notes,
+            topic_commands::discover_topics,
+            topic_commands::accept_topic_candidate,
             topic_commands::get_notes_for_topic,
             topic_commands::get_topics_for_note,
             topic_commands::assign_topic_to_note,
             topic_commands::remove_topic_from_note,
             topic_commands::get_similar_notes,
             topic_commands::get_embedding_status,
+            topic_commands::get_embedding_settings,
+            topic_commands::update_embedding_settings,
+            topic_commands::start_embedding_queue,
+            topic_commands::pause_embedding_queue,
+            topic_commands::resume_embedding_queue,
+            topic_commands::cancel_embedding_queue,
             topic_commands::initialize_embeddings,
             // Search commands
             search_commands::search_notes,
             search_commands::semantic_search,
             search_commands::hybrid_search,
             search_commands::search_by_date_range,
+            search_commands::search_by_tags,
+            search_commands::rebuild_search_index,
             // Git commands
             git_commands::git_init,
             git_commands::git_commit,
@@ -256,8 +281,36 @@ pub fn run() {
             graph_commands::get_backlinks,
             graph_commands::get_forward_links,
             graph_commands::get_graph_data,
+            graph_commands::get_link_suggestions,
+            graph_commands::apply_link_suggestion,
+            graph_commands::get_ambiguous_links,
+            graph_commands::get_note_blocks,
+            graph_commands::resolve_embed,
+            // Duplicate commands
+            duplicate_commands::find_duplicate_notes,
+            duplicate_commands::merge_notes,
+            // Refactor commands
+            refactor_commands::preview_note_rename,
+            refactor_commands::rename_note,
+            refactor_commands::preview_tag_rename,
+            refactor_commands::rename_tag,
+            // Question answering commands
+            question_answering_commands::ask_question,
+            question_answering_commands::get_chat_settings,
+            question_answering_commands::update_chat_settings,
+            // Periodic note commands
+            periodic_note_commands::open_periodic_note,
+            periodic_note_commands::get_periodic_settings,
+            periodic_note_commands::update_periodic_settings,
+            // Template commands
+            template_commands::list_templates,
+            template_commands::get_template_settings,
+            template_commands::update_template_settings,
+            template_commands::set_notebook_template,
+            template_commands::create_note_from_template,
             // Quick capture commands
             quick_capture_commands::append_to_journal,
+            quick_capture_commands::append_to_periodic_note,
             quick_capture_commands::hide_quick_capture,
             quick_capture_commands::get_quick_capture_state,
             quick_capture_commands::log_from_frontend,
@@ -283,6 +336,19 @@ pub fn run() {
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|app, event| {
+            // Save vector index changes made since the last periodic save
+            if let tauri::RunEvent::Exit = event {
+                if let Some(embedding_service) = app
+                    .try_state::<std::sync::Arc<adapters::outbound::services::FastEmbedService>>()
+                {
+                    if let Err(e) =
+                        tauri::async_runtime::block_on(embedding_service.flush_vector_index())
+                    {
+                        tracing::warn!("Failed to save vector index: {}", e);
+                    }
+                }
+            }
+
             // Handle macOS dock icon click when no windows are visible
             #[cfg(target_os = "macos")]
             if let tauri::RunEvent::Reopen { has_visible_windows, .. } = event {
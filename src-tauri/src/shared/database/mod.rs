@@ -12,7 +12,7 @@
 pub mod schema;
 
 use diesel::prelude::*;
-use diesel::r2d2::{self, ConnectionManager};
+use diesel::r2d2::{self, ConnectionManager, CustomizeConnection};
 use std::error::Error;
 
 /// Database connection pool type
@@ -27,6 +27,9 @@ pub struct DatabaseConfig {
     pub max_pool_size: u32,
     pub min_idle: Option<u32>,
     pub connection_timeout_secs: u64,
+    /// How long a connection waits for another writer before failing with
+    /// "database is locked"; applied to every pooled connection
+    pub busy_timeout_ms: Option<u32>,
 }
 
 impl DatabaseConfig {
@@ -37,6 +40,7 @@ impl DatabaseConfig {
             max_pool_size: 10,
             min_idle: Some(1),
             connection_timeout_secs: 30,
+            busy_timeout_ms: Some(5000),
         }
     }
 
@@ -47,6 +51,7 @@ impl DatabaseConfig {
             max_pool_size: 1,
             min_idle: None,
             connection_timeout_secs: 5,
+            busy_timeout_ms: None,
         }
     }
 }
@@ -74,7 +79,27 @@ pub fn create_pool(config: DatabaseConfig) -> Result<DbPool, Box<dyn Error + Sen
         pool_builder = pool_builder.min_idle(Some(min_idle));
     }
 
+    if let Some(busy_timeout_ms) = config.busy_timeout_ms {
+        pool_builder = pool_builder.connection_customizer(Box::new(BusyTimeout(busy_timeout_ms)));
+    }
+
     let pool = pool_builder.build(manager)?;
 
     Ok(pool)
 }
+
+/// Sets `PRAGMA busy_timeout` on each new connection
+///
+/// The pragma is per connection, so setting it once at startup leaves the
+/// rest of the pool failing immediately when background writers overlap.
+#[derive(Debug)]
+struct BusyTimeout(u32);
+
+impl CustomizeConnection<SqliteConnection, r2d2::Error> for BusyTimeout {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), r2d2::Error> {
+        diesel::sql_query(format!("PRAGMA busy_timeout = {}", self.0))
+            .execute(conn)
+            .map(|_| ())
+            .map_err(r2d2::Error::QueryError)
+    }
+}
@@ -13,10 +13,50 @@ diesel::table! {
 }
 
 diesel::table! {
-    note_links (source_note_id, target_note_id) {
+    embeddings (id) {
+        id -> Text,
+        note_id -> Text,
+        chunk_index -> Nullable<Integer>,
+        model_name -> Text,
+        model_version -> Text,
+        content_hash -> Nullable<Text>,
+        dimensions -> Integer,
+        quantization -> Text,
+        vector -> Binary,
+        created_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    note_blocks (note_id, block_id) {
+        note_id -> Text,
+        block_id -> Text,
+        content -> Text,
+        start_offset -> Integer,
+        end_offset -> Integer,
+        line_number -> Integer,
+        created_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    note_chunks (note_id, chunk_index) {
+        note_id -> Text,
+        chunk_index -> Integer,
+        heading_path -> Text,
+        content -> Text,
+        start_offset -> Integer,
+        end_offset -> Integer,
+        created_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    note_links (source_note_id, target_note_id, anchor) {
         source_note_id -> Text,
         target_note_id -> Text,
         created_at -> BigInt,
+        anchor -> Text,
     }
 }
 
@@ -25,6 +65,7 @@ diesel::table! {
         note_id -> Text,
         tag_id -> Text,
         created_at -> BigInt,
+        source -> Text,
     }
 }
 
@@ -76,9 +117,10 @@ diesel::table! {
         is_archived -> Integer,
         is_deleted -> Integer,
         deleted_at -> Nullable<BigInt>,
-        embedding -> Nullable<Binary>,
         created_at -> BigInt,
         updated_at -> BigInt,
+        aliases -> Text,
+        properties -> Text,
     }
 }
 
@@ -126,6 +168,9 @@ diesel::table! {
 }
 
 diesel::joinable!(attachments -> notes (note_id));
+diesel::joinable!(embeddings -> notes (note_id));
+diesel::joinable!(note_blocks -> notes (note_id));
+diesel::joinable!(note_chunks -> notes (note_id));
 diesel::joinable!(note_tags -> notes (note_id));
 diesel::joinable!(note_tags -> tags (tag_id));
 diesel::joinable!(note_topics -> notes (note_id));
@@ -137,6 +182,9 @@ diesel::joinable!(notes -> workspaces (workspace_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     attachments,
+    embeddings,
+    note_blocks,
+    note_chunks,
     note_links,
     note_tags,
     note_topics,
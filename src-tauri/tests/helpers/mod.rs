@@ -3,8 +3,43 @@
 
 use stone_tauri_lib::infrastructure::{DatabaseManager, DatabaseConfig};
 use std::path::PathBuf;
+use std::sync::Arc;
+use async_trait::async_trait;
 use chrono::Timelike;
 
+use stone_tauri_lib::adapters::outbound::{
+    persistence::{
+        DieselAttachmentRepository, DieselEmbeddingRepository, DieselNoteBlockRepository,
+        DieselNoteChunkRepository, DieselNoteLinkRepository, DieselNoteRepository,
+        DieselNotebookRepository, DieselSettingsRepository, DieselTagRepository,
+        DieselTopicRepository, DieselWorkspaceRepository,
+    },
+    services::{
+        FastEmbedService, Fts5SearchService, Git2Service, OpenAiChatService,
+        PulldownMarkdownService, StubExportService, TokioEventPublisher,
+    },
+    storage::TokioFileStorage,
+};
+use stone_tauri_lib::application::{
+    services::{
+        EmbeddingQueue, LinkRefactorService, NoteFilenameService, NoteIndexingService,
+        NoteMetadataService, NoteRenameService, PeriodicNoteService, TransclusionService,
+    },
+    usecases::{
+        DuplicateUseCasesImpl, ExportUseCasesImpl, GraphUseCasesImpl, NoteUseCasesImpl,
+        PeriodicNoteUseCasesImpl, QuestionAnsweringUseCasesImpl, QuickCaptureUseCasesImpl,
+        RefactorUseCasesImpl, TagUseCasesImpl, TemplateUseCasesImpl, WorkspaceUseCasesImpl,
+    },
+};
+use stone_tauri_lib::domain::{
+    entities::Workspace,
+    errors::DomainResult,
+    ports::outbound::{
+        EmbeddingProvider, EventPublisher, FilePickerOptions, FilePickerResult, FileStorage,
+        FolderPickerOptions, SystemService, WorkspaceRepository,
+    },
+};
+
 /// Creates an in-memory database configuration for testing
 ///
 /// Using :memory: for tests provides:
@@ -32,6 +67,403 @@ pub async fn setup_test_db_manager() -> DatabaseManager {
     manager
 }
 
+/// Creates and initializes a database manager backed by a temporary file
+///
+/// Unlike `:memory:`, every pooled connection sees the same database, so
+/// repositories and services can be exercised together. Keep the returned
+/// `TempDir` alive for the duration of the test.
+pub async fn setup_temp_db_manager() -> (tempfile::TempDir, DatabaseManager) {
+    let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let config = DatabaseConfig::development(temp_dir.path().join("test.db"));
+    let manager = DatabaseManager::new(config)
+        .await
+        .expect("Failed to create test database manager");
+
+    manager.initialize()
+        .await
+        .expect("Failed to initialize test database");
+
+    (temp_dir, manager)
+}
+
+/// The application's repositories and services over a temporary database
+///
+/// Wired the way the container wires them, around a saved and active
+/// workspace in its own temporary folder. Use cases are built on demand, so a
+/// test only pays for the ones it exercises. Keep the value alive for the
+/// duration of the test: it owns both temporary directories.
+pub struct TestServices {
+    pub workspace: Workspace,
+    pub workspace_dir: tempfile::TempDir,
+    _db_dir: tempfile::TempDir,
+
+    pub workspaces: Arc<DieselWorkspaceRepository>,
+    pub notes: Arc<DieselNoteRepository>,
+    pub notebooks: Arc<DieselNotebookRepository>,
+    pub tags: Arc<DieselTagRepository>,
+    pub topics: Arc<DieselTopicRepository>,
+    pub links: Arc<DieselNoteLinkRepository>,
+    pub chunks: Arc<DieselNoteChunkRepository>,
+    pub blocks: Arc<DieselNoteBlockRepository>,
+    pub embeddings: Arc<DieselEmbeddingRepository>,
+    pub attachments: Arc<DieselAttachmentRepository>,
+    pub settings: Arc<DieselSettingsRepository>,
+
+    pub file_storage: Arc<dyn FileStorage>,
+    pub markdown: Arc<PulldownMarkdownService>,
+    pub events: Arc<TokioEventPublisher>,
+    pub embedding_service: Arc<FastEmbedService>,
+    pub search_engine: Arc<Fts5SearchService>,
+    pub link_refactor: Arc<LinkRefactorService>,
+    pub periodic_notes: Arc<PeriodicNoteService>,
+    pub note_filenames: Arc<NoteFilenameService>,
+    pub note_metadata: Arc<NoteMetadataService>,
+    pub note_renames: Arc<NoteRenameService>,
+    pub transclusion: Arc<TransclusionService>,
+}
+
+/// Builder for [`TestServices`], for tests that swap in their own adapters
+pub struct TestServicesBuilder {
+    workspace_name: String,
+    file_storage: Option<Arc<dyn FileStorage>>,
+    embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
+}
+
+impl TestServicesBuilder {
+    /// Use `file_storage` instead of the local file system
+    pub fn file_storage(mut self, file_storage: Arc<dyn FileStorage>) -> Self {
+        self.file_storage = Some(file_storage);
+        self
+    }
+
+    /// Embed with `provider` instead of the FastEmbed model
+    pub fn embedding_provider(mut self, provider: Arc<dyn EmbeddingProvider>) -> Self {
+        self.embedding_provider = Some(provider);
+        self
+    }
+
+    pub async fn build(self) -> TestServices {
+        let (db_dir, manager) = setup_temp_db_manager().await;
+        let pool = manager.get_pool();
+        let workspace_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+
+        let workspaces = Arc::new(DieselWorkspaceRepository::new(pool.clone()));
+        let workspace =
+            Workspace::new(&self.workspace_name, workspace_dir.path().to_str().unwrap()).unwrap();
+        workspaces.save(&workspace).await.unwrap();
+        workspaces.set_active(&workspace.id).await.unwrap();
+
+        let notes = Arc::new(DieselNoteRepository::new(pool.clone()));
+        let tags = Arc::new(DieselTagRepository::new(pool.clone()));
+        let topics = Arc::new(DieselTopicRepository::new(pool.clone()));
+        let chunks = Arc::new(DieselNoteChunkRepository::new(pool.clone()));
+        let embeddings = Arc::new(DieselEmbeddingRepository::new(pool.clone()));
+        let settings = Arc::new(DieselSettingsRepository::new(pool.clone()));
+
+        let file_storage = self
+            .file_storage
+            .unwrap_or_else(|| Arc::new(TokioFileStorage::new()));
+        let markdown = Arc::new(PulldownMarkdownService::new());
+        let events = Arc::new(TokioEventPublisher::new());
+        let event_publisher: Option<Arc<dyn EventPublisher>> = Some(events.clone());
+
+        let mut embedding_service = FastEmbedService::new(
+            notes.clone(),
+            chunks.clone(),
+            embeddings.clone(),
+            topics.clone(),
+        );
+        if let Some(provider) = self.embedding_provider {
+            embedding_service = embedding_service.with_provider(provider);
+        }
+        let embedding_service = Arc::new(embedding_service);
+        let search_engine = Arc::new(Fts5SearchService::new(
+            pool.clone(),
+            file_storage.clone(),
+            markdown.clone(),
+            embedding_service.clone(),
+        ));
+
+        let link_refactor = Arc::new(LinkRefactorService::new(file_storage.clone()));
+        let periodic_notes = Arc::new(PeriodicNoteService::new(
+            notes.clone(),
+            workspaces.clone(),
+            settings.clone(),
+            file_storage.clone(),
+            event_publisher,
+        ));
+        let note_filenames = Arc::new(NoteFilenameService::new(
+            notes.clone(),
+            settings.clone(),
+            file_storage.clone(),
+            periodic_notes.clone(),
+            Arc::new(Git2Service::new()),
+            link_refactor.clone(),
+        ));
+        let links = Arc::new(DieselNoteLinkRepository::new(pool.clone()));
+        let note_renames = Arc::new(NoteRenameService::new(
+            notes.clone(),
+            links.clone(),
+            link_refactor.clone(),
+            note_filenames.clone(),
+        ));
+        let note_metadata = Arc::new(NoteMetadataService::new(
+            notes.clone(),
+            workspaces.clone(),
+            tags.clone(),
+            file_storage.clone(),
+        ));
+        let transclusion = Arc::new(TransclusionService::new(
+            notes.clone(),
+            workspaces.clone(),
+            file_storage.clone(),
+        ));
+
+        TestServices {
+            workspace,
+            workspace_dir,
+            _db_dir: db_dir,
+            workspaces,
+            notes,
+            notebooks: Arc::new(DieselNotebookRepository::new(pool.clone())),
+            tags,
+            topics,
+            links,
+            chunks,
+            blocks: Arc::new(DieselNoteBlockRepository::new(pool.clone())),
+            embeddings,
+            attachments: Arc::new(DieselAttachmentRepository::new(pool)),
+            settings,
+            file_storage,
+            markdown,
+            events,
+            embedding_service,
+            search_engine,
+            link_refactor,
+            periodic_notes,
+            note_filenames,
+            note_metadata,
+            note_renames,
+            transclusion,
+        }
+    }
+}
+
+impl TestServices {
+    /// Services around a workspace named `workspace_name`
+    pub async fn new(workspace_name: &str) -> Self {
+        Self::builder(workspace_name).build().await
+    }
+
+    pub fn builder(workspace_name: &str) -> TestServicesBuilder {
+        TestServicesBuilder {
+            workspace_name: workspace_name.to_string(),
+            file_storage: None,
+            embedding_provider: None,
+        }
+    }
+
+    fn event_publisher(&self) -> Option<Arc<dyn EventPublisher>> {
+        Some(self.events.clone())
+    }
+
+    pub fn note_usecases(&self) -> NoteUseCasesImpl {
+        NoteUseCasesImpl::new(
+            self.notes.clone(),
+            self.workspaces.clone(),
+            self.file_storage.clone(),
+            self.markdown.clone(),
+            self.periodic_notes.clone(),
+            self.note_filenames.clone(),
+            self.note_metadata.clone(),
+            self.note_renames.clone(),
+            self.event_publisher(),
+        )
+    }
+
+    /// Workspace use cases whose folder and file pickers are always cancelled
+    pub fn workspace_usecases(&self) -> WorkspaceUseCasesImpl {
+        WorkspaceUseCasesImpl::new(
+            self.workspaces.clone(),
+            self.notes.clone(),
+            self.file_storage.clone(),
+            Arc::new(NoDialogs),
+            self.markdown.clone(),
+            self.link_refactor.clone(),
+            self.note_filenames.clone(),
+            self.note_metadata.clone(),
+            self.event_publisher(),
+        )
+    }
+
+    pub fn tag_usecases(&self) -> TagUseCasesImpl {
+        TagUseCasesImpl::new(
+            self.tags.clone(),
+            self.note_metadata.clone(),
+            self.event_publisher(),
+        )
+    }
+
+    pub fn graph_usecases(&self) -> GraphUseCasesImpl {
+        GraphUseCasesImpl::new(
+            self.notes.clone(),
+            self.links.clone(),
+            self.workspaces.clone(),
+            self.chunks.clone(),
+            self.blocks.clone(),
+            self.embeddings.clone(),
+            self.file_storage.clone(),
+            self.embedding_service.clone(),
+            self.transclusion.clone(),
+            self.event_publisher(),
+        )
+    }
+
+    pub fn refactor_usecases(&self) -> RefactorUseCasesImpl {
+        RefactorUseCasesImpl::new(
+            self.notes.clone(),
+            self.tags.clone(),
+            self.workspaces.clone(),
+            self.link_refactor.clone(),
+            self.note_renames.clone(),
+            self.event_publisher(),
+        )
+    }
+
+    pub fn duplicate_usecases(&self) -> DuplicateUseCasesImpl {
+        DuplicateUseCasesImpl::new(
+            self.notes.clone(),
+            self.workspaces.clone(),
+            self.tags.clone(),
+            self.topics.clone(),
+            self.attachments.clone(),
+            self.links.clone(),
+            self.embeddings.clone(),
+            self.file_storage.clone(),
+            self.markdown.clone(),
+            self.event_publisher(),
+        )
+    }
+
+    pub fn template_usecases(&self) -> TemplateUseCasesImpl {
+        TemplateUseCasesImpl::new(
+            self.notes.clone(),
+            self.notebooks.clone(),
+            self.workspaces.clone(),
+            self.tags.clone(),
+            self.settings.clone(),
+            self.file_storage.clone(),
+            self.note_filenames.clone(),
+            self.event_publisher(),
+        )
+    }
+
+    pub fn export_usecases(&self) -> ExportUseCasesImpl {
+        ExportUseCasesImpl::new(
+            self.notes.clone(),
+            self.workspaces.clone(),
+            self.file_storage.clone(),
+            self.markdown.clone(),
+            Arc::new(StubExportService::new()),
+            self.transclusion.clone(),
+        )
+    }
+
+    pub fn periodic_note_usecases(&self) -> PeriodicNoteUseCasesImpl {
+        PeriodicNoteUseCasesImpl::new(self.periodic_notes.clone())
+    }
+
+    pub fn quick_capture_usecases(&self) -> QuickCaptureUseCasesImpl {
+        QuickCaptureUseCasesImpl::new(
+            self.notes.clone(),
+            self.file_storage.clone(),
+            self.periodic_notes.clone(),
+            self.events.clone(),
+        )
+    }
+
+    pub fn question_answering_usecases(&self) -> QuestionAnsweringUseCasesImpl {
+        QuestionAnsweringUseCasesImpl::new(
+            self.notes.clone(),
+            self.workspaces.clone(),
+            self.file_storage.clone(),
+            self.search_engine.clone(),
+            self.embedding_service.clone(),
+            Arc::new(OpenAiChatService::new()),
+            self.settings.clone(),
+            self.event_publisher(),
+        )
+    }
+
+    /// A background indexer, not yet started
+    pub fn note_indexer(&self) -> NoteIndexingService {
+        NoteIndexingService::new(
+            self.notes.clone(),
+            self.workspaces.clone(),
+            self.blocks.clone(),
+            self.file_storage.clone(),
+            self.markdown.clone(),
+            self.search_engine.clone(),
+            self.embedding_service.clone(),
+            self.events.clone(),
+        )
+    }
+
+    pub fn embedding_queue(&self) -> EmbeddingQueue {
+        EmbeddingQueue::new(
+            self.notes.clone(),
+            self.workspaces.clone(),
+            self.file_storage.clone(),
+            self.markdown.clone(),
+            self.embedding_service.clone(),
+            self.events.clone(),
+        )
+    }
+}
+
+/// System service for headless tests: every picker is cancelled
+pub struct NoDialogs;
+
+#[async_trait]
+impl SystemService for NoDialogs {
+    async fn get_fonts(&self) -> DomainResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    async fn select_folder(
+        &self,
+        _options: Option<FolderPickerOptions>,
+    ) -> DomainResult<Option<String>> {
+        Ok(None)
+    }
+
+    async fn select_file(
+        &self,
+        _options: Option<FilePickerOptions>,
+    ) -> DomainResult<Option<FilePickerResult>> {
+        Ok(None)
+    }
+
+    async fn select_save_location(
+        &self,
+        _options: Option<FilePickerOptions>,
+    ) -> DomainResult<Option<String>> {
+        Ok(None)
+    }
+
+    async fn validate_path(&self, _path: &str) -> DomainResult<bool> {
+        Ok(true)
+    }
+
+    fn show_in_folder(&self, _path: &str) -> DomainResult<()> {
+        Ok(())
+    }
+
+    async fn open_external(&self, _url: &str) -> DomainResult<()> {
+        Ok(())
+    }
+}
+
 /// Test data builder for consistent test fixtures
 pub struct TestDataBuilder;
 
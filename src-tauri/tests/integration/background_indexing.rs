@@ -0,0 +1,251 @@
+//! Test Background Indexing Workflow
+//!
+//! Critical Path: Note changes → Debounced per note → FTS index updated → Progress event
+//! Editors save often; a burst of saves must result in a single re-index, and a burst
+//! too large for the event channel re-indexes every note rather than losing changes.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use stone_tauri_lib::application::services::NoteIndexingService;
+use stone_tauri_lib::domain::{
+    entities::Note,
+    ports::outbound::{
+        DomainEvent, EventPublisher, FileSyncOperation, NoteRepository, SearchEngine,
+    },
+};
+
+use crate::helpers::TestServices;
+
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+struct Fixture {
+    services: TestServices,
+    indexer: Arc<NoteIndexingService>,
+    progress: Arc<Mutex<Vec<DomainEvent>>>,
+    _subscriptions: Vec<Box<dyn Fn() + Send + Sync>>,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::new("Indexing").await;
+    let indexer = Arc::new(services.note_indexer().with_debounce(DEBOUNCE));
+    indexer.start();
+
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let sink = progress.clone();
+    let subscription = services.events.subscribe(
+        "index:progress",
+        Arc::new(move |event| sink.lock().unwrap().push(event)),
+    );
+
+    // Give the subscribers a moment to set up
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    Fixture {
+        services,
+        indexer,
+        progress,
+        _subscriptions: vec![subscription],
+    }
+}
+
+async fn write_note(fx: &Fixture, title: &str, relative_path: &str, body: &str) -> Note {
+    let absolute = fx.workspace_dir.path().join(relative_path);
+    std::fs::create_dir_all(absolute.parent().unwrap()).unwrap();
+    std::fs::write(&absolute, format!("# {}\n\n{}", title, body)).unwrap();
+
+    let mut note = Note::new(title, Some(fx.workspace.id.clone())).unwrap();
+    note.set_file_path(Some(relative_path.to_string())).unwrap();
+    fx.notes.save(&note).await.unwrap();
+    note
+}
+
+fn updated(note: &Note) -> DomainEvent {
+    DomainEvent::NoteUpdated {
+        timestamp: chrono::Utc::now(),
+        id: note.id.clone(),
+        title: note.title.clone(),
+        changes: vec!["content".to_string()],
+    }
+}
+
+async fn search_ids(fx: &Fixture, query: &str) -> Vec<String> {
+    fx.search_engine
+        .search_full_text(query, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.note.id)
+        .collect()
+}
+
+async fn wait_for_idle(fx: &Fixture) {
+    tokio::time::sleep(DEBOUNCE * 2).await;
+    for _ in 0..50 {
+        if fx.indexer.pending_count() == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    // Let the progress subscriber drain
+    tokio::time::sleep(Duration::from_millis(20)).await;
+}
+
+#[tokio::test]
+async fn test_burst_of_updates_is_indexed_once() {
+    let fx = fixture().await;
+    let note = write_note(&fx, "Garden plan", "Personal/garden.md", "Plant tomatoes").await;
+
+    for _ in 0..5 {
+        fx.events.publish(updated(&note));
+    }
+    wait_for_idle(&fx).await;
+
+    assert_eq!(search_ids(&fx, "tomatoes").await, vec![note.id.clone()]);
+
+    let progress = fx.progress.lock().unwrap();
+    assert_eq!(
+        progress.len(),
+        1,
+        "a burst should produce a single index run"
+    );
+    match &progress[0] {
+        DomainEvent::IndexingProgress {
+            note_id,
+            processed,
+            total,
+            failed,
+            ..
+        } => {
+            assert_eq!(note_id, &note.id);
+            assert_eq!((*processed, *total, *failed), (1, 1, 0));
+        }
+        other => panic!("unexpected event: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_progress_counts_every_note_in_a_burst() {
+    let fx = fixture().await;
+    let first = write_note(&fx, "First", "Personal/first.md", "alpha").await;
+    let second = write_note(&fx, "Second", "Personal/second.md", "beta").await;
+
+    fx.events.publish(updated(&first));
+    fx.events.publish(updated(&second));
+    wait_for_idle(&fx).await;
+
+    let totals: Vec<(i32, i32)> = fx
+        .progress
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|event| match event {
+            DomainEvent::IndexingProgress {
+                processed, total, ..
+            } => Some((*processed, *total)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(totals, vec![(1, 2), (2, 2)]);
+}
+
+#[tokio::test]
+async fn test_deleted_note_is_removed_from_index() {
+    let fx = fixture().await;
+    let mut note = write_note(&fx, "Old idea", "Personal/old.md", "quantum widgets").await;
+
+    fx.events.publish(updated(&note));
+    wait_for_idle(&fx).await;
+    assert_eq!(search_ids(&fx, "widgets").await.len(), 1);
+
+    note.delete();
+    fx.notes.save(&note).await.unwrap();
+    fx.events.publish(DomainEvent::NoteDeleted {
+        timestamp: chrono::Utc::now(),
+        id: note.id.clone(),
+        title: note.title.clone(),
+        permanent: false,
+    });
+    wait_for_idle(&fx).await;
+
+    assert!(search_ids(&fx, "widgets").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_file_synced_event_reindexes_external_edit() {
+    let fx = fixture().await;
+    let note = write_note(&fx, "Recipes", "Kitchen/recipes.md", "pancakes").await;
+
+    // Edited in another editor; only the watcher notices
+    let absolute = fx.workspace_dir.path().join("Kitchen/recipes.md");
+    std::fs::write(&absolute, "# Recipes\n\nwaffles").unwrap();
+    fx.events.publish(DomainEvent::FileSynced {
+        timestamp: chrono::Utc::now(),
+        file_path: absolute.to_string_lossy().to_string(),
+        operation: FileSyncOperation::Updated,
+    });
+    wait_for_idle(&fx).await;
+
+    assert_eq!(search_ids(&fx, "waffles").await, vec![note.id.clone()]);
+    assert!(search_ids(&fx, "pancakes").await.is_empty());
+
+    // Removed on disk: dropped from the index before the next sync runs
+    std::fs::remove_file(&absolute).unwrap();
+    fx.events.publish(DomainEvent::FileSynced {
+        timestamp: chrono::Utc::now(),
+        file_path: absolute.to_string_lossy().to_string(),
+        operation: FileSyncOperation::Deleted,
+    });
+    wait_for_idle(&fx).await;
+
+    assert!(search_ids(&fx, "waffles").await.is_empty());
+}
+
+#[tokio::test]
+async fn test_model_change_is_left_to_the_embedding_queue() {
+    let fx = fixture().await;
+    let stale = write_note(&fx, "Stale", "Personal/stale.md", "sourdough").await;
+
+    fx.events.publish(DomainEvent::EmbeddingModelChanged {
+        timestamp: chrono::Utc::now(),
+        model_name: "nomic-embed-text".to_string(),
+        model_version: "openai:http://localhost:11434".to_string(),
+        stale_note_ids: vec![stale.id.clone()],
+    });
+    wait_for_idle(&fx).await;
+
+    // The queue re-embeds in batches; the indexer doesn't pick the notes up
+    assert!(search_ids(&fx, "sourdough").await.is_empty());
+    assert!(fx.progress.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_overflowing_the_event_channel_reindexes_everything() {
+    let fx = fixture().await;
+    let dropped = write_note(&fx, "Dropped", "Personal/dropped.md", "marmalade").await;
+    let ghost = Note::new("Ghost", Some(fx.workspace.id.clone())).unwrap();
+
+    // The first event is pushed out of the channel before the indexer runs
+    fx.events.publish(updated(&dropped));
+    for _ in 0..1100 {
+        fx.events.publish(updated(&ghost));
+    }
+    wait_for_idle(&fx).await;
+    wait_for_idle(&fx).await;
+
+    assert_eq!(search_ids(&fx, "marmalade").await, vec![dropped.id.clone()]);
+
+    // Later changes are still picked up
+    let later = write_note(&fx, "Later", "Personal/later.md", "crumpets").await;
+    fx.events.publish(updated(&later));
+    wait_for_idle(&fx).await;
+    assert_eq!(search_ids(&fx, "crumpets").await, vec![later.id]);
+}
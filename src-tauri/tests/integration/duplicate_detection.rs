@@ -0,0 +1,109 @@
+//! Test Duplicate Detection
+//!
+//! Critical Path: Note text + embeddings → Shingles/MinHash → Duplicate groups
+//! Quick capture and imports leave near-identical notes behind; they must be
+//! found without flagging notes that merely share a subject.
+
+use stone_tauri_lib::domain::services::{
+    DuplicateConfig, DuplicateDetector, DuplicateDocument, MinHasher,
+};
+
+const STANDUP: &str = "Standup notes: the release is blocked on the database migration, \
+    Anna is reviewing the sync fix and we ship the beta on Friday after QA signs off.";
+
+fn document(text: &str, embedding: Option<Vec<f32>>) -> DuplicateDocument {
+    DuplicateDocument {
+        text: text.to_string(),
+        embedding,
+    }
+}
+
+#[test]
+fn test_near_identical_notes_are_grouped() {
+    let documents = vec![
+        document(STANDUP, None),
+        document("Grocery list: eggs, flour, butter and two lemons.", None),
+        // Captured again with a remark added
+        document(&format!("{} Thanks all.", STANDUP), None),
+        document(&STANDUP.to_uppercase(), None),
+        document("", None),
+        document("", None),
+    ];
+
+    let groups = DuplicateDetector::default().find_groups(&documents);
+
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].members, vec![0, 2, 3]);
+    // Case doesn't matter
+    let exact = groups[0]
+        .pairs
+        .iter()
+        .find(|p| (p.a, p.b) == (0, 3))
+        .unwrap();
+    assert_eq!(exact.text_similarity, 1.0);
+    assert!(groups[0].pairs.iter().all(|p| p.score >= 0.75 && p.a < p.b));
+    assert!(groups[0].score > 0.75 && groups[0].score <= 1.0);
+}
+
+#[test]
+fn test_embeddings_separate_lookalikes_from_duplicates() {
+    let template = "Meeting with the design team about the onboarding flow and next steps";
+    let documents = vec![
+        document(template, Some(vec![1.0, 0.0])),
+        document(
+            &template.replace("onboarding flow", "billing page"),
+            Some(vec![0.0, 1.0]),
+        ),
+        document(&format!("{} today", template), Some(vec![0.99, 0.1])),
+    ];
+
+    let detector = DuplicateDetector::new(DuplicateConfig {
+        min_score: 0.7,
+        ..DuplicateConfig::default()
+    });
+    let pairs = detector.find_pairs(&documents);
+
+    // Same wording but a different subject: the embeddings disagree
+    assert!(pairs.iter().all(|p| p.b != 1 && p.a != 1));
+    assert_eq!(pairs.len(), 1);
+    assert_eq!((pairs[0].a, pairs[0].b), (0, 2));
+    assert!(pairs[0].embedding_similarity.unwrap() > 0.99);
+
+    // Without embeddings, text alone decides
+    let text_only = DuplicateDetector::new(DuplicateConfig {
+        min_score: 0.5,
+        ..DuplicateConfig::default()
+    });
+    let documents: Vec<DuplicateDocument> = documents
+        .into_iter()
+        .map(|d| document(&d.text, None))
+        .collect();
+    assert!(text_only
+        .find_pairs(&documents)
+        .iter()
+        .all(|p| p.embedding_similarity.is_none()));
+}
+
+#[test]
+fn test_minhash_estimates_jaccard() {
+    let detector = DuplicateDetector::default();
+    let hasher = MinHasher::default();
+
+    let words: Vec<String> = (0..200).map(|i| format!("word{}", i)).collect();
+    let a = detector.shingles(&words[..150].join(" "));
+    let b = detector.shingles(&words[50..].join(" "));
+    // 98 shared shingles out of 198
+    let estimate = MinHasher::similarity(&hasher.signature(&a), &hasher.signature(&b));
+    assert!(
+        (estimate - 98.0 / 198.0).abs() < 0.15,
+        "estimate {}",
+        estimate
+    );
+
+    assert_eq!(
+        MinHasher::similarity(&hasher.signature(&a), &hasher.signature(&a)),
+        1.0
+    );
+    assert_eq!(detector.shingles("Two words").len(), 1);
+    assert!(detector.shingles("  ... ").is_empty());
+}
@@ -0,0 +1,324 @@
+//! Test Embedding Queue Workflow
+//!
+//! Critical Path: Scan notes → Compare content hashes → Embed stale notes in batches → Progress events
+//! Only notes whose text changed since they were embedded are embedded again,
+//! trashed notes are left out, and a run can be paused, resumed and cancelled
+//! between batches.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::Semaphore;
+
+use stone_tauri_lib::application::services::{EmbeddingQueue, EmbeddingQueueOptions};
+use stone_tauri_lib::domain::{
+    entities::Note,
+    errors::DomainResult,
+    ports::outbound::{
+        DomainEvent, EmbeddingModelInfo, EmbeddingProvider, EmbeddingProviderConfig,
+        EmbeddingQueueState, EmbeddingQueueStatus, EmbeddingService, EventPublisher,
+        NoteRepository,
+    },
+};
+
+use crate::helpers::TestServices;
+
+/// Embeds each text as [its length, 1], recording the size of every batch
+///
+/// With a gate, each batch waits for a permit, so tests can hold the worker
+/// in the middle of a batch.
+struct StubProvider {
+    batches: Mutex<Vec<usize>>,
+    gate: Option<Arc<Semaphore>>,
+    ready: AtomicBool,
+}
+
+#[async_trait]
+impl EmbeddingProvider for StubProvider {
+    fn model(&self) -> EmbeddingModelInfo {
+        EmbeddingModelInfo {
+            name: "stub".to_string(),
+            version: "1".to_string(),
+            dimensions: Some(2),
+        }
+    }
+
+    async fn initialize(&self) -> DomainResult<()> {
+        self.ready.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> DomainResult<Vec<Vec<f32>>> {
+        self.batches.lock().unwrap().push(texts.len());
+        if let Some(gate) = &self.gate {
+            gate.acquire().await.unwrap().forget();
+        }
+        Ok(texts
+            .iter()
+            .map(|text| vec![text.len() as f32, 1.0])
+            .collect())
+    }
+}
+
+struct Fixture {
+    services: TestServices,
+    provider: Arc<StubProvider>,
+    queue: Arc<EmbeddingQueue>,
+    progress: Arc<Mutex<Vec<DomainEvent>>>,
+    _subscriptions: Vec<Box<dyn Fn() + Send + Sync>>,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+async fn fixture(gate: Option<Arc<Semaphore>>) -> Fixture {
+    let provider = Arc::new(StubProvider {
+        batches: Mutex::new(Vec::new()),
+        gate,
+        ready: AtomicBool::new(false),
+    });
+    let services = TestServices::builder("Queue")
+        .embedding_provider(provider.clone())
+        .build()
+        .await;
+    services.embedding_service.initialize().await.unwrap();
+
+    // Leave the seeded sample notes out of the runs
+    for note in services.notes.find_all(Default::default()).await.unwrap() {
+        services.notes.delete(&note.id).await.unwrap();
+    }
+
+    let queue = Arc::new(services.embedding_queue());
+
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let sink = progress.clone();
+    let subscription = services.events.subscribe(
+        "embedding:queue-progress",
+        Arc::new(move |event| sink.lock().unwrap().push(event)),
+    );
+
+    // Give the subscriber a moment to set up
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    Fixture {
+        services,
+        provider,
+        queue,
+        progress,
+        _subscriptions: vec![subscription],
+    }
+}
+
+async fn write_note(fx: &Fixture, title: &str, relative_path: &str, body: &str) -> Note {
+    let mut note = Note::new(title, Some(fx.workspace.id.clone())).unwrap();
+    note.set_file_path(Some(relative_path.to_string())).unwrap();
+    fx.notes.save(&note).await.unwrap();
+    edit_note(fx, relative_path, title, body);
+    note
+}
+
+fn edit_note(fx: &Fixture, relative_path: &str, title: &str, body: &str) {
+    let absolute = fx.workspace_dir.path().join(relative_path);
+    std::fs::create_dir_all(absolute.parent().unwrap()).unwrap();
+    std::fs::write(&absolute, format!("# {}\n\n{}", title, body)).unwrap();
+}
+
+fn options(batch_size: usize) -> EmbeddingQueueOptions {
+    EmbeddingQueueOptions::new(Some(batch_size), Some(100)).unwrap()
+}
+
+/// Poll until the queue status satisfies `done`
+async fn wait_for(
+    fx: &Fixture,
+    done: impl Fn(&EmbeddingQueueStatus) -> bool,
+) -> EmbeddingQueueStatus {
+    for _ in 0..200 {
+        let status = fx.queue.status();
+        if done(&status) {
+            return status;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!(
+        "queue never reached the expected state: {:?}",
+        fx.queue.status()
+    );
+}
+
+async fn run_to_completion(fx: &Fixture, batch_size: usize) -> EmbeddingQueueStatus {
+    fx.queue.start(options(batch_size)).unwrap();
+    let status = wait_for(fx, |status| status.state == EmbeddingQueueState::Idle).await;
+    // Let the progress subscriber drain
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    status
+}
+
+fn batches(fx: &Fixture) -> Vec<usize> {
+    fx.provider.batches.lock().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_embeds_pending_notes_in_batches() {
+    let fx = fixture(None).await;
+    write_note(&fx, "Bread", "Cooking/bread.md", "Knead the dough.").await;
+    write_note(&fx, "Soup", "Cooking/soup.md", "Simmer the stock.").await;
+    write_note(&fx, "Tea", "Cooking/tea.md", "Steep the leaves.").await;
+
+    let status = run_to_completion(&fx, 2).await;
+    assert_eq!((status.total, status.processed, status.failed), (3, 3, 0));
+    assert_eq!(batches(&fx), vec![2, 1]);
+
+    // Progress is reported per batch, ending with the idle summary
+    let progress = fx.progress.lock().unwrap();
+    match progress.last().unwrap() {
+        DomainEvent::EmbeddingQueueProgress {
+            state,
+            processed,
+            total,
+            failed,
+            ..
+        } => {
+            assert_eq!(*state, EmbeddingQueueState::Idle);
+            assert_eq!((*processed, *total, *failed), (3, 3, 0));
+        }
+        other => panic!("unexpected event: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_only_changed_notes_are_embedded_again() {
+    let fx = fixture(None).await;
+    write_note(&fx, "Bread", "Cooking/bread.md", "Knead the dough.").await;
+    write_note(&fx, "Soup", "Cooking/soup.md", "Simmer the stock.").await;
+    run_to_completion(&fx, 8).await;
+    assert_eq!(batches(&fx), vec![2]);
+
+    // Nothing changed: the run finds no work
+    let status = run_to_completion(&fx, 8).await;
+    assert_eq!((status.total, status.processed), (0, 0));
+    assert_eq!(batches(&fx), vec![2]);
+
+    // An edit changes the content hash
+    edit_note(
+        &fx,
+        "Cooking/soup.md",
+        "Soup",
+        "Simmer the stock for an hour.",
+    );
+    let status = run_to_completion(&fx, 8).await;
+    assert_eq!((status.total, status.processed), (1, 1));
+    assert_eq!(batches(&fx), vec![2, 1]);
+}
+
+#[tokio::test]
+async fn test_trashed_notes_are_not_embedded() {
+    let fx = fixture(None).await;
+    write_note(&fx, "Bread", "Cooking/bread.md", "Knead the dough.").await;
+    let mut soup = write_note(&fx, "Soup", "Cooking/soup.md", "Simmer the stock.").await;
+    soup.delete();
+    fx.notes.save(&soup).await.unwrap();
+
+    let status = run_to_completion(&fx, 8).await;
+    assert_eq!((status.total, status.processed, status.failed), (1, 1, 0));
+    assert_eq!(batches(&fx), vec![1]);
+}
+
+#[tokio::test]
+async fn test_pause_resume_and_cancel_between_batches() {
+    let gate = Arc::new(Semaphore::new(0));
+    let fx = fixture(Some(gate.clone())).await;
+    for name in ["a", "b", "c", "d"] {
+        let path = format!("Notes/{}.md", name);
+        write_note(&fx, name, &path, "Some text worth embedding.").await;
+    }
+
+    fx.queue.start(options(1)).unwrap();
+    wait_for(&fx, |_| batches(&fx).len() == 1).await;
+
+    // Pausing lets the batch in progress finish, then holds the worker
+    assert_eq!(fx.queue.pause().state, EmbeddingQueueState::Paused);
+    gate.add_permits(1);
+    wait_for(&fx, |status| status.processed == 1).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(batches(&fx).len(), 1);
+    assert_eq!(fx.queue.status().state, EmbeddingQueueState::Paused);
+
+    assert_eq!(fx.queue.resume().state, EmbeddingQueueState::Running);
+    gate.add_permits(1);
+    wait_for(&fx, |status| status.processed == 2).await;
+    wait_for(&fx, |_| batches(&fx).len() == 3).await;
+
+    // Cancelling also waits for the batch in progress, then skips the rest
+    fx.queue.cancel();
+    gate.add_permits(1);
+    let status = wait_for(&fx, |status| status.state == EmbeddingQueueState::Idle).await;
+    assert_eq!((status.total, status.processed, status.failed), (4, 3, 0));
+    assert_eq!(batches(&fx).len(), 3);
+
+    // Notes embedded before the cancel are not embedded again
+    gate.add_permits(10);
+    let status = run_to_completion(&fx, 1).await;
+    assert_eq!((status.total, status.processed), (1, 1));
+}
+
+#[tokio::test]
+async fn test_restart_rescans_after_the_batch_in_progress() {
+    let gate = Arc::new(Semaphore::new(0));
+    let fx = fixture(Some(gate.clone())).await;
+    for name in ["a", "b", "c"] {
+        let path = format!("Notes/{}.md", name);
+        write_note(&fx, name, &path, "Some text worth embedding.").await;
+    }
+
+    fx.queue.start(options(1)).unwrap();
+    wait_for(&fx, |_| batches(&fx).len() == 1).await;
+
+    // The run in progress stops after its batch and a fresh scan takes over
+    assert_eq!(
+        fx.queue.restart(options(2)).unwrap().state,
+        EmbeddingQueueState::Running
+    );
+    gate.add_permits(10);
+    let status = wait_for(&fx, |status| status.state == EmbeddingQueueState::Idle).await;
+    assert_eq!((status.total, status.processed, status.failed), (2, 2, 0));
+    assert_eq!(batches(&fx), vec![1, 2]);
+
+    // With nothing running, a restart is a plain start
+    let status = fx.queue.restart(options(2)).unwrap();
+    assert_eq!(status.state, EmbeddingQueueState::Running);
+    let status = wait_for(&fx, |status| status.state == EmbeddingQueueState::Idle).await;
+    assert_eq!(status.total, 0);
+}
+
+#[tokio::test]
+async fn test_unreachable_provider_is_not_swapped_in() {
+    let fx = fixture(None).await;
+    // A port nothing listens on
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let result = fx
+        .embedding_service
+        .configure(EmbeddingProviderConfig::OpenAiCompatible {
+            base_url,
+            model: "nomic-embed-text".to_string(),
+            api_key: None,
+        })
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(fx.embedding_service.model().name, "stub");
+    assert!(fx.embedding_service.is_ready());
+}
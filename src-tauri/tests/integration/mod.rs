@@ -5,3 +5,21 @@ pub mod workspace_sync;
 pub mod quick_capture;
 pub mod note_linking;
 pub mod search_workflows;
+pub mod tag_hierarchy;
+pub mod background_indexing;
+pub mod vector_index;
+pub mod note_chunking;
+pub mod topic_classification;
+pub mod topic_discovery;
+pub mod duplicate_detection;
+pub mod embedding_queue;
+pub mod question_answering;
+pub mod note_templates;
+pub mod periodic_notes;
+pub mod note_aliases;
+pub mod note_anchors;
+pub mod note_rename;
+pub mod note_filenames;
+pub mod note_frontmatter;
+pub mod note_hashtags;
+pub mod tag_rename;
@@ -0,0 +1,280 @@
+//! Test Note Aliases Workflow
+//!
+//! Critical Path: `aliases:` frontmatter → Stored with the note → Links, mentions and title search resolve through them
+//! Names several notes answer to are reported instead of linked.
+
+use stone_tauri_lib::application::usecases::{
+    DuplicateUseCasesImpl, GraphUseCasesImpl, NoteUseCasesImpl,
+};
+use stone_tauri_lib::domain::{
+    entities::Note,
+    ports::{
+        inbound::{
+            DuplicateUseCases, GraphUseCases, LinkSuggestionKind, LinkSuggestionsRequest,
+            MergeNotesRequest, MergeStrategy, NoteUseCases,
+        },
+        outbound::{NoteLinkRepository, NoteRepository},
+    },
+    services::{LinkResolution, LinkResolver, MentionTarget},
+};
+
+use crate::helpers::TestServices;
+
+struct Fixture {
+    services: TestServices,
+    graph: GraphUseCasesImpl,
+    notes_usecases: NoteUseCasesImpl,
+    duplicates: DuplicateUseCasesImpl,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+impl Fixture {
+    /// A note whose file holds `body` under its title heading
+    async fn note(&self, title: &str, body: &str) -> Note {
+        let mut note = Note::new(title, Some(self.workspace.id.clone())).unwrap();
+        note.set_file_path(Some(format!("{}.md", title))).unwrap();
+        self.notes.save(&note).await.unwrap();
+        self.notes_usecases
+            .save_note_content(&note.id, body)
+            .await
+            .unwrap();
+        self.notes.find_by_id(&note.id).await.unwrap().unwrap()
+    }
+
+    async fn linked_titles(&self, note_id: &str) -> Vec<String> {
+        let mut titles: Vec<String> = self
+            .links
+            .get_forward_links(note_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|n| n.title)
+            .collect();
+        titles.sort();
+        titles
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::new("Aliases").await;
+    Fixture {
+        graph: services.graph_usecases(),
+        notes_usecases: services.note_usecases(),
+        duplicates: services.duplicate_usecases(),
+        services,
+    }
+}
+
+#[test]
+fn test_aliases_from_frontmatter() {
+    let inline =
+        "---\ntitle: Rust\naliases: [Rust Lang, \"rustlang\"]\ntags: [code]\n---\n# Rust\n";
+    assert_eq!(
+        LinkResolver::aliases_from_markdown(inline),
+        vec!["Rust Lang", "rustlang"]
+    );
+
+    let block = "---\naliases:\n  - Rust Lang\n  - 'rust lang'\n  - Ferris\ntags:\n  - code\n---\n";
+    assert_eq!(
+        LinkResolver::aliases_from_markdown(block),
+        vec!["Rust Lang", "Ferris"]
+    );
+
+    assert_eq!(
+        LinkResolver::aliases_from_markdown("---\nalias: Ferris\n---\n"),
+        vec!["Ferris"]
+    );
+    // Only frontmatter counts
+    assert!(LinkResolver::aliases_from_markdown("# Rust\n\naliases: [Rust Lang]\n").is_empty());
+}
+
+#[test]
+fn test_titles_take_precedence_over_aliases() {
+    let target = |note_id: &str, title: &str, aliases: &[&str]| MentionTarget {
+        note_id: note_id.to_string(),
+        title: title.to_string(),
+        aliases: aliases.iter().map(|a| a.to_string()).collect(),
+    };
+    let resolver = LinkResolver::new(&[
+        target("rust", "Rust", &["Ferris", "Crab"]),
+        target("crab", "Crab", &[]),
+        target("mascots", "Mascots", &["ferris"]),
+        target("go", "Go", &["Gopher"]),
+    ]);
+
+    assert_eq!(
+        resolver.resolve("rust"),
+        LinkResolution::Resolved("rust".to_string())
+    );
+    assert_eq!(
+        resolver.resolve(" gopher "),
+        LinkResolution::Resolved("go".to_string())
+    );
+    // A title beats an alias of another note
+    assert_eq!(
+        resolver.resolve("Crab"),
+        LinkResolution::Resolved("crab".to_string())
+    );
+    assert_eq!(
+        resolver.resolve("Ferris"),
+        LinkResolution::Ambiguous(vec!["rust".to_string(), "mascots".to_string()])
+    );
+    assert_eq!(resolver.resolve("Python"), LinkResolution::Unresolved);
+}
+
+#[tokio::test]
+async fn test_links_resolve_through_aliases() {
+    let f = fixture().await;
+
+    let ownership = f
+        .note(
+            "Rust Ownership",
+            "---\naliases:\n  - Borrowing\n  - Memory Model\n---\n\nMoves and borrows.",
+        )
+        .await;
+    assert_eq!(ownership.aliases, vec!["Borrowing", "Memory Model"]);
+    // Frontmatter stays at the top of the file, above the title heading
+    let written =
+        std::fs::read_to_string(f.workspace_dir.path().join("Rust Ownership.md")).unwrap();
+    assert!(written.starts_with("---\naliases:\n  - Borrowing\n"));
+    assert!(written.contains("---\n# Rust Ownership\n\nMoves and borrows."));
+
+    f.note(
+        "Garbage Collection",
+        "---\naliases: [Memory Model]\n---\n\nTracing GC.",
+    )
+    .await;
+    let reader = f
+        .note(
+            "Reading List",
+            "Start with [[borrowing]], then [[Memory Model]] and [[Garbage Collection]].\n\n\
+             Lifetimes build on borrowing too.",
+        )
+        .await;
+
+    let content = std::fs::read_to_string(f.workspace_dir.path().join("Reading List.md")).unwrap();
+    let ambiguous = f
+        .graph
+        .update_note_links(&reader.id, &content)
+        .await
+        .unwrap();
+
+    // The alias link resolves; the shared alias is reported, not guessed
+    assert_eq!(
+        f.linked_titles(&reader.id).await,
+        vec!["Garbage Collection", "Rust Ownership"]
+    );
+    assert_eq!(ambiguous.len(), 1);
+    assert_eq!(ambiguous[0].target, "Memory Model");
+    let mut candidates: Vec<&str> = ambiguous[0]
+        .candidates
+        .iter()
+        .map(|c| c.title.as_str())
+        .collect();
+    candidates.sort();
+    assert_eq!(candidates, vec!["Garbage Collection", "Rust Ownership"]);
+
+    let reported = f.graph.get_ambiguous_links(&reader.id).await.unwrap();
+    assert_eq!(reported.len(), 1);
+    assert_eq!(reported[0].target, "Memory Model");
+
+    // Aliases are searchable like titles
+    let found = f
+        .notes
+        .search_by_title("borrow", Some(&f.workspace.id), None)
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, ownership.id);
+
+    // Dropping the alias from the frontmatter drops it from the note
+    f.notes_usecases
+        .save_note_content(&ownership.id, "Moves and borrows.")
+        .await
+        .unwrap();
+    let ownership = f.notes.find_by_id(&ownership.id).await.unwrap().unwrap();
+    assert!(ownership.aliases.is_empty());
+}
+
+#[tokio::test]
+async fn test_unlinked_mentions_include_aliases() {
+    let f = fixture().await;
+
+    let ownership = f
+        .note(
+            "Rust Ownership",
+            "---\naliases: [Borrow Checker]\n---\n\nRules.",
+        )
+        .await;
+    let reader = f
+        .note(
+            "Compiler Errors",
+            "Most of them come from the borrow checker.",
+        )
+        .await;
+
+    let suggestions = f
+        .graph
+        .get_link_suggestions(LinkSuggestionsRequest {
+            note_id: reader.id.clone(),
+            related_limit: Some(0),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].kind, LinkSuggestionKind::Mention);
+    assert_eq!(suggestions[0].target_note_id, ownership.id);
+    assert_eq!(suggestions[0].target_title, "Rust Ownership");
+    assert_eq!(suggestions[0].text, "borrow checker");
+}
+
+#[tokio::test]
+async fn test_merging_points_alias_links_at_the_target() {
+    let f = fixture().await;
+
+    let guide = f.note("Rust Guide", "Everything about Rust.").await;
+    let ownership = f
+        .note(
+            "Rust Ownership",
+            "---\naliases: [Borrowing]\n---\n\nMoves and borrows.",
+        )
+        .await;
+    let reader = f
+        .note(
+            "Reading List",
+            "Start with [[borrowing]], then [[Borrowing#Rules|the rules]] and [[Rust Ownership]].",
+        )
+        .await;
+
+    let response = f
+        .duplicates
+        .merge_notes(MergeNotesRequest {
+            target_id: guide.id.clone(),
+            source_ids: vec![ownership.id.clone()],
+            strategy: MergeStrategy::Keep,
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.merged_ids, vec![ownership.id]);
+    assert_eq!(response.relinked_note_ids, vec![reader.id.clone()]);
+
+    // Links through the merged note's alias no longer lead to the trash
+    let content = std::fs::read_to_string(f.workspace_dir.path().join("Reading List.md")).unwrap();
+    assert!(content.contains(
+        "Start with [[Rust Guide|borrowing]], then [[Rust Guide#Rules|the rules]] \
+         and [[Rust Guide|Rust Ownership]]."
+    ));
+    f.graph
+        .update_note_links(&reader.id, &content)
+        .await
+        .unwrap();
+    assert_eq!(f.linked_titles(&reader.id).await, vec!["Rust Guide"]);
+}
@@ -0,0 +1,241 @@
+//! Test Heading and Block Anchors Workflow
+//!
+//! Critical Path: [[Note#Heading]] / [[Note#^block-id]] → Anchor parsed and stored with the link
+//! → ![[Note#Heading]] embeds expanded inline on export
+
+use stone_tauri_lib::application::usecases::{ExportUseCasesImpl, GraphUseCasesImpl};
+use stone_tauri_lib::domain::{
+    entities::Note,
+    ports::{
+        inbound::{ExportUseCases, GraphUseCases},
+        outbound::{NoteLinkRepository, NoteRepository},
+    },
+    services::{LinkAnchor, LinkExtractor, LinkResolver, NoteAnchors},
+};
+
+use crate::helpers::TestServices;
+
+struct Fixture {
+    services: TestServices,
+    graph: GraphUseCasesImpl,
+    export: ExportUseCasesImpl,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+impl Fixture {
+    /// A note whose file holds exactly `markdown`
+    async fn note(&self, title: &str, markdown: &str) -> Note {
+        let file_path = format!("{}.md", title);
+        std::fs::write(self.workspace_dir.path().join(&file_path), markdown).unwrap();
+
+        let mut note = Note::new(title, Some(self.workspace.id.clone())).unwrap();
+        note.set_file_path(Some(file_path)).unwrap();
+        note.set_aliases(LinkResolver::aliases_from_markdown(markdown));
+        self.notes.save(&note).await.unwrap();
+        note
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::new("Anchors").await;
+    Fixture {
+        graph: services.graph_usecases(),
+        export: services.export_usecases(),
+        services,
+    }
+}
+
+#[test]
+fn test_wiki_link_anchors() {
+    let markdown = "See [[Rust#Ownership]], [[Rust#^claim|the claim]] and [[#Local]].\n\
+                    ![[Rust#Ownership]] then [guide](docs/Rust.md#setup).";
+
+    let links = LinkExtractor::extract_all_links(markdown);
+    assert_eq!(links.len(), 5);
+
+    assert_eq!(links[0].target, "Rust");
+    assert_eq!(
+        links[0].anchor,
+        Some(LinkAnchor::Heading("Ownership".to_string()))
+    );
+    assert_eq!(links[0].text, "Rust#Ownership");
+    assert!(!links[0].embed);
+
+    assert_eq!(
+        links[1].anchor,
+        Some(LinkAnchor::Block("claim".to_string()))
+    );
+    assert_eq!(links[1].text, "the claim");
+
+    // Links into the note itself have no target
+    assert_eq!(links[2].target, "");
+    assert_eq!(
+        links[2].anchor,
+        Some(LinkAnchor::Heading("Local".to_string()))
+    );
+
+    assert!(links[3].embed);
+    assert_eq!(
+        &markdown[links[3].start_index..links[3].end_index],
+        "![[Rust#Ownership]]"
+    );
+
+    assert_eq!(links[4].target, "docs/Rust.md");
+    assert_eq!(
+        links[4].anchor,
+        Some(LinkAnchor::Heading("setup".to_string()))
+    );
+
+    // Anchors don't change which notes are referenced
+    assert_eq!(
+        LinkExtractor::get_referenced_note_titles(markdown),
+        vec!["Rust"]
+    );
+    assert_eq!(LinkAnchor::Block("claim".to_string()).to_string(), "^claim");
+}
+
+#[test]
+fn test_block_markers_and_heading_sections() {
+    let markdown = "---\ntitle: Rust\n---\n# Rust\n\n\
+                    Memory safety\nwithout a GC. ^safety\n\n\
+                    - owned values ^owned\n- borrowed values\n\n\
+                    | a | b |\n|---|---|\n^table\n\n\
+                    ```\nnot a block ^code\n```\n\n\
+                    ## Ownership\n\nEach value has one owner.\n\n### Moves\n\nMoves transfer it.\n\n\
+                    ## Borrowing\n\nReferences.\n";
+
+    let blocks = NoteAnchors::extract_blocks(markdown);
+    let ids: Vec<&str> = blocks.iter().map(|b| b.block_id.as_str()).collect();
+    assert_eq!(ids, vec!["safety", "owned", "table"]);
+
+    assert_eq!(blocks[0].text, "Memory safety\nwithout a GC.");
+    assert_eq!(blocks[0].line_number, 6);
+    assert_eq!(
+        &markdown[blocks[0].start..blocks[0].end],
+        "Memory safety\nwithout a GC. ^safety"
+    );
+    assert_eq!(blocks[1].text, "- owned values");
+    assert_eq!(blocks[2].text, "| a | b |\n|---|---|");
+
+    // A section runs to the next heading of the same or a higher level
+    assert_eq!(
+        NoteAnchors::section(markdown, &LinkAnchor::Heading("ownership".to_string())).unwrap(),
+        "## Ownership\n\nEach value has one owner.\n\n### Moves\n\nMoves transfer it."
+    );
+    assert_eq!(
+        NoteAnchors::section(markdown, &LinkAnchor::Block("safety".to_string())).unwrap(),
+        "Memory safety\nwithout a GC."
+    );
+    assert!(
+        NoteAnchors::section(markdown, &LinkAnchor::Heading("Lifetimes".to_string())).is_none()
+    );
+    assert!(NoteAnchors::section(markdown, &LinkAnchor::Block("code".to_string())).is_none());
+}
+
+#[tokio::test]
+async fn test_links_keep_their_anchors() {
+    let f = fixture().await;
+
+    let rust = f
+        .note("Rust", "# Rust\n\n## Ownership\n\nOne owner. ^owner\n")
+        .await;
+    let reader = f
+        .note(
+            "Reading List",
+            "[[Rust]], [[Rust#Ownership]], [[rust#^owner]] and [[Rust#Ownership|again]].",
+        )
+        .await;
+
+    let content = std::fs::read_to_string(f.workspace_dir.path().join("Reading List.md")).unwrap();
+    f.graph
+        .update_note_links(&reader.id, &content)
+        .await
+        .unwrap();
+
+    let mut anchors: Vec<Option<String>> = f
+        .links
+        .find_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|link| link.target_note_id == rust.id)
+        .map(|link| link.anchor)
+        .collect();
+    anchors.sort();
+    assert_eq!(
+        anchors,
+        vec![
+            None,
+            Some("Ownership".to_string()),
+            Some("^owner".to_string())
+        ]
+    );
+
+    // Still one note linked, and one edge in the graph
+    let forward = f.graph.get_forward_links(&reader.id).await.unwrap();
+    assert_eq!(forward.len(), 1);
+    let graph = f.graph.get_graph_data(None).await.unwrap();
+    assert_eq!(graph.links.len(), 1);
+}
+
+#[tokio::test]
+async fn test_embeds_render_inline() {
+    let f = fixture().await;
+
+    f.note(
+        "Rust",
+        "---\naliases: [Ferris]\n---\n# Rust\n\n## Ownership\n\nOne owner. ^owner\n\n\
+         ![[Rust#Ownership]]\n\n## Borrowing\n\nShared or unique.\n",
+    )
+    .await;
+    let summary = f
+        .note(
+            "Summary",
+            "# Summary\n\n![[Ferris#Ownership]]\n\nIn short: ![[Rust#^owner]]\n\n\
+             ![[Rust#Lifetimes]]\n\n![[Summary]]\n",
+        )
+        .await;
+
+    let resolved = f
+        .graph
+        .resolve_embed(&summary.id, "Rust#Borrowing")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(resolved.title, "Rust");
+    assert_eq!(resolved.anchor.as_deref(), Some("Borrowing"));
+    assert_eq!(resolved.markdown, "## Borrowing\n\nShared or unique.");
+    assert!(f
+        .graph
+        .resolve_embed(&summary.id, "Rust#Lifetimes")
+        .await
+        .unwrap()
+        .is_none());
+
+    let exported = f.export.export_markdown(&summary.id, None).await.unwrap();
+    let exported = String::from_utf8(exported.content).unwrap();
+
+    // Sections and blocks are transcluded without their markers; the section
+    // embedding itself, the missing heading and the note embedding itself stay
+    assert_eq!(
+        exported,
+        "# Summary\n\n## Ownership\n\nOne owner.\n\n![[Rust#Ownership]]\n\n\
+         In short: One owner.\n\n![[Rust#Lifetimes]]\n\n![[Summary]]\n"
+    );
+
+    let html = f
+        .export
+        .export_html(&summary.id, None, None, None)
+        .await
+        .unwrap();
+    let html = String::from_utf8(html.content).unwrap();
+    assert!(html.contains("<h2>Ownership</h2>"));
+    assert!(html.contains("In short: One owner."));
+}
@@ -0,0 +1,131 @@
+//! Test Note Chunking
+//!
+//! Critical Path: Note markdown → Heading/paragraph chunks → Passage embeddings
+//! Long notes must be split so that every section reaches the embedding model,
+//! and each passage must point back at its exact place in the file.
+
+use stone_tauri_lib::domain::services::NoteChunker;
+
+const GUIDE: &str = "---
+title: Guide
+tags: [docs]
+---
+# Guide
+
+Welcome to the guide.
+
+## Install
+
+Download the installer.
+
+Run it twice.
+
+### Linux
+
+```sh
+# not a heading
+
+make install
+```
+
+## Usage
+
+Open the app.
+";
+
+#[test]
+fn test_chunks_follow_headings() {
+    let chunks = NoteChunker::default().chunk(GUIDE);
+
+    let paths: Vec<Vec<&str>> = chunks
+        .iter()
+        .map(|c| c.heading_path.iter().map(String::as_str).collect())
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            vec!["Guide"],
+            vec!["Guide", "Install"],
+            vec!["Guide", "Install", "Linux"],
+            vec!["Guide", "Usage"],
+        ]
+    );
+
+    // Paragraphs of a section stay together when they fit
+    assert_eq!(
+        chunks[1].text,
+        "## Install\n\nDownload the installer.\n\nRun it twice."
+    );
+    // Code blocks are kept whole and their comments aren't headings
+    assert!(chunks[2]
+        .text
+        .contains("# not a heading\n\nmake install\n```"));
+}
+
+#[test]
+fn test_offsets_point_into_the_markdown() {
+    let chunks = NoteChunker::default().chunk(GUIDE);
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert_eq!(chunk.index, i);
+        assert_eq!(&GUIDE[chunk.start..chunk.end], chunk.text);
+    }
+    // Frontmatter is not part of any passage
+    assert!(chunks[0].text.starts_with("# Guide"));
+}
+
+#[test]
+fn test_long_sections_are_split_within_the_limit() {
+    let paragraph = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(4);
+    let markdown = format!(
+        "# Notes\n\n{}\n\n{}\n\n{}\n\nÜberlange Zeile {}",
+        paragraph,
+        paragraph,
+        paragraph,
+        "wörter ".repeat(60)
+    );
+
+    let chunker = NoteChunker::new(300);
+    let chunks = chunker.chunk(&markdown);
+
+    assert!(chunks.len() > 3);
+    for chunk in &chunks {
+        assert!(chunk.heading_path == vec!["Notes"]);
+        assert_eq!(&markdown[chunk.start..chunk.end], chunk.text);
+        // The heading may ride along with the first passage
+        assert!(chunk.text.len() <= 300 + "# Notes\n\n".len());
+    }
+
+    // Every word of the note ends up in some passage
+    let covered: usize = chunks
+        .iter()
+        .map(|c| c.text.split_whitespace().count())
+        .sum();
+    assert_eq!(covered, markdown.split_whitespace().count());
+}
+
+#[test]
+fn test_limit_narrower_than_a_character_still_advances() {
+    let markdown = "日本語✓";
+
+    let chunks = NoteChunker::new(2).chunk(markdown);
+
+    assert_eq!(
+        chunks.iter().map(|c| c.text.as_str()).collect::<Vec<_>>(),
+        vec!["日", "本", "語", "✓"]
+    );
+}
+
+#[test]
+fn test_empty_and_heading_only_notes() {
+    let chunker = NoteChunker::default();
+
+    assert!(chunker.chunk("").is_empty());
+    assert!(chunker.chunk("---\ntitle: Empty\n---\n").is_empty());
+    assert!(chunker.chunk("# Title\n\n## Section\n").is_empty());
+
+    let chunks = chunker.chunk("Plain note without headings.\n\n#tag");
+    assert_eq!(chunks.len(), 1);
+    assert!(chunks[0].heading_path.is_empty());
+    assert_eq!(chunks[0].text, "Plain note without headings.\n\n#tag");
+}
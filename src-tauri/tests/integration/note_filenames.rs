@@ -0,0 +1,307 @@
+//! Test Note Filename Workflow
+//!
+//! Critical Path: Workspace filename settings → New notes named by timestamp, slug or title → Collisions suffixed
+//! Retitled notes can have their file renamed, with relative links, backlinks and the git index following.
+
+use std::path::Path;
+
+use stone_tauri_lib::adapters::outbound::services::Git2Service;
+use stone_tauri_lib::application::usecases::{NoteUseCasesImpl, RefactorUseCasesImpl};
+use stone_tauri_lib::domain::{
+    entities::{Note, NoteLink},
+    ports::{
+        inbound::{
+            CreateNoteInput, FilenameSettings, NoteUseCases, RefactorUseCases, RenameNoteRequest,
+            UpdateNoteInput,
+        },
+        outbound::{GitService, NoteLinkRepository, NoteRepository},
+    },
+    services::{FilenameStrategy, NoteFilenames},
+};
+
+use crate::helpers::TestServices;
+
+struct Fixture {
+    services: TestServices,
+    notes_usecases: NoteUseCasesImpl,
+    refactor: RefactorUseCasesImpl,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+impl Fixture {
+    async fn use_strategy(&self, strategy: FilenameStrategy, rename_on_title_change: bool) {
+        self.note_filenames
+            .save_settings(
+                &self.workspace.id,
+                &FilenameSettings {
+                    strategy,
+                    rename_on_title_change,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn create(&self, title: &str, folder: &str, content: &str) -> Note {
+        self.notes_usecases
+            .create_note(CreateNoteInput {
+                title: title.to_string(),
+                content: Some(content.to_string()),
+                notebook_id: None,
+                workspace_id: None,
+                folder_path: Some(folder.to_string()),
+                relative_path: None,
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn retitle(&self, note: &Note, title: &str) -> Note {
+        self.notes_usecases
+            .update_note(UpdateNoteInput {
+                id: note.id.clone(),
+                title: Some(title.to_string()),
+                content: None,
+                notebook_id: None,
+                is_favorite: None,
+                is_pinned: None,
+                is_archived: None,
+            })
+            .await
+            .unwrap()
+    }
+
+    fn exists(&self, file_path: &str) -> bool {
+        self.workspace_dir.path().join(file_path).exists()
+    }
+
+    fn read(&self, file_path: &str) -> String {
+        std::fs::read_to_string(self.workspace_dir.path().join(file_path)).unwrap()
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::new("Filenames").await;
+    Fixture {
+        notes_usecases: services.note_usecases(),
+        refactor: services.refactor_usecases(),
+        services,
+    }
+}
+
+#[test]
+fn test_slugify_transliterates_titles() {
+    assert_eq!(NoteFilenames::slugify("Crème Brûlée"), "creme-brulee");
+    assert_eq!(NoteFilenames::slugify("Straße & Ærø"), "strasse-and-aero");
+    assert_eq!(NoteFilenames::slugify("Привет, мир!"), "privet-mir");
+    assert_eq!(NoteFilenames::slugify("Ελληνικά"), "ellinika");
+    assert_eq!(
+        NoteFilenames::slugify("  Q3 -- Plan (draft) "),
+        "q3-plan-draft"
+    );
+    // Decomposed accents are dropped rather than splitting the word
+    assert_eq!(NoteFilenames::slugify("Cafe\u{301}"), "cafe");
+    assert_eq!(NoteFilenames::slugify("日本語 notes"), "日本語-notes");
+    assert_eq!(NoteFilenames::slugify("???"), "untitled");
+    assert_eq!(NoteFilenames::slugify(&"a".repeat(150)).len(), 100);
+}
+
+#[test]
+fn test_sanitize_keeps_titles_readable() {
+    assert_eq!(NoteFilenames::sanitize("Crème Brûlée"), "Crème Brûlée");
+    assert_eq!(
+        NoteFilenames::sanitize("Q3: Plan / Review?"),
+        "Q3- Plan - Review-"
+    );
+    assert_eq!(
+        NoteFilenames::sanitize("  ..hidden  note.  "),
+        "hidden note"
+    );
+    assert_eq!(NoteFilenames::sanitize("..."), "Untitled");
+
+    assert_eq!(
+        NoteFilenames::with_suffix(FilenameStrategy::Title, "Road Map", 2),
+        "Road Map 2"
+    );
+    assert_eq!(
+        NoteFilenames::with_suffix(FilenameStrategy::Slug, "road-map", 2),
+        "road-map-2"
+    );
+    assert!(NoteFilenames::is_variant_of(
+        FilenameStrategy::Slug,
+        "road-map-12",
+        "road-map"
+    ));
+    assert!(!NoteFilenames::is_variant_of(
+        FilenameStrategy::Slug,
+        "road-map-v2",
+        "road-map"
+    ));
+}
+
+#[tokio::test]
+async fn test_create_note_names_files_by_strategy() {
+    let f = fixture().await;
+
+    // Timestamps stay the default
+    let stamped = f.create("Crème Brûlée", "Recipes", "").await;
+    let stamped_name = Path::new(stamped.file_path.as_deref().unwrap())
+        .file_stem()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+    assert_eq!(stamped_name.len(), "20260117-093012-345".len());
+    assert!(stamped_name.chars().all(|c| c.is_ascii_digit() || c == '-'));
+
+    f.use_strategy(FilenameStrategy::Slug, false).await;
+    let first = f.create("Crème Brûlée", "Recipes", "").await;
+    let second = f.create("Creme brulee!", "Recipes", "").await;
+    assert_eq!(first.file_path.as_deref(), Some("Recipes/creme-brulee.md"));
+    assert_eq!(
+        second.file_path.as_deref(),
+        Some("Recipes/creme-brulee-2.md")
+    );
+    assert!(f.exists("Recipes/creme-brulee-2.md"));
+
+    // A file nobody indexed yet still takes the name
+    std::fs::write(
+        f.workspace_dir.path().join("Recipes/Road Map.md"),
+        "# Old\n",
+    )
+    .unwrap();
+    f.use_strategy(FilenameStrategy::Title, false).await;
+    let titled = f.create("Road Map", "Recipes", "").await;
+    assert_eq!(titled.file_path.as_deref(), Some("Recipes/Road Map 2.md"));
+
+    let settings = f.note_filenames.settings(&f.workspace.id).await.unwrap();
+    assert_eq!(settings.strategy, FilenameStrategy::Title);
+    assert!(!settings.rename_on_title_change);
+}
+
+#[tokio::test]
+async fn test_retitling_renames_file_and_keeps_links_working() {
+    let f = fixture().await;
+    f.use_strategy(FilenameStrategy::Slug, true).await;
+
+    let draft = f
+        .create(
+            "Draft",
+            "Plans",
+            "# Draft\n\nSee [the index](../index.md).\n",
+        )
+        .await;
+    let index = f
+        .create("Index", "", "# Index\n\n- [Draft](Plans/draft.md#goals)\n")
+        .await;
+    assert_eq!(draft.file_path.as_deref(), Some("Plans/draft.md"));
+    assert_eq!(index.file_path.as_deref(), Some("index.md"));
+
+    let renamed = f.retitle(&draft, "Launch Plan").await;
+    assert_eq!(renamed.file_path.as_deref(), Some("Plans/launch-plan.md"));
+    assert!(!f.exists("Plans/draft.md"));
+    assert_eq!(
+        f.read("Plans/launch-plan.md"),
+        "# Launch Plan\n\nSee [the index](../index.md).\n"
+    );
+    assert_eq!(
+        f.read("index.md"),
+        "# Index\n\n- [Draft](Plans/launch-plan.md#goals)\n"
+    );
+
+    let stored = f.notes.find_by_id(&draft.id).await.unwrap().unwrap();
+    assert_eq!(stored.file_path.as_deref(), Some("Plans/launch-plan.md"));
+    let by_path = f
+        .notes
+        .find_by_file_path("Plans/launch-plan.md", Some(&f.workspace.id))
+        .await
+        .unwrap();
+    assert_eq!(by_path.map(|n| n.id), Some(draft.id.clone()));
+
+    // A title with the same slug, or a numbered variant of it, keeps the file
+    let same = f.retitle(&renamed, "Launch plan!").await;
+    assert_eq!(same.file_path.as_deref(), Some("Plans/launch-plan.md"));
+
+    // Without the setting only the title changes
+    f.use_strategy(FilenameStrategy::Slug, false).await;
+    let kept = f.retitle(&same, "Roadmap").await;
+    assert_eq!(kept.file_path.as_deref(), Some("Plans/launch-plan.md"));
+}
+
+#[tokio::test]
+async fn test_rename_note_moves_file_with_backlinks() {
+    let f = fixture().await;
+    f.use_strategy(FilenameStrategy::Title, true).await;
+
+    let rust = f.create("Rust", "Ideas", "# Rust\n").await;
+    let journal = f
+        .create("Journal", "", "# Journal\n\nLearning [[Rust]] today.\n")
+        .await;
+    f.links
+        .save(&NoteLink::new(journal.id.clone(), rust.id.clone()).unwrap())
+        .await
+        .unwrap();
+
+    // A file no note is stored for yet takes the name, so the rename gets a suffix
+    std::fs::write(
+        f.workspace_dir.path().join("Ideas/Rust Language.md"),
+        "# Draft\n",
+    )
+    .unwrap();
+
+    let response = f
+        .refactor
+        .rename_note(RenameNoteRequest {
+            note_id: rust.id.clone(),
+            new_title: "Rust Language".to_string(),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.note.file_path.as_deref(),
+        Some("Ideas/Rust Language 2.md")
+    );
+    assert!(!f.exists("Ideas/Rust.md"));
+    assert_eq!(f.read("Ideas/Rust Language 2.md"), "# Rust Language\n");
+    assert_eq!(
+        f.read("Journal.md"),
+        "# Journal\n\nLearning [[Rust Language]] today.\n"
+    );
+}
+
+#[tokio::test]
+async fn test_rename_is_staged_in_git() {
+    let f = fixture().await;
+    f.use_strategy(FilenameStrategy::Slug, true).await;
+    let note = f.create("Draft", "Plans", "# Draft\n").await;
+
+    let git = Git2Service::new();
+    let root = f.workspace.folder_path.clone();
+    git.init(&root).await.unwrap();
+    git.stage(&root, None).await.unwrap();
+    git.commit(&root, "Add draft").await.unwrap();
+
+    let renamed = f.retitle(&note, "Final Plan").await;
+    assert_eq!(renamed.file_path.as_deref(), Some("Plans/final-plan.md"));
+
+    let repo = git2::Repository::open(&root).unwrap();
+    let index = repo.index().unwrap();
+    assert!(index.get_path(Path::new("Plans/draft.md"), 0).is_none());
+    assert!(index
+        .get_path(Path::new("Plans/final-plan.md"), 0)
+        .is_some());
+
+    // Untracked notes are renamed without being added
+    let loose = f.create("Loose", "Plans", "").await;
+    f.retitle(&loose, "Tied").await;
+    let index = repo.index().unwrap();
+    assert!(index.get_path(Path::new("Plans/tied.md"), 0).is_none());
+    assert!(f.exists("Plans/tied.md"));
+}
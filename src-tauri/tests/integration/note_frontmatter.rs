@@ -0,0 +1,286 @@
+//! Test Note Frontmatter Workflow
+//!
+//! Critical Path: YAML frontmatter parsed → Tags, aliases, created date and properties synced on save and workspace sync
+//! Tags changed in the app are written back into the file, leaving the rest of the frontmatter as it was.
+
+use std::path::Path;
+
+use stone_tauri_lib::application::usecases::{
+    NoteUseCasesImpl, TagUseCasesImpl, WorkspaceUseCasesImpl,
+};
+use stone_tauri_lib::domain::{
+    entities::Tag,
+    ports::{
+        inbound::{NoteUseCases, TagUseCases, WorkspaceUseCases},
+        outbound::{NoteRepository, TagRepository},
+    },
+    services::{Frontmatter, NoteFrontmatter, YamlValue},
+};
+
+use crate::helpers::TestServices;
+
+/// Frontmatter the old line-based parser got wrong
+const FRONTMATTER: &str = r#"title: "Rust: the good parts" # the title
+aliases:
+  - Rust Book
+  - 'It''s Rust'
+tags: [rust, "lang: systems"]
+# Written by hand
+project:
+  name: Stone
+  owners: [ana, ben]
+  links:
+    - url: https://example.com/a
+      kind: doc
+summary: >-
+  Folded
+  text
+created: 2026-01-17 09:30"#;
+
+struct Fixture {
+    services: TestServices,
+    notes_usecases: NoteUseCasesImpl,
+    workspace_usecases: WorkspaceUseCasesImpl,
+    tag_usecases: TagUseCasesImpl,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+impl Fixture {
+    fn write(&self, file_path: &str, content: &str) {
+        let path = Path::new(&self.workspace.folder_path).join(file_path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn read(&self, file_path: &str) -> String {
+        std::fs::read_to_string(Path::new(&self.workspace.folder_path).join(file_path)).unwrap()
+    }
+
+    async fn tag_names(&self, note_id: &str) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .tags
+            .find_by_note_id(note_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::new("Frontmatter").await;
+    Fixture {
+        notes_usecases: services.note_usecases(),
+        workspace_usecases: services.workspace_usecases(),
+        tag_usecases: services.tag_usecases(),
+        services,
+    }
+}
+
+#[test]
+fn test_parse_nested_frontmatter() {
+    let frontmatter = Frontmatter::parse(FRONTMATTER).unwrap();
+
+    assert_eq!(
+        frontmatter.get("title"),
+        Some(&YamlValue::String("Rust: the good parts".to_string()))
+    );
+    assert_eq!(
+        frontmatter.get("aliases").unwrap().as_string_list(),
+        vec!["Rust Book", "It's Rust"]
+    );
+    assert_eq!(
+        frontmatter.get("tags").unwrap().as_string_list(),
+        vec!["rust", "lang: systems"]
+    );
+    assert_eq!(
+        frontmatter.get("summary"),
+        Some(&YamlValue::String("Folded text".to_string()))
+    );
+
+    let project = frontmatter.get("project").unwrap().to_json();
+    assert_eq!(project["name"], "Stone");
+    assert_eq!(project["owners"], serde_json::json!(["ana", "ben"]));
+    assert_eq!(project["links"][0]["url"], "https://example.com/a");
+    assert_eq!(project["links"][0]["kind"], "doc");
+
+    let keys: Vec<&str> = frontmatter.entries().map(|(key, _)| key).collect();
+    assert_eq!(
+        keys,
+        vec!["title", "aliases", "tags", "project", "summary", "created"]
+    );
+
+    // Untouched frontmatter comes back byte for byte
+    assert_eq!(frontmatter.to_yaml(), FRONTMATTER);
+
+    assert!(Frontmatter::parse("tags: [unclosed").is_err());
+    assert!(Frontmatter::parse("- not\n- a mapping").is_err());
+}
+
+#[test]
+fn test_set_rewrites_only_that_key() {
+    let mut frontmatter = Frontmatter::parse(FRONTMATTER).unwrap();
+    frontmatter.set("aliases", YamlValue::string_list(&["Rust Book", "Ferris"]));
+    frontmatter.set("tags", YamlValue::string_list(&["rust"]));
+    frontmatter.set("status", YamlValue::String("draft".to_string()));
+
+    let yaml = frontmatter.to_yaml();
+    // Block lists stay block lists, with their quoting
+    assert!(yaml.contains("aliases:\n  - 'Rust Book'\n  - 'Ferris'\n"));
+    // Flow lists stay flow lists
+    assert!(yaml.contains("\ntags: [\"rust\"]\n"));
+    // Comments, other keys and their order are kept; new keys are appended
+    assert!(yaml.starts_with("title: \"Rust: the good parts\" # the title\n"));
+    assert!(yaml.contains("# Written by hand\nproject:\n  name: Stone\n"));
+    assert!(yaml.ends_with("created: 2026-01-17 09:30\nstatus: \"draft\""));
+
+    let reparsed = Frontmatter::parse(&yaml).unwrap();
+    assert_eq!(
+        reparsed.get("aliases").unwrap().as_string_list(),
+        vec!["Rust Book", "Ferris"]
+    );
+    assert_eq!(reparsed.get("project"), frontmatter.get("project"));
+
+    assert!(frontmatter.remove("summary"));
+    assert!(!frontmatter.to_yaml().contains("Folded"));
+}
+
+#[test]
+fn test_note_frontmatter_fields() {
+    let markdown = format!("---\n{}\n---\n# Rust\n", FRONTMATTER);
+    let note = NoteFrontmatter::from_markdown(&markdown);
+
+    assert_eq!(
+        note.tags,
+        Some(vec!["rust".to_string(), "lang: systems".to_string()])
+    );
+    assert_eq!(note.aliases, vec!["Rust Book", "It's Rust"]);
+    assert_eq!(
+        note.created.unwrap().to_rfc3339(),
+        "2026-01-17T09:30:00+00:00"
+    );
+    let keys: Vec<&String> = note.properties.keys().collect();
+    assert_eq!(keys, vec!["project", "summary"]);
+
+    // No `tags` key leaves tags alone; comma-separated strings are lists
+    let untagged = NoteFrontmatter::from_markdown("---\naliases: A, B\n---\n");
+    assert_eq!(untagged.tags, None);
+    assert_eq!(untagged.aliases, vec!["A", "B"]);
+    assert_eq!(
+        NoteFrontmatter::from_markdown("---\ntags: '#rust, #web'\n---\n").tags,
+        Some(vec!["rust".to_string(), "web".to_string()])
+    );
+}
+
+#[tokio::test]
+async fn test_workspace_sync_reads_frontmatter() {
+    let f = fixture().await;
+    f.write(
+        "Rust.md",
+        &format!("---\n{}\n---\n# Rust\n\nBody\n", FRONTMATTER),
+    );
+
+    f.workspace_usecases
+        .sync_workspace(Some(&f.workspace.id))
+        .await
+        .unwrap();
+
+    let note = f
+        .notes
+        .find_by_file_path("Rust.md", Some(&f.workspace.id))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(note.aliases, vec!["Rust Book", "It's Rust"]);
+    assert_eq!(note.created_at.to_rfc3339(), "2026-01-17T09:30:00+00:00");
+    assert_eq!(note.properties["project"]["name"], "Stone");
+    assert_eq!(note.properties["summary"], "Folded text");
+    assert_eq!(f.tag_names(&note.id).await, vec!["lang-systems", "rust"]);
+}
+
+#[tokio::test]
+async fn test_saving_syncs_tags_and_app_tags_write_back() {
+    let f = fixture().await;
+    let body = "---\n# keep me\ntags:\n  - Rust\n  - web\nstatus: draft\n---\n\nBody\n";
+    f.write("Rust.md", &format!("# Rust\n\n{}", body));
+    f.workspace_usecases
+        .sync_workspace(Some(&f.workspace.id))
+        .await
+        .unwrap();
+    let note = f
+        .notes
+        .find_by_file_path("Rust.md", Some(&f.workspace.id))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Saving replaces the note's tags with those the frontmatter lists
+    f.notes_usecases
+        .save_note_content(&note.id, body)
+        .await
+        .unwrap();
+    assert_eq!(f.tag_names(&note.id).await, vec!["rust", "web"]);
+    assert_eq!(
+        f.notes
+            .find_by_id(&note.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .properties["status"],
+        "draft"
+    );
+
+    f.notes_usecases
+        .save_note_content(&note.id, &body.replace("  - web\n", ""))
+        .await
+        .unwrap();
+    assert_eq!(f.tag_names(&note.id).await, vec!["rust"]);
+
+    // Tags added and removed in the app land in the file
+    let tag = Tag::new("notes").unwrap();
+    f.tags.save(&tag).await.unwrap();
+    f.tag_usecases
+        .add_tag_to_note(&note.id, &tag.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        f.read("Rust.md"),
+        "---\n# keep me\ntags:\n  - Rust\n  - notes\nstatus: draft\n---\n# Rust\n\nBody\n"
+    );
+
+    let rust = f.tags.find_by_name("rust").await.unwrap().unwrap();
+    f.tag_usecases
+        .remove_tag_from_note(&note.id, &rust.id)
+        .await
+        .unwrap();
+    assert!(f
+        .read("Rust.md")
+        .starts_with("---\n# keep me\ntags:\n  - notes\nstatus: draft\n---\n"));
+
+    // A file without frontmatter gets one when the note is tagged
+    f.write("Plain.md", "# Plain\n\nText\n");
+    let plain = f
+        .notes_usecases
+        .get_note_by_path("Plain.md")
+        .await
+        .unwrap()
+        .unwrap();
+    f.tag_usecases
+        .add_tag_to_note(&plain.id, &tag.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        f.read("Plain.md"),
+        "---\ntags: [\"notes\"]\n---\n\n# Plain\n\nText\n"
+    );
+}
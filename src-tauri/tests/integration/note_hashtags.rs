@@ -0,0 +1,230 @@
+//! Test Note Hashtags Workflow
+//!
+//! Critical Path: #hashtags extracted from content → Tags synced on save and workspace sync
+//! Each note tag records whether it came from the content or was added by hand, and only
+//! manual tags are written to the frontmatter.
+
+use std::path::Path;
+
+use stone_tauri_lib::application::usecases::{
+    NoteUseCasesImpl, TagUseCasesImpl, WorkspaceUseCasesImpl,
+};
+use stone_tauri_lib::domain::{
+    entities::{Note, Tag, TagSource},
+    ports::{
+        inbound::{NoteUseCases, TagUseCases, WorkspaceUseCases},
+        outbound::{NoteRepository, TagRepository},
+    },
+    services::{Frontmatter, HashtagExtractor},
+};
+
+use crate::helpers::TestServices;
+
+struct Fixture {
+    services: TestServices,
+    notes_usecases: NoteUseCasesImpl,
+    workspace_usecases: WorkspaceUseCasesImpl,
+    tag_usecases: TagUseCasesImpl,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+impl Fixture {
+    fn write(&self, file_path: &str, content: &str) {
+        let path = Path::new(&self.workspace.folder_path).join(file_path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn read(&self, file_path: &str) -> String {
+        std::fs::read_to_string(Path::new(&self.workspace.folder_path).join(file_path)).unwrap()
+    }
+
+    async fn tag_sources(&self, note_id: &str) -> Vec<(String, TagSource)> {
+        self.tags
+            .find_note_tags_with_source(note_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|link| (link.tag.name, link.source))
+            .collect()
+    }
+
+    async fn note(&self, file_path: &str) -> Note {
+        self.notes
+            .find_by_file_path(file_path, Some(&self.workspace.id))
+            .await
+            .unwrap()
+            .unwrap()
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::new("Hashtags").await;
+    Fixture {
+        notes_usecases: services.note_usecases(),
+        workspace_usecases: services.workspace_usecases(),
+        tag_usecases: services.tag_usecases(),
+        services,
+    }
+}
+
+#[test]
+fn test_extract_hashtags() {
+    let markdown = r#"---
+tags: [front]
+---
+# Heading #not-a-tag
+
+Working on #rust and #Area/Sub-tag today, (#todo) and #rust again.
+Not tags: C#, &#39;, #123, ##double, `#code`, [[Note#Section]],
+[link](https://example.com/page#anchor), https://example.com/#top
+
+```sh
+# comment #shell
+```
+
+    #indented-code
+
+## Next #heading
+Trailing #done/ and #end."#;
+
+    assert_eq!(
+        HashtagExtractor::extract(markdown),
+        vec!["rust", "Area/Sub-tag", "todo", "done", "end"]
+    );
+
+    let found = HashtagExtractor::find("See #rust-lang, ok");
+    assert_eq!(found.len(), 1);
+    assert_eq!(
+        &"See #rust-lang, ok"[found[0].start_index..found[0].end_index],
+        "#rust-lang"
+    );
+}
+
+#[test]
+fn test_crlf_frontmatter_holds_no_hashtags() {
+    let markdown = "---\r\ncolor: #ff0000\r\ntags: [front]\r\n---\r\nBody with #real.\r\n";
+
+    let (yaml, body) = Frontmatter::split(markdown);
+    assert_eq!(yaml, Some("color: #ff0000\r\ntags: [front]"));
+    assert_eq!(body, "Body with #real.\r\n");
+    assert_eq!(HashtagExtractor::extract(markdown), vec!["real"]);
+}
+
+#[test]
+fn test_nested_tag_names() {
+    assert_eq!(Tag::normalize_name("Area/Sub Tag").unwrap(), "area/sub-tag");
+    assert_eq!(Tag::normalize_name("/area//sub/").unwrap(), "area/sub");
+    assert!(Tag::normalize_name("//").is_err());
+}
+
+#[tokio::test]
+async fn test_hashtags_sync_as_content_tags() {
+    let f = fixture().await;
+    f.write(
+        "Rust.md",
+        "---\ntags: [web]\n---\n# Rust\n\nLearning #rust and #area/systems.\n",
+    );
+    f.workspace_usecases
+        .sync_workspace(Some(&f.workspace.id))
+        .await
+        .unwrap();
+    let note = f.note("Rust.md").await;
+    assert_eq!(
+        f.tag_sources(&note.id).await,
+        vec![
+            ("area/systems".to_string(), TagSource::Content),
+            ("rust".to_string(), TagSource::Content),
+            ("web".to_string(), TagSource::Manual),
+        ]
+    );
+
+    // Deleting a hashtag from the text removes its tag; listing it keeps it as manual
+    f.notes_usecases
+        .save_note_content(
+            &note.id,
+            "---\ntags: [web, rust]\n---\n\nLearning #area/systems.\n",
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        f.tag_sources(&note.id).await,
+        vec![
+            ("area/systems".to_string(), TagSource::Content),
+            ("rust".to_string(), TagSource::Manual),
+            ("web".to_string(), TagSource::Manual),
+        ]
+    );
+
+    f.notes_usecases
+        .save_note_content(&note.id, "---\ntags: [web, rust]\n---\n\nLearning.\n")
+        .await
+        .unwrap();
+    assert_eq!(
+        f.tag_sources(&note.id).await,
+        vec![
+            ("rust".to_string(), TagSource::Manual),
+            ("web".to_string(), TagSource::Manual),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_manual_tags_outlive_their_hashtags() {
+    let f = fixture().await;
+    f.write("Plain.md", "# Plain\n\nAbout #rust.\n");
+    let note = f
+        .notes_usecases
+        .get_note_by_path("Plain.md")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        f.tag_sources(&note.id).await,
+        vec![("rust".to_string(), TagSource::Content)]
+    );
+    // Content tags stay out of the frontmatter
+    assert_eq!(f.read("Plain.md"), "# Plain\n\nAbout #rust.\n");
+
+    // Adding the tag in the app makes it manual and writes it to the file
+    let rust = f.tags.find_by_name("rust").await.unwrap().unwrap();
+    f.tag_usecases
+        .add_tag_to_note(&note.id, &rust.id)
+        .await
+        .unwrap();
+    let note_tags = f.tag_usecases.get_note_tags(&note.id).await.unwrap();
+    assert_eq!(note_tags.len(), 1);
+    assert_eq!(note_tags[0].source, TagSource::Manual);
+    assert!(f
+        .read("Plain.md")
+        .starts_with("---\ntags: [\"rust\"]\n---\n"));
+
+    f.notes_usecases
+        .save_note_content(&note.id, "---\ntags: [\"rust\"]\n---\n\nNo hashtag now.\n")
+        .await
+        .unwrap();
+    assert_eq!(
+        f.tag_sources(&note.id).await,
+        vec![("rust".to_string(), TagSource::Manual)]
+    );
+
+    // Without a tags key, manual tags stay and new hashtags are added
+    f.notes_usecases
+        .save_note_content(&note.id, "Now #web.\n")
+        .await
+        .unwrap();
+    assert_eq!(
+        f.tag_sources(&note.id).await,
+        vec![
+            ("rust".to_string(), TagSource::Manual),
+            ("web".to_string(), TagSource::Content),
+        ]
+    );
+}
@@ -3,8 +3,10 @@
 //! Critical Path: Create wiki links → Track relationships → Query backlinks
 //! This enables knowledge graph and note connections
 
+use std::collections::HashMap;
+
 use stone_tauri_lib::domain::entities::NoteLink;
-use stone_tauri_lib::domain::services::LinkExtractor;
+use stone_tauri_lib::domain::services::{LinkExtractor, LinkSuggester, MentionTarget};
 
 #[test]
 fn test_note_link_creation() {
@@ -125,3 +127,116 @@ fn test_empty_link_handling() {
         assert!(links.is_empty() || links[0].target.trim().is_empty());
     }
 }
+
+#[test]
+fn test_retarget_wiki_links_keeps_display_text() {
+    // Merging notes points links at the surviving note
+    let markdown = "See [[Old Idea]], [[old idea|the idea]] and [[Other]].";
+    let targets = HashMap::from([("old idea".to_string(), "New Idea".to_string())]);
+
+    let rewritten = LinkExtractor::retarget_wiki_links(markdown, &targets).unwrap();
+
+    assert_eq!(
+        rewritten,
+        "See [[New Idea|Old Idea]], [[New Idea|the idea]] and [[Other]]."
+    );
+    assert!(LinkExtractor::retarget_wiki_links("No [[Other]] links", &targets).is_none());
+}
+
+fn mention_target(note_id: &str, title: &str, aliases: &[&str]) -> MentionTarget {
+    MentionTarget {
+        note_id: note_id.to_string(),
+        title: title.to_string(),
+        aliases: aliases.iter().map(|a| a.to_string()).collect(),
+    }
+}
+
+#[test]
+fn test_unlinked_mentions_skip_code_links_and_headings() {
+    let markdown = "---\ntitle: Rust Ownership\n---\n# Rust Ownership\n\n\
+        Borrowing builds on rust ownership, see `Rust Ownership` and [[Lifetimes]].\n\
+        ```\nRust Ownership in code\n```\n\
+        Read https://example.com/rust-ownership or RustOwnership. The Borrow Checker too.\n";
+    let targets = vec![
+        mention_target("n1", "Rust Ownership", &[]),
+        mention_target("n2", "Lifetimes", &[]),
+        mention_target("n3", "Borrow Checker", &["borrowck"]),
+        mention_target("n4", "Go", &[]),
+    ];
+
+    let mentions = LinkSuggester::find_unlinked_mentions(markdown, &targets);
+
+    // Lifetimes is already linked, "Go" is too short to suggest
+    assert_eq!(mentions.len(), 2);
+    assert_eq!(mentions[0].note_id, "n1");
+    assert_eq!(mentions[0].text, "rust ownership");
+    assert_eq!(mentions[0].name, "Rust Ownership");
+    assert_eq!(mentions[0].line_number, 6);
+    assert_eq!(
+        &markdown[mentions[0].start..mentions[0].end],
+        "rust ownership"
+    );
+    assert_eq!(mentions[1].note_id, "n3");
+    assert_eq!(mentions[1].text, "Borrow Checker");
+}
+
+#[test]
+fn test_unlinked_mentions_skip_crlf_frontmatter_and_indented_code() {
+    let markdown = "---\r\nsummary: Rust Ownership\r\n---\r\n\
+        #rust is about Rust Ownership.\r\n\r\n    Rust Ownership in code\r\n";
+    let targets = vec![mention_target("n1", "Rust Ownership", &[])];
+
+    let mentions = LinkSuggester::find_unlinked_mentions(markdown, &targets);
+
+    // A hashtag line is prose, not a heading
+    assert_eq!(mentions.len(), 1);
+    assert_eq!(mentions[0].line_number, 4);
+    assert_eq!(
+        &markdown[mentions[0].start..mentions[0].end],
+        "Rust Ownership"
+    );
+}
+
+#[test]
+fn test_unlinked_mentions_prefer_longest_name() {
+    let markdown = "Notes on Project Apollo and project ideas.";
+    let targets = vec![
+        mention_target("short", "Project", &[]),
+        mention_target("long", "Project Apollo", &[]),
+        mention_target("alias", "Brainstorm", &["Project Ideas"]),
+    ];
+
+    let mentions = LinkSuggester::find_unlinked_mentions(markdown, &targets);
+    let found: Vec<(&str, &str)> = mentions
+        .iter()
+        .map(|m| (m.note_id.as_str(), m.text.as_str()))
+        .collect();
+
+    assert_eq!(
+        found,
+        vec![("long", "Project Apollo"), ("alias", "project ideas")]
+    );
+}
+
+#[test]
+fn test_link_span_rewrites_mention() {
+    let markdown = "Borrowing builds on rust ownership.";
+    let start = markdown.find("rust").unwrap();
+    let end = start + "rust ownership".len();
+
+    assert_eq!(
+        LinkSuggester::link_span(markdown, start, end, "Rust Ownership").unwrap(),
+        "Borrowing builds on [[Rust Ownership|rust ownership]]."
+    );
+    // Empty span inserts the link
+    let period = markdown.len() - 1;
+    assert_eq!(
+        LinkSuggester::link_span(markdown, period, period, "Lifetimes").unwrap(),
+        "Borrowing builds on rust ownership [[Lifetimes]]."
+    );
+
+    // Spans touching existing links or out of bounds are refused
+    let linked = "See [[Lifetimes]] now";
+    assert!(LinkSuggester::link_span(linked, 4, 9, "Other").is_none());
+    assert!(LinkSuggester::link_span(markdown, 10, 100, "Other").is_none());
+}
@@ -0,0 +1,400 @@
+//! Test Note Rename Refactoring Workflow
+//!
+//! Critical Path: Preview rename → Backlinks rewritten with anchors and display text kept → Title saved
+//! Editing a note's title rewrites its backlinks the same way.
+//! A failed write puts every file back; moving a folder keeps relative markdown links working.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use stone_tauri_lib::adapters::outbound::storage::TokioFileStorage;
+use stone_tauri_lib::application::{services::LinkRefactorService, usecases::RefactorUseCasesImpl};
+use stone_tauri_lib::domain::{
+    entities::{Note, NoteLink},
+    errors::{DomainError, DomainResult},
+    ports::{
+        inbound::{NoteUseCases, RefactorUseCases, RenameNoteRequest, UpdateNoteInput},
+        outbound::{FileInfo, FileStorage, NoteLinkRepository, NoteRepository},
+    },
+    services::{LinkExtractor, RelativeLinks},
+};
+
+use crate::helpers::TestServices;
+
+/// Local file storage whose writes to the given file names fail
+struct FailingWrites {
+    inner: TokioFileStorage,
+    failing: HashSet<String>,
+}
+
+#[async_trait]
+impl FileStorage for FailingWrites {
+    async fn read(&self, file_path: &str) -> DomainResult<Option<String>> {
+        self.inner.read(file_path).await
+    }
+
+    async fn write(&self, file_path: &str, content: &str) -> DomainResult<()> {
+        if self.failing.iter().any(|name| file_path.ends_with(name)) {
+            return Err(DomainError::FileStorageError(format!(
+                "Disk full: {}",
+                file_path
+            )));
+        }
+        self.inner.write(file_path, content).await
+    }
+
+    async fn delete(&self, file_path: &str) -> DomainResult<()> {
+        self.inner.delete(file_path).await
+    }
+
+    async fn exists(&self, file_path: &str) -> DomainResult<bool> {
+        self.inner.exists(file_path).await
+    }
+
+    async fn rename(&self, old_path: &str, new_path: &str) -> DomainResult<()> {
+        self.inner.rename(old_path, new_path).await
+    }
+
+    async fn create_directory(&self, dir_path: &str) -> DomainResult<()> {
+        self.inner.create_directory(dir_path).await
+    }
+
+    async fn delete_directory(&self, dir_path: &str) -> DomainResult<()> {
+        self.inner.delete_directory(dir_path).await
+    }
+
+    async fn list_files(&self, dir_path: &str) -> DomainResult<Vec<FileInfo>> {
+        self.inner.list_files(dir_path).await
+    }
+
+    async fn glob(&self, pattern: &str, base_path: &str) -> DomainResult<Vec<String>> {
+        self.inner.glob(pattern, base_path).await
+    }
+
+    async fn get_file_info(&self, file_path: &str) -> DomainResult<Option<FileInfo>> {
+        self.inner.get_file_info(file_path).await
+    }
+
+    async fn copy(&self, source_path: &str, dest_path: &str) -> DomainResult<()> {
+        self.inner.copy(source_path, dest_path).await
+    }
+}
+
+struct Fixture {
+    services: TestServices,
+    refactor: RefactorUseCasesImpl,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+impl Fixture {
+    /// A note whose file holds exactly `markdown`
+    async fn note(&self, title: &str, markdown: &str) -> Note {
+        let file_path = format!("{}.md", title);
+        std::fs::write(self.workspace_dir.path().join(&file_path), markdown).unwrap();
+
+        let mut note = Note::new(title, Some(self.workspace.id.clone())).unwrap();
+        note.set_file_path(Some(file_path)).unwrap();
+        self.notes.save(&note).await.unwrap();
+        note
+    }
+
+    async fn link(&self, source: &Note, target: &Note) {
+        self.links
+            .save(&NoteLink::new(source.id.clone(), target.id.clone()).unwrap())
+            .await
+            .unwrap();
+    }
+
+    fn read(&self, file_name: &str) -> String {
+        std::fs::read_to_string(self.workspace_dir.path().join(file_name)).unwrap()
+    }
+}
+
+async fn fixture(failing_writes: &[&str]) -> Fixture {
+    let services = TestServices::builder("Rename")
+        .file_storage(Arc::new(FailingWrites {
+            inner: TokioFileStorage::new(),
+            failing: failing_writes.iter().map(|name| name.to_string()).collect(),
+        }))
+        .build()
+        .await;
+    Fixture {
+        refactor: services.refactor_usecases(),
+        services,
+    }
+}
+
+fn rename_request(note: &Note, new_title: &str) -> RenameNoteRequest {
+    RenameNoteRequest {
+        note_id: note.id.clone(),
+        new_title: new_title.to_string(),
+    }
+}
+
+#[test]
+fn test_rename_wiki_links_keeps_anchors_and_text() {
+    let markdown = "See [[Rust]], [[rust#Ownership]] and [[Rust#^claim|the claim]].\n\
+                    ![[Rust#Borrowing]] beside [[Rust Lang]], [[Rusty]] and [[#Rust]].";
+
+    let renamed = LinkExtractor::rename_wiki_links(markdown, "Rust", "Rust Language").unwrap();
+
+    assert_eq!(
+        renamed,
+        "See [[Rust Language]], [[Rust Language#Ownership]] and \
+         [[Rust Language#^claim|the claim]].\n\
+         ![[Rust Language#Borrowing]] beside [[Rust Lang]], [[Rusty]] and [[#Rust]]."
+    );
+    assert!(LinkExtractor::rename_wiki_links("Only [[Go]] here", "Rust", "Ferris").is_none());
+}
+
+#[test]
+fn test_relative_links_follow_moves() {
+    assert_eq!(
+        RelativeLinks::resolve("Projects/Plan.md", "../Ideas/Rust.md").as_deref(),
+        Some("Ideas/Rust.md")
+    );
+    assert!(RelativeLinks::resolve("Plan.md", "../Outside.md").is_none());
+    assert!(RelativeLinks::resolve("Plan.md", "https://example.com/a.md").is_none());
+    assert_eq!(
+        RelativeLinks::relative("Archive/2024/Plan.md", "Ideas/Rust.md"),
+        "../../Ideas/Rust.md"
+    );
+
+    // Ideas/ moved to Archive/Ideas/, seen from a note that stayed put
+    let markdown = "[Rust](Ideas/Rust.md#setup) ![chart](Ideas/img/chart%20v2.png)\n\
+                    [Site](https://example.com) [Here](#top) [Plan](<Plan.md> \"The plan\")";
+    let moved = |path: &str| RelativeLinks::moved_path(path, "Ideas", "Archive/Ideas");
+    assert_eq!(
+        RelativeLinks::rewrite(markdown, "Index.md", "Index.md", moved).unwrap(),
+        "[Rust](Archive/Ideas/Rust.md#setup) ![chart](Archive/Ideas/img/chart%20v2.png)\n\
+         [Site](https://example.com) [Here](#top) [Plan](<Plan.md> \"The plan\")"
+    );
+
+    // A note inside the moved folder: links within the folder still work
+    let inside = "[Go](Go.md) and [Index](../Index.md)";
+    assert_eq!(
+        RelativeLinks::rewrite(inside, "Ideas/Rust.md", "Archive/Ideas/Rust.md", moved).unwrap(),
+        "[Go](Go.md) and [Index](../../Index.md)"
+    );
+}
+
+#[tokio::test]
+async fn test_rename_previews_then_rewrites_backlinks() {
+    let f = fixture(&[]).await;
+
+    let rust = f
+        .note(
+            "Rust",
+            "---\naliases: [Ferris]\n---\n# Rust\n\n## Ownership\n\nSee [[Rust#Ownership]].\n",
+        )
+        .await;
+    let reading = f
+        .note(
+            "Reading List",
+            "# Reading List\n\n- [[Rust]]\n- [[rust#Ownership|owning things]]\n- ![[Rust#Ownership]]\n- [[Ferris]]\n",
+        )
+        .await;
+    let unrelated = f.note("Go", "# Go\n\nNothing about [[Python]].\n").await;
+    f.link(&reading, &rust).await;
+    f.link(&unrelated, &rust).await;
+
+    let plan = f
+        .refactor
+        .preview_note_rename(rename_request(&rust, " Rust Language "))
+        .await
+        .unwrap();
+    assert_eq!(plan.old_title, "Rust");
+    assert_eq!(plan.new_title, "Rust Language");
+
+    // The note's own file, then each backlink that actually changes
+    let files: Vec<&str> = plan.changes.iter().map(|c| c.file_path.as_str()).collect();
+    assert_eq!(files, vec!["Rust.md", "Reading List.md"]);
+    let own = &plan.changes[0].edits;
+    assert_eq!(own.len(), 2);
+    assert_eq!(own[0].line_number, 4);
+    assert_eq!(own[0].after, "# Rust Language");
+    assert_eq!(own[1].after, "See [[Rust Language#Ownership]].");
+    let edits = &plan.changes[1].edits;
+    assert_eq!(edits.len(), 3);
+    assert_eq!(edits[1].before, "- [[rust#Ownership|owning things]]");
+    assert_eq!(
+        edits[1].after,
+        "- [[Rust Language#Ownership|owning things]]"
+    );
+
+    // Previewing writes nothing
+    assert!(f.read("Reading List.md").contains("- [[Rust]]\n"));
+    assert_eq!(
+        f.notes.find_by_id(&rust.id).await.unwrap().unwrap().title,
+        "Rust"
+    );
+
+    let renamed = f
+        .refactor
+        .rename_note(rename_request(&rust, "Rust Language"))
+        .await
+        .unwrap();
+    assert_eq!(renamed.note.title, "Rust Language");
+    assert_eq!(renamed.changes.len(), 2);
+
+    assert_eq!(
+        f.read("Reading List.md"),
+        "# Reading List\n\n- [[Rust Language]]\n- [[Rust Language#Ownership|owning things]]\n\
+         - ![[Rust Language#Ownership]]\n- [[Ferris]]\n"
+    );
+    assert!(f
+        .read("Rust.md")
+        .starts_with("---\naliases: [Ferris]\n---\n# Rust Language\n"));
+    assert_eq!(f.read("Go.md"), "# Go\n\nNothing about [[Python]].\n");
+    assert_eq!(
+        f.notes.find_by_id(&rust.id).await.unwrap().unwrap().title,
+        "Rust Language"
+    );
+
+    // Titles other notes already use are refused
+    let err = f
+        .refactor
+        .preview_note_rename(rename_request(&unrelated, "rust language"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DomainError::NoteAlreadyExists(_)));
+}
+
+#[tokio::test]
+async fn test_rename_rolls_back_when_a_write_fails() {
+    let f = fixture(&["Zettel.md"]).await;
+
+    let rust = f.note("Rust", "# Rust\n").await;
+    let first = f.note("Alpha", "# Alpha\n\n[[Rust]]\n").await;
+    let last = f.note("Zettel", "# Zettel\n\n[[Rust]]\n").await;
+    f.link(&first, &rust).await;
+    f.link(&last, &rust).await;
+
+    let result = f
+        .refactor
+        .rename_note(rename_request(&rust, "Rust Language"))
+        .await;
+    assert!(matches!(result, Err(DomainError::FileStorageError(_))));
+
+    // Files written before the failure have their content back
+    assert_eq!(f.read("Rust.md"), "# Rust\n");
+    assert_eq!(f.read("Alpha.md"), "# Alpha\n\n[[Rust]]\n");
+    assert_eq!(f.read("Zettel.md"), "# Zettel\n\n[[Rust]]\n");
+    assert_eq!(
+        f.notes.find_by_id(&rust.id).await.unwrap().unwrap().title,
+        "Rust"
+    );
+}
+
+#[tokio::test]
+async fn test_editing_a_title_rewrites_backlinks() {
+    let f = fixture(&[]).await;
+    let note_usecases = f.note_usecases();
+
+    let rust = f.note("Rust", "# Rust\n\nSee [[Rust#Ownership]].\n").await;
+    let reading = f
+        .note("Reading List", "# Reading List\n\n- [[Rust|the book]]\n")
+        .await;
+    f.note("Go", "# Go\n").await;
+    f.link(&reading, &rust).await;
+
+    let title_input = |title: &str, content: Option<&str>| UpdateNoteInput {
+        id: rust.id.clone(),
+        title: Some(title.to_string()),
+        content: content.map(str::to_string),
+        notebook_id: None,
+        is_favorite: None,
+        is_pinned: None,
+        is_archived: None,
+    };
+
+    // The editor saves the title along with the body
+    let updated = note_usecases
+        .update_note(title_input(
+            "Rust Language",
+            Some("See [[Rust Language#Ownership]].\n"),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(updated.title, "Rust Language");
+    assert_eq!(
+        f.read("Reading List.md"),
+        "# Reading List\n\n- [[Rust Language|the book]]\n"
+    );
+    assert_eq!(
+        f.read("Rust.md"),
+        "# Rust Language\n\nSee [[Rust Language#Ownership]].\n"
+    );
+
+    // Titles other notes already use are refused, and nothing is written
+    let err = note_usecases
+        .update_note(title_input("go", None))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, DomainError::NoteAlreadyExists(_)));
+    assert!(f
+        .read("Reading List.md")
+        .contains("[[Rust Language|the book]]"));
+    assert_eq!(
+        f.notes.find_by_id(&rust.id).await.unwrap().unwrap().title,
+        "Rust Language"
+    );
+}
+
+#[tokio::test]
+async fn test_folder_move_rewrites_relative_links() {
+    let workspace_dir = tempfile::TempDir::new().unwrap();
+    let root = workspace_dir.path();
+    std::fs::create_dir_all(root.join("Ideas")).unwrap();
+    std::fs::write(root.join("Index.md"), "[Rust](Ideas/Rust.md)\n").unwrap();
+    std::fs::write(
+        root.join("Ideas/Rust.md"),
+        "[Go](Go.md)\n[Home](../Index.md)\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("Ideas/Go.md"), "[Rust](Rust.md)\n").unwrap();
+
+    let service = LinkRefactorService::new(Arc::new(TokioFileStorage::new()));
+    let folder = root.to_str().unwrap();
+    let rewrites = service
+        .plan_move(folder, "Ideas", "Archive/Ideas")
+        .await
+        .unwrap();
+    let mut files: Vec<&str> = rewrites.iter().map(|r| r.file_path.as_str()).collect();
+    files.sort();
+    assert_eq!(files, vec!["Archive/Ideas/Rust.md", "Index.md"]);
+
+    std::fs::create_dir_all(root.join("Archive")).unwrap();
+    std::fs::rename(root.join("Ideas"), root.join("Archive/Ideas")).unwrap();
+    service.apply(&rewrites).await.unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(root.join("Index.md")).unwrap(),
+        "[Rust](Archive/Ideas/Rust.md)\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(root.join("Archive/Ideas/Rust.md")).unwrap(),
+        "[Go](Go.md)\n[Home](../../Index.md)\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(root.join("Archive/Ideas/Go.md")).unwrap(),
+        "[Rust](Rust.md)\n"
+    );
+
+    // A plan is only applied to the files it was made from
+    std::fs::write(root.join("Index.md"), "Edited meanwhile\n").unwrap();
+    assert!(service.apply(&rewrites).await.is_err());
+    assert_eq!(
+        std::fs::read_to_string(root.join("Archive/Ideas/Rust.md")).unwrap(),
+        "[Go](Go.md)\n[Home](../../Index.md)\n"
+    );
+}
@@ -0,0 +1,270 @@
+//! Test Note Template Workflow
+//!
+//! Critical Path: Template file → Expand placeholders → New note with frontmatter defaults
+//! Notebooks can name a default template that their sub-notebooks inherit.
+
+use std::collections::HashMap;
+
+use stone_tauri_lib::application::usecases::TemplateUseCasesImpl;
+use stone_tauri_lib::domain::{
+    entities::Notebook,
+    ports::{
+        inbound::{CreateNoteFromTemplateRequest, TemplateSettings, TemplateUseCases},
+        outbound::{NotebookRepository, TagRepository},
+    },
+    services::{TemplateContext, TemplateRenderer},
+};
+
+use crate::helpers::TestServices;
+
+struct Fixture {
+    services: TestServices,
+    usecases: TemplateUseCasesImpl,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+impl Fixture {
+    fn write_template(&self, path: &str, content: &str) {
+        let path = self.workspace_dir.path().join("Templates").join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn read_note(&self, file_path: &str) -> String {
+        std::fs::read_to_string(self.workspace_dir.path().join(file_path)).unwrap()
+    }
+
+    async fn notebook(&self, name: &str, parent_id: Option<String>) -> Notebook {
+        let mut notebook = Notebook::new(name, Some(self.workspace.id.clone()), parent_id).unwrap();
+        notebook.folder_path = Some(name.to_string());
+        self.notebooks.save(&notebook).await.unwrap();
+        notebook
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::new("Lab").await;
+    Fixture {
+        usecases: services.template_usecases(),
+        services,
+    }
+}
+
+#[tokio::test]
+async fn test_create_note_from_template_expands_placeholders() {
+    let f = fixture().await;
+    let meetings = f.notebook("Meetings", None).await;
+    f.write_template(
+        "Meeting.md",
+        "---\ntags: [meeting, \"Weekly Sync\"]\nnotebook: meetings\ncreated: {{date}}\n---\n\
+         # {{title}}\n\nWorkspace: {{workspace}}\nDate: {{date:YYYY-[W]WW}}\n\n## Notes\n- {{cursor}}\n",
+    );
+
+    let response = f
+        .usecases
+        .create_note_from_template(CreateNoteFromTemplateRequest {
+            title: "Planning".to_string(),
+            template_path: Some("Meeting.md".to_string()),
+            workspace_id: Some(f.workspace.id.clone()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    // The template's frontmatter picks the notebook, and its folder holds the file
+    let note = response.note;
+    assert_eq!(note.notebook_id.as_deref(), Some(meetings.id.as_str()));
+    let file_path = note.file_path.clone().unwrap();
+    assert!(file_path.starts_with("Meetings/"));
+
+    let today = chrono::Local::now().naive_local();
+    let content = f.read_note(&file_path);
+    assert!(
+        !content.contains("notebook:"),
+        "template-only key kept: {}",
+        content
+    );
+    assert!(content.contains(&format!("created: {}", today.format("%Y-%m-%d"))));
+    assert!(content.contains("# Planning\n"));
+    assert!(content.contains("Workspace: Lab\n"));
+    assert!(content.contains(&format!("Date: {}\n", today.format("%G-W%V"))));
+    assert!(!content.contains("{{"));
+
+    let cursor = response.cursor_offset.unwrap() as usize;
+    assert!(content[..cursor].ends_with("## Notes\n- "));
+
+    let mut tag_names: Vec<String> = f
+        .tags
+        .find_by_note_id(&note.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tag| tag.name)
+        .collect();
+    tag_names.sort();
+    assert_eq!(tag_names, vec!["meeting", "weekly-sync"]);
+}
+
+#[test]
+fn test_template_frontmatter_is_read_as_yaml() {
+    let frontmatter = concat!(
+        "---\n",
+        "# Filled in by the template\n",
+        "tags: [meeting, # the kind\n",
+        "  \"Weekly: Sync\"]\n",
+    );
+    let template = format!(
+        "{}notebook: \"Team #2\" # where it goes\n---\n# {{{{title}}}}\n",
+        frontmatter
+    );
+    let context = TemplateContext {
+        title: "Planning".to_string(),
+        workspace: "Lab".to_string(),
+        now: chrono::NaiveDate::from_ymd_opt(2026, 3, 2)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap(),
+    };
+
+    let rendered = TemplateRenderer::render(&template, &context);
+
+    assert_eq!(rendered.tags, vec!["meeting", "Weekly: Sync"]);
+    assert_eq!(rendered.notebook.as_deref(), Some("Team #2"));
+    assert_eq!(
+        rendered.content,
+        format!("{}---\n# Planning\n", frontmatter)
+    );
+}
+
+#[tokio::test]
+async fn test_notebook_default_template_is_inherited() {
+    let f = fixture().await;
+    let projects = f.notebook("Projects", None).await;
+    let apollo = f.notebook("Apollo", Some(projects.id.clone())).await;
+    f.write_template("Work/Project.md", "Status: planning\n{{cursor}}");
+
+    // Without a default there is nothing to create the note from
+    let missing = f
+        .usecases
+        .create_note_from_template(CreateNoteFromTemplateRequest {
+            title: "Kickoff".to_string(),
+            notebook_id: Some(apollo.id.clone()),
+            ..Default::default()
+        })
+        .await;
+    assert!(missing.is_err());
+
+    let settings = f
+        .usecases
+        .set_notebook_template(&projects.id, Some("Work/Project.md".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(
+        settings
+            .notebook_templates
+            .get(&projects.id)
+            .map(String::as_str),
+        Some("Work/Project.md")
+    );
+
+    let response = f
+        .usecases
+        .create_note_from_template(CreateNoteFromTemplateRequest {
+            title: "Kickoff".to_string(),
+            notebook_id: Some(apollo.id.clone()),
+            workspace_id: Some(f.workspace.id.clone()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.note.notebook_id.as_deref(),
+        Some(apollo.id.as_str())
+    );
+    let content = f.read_note(response.note.file_path.as_deref().unwrap());
+    // A template without a heading gets the note's title
+    assert_eq!(content, "# Kickoff\n\nStatus: planning\n");
+    assert_eq!(response.cursor_offset, Some(content.len() as i32));
+
+    // Clearing the default falls back to the error again
+    f.usecases
+        .set_notebook_template(&projects.id, None)
+        .await
+        .unwrap();
+    let cleared = f
+        .usecases
+        .create_note_from_template(CreateNoteFromTemplateRequest {
+            title: "Retro".to_string(),
+            notebook_id: Some(apollo.id.clone()),
+            workspace_id: Some(f.workspace.id.clone()),
+            ..Default::default()
+        })
+        .await;
+    assert!(cleared.is_err());
+}
+
+#[tokio::test]
+async fn test_templates_folder_is_configurable() {
+    let f = fixture().await;
+    f.write_template("Daily.md", "# {{date}}\n");
+    f.write_template("Work/Standup.md", "# Standup\n");
+
+    let templates = f
+        .usecases
+        .list_templates(Some(f.workspace.id.clone()))
+        .await
+        .unwrap();
+    let paths: Vec<&str> = templates.iter().map(|t| t.path.as_str()).collect();
+    assert_eq!(paths, vec!["Daily.md", "Work/Standup.md"]);
+    assert_eq!(templates[1].name, "Standup");
+
+    // Folders outside the workspace and escaping template paths are rejected
+    let outside = TemplateSettings {
+        folder: "../elsewhere".to_string(),
+        notebook_templates: HashMap::new(),
+    };
+    assert!(f
+        .usecases
+        .update_template_settings(Some(f.workspace.id.clone()), outside)
+        .await
+        .is_err());
+    let escaping = f
+        .usecases
+        .create_note_from_template(CreateNoteFromTemplateRequest {
+            title: "Secrets".to_string(),
+            template_path: Some("../../etc/passwd".to_string()),
+            workspace_id: Some(f.workspace.id.clone()),
+            ..Default::default()
+        })
+        .await;
+    assert!(escaping.is_err());
+
+    let settings = f
+        .usecases
+        .update_template_settings(
+            Some(f.workspace.id.clone()),
+            TemplateSettings {
+                folder: "Templates/Work/".to_string(),
+                notebook_templates: HashMap::new(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(settings.folder, "Templates/Work");
+
+    let templates = f
+        .usecases
+        .list_templates(Some(f.workspace.id.clone()))
+        .await
+        .unwrap();
+    let paths: Vec<&str> = templates.iter().map(|t| t.path.as_str()).collect();
+    assert_eq!(paths, vec!["Standup.md"]);
+}
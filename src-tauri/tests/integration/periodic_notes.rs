@@ -0,0 +1,238 @@
+//! Test Periodic Notes Workflow
+//!
+//! Critical Path: Period + date → Folder/file name from settings → Note created from template
+//! Quick capture appends to the current note of any period, honouring the day rollover.
+
+use chrono::{NaiveDate, NaiveTime};
+
+use stone_tauri_lib::application::usecases::{PeriodicNoteUseCasesImpl, QuickCaptureUseCasesImpl};
+use stone_tauri_lib::domain::{
+    ports::inbound::{
+        OpenPeriodicNoteRequest, PeriodicNoteUseCases, PeriodicNotesSettings, QuickCaptureUseCases,
+    },
+    services::{NotePeriod, PeriodicNotes},
+};
+
+use crate::helpers::TestServices;
+
+fn date(value: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+}
+
+struct Fixture {
+    services: TestServices,
+    periodic: PeriodicNoteUseCasesImpl,
+    capture: QuickCaptureUseCasesImpl,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+impl Fixture {
+    fn read(&self, relative_path: &str) -> String {
+        std::fs::read_to_string(self.workspace_dir.path().join(relative_path)).unwrap()
+    }
+
+    async fn open(&self, period: NotePeriod, day: &str, offset: i32) -> (String, String, bool) {
+        let response = self
+            .periodic
+            .open_periodic_note(OpenPeriodicNoteRequest {
+                period,
+                date: Some(day.to_string()),
+                offset: Some(offset),
+                workspace_id: Some(self.workspace.id.clone()),
+            })
+            .await
+            .unwrap();
+        (
+            response.note.file_path.unwrap(),
+            format!("{}..{}", response.start_date, response.end_date),
+            response.created,
+        )
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::new("Diary").await;
+    Fixture {
+        periodic: services.periodic_note_usecases(),
+        capture: services.quick_capture_usecases(),
+        services,
+    }
+}
+
+#[test]
+fn test_period_boundaries_and_navigation() {
+    // 2026-02-11 is a Wednesday in ISO week 7 of Q1
+    let day = date("2026-02-11");
+    assert_eq!(
+        PeriodicNotes::start_of(NotePeriod::Weekly, day),
+        date("2026-02-09")
+    );
+    assert_eq!(
+        PeriodicNotes::end_of(NotePeriod::Weekly, day),
+        date("2026-02-15")
+    );
+    assert_eq!(
+        PeriodicNotes::end_of(NotePeriod::Monthly, day),
+        date("2026-02-28")
+    );
+    assert_eq!(
+        PeriodicNotes::start_of(NotePeriod::Quarterly, day),
+        date("2026-01-01")
+    );
+    assert_eq!(
+        PeriodicNotes::end_of(NotePeriod::Quarterly, day),
+        date("2026-03-31")
+    );
+
+    assert_eq!(
+        PeriodicNotes::shift(NotePeriod::Daily, day, -11),
+        date("2026-01-31")
+    );
+    assert_eq!(
+        PeriodicNotes::shift(NotePeriod::Weekly, day, 1),
+        date("2026-02-16")
+    );
+    assert_eq!(
+        PeriodicNotes::shift(NotePeriod::Monthly, day, -2),
+        date("2025-12-01")
+    );
+    assert_eq!(
+        PeriodicNotes::shift(NotePeriod::Quarterly, day, 4),
+        date("2027-01-01")
+    );
+
+    assert_eq!(PeriodicNotes::note_name("GGGG-[W]WW", day), "2026-W07");
+    assert_eq!(PeriodicNotes::note_name("YYYY-[Q]Q", day), "2026-Q1");
+    // ISO week-years differ from calendar years around New Year
+    assert_eq!(
+        PeriodicNotes::note_name("GGGG-[W]WW", date("2027-01-01")),
+        "2026-W53"
+    );
+
+    // Before the rollover, the night still belongs to the previous day
+    let four = NaiveTime::from_hms_opt(4, 0, 0).unwrap();
+    let night = date("2026-03-01").and_hms_opt(1, 30, 0).unwrap();
+    let morning = date("2026-03-01").and_hms_opt(4, 0, 0).unwrap();
+    assert_eq!(PeriodicNotes::current_date(night, four), date("2026-02-28"));
+    assert_eq!(
+        PeriodicNotes::current_date(morning, four),
+        date("2026-03-01")
+    );
+}
+
+#[tokio::test]
+async fn test_periodic_notes_follow_settings() {
+    let f = fixture().await;
+
+    // Defaults keep daily notes where the journal always was
+    let (path, range, created) = f.open(NotePeriod::Daily, "2026-02-11", 0).await;
+    assert_eq!(path, "Journal/2026-02-11.md");
+    assert_eq!(range, "2026-02-11..2026-02-11");
+    assert!(created);
+    assert_eq!(f.read(&path), "# 2026-02-11\n");
+
+    // Opening it again finds the same note
+    let (again, _, created) = f.open(NotePeriod::Daily, "2026-02-11", 0).await;
+    assert_eq!(again, path);
+    assert!(!created);
+
+    let template_dir = f.workspace_dir.path().join("Templates");
+    std::fs::create_dir_all(&template_dir).unwrap();
+    std::fs::write(
+        template_dir.join("Week.md"),
+        "# Week of {{date:MMMM D}}\n\n## Goals\n",
+    )
+    .unwrap();
+
+    let mut settings = f
+        .periodic
+        .get_periodic_settings(Some(f.workspace.id.clone()))
+        .await
+        .unwrap();
+    settings.weekly.folder = "Reviews".to_string();
+    settings.weekly.format = "YYYY-[Week]-WW".to_string();
+    settings.weekly.template = Some("Week.md".to_string());
+    settings.quarterly.enabled = false;
+    f.periodic
+        .update_periodic_settings(Some(f.workspace.id.clone()), settings)
+        .await
+        .unwrap();
+
+    // The previous week, created from its template dated to the week's Monday
+    let (path, range, created) = f.open(NotePeriod::Weekly, "2026-02-11", -1).await;
+    assert_eq!(path, "Reviews/2026-Week-06.md");
+    assert_eq!(range, "2026-02-02..2026-02-08");
+    assert!(created);
+    assert_eq!(f.read(&path), "# Week of February 2\n\n## Goals\n");
+
+    let (path, range, _) = f.open(NotePeriod::Monthly, "2026-02-11", 1).await;
+    assert_eq!(path, "Journal/Monthly/2026-03.md");
+    assert_eq!(range, "2026-03-01..2026-03-31");
+
+    let disabled = f
+        .periodic
+        .open_periodic_note(OpenPeriodicNoteRequest {
+            period: NotePeriod::Quarterly,
+            date: None,
+            offset: None,
+            workspace_id: Some(f.workspace.id.clone()),
+        })
+        .await;
+    assert!(disabled.is_err());
+
+    let invalid = PeriodicNotesSettings {
+        day_starts_at: "25:00".to_string(),
+        ..Default::default()
+    };
+    assert!(f
+        .periodic
+        .update_periodic_settings(Some(f.workspace.id.clone()), invalid)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_quick_capture_targets_any_period() {
+    let f = fixture().await;
+
+    let first = f
+        .capture
+        .append_to_periodic_note(
+            "Plan the offsite",
+            NotePeriod::Monthly,
+            Some(&f.workspace.id),
+        )
+        .await
+        .unwrap();
+    assert!(!first.appended, "the month's note is new");
+
+    let second = f
+        .capture
+        .append_to_periodic_note("Book the venue", NotePeriod::Monthly, Some(&f.workspace.id))
+        .await
+        .unwrap();
+    assert!(second.appended);
+    assert_eq!(second.note_id, first.note_id);
+
+    let month = chrono::Local::now().format("%Y-%m").to_string();
+    let content = f.read(&format!("Journal/Monthly/{}.md", month));
+    assert!(content.starts_with(&format!("# {}\n\n[", month)));
+    let offsite = content.find("] Plan the offsite").unwrap();
+    let venue = content.find("] Book the venue").unwrap();
+    assert!(offsite < venue);
+
+    // The journal entry point still writes to the daily note
+    let daily = f
+        .capture
+        .append_to_journal("Stand-up notes", Some(&f.workspace.id))
+        .await
+        .unwrap();
+    assert_ne!(daily.note_id, first.note_id);
+}
@@ -0,0 +1,415 @@
+//! Test Question Answering Workflow
+//!
+//! Critical Path: Question → Retrieve passages → Local chat endpoint → Streamed answer → Citations
+//! Answers must point back at the notes and byte ranges they were drawn from.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use stone_tauri_lib::application::{
+    services::NoteEmbedder, usecases::QuestionAnsweringUseCasesImpl,
+};
+use stone_tauri_lib::domain::{
+    entities::Note,
+    errors::{DomainError, DomainResult},
+    ports::{
+        inbound::{AskQuestionRequest, QuestionAnsweringUseCases, RetrievalMode},
+        outbound::{
+            ChatModelConfig, DomainEvent, EmbeddingModelInfo, EmbeddingProvider, EmbeddingService,
+            EventPublisher, NoteRepository, SearchEngine,
+        },
+    },
+};
+
+use crate::helpers::TestServices;
+
+/// Words the stub embedding counts, one dimension each
+const VOCABULARY: [&str; 5] = ["billing", "stripe", "garden", "tomato", "soil"];
+
+/// Embeds text as counts of the vocabulary words, so similarity follows wording
+struct VocabularyProvider {
+    ready: AtomicBool,
+}
+
+#[async_trait]
+impl EmbeddingProvider for VocabularyProvider {
+    fn model(&self) -> EmbeddingModelInfo {
+        EmbeddingModelInfo {
+            name: "vocabulary".to_string(),
+            version: "1".to_string(),
+            dimensions: Some(VOCABULARY.len() + 1),
+        }
+    }
+
+    async fn initialize(&self) -> DomainResult<()> {
+        self.ready.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> DomainResult<Vec<Vec<f32>>> {
+        Ok(texts
+            .iter()
+            .map(|text| {
+                let text = text.to_lowercase();
+                VOCABULARY
+                    .iter()
+                    .map(|word| text.matches(word).count() as f32)
+                    .chain(std::iter::once(0.1))
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+/// Serve a streamed chat reply made of `parts` on a free local port,
+/// recording each request body
+async fn stub_chat_server(parts: Vec<&'static str>) -> (String, Arc<Mutex<Vec<Value>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let log = received.clone();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let (log, parts) = (log.clone(), parts.clone());
+            tokio::spawn(async move {
+                let mut buffer = Vec::new();
+                let mut chunk = [0u8; 4096];
+                let body = loop {
+                    let read = socket.read(&mut chunk).await.unwrap();
+                    if read == 0 {
+                        return;
+                    }
+                    buffer.extend_from_slice(&chunk[..read]);
+
+                    let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") else {
+                        continue;
+                    };
+                    let head = String::from_utf8_lossy(&buffer[..end]).to_lowercase();
+                    let length = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length:"))
+                        .and_then(|value| value.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    if buffer.len() >= end + 4 + length {
+                        break buffer[end + 4..end + 4 + length].to_vec();
+                    }
+                };
+                log.lock()
+                    .unwrap()
+                    .push(serde_json::from_slice(&body).unwrap_or(Value::Null));
+
+                let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n";
+                let _ = socket.write_all(head.as_bytes()).await;
+                for part in parts {
+                    let event = json!({ "choices": [{ "delta": { "content": part } }] });
+                    let _ = socket
+                        .write_all(format!("data: {}\n\n", event).as_bytes())
+                        .await;
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+                let _ = socket.write_all(b"data: [DONE]\n\n").await;
+            });
+        }
+    });
+
+    (base_url, received)
+}
+
+struct Fixture {
+    services: TestServices,
+    embedder: NoteEmbedder,
+    usecases: QuestionAnsweringUseCasesImpl,
+    received: Arc<Mutex<Vec<DomainEvent>>>,
+    _subscriptions: Vec<Box<dyn Fn() + Send + Sync>>,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::builder("Answers")
+        .embedding_provider(Arc::new(VocabularyProvider {
+            ready: AtomicBool::new(false),
+        }))
+        .build()
+        .await;
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let subscriptions = ["qa:answer-delta", "qa:answered"]
+        .into_iter()
+        .map(|event_type| {
+            let sink = received.clone();
+            services.events.subscribe(
+                event_type,
+                Arc::new(move |event| sink.lock().unwrap().push(event)),
+            )
+        })
+        .collect();
+
+    // Give the subscribers a moment to set up
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    Fixture {
+        embedder: NoteEmbedder::new(
+            services.embedding_service.clone(),
+            services.markdown.clone(),
+        ),
+        usecases: services.question_answering_usecases(),
+        services,
+        received,
+        _subscriptions: subscriptions,
+    }
+}
+
+/// Save a note and its file, and add it to the full-text index
+async fn write_note(fx: &Fixture, title: &str, relative_path: &str, body: &str) -> (Note, String) {
+    let markdown = format!("# {}\n\n{}", title, body);
+    let absolute = fx.workspace_dir.path().join(relative_path);
+    std::fs::create_dir_all(absolute.parent().unwrap()).unwrap();
+    std::fs::write(&absolute, &markdown).unwrap();
+
+    let mut note = Note::new(title, Some(fx.workspace.id.clone())).unwrap();
+    note.set_file_path(Some(relative_path.to_string())).unwrap();
+    fx.notes.save(&note).await.unwrap();
+    fx.search_engine
+        .index_note(&note.id, title, &markdown)
+        .await
+        .unwrap();
+    (note, markdown)
+}
+
+const BILLING_NOTE: &str = "## Background\n\nInvoices are sent by hand every month.\n\n\
+## Decision\n\nWe decided to move billing to Stripe in March.\n";
+
+const GARDEN_NOTE: &str = "Plant the tomato seedlings once the soil is warm.\n";
+
+async fn use_stub_chat(fx: &Fixture, base_url: &str) {
+    fx.usecases
+        .update_chat_settings(ChatModelConfig {
+            base_url: base_url.to_string(),
+            model: "llama3.2".to_string(),
+            api_key: None,
+            temperature: None,
+        })
+        .await
+        .unwrap();
+}
+
+fn question(text: &str, retrieval: RetrievalMode) -> AskQuestionRequest {
+    AskQuestionRequest {
+        question: text.to_string(),
+        question_id: Some("q1".to_string()),
+        retrieval: Some(retrieval),
+        limit: Some(3),
+        workspace_id: None,
+        notebook_id: None,
+    }
+}
+
+/// Answer text put together from the streamed deltas
+async fn streamed_answer(fx: &Fixture) -> (String, Vec<String>) {
+    // Let the subscribers drain
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut answer = String::new();
+    let mut cited = Vec::new();
+    for event in fx.received.lock().unwrap().iter() {
+        match event {
+            DomainEvent::AnswerDelta {
+                question_id, delta, ..
+            } => {
+                assert_eq!(question_id, "q1");
+                answer.push_str(delta);
+            }
+            DomainEvent::QuestionAnswered { cited_note_ids, .. } => {
+                cited = cited_note_ids.clone();
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+    (answer, cited)
+}
+
+#[tokio::test]
+async fn test_semantic_answer_streams_and_cites_passages() {
+    let fx = fixture().await;
+    let (billing, markdown) =
+        write_note(&fx, "Billing migration", "Work/billing.md", BILLING_NOTE).await;
+    let (garden, garden_markdown) = write_note(&fx, "Garden", "Home/garden.md", GARDEN_NOTE).await;
+    fx.embedding_service.initialize().await.unwrap();
+    fx.embedder
+        .embed_note(&billing.id, &markdown)
+        .await
+        .unwrap();
+    fx.embedder
+        .embed_note(&garden.id, &garden_markdown)
+        .await
+        .unwrap();
+
+    let (base_url, received) = stub_chat_server(vec![
+        "We moved billing ",
+        "to Stripe [1].",
+        " See also [2, 9].",
+    ])
+    .await;
+    use_stub_chat(&fx, &base_url).await;
+
+    let response = fx
+        .usecases
+        .ask_question(question(
+            "What did we decide about billing with Stripe?",
+            RetrievalMode::Semantic,
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.question_id, "q1");
+    assert_eq!(
+        response.answer,
+        "We moved billing to Stripe [1]. See also [2, 9]."
+    );
+
+    // The best passage of the closest note comes first
+    let first = &response.sources[0];
+    assert_eq!(first.note_id, billing.id);
+    assert_eq!(first.heading_path, vec!["Billing migration", "Decision"]);
+    let quoted = &markdown[first.start_offset as usize..first.end_offset as usize];
+    assert!(quoted.contains("move billing to Stripe"), "{:?}", quoted);
+
+    // [9] has no passage, so only [1] and [2] are citations
+    let numbers: Vec<i32> = response.citations.iter().map(|c| c.number).collect();
+    assert_eq!(numbers, vec![1, 2]);
+    let citation = &response.citations[0];
+    assert_eq!(citation.note_id, billing.id);
+    assert_eq!(
+        (citation.start_offset, citation.end_offset),
+        (first.start_offset, first.end_offset)
+    );
+    let (start, end) = citation.answer_ranges[0];
+    assert_eq!(&response.answer[start as usize..end as usize], "[1]");
+
+    // The passages were sent numbered, after the instructions
+    let request = received.lock().unwrap()[0].clone();
+    assert_eq!(request["model"], "llama3.2");
+    assert_eq!(request["messages"][0]["role"], "system");
+    let prompt = request["messages"][1]["content"].as_str().unwrap();
+    assert!(prompt.contains("[1] Billing migration > Decision"));
+    assert!(prompt.ends_with("Question: What did we decide about billing with Stripe?"));
+
+    let (streamed, cited) = streamed_answer(&fx).await;
+    assert_eq!(streamed, response.answer);
+    assert_eq!(
+        cited,
+        vec![billing.id.clone(), response.sources[1].note_id.clone()]
+    );
+}
+
+#[tokio::test]
+async fn test_hybrid_answer_without_embeddings_quotes_keyword_passage() {
+    let fx = fixture().await;
+    let (billing, markdown) =
+        write_note(&fx, "Billing migration", "Work/billing.md", BILLING_NOTE).await;
+    write_note(&fx, "Garden", "Home/garden.md", GARDEN_NOTE).await;
+
+    let (base_url, _) = stub_chat_server(vec!["Stripe, from March [1]."]).await;
+    use_stub_chat(&fx, &base_url).await;
+
+    // Semantic retrieval needs the embedding model
+    let error = fx
+        .usecases
+        .ask_question(question("Where does billing go?", RetrievalMode::Semantic))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, DomainError::ValidationError(_)));
+
+    // Full-text search alone finds the note; the passage comes from its text
+    let response = fx
+        .usecases
+        .ask_question(question(
+            "What did we decide about billing with Stripe?",
+            RetrievalMode::Hybrid,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.sources.len(), 1);
+    let citation = &response.citations[0];
+    assert_eq!(citation.note_id, billing.id);
+    let quoted = &markdown[citation.start_offset as usize..citation.end_offset as usize];
+    assert!(quoted.contains("move billing to Stripe"), "{:?}", quoted);
+}
+
+#[tokio::test]
+async fn test_question_without_matching_notes_skips_the_model() {
+    let fx = fixture().await;
+    write_note(&fx, "Garden", "Home/garden.md", GARDEN_NOTE).await;
+
+    let (base_url, received) = stub_chat_server(vec!["Should not be asked"]).await;
+    use_stub_chat(&fx, &base_url).await;
+
+    let response = fx
+        .usecases
+        .ask_question(question(
+            "When is the quarterly payroll audit?",
+            RetrievalMode::Hybrid,
+        ))
+        .await
+        .unwrap();
+    assert!(response.sources.is_empty());
+    assert!(response.citations.is_empty());
+    assert!(!response.answer.is_empty());
+    assert!(received.lock().unwrap().is_empty());
+
+    let (streamed, cited) = streamed_answer(&fx).await;
+    assert_eq!(streamed, response.answer);
+    assert!(cited.is_empty());
+}
+
+#[tokio::test]
+async fn test_chat_settings_must_stay_on_this_machine() {
+    let fx = fixture().await;
+    assert_eq!(
+        fx.usecases.get_chat_settings().await.unwrap(),
+        ChatModelConfig::default()
+    );
+
+    let remote = ChatModelConfig {
+        base_url: "https://api.example.com/v1".to_string(),
+        ..ChatModelConfig::default()
+    };
+    let error = fx.usecases.update_chat_settings(remote).await.unwrap_err();
+    assert!(matches!(error, DomainError::ValidationError(_)));
+
+    let error = fx
+        .usecases
+        .ask_question(question("   ", RetrievalMode::Hybrid))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, DomainError::ValidationError(_)));
+
+    let local = ChatModelConfig {
+        base_url: "http://127.0.0.1:8080".to_string(),
+        model: "qwen2.5".to_string(),
+        ..ChatModelConfig::default()
+    };
+    fx.usecases
+        .update_chat_settings(local.clone())
+        .await
+        .unwrap();
+    assert_eq!(fx.usecases.get_chat_settings().await.unwrap(), local);
+}
@@ -158,3 +158,379 @@ fn test_semantic_search_scenario() {
     assert!(ml_to_nn > ml_to_recipe,
         "Related notes should be more similar than unrelated ones");
 }
+
+#[test]
+fn test_structured_query_parsing() {
+    use stone_tauri_lib::domain::ports::outbound::DateRangeField;
+    use stone_tauri_lib::domain::services::SearchQueryParser;
+
+    let parsed = SearchQueryParser::parse(
+        r#"tag:project/alpha notebook:Work is:pinned -draft "exact phrase" updated:>2026-01-01 rust"#,
+    )
+    .expect("Query should parse");
+
+    assert_eq!(parsed.terms, vec!["rust"]);
+    assert_eq!(parsed.options.tag_names, Some(vec!["project/alpha".to_string()]));
+    assert_eq!(parsed.options.notebook_name.as_deref(), Some("Work"));
+    assert_eq!(parsed.options.is_pinned, Some(true));
+    assert_eq!(parsed.options.excluded_terms, Some(vec!["draft".to_string()]));
+    assert_eq!(parsed.options.phrases, Some(vec!["exact phrase".to_string()]));
+
+    let ranges = parsed.options.date_ranges.expect("Date range should be set");
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(ranges[0].field, Some(DateRangeField::Updated));
+    assert_eq!(ranges[0].start_date.to_rfc3339(), "2026-01-02T00:00:00+00:00");
+}
+
+#[test]
+fn test_query_parsing_keeps_plain_colons_as_text() {
+    use stone_tauri_lib::domain::services::SearchQueryParser;
+
+    // Unknown keys (URLs, times) are free text, not filters
+    let parsed = SearchQueryParser::parse("https://example.com 10:30 notebook:\"Side Projects\"")
+        .expect("Query should parse");
+
+    assert_eq!(parsed.terms, vec!["https://example.com", "10:30"]);
+    assert_eq!(parsed.options.notebook_name.as_deref(), Some("Side Projects"));
+}
+
+#[test]
+fn test_query_parsing_date_ranges() {
+    use stone_tauri_lib::domain::services::SearchQueryParser;
+
+    let parsed = SearchQueryParser::parse("created:2026-01-01..2026-01-31").unwrap();
+    let range = &parsed.options.date_ranges.unwrap()[0];
+    assert_eq!(range.start_date.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+    assert_eq!(range.end_date.to_rfc3339(), "2026-01-31T23:59:59+00:00");
+
+    let parsed = SearchQueryParser::parse("created:2026-03-05").unwrap();
+    let range = &parsed.options.date_ranges.unwrap()[0];
+    assert_eq!(range.start_date.to_rfc3339(), "2026-03-05T00:00:00+00:00");
+    assert_eq!(range.end_date.to_rfc3339(), "2026-03-05T23:59:59+00:00");
+}
+
+#[test]
+fn test_query_parse_errors_report_positions() {
+    use stone_tauri_lib::domain::services::SearchQueryParser;
+
+    let err = SearchQueryParser::parse("notes is:shiny").unwrap_err();
+    assert_eq!((err.start, err.end), (9, 14));
+
+    let err = SearchQueryParser::parse("updated:>2026-13-01").unwrap_err();
+    assert_eq!((err.start, err.end), (9, 19));
+    assert!(err.message.contains("2026-13-01"));
+
+    let err = SearchQueryParser::parse("hello \"unterminated").unwrap_err();
+    assert_eq!((err.start, err.end), (6, 19));
+
+    let err = SearchQueryParser::parse("-tag:work").unwrap_err();
+    assert_eq!((err.start, err.end), (0, 9));
+
+    let err = SearchQueryParser::parse("tag:").unwrap_err();
+    assert!(err.message.contains("Missing value"));
+
+    let err = SearchQueryParser::parse("created:2026-02-01..2026-01-01").unwrap_err();
+    assert!(err.message.contains("ends before"));
+}
+
+#[test]
+fn test_hybrid_rank_fusion() {
+    use stone_tauri_lib::domain::ports::outbound::{FusionMethod, SearchRetriever, SearchWeights};
+    use stone_tauri_lib::domain::services::{RankFusion, RankedHit};
+
+    let hit = |id: &str, score: f32| RankedHit {
+        note_id: id.to_string(),
+        score,
+    };
+
+    // "exact" is a keyword-only hit, "concept" semantic-only, "both" found by each
+    let fts = vec![hit("exact", 12.0), hit("both", 8.0)];
+    let semantic = vec![hit("both", 0.9), hit("concept", 0.8)];
+    let weights = SearchWeights {
+        fts: 0.5,
+        semantic: 0.5,
+    };
+
+    for method in [FusionMethod::Rrf, FusionMethod::Weighted] {
+        let fused = RankFusion::fuse(&fts, &semantic, &weights, method);
+
+        // Deduplicated, and the note found by both retrievers ranks first
+        assert_eq!(fused.len(), 3);
+        assert_eq!(fused[0].note_id, "both");
+        assert_eq!(
+            fused[0].retrievers,
+            vec![SearchRetriever::Fts, SearchRetriever::Semantic]
+        );
+        let concept = fused.iter().find(|h| h.note_id == "concept").unwrap();
+        assert_eq!(concept.retrievers, vec![SearchRetriever::Semantic]);
+    }
+}
+
+#[test]
+fn test_rank_fusion_honors_weights() {
+    use stone_tauri_lib::domain::ports::outbound::{FusionMethod, SearchWeights};
+    use stone_tauri_lib::domain::services::{RankFusion, RankedHit};
+
+    let fts = vec![RankedHit {
+        note_id: "keyword".to_string(),
+        score: 5.0,
+    }];
+    let semantic = vec![RankedHit {
+        note_id: "concept".to_string(),
+        score: 0.7,
+    }];
+
+    let keyword_heavy = SearchWeights {
+        fts: 0.9,
+        semantic: 0.1,
+    };
+    let fused = RankFusion::fuse(&fts, &semantic, &keyword_heavy, FusionMethod::Rrf);
+    assert_eq!(fused[0].note_id, "keyword");
+
+    let semantic_heavy = SearchWeights {
+        fts: 0.1,
+        semantic: 0.9,
+    };
+    let fused = RankFusion::fuse(&fts, &semantic, &semantic_heavy, FusionMethod::Weighted);
+    assert_eq!(fused[0].note_id, "concept");
+}
+
+mod tag_and_date_search {
+    use chrono::{Duration, TimeZone, Utc};
+    use stone_tauri_lib::domain::{
+        entities::{Note, Tag},
+        ports::outbound::{
+            DateRangeField, DateRangeOptions, NoteRepository, SearchEngine, SearchOptions,
+            TagRepository, TagSearchOptions,
+        },
+    };
+
+    use crate::helpers::TestServices;
+
+    async fn fixture() -> TestServices {
+        TestServices::new("Search").await
+    }
+
+    async fn note_with_dates(
+        fx: &TestServices,
+        title: &str,
+        workspace_id: Option<String>,
+        created_days_ago: i64,
+        updated_days_ago: i64,
+    ) -> Note {
+        let base = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let mut note = Note::new(title, workspace_id).unwrap();
+        note.created_at = base - Duration::days(created_days_ago);
+        note.updated_at = base - Duration::days(updated_days_ago);
+        fx.notes.save(&note).await.unwrap();
+        note
+    }
+
+    async fn tag(fx: &TestServices, name: &str) -> Tag {
+        let tag = Tag::new(name).unwrap();
+        fx.tags.save(&tag).await.unwrap();
+        tag
+    }
+
+    fn tag_options(match_all: bool, workspace_id: Option<String>) -> TagSearchOptions {
+        TagSearchOptions {
+            base: SearchOptions {
+                workspace_id,
+                ..Default::default()
+            },
+            match_all: Some(match_all),
+            include_descendants: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_by_tags_any_and_all() {
+        let fx = fixture().await;
+        let ws = Some(fx.workspace.id.clone());
+        let rust = tag(&fx, "rust").await;
+        let web = tag(&fx, "web").await;
+
+        let both = note_with_dates(&fx, "Axum server", ws.clone(), 3, 1).await;
+        let only_rust = note_with_dates(&fx, "Ownership", ws.clone(), 3, 2).await;
+        let only_web = note_with_dates(&fx, "CSS grid", ws.clone(), 3, 3).await;
+        note_with_dates(&fx, "Untagged", ws.clone(), 3, 4).await;
+
+        for note in [&both, &only_rust] {
+            fx.tags.add_tag_to_note(&note.id, &rust.id).await.unwrap();
+        }
+        for note in [&both, &only_web] {
+            fx.tags.add_tag_to_note(&note.id, &web.id).await.unwrap();
+        }
+        let ids = vec![rust.id.clone(), web.id.clone()];
+
+        // OR: any tag, most recently updated first
+        let any = fx
+            .search_engine
+            .search_by_tags(ids.clone(), Some(tag_options(false, ws.clone())))
+            .await
+            .unwrap();
+        let titles: Vec<_> = any.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, vec!["Axum server", "Ownership", "CSS grid"]);
+
+        // AND: every tag
+        let all = fx
+            .search_engine
+            .search_by_tags(ids, Some(tag_options(true, ws)))
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, both.id);
+    }
+
+    #[tokio::test]
+    async fn test_search_by_tags_pagination_and_scoping() {
+        let fx = fixture().await;
+        let ws = Some(fx.workspace.id.clone());
+        let project = tag(&fx, "project").await;
+
+        for i in 0..5 {
+            let note = note_with_dates(&fx, &format!("Project {}", i), ws.clone(), 10, i).await;
+            fx.tags.add_tag_to_note(&note.id, &project.id).await.unwrap();
+        }
+        // Same tag outside the workspace and in the trash
+        let elsewhere = note_with_dates(&fx, "Elsewhere", None, 10, 0).await;
+        fx.tags.add_tag_to_note(&elsewhere.id, &project.id).await.unwrap();
+        let mut trashed = note_with_dates(&fx, "Trashed", ws.clone(), 10, 0).await;
+        trashed.delete();
+        fx.notes.save(&trashed).await.unwrap();
+        fx.tags.add_tag_to_note(&trashed.id, &project.id).await.unwrap();
+
+        let mut options = tag_options(false, ws);
+        options.base.limit = Some(2);
+        options.base.offset = Some(2);
+        let page = fx
+            .search_engine
+            .search_by_tags(vec![project.id.clone()], Some(options))
+            .await
+            .unwrap();
+        let titles: Vec<_> = page.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, vec!["Project 2", "Project 3"]);
+
+        let unscoped = fx
+            .search_engine
+            .search_by_tags(vec![project.id], None)
+            .await
+            .unwrap();
+        assert_eq!(unscoped.len(), 6, "Deleted notes are excluded by default");
+    }
+
+    #[tokio::test]
+    async fn test_tag_filters_include_nested_tags() {
+        let fx = fixture().await;
+        let ws = Some(fx.workspace.id.clone());
+        let alpha = tag(&fx, "project/alpha").await;
+        let beta = tag(&fx, "project/beta/v2").await;
+        let projects = tag(&fx, "projects").await;
+        let project = fx.tags.find_by_name("project").await.unwrap().unwrap();
+
+        let on_alpha = note_with_dates(&fx, "Alpha plan", ws.clone(), 3, 1).await;
+        let on_beta = note_with_dates(&fx, "Beta plan", ws.clone(), 3, 2).await;
+        let on_both = note_with_dates(&fx, "Both plans", ws.clone(), 3, 3).await;
+        let elsewhere = note_with_dates(&fx, "Other projects", ws.clone(), 3, 4).await;
+        fx.tags.add_tag_to_note(&on_alpha.id, &alpha.id).await.unwrap();
+        fx.tags.add_tag_to_note(&on_beta.id, &beta.id).await.unwrap();
+        fx.tags.add_tag_to_note(&on_both.id, &alpha.id).await.unwrap();
+        fx.tags.add_tag_to_note(&on_both.id, &beta.id).await.unwrap();
+        fx.tags.add_tag_to_note(&elsewhere.id, &projects.id).await.unwrap();
+
+        // Only the exact tag unless descendants are asked for
+        let exact = fx
+            .search_engine
+            .search_by_tags(vec![project.id.clone()], Some(tag_options(false, ws.clone())))
+            .await
+            .unwrap();
+        assert!(exact.is_empty());
+
+        let mut options = tag_options(true, ws.clone());
+        options.include_descendants = Some(true);
+        let nested = fx
+            .search_engine
+            .search_by_tags(vec![project.id.clone(), alpha.id.clone()], Some(options))
+            .await
+            .unwrap();
+        let titles: Vec<_> = nested.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, vec!["Alpha plan", "Both plans"]);
+
+        // Search filters by tag always cover nested tags
+        let by_name = fx
+            .search_engine
+            .search_full_text(
+                "",
+                Some(SearchOptions {
+                    workspace_id: ws.clone(),
+                    tag_names: Some(vec!["project".to_string()]),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+        let mut titles: Vec<_> = by_name.iter().map(|r| r.note.title.as_str()).collect();
+        titles.sort();
+        assert_eq!(titles, vec!["Alpha plan", "Beta plan", "Both plans"]);
+
+        let by_id = fx
+            .search_engine
+            .search_full_text(
+                "",
+                Some(SearchOptions {
+                    workspace_id: ws,
+                    tag_ids: Some(vec![
+                        fx.tags.find_by_name("project/beta").await.unwrap().unwrap().id,
+                    ]),
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+        let mut titles: Vec<_> = by_id.iter().map(|r| r.note.title.as_str()).collect();
+        titles.sort();
+        assert_eq!(titles, vec!["Beta plan", "Both plans"]);
+    }
+
+    #[tokio::test]
+    async fn test_search_by_date_range_created_and_updated() {
+        let fx = fixture().await;
+        let ws = Some(fx.workspace.id.clone());
+
+        let old = note_with_dates(&fx, "Old but edited", ws.clone(), 30, 1).await;
+        let recent = note_with_dates(&fx, "Recent", ws.clone(), 2, 2).await;
+        note_with_dates(&fx, "Other workspace", None, 2, 2).await;
+
+        let window = |field| DateRangeOptions {
+            start_date: Utc.with_ymd_and_hms(2026, 2, 25, 0, 0, 0).unwrap(),
+            end_date: Utc.with_ymd_and_hms(2026, 3, 1, 23, 59, 59).unwrap(),
+            workspace_id: ws.clone(),
+            field: Some(field),
+            limit: None,
+            offset: None,
+        };
+
+        let created = fx
+            .search_engine
+            .search_by_date_range(window(DateRangeField::Created))
+            .await
+            .unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].id, recent.id);
+
+        let updated = fx
+            .search_engine
+            .search_by_date_range(window(DateRangeField::Updated))
+            .await
+            .unwrap();
+        let ids: Vec<_> = updated.iter().map(|n| n.id.clone()).collect();
+        assert_eq!(ids, vec![old.id.clone(), recent.id.clone()]);
+
+        let mut paged = window(DateRangeField::Updated);
+        paged.limit = Some(1);
+        paged.offset = Some(1);
+        let page = fx.search_engine.search_by_date_range(paged).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].id, recent.id);
+    }
+}
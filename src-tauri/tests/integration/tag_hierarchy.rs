@@ -0,0 +1,167 @@
+//! Test Tag Hierarchy Workflow
+//!
+//! Critical Path: Nested tag saved → Parents created → Counts rolled up into a tree
+
+use stone_tauri_lib::application::usecases::TagUseCasesImpl;
+use stone_tauri_lib::domain::{
+    entities::{Note, Tag},
+    ports::{
+        inbound::{ListTagsRequest, TagList, TagUseCases, UpdateTagRequest},
+        outbound::{NoteRepository, TagRepository, TagWithCount},
+    },
+};
+
+use crate::helpers::TestServices;
+
+struct Fixture {
+    services: TestServices,
+    tag_usecases: TagUseCasesImpl,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+impl Fixture {
+    async fn tag(&self, name: &str) -> Tag {
+        let tag = Tag::new(name).unwrap();
+        self.tags.save(&tag).await.unwrap();
+        tag
+    }
+
+    async fn note(&self, title: &str, tags: &[&Tag]) -> Note {
+        let note = Note::new(title, None).unwrap();
+        self.notes.save(&note).await.unwrap();
+        for tag in tags {
+            self.tags.add_tag_to_note(&note.id, &tag.id).await.unwrap();
+        }
+        note
+    }
+
+    /// Names of the tags under `roots`, leaving out the seeded ones
+    async fn tag_names(&self, roots: &[&str]) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .tags
+            .find_all()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|tag| tag.name)
+            .filter(|name| roots.iter().any(|root| Tag::is_within(name, root)))
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::new("Tags").await;
+    Fixture {
+        tag_usecases: services.tag_usecases(),
+        services,
+    }
+}
+
+#[test]
+fn test_tag_name_hierarchy() {
+    assert_eq!(Tag::parent_name("project/alpha/v2"), Some("project/alpha"));
+    assert_eq!(Tag::parent_name("project"), None);
+    assert_eq!(
+        Tag::ancestor_names("project/alpha/v2"),
+        vec!["project", "project/alpha"]
+    );
+    assert!(Tag::is_within("project/alpha", "project"));
+    assert!(Tag::is_within("project", "project"));
+    assert!(!Tag::is_within("projects", "project"));
+    assert_eq!(
+        Tag::rebase_name("project/alpha", "project", "work").as_deref(),
+        Some("work/alpha")
+    );
+    assert_eq!(Tag::rebase_name("projects", "project", "work"), None);
+}
+
+#[tokio::test]
+async fn test_counts_roll_up_into_tree() {
+    let f = fixture().await;
+    let alpha = f.tag("project/alpha").await;
+    let beta = f.tag("project/beta").await;
+    f.tag("zen").await;
+    let project = f.tags.find_by_name("project").await.unwrap().unwrap();
+    assert_eq!(
+        f.tag_names(&["project", "zen"]).await,
+        vec!["project", "project/alpha", "project/beta", "zen"]
+    );
+
+    f.note("Alpha", &[&alpha]).await;
+    f.note("Beta", &[&beta]).await;
+    f.note("Everything", &[&project, &alpha, &beta]).await;
+    let mut trashed = f.note("Trashed", &[&alpha]).await;
+    trashed.delete();
+    f.notes.save(&trashed).await.unwrap();
+
+    let TagList::WithCount(tree) = f
+        .tag_usecases
+        .list_tags(Some(ListTagsRequest {
+            include_note_count: Some(true),
+        }))
+        .await
+        .unwrap()
+    else {
+        panic!("Expected tags with counts");
+    };
+
+    let summary = |tag: &TagWithCount| (tag.name.clone(), tag.note_count, tag.total_note_count);
+    let project = tree.iter().find(|tag| tag.name == "project").unwrap();
+    let zen = tree.iter().find(|tag| tag.name == "zen").unwrap();
+    assert_eq!(summary(project), ("project".to_string(), 1, 3));
+    assert_eq!(summary(zen), ("zen".to_string(), 0, 0));
+    assert!(zen.children.is_empty());
+    assert_eq!(
+        project.children.iter().map(summary).collect::<Vec<_>>(),
+        vec![
+            ("project/alpha".to_string(), 2, 2),
+            ("project/beta".to_string(), 2, 2),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_update_tag_leaves_renames_to_rename_tag() {
+    let f = fixture().await;
+    let alpha = f.tag("project/alpha").await;
+
+    let err = f
+        .tag_usecases
+        .update_tag(UpdateTagRequest {
+            id: alpha.id.clone(),
+            name: Some("area/alpha".to_string()),
+            color: Some("#ff0000".to_string()),
+        })
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("rename_tag"));
+    assert_eq!(
+        f.tag_names(&["area", "project"]).await,
+        vec!["project", "project/alpha"]
+    );
+
+    // The same name, however it is written, is not a rename
+    let updated = f
+        .tag_usecases
+        .update_tag(UpdateTagRequest {
+            id: alpha.id.clone(),
+            name: Some("#Project/Alpha".to_string()),
+            color: Some("#ff0000".to_string()),
+        })
+        .await
+        .unwrap();
+    assert_eq!(updated.name, "project/alpha");
+    assert_eq!(
+        f.tags.find_by_id(&alpha.id).await.unwrap().unwrap().color,
+        "#ff0000"
+    );
+}
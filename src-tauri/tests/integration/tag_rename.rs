@@ -0,0 +1,355 @@
+//! Test Tag Rename Workflow
+//!
+//! Critical Path: Preview tag rename → Frontmatter tags and hashtags rewritten → Tags moved
+//! Renaming a parent tag renames every tag nested under it, and renaming onto
+//! an existing tag merges the two, keeping each note tagged once. The tag rows
+//! change in one transaction.
+
+use stone_tauri_lib::application::usecases::RefactorUseCasesImpl;
+use stone_tauri_lib::domain::{
+    entities::{Note, Tag, TagSource},
+    ports::{
+        inbound::{RefactorUseCases, RenameTagRequest},
+        outbound::{NoteRepository, TagMove, TagRepository},
+    },
+    services::{Frontmatter, HashtagExtractor},
+};
+
+use crate::helpers::TestServices;
+
+struct Fixture {
+    services: TestServices,
+    refactor: RefactorUseCasesImpl,
+}
+
+impl std::ops::Deref for Fixture {
+    type Target = TestServices;
+
+    fn deref(&self) -> &TestServices {
+        &self.services
+    }
+}
+
+impl Fixture {
+    async fn tag(&self, name: &str) -> Tag {
+        let tag = Tag::new(name).unwrap();
+        self.tags.save(&tag).await.unwrap();
+        tag
+    }
+
+    /// A note whose file holds exactly `markdown`, linked to `tags`
+    async fn note(&self, title: &str, markdown: &str, tags: &[(&Tag, TagSource)]) -> Note {
+        let file_path = format!("{}.md", title);
+        std::fs::write(self.workspace_dir.path().join(&file_path), markdown).unwrap();
+
+        let mut note = Note::new(title, Some(self.workspace.id.clone())).unwrap();
+        note.set_file_path(Some(file_path)).unwrap();
+        self.notes.save(&note).await.unwrap();
+        for (tag, source) in tags {
+            self.tags
+                .link_tag(&note.id, &tag.id, *source)
+                .await
+                .unwrap();
+        }
+        note
+    }
+
+    fn read(&self, file_name: &str) -> String {
+        std::fs::read_to_string(self.workspace_dir.path().join(file_name)).unwrap()
+    }
+
+    /// The note's tag names and sources, sorted
+    async fn note_tags(&self, note: &Note) -> Vec<(String, TagSource)> {
+        let mut tags: Vec<(String, TagSource)> = self
+            .tags
+            .find_note_tags_with_source(&note.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|note_tag| (note_tag.tag.name, note_tag.source))
+            .collect();
+        tags.sort_by(|a, b| a.0.cmp(&b.0));
+        tags
+    }
+}
+
+async fn fixture() -> Fixture {
+    let services = TestServices::new("Tags").await;
+    Fixture {
+        refactor: services.refactor_usecases(),
+        services,
+    }
+}
+
+fn frontmatter_tags(markdown: &str) -> Vec<String> {
+    Frontmatter::from_markdown(markdown)
+        .unwrap()
+        .unwrap()
+        .get("tags")
+        .unwrap()
+        .as_string_list()
+}
+
+fn rename_request(tag: &Tag, new_name: &str) -> RenameTagRequest {
+    RenameTagRequest {
+        tag_id: tag.id.clone(),
+        new_name: new_name.to_string(),
+    }
+}
+
+#[test]
+fn test_rename_tags_in_markdown() {
+    let markdown = "Plans for #Project and #project/alpha, not #projects.\n\
+                    `#project` in code and [[Note#project]] stay.\n";
+    assert_eq!(
+        HashtagExtractor::rename_tags(markdown, "project", "work").unwrap(),
+        "Plans for #work and #work/alpha, not #projects.\n\
+         `#project` in code and [[Note#project]] stay.\n"
+    );
+    assert!(HashtagExtractor::rename_tags("Only #projects", "project", "work").is_none());
+
+    let mut frontmatter = Frontmatter::from_markdown(
+        "---\ntags: [project, \"#project/alpha\", work, ideas]\n---\nBody\n",
+    )
+    .unwrap()
+    .unwrap();
+    assert!(frontmatter.rename_tags("project", "work"));
+    assert_eq!(
+        frontmatter.get("tags").unwrap().as_string_list(),
+        vec!["work", "#work/alpha", "ideas"]
+    );
+    assert!(!frontmatter.rename_tags("missing", "work"));
+}
+
+#[tokio::test]
+async fn test_preview_then_rename_rewrites_files() {
+    let f = fixture().await;
+    let project = f.tag("project").await;
+    let alpha = f.tag("project/alpha").await;
+
+    let plan_note = f
+        .note(
+            "Plan",
+            "---\ntitle: Plan\ntags:\n  - project\n---\n# Plan\n\nKickoff for #project/alpha.\n",
+            &[(&project, TagSource::Manual), (&alpha, TagSource::Content)],
+        )
+        .await;
+    let journal = f
+        .note(
+            "Journal",
+            "Worked on #Project today, unlike #projects.\n",
+            &[(&project, TagSource::Content)],
+        )
+        .await;
+    let untouched = f.note("Go", "# Go\n\nNothing tagged.\n", &[]).await;
+
+    let plan = f
+        .refactor
+        .preview_tag_rename(rename_request(&project, "Work"))
+        .await
+        .unwrap();
+    assert_eq!(plan.old_name, "project");
+    assert_eq!(plan.new_name, "work");
+    assert_eq!(plan.note_count, 2);
+    assert_eq!(
+        plan.tags
+            .iter()
+            .map(|rename| (
+                rename.from.as_str(),
+                rename.to.as_str(),
+                rename.merged_into.is_some()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("project", "work", false),
+            ("project/alpha", "work/alpha", false)
+        ]
+    );
+    assert_eq!(
+        plan.changes
+            .iter()
+            .map(|change| change.file_path.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Journal.md", "Plan.md"]
+    );
+    // A preview writes nothing
+    assert!(f.read("Plan.md").contains("#project/alpha"));
+    assert!(f.tags.find_by_name("work").await.unwrap().is_none());
+
+    let response = f
+        .refactor
+        .rename_tag(rename_request(&project, "Work"))
+        .await
+        .unwrap();
+    assert_eq!(response.tag.id, project.id);
+    assert_eq!(response.tag.name, "work");
+    assert_eq!(response.changes.len(), 2);
+
+    let plan_markdown = f.read("Plan.md");
+    assert!(plan_markdown.contains("Kickoff for #work/alpha."));
+    assert!(plan_markdown.contains("title: Plan"));
+    assert_eq!(frontmatter_tags(&plan_markdown), vec!["work"]);
+    assert_eq!(
+        f.read("Journal.md"),
+        "Worked on #work today, unlike #projects.\n"
+    );
+    assert_eq!(f.read("Go.md"), "# Go\n\nNothing tagged.\n");
+
+    // The rows keep their ids and each link keeps its source
+    assert!(f.tags.find_by_name("project").await.unwrap().is_none());
+    assert_eq!(
+        f.note_tags(&plan_note).await,
+        vec![
+            ("work".to_string(), TagSource::Manual),
+            ("work/alpha".to_string(), TagSource::Content),
+        ]
+    );
+    assert_eq!(
+        f.note_tags(&journal).await,
+        vec![("work".to_string(), TagSource::Content)]
+    );
+    assert!(f.note_tags(&untouched).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_rename_onto_existing_tag_merges() {
+    let f = fixture().await;
+    let todo = f.tag("todo").await;
+    let tasks = f.tag("tasks").await;
+
+    let both = f
+        .note(
+            "Both",
+            "---\ntags: [todo, tasks]\n---\nSee #todo and #tasks.\n",
+            &[(&todo, TagSource::Manual), (&tasks, TagSource::Content)],
+        )
+        .await;
+    let only_todo = f
+        .note("Only", "Remember #todo.\n", &[(&todo, TagSource::Content)])
+        .await;
+
+    let plan = f
+        .refactor
+        .preview_tag_rename(rename_request(&todo, "tasks"))
+        .await
+        .unwrap();
+    assert_eq!(plan.tags.len(), 1);
+    assert_eq!(plan.tags[0].merged_into.as_deref(), Some(tasks.id.as_str()));
+
+    let response = f
+        .refactor
+        .rename_tag(rename_request(&todo, "tasks"))
+        .await
+        .unwrap();
+    assert_eq!(response.tag.id, tasks.id);
+
+    // The frontmatter list lists the merged tag once
+    let both_markdown = f.read("Both.md");
+    assert!(both_markdown.contains("See #tasks and #tasks."));
+    assert_eq!(frontmatter_tags(&both_markdown), vec!["tasks"]);
+    assert_eq!(f.read("Only.md"), "Remember #tasks.\n");
+
+    // The merged tag is gone, its notes carry the target once, manual winning
+    assert!(f.tags.find_by_id(&todo.id).await.unwrap().is_none());
+    assert_eq!(
+        f.note_tags(&both).await,
+        vec![("tasks".to_string(), TagSource::Manual)]
+    );
+    assert_eq!(
+        f.note_tags(&only_todo).await,
+        vec![("tasks".to_string(), TagSource::Content)]
+    );
+
+    // Moving a tag under itself is refused before any file is touched
+    let err = f
+        .refactor
+        .rename_tag(rename_request(&tasks, "tasks/later"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("under itself"));
+    assert_eq!(f.read("Only.md"), "Remember #tasks.\n");
+}
+
+#[tokio::test]
+async fn test_renaming_parent_moves_nested_tags() {
+    let f = fixture().await;
+    f.tag("project/alpha/v2").await;
+    f.tag("projects").await;
+    let project = f.tags.find_by_name("project").await.unwrap().unwrap();
+    let alpha = f.tags.find_by_name("project/alpha").await.unwrap().unwrap();
+    let note = f
+        .note(
+            "Alpha",
+            "Shipped #project/alpha/v2.\n",
+            &[(&alpha, TagSource::Manual)],
+        )
+        .await;
+
+    let response = f
+        .refactor
+        .rename_tag(rename_request(&project, "Area/Work"))
+        .await
+        .unwrap();
+    assert_eq!(response.tag.name, "area/work");
+    assert_eq!(f.read("Alpha.md"), "Shipped #area/work/alpha/v2.\n");
+
+    let mut names: Vec<String> = f
+        .tags
+        .find_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tag| tag.name)
+        .filter(|name| {
+            ["area", "project"]
+                .iter()
+                .any(|root| Tag::is_within(name, root))
+        })
+        .collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec!["area", "area/work", "area/work/alpha", "area/work/alpha/v2"]
+    );
+    assert!(f.tags.find_by_name("projects").await.unwrap().is_some());
+    assert_eq!(
+        f.note_tags(&note).await,
+        vec![("area/work/alpha".to_string(), TagSource::Manual)]
+    );
+}
+
+#[tokio::test]
+async fn test_tag_moves_are_all_or_nothing() {
+    let f = fixture().await;
+    let todo = f.tag("todo").await;
+    let tasks = f.tag("tasks").await;
+    let done = f.tag("done").await;
+    let note = f
+        .note("Todo", "Remember #todo.\n", &[(&todo, TagSource::Content)])
+        .await;
+
+    // The rename onto a taken name fails after the merge has run
+    let mut renamed = done.clone();
+    renamed.rename("tasks").unwrap();
+    assert!(f
+        .tags
+        .move_tags(vec![
+            TagMove::Merge {
+                from_id: todo.id.clone(),
+                into_id: tasks.id.clone(),
+            },
+            TagMove::Rename(renamed),
+        ])
+        .await
+        .is_err());
+
+    assert!(f.tags.find_by_id(&todo.id).await.unwrap().is_some());
+    assert_eq!(
+        f.tags.find_by_id(&done.id).await.unwrap().unwrap().name,
+        "done"
+    );
+    assert_eq!(
+        f.note_tags(&note).await,
+        vec![("todo".to_string(), TagSource::Content)]
+    );
+}
@@ -0,0 +1,109 @@
+//! Test Topic Classification
+//!
+//! Critical Path: Note embedding → Topic centroids → Assigned topics
+//! Centroids follow the user's manual assignments more than the classifier's
+//! own guesses, and only confident matches are assigned.
+
+use stone_tauri_lib::domain::entities::Topic;
+use stone_tauri_lib::domain::services::{CentroidMember, ClassificationConfig, TopicClassifier};
+
+fn topic(name: &str, centroid: &[f32]) -> Topic {
+    let mut topic = Topic::new(name).unwrap();
+    topic.update_centroid(Topic::encode_centroid(centroid));
+    topic
+}
+
+fn member(embedding: Vec<f32>, is_manual: bool, confidence: f32) -> CentroidMember {
+    CentroidMember {
+        embedding,
+        is_manual,
+        confidence,
+    }
+}
+
+#[test]
+fn test_manual_members_dominate_the_centroid() {
+    let classifier = TopicClassifier::default();
+
+    let centroid = classifier
+        .centroid(&[
+            member(vec![1.0, 0.0], true, 1.0),
+            member(vec![0.0, 1.0], false, 0.9),
+            member(vec![0.0, 1.0], false, 0.9),
+        ])
+        .unwrap();
+
+    // 3.0 manual weight against 1.8 of automatic confidence
+    assert!(centroid[0] > centroid[1]);
+    let norm: f32 = centroid.iter().map(|v| v * v).sum::<f32>().sqrt();
+    assert!((norm - 1.0).abs() < 1e-6);
+
+    // Mismatched dimensions are ignored; no members means no centroid
+    let centroid = classifier
+        .centroid(&[
+            member(vec![0.0, 2.0], false, 0.5),
+            member(vec![1.0], true, 1.0),
+        ])
+        .unwrap();
+    assert_eq!(centroid, vec![0.0, 1.0]);
+    assert!(classifier.centroid(&[]).is_none());
+    assert!(classifier
+        .centroid(&[member(vec![1.0, 0.0], false, 0.0)])
+        .is_none());
+}
+
+#[test]
+fn test_classify_applies_threshold_and_top_k() {
+    let topics = vec![
+        topic("Rust", &[1.0, 0.0, 0.0]),
+        topic("Systems", &[0.8, 0.6, 0.0]),
+        topic("Cooking", &[0.0, 0.0, 1.0]),
+        Topic::new("Empty").unwrap(),
+    ];
+    let embedding = [0.9, 0.436, 0.0];
+
+    let classifier = TopicClassifier::new(ClassificationConfig {
+        threshold: 0.5,
+        top_k: 3,
+        manual_weight: 3.0,
+    });
+    let names: Vec<String> = classifier
+        .classify(&embedding, &topics)
+        .into_iter()
+        .map(|m| m.topic_name)
+        .collect();
+    assert_eq!(names, vec!["Systems", "Rust"]);
+
+    let classifier = TopicClassifier::new(ClassificationConfig {
+        top_k: 1,
+        ..ClassificationConfig::default()
+    });
+    let matches = classifier.classify(&embedding, &topics);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].topic_name, "Systems");
+    assert!(matches[0].confidence > 0.95);
+
+    let strict = TopicClassifier::new(ClassificationConfig {
+        threshold: 0.99,
+        ..ClassificationConfig::default()
+    });
+    assert!(strict.classify(&embedding, &topics).is_empty());
+}
+
+#[test]
+fn test_classification_setting_overrides_defaults() {
+    let config = ClassificationConfig::from_setting(r#"{"threshold": 0.7, "topK": 1}"#).unwrap();
+    assert_eq!(
+        config,
+        ClassificationConfig {
+            threshold: 0.7,
+            top_k: 1,
+            ..ClassificationConfig::default()
+        }
+    );
+
+    assert!(ClassificationConfig::from_setting(r#"{"threshold": 1.5}"#).is_err());
+    assert!(ClassificationConfig::from_setting(r#"{"topK": 0}"#).is_err());
+    assert!(ClassificationConfig::from_setting(r#"{"manualWeight": -1}"#).is_err());
+    assert!(ClassificationConfig::from_setting("not json").is_err());
+}
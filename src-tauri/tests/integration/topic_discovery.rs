@@ -0,0 +1,125 @@
+//! Test Topic Discovery
+//!
+//! Critical Path: Note embeddings → Clusters → TF-IDF labels → Candidate topics
+//! Stone proposes new topics from groups of similar notes; the number of
+//! groups isn't known up front and labels must describe what sets a group apart.
+
+use stone_tauri_lib::domain::services::{DiscoveryConfig, EmbeddingCluster, TopicDiscovery};
+
+/// Three well separated groups of four notes around the first three axes
+fn grouped_embeddings() -> Vec<Vec<f32>> {
+    let mut embeddings = Vec::new();
+    for i in 0..12 {
+        let group = i % 3;
+        let mut embedding = vec![0.0f32; 6];
+        embedding[group] = 1.0;
+        // Small, distinct offsets so no two notes are identical
+        embedding[3 + (i % 3)] = 0.05 * (i / 3) as f32;
+        embedding[(group + 1) % 3] = 0.02 * (i % 4) as f32;
+        embeddings.push(embedding);
+    }
+    embeddings
+}
+
+fn sorted_groups(clusters: &[EmbeddingCluster]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = clusters.iter().map(|c| c.members.clone()).collect();
+    groups.sort();
+    groups
+}
+
+#[test]
+fn test_clusters_are_found_without_knowing_k() {
+    let clusters = TopicDiscovery::default().cluster(&grouped_embeddings());
+
+    assert_eq!(
+        sorted_groups(&clusters),
+        vec![vec![0, 3, 6, 9], vec![1, 4, 7, 10], vec![2, 5, 8, 11]]
+    );
+    for cluster in &clusters {
+        assert!(cluster.cohesion > 0.95);
+        assert_eq!(cluster.similarities.len(), cluster.members.len());
+        let norm: f32 = cluster.centroid.iter().map(|v| v * v).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+    }
+    // Most cohesive first
+    assert!(clusters.windows(2).all(|w| w[0].cohesion >= w[1].cohesion));
+
+    // Same seed, same answer
+    assert_eq!(
+        TopicDiscovery::default().cluster(&grouped_embeddings()),
+        clusters
+    );
+}
+
+#[test]
+fn test_small_and_unusable_groups_are_not_proposed() {
+    let mut embeddings = grouped_embeddings();
+    embeddings.truncate(9); // Three notes per group
+    embeddings.push(vec![0.0; 6]); // Zero vector
+    embeddings.push(vec![1.0, 0.0]); // Wrong dimension
+
+    let discovery = TopicDiscovery::new(DiscoveryConfig {
+        min_cluster_size: 4,
+        min_clusters: 1,
+        ..DiscoveryConfig::default()
+    });
+    let clusters = discovery.cluster(&embeddings);
+    // Only k = 1 or 2 fit nine notes at four per cluster; no cluster may include
+    // the ignored embeddings
+    assert!(clusters
+        .iter()
+        .all(|c| c.members.len() >= 4 && c.members.iter().all(|m| *m < 9)));
+
+    assert!(TopicDiscovery::default().cluster(&[]).is_empty());
+    assert!(TopicDiscovery::default()
+        .cluster(&grouped_embeddings()[..5])
+        .is_empty());
+}
+
+#[test]
+fn test_labels_prefer_distinctive_terms() {
+    let documents: Vec<String> = [
+        "Rust ownership and the borrow checker in my project",
+        "Borrow checker errors in the Rust project",
+        "Rust lifetimes: ownership across functions in the project",
+        "Sourdough bread recipe for the project",
+        "Baking sourdough bread at high hydration",
+        "Sourdough starter feeding for bread",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    let clusters = vec![
+        EmbeddingCluster {
+            members: vec![0, 1, 2],
+            similarities: vec![1.0; 3],
+            centroid: vec![1.0, 0.0],
+            cohesion: 1.0,
+        },
+        EmbeddingCluster {
+            members: vec![3, 4, 5],
+            similarities: vec![1.0; 3],
+            centroid: vec![0.0, 1.0],
+            cohesion: 1.0,
+        },
+    ];
+
+    let labels = TopicDiscovery::default().label(&documents, &clusters);
+
+    // "project" is everywhere and single mentions don't count
+    assert_eq!(labels[0], vec!["borrow", "checker", "ownership"]);
+    assert_eq!(labels[1], vec!["bread", "sourdough"]);
+    assert_eq!(
+        TopicDiscovery::name_for(&labels[1]).as_deref(),
+        Some("Bread, Sourdough")
+    );
+    assert_eq!(TopicDiscovery::name_for(&[]), None);
+}
+
+#[test]
+fn test_tokenize_skips_noise() {
+    assert_eq!(
+        TopicDiscovery::tokenize("The Überblick of 2024: see https://x.io and tokio's API"),
+        vec!["überblick", "see", "tokio", "api"]
+    );
+}
@@ -0,0 +1,203 @@
+//! Test Vector Index
+//!
+//! Critical Path: Embeddings stored → HNSW graph → Nearest notes for semantic search
+//! The approximate index must agree with an exact scan closely enough that
+//! users don't notice, while staying fast on large workspaces.
+//!
+//! Benchmark against the brute-force scan with:
+//! `cargo test --release --test lib vector_index -- --ignored --nocapture`
+
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use stone_tauri_lib::domain::services::{HnswConfig, HnswIndex, VectorHit};
+
+/// Clustered vectors, which is how note embeddings tend to look
+fn clustered_vectors(rng: &mut StdRng, count: usize, dimensions: usize) -> Vec<Vec<f32>> {
+    let centers: Vec<Vec<f32>> = (0..50)
+        .map(|_| {
+            (0..dimensions)
+                .map(|_| rng.gen_range(-1.0f32..1.0))
+                .collect()
+        })
+        .collect();
+
+    (0..count)
+        .map(|i| {
+            centers[i % centers.len()]
+                .iter()
+                .map(|c| c + rng.gen_range(-0.3f32..0.3))
+                .collect()
+        })
+        .collect()
+}
+
+fn build_index(vectors: &[Vec<f32>]) -> HnswIndex {
+    let mut index = HnswIndex::new(HnswConfig::default());
+    for (i, vector) in vectors.iter().enumerate() {
+        index.insert(&format!("note-{}", i), vector).unwrap();
+    }
+    index
+}
+
+fn recall(approximate: &[VectorHit], exact: &[VectorHit]) -> f32 {
+    let found = approximate
+        .iter()
+        .filter(|hit| exact.iter().any(|e| e.id == hit.id))
+        .count();
+    found as f32 / exact.len().max(1) as f32
+}
+
+#[test]
+fn test_search_matches_exact_scan() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let vectors = clustered_vectors(&mut rng, 2000, 32);
+    let index = build_index(&vectors);
+    assert_eq!(index.len(), 2000);
+
+    let mut total = 0.0;
+    for i in 0..50 {
+        let query: Vec<f32> = vectors[i * 37]
+            .iter()
+            .map(|v| v + rng.gen_range(-0.3f32..0.3))
+            .collect();
+        let approximate = index.search(&query, 10, None);
+        let exact = index.exact_search(&query, 10, None);
+        assert_eq!(approximate.len(), 10);
+        total += recall(&approximate, &exact);
+    }
+
+    assert!(total / 50.0 >= 0.95, "recall@10 too low: {}", total / 50.0);
+}
+
+#[test]
+fn test_search_respects_filter() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let vectors = clustered_vectors(&mut rng, 1000, 16);
+    let index = build_index(&vectors);
+
+    // Only one in ten notes is in the requested notebook
+    let in_notebook = |id: &str| id.ends_with('3');
+    let hits = index.search(&vectors[0], 10, Some(&in_notebook));
+
+    assert_eq!(hits.len(), 10);
+    assert!(hits.iter().all(|hit| in_notebook(&hit.id)));
+}
+
+#[test]
+fn test_remove_and_replace() {
+    let mut index = HnswIndex::new(HnswConfig::default());
+    index.insert("a", &[1.0, 0.0]).unwrap();
+    index.insert("b", &[0.9, 0.1]).unwrap();
+    index.insert("c", &[0.0, 1.0]).unwrap();
+
+    assert!(index.remove("b"));
+    assert!(!index.remove("b"));
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.deleted_count(), 1);
+    let ids: Vec<String> = index
+        .search(&[1.0, 0.0], 5, None)
+        .into_iter()
+        .map(|h| h.id)
+        .collect();
+    assert_eq!(ids, vec!["a", "c"]);
+
+    // Re-inserting an ID replaces its vector
+    index.insert("c", &[1.0, 0.05]).unwrap();
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.search(&[1.0, 0.0], 1, None)[0].id, "a");
+    assert_eq!(index.search(&[0.0, 1.0], 2, None)[0].id, "c");
+
+    let compacted = index.compacted();
+    assert_eq!(compacted.len(), 2);
+    assert_eq!(compacted.deleted_count(), 0);
+}
+
+#[test]
+fn test_rejects_mismatched_dimensions() {
+    let mut index = HnswIndex::new(HnswConfig::default());
+    index.insert("a", &[1.0, 0.0, 0.0]).unwrap();
+
+    assert!(index.insert("b", &[1.0, 0.0]).is_err());
+    assert!(index.insert("c", &[]).is_err());
+    assert!(index.search(&[1.0, 0.0], 5, None).is_empty());
+}
+
+#[test]
+fn test_round_trips_through_bytes() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let vectors = clustered_vectors(&mut rng, 300, 8);
+    let mut index = build_index(&vectors);
+    index.remove("note-5");
+
+    let bytes = index.to_bytes();
+    let restored = HnswIndex::from_bytes(&bytes).unwrap();
+
+    assert_eq!(restored.len(), index.len());
+    assert_eq!(restored.deleted_count(), 1);
+    assert!(!restored.contains("note-5"));
+    assert_eq!(
+        restored.search(&vectors[1], 10, None),
+        index.search(&vectors[1], 10, None)
+    );
+
+    assert!(HnswIndex::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(HnswIndex::from_bytes(b"garbage").is_err());
+}
+
+#[test]
+#[ignore = "benchmark; run in release mode"]
+fn bench_hnsw_against_brute_force() {
+    const NOTES: usize = 20_000;
+    const DIMENSIONS: usize = 384; // all-MiniLM-L6-v2
+    const QUERIES: usize = 200;
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let vectors = clustered_vectors(&mut rng, NOTES, DIMENSIONS);
+
+    let started = Instant::now();
+    let index = build_index(&vectors);
+    let build_time = started.elapsed();
+
+    let queries: Vec<Vec<f32>> = (0..QUERIES)
+        .map(|i| {
+            vectors[(i * 97) % NOTES]
+                .iter()
+                .map(|v| v + rng.gen_range(-0.3f32..0.3))
+                .collect()
+        })
+        .collect();
+
+    let (mut ann_time, mut exact_time) = (Duration::ZERO, Duration::ZERO);
+    let mut total_recall = 0.0;
+    for query in &queries {
+        let started = Instant::now();
+        let approximate = index.search(query, 10, None);
+        ann_time += started.elapsed();
+
+        let started = Instant::now();
+        let exact = index.exact_search(query, 10, None);
+        exact_time += started.elapsed();
+
+        total_recall += recall(&approximate, &exact);
+    }
+
+    let bytes = index.to_bytes();
+    println!(
+        "{} x {}d: build {:?}, index {:.1} MB",
+        NOTES,
+        DIMENSIONS,
+        build_time,
+        bytes.len() as f64 / 1_048_576.0
+    );
+    println!(
+        "per query: hnsw {:?}, brute force {:?}, recall@10 {:.3}",
+        ann_time / QUERIES as u32,
+        exact_time / QUERIES as u32,
+        total_recall / QUERIES as f32
+    );
+
+    assert!(total_recall / QUERIES as f32 >= 0.95);
+    assert!(ann_time < exact_time);
+}
@@ -8,10 +8,46 @@
 import { z } from 'zod';
 import { invokeIpc } from '../lib/tauri-ipc';
 import { NOTE_COMMANDS } from '../constants/tauriCommands';
-import type { Note, IpcResponse, TodoItem } from '../types';
+import type {
+  Note,
+  IpcResponse,
+  TodoItem,
+  DuplicateCluster,
+  LinkSuggestion,
+  AmbiguousLink,
+  NoteBlock,
+  EmbedContent,
+  FileChange,
+  RenameNotePlan,
+  TemplateInfo,
+  TemplateSettings,
+  CreateNoteFromTemplateRequest,
+  CreateNoteFromTemplateResponse,
+  OpenPeriodicNoteRequest,
+  PeriodicNoteResponse,
+  PeriodicNotesSettings,
+} from '../types';
 import type { NoteFilters, GraphData as SpecGraphData } from '@/specs';
 import { validateResponse } from './validation';
-import { NoteSchema, NoteWithMetaSchema, TodoItemSchema, GraphDataSchema } from './schemas';
+import {
+  NoteSchema,
+  NoteWithMetaSchema,
+  TodoItemSchema,
+  GraphDataSchema,
+  DuplicateClusterSchema,
+  MergeNotesResponseSchema,
+  LinkSuggestionSchema,
+  AmbiguousLinkSchema,
+  NoteBlockSchema,
+  EmbedContentSchema,
+  RenameNotePlanSchema,
+  RenameNoteResponseSchema,
+  TemplateInfoSchema,
+  TemplateSettingsSchema,
+  CreateNoteFromTemplateResponseSchema,
+  PeriodicNoteResponseSchema,
+  PeriodicNotesSettingsSchema,
+} from './schemas';
 
 // Re-export types aligned with specs
 export type GetAllNotesParams = NoteFilters;
@@ -81,6 +117,94 @@ export const noteAPI = {
     return validateResponse(response, NoteSchema);
   },
 
+  /**
+   * Create a note from a template, or from the notebook's default template
+   */
+  createFromTemplate: async (
+    request: CreateNoteFromTemplateRequest,
+  ): Promise<IpcResponse<CreateNoteFromTemplateResponse>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.CREATE_FROM_TEMPLATE, { request });
+    return validateResponse(response, CreateNoteFromTemplateResponseSchema);
+  },
+
+  /**
+   * List templates in the workspace's templates folder
+   */
+  listTemplates: async (workspaceId?: string): Promise<IpcResponse<TemplateInfo[]>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.LIST_TEMPLATES, { workspaceId });
+    return validateResponse(response, z.array(TemplateInfoSchema));
+  },
+
+  /**
+   * Get the templates folder and per-notebook default templates
+   */
+  getTemplateSettings: async (workspaceId?: string): Promise<IpcResponse<TemplateSettings>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.GET_TEMPLATE_SETTINGS, { workspaceId });
+    return validateResponse(response, TemplateSettingsSchema);
+  },
+
+  /**
+   * Change the templates folder and per-notebook default templates
+   */
+  updateTemplateSettings: async (
+    settings: TemplateSettings,
+    workspaceId?: string,
+  ): Promise<IpcResponse<TemplateSettings>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.UPDATE_TEMPLATE_SETTINGS, {
+      workspaceId,
+      settings,
+    });
+    return validateResponse(response, TemplateSettingsSchema);
+  },
+
+  /**
+   * Set or clear a notebook's default template
+   */
+  setNotebookTemplate: async (
+    notebookId: string,
+    templatePath: string | null,
+  ): Promise<IpcResponse<TemplateSettings>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.SET_NOTEBOOK_TEMPLATE, {
+      notebookId,
+      templatePath,
+    });
+    return validateResponse(response, TemplateSettingsSchema);
+  },
+
+  /**
+   * Open the daily, weekly, monthly or quarterly note, creating it if needed
+   */
+  openPeriodicNote: async (
+    request: OpenPeriodicNoteRequest,
+  ): Promise<IpcResponse<PeriodicNoteResponse>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.OPEN_PERIODIC_NOTE, { request });
+    return validateResponse(response, PeriodicNoteResponseSchema);
+  },
+
+  /**
+   * Get the folders, formats and templates of periodic notes
+   */
+  getPeriodicSettings: async (
+    workspaceId?: string,
+  ): Promise<IpcResponse<PeriodicNotesSettings>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.GET_PERIODIC_SETTINGS, { workspaceId });
+    return validateResponse(response, PeriodicNotesSettingsSchema);
+  },
+
+  /**
+   * Change the folders, formats and templates of periodic notes
+   */
+  updatePeriodicSettings: async (
+    settings: PeriodicNotesSettings,
+    workspaceId?: string,
+  ): Promise<IpcResponse<PeriodicNotesSettings>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.UPDATE_PERIODIC_SETTINGS, {
+      workspaceId,
+      settings,
+    });
+    return validateResponse(response, PeriodicNotesSettingsSchema);
+  },
+
   /**
    * Update an existing note
    */
@@ -210,6 +334,118 @@ export const noteAPI = {
     return validateResponse(response, GraphDataSchema);
   },
 
+  /**
+   * Suggest links: unlinked mentions of other notes, then related notes
+   */
+  getLinkSuggestions: async (
+    noteId: string,
+    relatedLimit?: number,
+  ): Promise<IpcResponse<LinkSuggestion[]>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.GET_LINK_SUGGESTIONS, {
+      request: { noteId, relatedLimit },
+    });
+    return validateResponse(response, z.array(LinkSuggestionSchema));
+  },
+
+  /**
+   * Rewrite a suggestion into a wiki link; returns the updated markdown
+   */
+  applyLinkSuggestion: async (
+    noteId: string,
+    suggestion: LinkSuggestion,
+  ): Promise<IpcResponse<string>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.APPLY_LINK_SUGGESTION, {
+      request: {
+        noteId,
+        targetNoteId: suggestion.targetNoteId,
+        kind: suggestion.kind,
+        startOffset: suggestion.startOffset,
+        endOffset: suggestion.endOffset,
+        text: suggestion.text,
+      },
+    });
+    return validateResponse(response, z.string());
+  },
+
+  /**
+   * Links in a note whose target is the title or alias of several notes
+   */
+  getAmbiguousLinks: async (noteId: string): Promise<IpcResponse<AmbiguousLink[]>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.GET_AMBIGUOUS_LINKS, { noteId });
+    return validateResponse(response, z.array(AmbiguousLinkSchema));
+  },
+
+  /**
+   * Blocks of a note marked with ^block-id, for block link completion
+   */
+  getNoteBlocks: async (noteId: string): Promise<IpcResponse<NoteBlock[]>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.GET_NOTE_BLOCKS, { noteId });
+    return validateResponse(response, z.array(NoteBlockSchema));
+  },
+
+  /**
+   * Resolve an embed in a note (`Note#Heading`, `Note#^block-id`) to the
+   * markdown it transcludes; null when the target doesn't exist
+   */
+  resolveEmbed: async (
+    noteId: string,
+    target: string,
+  ): Promise<IpcResponse<EmbedContent | null>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.RESOLVE_EMBED, { noteId, target });
+    return validateResponse(response, EmbedContentSchema.nullable());
+  },
+
+  /**
+   * Find groups of near-duplicate notes in a workspace
+   */
+  findDuplicates: async (request?: {
+    workspaceId?: string;
+    minSimilarity?: number;
+  }): Promise<IpcResponse<DuplicateCluster[]>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.FIND_DUPLICATES, { request });
+    return validateResponse(response, z.array(DuplicateClusterSchema));
+  },
+
+  /**
+   * Merge notes into a target note; the merged notes are moved to trash
+   */
+  merge: async (request: {
+    targetId: string;
+    sourceIds: string[];
+    strategy?: 'keep' | 'concatenate';
+  }): Promise<
+    IpcResponse<{ note: Note; mergedIds: string[]; relinkedNoteIds: string[] }>
+  > => {
+    const response = await invokeIpc(NOTE_COMMANDS.MERGE, { request });
+    return validateResponse(response, MergeNotesResponseSchema);
+  },
+
+  /**
+   * Preview the files renaming a note would rewrite, without writing them
+   */
+  previewRename: async (
+    noteId: string,
+    newTitle: string,
+  ): Promise<IpcResponse<RenameNotePlan>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.PREVIEW_RENAME, {
+      request: { noteId, newTitle },
+    });
+    return validateResponse(response, RenameNotePlanSchema);
+  },
+
+  /**
+   * Rename a note and rewrite the wiki links to it; all files or none
+   */
+  rename: async (
+    noteId: string,
+    newTitle: string,
+  ): Promise<IpcResponse<{ note: Note; changes: FileChange[] }>> => {
+    const response = await invokeIpc(NOTE_COMMANDS.RENAME, {
+      request: { noteId, newTitle },
+    });
+    return validateResponse(response, RenameNoteResponseSchema);
+  },
+
   /**
    * Export note as HTML
    * @param id - Note ID
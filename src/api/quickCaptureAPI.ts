@@ -6,7 +6,7 @@
 
 import { invokeIpc } from "../lib/tauri-ipc";
 import { QUICK_CAPTURE_COMMANDS } from "../constants/tauriCommands";
-import type { IpcResponse } from "../types";
+import type { IpcResponse, NotePeriod } from "../types";
 
 export interface AppendToJournalResponse {
   noteId: string;
@@ -32,6 +32,20 @@ export const quickCaptureAPI = {
       workspace_id: workspaceId,
     }),
 
+  /**
+   * Append text to the current daily, weekly, monthly or quarterly note
+   */
+  appendToPeriodicNote: (
+    text: string,
+    period: NotePeriod,
+    workspaceId?: string,
+  ): Promise<IpcResponse<AppendToJournalResponse>> =>
+    invokeIpc(QUICK_CAPTURE_COMMANDS.APPEND_TO_PERIODIC_NOTE, {
+      content: text,
+      period,
+      workspace_id: workspaceId,
+    }),
+
   /**
    * Hide the quick capture window
    */
@@ -39,9 +39,10 @@ export const NoteSchema = z.object({
   isArchived: z.boolean().nullable(),
   isDeleted: z.boolean().nullable(),
   deletedAt: z.union([z.string(), z.date(), z.number()]).nullable(),
-  embedding: z.unknown().nullable(),
   createdAt: z.union([z.string(), z.date(), z.number()]),
   updatedAt: z.union([z.string(), z.date(), z.number()]),
+  aliases: z.array(z.string()).optional(),
+  properties: z.record(z.string(), z.unknown()).optional(),
 });
 
 export const NoteWithMetaSchema = NoteSchema.extend({
@@ -92,10 +93,13 @@ export const TagSchema = z.object({
   color: z.string().nullable(),
   createdAt: z.union([z.string(), z.date(), z.number()]),
   updatedAt: z.union([z.string(), z.date(), z.number()]),
+  source: z.enum(['manual', 'content']).optional(),
 });
 
-export const TagWithCountSchema = TagSchema.extend({
+export const TagWithCountSchema: z.ZodType<any> = TagSchema.extend({
   noteCount: z.number(),
+  totalNoteCount: z.number().optional(),
+  children: z.lazy(() => z.array(TagWithCountSchema)).optional(),
 });
 
 export const TopicSchema = z.object({
@@ -132,6 +136,11 @@ export const WorkspaceSchema = z.object({
   lastAccessedAt: z.union([z.string(), z.date(), z.number()]),
 });
 
+export const FilenameSettingsSchema = z.object({
+  strategy: z.enum(['timestamp', 'slug', 'title']),
+  renameOnTitleChange: z.boolean(),
+});
+
 export const AttachmentSchema = z.object({
   id: z.string(),
   noteId: z.string(),
@@ -270,15 +279,152 @@ export const GraphDataSchema = z.object({
   links: z.array(GraphLinkSchema),
 });
 
+export const DuplicateClusterSchema = z.object({
+  notes: z.array(
+    z.object({
+      noteId: z.string(),
+      title: z.string(),
+      filePath: z.string().nullable(),
+      updatedAt: z.string(),
+    }),
+  ),
+  pairs: z.array(
+    z.object({
+      noteId: z.string(),
+      otherNoteId: z.string(),
+      similarity: z.number(),
+      textSimilarity: z.number(),
+      embeddingSimilarity: z.number().nullable(),
+    }),
+  ),
+  similarity: z.number(),
+});
+
+export const MergeNotesResponseSchema = z.object({
+  note: NoteSchema,
+  mergedIds: z.array(z.string()),
+  relinkedNoteIds: z.array(z.string()),
+});
+
+export const TemplateInfoSchema = z.object({
+  name: z.string(),
+  path: z.string(),
+});
+
+export const TemplateSettingsSchema = z.object({
+  folder: z.string(),
+  notebookTemplates: z.record(z.string(), z.string()),
+});
+
+export const CreateNoteFromTemplateResponseSchema = z.object({
+  note: NoteSchema,
+  cursorOffset: z.number().nullable(),
+});
+
+export const NotePeriodSchema = z.enum(['daily', 'weekly', 'monthly', 'quarterly']);
+
+export const PeriodConfigSchema = z.object({
+  enabled: z.boolean(),
+  folder: z.string(),
+  format: z.string(),
+  template: z.string().nullable(),
+});
+
+export const PeriodicNotesSettingsSchema = z.object({
+  dayStartsAt: z.string(),
+  daily: PeriodConfigSchema,
+  weekly: PeriodConfigSchema,
+  monthly: PeriodConfigSchema,
+  quarterly: PeriodConfigSchema,
+});
+
+export const PeriodicNoteResponseSchema = z.object({
+  note: NoteSchema,
+  period: NotePeriodSchema,
+  startDate: z.string(),
+  endDate: z.string(),
+  created: z.boolean(),
+});
+
 // ============================================================================
 // Topic/Embedding Schemas
 // ============================================================================
 
+export const EmbeddingQueueStatusSchema = z.object({
+  state: z.enum(['idle', 'running', 'paused']),
+  total: z.number(),
+  processed: z.number(),
+  failed: z.number(),
+});
+
 export const EmbeddingStatusSchema = z.object({
   ready: z.boolean(),
   totalNotes: z.number(),
   embeddedNotes: z.number(),
   pendingNotes: z.number(),
+  queue: EmbeddingQueueStatusSchema,
+});
+
+export const EmbeddingProviderConfigSchema = z.discriminatedUnion('kind', [
+  z.object({ kind: z.literal('fastEmbed'), model: z.string() }),
+  z.object({ kind: z.literal('localModel'), path: z.string() }),
+  z.object({
+    kind: z.literal('openAiCompatible'),
+    baseUrl: z.string(),
+    model: z.string(),
+    apiKey: z.string().nullable().optional(),
+  }),
+]);
+
+export const EmbeddingSettingsSchema = z.object({
+  provider: EmbeddingProviderConfigSchema,
+  model: z.object({
+    name: z.string(),
+    version: z.string(),
+    dimensions: z.number().nullable().optional(),
+  }),
+  ready: z.boolean(),
+  availableModels: z.array(
+    z.object({
+      id: z.string(),
+      description: z.string(),
+      dimensions: z.number(),
+    }),
+  ),
+  staleNotes: z.number(),
+});
+
+export const ChatModelConfigSchema = z.object({
+  baseUrl: z.string(),
+  model: z.string(),
+  apiKey: z.string().nullable().optional(),
+  temperature: z.number().nullable().optional(),
+});
+
+export const AnswerResponseSchema = z.object({
+  questionId: z.string(),
+  answer: z.string(),
+  citations: z.array(
+    z.object({
+      number: z.number(),
+      noteId: z.string(),
+      title: z.string(),
+      startOffset: z.number(),
+      endOffset: z.number(),
+      answerRanges: z.array(z.tuple([z.number(), z.number()])),
+    }),
+  ),
+  sources: z.array(
+    z.object({
+      number: z.number(),
+      noteId: z.string(),
+      title: z.string(),
+      headingPath: z.array(z.string()),
+      text: z.string(),
+      startOffset: z.number(),
+      endOffset: z.number(),
+    }),
+  ),
 });
 
 export const NoteTopicDetailsSchema = z.object({
@@ -291,11 +437,107 @@ export const NoteTopicDetailsSchema = z.object({
   topicColor: z.string(),
 });
 
+export const NotePassageSchema = z.object({
+  chunkIndex: z.number(),
+  headingPath: z.array(z.string()),
+  text: z.string(),
+  startOffset: z.number(),
+  endOffset: z.number(),
+});
+
 export const SimilarNoteResultSchema = z.object({
   noteId: z.string(),
   title: z.string(),
   similarity: z.number(),
   distance: z.number().optional(),
+  passage: NotePassageSchema.nullish(),
+});
+
+export const LinkSuggestionSchema = z.object({
+  kind: z.enum(['mention', 'related']),
+  targetNoteId: z.string(),
+  targetTitle: z.string(),
+  text: z.string(),
+  startOffset: z.number(),
+  endOffset: z.number(),
+  lineNumber: z.number(),
+  similarity: z.number().nullish(),
+  targetPassage: NotePassageSchema.nullish(),
+});
+
+export const AmbiguousLinkSchema = z.object({
+  target: z.string(),
+  candidates: z.array(
+    z.object({
+      noteId: z.string(),
+      title: z.string(),
+      filePath: z.string().nullable(),
+    }),
+  ),
+});
+
+export const NoteBlockSchema = z.object({
+  noteId: z.string(),
+  blockId: z.string(),
+  content: z.string(),
+  startOffset: z.number(),
+  endOffset: z.number(),
+  lineNumber: z.number(),
+  createdAt: z.string(),
+});
+
+export const EmbedContentSchema = z.object({
+  noteId: z.string(),
+  title: z.string(),
+  anchor: z.string().nullable(),
+  markdown: z.string(),
+});
+
+export const FileChangeSchema = z.object({
+  noteId: z.string().nullable(),
+  title: z.string().nullable(),
+  filePath: z.string(),
+  edits: z.array(
+    z.object({
+      lineNumber: z.number(),
+      before: z.string(),
+      after: z.string(),
+    }),
+  ),
+});
+
+export const RenameNotePlanSchema = z.object({
+  noteId: z.string(),
+  oldTitle: z.string(),
+  newTitle: z.string(),
+  changes: z.array(FileChangeSchema),
+});
+
+export const RenameNoteResponseSchema = z.object({
+  note: NoteSchema,
+  changes: z.array(FileChangeSchema),
+});
+
+export const TagRenameSchema = z.object({
+  tagId: z.string(),
+  from: z.string(),
+  to: z.string(),
+  mergedInto: z.string().nullable(),
+});
+
+export const RenameTagPlanSchema = z.object({
+  tagId: z.string(),
+  oldName: z.string(),
+  newName: z.string(),
+  tags: z.array(TagRenameSchema),
+  noteCount: z.number(),
+  changes: z.array(FileChangeSchema),
+});
+
+export const RenameTagResponseSchema = z.object({
+  tag: TagSchema,
+  tags: z.array(TagRenameSchema),
+  changes: z.array(FileChangeSchema),
 });
 
 export const ClassifyNoteResponseSchema = z.object({
@@ -307,6 +549,25 @@ export const ClassifyAllResponseSchema = z.object({
   processed: z.number(),
   total: z.number(),
   failed: z.number(),
+  results: z.array(ClassifyNoteResponseSchema).optional().default([]),
+});
+
+export const TopicCandidateSchema = z.object({
+  topic: TopicSchema,
+  terms: z.array(z.string()),
+  members: z.array(
+    z.object({
+      noteId: z.string(),
+      title: z.string(),
+      similarity: z.number(),
+    }),
+  ),
+  cohesion: z.number(),
+});
+
+export const DiscoverTopicsResponseSchema = z.object({
+  candidates: z.array(TopicCandidateSchema),
+  notesConsidered: z.number(),
 });
 
 // ============================================================================
@@ -7,7 +7,15 @@
 
 import { invokeIpc } from '../lib/tauri-ipc';
 import { SEARCH_COMMANDS } from '../constants/tauriCommands';
-import type { SearchResults, IpcResponse } from '../types';
+import type {
+  SearchResults,
+  IpcResponse,
+  AskQuestionRequest,
+  AnswerResponse,
+  ChatModelConfig,
+} from '../types';
+import { validateResponse } from './validation';
+import { AnswerResponseSchema, ChatModelConfigSchema } from './schemas';
 
 export interface SearchParams {
   query: string;
@@ -55,4 +63,28 @@ export const searchAPI = {
    */
   byDateRange: (params: DateRangeParams): Promise<IpcResponse<SearchResults>> =>
     invokeIpc(SEARCH_COMMANDS.BY_DATE_RANGE, params),
+
+  /**
+   * Answer a question from the notes; the answer also streams as qa:answer-delta events
+   */
+  ask: async (request: AskQuestionRequest): Promise<IpcResponse<AnswerResponse>> => {
+    const response = await invokeIpc(SEARCH_COMMANDS.ASK_QUESTION, { request });
+    return validateResponse(response, AnswerResponseSchema);
+  },
+
+  /**
+   * Get the local chat endpoint questions are sent to
+   */
+  getChatSettings: async (): Promise<IpcResponse<ChatModelConfig>> => {
+    const response = await invokeIpc(SEARCH_COMMANDS.GET_CHAT_SETTINGS, {});
+    return validateResponse(response, ChatModelConfigSchema);
+  },
+
+  /**
+   * Change the chat endpoint; it must be on this machine
+   */
+  updateChatSettings: async (config: ChatModelConfig): Promise<IpcResponse<ChatModelConfig>> => {
+    const response = await invokeIpc(SEARCH_COMMANDS.UPDATE_CHAT_SETTINGS, { config });
+    return validateResponse(response, ChatModelConfigSchema);
+  },
 };
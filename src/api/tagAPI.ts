@@ -7,9 +7,14 @@
 
 import { invokeIpc } from '../lib/tauri-ipc';
 import { TAG_COMMANDS } from '../constants/tauriCommands';
-import type { Tag, TagWithCount, IpcResponse } from '../types';
+import type { Tag, TagWithCount, TagRename, RenameTagPlan, FileChange, IpcResponse } from '../types';
 import { validateResponse } from './validation';
-import { TagSchema, TagWithCountSchema } from './schemas';
+import {
+  TagSchema,
+  TagWithCountSchema,
+  RenameTagPlanSchema,
+  RenameTagResponseSchema,
+} from './schemas';
 import { z } from 'zod';
 
 export interface GetAllTagsParams {
@@ -80,4 +85,27 @@ export const tagAPI = {
     });
     return validateResponse(response, z.void());
   },
+
+  /**
+   * Preview renaming a tag, or merging it into an existing one, without writing anything
+   */
+  previewRename: async (tagId: string, newName: string): Promise<IpcResponse<RenameTagPlan>> => {
+    const response = await invokeIpc(TAG_COMMANDS.PREVIEW_RENAME, {
+      request: { tagId, newName },
+    });
+    return validateResponse(response, RenameTagPlanSchema);
+  },
+
+  /**
+   * Rename or merge a tag and rewrite the files using it; all files or none
+   */
+  rename: async (
+    tagId: string,
+    newName: string,
+  ): Promise<IpcResponse<{ tag: Tag; tags: TagRename[]; changes: FileChange[] }>> => {
+    const response = await invokeIpc(TAG_COMMANDS.RENAME, {
+      request: { tagId, newName },
+    });
+    return validateResponse(response, RenameTagResponseSchema);
+  },
 };
@@ -10,9 +10,14 @@ import { TOPIC_COMMANDS } from '../constants/tauriCommands';
 import type {
   Topic,
   TopicWithCount,
+  TopicCandidate,
   ClassificationResult,
   SimilarNote,
   EmbeddingStatus,
+  EmbeddingProviderConfig,
+  EmbeddingSettings,
+  EmbeddingQueueStatus,
+  StartEmbeddingQueueRequest,
   IpcResponse,
 } from '../types';
 import { validateResponse } from './validation';
@@ -21,9 +26,12 @@ import {
   TopicWithCountSchema,
   SimilarNoteResultSchema,
   EmbeddingStatusSchema,
+  EmbeddingSettingsSchema,
+  EmbeddingQueueStatusSchema,
   NoteTopicDetailsSchema,
   ClassifyNoteResponseSchema,
   ClassifyAllResponseSchema,
+  DiscoverTopicsResponseSchema,
 } from './schemas';
 import { z } from 'zod';
 
@@ -207,6 +215,31 @@ export const topicAPI = {
     );
   },
 
+  /**
+   * Propose new topics from clusters of similar notes
+   */
+  discover: async (options?: {
+    workspaceId?: string;
+    maxTopics?: number;
+    minNotes?: number;
+  }): Promise<IpcResponse<{ candidates: TopicCandidate[]; notesConsidered: number }>> => {
+    const response = await invokeIpc(TOPIC_COMMANDS.DISCOVER, { request: options ?? null });
+    return validateResponse(response, DiscoverTopicsResponseSchema);
+  },
+
+  /**
+   * Create a topic from a discovered candidate and assign its notes
+   */
+  acceptCandidate: async (request: {
+    name: string;
+    description?: string;
+    color?: string;
+    noteIds: string[];
+  }): Promise<IpcResponse<Topic>> => {
+    const response = await invokeIpc(TOPIC_COMMANDS.ACCEPT_CANDIDATE, { request });
+    return validateResponse(response, TopicSchema);
+  },
+
   /**
    * Semantic search
    */
@@ -247,4 +280,58 @@ export const topicAPI = {
     const response = await invokeIpc(TOPIC_COMMANDS.GET_EMBEDDING_STATUS, {});
     return validateResponse(response, EmbeddingStatusSchema);
   },
+
+  /**
+   * Get the embedding provider, current model and the models to choose from
+   */
+  getEmbeddingSettings: async (): Promise<IpcResponse<EmbeddingSettings>> => {
+    const response = await invokeIpc(TOPIC_COMMANDS.GET_EMBEDDING_SETTINGS, {});
+    return validateResponse(response, EmbeddingSettingsSchema);
+  },
+
+  /**
+   * Switch embedding provider; notes embedded with another model are re-embedded
+   */
+  updateEmbeddingSettings: async (
+    provider: EmbeddingProviderConfig,
+  ): Promise<IpcResponse<EmbeddingSettings>> => {
+    const response = await invokeIpc(TOPIC_COMMANDS.UPDATE_EMBEDDING_SETTINGS, { provider });
+    return validateResponse(response, EmbeddingSettingsSchema);
+  },
+
+  /**
+   * Embed un-embedded and out-of-date notes in the background
+   */
+  startEmbeddingQueue: async (
+    request?: StartEmbeddingQueueRequest,
+  ): Promise<IpcResponse<EmbeddingQueueStatus>> => {
+    const response = await invokeIpc(TOPIC_COMMANDS.START_EMBEDDING_QUEUE, {
+      request: request ?? null,
+    });
+    return validateResponse(response, EmbeddingQueueStatusSchema);
+  },
+
+  /**
+   * Pause the embedding queue after the batch in progress
+   */
+  pauseEmbeddingQueue: async (): Promise<IpcResponse<EmbeddingQueueStatus>> => {
+    const response = await invokeIpc(TOPIC_COMMANDS.PAUSE_EMBEDDING_QUEUE, {});
+    return validateResponse(response, EmbeddingQueueStatusSchema);
+  },
+
+  /**
+   * Resume a paused embedding queue
+   */
+  resumeEmbeddingQueue: async (): Promise<IpcResponse<EmbeddingQueueStatus>> => {
+    const response = await invokeIpc(TOPIC_COMMANDS.RESUME_EMBEDDING_QUEUE, {});
+    return validateResponse(response, EmbeddingQueueStatusSchema);
+  },
+
+  /**
+   * Cancel the embedding queue after the batch in progress
+   */
+  cancelEmbeddingQueue: async (): Promise<IpcResponse<EmbeddingQueueStatus>> => {
+    const response = await invokeIpc(TOPIC_COMMANDS.CANCEL_EMBEDDING_QUEUE, {});
+    return validateResponse(response, EmbeddingQueueStatusSchema);
+  },
 };
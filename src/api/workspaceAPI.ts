@@ -7,9 +7,9 @@
 
 import { invokeIpc } from '../lib/tauri-ipc';
 import { WORKSPACE_COMMANDS } from '../constants/tauriCommands';
-import type { Workspace, IpcResponse } from '../types';
+import type { Workspace, FilenameSettings, IpcResponse } from '../types';
 import { validateResponse } from './validation';
-import { WorkspaceSchema } from './schemas';
+import { WorkspaceSchema, FilenameSettingsSchema } from './schemas';
 import { z } from 'zod';
 import { logger } from '../utils/logger';
 
@@ -254,5 +254,27 @@ export const workspaceAPI = {
       z.object({ canceled: z.boolean().optional(), folderPath: z.string().optional() }),
     );
   },
+
+  /**
+   * Get how the workspace names note files
+   */
+  getFilenameSettings: async (workspaceId?: string): Promise<IpcResponse<FilenameSettings>> => {
+    const response = await invokeIpc(WORKSPACE_COMMANDS.GET_FILENAME_SETTINGS, { workspaceId });
+    return validateResponse(response, FilenameSettingsSchema);
+  },
+
+  /**
+   * Change how the workspace names note files
+   */
+  updateFilenameSettings: async (
+    settings: FilenameSettings,
+    workspaceId?: string,
+  ): Promise<IpcResponse<FilenameSettings>> => {
+    const response = await invokeIpc(WORKSPACE_COMMANDS.UPDATE_FILENAME_SETTINGS, {
+      workspaceId,
+      settings,
+    });
+    return validateResponse(response, FilenameSettingsSchema);
+  },
 };
 
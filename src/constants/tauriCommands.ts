@@ -22,6 +22,8 @@ export const WORKSPACE_COMMANDS = {
   MOVE_FOLDER: 'move_folder',
   VALIDATE_PATH: 'validate_path',
   SELECT_FOLDER: 'select_folder',
+  GET_FILENAME_SETTINGS: 'get_filename_settings',
+  UPDATE_FILENAME_SETTINGS: 'update_filename_settings',
 } as const;
 
 // Note Operations
@@ -47,6 +49,23 @@ export const NOTE_COMMANDS = {
   GET_BACKLINKS: 'get_backlinks',
   GET_FORWARD_LINKS: 'get_forward_links',
   GET_GRAPH_DATA: 'get_graph_data',
+  GET_LINK_SUGGESTIONS: 'get_link_suggestions',
+  APPLY_LINK_SUGGESTION: 'apply_link_suggestion',
+  GET_AMBIGUOUS_LINKS: 'get_ambiguous_links',
+  GET_NOTE_BLOCKS: 'get_note_blocks',
+  RESOLVE_EMBED: 'resolve_embed',
+  FIND_DUPLICATES: 'find_duplicate_notes',
+  MERGE: 'merge_notes',
+  PREVIEW_RENAME: 'preview_note_rename',
+  RENAME: 'rename_note',
+  CREATE_FROM_TEMPLATE: 'create_note_from_template',
+  LIST_TEMPLATES: 'list_templates',
+  GET_TEMPLATE_SETTINGS: 'get_template_settings',
+  UPDATE_TEMPLATE_SETTINGS: 'update_template_settings',
+  SET_NOTEBOOK_TEMPLATE: 'set_notebook_template',
+  OPEN_PERIODIC_NOTE: 'open_periodic_note',
+  GET_PERIODIC_SETTINGS: 'get_periodic_settings',
+  UPDATE_PERIODIC_SETTINGS: 'update_periodic_settings',
   MOVE: 'move_note',
   EXPORT_HTML: 'export_note_html',
   EXPORT_PDF: 'export_note_pdf',
@@ -69,6 +88,8 @@ export const TAG_COMMANDS = {
   GET_ALL: 'list_tags',
   ADD_TO_NOTE: 'add_tag_to_note',
   REMOVE_FROM_NOTE: 'remove_tag_from_note',
+  PREVIEW_RENAME: 'preview_tag_rename',
+  RENAME: 'rename_tag',
 } as const;
 
 // Topic Operations (Semantic Classification)
@@ -85,10 +106,18 @@ export const TOPIC_COMMANDS = {
   CLASSIFY_NOTE: 'classify_note',
   CLASSIFY_ALL: 'classify_all_notes',
   RECLASSIFY_ALL: 'classify_all_notes', // Same as CLASSIFY_ALL
+  DISCOVER: 'discover_topics',
+  ACCEPT_CANDIDATE: 'accept_topic_candidate',
   SEMANTIC_SEARCH: 'semantic_search',
   GET_SIMILAR_NOTES: 'get_similar_notes',
   RECOMPUTE_CENTROIDS: 'classify_all_notes', // Uses classify_all which recomputes
   GET_EMBEDDING_STATUS: 'get_embedding_status',
+  GET_EMBEDDING_SETTINGS: 'get_embedding_settings',
+  UPDATE_EMBEDDING_SETTINGS: 'update_embedding_settings',
+  START_EMBEDDING_QUEUE: 'start_embedding_queue',
+  PAUSE_EMBEDDING_QUEUE: 'pause_embedding_queue',
+  RESUME_EMBEDDING_QUEUE: 'resume_embedding_queue',
+  CANCEL_EMBEDDING_QUEUE: 'cancel_embedding_queue',
   INITIALIZE: 'initialize_embeddings', // Initialize the embedding model
 } as const;
 
@@ -99,6 +128,9 @@ export const SEARCH_COMMANDS = {
   HYBRID: 'hybrid_search',
   BY_TAG: 'search_notes', // Can filter by tag using params
   BY_DATE_RANGE: 'search_by_date_range',
+  ASK_QUESTION: 'ask_question',
+  GET_CHAT_SETTINGS: 'get_chat_settings',
+  UPDATE_CHAT_SETTINGS: 'update_chat_settings',
 } as const;
 
 // Attachment Operations
@@ -160,6 +192,7 @@ export const PERFORMANCE_COMMANDS = {
 // Quick Capture Operations
 export const QUICK_CAPTURE_COMMANDS = {
   APPEND_TO_JOURNAL: 'append_to_journal',
+  APPEND_TO_PERIODIC_NOTE: 'append_to_periodic_note',
   HIDE: 'hide_quick_capture',
   GET_STATE: 'get_quick_capture_state',
 } as const;
@@ -202,6 +235,7 @@ export const EVENTS = {
   // Tag events
   TAG_CREATED: 'tag:created',
   TAG_UPDATED: 'tag:updated',
+  TAG_RENAMED: 'tag:renamed',
   TAG_DELETED: 'tag:deleted',
 
   // Attachment events
@@ -227,6 +261,13 @@ export const EVENTS = {
   TOPIC_DELETED: 'topic:deleted',
   NOTE_CLASSIFIED: 'note:classified',
   EMBEDDING_PROGRESS: 'embedding:progress',
+  EMBEDDING_MODEL_CHANGED: 'embedding:model-changed',
+  EMBEDDING_QUEUE_PROGRESS: 'embedding:queue-progress',
+  INDEX_PROGRESS: 'index:progress',
+
+  // Question answering events
+  QA_ANSWER_DELTA: 'qa:answer-delta',
+  QA_ANSWERED: 'qa:answered',
 
   // ML Service status events
   ML_STATUS_CHANGED: 'ml:status:changed',
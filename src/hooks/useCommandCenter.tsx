@@ -246,8 +246,12 @@ export function useCommandCenter() {
         }));
     }
 
-    // Fuzzy match against titles and file paths
-    return fuzzyFilter(activeNotes, q, (note) => [note.title || 'Untitled', note.filePath || ''])
+    // Fuzzy match against titles, aliases and file paths
+    return fuzzyFilter(activeNotes, q, (note) => [
+      note.title || 'Untitled',
+      ...(note.aliases ?? []),
+      note.filePath || '',
+    ])
       .slice(0, 15)
       .map(({ score, ...note }) => ({
         id: `note-${note.id}`,
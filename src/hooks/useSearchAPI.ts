@@ -122,6 +122,7 @@ export function useSearchAPI() {
     async (
       tagIds: string[],
       matchAll = false,
+      includeDescendants = false,
     ): Promise<{ notes: Note[]; total: number } | null> => {
       setLoading(true);
       setError(null);
@@ -129,6 +130,7 @@ export function useSearchAPI() {
         const response = await invokeIpc<{ notes: Note[]; total: number }>(SEARCH_COMMANDS.BY_TAG, {
           tagIds: tagIds,
           match_all: matchAll,
+          include_descendants: includeDescendants,
         });
         if (response.success && response.data) {
           return response.data;
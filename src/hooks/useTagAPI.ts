@@ -42,7 +42,7 @@ const useTagCRUD = createEntityAPI<TagWithCount>({
  */
 export function useTagAPI() {
   const { loadAll, create, remove } = useTagCRUD();
-  const { setError } = useTagStore();
+  const { setError, deselectTag } = useTagStore();
 
   /**
    * Load tags with optional sorting
@@ -97,11 +97,64 @@ export function useTagAPI() {
     [setError],
   );
 
+  /**
+   * Preview renaming a tag, or merging it into an existing one; nothing is written
+   */
+  const previewRenameTag = useCallback(
+    async (tagId: string, newName: string) => {
+      setError(null);
+      try {
+        const response = await tagAPI.previewRename(tagId, newName);
+        const result = handleIpcResponse(response, 'Failed to preview tag rename');
+        if (result.success) {
+          return result.data;
+        }
+        setError(result.error);
+        return null;
+      } catch (error) {
+        setError(error instanceof Error ? error.message : 'Failed to preview tag rename');
+        return null;
+      }
+    },
+    [setError],
+  );
+
+  /**
+   * Rename or merge a tag along with the tags nested under it, then reload the tags
+   */
+  const renameTag = useCallback(
+    async (tagId: string, newName: string) => {
+      setError(null);
+      try {
+        const response = await tagAPI.rename(tagId, newName);
+        const result = handleIpcResponse(response, 'Failed to rename tag');
+        if (!result.success) {
+          setError(result.error);
+          return null;
+        }
+        // Merged tags no longer exist
+        for (const rename of result.data.tags) {
+          if (rename.mergedInto) {
+            deselectTag(rename.tagId);
+          }
+        }
+        await loadTags();
+        return result.data;
+      } catch (error) {
+        setError(error instanceof Error ? error.message : 'Failed to rename tag');
+        return null;
+      }
+    },
+    [setError, deselectTag, loadTags],
+  );
+
   return {
     loadTags,
     createTag: create,
     deleteTag: remove,
     addTagToNote,
     removeTagFromNote,
+    previewRenameTag,
+    renameTag,
   };
 }
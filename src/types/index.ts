@@ -24,6 +24,15 @@ export interface Workspace {
   updated_at: Date | string;
 }
 
+// How the files of new notes are named
+export type FilenameStrategy = 'timestamp' | 'slug' | 'title';
+
+export interface FilenameSettings {
+  strategy: FilenameStrategy;
+  // Rename a note's file when its title changes; never done for timestamps
+  renameOnTitleChange: boolean;
+}
+
 export interface Note {
   id: string;
   title: string;
@@ -45,6 +54,10 @@ export interface Note {
   createdAt?: Date | string; // Alias
   updated_at: Date | string;
   updatedAt?: Date | string; // Alias
+  // Other names the note answers to in links, from its frontmatter
+  aliases?: string[];
+  // Custom frontmatter keys and their values
+  properties?: Record<string, unknown>;
 }
 
 export interface Notebook {
@@ -67,10 +80,14 @@ export interface Tag {
   color: string | null;
   created_at: Date | string;
   updated_at: Date | string;
+  /** On a note's tags: whether it was added by hand or comes from a #hashtag */
+  source?: 'manual' | 'content';
 }
 
 export interface TagWithCount extends Tag {
   note_count: number;
+  total_note_count?: number;
+  children?: TagWithCount[];
 }
 
 export interface Topic {
@@ -106,6 +123,27 @@ export interface NoteVersion {
   created_at: Date | string;
 }
 
+// Group of near-identical notes, most recently updated first
+export interface DuplicateCluster {
+  notes: Array<{ noteId: string; title: string; filePath: string | null; updatedAt: string }>;
+  pairs: Array<{
+    noteId: string;
+    otherNoteId: string;
+    similarity: number;
+    textSimilarity: number;
+    embeddingSimilarity: number | null;
+  }>;
+  similarity: number;
+}
+
+// Topic proposed by clustering similar notes (not saved until accepted)
+export interface TopicCandidate {
+  topic: Topic;
+  terms: string[];
+  members: Array<{ noteId: string; title: string; similarity: number }>;
+  cohesion: number;
+}
+
 // Classification result from embedding similarity
 export interface ClassificationResult {
   topicId: string;
@@ -114,10 +152,95 @@ export interface ClassificationResult {
 }
 
 // Similar note result from semantic search
+export interface NotePassage {
+  chunkIndex: number;
+  headingPath: string[];
+  text: string;
+  startOffset: number;
+  endOffset: number;
+}
+
+// Link opportunity: an unlinked mention or a semantically related note
+export interface LinkSuggestion {
+  kind: 'mention' | 'related';
+  targetNoteId: string;
+  targetTitle: string;
+  text: string;
+  startOffset: number;
+  endOffset: number;
+  lineNumber: number;
+  similarity?: number | null;
+  targetPassage?: NotePassage | null;
+}
+
+// A link whose target is the title or alias of several notes
+export interface AmbiguousLink {
+  target: string;
+  candidates: {
+    noteId: string;
+    title: string;
+    filePath: string | null;
+  }[];
+}
+
+// A block of a note marked with `^block-id`
+export interface NoteBlock {
+  noteId: string;
+  blockId: string;
+  content: string;
+  startOffset: number;
+  endOffset: number;
+  lineNumber: number;
+  createdAt: string;
+}
+
+// Markdown an embed like ![[Note#Heading]] or ![[Note#^block-id]] transcludes
+export interface EmbedContent {
+  noteId: string;
+  title: string;
+  anchor: string | null;
+  markdown: string;
+}
+
+// A file a refactoring rewrites, with the lines it changes
+export interface FileChange {
+  noteId: string | null;
+  title: string | null;
+  filePath: string;
+  edits: Array<{ lineNumber: number; before: string; after: string }>;
+}
+
+// Files renaming a note would rewrite: its own heading and the links to it
+export interface RenameNotePlan {
+  noteId: string;
+  oldTitle: string;
+  newTitle: string;
+  changes: FileChange[];
+}
+
+// A tag a rename moves; mergedInto is set when the new name already exists
+export interface TagRename {
+  tagId: string;
+  from: string;
+  to: string;
+  mergedInto: string | null;
+}
+
+// Tags and files renaming or merging a tag would change
+export interface RenameTagPlan {
+  tagId: string;
+  oldName: string;
+  newName: string;
+  tags: TagRename[];
+  noteCount: number;
+  changes: FileChange[];
+}
+
 export interface SimilarNote {
   noteId: string;
   title: string;
   distance: number;
+  passage?: NotePassage | null;
 }
 
 // Embedding status
@@ -126,6 +249,162 @@ export interface EmbeddingStatus {
   totalNotes: number;
   embeddedNotes: number;
   pendingNotes: number;
+  queue: EmbeddingQueueStatus;
+}
+
+// Background embedding queue
+export type EmbeddingQueueState = 'idle' | 'running' | 'paused';
+
+export interface EmbeddingQueueStatus {
+  state: EmbeddingQueueState;
+  total: number;
+  processed: number;
+  failed: number;
+}
+
+export interface StartEmbeddingQueueRequest {
+  batchSize?: number;
+  maxCpuPercent?: number;
+}
+
+// Embedding provider, as chosen in settings
+export type EmbeddingProviderConfig =
+  | { kind: 'fastEmbed'; model: string }
+  | { kind: 'localModel'; path: string }
+  | { kind: 'openAiCompatible'; baseUrl: string; model: string; apiKey?: string | null };
+
+export interface EmbeddingModelInfo {
+  name: string;
+  version: string;
+  dimensions?: number | null;
+}
+
+export interface EmbeddingModelOption {
+  id: string;
+  description: string;
+  dimensions: number;
+}
+
+export interface EmbeddingSettings {
+  provider: EmbeddingProviderConfig;
+  model: EmbeddingModelInfo;
+  ready: boolean;
+  availableModels: EmbeddingModelOption[];
+  staleNotes: number;
+}
+
+// Local OpenAI-compatible chat endpoint used to answer questions
+export interface ChatModelConfig {
+  baseUrl: string;
+  model: string;
+  apiKey?: string | null;
+  temperature?: number | null;
+}
+
+export interface AskQuestionRequest {
+  question: string;
+  // Echoed in the qa:answer-delta events; generated when left out
+  questionId?: string;
+  retrieval?: 'semantic' | 'hybrid';
+  limit?: number;
+  workspaceId?: string;
+  notebookId?: string;
+}
+
+// Passage given to the model; offsets are byte offsets in the note's markdown
+export interface AnswerSource {
+  number: number;
+  noteId: string;
+  title: string;
+  headingPath: string[];
+  text: string;
+  startOffset: number;
+  endOffset: number;
+}
+
+export interface AnswerCitation {
+  number: number;
+  noteId: string;
+  title: string;
+  startOffset: number;
+  endOffset: number;
+  // Byte ranges of the [n] markers in the answer
+  answerRanges: Array<[number, number]>;
+}
+
+export interface AnswerResponse {
+  questionId: string;
+  answer: string;
+  citations: AnswerCitation[];
+  sources: AnswerSource[];
+}
+
+// Templates
+export interface TemplateInfo {
+  name: string;
+  // Relative to the templates folder
+  path: string;
+}
+
+export interface TemplateSettings {
+  // Relative to the workspace root
+  folder: string;
+  // Default template path by notebook ID
+  notebookTemplates: Record<string, string>;
+}
+
+export interface CreateNoteFromTemplateRequest {
+  title: string;
+  // Falls back to the notebook's default template
+  templatePath?: string;
+  notebookId?: string;
+  workspaceId?: string;
+  folderPath?: string;
+}
+
+export interface CreateNoteFromTemplateResponse {
+  note: Note;
+  // Byte offset in the note's markdown where the template put {{cursor}}
+  cursorOffset: number | null;
+}
+
+// Periodic notes
+export type NotePeriod = 'daily' | 'weekly' | 'monthly' | 'quarterly';
+
+export interface PeriodConfig {
+  enabled: boolean;
+  // Relative to the workspace root
+  folder: string;
+  // Moment-style file name format, e.g. YYYY-MM-DD or GGGG-[W]WW
+  format: string;
+  // Relative to the templates folder
+  template: string | null;
+}
+
+export interface PeriodicNotesSettings {
+  // HH:MM; earlier moments belong to the day before
+  dayStartsAt: string;
+  daily: PeriodConfig;
+  weekly: PeriodConfig;
+  monthly: PeriodConfig;
+  quarterly: PeriodConfig;
+}
+
+export interface OpenPeriodicNoteRequest {
+  period: NotePeriod;
+  // Any day (YYYY-MM-DD) of the period; the current one when omitted
+  date?: string;
+  // Periods to step from date, e.g. -1 for the previous one
+  offset?: number;
+  workspaceId?: string;
+}
+
+export interface PeriodicNoteResponse {
+  note: Note;
+  period: NotePeriod;
+  startDate: string;
+  endDate: string;
+  created: boolean;
 }
 
 // Settings
@@ -179,6 +458,7 @@ export interface SearchResults {
 
 export interface VectorSearchResult extends SearchResult {
   similarity: number;
+  passage?: NotePassage | null;
 }
 
 // Backup Types